@@ -2,8 +2,8 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 
-use crate::detection::{McpServerConfig, ServerType, ApplicationProfile, ConfigFormat};
-use super::{ApplicationAdapter, ExtractionResult, ApplicationResult};
+use crate::detection::{is_metadata_key, McpServerConfig, ServerType, ApplicationProfile, ConfigFormat};
+use super::{ApplicationAdapter, ExtractionResult, ApplicationResult, extra_options, flat_entry, insert_env, insert_extra_options, server_env};
 
 /// Amazon Q Developer application adapter
 pub struct AmazonQAdapter;
@@ -14,6 +14,12 @@ impl AmazonQAdapter {
     }
 }
 
+impl Default for AmazonQAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl ApplicationAdapter for AmazonQAdapter {
     async fn extract_server_configs(&self, config: &JsonValue) -> Result<ExtractionResult> {
@@ -22,7 +28,7 @@ impl ApplicationAdapter for AmazonQAdapter {
         
         // Amazon Q stores MCP configs in config.mcpServers
         if let Some(mcp_servers) = config.get("mcpServers").and_then(|v| v.as_object()) {
-            for (name, server_config) in mcp_servers {
+            for (name, server_config) in mcp_servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
                 match self.parse_server_config(name, server_config) {
                     Ok(server) => servers.push(server),
                     Err(e) => {
@@ -80,7 +86,7 @@ impl ApplicationAdapter for AmazonQAdapter {
             
             // Validate each server configuration
             if let Some(servers) = mcp_servers.as_object() {
-                for (name, server_config) in servers {
+                for (name, server_config) in servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
                     if !self.validate_server_config(name, server_config) {
                         return Ok(false);
                     }
@@ -106,6 +112,7 @@ impl ApplicationAdapter for AmazonQAdapter {
 
 impl AmazonQAdapter {
     fn parse_server_config(&self, name: &str, config: &JsonValue) -> Result<McpServerConfig> {
+        let config = &flat_entry(config);
         use crate::detection::{ServerMetadata, ConfigSource};
         
         let command = config.get("command")
@@ -117,21 +124,16 @@ impl AmazonQAdapter {
             .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
             .unwrap_or_default();
         
-        let env = config.get("env")
-            .and_then(|v| v.as_object())
-            .map(|obj| {
-                obj.iter()
-                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                    .collect()
-            })
-            .unwrap_or_default();
+        let env = server_env(config);
         
         Ok(McpServerConfig {
             name: name.to_string(),
             command: Some(command.to_string()),
             args,
             env,
-            cwd: None,
+            cwd: config.get("cwd").and_then(|v| v.as_str()).map(String::from),
+            inherit_env: config.get("inheritEnv").and_then(|v| v.as_bool()).unwrap_or(true),
+            extra_options: extra_options(config),
             server_type: ServerType::Stdio,
             metadata: ServerMetadata {
                 description: None,
@@ -150,8 +152,15 @@ impl AmazonQAdapter {
             "args": server.args
         });
         
-        if !server.env.is_empty() {
-            config["env"] = serde_json::json!(server.env);
+        if let Some(cwd) = &server.cwd {
+            config["cwd"] = serde_json::json!(cwd);
+        }
+        
+        insert_extra_options(&mut config, server);
+        insert_env(&mut config, &server.env);
+        
+        if !server.inherit_env {
+            config["inheritEnv"] = serde_json::json!(false);
         }
         
         if !server.metadata.enabled {
@@ -162,6 +171,7 @@ impl AmazonQAdapter {
     }
     
     fn validate_server_config(&self, _name: &str, config: &JsonValue) -> bool {
+        let config = &flat_entry(config);
         // Must have command
         if config.get("command").and_then(|v| v.as_str()).is_none() {
             return false;
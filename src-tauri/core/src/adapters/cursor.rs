@@ -2,8 +2,8 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 
-use crate::detection::{McpServerConfig, ServerType, ApplicationProfile, ConfigFormat};
-use super::{ApplicationAdapter, ExtractionResult, ApplicationResult};
+use crate::detection::{is_metadata_key, McpServerConfig, ServerType, ApplicationProfile, ConfigFormat};
+use super::{ApplicationAdapter, ExtractionResult, ApplicationResult, extra_options, flat_entry, insert_env, insert_extra_options, server_env};
 
 /// Cursor application adapter
 pub struct CursorAdapter;
@@ -14,16 +14,30 @@ impl CursorAdapter {
     }
 }
 
+impl Default for CursorAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Server entries from `mcpServers`, falling back to the legacy nested `mcp.servers` block
+pub fn servers_section(config: &JsonValue) -> Option<&serde_json::Map<String, JsonValue>> {
+    config
+        .get("mcpServers")
+        .and_then(|v| v.as_object())
+        .or_else(|| config.get("mcp").and_then(|m| m.get("servers")).and_then(|v| v.as_object()))
+}
+
 #[async_trait]
 impl ApplicationAdapter for CursorAdapter {
     async fn extract_server_configs(&self, config: &JsonValue) -> Result<ExtractionResult> {
         let mut servers = Vec::new();
         let mut messages = Vec::new();
         
-        // Cursor stores MCP configs in config.mcp.servers
-        if let Some(mcp_config) = config.get("mcp") {
-            if let Some(mcp_servers) = mcp_config.get("servers").and_then(|v| v.as_object()) {
-                for (name, server_config) in mcp_servers {
+        // Cursor reads `mcpServers` from ~/.cursor/mcp.json; older releases used `mcp.servers` in settings.json
+        match servers_section(config) {
+            Some(mcp_servers) => {
+                for (name, server_config) in mcp_servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
                     match self.parse_server_config(name, server_config) {
                         Ok(server) => servers.push(server),
                         Err(e) => {
@@ -31,11 +45,8 @@ impl ApplicationAdapter for CursorAdapter {
                         }
                     }
                 }
-            } else {
-                messages.push("No servers section found in mcp configuration".to_string());
             }
-        } else {
-            messages.push("No mcp section found in configuration".to_string());
+            None => messages.push("No mcpServers section found in configuration".to_string()),
         }
         
         Ok(ExtractionResult {
@@ -49,17 +60,14 @@ impl ApplicationAdapter for CursorAdapter {
         let mut new_config = config.clone();
         let mut messages = Vec::new();
         
-        // Ensure mcp.servers structure exists
-        if new_config.get("mcp").is_none() {
-            new_config["mcp"] = serde_json::json!({});
-        }
-        if new_config["mcp"].get("servers").is_none() {
-            new_config["mcp"]["servers"] = serde_json::json!({});
+        // Ensure mcpServers structure exists
+        if !new_config.get("mcpServers").is_some_and(|s| s.is_object()) {
+            new_config["mcpServers"] = serde_json::json!({});
         }
         
-        let mcp_servers = new_config["mcp"]["servers"]
+        let mcp_servers = new_config["mcpServers"]
             .as_object_mut()
-            .context("Failed to get mcp.servers as object")?;
+            .context("Failed to get mcpServers as object")?;
         
         // Clear existing servers
         mcp_servers.clear();
@@ -79,19 +87,19 @@ impl ApplicationAdapter for CursorAdapter {
     }
     
     async fn validate_config(&self, config: &JsonValue) -> Result<bool> {
-        // Check if mcp.servers exists and is an object
-        if let Some(mcp_config) = config.get("mcp") {
-            if let Some(mcp_servers) = mcp_config.get("servers") {
-                if !mcp_servers.is_object() {
-                    return Ok(false);
-                }
-                
-                // Validate each server configuration
-                if let Some(servers) = mcp_servers.as_object() {
-                    for (name, server_config) in servers {
-                        if !self.validate_server_config(name, server_config) {
-                            return Ok(false);
-                        }
+        // Check the servers section is an object, in either layout
+        let section = config.get("mcpServers")
+            .or_else(|| config.get("mcp").and_then(|m| m.get("servers")));
+        if let Some(mcp_servers) = section {
+            if !mcp_servers.is_object() {
+                return Ok(false);
+            }
+            
+            // Validate each server configuration
+            if let Some(servers) = mcp_servers.as_object() {
+                for (name, server_config) in servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
+                    if !self.validate_server_config(name, server_config) {
+                        return Ok(false);
                     }
                 }
             }
@@ -115,6 +123,7 @@ impl ApplicationAdapter for CursorAdapter {
 
 impl CursorAdapter {
     fn parse_server_config(&self, name: &str, config: &JsonValue) -> Result<McpServerConfig> {
+        let config = &flat_entry(config);
         use crate::detection::{ServerMetadata, ConfigSource};
         
         let command = config.get("command")
@@ -126,21 +135,16 @@ impl CursorAdapter {
             .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
             .unwrap_or_default();
         
-        let env = config.get("env")
-            .and_then(|v| v.as_object())
-            .map(|obj| {
-                obj.iter()
-                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                    .collect()
-            })
-            .unwrap_or_default();
+        let env = server_env(config);
         
         Ok(McpServerConfig {
             name: name.to_string(),
             command: Some(command.to_string()),
             args,
             env,
-            cwd: None,
+            cwd: config.get("cwd").and_then(|v| v.as_str()).map(String::from),
+            inherit_env: config.get("inheritEnv").and_then(|v| v.as_bool()).unwrap_or(true),
+            extra_options: extra_options(config),
             server_type: ServerType::Stdio,
             metadata: ServerMetadata {
                 description: None,
@@ -159,14 +163,22 @@ impl CursorAdapter {
             "args": server.args
         });
         
-        if !server.env.is_empty() {
-            config["env"] = serde_json::json!(server.env);
+        if let Some(cwd) = &server.cwd {
+            config["cwd"] = serde_json::json!(cwd);
+        }
+        
+        insert_extra_options(&mut config, server);
+        insert_env(&mut config, &server.env);
+        
+        if !server.inherit_env {
+            config["inheritEnv"] = serde_json::json!(false);
         }
         
         Ok(config)
     }
     
     fn validate_server_config(&self, _name: &str, config: &JsonValue) -> bool {
+        let config = &flat_entry(config);
         // Must have command
         if config.get("command").and_then(|v| v.as_str()).is_none() {
             return false;
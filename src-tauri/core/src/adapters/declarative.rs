@@ -0,0 +1,476 @@
+use anyhow::{bail, Context, Result};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::collections::HashMap;
+
+use crate::detection::{ApplicationProfile, ConfigFormat, ConfigSource, McpServerConfig, ServerMetadata, ServerType};
+use super::{ApplicationAdapter, ApplicationResult, ExtractionResult};
+
+/// How the servers container holds its entries
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ContainerLayout {
+    /// An object keyed by server name, like `mcpServers`
+    Map,
+    /// An array of entries, each carrying its name at `name` (a JSON pointer into the entry)
+    List { name: String },
+}
+
+/// JSON pointers into a client's server entry for each field we understand.
+///
+/// A field without a pointer is neither read nor written.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EntryFieldMap {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub args: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub env: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// Boolean that is true when the server is switched off
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disabled: Option<String>,
+    /// Boolean that is true when the server is switched on; the inverse of `disabled`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<String>,
+}
+
+/// Adapter for a client's config shape, declared in applications.json instead of code.
+///
+/// Lets registry authors support clients whose server entries don't follow any shape we
+/// ship an adapter for. Pointers follow RFC 6901, e.g. `/assistant/mcp` or `/launch/program`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DeclarativeAdapterSpec {
+    /// Pointer to the servers container from the config root
+    pub container: String,
+    pub layout: ContainerLayout,
+    pub fields: EntryFieldMap,
+    /// Values written into every entry, keyed by pointer, such as `{"/type": "stdio"}`
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    pub constants: Map<String, JsonValue>,
+}
+
+impl DeclarativeAdapterSpec {
+    /// Check the spec is usable before any config is read with it
+    pub fn validate(&self) -> Result<()> {
+        check_pointer("container", &self.container)?;
+        if let ContainerLayout::List { name } = &self.layout {
+            check_pointer("layout.name", name)?;
+            if name.is_empty() {
+                bail!("layout.name must point inside the entry");
+            }
+        }
+
+        let fields = [
+            ("command", &self.fields.command),
+            ("args", &self.fields.args),
+            ("env", &self.fields.env),
+            ("url", &self.fields.url),
+            ("disabled", &self.fields.disabled),
+            ("enabled", &self.fields.enabled),
+        ];
+        for (field, pointer) in fields {
+            if let Some(pointer) = pointer {
+                check_pointer(&format!("fields.{}", field), pointer)?;
+                if pointer.is_empty() {
+                    bail!("fields.{} must point inside the entry", field);
+                }
+            }
+        }
+        if self.fields.command.is_none() && self.fields.url.is_none() {
+            bail!("fields must map at least one of command or url");
+        }
+        if self.fields.disabled.is_some() && self.fields.enabled.is_some() {
+            bail!("fields.disabled and fields.enabled are mutually exclusive");
+        }
+
+        for pointer in self.constants.keys() {
+            check_pointer("constants", pointer)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Interprets a [`DeclarativeAdapterSpec`] as an application adapter
+pub struct DeclarativeAdapter {
+    name: String,
+    spec: DeclarativeAdapterSpec,
+}
+
+impl DeclarativeAdapter {
+    pub fn new(name: &str, spec: DeclarativeAdapterSpec) -> Self {
+        Self {
+            name: name.to_string(),
+            spec,
+        }
+    }
+
+    /// Name and entry pairs in the container's order
+    fn entries<'a>(&self, container: &'a JsonValue) -> Vec<(String, &'a JsonValue)> {
+        match (&self.spec.layout, container) {
+            (ContainerLayout::Map, JsonValue::Object(servers)) => {
+                servers.iter().map(|(name, entry)| (name.clone(), entry)).collect()
+            }
+            (ContainerLayout::List { name }, JsonValue::Array(servers)) => servers
+                .iter()
+                .filter_map(|entry| Some((entry.pointer(name)?.as_str()?.to_string(), entry)))
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    fn parse_entry(&self, name: &str, entry: &JsonValue) -> Result<McpServerConfig> {
+        let fields = &self.spec.fields;
+        let read = |pointer: &Option<String>| pointer.as_deref().and_then(|p| entry.pointer(p));
+
+        let command = read(&fields.command).and_then(|v| v.as_str()).map(String::from);
+        let url = read(&fields.url).and_then(|v| v.as_str()).map(String::from);
+        if command.is_none() && url.is_none() {
+            bail!("entry has neither a command nor a url");
+        }
+
+        let args = read(&fields.args)
+            .and_then(|v| v.as_array())
+            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+            .unwrap_or_default();
+        let env = read(&fields.env)
+            .and_then(|v| v.as_object())
+            .map(|obj| {
+                obj.iter()
+                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                    .collect()
+            })
+            .unwrap_or_default();
+        let enabled = match (read(&fields.disabled), read(&fields.enabled)) {
+            (Some(disabled), _) => !disabled.as_bool().unwrap_or(false),
+            (_, Some(enabled)) => enabled.as_bool().unwrap_or(true),
+            _ => true,
+        };
+
+        Ok(McpServerConfig {
+            name: name.to_string(),
+            command,
+            args,
+            env,
+            cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
+            server_type: match url {
+                Some(base_url) => ServerType::Http { base_url },
+                None => ServerType::Stdio,
+            },
+            metadata: ServerMetadata {
+                description: None,
+                version: None,
+                author: None,
+                capabilities: Vec::new(),
+                enabled,
+                source: ConfigSource::MainConfig,
+            },
+        })
+    }
+
+    /// Write a server into an entry, on top of whatever the entry already held
+    fn format_entry(&self, server: &McpServerConfig, mut entry: JsonValue) -> JsonValue {
+        let fields = &self.spec.fields;
+
+        for (pointer, value) in &self.spec.constants {
+            pointer_set(&mut entry, pointer, value.clone());
+        }
+        if let ContainerLayout::List { name } = &self.spec.layout {
+            pointer_set(&mut entry, name, server.name.clone().into());
+        }
+
+        if let (Some(pointer), Some(command)) = (&fields.command, &server.command) {
+            pointer_set(&mut entry, pointer, command.clone().into());
+        }
+        if let Some(pointer) = &fields.args {
+            if server.args.is_empty() {
+                pointer_remove(&mut entry, pointer);
+            } else {
+                pointer_set(&mut entry, pointer, serde_json::json!(server.args));
+            }
+        }
+        if let Some(pointer) = &fields.env {
+            if server.env.is_empty() {
+                pointer_remove(&mut entry, pointer);
+            } else {
+                let env: Map<String, JsonValue> = server.env.iter().map(|(k, v)| (k.clone(), v.clone().into())).collect();
+                pointer_set(&mut entry, pointer, JsonValue::Object(env));
+            }
+        }
+        if let Some(pointer) = &fields.url {
+            match &server.server_type {
+                ServerType::Http { base_url: url } | ServerType::Sse { url } | ServerType::WebSocket { url } => {
+                    pointer_set(&mut entry, pointer, url.clone().into())
+                }
+                _ => pointer_remove(&mut entry, pointer),
+            }
+        }
+        if let Some(pointer) = &fields.disabled {
+            if server.metadata.enabled {
+                pointer_remove(&mut entry, pointer);
+            } else {
+                pointer_set(&mut entry, pointer, true.into());
+            }
+        }
+        if let Some(pointer) = &fields.enabled {
+            pointer_set(&mut entry, pointer, server.metadata.enabled.into());
+        }
+
+        entry
+    }
+
+    /// Read the servers at the declared container; entries that don't parse are reported
+    pub fn extract(&self, config: &JsonValue) -> ExtractionResult {
+        let mut servers = Vec::new();
+        let mut messages = Vec::new();
+
+        match config.pointer(&self.spec.container) {
+            Some(container) => {
+                for (name, entry) in self.entries(container) {
+                    match self.parse_entry(&name, entry) {
+                        Ok(server) => servers.push(server),
+                        Err(e) => messages.push(format!("Failed to parse server '{}': {}", name, e)),
+                    }
+                }
+            }
+            None => messages.push(format!("No servers found at {}", self.spec.container)),
+        }
+
+        ExtractionResult {
+            servers,
+            messages,
+            success: true,
+        }
+    }
+
+    /// Write `servers` to the declared container, keeping unmapped fields of existing entries
+    pub fn apply(&self, config: &JsonValue, servers: &[McpServerConfig]) -> ApplicationResult {
+        let mut new_config = config.clone();
+        let mut messages = Vec::new();
+
+        // Entries we keep start from what the client had, so unmapped fields survive
+        let mut existing: HashMap<String, JsonValue> = new_config
+            .pointer(&self.spec.container)
+            .map(|container| self.entries(container).into_iter().map(|(name, entry)| (name, entry.clone())).collect())
+            .unwrap_or_default();
+
+        let entries = servers.iter().map(|server| {
+            let base = existing.remove(&server.name).unwrap_or_else(|| JsonValue::Object(Map::new()));
+            messages.push(format!("Added server '{}'", server.name));
+            (server.name.clone(), self.format_entry(server, base))
+        });
+        let container = match &self.spec.layout {
+            ContainerLayout::Map => JsonValue::Object(entries.collect()),
+            ContainerLayout::List { .. } => JsonValue::Array(entries.map(|(_, entry)| entry).collect()),
+        };
+
+        if self.spec.container.is_empty() {
+            new_config = container;
+        } else {
+            pointer_set(&mut new_config, &self.spec.container, container);
+        }
+
+        ApplicationResult {
+            config: new_config,
+            messages,
+            success: true,
+        }
+    }
+}
+
+#[async_trait]
+impl ApplicationAdapter for DeclarativeAdapter {
+    async fn extract_server_configs(&self, config: &JsonValue) -> Result<ExtractionResult> {
+        Ok(self.extract(config))
+    }
+
+    async fn apply_server_configs(&self, config: &JsonValue, servers: &[McpServerConfig]) -> Result<ApplicationResult> {
+        Ok(self.apply(config, servers))
+    }
+
+    async fn validate_config(&self, config: &JsonValue) -> Result<bool> {
+        Ok(match (config.pointer(&self.spec.container), &self.spec.layout) {
+            (None, _) => true,
+            (Some(container), ContainerLayout::Map) => container
+                .as_object()
+                .is_some_and(|servers| servers.iter().all(|(name, entry)| self.parse_entry(name, entry).is_ok())),
+            (Some(container), ContainerLayout::List { .. }) => container.as_array().is_some_and(|servers| {
+                self.entries(container).len() == servers.len()
+                    && self.entries(container).iter().all(|(name, entry)| self.parse_entry(name, entry).is_ok())
+            }),
+        })
+    }
+
+    fn get_supported_formats(&self) -> Vec<ConfigFormat> {
+        vec![ConfigFormat::Json, ConfigFormat::Yaml, ConfigFormat::Toml]
+    }
+
+    fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    fn can_handle(&self, profile: &ApplicationProfile) -> bool {
+        profile.metadata.declarative_adapter.as_ref() == Some(&self.spec)
+    }
+}
+
+fn check_pointer(field: &str, pointer: &str) -> Result<()> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        bail!("{} must be a JSON pointer starting with '/', got '{}'", field, pointer);
+    }
+    Ok(())
+}
+
+/// Unescaped reference tokens of a JSON pointer (`~1` is `/`, `~0` is `~`)
+pub(crate) fn pointer_tokens(pointer: &str) -> Vec<String> {
+    pointer
+        .split('/')
+        .skip(1)
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+        .collect()
+}
+
+/// Set the value at `pointer`, creating objects along the way and replacing non-objects
+fn pointer_set(target: &mut JsonValue, pointer: &str, value: JsonValue) {
+    let tokens = pointer_tokens(pointer);
+    let Some((last, parents)) = tokens.split_last() else {
+        *target = value;
+        return;
+    };
+
+    let mut node = target;
+    for token in parents {
+        if !node.is_object() {
+            *node = JsonValue::Object(Map::new());
+        }
+        node = node
+            .as_object_mut()
+            .map(|object| object.entry(token.clone()).or_insert_with(|| JsonValue::Object(Map::new())))
+            .expect("node was just made an object");
+    }
+    if !node.is_object() {
+        *node = JsonValue::Object(Map::new());
+    }
+    if let Some(object) = node.as_object_mut() {
+        object.insert(last.clone(), value);
+    }
+}
+
+fn pointer_remove(target: &mut JsonValue, pointer: &str) {
+    let tokens = pointer_tokens(pointer);
+    let Some((last, parents)) = tokens.split_last() else {
+        return;
+    };
+    let parent = parents.iter().try_fold(target, |node, token| node.get_mut(token.as_str()));
+    if let Some(object) = parent.and_then(|node| node.as_object_mut()) {
+        object.remove(last);
+    }
+}
+
+/// Parse and validate the adapter a profile declares, if any
+pub fn declared_adapter(profile: &ApplicationProfile) -> Result<Option<DeclarativeAdapter>> {
+    let Some(spec) = &profile.metadata.declarative_adapter else {
+        return Ok(None);
+    };
+    spec.validate()
+        .with_context(|| format!("Invalid declarative adapter for {}", profile.id))?;
+    Ok(Some(DeclarativeAdapter::new(&profile.id, spec.clone())))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::ApplicationRegistry;
+    use serde_json::json;
+
+    /// A made-up client that lists servers under `assistant.tools` with a nested launch block
+    fn registry_json(adapter: JsonValue) -> String {
+        let mut profile = serde_json::to_value(ApplicationRegistry::new().get_application("cursor").unwrap()).unwrap();
+        profile["id"] = "orbit".into();
+        profile["name"] = "Orbit".into();
+        profile["metadata"]["declarative_adapter"] = adapter;
+        json!({"version": "1.0.0", "applications": [profile]}).to_string()
+    }
+
+    fn orbit_adapter() -> JsonValue {
+        json!({
+            "container": "/assistant/tools",
+            "layout": {"kind": "list", "name": "/id"},
+            "fields": {
+                "command": "/launch/program",
+                "args": "/launch/arguments",
+                "env": "/launch/environment",
+                "url": "/endpoint",
+                "enabled": "/active"
+            },
+            "constants": {"/kind": "mcp"}
+        })
+    }
+
+    #[tokio::test]
+    async fn test_declarative_adapter_round_trip() {
+        let registry = ApplicationRegistry::from_json_str(&registry_json(orbit_adapter())).unwrap();
+        let orbit = registry.get_application("orbit").unwrap();
+        let adapter = super::super::AdapterFactory::create_adapter(orbit).unwrap();
+        assert_eq!(adapter.get_name(), "orbit");
+        assert!(adapter.can_handle(orbit));
+
+        let config = json!({
+            "theme": "nebula",
+            "assistant": {
+                "model": "large",
+                "tools": [
+                    {"kind": "mcp", "id": "github", "active": true, "timeoutMs": 30000,
+                     "launch": {"program": "npx", "arguments": ["-y", "@modelcontextprotocol/server-github"],
+                                "environment": {"GITHUB_TOKEN": "${GITHUB_TOKEN}"}}},
+                    {"kind": "mcp", "id": "docs", "active": false, "endpoint": "https://docs.example.com/mcp"}
+                ]
+            }
+        });
+        assert!(adapter.validate_config(&config).await.unwrap());
+
+        let extracted = adapter.extract_server_configs(&config).await.unwrap();
+        assert!(extracted.messages.is_empty());
+        assert_eq!(extracted.servers.len(), 2);
+        let github = &extracted.servers[0];
+        assert_eq!(github.command.as_deref(), Some("npx"));
+        assert_eq!(github.env["GITHUB_TOKEN"], "${GITHUB_TOKEN}");
+        let docs = &extracted.servers[1];
+        assert_eq!(docs.server_type, ServerType::Http { base_url: "https://docs.example.com/mcp".to_string() });
+        assert!(!docs.metadata.enabled);
+
+        // Writing back what was read reproduces the config, unmapped fields included
+        let applied = adapter.apply_server_configs(&config, &extracted.servers).await.unwrap();
+        assert_eq!(applied.config, config);
+
+        // A new server gets the constants and the declared shape
+        let mut added = extracted.servers.clone();
+        added.push(McpServerConfig { name: "fetch".to_string(), command: Some("uvx".to_string()), args: vec!["mcp-server-fetch".to_string()], ..github.clone() });
+        let applied = adapter.apply_server_configs(&config, &added).await.unwrap();
+        assert_eq!(
+            applied.config["assistant"]["tools"][2],
+            json!({"kind": "mcp", "id": "fetch", "active": true,
+                   "launch": {"program": "uvx", "arguments": ["mcp-server-fetch"], "environment": {"GITHUB_TOKEN": "${GITHUB_TOKEN}"}}})
+        );
+        assert_eq!(applied.config["assistant"]["model"], "large");
+    }
+
+    #[test]
+    fn test_invalid_adapter_rejected_at_registry_load() {
+        let mut no_command = orbit_adapter();
+        no_command["fields"] = json!({"args": "/launch/arguments"});
+        let error = ApplicationRegistry::from_json_str(&registry_json(no_command)).unwrap_err();
+        assert!(format!("{:#}", error).contains("command or url"));
+
+        let mut bad_pointer = orbit_adapter();
+        bad_pointer["container"] = "assistant.tools".into();
+        let error = ApplicationRegistry::from_json_str(&registry_json(bad_pointer)).unwrap_err();
+        assert!(format!("{:#}", error).contains("JSON pointer"));
+    }
+}
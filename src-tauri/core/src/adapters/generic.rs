@@ -2,15 +2,30 @@ use anyhow::Result;
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 
-use crate::detection::{McpServerConfig, ApplicationProfile, ConfigFormat};
-use super::{ApplicationAdapter, ExtractionResult, ApplicationResult};
+use crate::detection::{is_metadata_key, McpServerConfig, ApplicationProfile, ConfigFormat, ServersPath};
+use super::{ApplicationAdapter, ExtractionResult, ApplicationResult, extra_options, flat_entry, insert_env, insert_extra_options, server_env};
 
 /// Generic application adapter for unknown applications
-pub struct GenericAdapter;
+pub struct GenericAdapter {
+    /// Where the servers object is; when unset, common patterns are tried
+    servers_path: Option<ServersPath>,
+}
 
 impl GenericAdapter {
     pub fn new() -> Self {
-        Self
+        Self { servers_path: None }
+    }
+
+    /// Read and write servers at the profile's servers path
+    pub fn with_servers_path(mut self, servers_path: ServersPath) -> Self {
+        self.servers_path = Some(servers_path);
+        self
+    }
+}
+
+impl Default for GenericAdapter {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -20,27 +35,24 @@ impl ApplicationAdapter for GenericAdapter {
         let mut servers = Vec::new();
         let mut messages = Vec::new();
         
-        // Try common MCP configuration patterns
-        if let Some(mcp_servers) = config.get("mcpServers").and_then(|v| v.as_object()) {
+        // The profile's own servers path first, then common MCP configuration patterns
+        let mcp_servers = self
+            .servers_path
+            .as_ref()
+            .and_then(|path| path.get(config))
             // Amazon Q / Claude Desktop style
-            for (name, server_config) in mcp_servers {
+            .or_else(|| config.get("mcpServers").and_then(|v| v.as_object()))
+            // Cursor style
+            .or_else(|| config.get("mcp").and_then(|m| m.get("servers")).and_then(|v| v.as_object()));
+        
+        if let Some(mcp_servers) = mcp_servers {
+            for (name, server_config) in mcp_servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
                 if let Ok(server) = self.parse_generic_server_config(name, server_config) {
                     servers.push(server);
                 } else {
                     messages.push(format!("Failed to parse server '{}'", name));
                 }
             }
-        } else if let Some(mcp_config) = config.get("mcp") {
-            if let Some(mcp_servers) = mcp_config.get("servers").and_then(|v| v.as_object()) {
-                // Cursor style
-                for (name, server_config) in mcp_servers {
-                    if let Ok(server) = self.parse_generic_server_config(name, server_config) {
-                        servers.push(server);
-                    } else {
-                        messages.push(format!("Failed to parse server '{}'", name));
-                    }
-                }
-            }
         } else {
             messages.push("No recognized MCP configuration pattern found".to_string());
         }
@@ -58,14 +70,15 @@ impl ApplicationAdapter for GenericAdapter {
         let mut new_config = config.clone();
         let mut messages = Vec::new();
         
-        // Use mcpServers format as default
-        if new_config.get("mcpServers").is_none() {
-            new_config["mcpServers"] = serde_json::json!({});
+        // Use the profile's servers path, or mcpServers format as default
+        let servers_path = self
+            .servers_path
+            .clone()
+            .unwrap_or_else(|| ServersPath::nested(vec!["mcpServers".to_string()]));
+        if !new_config.is_object() {
+            new_config = serde_json::json!({});
         }
-        
-        let mcp_servers = new_config["mcpServers"]
-            .as_object_mut()
-            .ok_or_else(|| anyhow::anyhow!("Failed to get mcpServers as object"))?;
+        let mcp_servers = servers_path.get_or_insert(&mut new_config);
         
         // Clear existing servers
         mcp_servers.clear();
@@ -105,6 +118,7 @@ impl ApplicationAdapter for GenericAdapter {
 
 impl GenericAdapter {
     fn parse_generic_server_config(&self, name: &str, config: &JsonValue) -> Result<McpServerConfig> {
+        let config = &flat_entry(config);
         use crate::detection::{ServerType, ServerMetadata, ConfigSource};
         
         let command = config.get("command")
@@ -116,21 +130,16 @@ impl GenericAdapter {
             .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
             .unwrap_or_default();
         
-        let env = config.get("env")
-            .and_then(|v| v.as_object())
-            .map(|obj| {
-                obj.iter()
-                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                    .collect()
-            })
-            .unwrap_or_default();
+        let env = server_env(config);
         
         Ok(McpServerConfig {
             name: name.to_string(),
             command,
             args,
             env,
-            cwd: None,
+            cwd: config.get("cwd").and_then(|v| v.as_str()).map(String::from),
+            inherit_env: config.get("inheritEnv").and_then(|v| v.as_bool()).unwrap_or(true),
+            extra_options: extra_options(config),
             server_type: ServerType::Stdio,
             metadata: ServerMetadata {
                 description: None,
@@ -149,8 +158,15 @@ impl GenericAdapter {
             "args": server.args
         });
         
-        if !server.env.is_empty() {
-            config["env"] = serde_json::json!(server.env);
+        if let Some(cwd) = &server.cwd {
+            config["cwd"] = serde_json::json!(cwd);
+        }
+        
+        insert_extra_options(&mut config, server);
+        insert_env(&mut config, &server.env);
+        
+        if !server.inherit_env {
+            config["inheritEnv"] = serde_json::json!(false);
         }
         
         if !server.metadata.enabled {
@@ -2,8 +2,8 @@ use anyhow::{Context, Result};
 use async_trait::async_trait;
 use serde_json::Value as JsonValue;
 
-use crate::detection::{McpServerConfig, ServerType, ApplicationProfile, ConfigFormat};
-use super::{ApplicationAdapter, ExtractionResult, ApplicationResult};
+use crate::detection::{is_metadata_key, McpServerConfig, ServerType, ApplicationProfile, ConfigFormat};
+use super::{ApplicationAdapter, ExtractionResult, ApplicationResult, extra_options, flat_entry, insert_env, insert_extra_options, server_env};
 
 /// JetBrains IDEs application adapter
 /// Handles all JetBrains products: IntelliJ IDEA, PHPStorm, WebStorm, PyCharm, etc.
@@ -15,6 +15,12 @@ impl JetBrainsAdapter {
     }
 }
 
+impl Default for JetBrainsAdapter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 #[async_trait]
 impl ApplicationAdapter for JetBrainsAdapter {
     async fn extract_server_configs(&self, config: &JsonValue) -> Result<ExtractionResult> {
@@ -25,7 +31,7 @@ impl ApplicationAdapter for JetBrainsAdapter {
         // Similar to VS Code/Cursor pattern
         if let Some(mcp_config) = config.get("mcp") {
             if let Some(mcp_servers) = mcp_config.get("servers").and_then(|v| v.as_object()) {
-                for (name, server_config) in mcp_servers {
+                for (name, server_config) in mcp_servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
                     match self.parse_server_config(name, server_config) {
                         Ok(server) => servers.push(server),
                         Err(e) => {
@@ -90,7 +96,7 @@ impl ApplicationAdapter for JetBrainsAdapter {
 
                 // Validate each server configuration
                 if let Some(servers) = mcp_servers.as_object() {
-                    for (name, server_config) in servers {
+                    for (name, server_config) in servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
                         if !self.validate_server_config(name, server_config) {
                             return Ok(false);
                         }
@@ -117,6 +123,7 @@ impl ApplicationAdapter for JetBrainsAdapter {
 
 impl JetBrainsAdapter {
     fn parse_server_config(&self, name: &str, config: &JsonValue) -> Result<McpServerConfig> {
+        let config = &flat_entry(config);
         use crate::detection::{ServerMetadata, ConfigSource};
 
         let command = config.get("command")
@@ -128,21 +135,16 @@ impl JetBrainsAdapter {
             .map(|arr| arr.iter().filter_map(|v| v.as_str().map(|s| s.to_string())).collect())
             .unwrap_or_default();
 
-        let env = config.get("env")
-            .and_then(|v| v.as_object())
-            .map(|obj| {
-                obj.iter()
-                    .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
-                    .collect()
-            })
-            .unwrap_or_default();
+        let env = server_env(config);
 
         Ok(McpServerConfig {
             name: name.to_string(),
             command: Some(command.to_string()),
             args,
             env,
-            cwd: None,
+            cwd: config.get("cwd").and_then(|v| v.as_str()).map(String::from),
+            inherit_env: config.get("inheritEnv").and_then(|v| v.as_bool()).unwrap_or(true),
+            extra_options: extra_options(config),
             server_type: ServerType::Stdio,
             metadata: ServerMetadata {
                 description: None,
@@ -161,8 +163,15 @@ impl JetBrainsAdapter {
             "args": server.args
         });
 
-        if !server.env.is_empty() {
-            config["env"] = serde_json::json!(server.env);
+        if let Some(cwd) = &server.cwd {
+            config["cwd"] = serde_json::json!(cwd);
+        }
+
+        insert_extra_options(&mut config, server);
+        insert_env(&mut config, &server.env);
+
+        if !server.inherit_env {
+            config["inheritEnv"] = serde_json::json!(false);
         }
 
         if !server.metadata.enabled {
@@ -173,6 +182,7 @@ impl JetBrainsAdapter {
     }
 
     fn validate_server_config(&self, _name: &str, config: &JsonValue) -> bool {
+        let config = &flat_entry(config);
         // Must have command
         if config.get("command").and_then(|v| v.as_str()).is_none() {
             return false;
@@ -0,0 +1,421 @@
+use anyhow::{bail, Context, Result};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde_json::{Map, Value as JsonValue};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use crate::detection::{ApplicationProfile, ConfigFormat};
+use crate::filesystem::FsProvider;
+
+/// Config format name used by profiles backed by a JetBrains IDE options file
+pub const OPTIONS_XML_FORMAT: &str = "jetbrains-options-xml";
+
+/// Options file AI Assistant keeps MCP servers in, relative to the IDE's versioned config dir
+pub const MCP_OPTIONS_FILE: &str = "options/llm.mcpServers.xml";
+
+/// Component holding the server list
+const MCP_COMPONENT: &str = "McpApplicationServerCommands";
+
+/// Element describing one stdio server inside the component's `<commands>`
+const COMMAND_ELEMENT: &str = "McpServerCommand";
+
+/// Whether a profile's servers live in a JetBrains options XML file
+pub fn is_options_xml_backed(profile: &ApplicationProfile) -> bool {
+    matches!(&profile.config_format, ConfigFormat::Custom(format) if format == OPTIONS_XML_FORMAT)
+}
+
+/// Resolve a wildcard directory such as `IntelliJIdea*` to the newest installed version.
+///
+/// JetBrains keeps one config dir per release (`IntelliJIdea2024.3`, `IntelliJIdea2025.1`);
+/// only names continuing with a version number match, so `PyCharm*` skips `PyCharmCE2025.1`.
+/// The file below the directory doesn't have to exist yet. Paths without a wildcard are
+/// returned unchanged.
+pub(crate) async fn resolve_versioned_path(fs: &dyn FsProvider, path: &Path) -> Option<PathBuf> {
+    let components: Vec<_> = path.components().collect();
+    let Some(index) = components.iter().position(|c| c.as_os_str().to_string_lossy().contains('*')) else {
+        return Some(path.to_path_buf());
+    };
+
+    let parent: PathBuf = components[..index].iter().collect();
+    let pattern = components[index].as_os_str().to_string_lossy();
+    let prefix = pattern.trim_end_matches('*');
+
+    let newest = fs
+        .read_dir(&parent)
+        .await
+        .ok()?
+        .into_iter()
+        .filter_map(|dir| {
+            let name = dir.file_name()?.to_string_lossy().to_string();
+            let version = parse_ide_version(name.strip_prefix(prefix)?)?;
+            Some((version, dir))
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))?
+        .1;
+
+    Some(components[index + 1..].iter().fold(newest, |path, c| path.join(c)))
+}
+
+/// `2025.1` -> [2025, 1]
+fn parse_ide_version(version: &str) -> Option<Vec<u32>> {
+    if !version.starts_with(|c: char| c.is_ascii_digit()) {
+        return None;
+    }
+    version.split('.').map(|part| part.parse().ok()).collect()
+}
+
+/// Servers in an options file, shaped like `{"mcp": {"servers": ...}}` for the JetBrains adapter.
+///
+/// An empty file, or one without the MCP component, has no servers.
+pub fn read_options_xml(content: &str) -> Result<JsonValue> {
+    let document = OptionsDocument::parse(content)?;
+    let servers: Map<String, JsonValue> = document.servers.into_iter().collect();
+    Ok(serde_json::json!({ "mcp": { "servers": servers } }))
+}
+
+/// Render an options file with its server list replaced by the servers in `config`.
+///
+/// Only the component's `<commands>` element is rewritten, in the layout the IDE itself
+/// writes; other components and attributes are kept byte for byte, and servers already in
+/// the file keep their position.
+pub fn write_options_xml(content: &str, config: &JsonValue) -> Result<String> {
+    let document = OptionsDocument::parse(content)?;
+    let empty = Map::new();
+    let servers = config
+        .get("mcp")
+        .and_then(|mcp| mcp.get("servers"))
+        .and_then(|servers| servers.as_object())
+        .unwrap_or(&empty);
+
+    let mut ordered: Vec<(&String, &JsonValue)> = document
+        .servers
+        .iter()
+        .filter_map(|(name, _)| servers.get_key_value(name))
+        .collect();
+    ordered.extend(servers.iter().filter(|(name, _)| !document.servers.iter().any(|(existing, _)| existing == *name)));
+    let commands = render_commands(&ordered);
+
+    let mut output = content.to_string();
+    match (document.commands, document.component_end, document.application_end) {
+        (Some(span), _, _) => output.replace_range(span, &commands),
+        (None, Some(end), _) => output.insert_str(end, &format!("  {}\n  ", commands)),
+        (None, None, Some(end)) => output.insert_str(
+            end,
+            &format!("  <component name=\"{}\">\n    {}\n  </component>\n", MCP_COMPONENT, commands),
+        ),
+        (None, None, None) if content.trim().is_empty() => {
+            output = format!(
+                "<application>\n  <component name=\"{}\">\n    {}\n  </component>\n</application>\n",
+                MCP_COMPONENT, commands
+            );
+        }
+        (None, None, None) => bail!("Options file has no <application> element"),
+    }
+    Ok(output)
+}
+
+/// What we need from an options file: its servers and where to splice a new list in
+#[derive(Debug, Default)]
+struct OptionsDocument {
+    /// Servers in file order
+    servers: Vec<(String, JsonValue)>,
+    /// Byte range of the MCP component's `<commands>` element
+    commands: Option<Range<usize>>,
+    /// Offset of the MCP component's closing tag
+    component_end: Option<usize>,
+    /// Offset of the closing `</application>` tag
+    application_end: Option<usize>,
+}
+
+/// Options of one `<McpServerCommand>`
+#[derive(Debug)]
+struct CommandOptions {
+    name: Option<String>,
+    executable: Option<String>,
+    program_args: String,
+    enabled: bool,
+    env: Map<String, JsonValue>,
+}
+
+impl CommandOptions {
+    fn new() -> Self {
+        Self {
+            name: None,
+            executable: None,
+            program_args: String::new(),
+            enabled: true,
+            env: Map::new(),
+        }
+    }
+
+    fn set(&mut self, option: &str, value: String) {
+        match option {
+            "name" => self.name = Some(value),
+            "executable" => self.executable = Some(value),
+            "programArgs" => self.program_args = value,
+            "enabled" => self.enabled = value != "false",
+            _ => {}
+        }
+    }
+
+    fn into_entry(self) -> Option<(String, JsonValue)> {
+        let mut entry = serde_json::json!({
+            "command": self.executable?,
+            "args": split_program_args(&self.program_args),
+        });
+        if !self.env.is_empty() {
+            entry["env"] = JsonValue::Object(self.env);
+        }
+        if !self.enabled {
+            entry["disabled"] = JsonValue::Bool(true);
+        }
+        Some((self.name?, entry))
+    }
+}
+
+impl OptionsDocument {
+    fn parse(content: &str) -> Result<Self> {
+        let mut document = Self::default();
+        let mut reader = Reader::from_str(content);
+        let mut in_component = false;
+        let mut commands_start = None;
+        let mut command: Option<CommandOptions> = None;
+        let mut in_envs = false;
+
+        loop {
+            let start = reader.buffer_position() as usize;
+            let event = reader.read_event().context("Failed to parse JetBrains options XML")?;
+            let end = reader.buffer_position() as usize;
+            match event {
+                Event::Start(element) => match element.name().as_ref() {
+                    b"component" => in_component = attribute(&element, "name")?.as_deref() == Some(MCP_COMPONENT),
+                    b"commands" if in_component => commands_start = Some(start),
+                    name if name == COMMAND_ELEMENT.as_bytes() && commands_start.is_some() => {
+                        command = Some(CommandOptions::new());
+                    }
+                    b"option" if command.is_some() => in_envs = attribute(&element, "name")?.as_deref() == Some("envs"),
+                    _ => {}
+                },
+                Event::Empty(element) => match element.name().as_ref() {
+                    b"commands" if in_component => document.commands = Some(start..end),
+                    b"option" => {
+                        if let (Some(command), Some(name)) = (command.as_mut(), attribute(&element, "name")?) {
+                            command.set(&name, attribute(&element, "value")?.unwrap_or_default());
+                        }
+                    }
+                    b"entry" if in_envs => {
+                        if let (Some(command), Some(key)) = (command.as_mut(), attribute(&element, "key")?) {
+                            let value = attribute(&element, "value")?.unwrap_or_default();
+                            command.env.insert(key, JsonValue::String(value));
+                        }
+                    }
+                    _ => {}
+                },
+                Event::End(element) => match element.name().as_ref() {
+                    b"option" => in_envs = false,
+                    name if name == COMMAND_ELEMENT.as_bytes() => {
+                        document.servers.extend(command.take().and_then(CommandOptions::into_entry));
+                    }
+                    b"commands" if in_component => {
+                        if let Some(commands_start) = commands_start.take() {
+                            document.commands = Some(commands_start..end);
+                        }
+                    }
+                    b"component" if in_component => {
+                        document.component_end = Some(start);
+                        in_component = false;
+                    }
+                    b"application" => document.application_end = Some(start),
+                    _ => {}
+                },
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+
+        Ok(document)
+    }
+}
+
+fn attribute(element: &BytesStart, name: &str) -> Result<Option<String>> {
+    match element.try_get_attribute(name)? {
+        Some(attribute) => Ok(Some(attribute.unescape_value()?.into_owned())),
+        None => Ok(None),
+    }
+}
+
+/// The `<commands>` element as the IDE writes it: two-space indents, options sorted by name
+fn render_commands(servers: &[(&String, &JsonValue)]) -> String {
+    if servers.is_empty() {
+        return "<commands />".to_string();
+    }
+
+    let mut xml = String::from("<commands>\n");
+    for (name, entry) in servers {
+        let enabled = !entry.get("disabled").and_then(|v| v.as_bool()).unwrap_or(false);
+        let command = entry.get("command").and_then(|v| v.as_str()).unwrap_or_default();
+        let args: Vec<&str> = entry
+            .get("args")
+            .and_then(|v| v.as_array())
+            .map(|args| args.iter().filter_map(|a| a.as_str()).collect())
+            .unwrap_or_default();
+
+        xml.push_str(&format!("      <{}>\n", COMMAND_ELEMENT));
+        xml.push_str(&format!("        <option name=\"enabled\" value=\"{}\" />\n", enabled));
+        if let Some(env) = entry.get("env").and_then(|v| v.as_object()).filter(|env| !env.is_empty()) {
+            xml.push_str("        <option name=\"envs\">\n          <map>\n");
+            for (key, value) in env {
+                xml.push_str(&format!(
+                    "            <entry key=\"{}\" value=\"{}\" />\n",
+                    escape_attribute(key),
+                    escape_attribute(value.as_str().unwrap_or_default())
+                ));
+            }
+            xml.push_str("          </map>\n        </option>\n");
+        }
+        xml.push_str(&format!("        <option name=\"executable\" value=\"{}\" />\n", escape_attribute(command)));
+        xml.push_str(&format!("        <option name=\"name\" value=\"{}\" />\n", escape_attribute(name)));
+        if !args.is_empty() {
+            xml.push_str(&format!(
+                "        <option name=\"programArgs\" value=\"{}\" />\n",
+                escape_attribute(&join_program_args(&args))
+            ));
+        }
+        xml.push_str(&format!("      </{}>\n", COMMAND_ELEMENT));
+    }
+    xml.push_str("    </commands>");
+    xml
+}
+
+fn escape_attribute(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+/// Split `programArgs` the way the IDE does: on whitespace, honoring double quotes and `\"`
+fn split_program_args(program_args: &str) -> Vec<String> {
+    let mut args = Vec::new();
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut has_token = false;
+
+    let mut chars = program_args.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if chars.peek() == Some(&'"') => {
+                current.push('"');
+                chars.next();
+                has_token = true;
+            }
+            '"' => {
+                in_quotes = !in_quotes;
+                has_token = true;
+            }
+            c if c.is_whitespace() && !in_quotes => {
+                if has_token {
+                    args.push(std::mem::take(&mut current));
+                    has_token = false;
+                }
+            }
+            c => {
+                current.push(c);
+                has_token = true;
+            }
+        }
+    }
+    if has_token {
+        args.push(current);
+    }
+    args
+}
+
+/// Inverse of [`split_program_args`]
+fn join_program_args(args: &[&str]) -> String {
+    args.iter()
+        .map(|arg| {
+            if arg.is_empty() || arg.contains(|c: char| c.is_whitespace() || c == '"') {
+                format!("\"{}\"", arg.replace('"', "\\\""))
+            } else {
+                arg.to_string()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::adapters::jetbrains::JetBrainsAdapter;
+    use crate::adapters::ApplicationAdapter;
+    use crate::filesystem::InMemoryFs;
+    use serde_json::json;
+
+    /// Captured from IntelliJ IDEA 2025.1 with AI Assistant and two servers configured
+    const IDEA_OPTIONS: &str = include_str!("../../tests/fixtures/jetbrains/IntelliJIdea2025.1/options/llm.mcpServers.xml");
+
+    #[tokio::test]
+    async fn test_reads_servers_from_captured_options() {
+        let config = read_options_xml(IDEA_OPTIONS).unwrap();
+        let servers = &config["mcp"]["servers"];
+        assert_eq!(servers["github"]["args"], json!(["-y", "@modelcontextprotocol/server-github"]));
+        assert_eq!(servers["github"]["env"]["GITHUB_PERSONAL_ACCESS_TOKEN"], "ghp_example");
+        assert_eq!(servers["fetch"]["args"], json!(["mcp-server-fetch", "--user-agent", "JetBrains MCP"]));
+        assert_eq!(servers["fetch"]["disabled"], true);
+
+        let extracted = JetBrainsAdapter::new().extract_server_configs(&config).await.unwrap();
+        assert_eq!(extracted.servers.len(), 2);
+    }
+
+    #[test]
+    fn test_write_round_trips_and_preserves_other_components() {
+        // Writing back unchanged servers reproduces the IDE's own output
+        let config = read_options_xml(IDEA_OPTIONS).unwrap();
+        assert_eq!(write_options_xml(IDEA_OPTIONS, &config).unwrap(), IDEA_OPTIONS);
+
+        let mut changed = config.clone();
+        changed["mcp"]["servers"].as_object_mut().unwrap().remove("fetch");
+        changed["mcp"]["servers"]["memory"] = json!({"command": "npx", "args": ["-y", "@modelcontextprotocol/server-memory"]});
+        let written = write_options_xml(IDEA_OPTIONS, &changed).unwrap();
+
+        assert!(written.contains(r#"<component name="McpApplicationServerCommands" modifiable="true" autoEnableExternalChanges="true">"#));
+        assert!(written.contains("<urlCommands />"));
+        assert!(written.contains(r#"<option name="notifyAboutAutoStart" value="false" />"#));
+        assert!(written.find("\"github\"").unwrap() < written.find("\"memory\"").unwrap());
+        assert!(!written.contains("fetch"));
+        assert_eq!(read_options_xml(&written).unwrap(), changed);
+    }
+
+    #[test]
+    fn test_write_creates_component_when_missing() {
+        let config = json!({"mcp": {"servers": {"memory": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-memory"]}}}});
+
+        let fresh = write_options_xml("", &config).unwrap();
+        assert_eq!(read_options_xml(&fresh).unwrap(), config);
+
+        let other = "<application>\n  <component name=\"Other\">\n    <option name=\"x\" value=\"1\" />\n  </component>\n</application>\n";
+        let written = write_options_xml(other, &config).unwrap();
+        assert!(written.starts_with("<application>\n  <component name=\"Other\">"));
+        assert_eq!(read_options_xml(&written).unwrap(), config);
+    }
+
+    #[tokio::test]
+    async fn test_resolves_newest_version_dir() {
+        let root = Path::new("/home/me/.config/JetBrains");
+        let fs = InMemoryFs::case_sensitive();
+        for dir in ["IntelliJIdea2024.3", "IntelliJIdea2025.1", "IntelliJIdea2024.10", "PyCharmCE2025.2", "PyCharm2024.1"] {
+            fs.add_dir(root.join(dir));
+        }
+
+        let idea = resolve_versioned_path(&fs, &root.join("IntelliJIdea*").join(MCP_OPTIONS_FILE)).await;
+        assert_eq!(idea, Some(root.join("IntelliJIdea2025.1/options/llm.mcpServers.xml")));
+        let pycharm = resolve_versioned_path(&fs, &root.join("PyCharm*")).await;
+        assert_eq!(pycharm, Some(root.join("PyCharm2024.1")));
+        assert_eq!(resolve_versioned_path(&fs, &root.join("WebStorm*")).await, None);
+    }
+}
@@ -0,0 +1,340 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+
+use crate::configuration::provenance::PROVENANCE_KEY;
+use crate::detection::{McpServerConfig, ApplicationProfile, ConfigFormat};
+
+pub mod claude_desktop;
+pub mod cursor;
+pub mod amazon_q;
+pub mod generic;
+pub mod warp;
+pub mod warp_store;
+pub mod claude_code;
+pub mod jetbrains;
+pub mod jetbrains_options;
+pub mod declarative;
+
+/// Result of configuration extraction
+#[derive(Debug, Clone)]
+pub struct ExtractionResult {
+    /// Extracted MCP server configurations
+    pub servers: Vec<McpServerConfig>,
+    /// Any warnings or issues encountered
+    pub messages: Vec<String>,
+    /// Whether extraction was successful
+    pub success: bool,
+}
+
+/// Result of configuration application
+#[derive(Debug, Clone)]
+pub struct ApplicationResult {
+    /// Modified configuration
+    pub config: JsonValue,
+    /// Any warnings or issues encountered
+    pub messages: Vec<String>,
+    /// Whether application was successful
+    pub success: bool,
+}
+
+/// Application adapter trait for handling MCP configurations
+#[async_trait]
+pub trait ApplicationAdapter: Send + Sync {
+    /// Extract MCP server configurations from application config
+    async fn extract_server_configs(&self, config: &JsonValue) -> Result<ExtractionResult>;
+    
+    /// Apply MCP server configurations to application config
+    async fn apply_server_configs(&self, config: &JsonValue, servers: &[McpServerConfig]) -> Result<ApplicationResult>;
+    
+    /// Validate application-specific configuration
+    async fn validate_config(&self, config: &JsonValue) -> Result<bool>;
+    
+    /// Get supported configuration formats
+    fn get_supported_formats(&self) -> Vec<ConfigFormat>;
+    
+    /// Get adapter name/identifier
+    fn get_name(&self) -> &str;
+    
+    /// Check if this adapter can handle the given application profile
+    fn can_handle(&self, profile: &ApplicationProfile) -> bool;
+}
+
+/// Factory for creating application adapters
+pub struct AdapterFactory;
+
+impl AdapterFactory {
+    /// Create an adapter for the given application profile
+    pub fn create_adapter(profile: &ApplicationProfile) -> Result<Box<dyn ApplicationAdapter>> {
+        if let Some(adapter) = declarative::declared_adapter(profile)? {
+            return Ok(Box::new(adapter));
+        }
+        match profile.id.as_str() {
+            "claude-desktop" => Ok(Box::new(claude_desktop::ClaudeDesktopAdapter::new())),
+            "claude-code" => Ok(Box::new(claude_code::ClaudeCodeAdapter::new())),
+            "cursor" => Ok(Box::new(cursor::CursorAdapter::new())),
+            "amazon-q" => Ok(Box::new(amazon_q::AmazonQAdapter::new())),
+            "warp" => Ok(Box::new(warp::WarpAdapter::new())),
+            id if id.starts_with("jetbrains-") => Ok(Box::new(jetbrains::JetBrainsAdapter::new())),
+            _ => Ok(Box::new(generic::GenericAdapter::new().with_servers_path(profile.servers_path()))),
+        }
+    }
+    
+    /// Get all available adapter types
+    pub fn get_available_adapters() -> Vec<&'static str> {
+        vec![
+            "claude-desktop",
+            "claude-code",
+            "cursor",
+            "amazon-q",
+            "warp",
+            "jetbrains-idea",
+            "jetbrains-phpstorm",
+            "jetbrains-webstorm",
+            "jetbrains-pycharm",
+            "generic"
+        ]
+    }
+}
+
+/// Server entry keys mapped onto dedicated `McpServerConfig` fields
+const CORE_SERVER_KEYS: &[&str] = &[
+    "command", "args", "env", "cwd", "working_directory", "disabled", "inheritEnv", PROVENANCE_KEY,
+];
+
+/// Sub-object some applications, such as Zed, nest a server's env under
+pub(crate) const SETTINGS_KEY: &str = "settings";
+
+/// A server entry in the flat shape, with any nested `transport` object lifted out
+pub(crate) fn flat_entry(config: &JsonValue) -> JsonValue {
+    let mut config = config.clone();
+    if let Some(entry) = config.as_object_mut() {
+        crate::analysis::flatten_transport(entry);
+    }
+    config
+}
+
+/// Read a server entry's env, falling back to `settings.env` when there is no top-level `env`
+pub(crate) fn server_env(config: &JsonValue) -> HashMap<String, String> {
+    config
+        .get("env")
+        .or_else(|| config.get(SETTINGS_KEY).and_then(|settings| settings.get("env")))
+        .and_then(|v| v.as_object())
+        .map(|obj| {
+            obj.iter()
+                .filter_map(|(k, v)| v.as_str().map(|s| (k.clone(), s.to_string())))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Collect client-specific options from a server entry
+pub(crate) fn extra_options(config: &JsonValue) -> HashMap<String, JsonValue> {
+    config
+        .as_object()
+        .map(|obj| {
+            obj.iter()
+                .filter(|(key, _)| !CORE_SERVER_KEYS.contains(&key.as_str()))
+                .map(|(key, value)| {
+                    let mut value = value.clone();
+                    // A nested env is read into `McpServerConfig::env`, so it is not kept twice
+                    if key == SETTINGS_KEY {
+                        if let Some(settings) = value.as_object_mut() {
+                            settings.remove("env");
+                        }
+                    }
+                    (key.clone(), value)
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Write client-specific options back into a server entry
+pub(crate) fn insert_extra_options(config: &mut JsonValue, server: &McpServerConfig) {
+    for (key, value) in &server.extra_options {
+        config[key] = value.clone();
+    }
+}
+
+/// Write a server's env where the entry keeps it: under `settings` if the entry has a
+/// settings object, otherwise at the top level
+pub(crate) fn insert_env(config: &mut JsonValue, env: &HashMap<String, String>) {
+    if env.is_empty() {
+        return;
+    }
+    match config.get_mut(SETTINGS_KEY).and_then(|s| s.as_object_mut()) {
+        Some(settings) => {
+            settings.insert("env".to_string(), serde_json::json!(env));
+        }
+        None => config["env"] = serde_json::json!(env),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_adapter_factory_creation() {
+        use crate::detection::{DetectionStrategy, DetectionMethod, ApplicationMetadata, ApplicationCategory, ConfigStructure};
+        
+        let profile = ApplicationProfile {
+            id: "claude-desktop".to_string(),
+            name: "Claude Desktop".to_string(),
+            bundle_id: "com.anthropic.claude".to_string(),
+            config_path: "test".to_string(),
+            alt_config_paths: vec![],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec![],
+            alt_executable_paths: vec![],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![DetectionMethod::BundleLookup],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Test".to_string(),
+                category: ApplicationCategory::ChatClient,
+                mcp_version: "1.0".to_string(),
+                notes: None,
+                requires_permissions: false,
+                ..Default::default()
+            },
+        };
+        
+        let adapter = AdapterFactory::create_adapter(&profile);
+        assert!(adapter.is_ok());
+        assert_eq!(adapter.unwrap().get_name(), "claude-desktop");
+    }
+    
+    #[test]
+    fn test_available_adapters() {
+        let adapters = AdapterFactory::get_available_adapters();
+        assert!(adapters.contains(&"claude-desktop"));
+        assert!(adapters.contains(&"cursor"));
+        assert!(adapters.contains(&"amazon-q"));
+        assert!(adapters.contains(&"generic"));
+    }
+
+    #[tokio::test]
+    async fn test_reads_env_nested_under_settings() {
+        let config = serde_json::json!({
+            "mcp": {"servers": {
+                "github": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-github"],
+                    "settings": {"env": {"GITHUB_TOKEN": "<token>"}, "timeout": 30}
+                }
+            }}
+        });
+
+        let adapter = generic::GenericAdapter::new();
+        let extracted = adapter.extract_server_configs(&config).await.unwrap();
+        let server = &extracted.servers[0];
+        assert_eq!(server.env["GITHUB_TOKEN"], "<token>");
+        assert_eq!(server.extra_options["settings"], serde_json::json!({"timeout": 30}));
+
+        // Written back where the entry keeps it rather than duplicated at the top level
+        let applied = adapter.apply_server_configs(&serde_json::json!({}), &extracted.servers).await.unwrap();
+        let entry = &applied.config["mcpServers"]["github"];
+        assert_eq!(entry["settings"], serde_json::json!({"env": {"GITHUB_TOKEN": "<token>"}, "timeout": 30}));
+        assert!(entry.get("env").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_nested_env_survives_round_trip_in_every_adapter() {
+        let config = serde_json::json!({
+            "mcpServers": {
+                "github": {
+                    "command": "npx",
+                    "settings": {"env": {"GITHUB_TOKEN": "<token>"}, "timeout": 30}
+                }
+            }
+        });
+        let adapters: Vec<Box<dyn ApplicationAdapter>> = vec![
+            Box::new(cursor::CursorAdapter::new()),
+            Box::new(claude_code::ClaudeCodeAdapter::new()),
+            Box::new(amazon_q::AmazonQAdapter::new()),
+        ];
+
+        for adapter in adapters {
+            let extracted = adapter.extract_server_configs(&config).await.unwrap();
+            assert_eq!(extracted.servers[0].env["GITHUB_TOKEN"], "<token>", "{}", adapter.get_name());
+
+            let applied = adapter.apply_server_configs(&serde_json::json!({}), &extracted.servers).await.unwrap();
+            let entry = &applied.config["mcpServers"]["github"];
+            assert_eq!(entry["settings"]["env"]["GITHUB_TOKEN"], "<token>", "{}", adapter.get_name());
+            assert!(entry.get("env").is_none(), "{}", adapter.get_name());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_cwd_survives_round_trip_in_every_adapter() {
+        let entry = serde_json::json!({"command": "node", "args": ["index.js"], "cwd": "/srv/notes"});
+        let direct = serde_json::json!({"mcpServers": {"notes": entry}});
+        let nested = serde_json::json!({"mcp": {"servers": {"notes": entry}}});
+        let adapters: Vec<(Box<dyn ApplicationAdapter>, &JsonValue)> = vec![
+            (Box::new(claude_desktop::ClaudeDesktopAdapter::new()), &direct),
+            (Box::new(cursor::CursorAdapter::new()), &direct),
+            (Box::new(claude_code::ClaudeCodeAdapter::new()), &direct),
+            (Box::new(amazon_q::AmazonQAdapter::new()), &direct),
+            (Box::new(jetbrains::JetBrainsAdapter::new()), &nested),
+        ];
+
+        for (adapter, config) in adapters {
+            let extracted = adapter.extract_server_configs(config).await.unwrap();
+            assert_eq!(extracted.servers[0].cwd.as_deref(), Some("/srv/notes"), "{}", adapter.get_name());
+
+            let applied = adapter.apply_server_configs(config, &extracted.servers).await.unwrap();
+            let written = serde_json::to_string(&applied.config).unwrap();
+            assert!(written.contains(r#""cwd":"/srv/notes""#), "{}: {}", adapter.get_name(), written);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_generic_adapter_uses_custom_servers_path() {
+        let mut profile = crate::detection::ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        profile.id = "custom-app".to_string();
+        profile.config_structure = crate::detection::ConfigStructure::Custom("/tools~1mcp/servers".to_string());
+        let adapter = AdapterFactory::create_adapter(&profile).unwrap();
+        assert_eq!(adapter.get_name(), "generic");
+
+        let config = serde_json::json!({
+            "tools/mcp": {"servers": {"notes": {"command": "uvx", "args": ["notes-mcp"]}}},
+            "mcpServers": {"other": {"command": "npx"}}
+        });
+        let extracted = adapter.extract_server_configs(&config).await.unwrap();
+        let names: Vec<&str> = extracted.servers.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["notes"]);
+
+        let applied = adapter.apply_server_configs(&serde_json::json!({}), &extracted.servers).await.unwrap();
+        assert_eq!(applied.config["tools/mcp"]["servers"]["notes"]["command"], "uvx");
+        assert!(applied.config.get("mcpServers").is_none());
+    }
+    #[tokio::test]
+    async fn test_validated_flat_settings_key_is_read_and_written_flat() {
+        let mut profile = crate::detection::ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        profile.id = "acme-editor".to_string();
+        profile.config_structure = crate::detection::ConfigStructure::Custom("acme.ai.servers".to_string());
+        let config = serde_json::json!({
+            "editor.fontSize": 14,
+            "acme.ai.servers": {"notes": {"command": "uvx", "args": ["notes-mcp"]}}
+        });
+        assert!(profile.validate_config_structure(&config).is_ok());
+
+        // A config that passes validation is read from, and written back to, the same key
+        let adapter = AdapterFactory::create_adapter(&profile).unwrap();
+        let extracted = adapter.extract_server_configs(&config).await.unwrap();
+        assert_eq!(extracted.servers.len(), 1);
+        let applied = adapter.apply_server_configs(&config, &extracted.servers).await.unwrap();
+        assert_eq!(applied.config["acme.ai.servers"]["notes"]["command"], "uvx");
+        assert!(applied.config.get("acme").is_none());
+        assert_eq!(applied.config["editor.fontSize"], 14);
+    }
+}
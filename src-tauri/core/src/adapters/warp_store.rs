@@ -0,0 +1,229 @@
+use anyhow::{Context, Result};
+use rusqlite::{Connection, OpenFlags, Row};
+use serde_json::{Map, Value as JsonValue};
+use std::path::{Path, PathBuf};
+
+use crate::detection::ApplicationProfile;
+use crate::filesystem::PathUtils;
+use super::{ApplicationAdapter, ExtractionResult};
+use super::warp::WarpAdapter;
+
+/// Locations of Warp's state database, macOS first
+pub const WARP_DATABASE_PATHS: &[&str] = &[
+    "~/Library/Group Containers/2BBY89MBSN.dev.warp/Library/Application Support/dev.warp.Warp-Stable/warp.sqlite",
+    "~/Library/Application Support/dev.warp.Warp-Stable/warp.sqlite",
+    "~/.local/state/warp-terminal/warp.sqlite",
+];
+
+/// Config format name used by profiles backed by a SQLite database
+pub const SQLITE_FORMAT: &str = "sqlite";
+
+/// Tables Warp keeps MCP server definitions in, newest schema first
+const SERVER_TABLES: &[&str] = &["mcp_servers", "mcp_server_installations"];
+
+/// Columns holding a whole server definition as JSON
+const JSON_COLUMNS: &[&str] = &["config", "server_config", "json"];
+
+/// Read-only view of the MCP servers stored in Warp's state database
+pub struct WarpStateStore {
+    path: PathBuf,
+    connection: Connection,
+}
+
+impl WarpStateStore {
+    /// Find the first Warp database that exists on this machine
+    pub fn locate() -> Option<PathBuf> {
+        WARP_DATABASE_PATHS
+            .iter()
+            .filter_map(|path| PathUtils::expand_tilde(path).ok())
+            .find(|path| path.is_file())
+    }
+
+    /// Open a database without taking a write lock, so a running Warp is unaffected
+    pub fn open(path: &Path) -> Result<Self> {
+        let connection = Connection::open_with_flags(
+            path,
+            OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+        )
+        .with_context(|| format!("Failed to open Warp database: {}", path.display()))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            connection,
+        })
+    }
+
+    /// Open the database a profile points at
+    pub fn open_for_profile(profile: &ApplicationProfile) -> Result<Self> {
+        let path = PathUtils::expand_tilde(&profile.config_path)?;
+        Self::open(&path)
+    }
+
+    /// Path of the opened database
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Server entries shaped like a Warp JSON config, so the Warp adapter can parse them
+    pub fn read_config(&self) -> Result<JsonValue> {
+        let mut servers = Map::new();
+
+        let Some(table) = self.server_table()? else {
+            return Ok(serde_json::json!({ "mcp": { "servers": servers } }));
+        };
+        let columns = self.columns(&table)?;
+        let json_column = JSON_COLUMNS.iter().find(|c| columns.iter().any(|col| col == *c));
+
+        let mut statement = self
+            .connection
+            .prepare(&format!("SELECT * FROM \"{}\"", table))
+            .with_context(|| format!("Failed to query Warp table {}", table))?;
+        let mut rows = statement.query([])?;
+
+        while let Some(row) = rows.next()? {
+            let name: Option<String> = column(row, &columns, "name");
+            let entry = match json_column {
+                Some(json_column) => column::<String>(row, &columns, json_column)
+                    .and_then(|json| serde_json::from_str::<JsonValue>(&json).ok()),
+                None => Some(entry_from_columns(row, &columns)),
+            };
+
+            match (name, entry) {
+                (Some(name), Some(entry)) => {
+                    servers.insert(name, entry);
+                }
+                // Some releases store `{"name": {...}}` without a separate name column
+                (None, Some(JsonValue::Object(named))) => servers.extend(named),
+                _ => log::warn!("Skipping unreadable MCP server row in {}", self.path.display()),
+            }
+        }
+
+        Ok(serde_json::json!({ "mcp": { "servers": servers } }))
+    }
+
+    /// Read the stored servers through the Warp adapter
+    pub async fn extract_servers(&self) -> Result<ExtractionResult> {
+        let config = self.read_config()?;
+        WarpAdapter::new().extract_server_configs(&config).await
+    }
+
+    fn server_table(&self) -> Result<Option<String>> {
+        for table in SERVER_TABLES {
+            let exists: bool = self.connection.query_row(
+                "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?1)",
+                [table],
+                |row| row.get(0),
+            )?;
+            if exists {
+                return Ok(Some(table.to_string()));
+            }
+        }
+        Ok(None)
+    }
+
+    fn columns(&self, table: &str) -> Result<Vec<String>> {
+        let mut statement = self.connection.prepare(&format!("PRAGMA table_info(\"{}\")", table))?;
+        let columns = statement
+            .query_map([], |row| row.get::<_, String>(1))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok(columns)
+    }
+}
+
+/// Whether a profile stores its servers in a SQLite database instead of a config file
+pub fn is_sqlite_backed(profile: &ApplicationProfile) -> bool {
+    matches!(&profile.config_format, crate::detection::ConfigFormat::Custom(format) if format == SQLITE_FORMAT)
+}
+
+fn column<T: rusqlite::types::FromSql>(row: &Row, columns: &[String], name: &str) -> Option<T> {
+    let index = columns.iter().position(|c| c == name)?;
+    row.get::<_, Option<T>>(index).ok().flatten()
+}
+
+/// Server entry assembled from separate command, args, and env columns
+fn entry_from_columns(row: &Row, columns: &[String]) -> JsonValue {
+    let mut entry = Map::new();
+
+    if let Some(command) = column::<String>(row, columns, "command") {
+        entry.insert("command".to_string(), JsonValue::String(command));
+    }
+    for key in ["args", "env"] {
+        if let Some(value) = column::<String>(row, columns, key).and_then(|v| serde_json::from_str(&v).ok()) {
+            entry.insert(key.to_string(), value);
+        }
+    }
+    if let Some(cwd) = column::<String>(row, columns, "working_directory") {
+        entry.insert("working_directory".to_string(), JsonValue::String(cwd));
+    }
+    if let Some(enabled) = column::<bool>(row, columns, "enabled") {
+        if !enabled {
+            entry.insert("disabled".to_string(), JsonValue::Bool(true));
+        }
+    }
+
+    JsonValue::Object(entry)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn fixture(temp_dir: &TempDir) -> PathBuf {
+        let path = temp_dir.path().join("warp.sqlite");
+        let connection = Connection::open(&path).unwrap();
+        connection
+            .execute_batch(
+                "CREATE TABLE mcp_servers (id INTEGER PRIMARY KEY, name TEXT, command TEXT, args TEXT, env TEXT, enabled INTEGER);
+                 INSERT INTO mcp_servers (name, command, args, env, enabled)
+                     VALUES ('github', 'npx', '[\"-y\", \"@modelcontextprotocol/server-github\"]', '{\"GITHUB_TOKEN\": \"abc\"}', 1);
+                 INSERT INTO mcp_servers (name, command, args, env, enabled)
+                     VALUES ('notes', 'uvx', '[\"notes-mcp\"]', NULL, 0);",
+            )
+            .unwrap();
+        path
+    }
+
+    #[tokio::test]
+    async fn test_reads_servers_from_fixture_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let store = WarpStateStore::open(&fixture(&temp_dir)).unwrap();
+
+        let result = store.extract_servers().await.unwrap();
+        assert_eq!(result.servers.len(), 2);
+
+        let github = result.servers.iter().find(|s| s.name == "github").unwrap();
+        assert_eq!(github.command.as_deref(), Some("npx"));
+        assert_eq!(github.args, vec!["-y", "@modelcontextprotocol/server-github"]);
+        assert_eq!(github.env.get("GITHUB_TOKEN").map(String::as_str), Some("abc"));
+        assert!(github.metadata.enabled);
+
+        let notes = result.servers.iter().find(|s| s.name == "notes").unwrap();
+        assert_eq!(notes.args, vec!["notes-mcp"]);
+        assert!(!notes.metadata.enabled);
+    }
+
+    #[test]
+    fn test_json_column_and_missing_table() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("warp.sqlite");
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE settings (key TEXT, value TEXT);",
+            )
+            .unwrap();
+        let empty = WarpStateStore::open(&path).unwrap().read_config().unwrap();
+        assert_eq!(empty["mcp"]["servers"], serde_json::json!({}));
+
+        Connection::open(&path)
+            .unwrap()
+            .execute_batch(
+                "CREATE TABLE mcp_server_installations (config TEXT);
+                 INSERT INTO mcp_server_installations VALUES ('{\"search\": {\"command\": \"search-mcp\"}}');",
+            )
+            .unwrap();
+        let config = WarpStateStore::open(&path).unwrap().read_config().unwrap();
+        assert_eq!(config["mcp"]["servers"]["search"]["command"], "search-mcp");
+    }
+}
@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use super::server_analyzer::DetectedConfig;
+
+/// Runtimes in the order we prefer them when several alternatives are ready.
+/// Alternatives without a runtime (native binaries) need nothing extra and rank first.
+const RUNTIME_PREFERENCE: &[&str] = &["node", "bun", "python"];
+
+/// Stable codes for the trade-offs of an alternative, translated into text by the UI
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MessageCode {
+    /// Downloads the package on first launch, so the first start needs network access
+    FetchesOnLaunch,
+    /// Runs from a local checkout that has to be installed and built first
+    RequiresLocalBuild,
+    /// Built for this OS and architecture only
+    PlatformSpecific,
+    /// Pinned to one release; updating means downloading again
+    PinnedRelease,
+    /// Installed from source, which may need build tools
+    BuildsFromSource,
+    /// Needs the Bun runtime, which is less commonly installed than Node
+    RequiresBun,
+}
+
+/// Command, arguments, and install step of one way to run a server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LaunchConfig {
+    pub command: String,
+    pub args: Vec<String>,
+    pub install_command: Option<String>,
+}
+
+/// One of several ways to run an analyzed server
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigAlternative {
+    /// Stable identifier, recorded so updates re-resolve the same alternative
+    pub id: String,
+    /// Short label shown to the user
+    pub label: String,
+    /// Runtime this alternative needs, if any
+    pub runtime: Option<String>,
+    pub config: LaunchConfig,
+    pub trade_offs: Vec<MessageCode>,
+}
+
+impl ConfigAlternative {
+    /// Create an alternative, inferring its runtime from the command
+    pub fn new(id: &str, label: &str, command: &str, args: Vec<String>) -> Self {
+        Self {
+            id: id.to_string(),
+            label: label.to_string(),
+            runtime: runtime_for_command(command).map(String::from),
+            config: LaunchConfig {
+                command: command.to_string(),
+                args,
+                install_command: None,
+            },
+            trade_offs: Vec::new(),
+        }
+    }
+
+    pub fn with_install_command(mut self, install_command: String) -> Self {
+        self.config.install_command = Some(install_command);
+        self
+    }
+
+    pub fn with_trade_off(mut self, code: MessageCode) -> Self {
+        self.trade_offs.push(code);
+        self
+    }
+}
+
+/// Runtime implied by a launcher command
+pub(crate) fn runtime_for_command(cmd: &str) -> Option<&'static str> {
+    match cmd {
+        "npx" | "node" | "npm" => Some("node"),
+        "bunx" | "bun" => Some("bun"),
+        "python" | "python3" => Some("python"),
+        "deno" => Some("deno"),
+        _ => None,
+    }
+}
+
+fn runtime_rank(alternative: &ConfigAlternative) -> usize {
+    match alternative.runtime.as_deref() {
+        None => 0,
+        Some(runtime) => RUNTIME_PREFERENCE
+            .iter()
+            .position(|r| *r == runtime)
+            .map(|i| i + 1)
+            .unwrap_or(RUNTIME_PREFERENCE.len() + 1),
+    }
+}
+
+/// Pick the primary alternative.
+///
+/// A pinned id wins when it is still offered. Otherwise ready alternatives beat unready
+/// ones, then the runtime preference applies, and remaining ties keep the analyzer's order.
+pub fn choose_alternative<'a, F>(
+    alternatives: &'a [ConfigAlternative],
+    pinned: Option<&str>,
+    is_ready: F,
+) -> Option<&'a ConfigAlternative>
+where
+    F: Fn(&ConfigAlternative) -> bool,
+{
+    if let Some(pinned) = alternatives.iter().find(|a| Some(a.id.as_str()) == pinned) {
+        return Some(pinned);
+    }
+
+    alternatives
+        .iter()
+        .enumerate()
+        .min_by_key(|(index, alternative)| (!is_ready(alternative), runtime_rank(alternative), *index))
+        .map(|(_, alternative)| alternative)
+}
+
+/// Whether an alternative's command can be launched on this machine.
+///
+/// Clients start servers without a shell, so a command written with `~` never runs as is.
+pub fn command_is_ready(alternative: &ConfigAlternative) -> bool {
+    let command = &alternative.config.command;
+    !command.starts_with('~') && which::which(command).is_ok()
+}
+
+/// Choose the primary alternative for this machine and apply it to the config
+pub fn select_primary(config: &mut DetectedConfig, pinned: Option<&str>) {
+    let chosen = choose_alternative(&config.alternatives, pinned, command_is_ready).map(|a| a.id.clone());
+    if let Some(id) = chosen {
+        config.apply_alternative(&id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn alternatives() -> Vec<ConfigAlternative> {
+        vec![
+            ConfigAlternative::new("npx", "Run with npx", "npx", vec!["-y".into(), "notes-mcp".into()])
+                .with_trade_off(MessageCode::FetchesOnLaunch),
+            ConfigAlternative::new("bun", "Run with Bun", "bun", vec!["run".into(), "src/index.ts".into()])
+                .with_trade_off(MessageCode::RequiresBun),
+            ConfigAlternative::new("release-binary", "Release binary", "~/.mcp-control/bin/notes", vec![])
+                .with_trade_off(MessageCode::PlatformSpecific),
+        ]
+    }
+
+    #[test]
+    fn test_chooser_prefers_ready_then_runtime() {
+        let alternatives = alternatives();
+
+        let all_ready = choose_alternative(&alternatives, None, |_| true).unwrap();
+        assert_eq!(all_ready.id, "release-binary");
+
+        let no_binary = choose_alternative(&alternatives, None, |a| a.runtime.is_some()).unwrap();
+        assert_eq!(no_binary.id, "npx");
+
+        let only_bun = choose_alternative(&alternatives, None, |a| a.id == "bun").unwrap();
+        assert_eq!(only_bun.id, "bun");
+    }
+
+    #[test]
+    fn test_chooser_is_deterministic_and_honors_pin() {
+        let alternatives = alternatives();
+        let first = choose_alternative(&alternatives, None, |_| false).map(|a| a.id.clone());
+        for _ in 0..10 {
+            assert_eq!(choose_alternative(&alternatives, None, |_| false).map(|a| a.id.clone()), first);
+        }
+
+        assert_eq!(choose_alternative(&alternatives, Some("bun"), |_| true).unwrap().id, "bun");
+        // A pin that is no longer offered falls back to the normal choice
+        assert_eq!(choose_alternative(&alternatives, Some("docker"), |_| true).unwrap().id, "release-binary");
+        assert!(choose_alternative(&[], None, |_| true).is_none());
+    }
+
+    #[test]
+    fn test_tilde_commands_are_not_ready() {
+        let home_binary = ConfigAlternative::new("home-sh", "Shell in home", "~/../../bin/sh", vec![]);
+        assert!(!command_is_ready(&home_binary));
+        assert!(!command_is_ready(&alternatives()[2]));
+    }
+}
@@ -0,0 +1,60 @@
+//! Blocking entry points to the analysis pipeline, for CLIs, doctests, and other callers
+//! without a Tokio runtime.
+//!
+//! Each wrapper drives the async version to completion on a private current-thread runtime,
+//! so parsing behaves exactly as in the app. They must not be called from inside a runtime.
+//! Package.json and README parsing need none of this: [`super::PackageParser::parse_package_json`]
+//! and [`super::ReadmeParser::parse_readme`] are already synchronous.
+
+use anyhow::{Context, Result};
+use std::future::Future;
+use std::path::Path;
+
+use super::server_analyzer::{AnalysisResult, ManifestAnalysis, ServerAnalyzer};
+
+impl ServerAnalyzer {
+    /// Blocking version of [`ServerAnalyzer::analyze_package`]
+    pub fn analyze_package_blocking(&self, package_name: &str) -> Result<AnalysisResult> {
+        block_on(self.analyze_package(package_name))?
+    }
+
+    /// Blocking version of [`ServerAnalyzer::analyze_manifest_url`]
+    pub fn analyze_manifest_url_blocking(&self, url: &str) -> Result<ManifestAnalysis> {
+        block_on(self.analyze_manifest_url(url))?
+    }
+
+    /// Blocking version of [`ServerAnalyzer::analyze_dropped_path`]
+    pub fn analyze_dropped_path_blocking(&self, path: &Path) -> Result<Vec<AnalysisResult>> {
+        block_on(self.analyze_dropped_path(path))?
+    }
+}
+
+fn block_on<F: Future>(future: F) -> Result<F::Output> {
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .context("Failed to start a runtime for blocking analysis")?;
+    Ok(runtime.block_on(future))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_analyze_local_path_blocking() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("package.json"),
+            r#"{ "name": "notes-mcp", "main": "dist/index.js" }"#,
+        ).unwrap();
+
+        let results = ServerAnalyzer::new()
+            .analyze_dropped_path_blocking(temp_dir.path())
+            .unwrap();
+
+        let result = &results[0];
+        assert_eq!(result.config.name, "notes-mcp");
+        assert_eq!(result.config.cwd.as_deref(), temp_dir.path().to_str());
+    }
+}
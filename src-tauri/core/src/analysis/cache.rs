@@ -0,0 +1,136 @@
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
+
+use super::package_parser::PackageParser;
+use super::server_analyzer::DetectedConfig;
+
+/// Analysis of a package pinned to the version that was analyzed
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalysisCacheEntry {
+    /// npm package name
+    pub package: String,
+    /// Version the analysis was run against
+    pub analyzed_version: String,
+    /// When the analysis was run
+    pub analyzed_at: DateTime<Utc>,
+    /// Detected configuration
+    pub config: DetectedConfig,
+}
+
+/// Freshness of a cached analysis compared to the registry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum CacheStatus {
+    /// The cached analysis matches the latest published version
+    Current,
+    /// A newer version has been published since the analysis ran
+    Stale { latest: String },
+}
+
+impl CacheStatus {
+    /// Whether the UI should show an "update available" badge
+    pub fn update_available(&self) -> bool {
+        matches!(self, CacheStatus::Stale { .. })
+    }
+}
+
+impl AnalysisCacheEntry {
+    /// Create an entry for an analysis that just ran
+    pub fn new(package: &str, analyzed_version: &str, config: DetectedConfig) -> Self {
+        Self {
+            package: package.to_string(),
+            analyzed_version: analyzed_version.to_string(),
+            analyzed_at: Utc::now(),
+            config,
+        }
+    }
+
+    /// Compare the analyzed version against the latest published one
+    pub fn status_against(&self, latest: &str) -> CacheStatus {
+        let stale = match (parse_version(&self.analyzed_version), parse_version(latest)) {
+            (Some(cached), Some(latest)) => latest.cmp(&cached) == Ordering::Greater,
+            // Fall back to a plain comparison for tags we cannot order
+            _ => self.analyzed_version != latest,
+        };
+
+        if stale {
+            CacheStatus::Stale { latest: latest.to_string() }
+        } else {
+            CacheStatus::Current
+        }
+    }
+
+    /// Check the registry for a newer version without re-running the analysis
+    pub async fn check_upstream(&self, parser: &PackageParser) -> Result<CacheStatus> {
+        let latest = parser.latest_version(&self.package).await?;
+        Ok(self.status_against(&latest))
+    }
+}
+
+/// Numeric release components of a version, ignoring any pre-release or build suffix
+fn parse_version(version: &str) -> Option<Vec<u64>> {
+    let release = version.trim_start_matches('v').split(['-', '+']).next()?;
+    release.split('.').map(|part| part.parse().ok()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    #[cfg(feature = "online")]
+    use std::io::{Read, Write};
+    #[cfg(feature = "online")]
+    use std::net::TcpListener;
+
+    fn entry(version: &str) -> AnalysisCacheEntry {
+        let config = serde_json::from_value(serde_json::json!({
+            "name": "notes-mcp",
+            "description": null,
+            "command": "npx",
+            "args": ["-y", "notes-mcp"],
+            "env": {},
+            "optional_args": [],
+            "server_type": "stdio",
+            "version": version,
+            "author": null,
+            "install_command": null
+        }))
+        .unwrap();
+        AnalysisCacheEntry::new("notes-mcp", version, config)
+    }
+
+    #[test]
+    fn test_status_against_latest() {
+        assert_eq!(entry("1.2.0").status_against("1.2.0"), CacheStatus::Current);
+        assert_eq!(entry("1.10.0").status_against("1.9.0"), CacheStatus::Current);
+        assert!(entry("1.2.0").status_against("1.10.0").update_available());
+        assert!(entry("canary").status_against("1.0.0").update_available());
+    }
+
+    #[cfg(feature = "online")]
+    #[tokio::test]
+    async fn test_newer_published_version_marks_cache_stale() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+        std::thread::spawn(move || {
+            if let Ok((mut stream, _)) = listener.accept() {
+                let body = r#"{"name": "notes-mcp", "dist-tags": {"latest": "1.3.0"}}"#;
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let _ = write!(
+                    stream,
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    body.len(),
+                    body
+                );
+            }
+        });
+
+        let status = entry("1.2.0")
+            .check_upstream(&PackageParser::with_registry(&base))
+            .await
+            .unwrap();
+
+        assert_eq!(status, CacheStatus::Stale { latest: "1.3.0".to_string() });
+    }
+}
@@ -0,0 +1,250 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use super::server_analyzer::DetectedConfig;
+use crate::detection::ApplicationProfile;
+
+/// Bundled protocol compatibility table
+const BUNDLED_TABLE: &str = include_str!("../../resources/protocol_compatibility.json");
+
+/// Mapping table between MCP protocol revisions, client version labels and SDK releases
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CompatibilityTable {
+    /// Known date-based protocol revisions, oldest first
+    pub protocol_revisions: Vec<String>,
+    /// Legacy client version labels mapped to protocol revisions (e.g. "1.0")
+    #[serde(default)]
+    pub client_aliases: HashMap<String, String>,
+    /// Minimum protocol revision required by SDK releases
+    #[serde(default)]
+    pub sdk_requirements: Vec<SdkRequirement>,
+}
+
+/// Protocol requirement of an SDK starting at a given release
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SdkRequirement {
+    pub package: String,
+    pub since: String,
+    pub min_protocol: String,
+}
+
+/// Result of a compatibility check
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum Compatibility {
+    Compatible,
+    Incompatible { reason: String },
+    /// Either side could not be parsed; we don't guess
+    Unknown { reason: String },
+}
+
+/// Minimum version of a particular client that a server documents, e.g. "requires Cursor >= 0.40"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ClientRequirement {
+    /// Id of the application profile the requirement applies to
+    pub app_id: String,
+    /// Client name as written in the documentation
+    pub client: String,
+    pub min_version: String,
+    /// The statement the requirement was read from
+    pub source: Option<String>,
+}
+
+impl ClientRequirement {
+    /// Whether an installed client version meets the requirement; None if either is unparseable
+    pub fn is_satisfied_by(&self, installed_version: &str) -> Option<bool> {
+        Some(parse_semver(installed_version)? >= parse_semver(&self.min_version)?)
+    }
+}
+
+/// Checks client protocol support against server protocol requirements
+pub struct CompatibilityChecker {
+    table: CompatibilityTable,
+}
+
+impl CompatibilityChecker {
+    /// Create a checker using the bundled mapping table
+    pub fn new() -> Self {
+        Self::from_json(BUNDLED_TABLE).expect("Bundled protocol compatibility table is invalid")
+    }
+
+    /// Create a checker from a mapping table in JSON form
+    pub fn from_json(content: &str) -> Result<Self> {
+        let table: CompatibilityTable = serde_json::from_str(content)
+            .context("Failed to parse protocol compatibility table")?;
+        Ok(Self { table })
+    }
+
+    /// Check a client's mcp_version against a server requirement.
+    ///
+    /// The requirement is either a protocol revision (`2025-03-26`) or an
+    /// SDK dependency (`@modelcontextprotocol/sdk@^1.12.0`).
+    pub fn check(&self, client_mcp_version: &str, server_requirement: &str) -> Compatibility {
+        let Some(client_revision) = self.client_revision(client_mcp_version) else {
+            return Compatibility::Unknown {
+                reason: format!("Unrecognized client protocol version '{}'", client_mcp_version),
+            };
+        };
+
+        let Some(required_revision) = self.required_revision(server_requirement) else {
+            return Compatibility::Unknown {
+                reason: format!("Unrecognized server protocol requirement '{}'", server_requirement),
+            };
+        };
+
+        if client_revision >= required_revision {
+            Compatibility::Compatible
+        } else {
+            Compatibility::Incompatible {
+                reason: format!(
+                    "Server requires protocol {} but client only supports {}",
+                    required_revision, client_revision
+                ),
+            }
+        }
+    }
+
+    /// Check whether an application can run a detected server
+    pub fn check_config(&self, profile: &ApplicationProfile, config: &DetectedConfig) -> Compatibility {
+        // A negotiated version from a live probe is more authoritative than the SDK dependency
+        let requirement = config
+            .probe_summary
+            .as_ref()
+            .and_then(|s| s.protocol_version.clone())
+            .or_else(|| config.protocol_requirement.clone());
+
+        match requirement {
+            Some(requirement) => self.check(&profile.metadata.mcp_version, &requirement),
+            None => Compatibility::Unknown {
+                reason: format!("No protocol requirement known for '{}'", config.name),
+            },
+        }
+    }
+
+    /// Check an application's installed version against the client versions a server documents
+    pub fn check_client_version(&self, profile: &ApplicationProfile, config: &DetectedConfig) -> Compatibility {
+        let Some(requirement) = config.client_requirements.iter().find(|r| r.app_id == profile.id) else {
+            return Compatibility::Compatible;
+        };
+        let Some(installed) = profile.metadata.version.as_deref() else {
+            return Compatibility::Unknown {
+                reason: format!("{} version unknown; '{}' requires {}", profile.name, config.name, requirement.min_version),
+            };
+        };
+
+        match requirement.is_satisfied_by(installed) {
+            Some(true) => Compatibility::Compatible,
+            Some(false) => Compatibility::Incompatible {
+                reason: format!(
+                    "'{}' requires {} {} or later but {} is installed",
+                    config.name, profile.name, requirement.min_version, installed
+                ),
+            },
+            None => Compatibility::Unknown {
+                reason: format!("Cannot compare {} version '{}' with '{}'", profile.name, installed, requirement.min_version),
+            },
+        }
+    }
+
+    /// Resolve a client version label to a protocol revision
+    fn client_revision(&self, version: &str) -> Option<String> {
+        let version = version.trim();
+        if let Some(alias) = self.table.client_aliases.get(version) {
+            return Some(alias.clone());
+        }
+        is_revision(version).then(|| version.to_string())
+    }
+
+    /// Resolve a server requirement to the minimum protocol revision it needs
+    fn required_revision(&self, requirement: &str) -> Option<String> {
+        let requirement = requirement.trim();
+        if is_revision(requirement) {
+            return Some(requirement.to_string());
+        }
+
+        // Split "package@range", keeping the leading @ of scoped packages
+        let split_at = requirement.rfind('@').filter(|&i| i > 0)?;
+        let (package, range) = (&requirement[..split_at], &requirement[split_at + 1..]);
+        let version = parse_semver(range)?;
+
+        self.table
+            .sdk_requirements
+            .iter()
+            .filter(|r| r.package == package)
+            .filter_map(|r| parse_semver(&r.since).map(|since| (since, &r.min_protocol)))
+            .filter(|(since, _)| *since <= version)
+            .max_by(|a, b| a.0.cmp(&b.0))
+            .map(|(_, protocol)| protocol.clone())
+    }
+}
+
+impl Default for CompatibilityChecker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Check for a date-based protocol revision (YYYY-MM-DD)
+fn is_revision(value: &str) -> bool {
+    chrono::NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok()
+}
+
+/// Parse the lower bound of a semver range like `^1.2.3`, `>=1.2` or `~0.6.0`
+pub(crate) fn parse_semver(range: &str) -> Option<(u64, u64, u64)> {
+    let version = range
+        .split_whitespace()
+        .next()?
+        .trim_start_matches(['^', '~', '>', '=', 'v']);
+    let core = version.split(['-', '+']).next()?;
+
+    let mut parts = core.split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().map(|p| p.parse().ok()).unwrap_or(Some(0))?;
+    let patch = parts.next().map(|p| p.parse().ok()).unwrap_or(Some(0))?;
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_TABLE: &str = r#"{
+        "protocol_revisions": ["2024-11-05", "2025-03-26"],
+        "client_aliases": { "1.0": "2024-11-05" },
+        "sdk_requirements": [
+            { "package": "@modelcontextprotocol/sdk", "since": "0.6.0", "min_protocol": "2024-11-05" },
+            { "package": "@modelcontextprotocol/sdk", "since": "2.0.0", "min_protocol": "2025-03-26" }
+        ]
+    }"#;
+
+    #[test]
+    fn test_compatible_pairing() {
+        let checker = CompatibilityChecker::from_json(TEST_TABLE).unwrap();
+        assert_eq!(
+            checker.check("1.0", "@modelcontextprotocol/sdk@^1.12.0"),
+            Compatibility::Compatible
+        );
+        assert_eq!(checker.check("2025-03-26", "2024-11-05"), Compatibility::Compatible);
+    }
+
+    #[test]
+    fn test_incompatible_pairing() {
+        let checker = CompatibilityChecker::from_json(TEST_TABLE).unwrap();
+        let result = checker.check("1.0", "@modelcontextprotocol/sdk@>=2.1.0");
+        assert!(matches!(result, Compatibility::Incompatible { .. }));
+    }
+
+    #[test]
+    fn test_unknown_when_unparseable() {
+        let checker = CompatibilityChecker::from_json(TEST_TABLE).unwrap();
+        assert!(matches!(checker.check("beta", "2024-11-05"), Compatibility::Unknown { .. }));
+        assert!(matches!(checker.check("1.0", "some-sdk@latest"), Compatibility::Unknown { .. }));
+        assert!(matches!(checker.check("1.0", "unknown-package@1.0.0"), Compatibility::Unknown { .. }));
+    }
+
+    #[test]
+    fn test_bundled_table_loads() {
+        let checker = CompatibilityChecker::new();
+        assert_eq!(checker.check("1.0", "mcp@1.9.4"), Compatibility::Compatible);
+    }
+}
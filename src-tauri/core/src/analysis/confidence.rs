@@ -0,0 +1,198 @@
+use serde::{Deserialize, Serialize};
+
+use super::server_analyzer::DetectedConfig;
+
+/// How many suggestions the UI shows under a low score
+const TOP_SUGGESTIONS: usize = 3;
+
+/// One input to the confidence score
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceFactor {
+    /// Stable identifier of the factor
+    pub id: String,
+    /// Short label shown to the user
+    pub label: String,
+    /// Share of the score this factor is worth
+    pub weight: f32,
+    /// Whether the analysis satisfied the factor
+    pub satisfied: bool,
+    /// What satisfied the factor, such as the README line a variable came from
+    pub provenance: Option<String>,
+    /// How to improve the score when the factor is not satisfied
+    pub suggestion: Option<String>,
+}
+
+/// Explanation of a confidence score, factor by factor
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConfidenceBreakdown {
+    pub factors: Vec<ConfidenceFactor>,
+}
+
+impl ConfidenceBreakdown {
+    /// Score a detected config and the messages the analysis produced
+    pub fn evaluate(config: &DetectedConfig, messages: &[String]) -> Self {
+        let parsed_message = messages.iter().find(|m| m.contains("Parsed"));
+        let first_env_location = {
+            let mut vars: Vec<_> = config.env.values().collect();
+            vars.sort_by(|a, b| a.name.cmp(&b.name));
+            vars.into_iter()
+                .find_map(|var| var.source_location.as_ref().map(|location| format!("{} ({})", var.name, location)))
+        };
+
+        let factors = vec![
+            factor(
+                "description",
+                "Description",
+                0.1,
+                config.description.as_ref().map(|_| "package or README description".to_string()),
+                "No description found; add one so the server is easy to recognize",
+            ),
+            factor(
+                "command",
+                "Launch command",
+                0.2,
+                (!config.command.is_empty()).then(|| match &config.selected_alternative {
+                    Some(id) => format!("{} (alternative '{}')", config.command, id),
+                    None => config.command.clone(),
+                }),
+                "No launch command found; provide the command manually",
+            ),
+            factor(
+                "args",
+                "Command arguments",
+                0.1,
+                (!config.args.is_empty()).then(|| config.args.join(" ")),
+                "README had no usage example; provide the command arguments manually",
+            ),
+            factor(
+                "env",
+                "Environment variables",
+                0.15,
+                (!config.env.is_empty())
+                    .then(|| first_env_location.unwrap_or_else(|| format!("{} variable(s)", config.env.len()))),
+                "No environment variables detected; check the server's docs for required API keys",
+            ),
+            factor(
+                "docs_url",
+                "Documentation link",
+                0.1,
+                config.docs_url.clone(),
+                "No documentation URL; add the repository or homepage link",
+            ),
+            factor(
+                "author",
+                "Author",
+                0.05,
+                config.author.clone(),
+                "Author unknown; check the publisher before installing",
+            ),
+            factor(
+                "metadata",
+                "Package metadata parsed",
+                0.3,
+                parsed_message.cloned(),
+                "No package metadata could be parsed; analyze the npm package or repository instead",
+            ),
+        ];
+
+        Self { factors }
+    }
+
+    /// Score between 0.0 and 1.0: satisfied weight over total weight
+    pub fn score(&self) -> f32 {
+        let total: f32 = self.factors.iter().map(|f| f.weight).sum();
+        let satisfied: f32 = self.factors.iter().filter(|f| f.satisfied).map(|f| f.weight).sum();
+
+        if total > 0.0 {
+            satisfied / total
+        } else {
+            0.0
+        }
+    }
+
+    /// Suggestions for the unsatisfied factors worth the most
+    pub fn top_suggestions(&self) -> Vec<&str> {
+        let mut unsatisfied: Vec<&ConfidenceFactor> = self.factors.iter().filter(|f| !f.satisfied).collect();
+        unsatisfied.sort_by(|a, b| b.weight.total_cmp(&a.weight));
+        unsatisfied
+            .into_iter()
+            .filter_map(|f| f.suggestion.as_deref())
+            .take(TOP_SUGGESTIONS)
+            .collect()
+    }
+}
+
+fn factor(id: &str, label: &str, weight: f32, provenance: Option<String>, suggestion: &str) -> ConfidenceFactor {
+    let satisfied = provenance.is_some();
+    ConfidenceFactor {
+        id: id.to_string(),
+        label: label.to_string(),
+        weight,
+        satisfied,
+        provenance,
+        suggestion: (!satisfied).then(|| suggestion.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::server_analyzer::EnvVarConfig;
+
+    fn config() -> DetectedConfig {
+        serde_json::from_value(serde_json::json!({
+            "name": "notes-mcp",
+            "description": "Notes",
+            "command": "npx",
+            "args": [],
+            "env": {},
+            "optional_args": [],
+            "server_type": "stdio",
+            "install_command": null,
+            "docs_url": null,
+            "author": null,
+            "version": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_breakdown_matches_score() {
+        let mut config = config();
+        config.env.insert("NOTES_TOKEN".to_string(), EnvVarConfig {
+            name: "NOTES_TOKEN".to_string(),
+            description: None,
+            required: true,
+            default: None,
+            example: None,
+            source_location: Some("code block line 12".to_string()),
+        });
+        let messages = vec!["Parsed package.json successfully".to_string()];
+
+        let breakdown = ConfidenceBreakdown::evaluate(&config, &messages);
+        let satisfied: f32 = breakdown.factors.iter().filter(|f| f.satisfied).map(|f| f.weight).sum();
+        let total: f32 = breakdown.factors.iter().map(|f| f.weight).sum();
+        assert!((breakdown.score() - satisfied / total).abs() < f32::EPSILON);
+        assert!((breakdown.score() - 0.75).abs() < 1e-6);
+
+        let env = breakdown.factors.iter().find(|f| f.id == "env").unwrap();
+        assert_eq!(env.provenance.as_deref(), Some("NOTES_TOKEN (code block line 12)"));
+    }
+
+    #[test]
+    fn test_suggestions_only_for_unsatisfied_factors() {
+        let breakdown = ConfidenceBreakdown::evaluate(&config(), &[]);
+
+        for factor in &breakdown.factors {
+            assert_eq!(factor.suggestion.is_some(), !factor.satisfied, "{}", factor.id);
+        }
+        assert_eq!(
+            breakdown.top_suggestions(),
+            vec![
+                "No package metadata could be parsed; analyze the npm package or repository instead",
+                "No environment variables detected; check the server's docs for required API keys",
+                "README had no usage example; provide the command arguments manually",
+            ]
+        );
+    }
+}
@@ -0,0 +1,317 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+/// Kind of package manifest recognized in a dropped file
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ManifestKind {
+    PackageJson,
+    PyProject,
+}
+
+impl ManifestKind {
+    /// Manifest file name inside a package directory
+    pub fn file_name(&self) -> &'static str {
+        match self {
+            ManifestKind::PackageJson => "package.json",
+            ManifestKind::PyProject => "pyproject.toml",
+        }
+    }
+}
+
+/// Which analyzer a dropped path should be routed to
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum DropRoute {
+    /// A package directory, analyzed like any local path
+    LocalPackageDir,
+    /// A package manifest; its containing directory is analyzed
+    ManifestFile { kind: ManifestKind },
+    /// A client config snippet with one or more server entries
+    SnippetFile,
+    /// A compiled server executable
+    Binary,
+    /// Nothing we know how to analyze
+    Unknown { reason: String },
+}
+
+/// Pre-filled analysis source for a dropped path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceSpec {
+    /// Path handed to the analyzer, which may differ from the dropped path
+    pub path: PathBuf,
+    /// Suggested server name
+    pub name: Option<String>,
+    /// Command to launch the server, when it is known up front
+    pub command: Option<String>,
+    /// Things the user should know before continuing
+    pub notes: Vec<String>,
+}
+
+/// Routing decision for a dropped path
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DropClassification {
+    pub route: DropRoute,
+    pub source: SourceSpec,
+}
+
+/// Leading bytes of executable formats: ELF, PE, Mach-O (32/64-bit, both byte orders) and fat binaries
+const BINARY_MAGIC: &[&[u8]] = &[
+    b"\x7fELF",
+    b"MZ",
+    &[0xfe, 0xed, 0xfa, 0xce],
+    &[0xfe, 0xed, 0xfa, 0xcf],
+    &[0xce, 0xfa, 0xed, 0xfe],
+    &[0xcf, 0xfa, 0xed, 0xfe],
+    &[0xca, 0xfe, 0xba, 0xbe],
+];
+
+/// Inspect a dropped file or folder and decide how to analyze it
+pub fn classify_dropped_path(path: &Path) -> DropClassification {
+    if path.is_dir() {
+        return match find_manifest(path) {
+            Some(_) => package_dir(path),
+            None => unknown(path, "Folder has no package.json or pyproject.toml"),
+        };
+    }
+
+    if !path.is_file() {
+        return unknown(path, "Path does not exist");
+    }
+
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+
+    if file_name == "pyproject.toml" {
+        return manifest_file(path, ManifestKind::PyProject, None);
+    }
+
+    if has_binary_magic(path) {
+        return binary(path);
+    }
+
+    if file_name.ends_with(".json") {
+        if let Some(json) = fs::read_to_string(path).ok().and_then(|c| serde_json::from_str(&c).ok()) {
+            if let Some(name) = snippet_server_name(&json) {
+                return snippet(path, name);
+            }
+            if file_name == "package.json" || looks_like_package_json(&json) {
+                let name = json.get("name").and_then(|n| n.as_str()).map(String::from);
+                return manifest_file(path, ManifestKind::PackageJson, name);
+            }
+        }
+    }
+
+    // Any other file inside a package stands in for the package itself
+    match path.ancestors().skip(1).find(|dir| find_manifest(dir).is_some()) {
+        Some(dir) => package_dir(dir),
+        None => unknown(path, "File is not a manifest, config snippet, or executable"),
+    }
+}
+
+/// Manifest present directly in a directory
+fn find_manifest(dir: &Path) -> Option<ManifestKind> {
+    [ManifestKind::PackageJson, ManifestKind::PyProject]
+        .into_iter()
+        .find(|kind| dir.join(kind.file_name()).is_file())
+}
+
+/// Name of the first server in a config snippet, if the JSON is one
+fn snippet_server_name(json: &serde_json::Value) -> Option<String> {
+    let servers = json
+        .get("mcpServers")
+        .or_else(|| json.get("servers"))
+        .or_else(|| json.get("mcp").and_then(|mcp| mcp.get("servers")))?
+        .as_object()?;
+
+    servers
+        .iter()
+        .find(|(_, entry)| entry.get("command").is_some() || entry.get("url").is_some())
+        .map(|(name, _)| name.clone())
+}
+
+fn looks_like_package_json(json: &serde_json::Value) -> bool {
+    json.get("name").is_some() && ["bin", "main", "dependencies"].iter().any(|key| json.get(key).is_some())
+}
+
+fn has_binary_magic(path: &Path) -> bool {
+    let mut header = [0u8; 4];
+    let read = fs::File::open(path)
+        .and_then(|mut file| file.read(&mut header))
+        .unwrap_or(0);
+
+    BINARY_MAGIC.iter().any(|magic| read >= magic.len() && header.starts_with(magic))
+}
+
+#[cfg(unix)]
+pub(crate) fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path).map(|m| m.permissions().mode() & 0o111 != 0).unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+fn file_stem(path: &Path) -> Option<String> {
+    path.file_stem().and_then(|n| n.to_str()).map(String::from)
+}
+
+fn package_dir(dir: &Path) -> DropClassification {
+    DropClassification {
+        route: DropRoute::LocalPackageDir,
+        source: SourceSpec {
+            path: dir.to_path_buf(),
+            name: dir.file_name().and_then(|n| n.to_str()).map(String::from),
+            command: None,
+            notes: Vec::new(),
+        },
+    }
+}
+
+fn manifest_file(path: &Path, kind: ManifestKind, name: Option<String>) -> DropClassification {
+    let dir = path.parent().unwrap_or(path);
+    DropClassification {
+        route: DropRoute::ManifestFile { kind },
+        source: SourceSpec {
+            path: dir.to_path_buf(),
+            name: name.or_else(|| dir.file_name().and_then(|n| n.to_str()).map(String::from)),
+            command: None,
+            notes: Vec::new(),
+        },
+    }
+}
+
+fn snippet(path: &Path, first_server: String) -> DropClassification {
+    DropClassification {
+        route: DropRoute::SnippetFile,
+        source: SourceSpec {
+            path: path.to_path_buf(),
+            name: Some(first_server),
+            command: None,
+            notes: Vec::new(),
+        },
+    }
+}
+
+fn binary(path: &Path) -> DropClassification {
+    let mut notes = Vec::new();
+    if !is_executable(path) {
+        notes.push(format!("{} is not executable; run `chmod +x` on it before starting the server", path.display()));
+    }
+
+    DropClassification {
+        route: DropRoute::Binary,
+        source: SourceSpec {
+            path: path.to_path_buf(),
+            name: file_stem(path),
+            command: Some(path.to_string_lossy().to_string()),
+            notes,
+        },
+    }
+}
+
+fn unknown(path: &Path, reason: &str) -> DropClassification {
+    DropClassification {
+        route: DropRoute::Unknown { reason: reason.to_string() },
+        source: SourceSpec {
+            path: path.to_path_buf(),
+            name: file_stem(path),
+            command: None,
+            notes: Vec::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn package(temp_dir: &TempDir) -> PathBuf {
+        let dir = temp_dir.path().join("weather-server");
+        fs::create_dir_all(dir.join("src")).unwrap();
+        fs::write(dir.join("package.json"), r#"{"name": "@acme/weather", "bin": "dist/index.js"}"#).unwrap();
+        fs::write(dir.join("src/index.ts"), "export {};").unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_package_dir_and_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = package(&temp_dir);
+
+        let folder = classify_dropped_path(&dir);
+        assert_eq!(folder.route, DropRoute::LocalPackageDir);
+        assert_eq!(folder.source.path, dir);
+
+        let manifest = classify_dropped_path(&dir.join("package.json"));
+        assert_eq!(manifest.route, DropRoute::ManifestFile { kind: ManifestKind::PackageJson });
+        assert_eq!(manifest.source.path, dir);
+        assert_eq!(manifest.source.name.as_deref(), Some("@acme/weather"));
+    }
+
+    #[test]
+    fn test_pyproject_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("pyproject.toml");
+        fs::write(&path, "[project]\nname = \"mcp-notes\"\n").unwrap();
+
+        let result = classify_dropped_path(&path);
+        assert_eq!(result.route, DropRoute::ManifestFile { kind: ManifestKind::PyProject });
+        assert_eq!(result.source.path, temp_dir.path());
+    }
+
+    #[test]
+    fn test_file_inside_package_routes_to_package() {
+        let temp_dir = TempDir::new().unwrap();
+        let dir = package(&temp_dir);
+
+        let result = classify_dropped_path(&dir.join("src/index.ts"));
+        assert_eq!(result.route, DropRoute::LocalPackageDir);
+        assert_eq!(result.source.path, dir);
+    }
+
+    #[test]
+    fn test_json_snippet_is_not_a_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("package.json");
+        fs::write(&path, r#"{"name": "shared", "mcpServers": {"memory": {"command": "npx", "args": ["-y", "@mcp/memory"]}}}"#).unwrap();
+
+        let result = classify_dropped_path(&path);
+        assert_eq!(result.route, DropRoute::SnippetFile);
+        assert_eq!(result.source.name.as_deref(), Some("memory"));
+        assert_eq!(result.source.path, path);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_binary_without_execute_permission() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("mcp-server");
+        fs::write(&path, b"\x7fELF\x02\x01\x01\x00").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        let result = classify_dropped_path(&path);
+        assert_eq!(result.route, DropRoute::Binary);
+        assert_eq!(result.source.command.as_deref(), path.to_str());
+        assert!(result.source.notes[0].contains("not executable"));
+
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(classify_dropped_path(&path).source.notes.is_empty());
+    }
+
+    #[test]
+    fn test_unknown_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let notes = temp_dir.path().join("notes.txt");
+        fs::write(&notes, "hello").unwrap();
+
+        assert!(matches!(classify_dropped_path(&notes).route, DropRoute::Unknown { .. }));
+        assert!(matches!(classify_dropped_path(temp_dir.path()).route, DropRoute::Unknown { .. }));
+        assert!(matches!(classify_dropped_path(&temp_dir.path().join("missing")).route, DropRoute::Unknown { .. }));
+    }
+}
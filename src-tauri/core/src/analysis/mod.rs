@@ -0,0 +1,25 @@
+pub mod server_analyzer;
+pub mod package_parser;
+pub mod pyproject_parser;
+pub mod readme_parser;
+pub mod schema_detector;
+pub mod compatibility;
+pub mod dropped_path;
+pub mod cache;
+pub mod alternatives;
+pub mod confidence;
+pub mod targets;
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+pub use server_analyzer::{ServerAnalyzer, AnalysisResult, ManifestAnalysis, SkippedManifestEntry, ArgInputKind, ConfigConflict, DetectedConfig, CapabilitySummary, ToolInfo, DEFAULT_IGNORED_ENV_VARS, INSTALL_LINK_SCHEME};
+pub use package_parser::{DenoSpecifier, PackageParser};
+pub use pyproject_parser::PyProjectParser;
+pub use readme_parser::ReadmeParser;
+pub use schema_detector::{flatten_transport, flatten_transports, SchemaDetector, ValidationReport};
+pub use compatibility::{ClientRequirement, CompatibilityChecker, Compatibility};
+pub use dropped_path::{classify_dropped_path, DropClassification, DropRoute, ManifestKind, SourceSpec};
+pub use cache::{AnalysisCacheEntry, CacheStatus};
+pub use alternatives::{choose_alternative, select_primary, ConfigAlternative, LaunchConfig, MessageCode};
+pub use confidence::{ConfidenceBreakdown, ConfidenceFactor};
+pub use targets::{recommend_detected_targets, recommend_targets, FitReason, TargetAction, TargetRecommendation};
@@ -0,0 +1,833 @@
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, HashSet};
+
+use super::alternatives::{ConfigAlternative, MessageCode};
+use super::readme_parser::resolve_relative_url;
+use super::server_analyzer::{default_ignored_env_vars, DetectedConfig, EnvVarConfig};
+use super::SchemaDetector;
+use crate::net;
+
+/// A package reference as written in Deno configs, such as `npm:@scope/pkg@1.2` or `jsr:@scope/pkg`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DenoSpecifier {
+    /// Package from the npm registry; the string is everything after `npm:`
+    Npm(String),
+    /// Package from JSR; the string is everything after `jsr:`
+    Jsr(String),
+}
+
+impl DenoSpecifier {
+    /// Parse a specifier, returning None for anything without an `npm:` or `jsr:` prefix
+    pub fn parse(spec: &str) -> Option<Self> {
+        let spec = spec.trim();
+        if let Some(package) = spec.strip_prefix("npm:").filter(|p| !p.is_empty()) {
+            return Some(DenoSpecifier::Npm(package.trim_start_matches('/').to_string()));
+        }
+        if let Some(package) = spec.strip_prefix("jsr:").filter(|p| !p.is_empty()) {
+            return Some(DenoSpecifier::Jsr(package.trim_start_matches('/').to_string()));
+        }
+        None
+    }
+
+    /// Package name without a version, as the registry looks it up
+    pub fn package_name(&self) -> &str {
+        let package = match self {
+            DenoSpecifier::Npm(package) | DenoSpecifier::Jsr(package) => package.as_str(),
+        };
+        // A leading @ belongs to the scope; a later one starts the version
+        match package.get(1..).and_then(|rest| rest.find('@')) {
+            Some(index) => &package[..index + 1],
+            None => package,
+        }
+    }
+
+    /// Arguments that run the package with Deno, keeping any pinned version
+    pub fn deno_args(&self) -> Vec<String> {
+        vec!["run".to_string(), "-A".to_string(), self.to_string()]
+    }
+}
+
+impl std::fmt::Display for DenoSpecifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DenoSpecifier::Npm(package) => write!(f, "npm:{}", package),
+            DenoSpecifier::Jsr(package) => write!(f, "jsr:{}", package),
+        }
+    }
+}
+
+/// Parser for package.json files
+pub struct PackageParser {
+    registry_base: String,
+    jsr_base: String,
+    /// Variables never reported as server env, such as `NODE_ENV`
+    ignored_env_vars: HashSet<String>,
+}
+
+impl PackageParser {
+    pub fn new() -> Self {
+        Self::with_registry("https://registry.npmjs.org")
+    }
+
+    /// Create a parser that fetches packages from a custom npm registry
+    pub fn with_registry(registry_base: &str) -> Self {
+        Self {
+            registry_base: registry_base.trim_end_matches('/').to_string(),
+            jsr_base: "https://jsr.io".to_string(),
+            ignored_env_vars: default_ignored_env_vars(),
+        }
+    }
+
+    /// Also ignore these variables, on top of `DEFAULT_IGNORED_ENV_VARS`
+    pub fn with_ignored_env_vars<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.ignored_env_vars.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Fetch JSR packages from a different host than jsr.io
+    pub fn with_jsr_registry(mut self, jsr_base: &str) -> Self {
+        self.jsr_base = jsr_base.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Fetch a JSR package's `meta.json`, which lists its scope, name and versions
+    pub async fn fetch_jsr_package(&self, package_name: &str) -> Result<String> {
+        if !package_name.starts_with('@') || !package_name.contains('/') {
+            return Err(anyhow::anyhow!("JSR packages are named @scope/name, got '{}'", package_name));
+        }
+        let url = format!("{}/{}/meta.json", self.jsr_base, package_name);
+
+        let response = net::get(&url, &[]).await?;
+        if !response.is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to fetch package from JSR: {}",
+                response.status_text
+            ));
+        }
+
+        Ok(response.body)
+    }
+
+    /// Parse a JSR `meta.json` into a config that runs the package with Deno
+    pub fn parse_jsr_meta(&self, content: &str) -> Result<DetectedConfig> {
+        let meta: JsonValue = serde_json::from_str(content)?;
+        let scope = meta.get("scope").and_then(|s| s.as_str()).context("JSR metadata has no scope")?;
+        let package = meta.get("name").and_then(|n| n.as_str()).context("JSR metadata has no name")?;
+        let name = format!("@{}/{}", scope, package);
+
+        let specifier = DenoSpecifier::Jsr(name.clone());
+        let args = specifier.deno_args();
+        let alternative = ConfigAlternative::new("deno", "Run with Deno", "deno", args.clone())
+            .with_trade_off(MessageCode::FetchesOnLaunch);
+
+        Ok(DetectedConfig {
+            description: None,
+            command: "deno".to_string(),
+            args: args.clone(),
+            env: HashMap::new(),
+            optional_args: Vec::new(),
+            server_type: "stdio".to_string(),
+            // Deno fetches and caches the package on first run
+            install_command: None,
+            docs_url: Some(format!("https://jsr.io/{}", name)),
+            author: Some(format!("@{}", scope)),
+            version: meta.get("latest").and_then(|v| v.as_str()).map(String::from),
+            declared_capabilities: Vec::new(),
+            command_candidates: vec![("deno".to_string(), args)],
+            probe_summary: None,
+            probed_tools: None,
+            protocol_requirement: None,
+            client_requirements: Vec::new(),
+            peer_dependencies: Vec::new(),
+            inherit_env: true,
+            cwd: None,
+            runtime: alternative.runtime.clone(),
+            selected_alternative: Some(alternative.id.clone()),
+            alternatives: vec![alternative],
+            url: None,
+            logo_url: None,
+            bin_name: None,
+            license: None,
+            last_published: None,
+            conflicts: Vec::new(),
+            health_url: None,
+            name,
+        })
+    }
+
+    /// Fetch package.json from npm registry
+    pub async fn fetch_npm_package(&self, package_name: &str) -> Result<String> {
+        let packument = self.fetch_npm_packument(package_name).await?;
+        Self::latest_manifest(&packument)
+    }
+
+    /// Fetch the full packument: every version's manifest plus the `time` map
+    pub async fn fetch_npm_packument(&self, package_name: &str) -> Result<JsonValue> {
+        let url = format!("{}/{}", self.registry_base, package_name);
+
+        let response = net::get(&url, &[]).await?;
+        if !response.is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to fetch package from npm: {}",
+                response.status_text
+            ));
+        }
+
+        response.json()
+    }
+
+    /// The package.json of the `latest` dist-tag in a packument
+    pub fn latest_manifest(packument: &JsonValue) -> Result<String> {
+        // Get the latest version
+        let latest_version = packument
+            .get("dist-tags")
+            .and_then(|t| t.get("latest"))
+            .and_then(|v| v.as_str())
+            .context("No latest version found")?;
+
+        // Get the package.json for the latest version
+        let package_json = packument
+            .get("versions")
+            .and_then(|v| v.get(latest_version))
+            .context("Version not found")?;
+
+        Ok(serde_json::to_string_pretty(package_json)?)
+    }
+
+    /// When `latest` was published, falling back to the packument's `time.modified`
+    pub fn last_published(packument: &JsonValue) -> Option<DateTime<Utc>> {
+        let time = packument.get("time")?;
+        let latest = packument.get("dist-tags").and_then(|t| t.get("latest")).and_then(|v| v.as_str());
+        latest
+            .and_then(|version| time.get(version))
+            .or_else(|| time.get("modified"))
+            .and_then(|t| t.as_str())
+            .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+            .map(|t| t.with_timezone(&Utc))
+    }
+
+    /// Fetch only the `latest` dist-tag of a package, without its version manifests
+    pub async fn latest_version(&self, name: &str) -> Result<String> {
+        // The abbreviated packument is much smaller than the full document
+        let url = format!("{}/{}", self.registry_base, name);
+
+        let response = net::get(&url, &[("Accept", "application/vnd.npm.install-v1+json")]).await?;
+        if !response.is_success() {
+            return Err(anyhow::anyhow!(
+                "Failed to fetch package from npm: {}",
+                response.status_text
+            ));
+        }
+
+        let npm_data: JsonValue = response.json()?;
+
+        npm_data
+            .get("dist-tags")
+            .and_then(|t| t.get("latest"))
+            .and_then(|v| v.as_str())
+            .map(String::from)
+            .context("No latest version found")
+    }
+
+    /// Fetch README from npm registry
+    pub async fn fetch_npm_readme(&self, package_name: &str) -> Result<String> {
+        let url = format!("{}/{}", self.registry_base, package_name);
+
+        let response = net::get(&url, &[]).await?;
+        if !response.is_success() {
+            return Err(anyhow::anyhow!("Failed to fetch package from npm"));
+        }
+
+        let npm_data: JsonValue = response.json()?;
+
+        npm_data
+            .get("readme")
+            .and_then(|r| r.as_str())
+            .map(|s| s.to_string())
+            .context("No README found in package")
+    }
+
+    /// Parse package.json content
+    pub fn parse_package_json(&self, content: &str) -> Result<DetectedConfig> {
+        let package: JsonValue = serde_json::from_str(content)?;
+
+        let name = package
+            .get("name")
+            .and_then(|n| n.as_str())
+            .unwrap_or("unknown")
+            .to_string();
+
+        let description = package
+            .get("description")
+            .and_then(|d| d.as_str())
+            .map(|s| s.to_string());
+
+        let version = package
+            .get("version")
+            .and_then(|v| v.as_str())
+            .map(|s| s.to_string());
+
+        let author = self.extract_author(&package);
+
+        // Determine command and args, keeping the alternatives for the UI
+        let bin_name = extract_bin_name(&package, &name);
+        let command_candidates = self.determine_command_candidates(&package, &name, bin_name.as_deref());
+        let (command, args) = command_candidates[0].clone();
+        let alternatives: Vec<ConfigAlternative> = command_candidates
+            .iter()
+            .map(|(command, args)| candidate_alternative(command, args))
+            .collect();
+        let runtime = alternatives[0].runtime.clone();
+        let selected_alternative = Some(alternatives[0].id.clone());
+
+        // Extract environment variables from various sources
+        let env = self.extract_env_vars(&package);
+
+        // Get repository URL for docs
+        let docs_url = self.extract_docs_url(&package);
+        let logo_url = self.extract_logo_url(&package);
+
+        Ok(DetectedConfig {
+            name,
+            description,
+            command,
+            args,
+            env,
+            optional_args: Vec::new(),
+            server_type: "stdio".to_string(),
+            install_command: Some(format!("npm install {}",
+                package.get("name").and_then(|n| n.as_str()).unwrap_or("unknown")
+            )),
+            docs_url,
+            author,
+            version,
+            declared_capabilities: self.extract_declared_capabilities(&package),
+            command_candidates,
+            probe_summary: None,
+            probed_tools: None,
+            protocol_requirement: self.extract_sdk_dependency(&package),
+            client_requirements: Vec::new(),
+            peer_dependencies: self.extract_peer_dependencies(&package),
+            inherit_env: self.extract_inherit_env(&package),
+            cwd: None,
+            runtime,
+            alternatives,
+            selected_alternative,
+            url: None,
+            logo_url,
+            bin_name,
+            license: self.extract_license(&package),
+            last_published: None,
+            conflicts: Vec::new(),
+            health_url: None,
+        })
+    }
+
+    /// Extract author from package.json
+    fn extract_author(&self, package: &JsonValue) -> Option<String> {
+        if let Some(author) = package.get("author") {
+            if let Some(name) = author.as_str() {
+                return Some(name.to_string());
+            } else if let Some(name) = author.get("name").and_then(|n| n.as_str()) {
+                return Some(name.to_string());
+            }
+        }
+        None
+    }
+
+    /// Determine viable commands and arguments from package.json, most reliable first
+    fn determine_command_candidates(
+        &self,
+        package: &JsonValue,
+        package_name: &str,
+        bin_name: Option<&str>,
+    ) -> Vec<(String, Vec<String>)> {
+        let mut candidates: Vec<(String, Vec<String>)> = Vec::new();
+
+        // Published executables are the most reliable way to launch a package
+        let has_bin = match package.get("bin") {
+            Some(bin) => bin.is_string() || bin.as_object().map(|o| !o.is_empty()).unwrap_or(false),
+            None => false,
+        };
+        if has_bin {
+            // npx only guesses the executable from the package name, so name it when they differ
+            let args = match bin_name.filter(|bin| *bin != unscoped_name(package_name)) {
+                Some(bin) => vec!["-y".to_string(), "--package".to_string(), package_name.to_string(), bin.to_string()],
+                None => vec!["-y".to_string(), package_name.to_string()],
+            };
+            candidates.push(("npx".to_string(), args));
+        }
+
+        // Check for main field
+        if let Some(main) = package.get("main").and_then(|m| m.as_str()) {
+            if main.ends_with(".js") || main.ends_with(".mjs") || main.ends_with(".cjs") {
+                candidates.push(("node".to_string(), vec![main.to_string()]));
+            }
+        }
+
+        // TypeScript entry points or an explicit bun engine can run directly under bun
+        let entry = package
+            .get("module")
+            .or_else(|| package.get("main"))
+            .and_then(|m| m.as_str());
+        let wants_bun = package.get("engines").and_then(|e| e.get("bun")).is_some();
+        if let Some(entry) = entry {
+            if entry.ends_with(".ts") || wants_bun {
+                candidates.push(("bun".to_string(), vec!["run".to_string(), entry.to_string()]));
+            }
+        }
+
+        // Check scripts for start or mcp
+        if let Some(scripts) = package.get("scripts").and_then(|s| s.as_object()) {
+            if scripts.contains_key("mcp") {
+                candidates.push(("npm".to_string(), vec!["run".to_string(), "mcp".to_string()]));
+            }
+            if scripts.contains_key("start") {
+                candidates.push(("npm".to_string(), vec!["start".to_string()]));
+            }
+        }
+
+        // Default to npx
+        if candidates.is_empty() {
+            candidates.push(("npx".to_string(), vec!["-y".to_string(), package_name.to_string()]));
+        }
+
+        candidates
+    }
+
+    /// Extract environment variables from package.json
+    fn extract_env_vars(&self, package: &JsonValue) -> HashMap<String, EnvVarConfig> {
+        let mut env_vars = HashMap::new();
+
+        // Look for mcp configuration
+        if let Some(mcp_config) = package.get("mcp").and_then(|m| m.as_object()) {
+            if let Some(env) = mcp_config.get("env").and_then(|e| e.as_object()) {
+                for (key, value) in env {
+                    let config = if let Some(obj) = value.as_object() {
+                        EnvVarConfig {
+                            name: key.clone(),
+                            description: obj.get("description")
+                                .and_then(|d| d.as_str())
+                                .map(|s| s.to_string()),
+                            required: obj.get("required")
+                                .and_then(|r| r.as_bool())
+                                .unwrap_or(false),
+                            default: obj.get("default")
+                                .and_then(|d| d.as_str())
+                                .map(|s| s.to_string()),
+                            example: obj.get("example")
+                                .and_then(|e| e.as_str())
+                                .map(|s| s.to_string()),
+                            source_location: None,
+                        }
+                    } else {
+                        EnvVarConfig {
+                            name: key.clone(),
+                            description: None,
+                            required: false,
+                            default: value.as_str().map(|s| s.to_string()),
+                            example: None,
+                            source_location: None,
+                        }
+                    };
+                    env_vars.insert(key.clone(), config);
+                }
+            }
+        }
+
+        // Look for configuration in keywords or description
+        if let Some(keywords) = package.get("keywords").and_then(|k| k.as_array()) {
+            for keyword in keywords {
+                if let Some(kw) = keyword.as_str() {
+                    if kw.to_uppercase().ends_with("_KEY") || kw.to_uppercase().ends_with("_TOKEN") {
+                        env_vars.entry(kw.to_uppercase()).or_insert(EnvVarConfig {
+                            name: kw.to_uppercase(),
+                            description: Some(format!("{} (detected from keywords)", kw)),
+                            required: false,
+                            default: None,
+                            example: None,
+                            source_location: None,
+                        });
+                    }
+                }
+            }
+        }
+
+        env_vars.retain(|name, _| !self.ignored_env_vars.contains(name));
+        env_vars
+    }
+
+    /// Extract capabilities declared in the package.json `mcp` block
+    fn extract_declared_capabilities(&self, package: &JsonValue) -> Vec<String> {
+        package
+            .get("mcp")
+            .map(|mcp| SchemaDetector::new().extract_capabilities(mcp))
+            .unwrap_or_default()
+    }
+
+    /// Read the environment inheritance flag from the `mcp` block, defaulting to true
+    fn extract_inherit_env(&self, package: &JsonValue) -> bool {
+        package
+            .get("mcp")
+            .and_then(|mcp| mcp.get("inheritEnv").or_else(|| mcp.get("inherit_env")))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(true)
+    }
+
+    /// Extract the MCP SDK dependency as `package@range`
+    fn extract_sdk_dependency(&self, package: &JsonValue) -> Option<String> {
+        const SDK_PACKAGES: &[&str] = &["@modelcontextprotocol/sdk", "fastmcp", "mcp"];
+
+        ["dependencies", "peerDependencies"]
+            .iter()
+            .filter_map(|key| package.get(*key).and_then(|d| d.as_object()))
+            .find_map(|deps| {
+                SDK_PACKAGES.iter().find_map(|sdk| {
+                    deps.get(*sdk)
+                        .and_then(|range| range.as_str())
+                        .map(|range| format!("{}@{}", sdk, range))
+                })
+            })
+    }
+
+    /// Extract `peerDependencies` as `name@range`, sorted by name
+    fn extract_peer_dependencies(&self, package: &JsonValue) -> Vec<String> {
+        let mut peers: Vec<String> = package
+            .get("peerDependencies")
+            .and_then(|d| d.as_object())
+            .map(|deps| {
+                deps.iter()
+                    .map(|(name, range)| match range.as_str() {
+                        Some(range) => format!("{}@{}", name, range),
+                        None => name.clone(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        peers.sort();
+        peers
+    }
+
+    /// Raw file URL of the package's GitHub repository (including a monorepo `directory`),
+    /// against which relative image paths in its package.json and README resolve
+    pub fn repository_raw_url(&self, content: &str) -> Option<String> {
+        let package: JsonValue = serde_json::from_str(content).ok()?;
+        self.raw_base_url(&package)
+    }
+
+    fn raw_base_url(&self, package: &JsonValue) -> Option<String> {
+        let repository = package.get("repository")?;
+        let url = repository.as_str().or_else(|| repository.get("url")?.as_str())?;
+
+        let slug = url
+            .trim_start_matches("git+")
+            .trim_end_matches('/')
+            .trim_end_matches(".git");
+        let slug = ["github:", "https://github.com/", "http://github.com/", "ssh://git@github.com/", "git@github.com:", "git://github.com/"]
+            .iter()
+            .find_map(|prefix| slug.strip_prefix(prefix))
+            .or_else(|| (!slug.contains(':') && slug.matches('/').count() == 1).then_some(slug))?;
+
+        let mut base = format!("https://raw.githubusercontent.com/{}/HEAD", slug);
+        if let Some(directory) = repository.get("directory").and_then(|d| d.as_str()) {
+            base.push('/');
+            base.push_str(directory.trim_matches('/'));
+        }
+        Some(base)
+    }
+
+    /// Extract the license, from either the SPDX string or the legacy `{ "type": .. }` object
+    fn extract_license(&self, package: &JsonValue) -> Option<String> {
+        let license = package.get("license")?;
+        license
+            .as_str()
+            .or_else(|| license.get("type").and_then(|t| t.as_str()))
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+    }
+
+    /// Extract the `icon` field as an absolute URL; a relative icon without a GitHub repository is dropped
+    fn extract_logo_url(&self, package: &JsonValue) -> Option<String> {
+        let icon = package.get("icon")?.as_str()?.trim();
+        if icon.is_empty() {
+            return None;
+        }
+
+        let is_absolute = icon.starts_with("http://") || icon.starts_with("https://") || icon.starts_with("//");
+        let raw_base = if is_absolute { String::new() } else { self.raw_base_url(package)? };
+        Some(resolve_relative_url(icon, &raw_base))
+    }
+
+    /// Extract documentation URL
+    fn extract_docs_url(&self, package: &JsonValue) -> Option<String> {
+        // Try homepage first
+        if let Some(homepage) = package.get("homepage").and_then(|h| h.as_str()) {
+            return Some(homepage.to_string());
+        }
+
+        // Try repository
+        if let Some(repository) = package.get("repository") {
+            if let Some(url) = repository.as_str() {
+                return Some(url.to_string());
+            } else if let Some(url) = repository.get("url").and_then(|u| u.as_str()) {
+                // Clean up git+https URLs
+                let clean_url = url
+                    .trim_start_matches("git+")
+                    .trim_end_matches(".git");
+                return Some(clean_url.to_string());
+            }
+        }
+
+        None
+    }
+}
+
+/// Package name without its `@scope/` prefix, which is what npm names a lone `bin` after
+fn unscoped_name(package_name: &str) -> &str {
+    package_name.rsplit('/').next().unwrap_or(package_name)
+}
+
+/// Executable the package installs; with several, the one named after the package or mentioning MCP
+fn extract_bin_name(package: &JsonValue, package_name: &str) -> Option<String> {
+    match package.get("bin")? {
+        JsonValue::String(_) => Some(unscoped_name(package_name).to_string()),
+        JsonValue::Object(bins) => {
+            let names: Vec<&String> = bins.keys().collect();
+            names
+                .iter()
+                .find(|name| name.as_str() == unscoped_name(package_name))
+                .or_else(|| names.iter().find(|name| name.contains("mcp")))
+                .or_else(|| names.first())
+                .map(|name| name.to_string())
+        }
+        _ => None,
+    }
+}
+
+/// Describe a launch candidate as an alternative with its trade-offs
+fn candidate_alternative(command: &str, args: &[String]) -> ConfigAlternative {
+    let (id, label) = match (command, args.first().map(String::as_str)) {
+        ("npx", _) => ("npx", "Run the published package with npx"),
+        ("node", _) => ("node", "Run the entry point with Node"),
+        ("bun", _) => ("bun", "Run the entry point with Bun"),
+        ("npm", Some("start")) => ("npm-start", "Run the start script"),
+        _ => ("npm-run-mcp", "Run the mcp script"),
+    };
+
+    let alternative = ConfigAlternative::new(id, label, command, args.to_vec());
+    match id {
+        "npx" => alternative.with_trade_off(MessageCode::FetchesOnLaunch),
+        "bun" => alternative
+            .with_trade_off(MessageCode::RequiresBun)
+            .with_trade_off(MessageCode::RequiresLocalBuild),
+        _ => alternative.with_trade_off(MessageCode::RequiresLocalBuild),
+    }
+}
+
+impl Default for PackageParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_declared_capabilities_from_mcp_block() {
+        let package = serde_json::json!({
+            "name": "@acme/notes-mcp",
+            "bin": { "notes-mcp": "dist/index.js" },
+            "mcp": {
+                "capabilities": ["tools", "resources"],
+                "env": { "NOTES_DIR": { "required": true } }
+            }
+        });
+
+        let config = PackageParser::new()
+            .parse_package_json(&package.to_string())
+            .unwrap();
+
+        assert_eq!(config.declared_capabilities, vec!["tools", "resources"]);
+        assert!(config.env["NOTES_DIR"].required);
+    }
+
+    #[test]
+    fn test_sdk_dependency_recorded_as_protocol_requirement() {
+        let package = serde_json::json!({
+            "name": "@acme/notes-mcp",
+            "dependencies": { "@modelcontextprotocol/sdk": "^1.12.0", "zod": "^3.0.0" }
+        });
+
+        let config = PackageParser::new()
+            .parse_package_json(&package.to_string())
+            .unwrap();
+
+        assert_eq!(config.protocol_requirement.as_deref(), Some("@modelcontextprotocol/sdk@^1.12.0"));
+    }
+
+    #[test]
+    fn test_mismatched_bin_name_uses_package_flag() {
+        let package = serde_json::json!({
+            "name": "@foo/mcp-server",
+            "bin": { "foo-mcp": "dist/cli.js" }
+        });
+
+        let config = PackageParser::new()
+            .parse_package_json(&package.to_string())
+            .unwrap();
+
+        assert_eq!(config.bin_name.as_deref(), Some("foo-mcp"));
+        assert_eq!(config.command, "npx");
+        assert_eq!(config.args, vec!["-y", "--package", "@foo/mcp-server", "foo-mcp"]);
+    }
+
+    #[test]
+    fn test_summary_line_with_env_vars_and_license() {
+        let package = serde_json::json!({
+            "name": "@acme/notes-mcp",
+            "bin": { "notes-mcp": "dist/index.js" },
+            "license": "MIT",
+            "mcp": {
+                "env": { "NOTES_DIR": { "required": true }, "NOTES_TOKEN": { "required": false } }
+            }
+        });
+
+        let mut config = PackageParser::new()
+            .parse_package_json(&package.to_string())
+            .unwrap();
+        assert_eq!(config.summary_line(), "Node · stdio · 2 env vars · MIT");
+
+        config.license = None;
+        config.env.clear();
+        assert_eq!(config.summary_line(), "Node · stdio");
+    }
+
+    #[test]
+    fn test_last_published_from_packument_time() {
+        let packument = serde_json::json!({
+            "dist-tags": { "latest": "1.2.0" },
+            "versions": { "1.2.0": { "name": "notes-mcp", "version": "1.2.0" } },
+            "time": {
+                "created": "2024-01-02T10:00:00.000Z",
+                "modified": "2025-03-09T08:30:00.000Z",
+                "1.1.0": "2024-11-20T12:00:00.000Z",
+                "1.2.0": "2025-03-01T16:45:12.345Z"
+            }
+        });
+
+        let published = PackageParser::last_published(&packument).unwrap();
+        assert_eq!(published.to_rfc3339_opts(chrono::SecondsFormat::Millis, true), "2025-03-01T16:45:12.345Z");
+        assert!(PackageParser::latest_manifest(&packument).unwrap().contains("\"1.2.0\""));
+
+        // Without a publish time for latest, the last modification stands in
+        let mut modified_only = packument.clone();
+        modified_only["time"].as_object_mut().unwrap().remove("1.2.0");
+        assert_eq!(
+            PackageParser::last_published(&modified_only).unwrap().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            "2025-03-09T08:30:00Z"
+        );
+        assert!(PackageParser::last_published(&serde_json::json!({})).is_none());
+    }
+
+    #[test]
+    fn test_bin_and_main_yield_ranked_candidates() {
+        let package = serde_json::json!({
+            "name": "@acme/weather-mcp",
+            "bin": { "weather-mcp": "dist/index.js" },
+            "main": "dist/index.js",
+            "scripts": { "start": "node dist/index.js" }
+        });
+
+        let config = PackageParser::new()
+            .parse_package_json(&package.to_string())
+            .unwrap();
+
+        assert!(config.command_candidates.len() >= 2);
+        assert_eq!(config.command_candidates[0], ("npx".to_string(), vec!["-y".to_string(), "@acme/weather-mcp".to_string()]));
+        assert_eq!(config.command_candidates[1], ("node".to_string(), vec!["dist/index.js".to_string()]));
+        assert_eq!(config.command, "npx");
+        assert_eq!(config.args, config.command_candidates[0].1);
+
+        let ids: Vec<&str> = config.alternatives.iter().map(|a| a.id.as_str()).collect();
+        assert_eq!(ids, vec!["npx", "node", "npm-start"]);
+        for (alternative, (command, args)) in config.alternatives.iter().zip(&config.command_candidates) {
+            assert_eq!((&alternative.config.command, &alternative.config.args), (command, args));
+        }
+        assert_eq!(config.selected_alternative.as_deref(), Some("npx"));
+        assert_eq!(config.runtime.as_deref(), Some("node"));
+    }
+
+    #[test]
+    fn test_no_mcp_block_declares_nothing() {
+        let config = PackageParser::new()
+            .parse_package_json(r#"{"name": "plain-server", "main": "index.js"}"#)
+            .unwrap();
+
+        assert!(config.declared_capabilities.is_empty());
+    }
+
+    #[test]
+    fn test_deno_specifiers() {
+        let npm = DenoSpecifier::parse("npm:@modelcontextprotocol/server-memory@0.6.2").unwrap();
+        assert_eq!(npm, DenoSpecifier::Npm("@modelcontextprotocol/server-memory@0.6.2".to_string()));
+        assert_eq!(npm.package_name(), "@modelcontextprotocol/server-memory");
+        assert_eq!(npm.deno_args(), vec!["run", "-A", "npm:@modelcontextprotocol/server-memory@0.6.2"]);
+
+        let jsr = DenoSpecifier::parse("jsr:@acme/weather-mcp").unwrap();
+        assert_eq!(jsr.package_name(), "@acme/weather-mcp");
+        assert_eq!(DenoSpecifier::parse("npm:weather-mcp@2").unwrap().package_name(), "weather-mcp");
+
+        assert!(DenoSpecifier::parse("@acme/weather-mcp").is_none());
+        assert!(DenoSpecifier::parse("npm:").is_none());
+    }
+
+    #[test]
+    fn test_logo_from_icon_field() {
+        let parser = PackageParser::new();
+        let package = r#"{
+            "name": "@acme/weather-mcp",
+            "icon": "./assets/logo.png",
+            "repository": {"type": "git", "url": "git+https://github.com/acme/mcp-servers.git", "directory": "packages/weather"}
+        }"#;
+
+        let config = parser.parse_package_json(package).unwrap();
+        assert_eq!(
+            config.logo_url.as_deref(),
+            Some("https://raw.githubusercontent.com/acme/mcp-servers/HEAD/packages/weather/assets/logo.png")
+        );
+
+        let absolute = r#"{"name": "weather-mcp", "icon": "https://acme.dev/logo.svg"}"#;
+        assert_eq!(parser.parse_package_json(absolute).unwrap().logo_url.as_deref(), Some("https://acme.dev/logo.svg"));
+
+        // Nowhere to resolve a relative icon against
+        let unresolvable = r#"{"name": "weather-mcp", "icon": "logo.png"}"#;
+        assert!(parser.parse_package_json(unresolvable).unwrap().logo_url.is_none());
+    }
+
+    #[test]
+    fn test_peer_dependencies_warn_about_companions() {
+        let package = serde_json::json!({
+            "name": "@acme/browser-mcp",
+            "bin": {"browser-mcp": "dist/index.js"},
+            "dependencies": {"@modelcontextprotocol/sdk": "^1.0.0"},
+            "peerDependencies": {"playwright": ">=1.40", "@acme/auth": "^2.1.0"}
+        });
+
+        let config = PackageParser::new()
+            .parse_package_json(&package.to_string())
+            .unwrap();
+
+        assert_eq!(config.peer_dependencies, vec!["@acme/auth@^2.1.0", "playwright@>=1.40"]);
+        let warning = config.peer_dependency_warning().unwrap();
+        assert!(warning.contains("playwright@>=1.40"));
+    }
+}
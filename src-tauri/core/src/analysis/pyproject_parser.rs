@@ -0,0 +1,228 @@
+use anyhow::{Context, Result};
+use toml::Value as TomlValue;
+
+use super::alternatives::{ConfigAlternative, MessageCode};
+use super::server_analyzer::{DetectedConfig, ServerAnalyzer};
+
+/// Parser for pyproject.toml files, in the PEP 621 `[project]` or Poetry `[tool.poetry]` layout
+pub struct PyProjectParser;
+
+impl PyProjectParser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Parse pyproject.toml content into configuration
+    ///
+    /// A console script entry point runs through `uvx <name>`, or `uvx --from <name> <script>`
+    /// when the script is named differently; without one the package runs as `python -m <module>`.
+    pub fn parse_pyproject(&self, content: &str) -> Result<DetectedConfig> {
+        let pyproject: TomlValue = content.parse().context("Invalid pyproject.toml")?;
+
+        // PEP 621 metadata wins; Poetry projects that adopted [project] keep [tool.poetry] for build settings
+        let project = pyproject
+            .get("project")
+            .filter(|p| p.get("name").is_some())
+            .map(|p| (p, Layout::Pep621))
+            .or_else(|| {
+                pyproject
+                    .get("tool")
+                    .and_then(|t| t.get("poetry"))
+                    .map(|p| (p, Layout::Poetry))
+            });
+        let Some((project, layout)) = project else {
+            return Err(anyhow::anyhow!("pyproject.toml has neither a [project] nor a [tool.poetry] table"));
+        };
+
+        let name = string_field(project, "name").unwrap_or_else(|| "unknown".to_string());
+        let script = self.select_script(project, &name);
+
+        let (command, args, alternative) = match &script {
+            Some(script) => {
+                let args = if normalize_name(script) == normalize_name(&name) {
+                    vec![name.clone()]
+                } else {
+                    vec!["--from".to_string(), name.clone(), script.clone()]
+                };
+                let alternative = ConfigAlternative::new("uvx", "Run the published package with uvx", "uvx", args.clone())
+                    .with_trade_off(MessageCode::FetchesOnLaunch);
+                ("uvx", args, alternative)
+            }
+            None => {
+                let args = vec!["-m".to_string(), name.replace('-', "_").to_lowercase()];
+                let alternative = ConfigAlternative::new("python-module", "Run the module with Python", "python", args.clone())
+                    .with_trade_off(MessageCode::RequiresLocalBuild);
+                ("python", args, alternative)
+            }
+        };
+
+        let mut config = ServerAnalyzer::basic_config(&name, command);
+        config.args = args.clone();
+        config.description = string_field(project, "description");
+        config.version = string_field(project, "version");
+        config.author = self.extract_authors(project, layout);
+        config.docs_url = self.extract_docs_url(project, layout);
+        config.license = self.extract_license(project);
+        config.install_command = Some(match script {
+            Some(_) => format!("uv tool install {}", name),
+            None => format!("pip install {}", name),
+        });
+        config.bin_name = script;
+        config.command_candidates = vec![(command.to_string(), args)];
+        config.runtime = Some("python".to_string());
+        config.selected_alternative = Some(alternative.id.clone());
+        config.alternatives = vec![alternative];
+
+        Ok(config)
+    }
+
+    /// Console script to launch: the one named after the package, else one mentioning mcp, else the first
+    fn select_script(&self, project: &TomlValue, name: &str) -> Option<String> {
+        let mut scripts: Vec<&String> = project.get("scripts").and_then(|s| s.as_table())?.keys().collect();
+        scripts.sort();
+
+        scripts
+            .iter()
+            .find(|script| normalize_name(script) == normalize_name(name))
+            .or_else(|| scripts.iter().find(|script| script.to_lowercase().contains("mcp")))
+            .or_else(|| scripts.first())
+            .map(|script| script.to_string())
+    }
+
+    /// Author names, joined with commas
+    ///
+    /// PEP 621 lists `{ name, email }` tables; Poetry lists `"Name <email>"` strings.
+    fn extract_authors(&self, project: &TomlValue, layout: Layout) -> Option<String> {
+        let authors: Vec<String> = project
+            .get("authors")
+            .and_then(|a| a.as_array())?
+            .iter()
+            .filter_map(|author| match layout {
+                Layout::Pep621 => string_field(author, "name").or_else(|| string_field(author, "email")),
+                Layout::Poetry => author.as_str().map(|a| a.split('<').next().unwrap_or(a).trim().to_string()),
+            })
+            .filter(|author| !author.is_empty())
+            .collect();
+
+        (!authors.is_empty()).then(|| authors.join(", "))
+    }
+
+    /// Documentation, homepage or repository URL, in that order of preference
+    fn extract_docs_url(&self, project: &TomlValue, layout: Layout) -> Option<String> {
+        const KEYS: &[&str] = &["documentation", "homepage", "repository", "source"];
+
+        match layout {
+            Layout::Pep621 => {
+                let urls = project.get("urls").and_then(|u| u.as_table())?;
+                KEYS.iter().find_map(|key| {
+                    urls.iter()
+                        .find(|(label, _)| label.to_lowercase() == *key)
+                        .and_then(|(_, url)| url.as_str())
+                        .map(String::from)
+                })
+            }
+            Layout::Poetry => KEYS.iter().find_map(|key| string_field(project, key)),
+        }
+    }
+
+    /// SPDX expression, or the `text` of a PEP 621 license table
+    fn extract_license(&self, project: &TomlValue) -> Option<String> {
+        let license = project.get("license")?;
+        license
+            .as_str()
+            .or_else(|| license.get("text").and_then(|t| t.as_str()))
+            .map(str::trim)
+            .filter(|l| !l.is_empty())
+            .map(String::from)
+    }
+}
+
+impl Default for PyProjectParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Layout {
+    Pep621,
+    Poetry,
+}
+
+fn string_field(table: &TomlValue, key: &str) -> Option<String> {
+    table.get(key).and_then(|v| v.as_str()).map(String::from)
+}
+
+/// Python package names compare case-insensitively with `-`, `_` and `.` interchangeable
+fn normalize_name(name: &str) -> String {
+    name.to_lowercase().replace(['_', '.'], "-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pep621_project_with_entry_point() {
+        let content = r#"
+[project]
+name = "mcp-server-notes"
+version = "0.4.1"
+description = "Notes for MCP clients"
+authors = [{ name = "Ada Lovelace", email = "ada@example.com" }, { email = "team@example.com" }]
+license = { text = "MIT" }
+
+[project.scripts]
+mcp-server-notes = "notes.server:main"
+
+[project.urls]
+Homepage = "https://example.com/notes"
+Documentation = "https://docs.example.com/notes"
+"#;
+
+        let config = PyProjectParser::new().parse_pyproject(content).unwrap();
+
+        assert_eq!(config.name, "mcp-server-notes");
+        assert_eq!(config.version.as_deref(), Some("0.4.1"));
+        assert_eq!(config.description.as_deref(), Some("Notes for MCP clients"));
+        assert_eq!(config.author.as_deref(), Some("Ada Lovelace, team@example.com"));
+        assert_eq!(config.license.as_deref(), Some("MIT"));
+        assert_eq!(config.docs_url.as_deref(), Some("https://docs.example.com/notes"));
+        assert_eq!(config.command, "uvx");
+        assert_eq!(config.args, vec!["mcp-server-notes"]);
+        assert_eq!(config.runtime.as_deref(), Some("python"));
+    }
+
+    #[test]
+    fn test_poetry_project_with_differently_named_script() {
+        let content = r#"
+[tool.poetry]
+name = "acme_weather"
+version = "1.2.0"
+description = "Weather tools"
+authors = ["Grace Hopper <grace@example.com>"]
+license = "Apache-2.0"
+repository = "https://github.com/acme/weather"
+
+[tool.poetry.scripts]
+weather-mcp = "acme_weather:run"
+weather-admin = "acme_weather.admin:run"
+"#;
+
+        let config = PyProjectParser::new().parse_pyproject(content).unwrap();
+
+        assert_eq!(config.name, "acme_weather");
+        assert_eq!(config.author.as_deref(), Some("Grace Hopper"));
+        assert_eq!(config.license.as_deref(), Some("Apache-2.0"));
+        assert_eq!(config.docs_url.as_deref(), Some("https://github.com/acme/weather"));
+        assert_eq!(config.bin_name.as_deref(), Some("weather-mcp"));
+        assert_eq!(config.command, "uvx");
+        assert_eq!(config.args, vec!["--from", "acme_weather", "weather-mcp"]);
+
+        // Without an entry point the package runs as a module
+        let without_scripts = content.split("[tool.poetry.scripts]").next().unwrap();
+        let config = PyProjectParser::new().parse_pyproject(without_scripts).unwrap();
+        assert_eq!(config.command, "python");
+        assert_eq!(config.args, vec!["-m", "acme_weather"]);
+    }
+}
@@ -0,0 +1,1046 @@
+use anyhow::Result;
+use regex::Regex;
+use std::collections::{HashMap, HashSet};
+use std::sync::OnceLock;
+
+use super::alternatives::runtime_for_command;
+use super::compatibility::ClientRequirement;
+use super::server_analyzer::{default_ignored_env_vars, ArgConfig, ArgInputKind, DetectedConfig, EnvVarConfig};
+
+/// READMEs are only scanned up to this many bytes; real ones are far smaller
+const MAX_SCAN_BYTES: usize = 256 * 1024;
+
+/// Client names as they appear in READMEs, lowercased with single spaces, and their profile ids
+const CLIENT_NAMES: &[(&str, &str)] = &[
+    ("claude desktop", "claude-desktop"),
+    ("claude code", "claude-code"),
+    ("cursor", "cursor"),
+    ("zed", "zed"),
+    ("vs code", "vscode"),
+    ("vscode", "vscode"),
+    ("visual studio code", "vscode"),
+    ("continue", "continue-dev"),
+    ("warp", "warp"),
+    ("amazon q", "amazon-q"),
+];
+
+/// Compile a pattern once and reuse it for every README.
+///
+/// The regex crate matches in linear time, so patterns cannot backtrack catastrophically;
+/// the remaining cost is compilation, which this keeps out of the per-README path.
+macro_rules! regex {
+    ($pattern:expr) => {{
+        static PATTERN: OnceLock<Regex> = OnceLock::new();
+        PATTERN.get_or_init(|| Regex::new($pattern).expect("README pattern is valid"))
+    }};
+}
+
+/// Parser for README.md files
+pub struct ReadmeParser {
+    /// Variables never reported as server env, such as `NODE_ENV`
+    ignored_env_vars: HashSet<String>,
+}
+
+impl ReadmeParser {
+    pub fn new() -> Self {
+        Self { ignored_env_vars: default_ignored_env_vars() }
+    }
+
+    /// Also ignore these variables, on top of `DEFAULT_IGNORED_ENV_VARS`
+    pub fn with_ignored_env_vars<I, S>(mut self, names: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.ignored_env_vars.extend(names.into_iter().map(Into::into));
+        self
+    }
+
+    /// Parse README content for configuration information
+    pub fn parse_readme(&self, content: &str) -> Result<DetectedConfig> {
+        let content = scan_window(content);
+        let mut config = DetectedConfig {
+            name: "unknown".to_string(),
+            description: None,
+            command: "npx".to_string(),
+            args: Vec::new(),
+            env: HashMap::new(),
+            optional_args: Vec::new(),
+            server_type: "stdio".to_string(),
+            install_command: None,
+            docs_url: None,
+            author: None,
+            version: None,
+            declared_capabilities: Vec::new(),
+            command_candidates: Vec::new(),
+            probe_summary: None,
+            probed_tools: None,
+            protocol_requirement: None,
+            client_requirements: Vec::new(),
+            peer_dependencies: Vec::new(),
+            inherit_env: true,
+            cwd: None,
+            runtime: None,
+            alternatives: Vec::new(),
+            selected_alternative: None,
+            url: None,
+            logo_url: None,
+            bin_name: None,
+            license: None,
+            last_published: None,
+            conflicts: Vec::new(),
+            health_url: None,
+        };
+
+        // Extract description from first paragraph
+        config.description = self.extract_description(content);
+
+        // Extract environment variables
+        config.env = self.extract_env_vars_from_readme(content);
+
+        // Extract command examples
+        if let Some((cmd, args)) = self.extract_command_example(content) {
+            let (args, optional_args) = split_placeholder_args(args);
+            config.runtime = runtime_for_command(&cmd).map(String::from);
+            config.command = cmd;
+            config.args = args;
+            config.optional_args = optional_args;
+        }
+
+        // Extract installation command
+        config.install_command = self.extract_install_command(content);
+        if config.install_command.is_none() && config.command == "bunx" {
+            // bunx fetches on demand; a global add makes the binary available offline
+            config.install_command = config
+                .args
+                .iter()
+                .find(|a| !a.starts_with('-'))
+                .map(|package| format!("bun add -g {}", package));
+        }
+
+        // Extract working directory from example config snippets
+        config.cwd = self.extract_snippet_cwd(content);
+
+        // Extract minimum client versions, e.g. "requires Claude Desktop >= 0.8"
+        config.client_requirements = self.extract_client_requirements(content);
+
+        // Extract the logo shown above the first section; relative paths are resolved by
+        // the caller, which knows where the README was fetched from
+        config.logo_url = self.extract_header_logo(content);
+
+        // Extract a plain HTTP health route, e.g. "GET /health returns 200"
+        config.health_url = self.extract_health_url(content);
+
+        // `API_KEY: "${API_KEY}"` means "required, from the environment", not an example
+        config.normalize_env_placeholders(&self.ignored_env_vars);
+        config.env.retain(|name, _| !self.ignored_env_vars.contains(name));
+
+        Ok(config)
+    }
+
+    /// Find a documented health route: a full URL, or a path like `/health` relative to the server URL
+    fn extract_health_url(&self, content: &str) -> Option<String> {
+        let absolute = regex!(r#"https?://[^\s"'`<>()\[\]]+?/(?:health|healthz|healthcheck)\b"#);
+        if let Some(found) = absolute.find(content) {
+            return Some(found.as_str().to_string());
+        }
+        let relative = regex!(r"(?:\bGET\s+|`)(/(?:[\w.-]+/)*(?:health|healthz|healthcheck))\b");
+        relative.captures(content).map(|caps| caps[1].to_string())
+    }
+
+    /// Find a `cwd` in JSON config snippets like `{ "mcpServers": { "x": { "cwd": ... } } }`
+    fn extract_snippet_cwd(&self, content: &str) -> Option<String> {
+        let block_pattern = regex!(r"(?s)```(?:json|jsonc)?[ \t\r]*\n(.*?)```");
+
+        let cwd = block_pattern
+            .captures_iter(content)
+            .filter_map(|cap| serde_json::from_str::<serde_json::Value>(cap.get(1)?.as_str()).ok())
+            .find_map(|snippet| {
+                snippet
+                    .get("mcpServers")?
+                    .as_object()?
+                    .values()
+                    .find_map(|server| server.get("cwd")?.as_str().map(String::from))
+            });
+        cwd
+    }
+
+    /// Find the first non-badge `<img>` before the README's first section heading
+    fn extract_header_logo(&self, content: &str) -> Option<String> {
+        let img_pattern = regex!(r#"(?i)<img\b[^>]*?\bsrc\s*=\s*["']([^"']+)["']"#);
+
+        let header_end = content
+            .match_indices('\n')
+            .map(|(index, _)| index + 1)
+            .find(|&start| content[start..].starts_with("##"))
+            .unwrap_or(content.len());
+
+        img_pattern
+            .captures_iter(&content[..header_end])
+            .map(|cap| cap[1].trim().to_string())
+            .find(|src| !is_badge_url(src))
+    }
+
+    /// Extract description from README
+    fn extract_description(&self, content: &str) -> Option<String> {
+        // Look for first paragraph after title
+        let lines: Vec<&str> = content.lines().collect();
+        let mut found_title = false;
+        let mut description = String::new();
+
+        for line in lines {
+            let trimmed = line.trim();
+
+            // Skip empty lines
+            if trimmed.is_empty() {
+                if found_title && !description.is_empty() {
+                    break;
+                }
+                continue;
+            }
+
+            // Skip title lines (# heading)
+            if trimmed.starts_with('#') {
+                found_title = true;
+                continue;
+            }
+
+            // Skip badges and images
+            if trimmed.starts_with("[![") || trimmed.starts_with("![") {
+                continue;
+            }
+
+            // Found first content paragraph
+            if found_title {
+                if description.is_empty() {
+                    description = trimmed.to_string();
+                } else {
+                    description.push(' ');
+                    description.push_str(trimmed);
+                }
+
+                // Stop at reasonable length
+                if description.len() > 200 {
+                    break;
+                }
+            }
+        }
+
+        if description.is_empty() {
+            None
+        } else {
+            Some(description)
+        }
+    }
+
+    /// Extract environment variables from README
+    fn extract_env_vars_from_readme(&self, content: &str) -> HashMap<String, EnvVarConfig> {
+        let mut env_vars = HashMap::new();
+
+        // Pattern 1: Environment Variables section with table or list
+        if let Some((heading, env_section)) = self.extract_section(content, &["Environment Variables", "Environment", "Configuration", "Setup"]) {
+            env_vars.extend(self.parse_env_section(&heading, &env_section));
+        }
+
+        // Pattern 2: Inline code blocks with export or env var patterns
+        let locations = LocationIndex::new(content);
+        let env_pattern = regex!(r"(?m)^(?:export[ \t]+)?([A-Z][A-Z0-9_]+)=(.*)$");
+        for cap in env_pattern.captures_iter(content) {
+            let var_name = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+            let var_value = cap.get(2).map(|m| m.as_str()).unwrap_or("");
+
+            if !var_name.is_empty() {
+                env_vars.entry(var_name.to_string()).or_insert_with(|| EnvVarConfig {
+                    name: var_name.to_string(),
+                    description: None,
+                    required: false,
+                    default: None,
+                    example: Some(var_value.trim().trim_matches('"').to_string()),
+                    source_location: Some(locations.describe(cap.get(0).unwrap().start())),
+                });
+            }
+        }
+
+        // Pattern 2b: env entries of JSON or JS config snippets, e.g. `"API_KEY": "..."`
+        let entry_pattern = regex!(r#""?([A-Z][A-Z0-9_]+)"?[ \t]*:[ \t]*"([^"\n]*)""#);
+        for cap in entry_pattern.captures_iter(content) {
+            let var_name = &cap[1];
+            env_vars.entry(var_name.to_string()).or_insert_with(|| EnvVarConfig {
+                name: var_name.to_string(),
+                description: None,
+                required: true,
+                default: None,
+                example: Some(cap[2].to_string()),
+                source_location: Some(locations.describe(cap.get(0).unwrap().start())),
+            });
+        }
+
+        // Pattern 3: ${VAR_NAME} or $VAR_NAME in code blocks
+        let var_ref_pattern = regex!(r"\$\{?([A-Z][A-Z0-9_]+)\}?");
+        for cap in var_ref_pattern.captures_iter(content) {
+            let var_name = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+            if !var_name.is_empty() {
+                env_vars.entry(var_name.to_string()).or_insert_with(|| EnvVarConfig {
+                    name: var_name.to_string(),
+                    description: Some("Required environment variable (detected from README)".to_string()),
+                    required: true,
+                    default: None,
+                    example: None,
+                    source_location: Some(locations.describe(cap.get(0).unwrap().start())),
+                });
+            }
+        }
+
+        // Pattern 4: author annotations override anything guessed from prose or examples
+        for annotated in self.parse_env_annotations(content, &locations) {
+            let guessed = env_vars.remove(&annotated.name);
+            let (description, example) = guessed.map(|g| (g.description, g.example)).unwrap_or_default();
+            // The `$VAR` fallback only says the variable is required, which the annotation decides
+            let description = description.filter(|d| !d.ends_with("(detected from README)"));
+            env_vars.insert(annotated.name.clone(), EnvVarConfig { description, example, ..annotated });
+        }
+
+        env_vars
+    }
+
+    /// Parse `<!-- mcp-env: NAME required|optional [default=VALUE] -->` comments.
+    ///
+    /// The default may be quoted to include spaces: `default="us east"`.
+    fn parse_env_annotations(&self, content: &str, locations: &LocationIndex) -> Vec<EnvVarConfig> {
+        let annotation = regex!(
+            r#"<!--[ \t]*mcp-env:[ \t]*([A-Za-z_][A-Za-z0-9_]*)[ \t]+(required|optional)(?:[ \t]+default=(?:"([^"]*)"|([^\s"]+?)))?[ \t]*-->"#
+        );
+        annotation
+            .captures_iter(content)
+            .map(|cap| EnvVarConfig {
+                name: cap[1].to_string(),
+                description: None,
+                required: &cap[2] == "required",
+                default: cap.get(3).or(cap.get(4)).map(|m| m.as_str().to_string()),
+                example: None,
+                source_location: Some(format!("mcp-env comment, {}", locations.describe(cap.get(0).unwrap().start()))),
+            })
+            .collect()
+    }
+
+
+    /// Extract a specific section from README, returning its heading and content
+    fn extract_section(&self, content: &str, section_names: &[&str]) -> Option<(String, String)> {
+        let lines: Vec<&str> = content.lines().collect();
+        let mut in_section = false;
+        let mut section_content = String::new();
+        let mut section_heading = String::new();
+        let mut section_level = 0;
+
+        for line in lines {
+            let trimmed = line.trim();
+
+            // Check if this is a heading
+            if trimmed.starts_with('#') {
+                let level = trimmed.chars().take_while(|&c| c == '#').count();
+                let heading_text = trimmed.trim_start_matches('#').trim().to_lowercase();
+
+                // Check if this is our target section
+                if section_names.iter().any(|&name| heading_text.contains(&name.to_lowercase())) {
+                    in_section = true;
+                    section_heading = trimmed.trim_start_matches('#').trim().to_string();
+                    section_level = level;
+                    continue;
+                }
+
+                // If we're in a section and hit a same or higher level heading, we're done
+                if in_section && level <= section_level {
+                    break;
+                }
+            }
+
+            if in_section {
+                section_content.push_str(line);
+                section_content.push('\n');
+            }
+        }
+
+        if section_content.is_empty() {
+            None
+        } else {
+            Some((section_heading, section_content))
+        }
+    }
+
+    /// Parse environment variables from a section
+    fn parse_env_section(&self, heading: &str, section: &str) -> HashMap<String, EnvVarConfig> {
+        let mut env_vars = HashMap::new();
+
+        // Try to parse as table
+        if section.contains('|') {
+            env_vars.extend(self.parse_table_format(section, &format!("{} table", heading)));
+        }
+
+        // Try to parse as list
+        env_vars.extend(self.parse_list_format(section, &format!("{} list", heading)));
+
+        env_vars
+    }
+
+    /// Parse markdown table format
+    fn parse_table_format(&self, content: &str, location: &str) -> HashMap<String, EnvVarConfig> {
+        let mut env_vars = HashMap::new();
+        let lines: Vec<&str> = content.lines().collect();
+
+        // Find table header
+        let mut header_idx = None;
+        for (i, line) in lines.iter().enumerate() {
+            if line.contains('|') && (line.to_lowercase().contains("name") || line.to_lowercase().contains("variable")) {
+                header_idx = Some(i);
+                break;
+            }
+        }
+
+        if let Some(header_idx) = header_idx {
+            // Skip separator line
+            for line in lines.iter().skip(header_idx + 2) {
+                if !line.contains('|') {
+                    break;
+                }
+
+                let cells: Vec<&str> = line.split('|').map(|s| s.trim()).collect();
+                if cells.len() >= 2 {
+                    let name = cells[1].trim();
+                    if !name.is_empty() && name.chars().next().map(|c| c.is_uppercase()).unwrap_or(false) {
+                        env_vars.insert(name.to_string(), EnvVarConfig {
+                            name: name.to_string(),
+                            description: cells.get(2).map(|s| s.trim().to_string()),
+                            required: cells.iter().any(|&s| s.to_lowercase().contains("required") || s.to_lowercase().contains("yes")),
+                            default: cells.iter()
+                                .find(|&&s| s.to_lowercase().contains("default"))
+                                .and_then(|s| {
+                                    let parts: Vec<&str> = s.split(':').collect();
+                                    parts.get(1).map(|p| p.trim().to_string())
+                                }),
+                            example: None,
+                            source_location: Some(location.to_string()),
+                        });
+                    }
+                }
+            }
+        }
+
+        env_vars
+    }
+
+    /// Parse list format
+    fn parse_list_format(&self, content: &str, location: &str) -> HashMap<String, EnvVarConfig> {
+        let mut env_vars = HashMap::new();
+
+        // Pattern: - `VAR_NAME`: description
+        let list_pattern = regex!(r"(?m)^[-*][ \t]*`?([A-Z][A-Z0-9_]+)`?[ \t]*[:–-][ \t]*(.*)$");
+
+        for cap in list_pattern.captures_iter(content) {
+            let var_name = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+            let mut description = cap.get(2).map(|m| m.as_str().trim().to_string()).unwrap_or_default();
+
+            // Indented lines after the item continue its description until the next item
+            let rest = &content[cap.get(0).map(|m| m.end()).unwrap_or(content.len())..];
+            for line in rest.lines().skip(1) {
+                let continuation = line.trim();
+                if continuation.is_empty()
+                    || !line.starts_with([' ', '\t'])
+                    || continuation.starts_with(['-', '*'])
+                {
+                    break;
+                }
+                if !description.is_empty() {
+                    description.push(' ');
+                }
+                description.push_str(continuation);
+            }
+
+            if !var_name.is_empty() {
+                let is_required = description.to_lowercase().contains("required");
+
+                env_vars.insert(var_name.to_string(), EnvVarConfig {
+                    name: var_name.to_string(),
+                    description: Some(description),
+                    required: is_required,
+                    default: None,
+                    example: None,
+                    source_location: Some(location.to_string()),
+                });
+            }
+        }
+
+        env_vars
+    }
+
+    /// Extract command example from code blocks
+    fn extract_command_example(&self, content: &str) -> Option<(String, Vec<String>)> {
+        // Look for code blocks with common MCP command patterns
+        let code_block_pattern = regex!(r"```(?:bash|sh|shell)?[ \t\r]*\n([\s\S]*?)\n```");
+
+        for cap in code_block_pattern.captures_iter(content) {
+            let code = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+
+            // Look for npx, node, or npm commands
+            for line in code.lines() {
+                let trimmed = line.trim();
+
+                // Skip comments and empty lines
+                if trimmed.is_empty() || trimmed.starts_with('#') {
+                    continue;
+                }
+
+                // Parse command
+                if let Some((cmd, args)) = self.parse_command_line(trimmed) {
+                    // Filter out installation commands
+                    if !args.iter().any(|a| a == "install" || a == "i") {
+                        return Some((cmd, args));
+                    }
+                }
+            }
+        }
+
+        None
+    }
+
+    /// Parse a command line into command and args
+    fn parse_command_line(&self, line: &str) -> Option<(String, Vec<String>)> {
+        let mut parts = tokenize_command_line(line).into_iter();
+        let cmd = parts.next()?;
+        let args: Vec<String> = parts.collect();
+
+        // Only return if it's a relevant command or a direct path to an executable
+        if runtime_for_command(&cmd).is_some() || is_executable_path(&cmd) {
+            Some((cmd.clone(), normalize_launcher_args(&cmd, args)))
+        } else {
+            None
+        }
+    }
+
+    /// Extract statements like "requires Cursor >= 0.40", keeping the first per client
+    fn extract_client_requirements(&self, content: &str) -> Vec<ClientRequirement> {
+        let pattern = regex!(
+            r"(?i)\b(?:requires?|needs?)[ \t]+(claude[ \t]+desktop|claude[ \t]+code|cursor|zed|vs[ \t]?code|visual[ \t]+studio[ \t]+code|continue|warp|amazon[ \t]+q)[ \t]*(?:version[ \t]*)?(?:>=|≥)?[ \t]*v?(\d+(?:\.\d+){0,2})"
+        );
+
+        let mut requirements: Vec<ClientRequirement> = Vec::new();
+        for cap in pattern.captures_iter(content) {
+            let client = cap[1].split_whitespace().collect::<Vec<_>>().join(" ");
+            let Some((_, app_id)) = CLIENT_NAMES.iter().find(|(name, _)| client.eq_ignore_ascii_case(name)) else {
+                continue;
+            };
+            if requirements.iter().any(|r| r.app_id == *app_id) {
+                continue;
+            }
+
+            requirements.push(ClientRequirement {
+                app_id: app_id.to_string(),
+                client,
+                min_version: cap[2].to_string(),
+                source: Some(cap[0].trim().to_string()),
+            });
+        }
+
+        requirements
+    }
+
+    /// Extract installation command, preferring the installation section when there is one
+    fn extract_install_command(&self, content: &str) -> Option<String> {
+        // Examples and contributing sections often install unrelated packages, so only
+        // search the whole document when the README has no installation section
+        let section = self.extract_section(content, &["Installation", "Install", "Getting Started"]);
+        let scope = section.as_ref().map_or(content, |(_, body)| body.as_str());
+
+        // Look for npm install commands
+        let install_pattern = regex!(r"npm[ \t]+(?:i|install)[ \t]+(\S+)");
+
+        if let Some(cap) = install_pattern.captures(scope) {
+            let package = cap.get(1).map(|m| m.as_str()).unwrap_or("");
+            return Some(format!("npm install {}", package));
+        }
+
+        None
+    }
+}
+
+impl Default for ReadmeParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Status badges from shields.io and CI providers, which sit beside a logo in README headers
+fn is_badge_url(src: &str) -> bool {
+    let lower = src.to_lowercase();
+    ["shields.io", "badge.fury.io", "badgen.net", "/badge.svg", "codecov.io", "/badges/"]
+        .iter()
+        .any(|marker| lower.contains(marker))
+}
+
+/// Resolve an image path from a README or package.json against the raw file URL of its repository.
+///
+/// Absolute and protocol-relative URLs are returned as-is (the latter as https).
+pub(crate) fn resolve_relative_url(src: &str, raw_base: &str) -> String {
+    if src.starts_with("http://") || src.starts_with("https://") || src.starts_with("data:") {
+        return src.to_string();
+    }
+    if let Some(rest) = src.strip_prefix("//") {
+        return format!("https://{}", rest);
+    }
+
+    let path = src.trim_start_matches("./").trim_start_matches('/');
+    format!("{}/{}", raw_base.trim_end_matches('/'), path)
+}
+
+/// The leading part of a README that is scanned, cut at a character boundary
+fn scan_window(content: &str) -> &str {
+    if content.len() <= MAX_SCAN_BYTES {
+        return content;
+    }
+
+    let mut end = MAX_SCAN_BYTES;
+    while !content.is_char_boundary(end) {
+        end -= 1;
+    }
+    &content[..end]
+}
+
+/// Line numbers and code-block state for byte offsets, computed in one pass
+struct LocationIndex {
+    line_starts: Vec<usize>,
+    /// Whether each line is inside a fenced code block
+    in_code_block: Vec<bool>,
+}
+
+impl LocationIndex {
+    fn new(content: &str) -> Self {
+        let mut line_starts = Vec::new();
+        let mut in_code_block = Vec::new();
+        let mut fences = 0;
+        let mut start = 0;
+
+        for line in content.split('\n') {
+            if line.trim_start().starts_with("```") {
+                fences += 1;
+            }
+            line_starts.push(start);
+            in_code_block.push(fences % 2 == 1);
+            start += line.len() + 1;
+        }
+
+        Self { line_starts, in_code_block }
+    }
+
+    /// Describe where a byte offset falls, e.g. "code block line 12"
+    fn describe(&self, offset: usize) -> String {
+        let index = self.line_starts.partition_point(|&start| start <= offset).saturating_sub(1);
+        let line = index + 1;
+
+        if self.in_code_block.get(index).copied().unwrap_or(false) {
+            format!("code block line {}", line)
+        } else {
+            format!("line {}", line)
+        }
+    }
+}
+
+/// Split a shell command line into tokens, keeping quoted and escaped spaces.
+///
+/// Single quotes are literal; inside double quotes a backslash escapes `"` and `\`.
+pub(crate) fn tokenize_command_line(line: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut in_token = false;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_token = true;
+                current.extend(chars.by_ref().take_while(|&c| c != '\''));
+            }
+            '"' => {
+                in_token = true;
+                while let Some(c) = chars.next() {
+                    match c {
+                        '"' => break,
+                        '\\' if matches!(chars.peek(), Some('"') | Some('\\')) => current.push(chars.next().unwrap()),
+                        _ => current.push(c),
+                    }
+                }
+            }
+            '\\' if chars.peek().is_some_and(|c| c.is_whitespace()) => {
+                in_token = true;
+                current.push(chars.next().unwrap());
+            }
+            c if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            _ => {
+                in_token = true;
+                current.push(c);
+            }
+        }
+    }
+
+    if in_token {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Whether a command is an absolute or home-relative path rather than a name on PATH
+fn is_executable_path(cmd: &str) -> bool {
+    let bytes = cmd.as_bytes();
+    cmd.starts_with('/')
+        || cmd.starts_with("~/")
+        || (bytes.len() >= 3 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':' && matches!(bytes[2], b'\\' | b'/'))
+}
+
+/// Drop flags a launcher does not need; `bunx` never prompts, so `-y` is redundant
+fn normalize_launcher_args(cmd: &str, args: Vec<String>) -> Vec<String> {
+    match cmd {
+        "bunx" => args.into_iter().filter(|a| a != "-y" && a != "--yes").collect(),
+        _ => args,
+    }
+}
+
+/// Whether a usage value is a placeholder to fill in rather than a literal
+fn is_placeholder(value: &str) -> bool {
+    let all_caps = value.len() > 1
+        && value.chars().any(|c| c.is_ascii_uppercase())
+        && value.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_');
+
+    all_caps || is_sample_value(value)
+}
+
+/// Whether a value was copied from documentation rather than filled in, such as
+/// `<token>`, `xxxx`, `your-api-key-here` or `/path/to/dir`.
+///
+/// Unlike [`is_placeholder`] this does not treat `ALL_CAPS` as a placeholder, since real
+/// credentials are often written that way.
+pub(crate) fn is_sample_value(value: &str) -> bool {
+    let lower = value.trim().to_lowercase();
+    if lower.is_empty() {
+        return false;
+    }
+
+    let bracketed = lower.len() > 2 && lower.starts_with('<') && lower.ends_with('>');
+    let masked = lower.len() >= 3 && lower.chars().all(|c| c == 'x' || c == '*');
+    let sample_path = lower.contains("/your") || lower.starts_with("your") || lower.contains("path/to");
+    let fill_in = lower.contains("_here")
+        || lower.contains("-here")
+        || ["changeme", "change-me", "replace-me", "replace_me", "placeholder", "todo", "example"]
+            .iter()
+            .any(|marker| lower.contains(marker));
+
+    bracketed || masked || sample_path || fill_in
+}
+
+/// Move `--flag <placeholder>` pairs out of the literal args into optional args with an example
+fn split_placeholder_args(args: Vec<String>) -> (Vec<String>, Vec<ArgConfig>) {
+    let mut literal = Vec::new();
+    let mut optional = Vec::new();
+    let mut iter = args.into_iter().peekable();
+
+    while let Some(arg) = iter.next() {
+        if !arg.starts_with('-') {
+            literal.push(arg);
+            continue;
+        }
+
+        let (flag, value) = match arg.split_once('=') {
+            Some((flag, value)) => (flag.to_string(), Some(value.to_string())),
+            None if iter.peek().map(|next| is_placeholder(next)).unwrap_or(false) => (arg.clone(), iter.next()),
+            None => (arg.clone(), None),
+        };
+
+        match value {
+            Some(value) if is_placeholder(&value) => optional.push(ArgConfig {
+                input_kind: ArgInputKind::classify(&flag, Some(&value)),
+                name: flag,
+                description: None,
+                default: None,
+                example: Some(value),
+            }),
+            _ => literal.push(arg),
+        }
+    }
+
+    (literal, optional)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_env_var_source_location() {
+        let readme = "# Weather MCP\n\
+\n\
+## Environment Variables\n\
+\n\
+| Name | Description | Required |\n\
+|------|-------------|----------|\n\
+| WEATHER_API_KEY | API key for the weather service | yes |\n\
+\n\
+## Usage\n\
+\n\
+```bash\n\
+export WEATHER_UNITS=metric\n\
+```\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+
+        assert_eq!(
+            config.env["WEATHER_API_KEY"].source_location.as_deref(),
+            Some("Environment Variables table")
+        );
+        assert_eq!(
+            config.env["WEATHER_UNITS"].source_location.as_deref(),
+            Some("code block line 12")
+        );
+    }
+
+    #[test]
+    fn test_env_vars_from_comment_annotations() {
+        let readme = "# Maps MCP\n\
+<!-- mcp-env: MAPS_API_KEY required -->\n\
+<!-- mcp-env: MAPS_REGION optional default=\"us east\" -->\n\
+\n\
+Set `$MAPS_REGION` to pick a data center.\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+
+        let key = &config.env["MAPS_API_KEY"];
+        assert!(key.required);
+        assert_eq!(key.default, None);
+        assert_eq!(key.source_location.as_deref(), Some("mcp-env comment, line 2"));
+
+        // The annotation wins over the `$MAPS_REGION` reference, which guesses required
+        let region = &config.env["MAPS_REGION"];
+        assert!(!region.required);
+        assert_eq!(region.default.as_deref(), Some("us east"));
+        assert_eq!(region.description, None);
+    }
+
+    #[test]
+    fn test_runtime_env_vars_are_ignored() {
+        let readme = "# Notes MCP\n\n```bash\n\
+export NODE_ENV=production\n\
+export NOTES_TOKEN=your-token\n\
+export NOTES_REGION=eu\n\
+node dist/index.js --log-dir $HOME/logs\n\
+```\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+        assert!(!config.env.contains_key("NODE_ENV"));
+        assert!(!config.env.contains_key("HOME"));
+        assert!(config.env.contains_key("NOTES_TOKEN"));
+
+        let config = ReadmeParser::new().with_ignored_env_vars(["NOTES_REGION"]).parse_readme(readme).unwrap();
+        assert!(!config.env.contains_key("NOTES_REGION"));
+        assert!(config.env.contains_key("NOTES_TOKEN"));
+    }
+
+    #[test]
+    fn test_self_referential_env_placeholder() {
+        let readme = "# Search MCP\n\n```json\n\
+{\n\
+  \"mcpServers\": {\n\
+    \"search\": {\n\
+      \"command\": \"npx\",\n\
+      \"args\": [\"-y\", \"search-mcp\", \"--index\", \"${SEARCH_INDEX}\"],\n\
+      \"env\": { \"API_KEY\": \"${API_KEY}\" }\n\
+    }\n\
+  }\n\
+}\n```\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+
+        let key = &config.env["API_KEY"];
+        assert!(key.required);
+        assert_eq!(key.default, None);
+        assert_eq!(key.example.as_deref(), Some("${API_KEY}"));
+        // Rendered as the placeholder itself, not `<YOUR_API_KEY>` or a nested `${${API_KEY}}`
+        assert_eq!(config.to_mcp_entry()["env"]["API_KEY"], "${API_KEY}");
+        assert!(config.env["SEARCH_INDEX"].required);
+    }
+
+    #[test]
+    fn test_list_description_continues_on_indented_lines() {
+        let readme = "# Search MCP\n\n## Configuration\n\n\
+- `SEARCH_API_KEY`: API key for the search service,\n  found under Settings > Keys. Required.\n\
+- `SEARCH_REGION`: Region to query\n\nSee the docs for more.\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+
+        let key = &config.env["SEARCH_API_KEY"];
+        assert_eq!(
+            key.description.as_deref(),
+            Some("API key for the search service, found under Settings > Keys. Required.")
+        );
+        assert!(key.required);
+        assert_eq!(config.env["SEARCH_REGION"].description.as_deref(), Some("Region to query"));
+    }
+
+    #[test]
+    fn test_health_url_from_readme() {
+        let parser = ReadmeParser::new();
+        let absolute = "## Monitoring\n\nThe server answers on http://localhost:8080/healthz once ready.\n";
+        assert_eq!(parser.parse_readme(absolute).unwrap().health_url.as_deref(), Some("http://localhost:8080/healthz"));
+
+        let relative = "## Health\n\n`GET /api/health` returns 200 while the server is up.\n";
+        assert_eq!(parser.parse_readme(relative).unwrap().health_url.as_deref(), Some("/api/health"));
+
+        assert!(parser.parse_readme("# Weather\n\nHealthy forecasts.\n").unwrap().health_url.is_none());
+    }
+
+    #[test]
+    fn test_cwd_from_config_snippet() {
+        let readme = "# Notes MCP\n\n```json\n{\n  \"mcpServers\": {\n    \"notes\": { \"command\": \"node\", \"args\": [\"index.js\"], \"cwd\": \"/opt/notes-mcp\" }\n  }\n}\n```\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+        assert_eq!(config.cwd.as_deref(), Some("/opt/notes-mcp"));
+    }
+
+    #[test]
+    fn test_bunx_command_example() {
+        let readme = "# Weather\n\nFetches forecasts.\n\n```bash\nbunx -y weather-mcp --units metric\n```\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+
+        assert_eq!(config.command, "bunx");
+        assert_eq!(config.args, vec!["weather-mcp", "--units", "metric"]);
+        assert_eq!(config.runtime.as_deref(), Some("bun"));
+        assert_eq!(config.install_command.as_deref(), Some("bun add -g weather-mcp"));
+    }
+
+    #[test]
+    fn test_install_command_prefers_installation_section() {
+        let readme = "# Weather\n\n## Examples\n\n```bash\nnpm install chart-renderer\n```\n\n## Installation\n\n```bash\nnpm install @acme/weather-mcp\n```\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+        assert_eq!(config.install_command.as_deref(), Some("npm install @acme/weather-mcp"));
+    }
+
+    #[test]
+    fn test_logo_from_readme_header_image() {
+        let parser = ReadmeParser::new();
+        let readme = r#"<p align="center">
+  <a href="https://www.npmjs.com/package/weather-mcp"><img src="https://img.shields.io/npm/v/weather-mcp.svg" alt="npm"></a>
+  <img src="./docs/logo.png" alt="Weather MCP" width="120">
+</p>
+
+# Weather MCP
+
+Forecasts for your assistant.
+
+## Screenshots
+
+<img src="docs/screenshot.png">
+"#;
+
+        let mut config = parser.parse_readme(readme).unwrap();
+        assert_eq!(config.logo_url.as_deref(), Some("./docs/logo.png"));
+
+        config.resolve_logo_url(Some("https://raw.githubusercontent.com/acme/weather-mcp/main"));
+        assert_eq!(
+            config.logo_url.as_deref(),
+            Some("https://raw.githubusercontent.com/acme/weather-mcp/main/docs/logo.png")
+        );
+
+        // Images below the first section are screenshots, not logos
+        let no_logo = "# Weather MCP\n\n## Screenshots\n\n<img src=\"docs/screenshot.png\">\n";
+        assert!(parser.parse_readme(no_logo).unwrap().logo_url.is_none());
+    }
+
+    #[test]
+    fn test_placeholder_args_become_examples() {
+        let readme = "# Files\n\n```bash\nnpx -y files-mcp --path <path> --mode=READ_MODE --port 3000 --root /your/directory\n```\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+
+        assert_eq!(config.args, vec!["-y", "files-mcp", "--port", "3000"]);
+        let examples: Vec<(&str, Option<&str>)> = config
+            .optional_args
+            .iter()
+            .map(|a| (a.name.as_str(), a.example.as_deref()))
+            .collect();
+        assert_eq!(
+            examples,
+            vec![("--path", Some("<path>")), ("--mode", Some("READ_MODE")), ("--root", Some("/your/directory"))]
+        );
+    }
+
+    #[test]
+    fn test_path_flags_get_picker_input_kinds() {
+        let readme = "# Files\n\n```bash\nnpx -y files-mcp --directory /path/to/allowed --config <config.json> --name <NAME>\n```\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+
+        let kinds: Vec<(&str, ArgInputKind)> = config.optional_args.iter().map(|a| (a.name.as_str(), a.input_kind)).collect();
+        assert_eq!(
+            kinds,
+            vec![("--directory", ArgInputKind::Directory), ("--config", ArgInputKind::File), ("--name", ArgInputKind::Text)]
+        );
+        assert_eq!(ArgInputKind::classify("--out", Some("<settings.yaml>")), ArgInputKind::File);
+        assert_eq!(ArgInputKind::classify("--output-dir", None), ArgInputKind::Directory);
+        assert_eq!(ArgInputKind::classify("--config_file", None), ArgInputKind::File);
+        assert_eq!(ArgInputKind::classify("--profile", Some("<name>")), ArgInputKind::Text);
+        assert_eq!(ArgInputKind::classify("--direction", Some("<dirty>")), ArgInputKind::Text);
+        assert_eq!(ArgInputKind::classify("--configure", Some("<default>")), ArgInputKind::Text);
+    }
+
+    #[test]
+    fn test_quoted_command_path_with_spaces() {
+        let parser = ReadmeParser::new();
+
+        let (cmd, args) = parser
+            .parse_command_line(r#""/Applications/My App/bin/server" --root '/Users/me/My Notes' --name "Team \"A\"""#)
+            .unwrap();
+        assert_eq!(cmd, "/Applications/My App/bin/server");
+        assert_eq!(args, vec!["--root", "/Users/me/My Notes", "--name", "Team \"A\""]);
+
+        let (cmd, args) = parser.parse_command_line(r"/opt/My\ Server/run --verbose").unwrap();
+        assert_eq!(cmd, "/opt/My Server/run");
+        assert_eq!(args, vec!["--verbose"]);
+
+        assert_eq!(tokenize_command_line(r#"node "" x"#), vec!["node", "", "x"]);
+        assert!(parser.parse_command_line("echo hello").is_none());
+    }
+
+    #[test]
+    fn test_pathological_readme_finishes_quickly() {
+        let mut readme = String::from("# Evil\n\n");
+        // Unterminated fences, endless variable references and a huge list item
+        readme.push_str(&"```bash\n".repeat(20_000));
+        readme.push_str(&"$A${B_".repeat(50_000));
+        readme.push_str(&format!("\n- `X`{}\n", " ".repeat(200_000)));
+        readme.push_str(&"EXPORT_VAR_NAME=".repeat(50_000));
+
+        let started = std::time::Instant::now();
+        let config = ReadmeParser::new().parse_readme(&readme).unwrap();
+        assert!(started.elapsed() < std::time::Duration::from_secs(2), "took {:?}", started.elapsed());
+        assert!(config.env.len() < 100);
+    }
+
+    #[test]
+    fn test_client_version_requirement() {
+        let readme = "# Notes MCP\n\nA notes server.\n\n> **Note:** requires Cursor >= 0.40 and needs Claude Desktop version 0.8.1.\n";
+
+        let config = ReadmeParser::new().parse_readme(readme).unwrap();
+
+        assert_eq!(config.client_requirements.len(), 2);
+        let cursor = &config.client_requirements[0];
+        assert_eq!(cursor.app_id, "cursor");
+        assert_eq!(cursor.min_version, "0.40");
+        assert_eq!(cursor.source.as_deref(), Some("requires Cursor >= 0.40"));
+        assert_eq!(cursor.is_satisfied_by("0.39.2"), Some(false));
+        assert_eq!(cursor.is_satisfied_by("0.42.0"), Some(true));
+        assert_eq!(config.client_requirements[1].app_id, "claude-desktop");
+        assert_eq!(config.client_requirements[1].min_version, "0.8.1");
+    }
+}
@@ -0,0 +1,233 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value as JsonValue};
+use std::path::{Path, PathBuf};
+
+use crate::detection::is_metadata_key;
+
+/// Keys that mark a `transport` object as holding the server's launch details
+const TRANSPORT_KEYS: &[&str] = &["type", "command", "url"];
+
+/// Lift a nested `transport: { type, command, url, .. }` object into the flat entry shape.
+///
+/// Keys already set on the entry win. Returns whether anything was flattened.
+pub fn flatten_transport(entry: &mut Map<String, JsonValue>) -> bool {
+    let nested = entry
+        .get("transport")
+        .and_then(|t| t.as_object())
+        .is_some_and(|t| TRANSPORT_KEYS.iter().any(|key| t.contains_key(*key)));
+    if !nested {
+        return false;
+    }
+    if let Some(JsonValue::Object(transport)) = entry.remove("transport") {
+        for (key, value) in transport {
+            entry.entry(key).or_insert(value);
+        }
+    }
+    true
+}
+
+/// Flatten every nested `transport` object in a config, wherever its servers live
+pub fn flatten_transports(config: &mut JsonValue) {
+    if let Some(object) = config.as_object_mut() {
+        flatten_transport(object);
+        for value in object.values_mut() {
+            flatten_transports(value);
+        }
+    }
+}
+
+/// Containers a config snippet may keep its servers under
+const SNIPPET_CONTAINERS: &[&[&str]] = &[&["mcpServers"], &["servers"], &["mcp", "servers"], &["context_servers"]];
+
+/// Outcome of validating one config file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ValidationReport {
+    pub valid: bool,
+    /// Server entries found in the file
+    pub servers: usize,
+    /// What is wrong, prefixed with the server name when the file holds several
+    pub problems: Vec<String>,
+}
+
+/// Detector for MCP server schemas and configurations
+pub struct SchemaDetector;
+
+impl SchemaDetector {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Detect server type from configuration
+    pub fn detect_server_type(&self, config: &JsonValue) -> String {
+        let mut config = config.clone();
+        flatten_transports(&mut config);
+        let config = &config;
+
+        // Check for explicit server type
+        if let Some(server_type) = config.get("type").and_then(|t| t.as_str()) {
+            return server_type.to_string();
+        }
+
+        // Check for transport hints
+        if config.get("stdio").is_some() {
+            return "stdio".to_string();
+        }
+
+        if config.get("sse").is_some() || config.get("url").is_some() {
+            return "sse".to_string();
+        }
+
+        if config.get("http").is_some() || config.get("port").is_some() {
+            return "http".to_string();
+        }
+
+        // Default to stdio
+        "stdio".to_string()
+    }
+
+    /// Validate MCP server configuration
+    pub fn validate_config(&self, config: &JsonValue) -> Result<bool> {
+        Ok(self.config_problems(config).is_empty())
+    }
+
+    /// Everything wrong with a single server entry; empty when it is valid
+    pub fn config_problems(&self, config: &JsonValue) -> Vec<String> {
+        let mut config = config.clone();
+        flatten_transports(&mut config);
+        let mut problems = Vec::new();
+
+        // Must have either command or url
+        if config.get("command").is_none() && config.get("url").is_none() {
+            problems.push("Missing 'command' or 'url'".to_string());
+        }
+
+        // If has command, args should be array if present
+        if config.get("command").is_some() && config.get("args").is_some_and(|args| !args.is_array()) {
+            problems.push("'args' must be an array".to_string());
+        }
+
+        // If has env, it should be an object
+        if config.get("env").is_some_and(|env| !env.is_object()) {
+            problems.push("'env' must be an object".to_string());
+        }
+
+        problems
+    }
+
+    /// Validate every `*.json` file under a directory, as a single server entry or a snippet of several.
+    ///
+    /// Files are reported in path order; unreadable or unparsable files are reported as invalid.
+    pub fn validate_dir(&self, dir: &Path) -> Vec<(PathBuf, ValidationReport)> {
+        let mut files = Vec::new();
+        collect_json_files(dir, &mut files);
+        files.sort();
+
+        files
+            .into_iter()
+            .map(|path| {
+                let report = match std::fs::read_to_string(&path)
+                    .map_err(anyhow::Error::from)
+                    .and_then(|content| Ok(serde_json::from_str::<JsonValue>(&content)?))
+                {
+                    Ok(config) => self.validate_file_content(&config),
+                    Err(e) => ValidationReport { valid: false, servers: 0, problems: vec![format!("Unreadable: {}", e)] },
+                };
+                (path, report)
+            })
+            .collect()
+    }
+
+    fn validate_file_content(&self, config: &JsonValue) -> ValidationReport {
+        let container = SNIPPET_CONTAINERS
+            .iter()
+            .find_map(|path| path.iter().try_fold(config, |node, key| node.get(*key)))
+            .and_then(|servers| servers.as_object());
+
+        let problems: Vec<String> = match container {
+            Some(servers) => servers
+                .iter()
+                .filter(|(name, _)| !is_metadata_key(name))
+                .flat_map(|(name, entry)| {
+                    self.config_problems(entry).into_iter().map(move |problem| format!("{}: {}", name, problem))
+                })
+                .collect(),
+            None => self.config_problems(config),
+        };
+        let servers = container.map_or(1, |servers| servers.keys().filter(|name| !is_metadata_key(name)).count());
+
+        ValidationReport { valid: problems.is_empty(), servers, problems }
+    }
+
+    /// Extract capabilities from server metadata
+    pub fn extract_capabilities(&self, metadata: &JsonValue) -> Vec<String> {
+        let mut capabilities = Vec::new();
+
+        if let Some(caps) = metadata.get("capabilities").and_then(|c| c.as_array()) {
+            for cap in caps {
+                if let Some(cap_str) = cap.as_str() {
+                    capabilities.push(cap_str.to_string());
+                }
+            }
+        }
+
+        // Detect common capability patterns
+        for key in ["tools", "prompts", "resources"] {
+            if metadata.get(key).is_some() && !capabilities.iter().any(|c| c == key) {
+                capabilities.push(key.to_string());
+            }
+        }
+
+        capabilities
+    }
+}
+
+impl Default for SchemaDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn collect_json_files(dir: &Path, files: &mut Vec<PathBuf>) {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for path in entries.flatten().map(|entry| entry.path()) {
+        if path.is_dir() {
+            collect_json_files(&path, files);
+        } else if path.extension().is_some_and(|ext| ext == "json") {
+            files.push(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_dir_reports_each_file() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("github.json"),
+            r#"{"mcpServers": {"github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}}}"#,
+        )
+        .unwrap();
+        std::fs::create_dir(dir.path().join("team")).unwrap();
+        std::fs::write(dir.path().join("team/broken.json"), r#"{"args": "--port 3000", "env": []}"#).unwrap();
+        std::fs::write(dir.path().join("README.md"), "not a config").unwrap();
+
+        let reports = SchemaDetector::new().validate_dir(dir.path());
+
+        assert_eq!(reports.len(), 2);
+        let (valid_path, valid) = &reports[0];
+        assert!(valid_path.ends_with("github.json"));
+        assert!(valid.valid);
+        assert_eq!(valid.servers, 1);
+
+        let (invalid_path, invalid) = &reports[1];
+        assert!(invalid_path.ends_with("team/broken.json"));
+        assert!(!invalid.valid);
+        assert_eq!(invalid.problems, vec!["Missing 'command' or 'url'", "'env' must be an object"]);
+    }
+}
@@ -0,0 +1,304 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::compatibility::{Compatibility, CompatibilityChecker};
+use super::server_analyzer::DetectedConfig;
+use crate::detection::{
+    classify_runtime, ApplicationCategory, ApplicationDetector, ConfigValidationResult, ConfigValidator, McpServerConfig,
+    ServerRuntime, ServerType,
+};
+
+/// Words in a server's name or description marking it as a code-intelligence server
+const CODE_KEYWORDS: &[&str] = &[
+    "code", "git", "repo", "lsp", "language server", "language-server", "refactor", "lint", "debug", "compiler",
+];
+
+/// Whether the server would be added or would replace an existing entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TargetAction {
+    Add,
+    /// The application already has this server under `existing_name`
+    Update { existing_name: String },
+}
+
+/// Why an application was ranked where it was
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum FitReason {
+    /// The application already runs servers on the same runtime
+    SameEcosystem { runtime: ServerRuntime, servers: usize },
+    /// The server is remote and the application can connect to remote servers
+    RemoteSupported,
+    /// The application cannot run the server, for the reasons its profile gives
+    CannotInstall { problems: Vec<String> },
+    /// The application's protocol or installed version is older than the server needs
+    Incompatible { reason: String },
+    /// Code-intelligence servers fit IDEs, editors and developer tools
+    CategoryAffinity { category: ApplicationCategory },
+    /// The application already has this server
+    AlreadyPresent { existing_name: String },
+    /// Servers are added by pasting a snippet into the application
+    ManualSetupOnly,
+}
+
+/// A detected application ranked as a place to add a server
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetRecommendation {
+    pub app_id: String,
+    pub app_name: String,
+    /// Higher is a better fit; only meaningful relative to the other recommendations
+    pub score: i32,
+    /// False when the application cannot run the server at all
+    pub compatible: bool,
+    pub action: TargetAction,
+    pub reasons: Vec<FitReason>,
+}
+
+impl TargetRecommendation {
+    /// Why the application cannot run the server; empty when it is compatible
+    pub fn problems(&self) -> Vec<&str> {
+        self.reasons
+            .iter()
+            .flat_map(|reason| match reason {
+                FitReason::CannotInstall { problems } => problems.iter().map(String::as_str).collect(),
+                FitReason::Incompatible { reason } => vec![reason.as_str()],
+                _ => Vec::new(),
+            })
+            .collect()
+    }
+
+    /// Why the server cannot be added to the application, as one message; None when it can
+    pub fn incompatibility(&self) -> Option<String> {
+        if self.compatible {
+            return None;
+        }
+        let problems = self.problems();
+        Some(if problems.is_empty() {
+            format!("{} cannot run this server", self.app_name)
+        } else {
+            format!("{} cannot run this server: {}", self.app_name, problems.join("; "))
+        })
+    }
+}
+
+/// Rank detected applications by how well they fit a newly analyzed server.
+///
+/// Incompatible applications are listed last; ties keep a stable order by name.
+pub fn recommend_targets(config: &DetectedConfig, scan: &[ConfigValidationResult]) -> Vec<TargetRecommendation> {
+    let is_remote = config.is_remote();
+    let runtime = (!config.command.trim().is_empty()).then(|| classify_runtime(&config.command));
+    let code_intelligence = is_code_intelligence(config);
+    let checker = CompatibilityChecker::new();
+
+    let mut ranked: Vec<TargetRecommendation> = scan
+        .iter()
+        .map(|result| {
+            let app = &result.application;
+            let mut score = 0;
+            let mut compatible = true;
+            let mut reasons = Vec::new();
+
+            if let Some(runtime) = &runtime {
+                let servers = result.mcp_servers.iter().filter(|s| s.runtime() == *runtime).count();
+                if servers > 0 {
+                    score += 3;
+                    reasons.push(FitReason::SameEcosystem { runtime: runtime.clone(), servers });
+                }
+            }
+
+            match app.can_install(config) {
+                Ok(()) if is_remote => {
+                    score += 2;
+                    reasons.push(FitReason::RemoteSupported);
+                }
+                Ok(()) => {}
+                Err(problems) => {
+                    compatible = false;
+                    reasons.push(FitReason::CannotInstall { problems });
+                }
+            }
+
+            // Unknown results are common (no requirement documented) and don't count against the app
+            for check in [checker.check_config(app, config), checker.check_client_version(app, config)] {
+                if let Compatibility::Incompatible { reason } = check {
+                    compatible = false;
+                    reasons.push(FitReason::Incompatible { reason });
+                }
+            }
+
+            let category = &app.metadata.category;
+            if code_intelligence
+                && matches!(
+                    category,
+                    ApplicationCategory::IDE | ApplicationCategory::CodeEditor | ApplicationCategory::DeveloperTool
+                )
+            {
+                score += 2;
+                reasons.push(FitReason::CategoryAffinity { category: category.clone() });
+            }
+
+            if !app.metadata.supports_config_writes {
+                score -= 1;
+                reasons.push(FitReason::ManualSetupOnly);
+            }
+
+            let action = match result.mcp_servers.iter().find(|s| is_same_server(config, s)) {
+                Some(existing) => {
+                    reasons.push(FitReason::AlreadyPresent { existing_name: existing.name.clone() });
+                    TargetAction::Update { existing_name: existing.name.clone() }
+                }
+                None => TargetAction::Add,
+            };
+
+            TargetRecommendation {
+                app_id: app.id.clone(),
+                app_name: app.name.clone(),
+                score,
+                compatible,
+                action,
+                reasons,
+            }
+        })
+        .collect();
+
+    ranked.sort_by(|a, b| {
+        b.compatible
+            .cmp(&a.compatible)
+            .then(b.score.cmp(&a.score))
+            .then_with(|| a.app_name.cmp(&b.app_name))
+    });
+    ranked
+}
+
+/// Rank the applications detected on this system, reading each one's current config
+pub async fn recommend_detected_targets(config: &DetectedConfig) -> Result<Vec<TargetRecommendation>> {
+    let detected: Vec<_> = ApplicationDetector::new()?
+        .get_detected_applications()
+        .await?
+        .into_iter()
+        .map(|result| result.profile)
+        .collect();
+    let scan = ConfigValidator::new()?.validate_multiple_configs(&detected).await?;
+    Ok(recommend_targets(config, &scan))
+}
+
+fn is_code_intelligence(config: &DetectedConfig) -> bool {
+    let text = format!("{} {}", config.name, config.description.as_deref().unwrap_or_default()).to_lowercase();
+    CODE_KEYWORDS.iter().any(|keyword| text.contains(keyword))
+}
+
+/// Same name ignoring case, same launch command, or same remote endpoint
+fn is_same_server(config: &DetectedConfig, existing: &McpServerConfig) -> bool {
+    if existing.name.eq_ignore_ascii_case(&config.name) {
+        return true;
+    }
+    if !config.command.is_empty() && existing.command.as_deref() == Some(config.command.as_str()) && existing.args == config.args {
+        return true;
+    }
+    let existing_url = match &existing.server_type {
+        ServerType::Sse { url } | ServerType::WebSocket { url } => Some(url),
+        ServerType::Http { base_url } => Some(base_url),
+        _ => None,
+    };
+    existing_url.is_some() && existing_url == config.url.as_ref()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::{ClientRequirement, ServerAnalyzer};
+    use crate::detection::{ApplicationRegistry, ConfigValidator};
+    use serde_json::json;
+    use std::fs;
+    use tempfile::TempDir;
+
+    /// Four detected apps: Claude Desktop and Cursor run Node servers, Zed runs a Python
+    /// one, and IntelliJ IDEA has none. Cursor already has the GitHub server.
+    async fn fixture(temp_dir: &TempDir) -> Vec<ConfigValidationResult> {
+        let registry = ApplicationRegistry::new();
+        let configs = [
+            ("claude-desktop", json!({"mcpServers": {"filesystem": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem"]}}})),
+            ("cursor", json!({"mcpServers": {"GitHub": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}}})),
+            ("zed", json!({"mcp": {"servers": {"fetch": {"command": "uvx", "args": ["mcp-server-fetch"]}}}})),
+            ("jetbrains-idea", json!({})),
+        ];
+
+        let validator = ConfigValidator::new().unwrap();
+        let mut scan = Vec::new();
+        for (id, config) in configs {
+            let path = temp_dir.path().join(format!("{}.json", id));
+            fs::write(&path, config.to_string()).unwrap();
+            let mut profile = registry.get_application(id).unwrap().clone();
+            profile.set_config_path(path.to_string_lossy());
+            profile.alt_config_paths.clear();
+            scan.push(validator.validate_application_config(&profile).await.unwrap());
+        }
+        scan
+    }
+
+    fn detected(name: &str, command: &str, args: &[&str]) -> DetectedConfig {
+        let mut config = ServerAnalyzer::basic_config(name, command);
+        config.args = args.iter().map(|a| a.to_string()).collect();
+        config
+    }
+
+    #[tokio::test]
+    async fn test_ranks_targets_and_suggests_update_for_existing_server() {
+        let temp_dir = TempDir::new().unwrap();
+        let scan = fixture(&temp_dir).await;
+
+        let mut github = detected("github", "npx", &["-y", "@modelcontextprotocol/server-github"]);
+        github.description = Some("GitHub repository management".to_string());
+        let ranked = recommend_targets(&github, &scan);
+
+        let order: Vec<&str> = ranked.iter().map(|r| r.app_id.as_str()).collect();
+        assert_eq!(order, vec!["cursor", "claude-desktop", "jetbrains-idea", "zed"]);
+        assert_eq!(ranked[0].action, TargetAction::Update { existing_name: "GitHub".to_string() });
+        assert!(ranked[0].reasons.contains(&FitReason::SameEcosystem { runtime: ServerRuntime::Node, servers: 1 }));
+        assert_eq!(ranked[1].action, TargetAction::Add);
+        assert!(ranked.iter().all(|r| r.compatible));
+    }
+
+    #[tokio::test]
+    async fn test_documented_client_version_rules_out_older_installs() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut scan = fixture(&temp_dir).await;
+        for result in &mut scan {
+            result.application.metadata.version = Some("0.45.2".to_string());
+        }
+
+        let mut github = detected("github", "npx", &["-y", "@modelcontextprotocol/server-github"]);
+        github.client_requirements = vec![ClientRequirement {
+            app_id: "cursor".to_string(),
+            client: "Cursor".to_string(),
+            min_version: "0.50".to_string(),
+            source: Some("Requires Cursor 0.50 or later".to_string()),
+        }];
+        let ranked = recommend_targets(&github, &scan);
+
+        let cursor = ranked.iter().find(|r| r.app_id == "cursor").unwrap();
+        assert!(!cursor.compatible);
+        assert_eq!(cursor.problems(), vec!["'github' requires Cursor 0.50 or later but 0.45.2 is installed"]);
+        assert_eq!(
+            cursor.incompatibility().as_deref(),
+            Some("Cursor cannot run this server: 'github' requires Cursor 0.50 or later but 0.45.2 is installed")
+        );
+        assert_eq!(ranked.last().unwrap().app_id, "cursor");
+        assert!(ranked.iter().filter(|r| r.app_id != "cursor").all(|r| r.compatible && r.incompatibility().is_none()));
+    }
+
+    #[tokio::test]
+    async fn test_remote_server_only_fits_remote_capable_clients() {
+        let temp_dir = TempDir::new().unwrap();
+        let scan = fixture(&temp_dir).await;
+
+        let mut docs = detected("docs", "", &[]);
+        docs.url = Some("https://docs.example.com/mcp".to_string());
+        let ranked = recommend_targets(&docs, &scan);
+
+        assert_eq!(ranked[0].app_id, "cursor");
+        assert!(ranked[0].compatible && ranked[0].reasons.contains(&FitReason::RemoteSupported));
+        assert!(ranked[1..].iter().all(|r| !r.compatible
+            && r.reasons.iter().any(|reason| matches!(reason, FitReason::CannotInstall { problems }
+                if problems.iter().any(|p| p.contains("only supports local stdio servers"))))));
+    }
+}
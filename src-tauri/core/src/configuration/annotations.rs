@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::quarantine::QuarantineRecord;
+
+/// User annotations attached to a server, kept outside application configs
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ServerAnnotations {
+    /// Only run this server when the user explicitly asks for it
+    #[serde(default)]
+    pub manual_only: bool,
+    /// Free-form user notes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Analysis alternative the server was added with, so updates stay on it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alternative: Option<String>,
+    /// Applications this server is quarantined in, with the entry to restore
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub quarantines: Vec<QuarantineRecord>,
+}
+
+/// Annotations sidecar file, keyed by server name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AnnotationStore {
+    #[serde(skip)]
+    path: PathBuf,
+    pub servers: HashMap<String, ServerAnnotations>,
+}
+
+impl AnnotationStore {
+    /// Load annotations from disk, starting empty if the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut store = if path.exists() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read annotations: {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| "Failed to parse annotations")?
+        } else {
+            Self::default()
+        };
+
+        store.path = path.to_path_buf();
+        Ok(store)
+    }
+
+    /// Load annotations from their default location in the user's home directory
+    pub fn load_default() -> Result<Self> {
+        let path = dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
+            .join(".mcp-control")
+            .join("annotations.json");
+        Self::load(&path)
+    }
+
+    /// Save annotations to disk
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create annotations directory: {}", parent.display()))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .with_context(|| "Failed to serialize annotations")?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write annotations: {}", self.path.display()))?;
+
+        Ok(())
+    }
+
+    /// Get the annotations of a server, if any
+    pub fn get(&self, server_name: &str) -> Option<&ServerAnnotations> {
+        self.servers.get(server_name)
+    }
+
+    /// Get mutable annotations of a server, creating an empty entry if needed
+    pub fn entry(&mut self, server_name: &str) -> &mut ServerAnnotations {
+        self.servers.entry(server_name.to_string()).or_default()
+    }
+
+    /// Alternative a server was added with, if one was recorded
+    pub fn pinned_alternative(&self, server_name: &str) -> Option<&str> {
+        self.get(server_name).and_then(|a| a.alternative.as_deref())
+    }
+
+    /// Check whether a server is marked manual-only
+    pub fn is_manual_only(&self, server_name: &str) -> bool {
+        self.get(server_name).map(|a| a.manual_only).unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_annotations_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("annotations.json");
+
+        let mut store = AnnotationStore::load(&path).unwrap();
+        store.entry("browser").manual_only = true;
+        store.save().unwrap();
+
+        let store = AnnotationStore::load(&path).unwrap();
+        assert!(store.is_manual_only("browser"));
+        assert!(!store.is_manual_only("filesystem"));
+    }
+}
@@ -0,0 +1,508 @@
+use anyhow::{anyhow, Result};
+use chrono::NaiveDate;
+use serde_json::Value as JsonValue;
+
+use crate::analysis::{AnalysisResult, DetectedConfig};
+use crate::detection::{ApplicationProfile, ConfigFormat, McpServerConfig};
+use crate::filesystem::jsonc::parse_jsonc;
+use crate::filesystem::yaml_edit::{indent_of, is_content, parse_key, parse_yaml_view};
+use crate::models::UserPreferences;
+use super::manifest::{package_spec, PackageRegistry};
+use super::provenance::{entry_hash, managed_statuses, ManagedStatus, ProvenanceSidecar};
+
+/// Text every generated comment starts with, so it can be found again
+const MARKER: &str = "managed by MCP Control";
+
+/// Where a server entry came from, written as a comment above the entry
+#[derive(Debug, Clone, PartialEq)]
+pub struct ManagedComment {
+    /// Package the entry was installed from, e.g. `npm:@scope/pkg@1.2.3`
+    pub source: String,
+    pub added: NaiveDate,
+}
+
+impl ManagedComment {
+    /// Comment for a server installed from an analysis
+    pub fn from_analysis(result: &AnalysisResult, added: NaiveDate) -> Self {
+        Self { source: source_label(&result.config), added }
+    }
+
+    /// Comment for a server already in a config, naming the package it runs when there is one
+    pub fn for_server(server: &McpServerConfig, added: NaiveDate) -> Self {
+        let source = match package_spec(server) {
+            Some((registry, package, version)) => {
+                let registry = match registry {
+                    PackageRegistry::Npm => "npm:",
+                    PackageRegistry::PyPI => "pypi:",
+                };
+                match version {
+                    Some(version) => format!("{}{}@{}", registry, package, version),
+                    None => format!("{}{}", registry, package),
+                }
+            }
+            None => server.command.clone().unwrap_or_else(|| server.name.clone()),
+        };
+        Self { source, added }
+    }
+
+    fn render(&self) -> String {
+        format!("{} — source: {}, added {}", MARKER, self.source, self.added.format("%Y-%m-%d"))
+    }
+
+    fn parse(text: &str) -> Option<Self> {
+        let rest = text.strip_prefix(MARKER)?.trim_start_matches([' ', '—']);
+        let (source, added) = rest.strip_prefix("source: ")?.rsplit_once(", added ")?;
+        let added = NaiveDate::parse_from_str(added.trim(), "%Y-%m-%d").ok()?;
+        Some(Self { source: source.to_string(), added })
+    }
+}
+
+/// `registry:name@version` for a detected server, with the registry implied by its runtime
+fn source_label(config: &DetectedConfig) -> String {
+    let registry = match config.runtime.as_deref() {
+        Some("node") | Some("bun") => "npm:",
+        Some("python") => "pypi:",
+        _ => "",
+    };
+    match &config.version {
+        Some(version) => format!("{}{}@{}", registry, config.name, version),
+        None => format!("{}{}", registry, config.name),
+    }
+}
+
+/// Writes provenance comments above the server entries MCP Control manages.
+///
+/// YAML and TOML configs get `#` comments, and JSON configs of clients that read them as
+/// JSONC get `//` comments. Plain JSON configs keep their provenance in the sidecar, as
+/// every format does when stamping is turned off in the preferences. TOML configs are
+/// rewritten whole, so stamp again after every write.
+#[derive(Debug, Clone)]
+pub struct CommentStamper {
+    enabled: bool,
+}
+
+impl CommentStamper {
+    pub fn new(enabled: bool) -> Self {
+        Self { enabled }
+    }
+
+    /// Stamper following the user's global comment stamping setting
+    pub fn from_preferences(preferences: &UserPreferences) -> Self {
+        Self::new(preferences.stamp_config_comments)
+    }
+
+    /// Write or refresh the comment above a server entry, returning the new content.
+    ///
+    /// An existing comment is replaced in place and keeps its original `added` date.
+    pub fn stamp(
+        &self,
+        profile: &ApplicationProfile,
+        content: &str,
+        server: &str,
+        comment: &ManagedComment,
+        sidecar: &mut ProvenanceSidecar,
+    ) -> Result<String> {
+        let prefix = match comment_prefix(profile) {
+            Some(prefix) if self.enabled => prefix,
+            _ => {
+                let entry = parse_entry(profile, content, server)?;
+                sidecar.record(&profile.id, server, &entry_hash(&entry));
+                return Ok(content.to_string());
+            }
+        };
+
+        let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        let line = find_entry_line(profile, &lines, server)
+            .ok_or_else(|| anyhow!("Server '{}' not found in the {} config", server, profile.name))?;
+        let indent = " ".repeat(indent_of(&lines[line]));
+
+        match existing_comment(&lines, line, prefix) {
+            Some(existing) => {
+                let previous = ManagedComment::parse(&existing);
+                let comment = ManagedComment {
+                    added: previous.map(|previous| previous.added).unwrap_or(comment.added),
+                    ..comment.clone()
+                };
+                lines[line - 1] = format!("{}{} {}", indent, prefix, comment.render());
+            }
+            None => lines.insert(line, format!("{}{} {}", indent, prefix, comment.render())),
+        }
+
+        Ok(join_lines(lines, content))
+    }
+
+    /// Remove the comment above a server entry and forget its sidecar record.
+    ///
+    /// Removal ignores the stamping setting so entries stamped earlier are cleaned up too.
+    pub fn unstamp(
+        &self,
+        profile: &ApplicationProfile,
+        content: &str,
+        server: &str,
+        sidecar: &mut ProvenanceSidecar,
+    ) -> Result<String> {
+        sidecar.forget(&profile.id, server);
+
+        let Some(prefix) = comment_prefix(profile) else {
+            return Ok(content.to_string());
+        };
+        let mut lines: Vec<String> = content.lines().map(String::from).collect();
+        let Some(line) = find_entry_line(profile, &lines, server) else {
+            return Ok(content.to_string());
+        };
+        if existing_comment(&lines, line, prefix).is_none() {
+            return Ok(content.to_string());
+        }
+
+        lines.remove(line - 1);
+        Ok(join_lines(lines, content))
+    }
+
+    /// Stamp every one of `servers` that MCP Control manages in a freshly written config.
+    ///
+    /// `previous` is the file as it was before the write; comments found there keep their
+    /// `added` date even when the write dropped them. Entries whose line cannot be found,
+    /// such as servers in a single-line JSON object, are left without a comment.
+    pub fn stamp_managed(
+        &self,
+        profile: &ApplicationProfile,
+        content: &str,
+        previous: Option<&str>,
+        servers: &[McpServerConfig],
+        sidecar: &mut ProvenanceSidecar,
+        today: NaiveDate,
+    ) -> Result<String> {
+        let managed: Vec<String> = managed_statuses(profile, &parse_config(profile, content)?, sidecar)
+            .into_iter()
+            .filter(|(_, status)| *status == ManagedStatus::Managed)
+            .map(|(name, _)| name)
+            .collect();
+
+        let mut content = content.to_string();
+        for server in servers.iter().filter(|server| managed.contains(&server.name)) {
+            let added = previous
+                .and_then(|previous| existing_entry_comment(profile, previous, &server.name))
+                .map_or(today, |comment| comment.added);
+            match self.stamp(profile, &content, &server.name, &ManagedComment::for_server(server, added), sidecar) {
+                Ok(stamped) => content = stamped,
+                Err(e) => log::debug!("Not stamping '{}' in the {} config: {}", server.name, profile.name, e),
+            }
+        }
+        Ok(content)
+    }
+}
+
+/// Generated comment above a server entry, if it has one
+fn existing_entry_comment(profile: &ApplicationProfile, content: &str, server: &str) -> Option<ManagedComment> {
+    let prefix = comment_prefix(profile)?;
+    let lines: Vec<String> = content.lines().map(String::from).collect();
+    let line = find_entry_line(profile, &lines, server)?;
+    ManagedComment::parse(&existing_comment(&lines, line, prefix)?)
+}
+
+impl Default for CommentStamper {
+    fn default() -> Self {
+        Self::new(true)
+    }
+}
+
+/// Line comment syntax of the application's config, if it allows comments
+fn comment_prefix(profile: &ApplicationProfile) -> Option<&'static str> {
+    match profile.config_format {
+        ConfigFormat::Yaml | ConfigFormat::Toml => Some("#"),
+        ConfigFormat::Json if profile.metadata.accepts_jsonc => Some("//"),
+        _ => None,
+    }
+}
+
+/// Text of the generated comment directly above an entry, without the comment prefix
+fn existing_comment(lines: &[String], line: usize, prefix: &str) -> Option<String> {
+    let text = lines.get(line.checked_sub(1)?)?.trim().strip_prefix(prefix)?.trim();
+    text.starts_with(MARKER).then(|| text.to_string())
+}
+
+fn join_lines(lines: Vec<String>, original: &str) -> String {
+    let mut joined = lines.join("\n");
+    if original.ends_with('\n') {
+        joined.push('\n');
+    }
+    joined
+}
+
+fn parse_config(profile: &ApplicationProfile, content: &str) -> Result<JsonValue> {
+    Ok(match profile.config_format {
+        ConfigFormat::Yaml => serde_json::to_value(parse_yaml_view(content)?)?,
+        ConfigFormat::Toml => serde_json::to_value(content.parse::<toml::Value>()?)?,
+        _ => parse_jsonc(content)?,
+    })
+}
+
+/// Parse a server entry so its hash can be recorded in the sidecar
+fn parse_entry(profile: &ApplicationProfile, content: &str, server: &str) -> Result<JsonValue> {
+    let config = parse_config(profile, content)?;
+    profile
+        .servers_path()
+        .get(&config)
+        .and_then(|servers| servers.get(server))
+        .cloned()
+        .ok_or_else(|| anyhow!("Server '{}' not found in the {} config", server, profile.name))
+}
+
+/// Line where a server entry starts: its key in YAML and JSON, its table header or key in TOML
+fn find_entry_line(profile: &ApplicationProfile, lines: &[String], server: &str) -> Option<usize> {
+    let config = parse_config(profile, &lines.join("\n")).unwrap_or_default();
+    let servers_path = profile.servers_path().keys_in(&config);
+    let path: Vec<&str> = servers_path.iter().map(String::as_str).chain([server]).collect();
+    match profile.config_format {
+        ConfigFormat::Yaml => find_yaml_key(lines, &path),
+        ConfigFormat::Toml => find_toml_entry(lines, &path),
+        ConfigFormat::Json => find_json_key(lines, &path),
+        _ => None,
+    }
+}
+
+/// Walk nested pretty-printed JSON objects down `path`, one member per line
+fn find_json_key(lines: &[String], path: &[&str]) -> Option<usize> {
+    let key_of = |line: &str| -> Option<String> {
+        let line = line.trim_start();
+        let end = line.strip_prefix('"')?.find('"')? + 1;
+        line[end + 1..].trim_start().starts_with(':').then(|| serde_json::from_str(&line[..=end]).ok())?
+    };
+
+    let (mut start, mut end) = (0, lines.len());
+    let mut parent_indent = None;
+    let mut found = None;
+
+    for key in path {
+        let indent = (start..end)
+            .filter(|&i| key_of(&lines[i]).is_some())
+            .map(|i| indent_of(&lines[i]))
+            .find(|indent| parent_indent.map_or(true, |parent| *indent > parent))?;
+        let line = (start..end)
+            .find(|&i| indent_of(&lines[i]) == indent && key_of(&lines[i]).is_some_and(|name| name == *key))?;
+
+        end = (line + 1..end)
+            .find(|&i| is_content(&lines[i]) && indent_of(&lines[i]) < indent)
+            .unwrap_or(end);
+        start = line + 1;
+        parent_indent = Some(indent);
+        found = Some(line);
+    }
+    found
+}
+
+/// Walk nested block mappings down `path`, returning the line of the last key
+fn find_yaml_key(lines: &[String], path: &[&str]) -> Option<usize> {
+    let (mut start, mut end) = (0, lines.len());
+    let mut parent_indent = None;
+    let mut found = None;
+
+    for key in path {
+        let indent = (start..end)
+            .filter(|&i| is_content(&lines[i]))
+            .map(|i| indent_of(&lines[i]))
+            .find(|indent| parent_indent.map_or(true, |parent| *indent > parent))?;
+        let line = (start..end).find(|&i| {
+            is_content(&lines[i])
+                && indent_of(&lines[i]) == indent
+                && parse_key(&lines[i][indent..]).is_some_and(|(name, _)| name == *key)
+        })?;
+
+        end = (line + 1..end)
+            .find(|&i| is_content(&lines[i]) && indent_of(&lines[i]) <= indent)
+            .unwrap_or(end);
+        start = line + 1;
+        parent_indent = Some(indent);
+        found = Some(line);
+    }
+    found
+}
+
+/// Find `[servers.name]`, or `name = { ... }` inside `[servers]`
+fn find_toml_entry(lines: &[String], path: &[&str]) -> Option<usize> {
+    let header = |line: &str| -> Option<Vec<String>> {
+        let line = line.trim();
+        if line.starts_with("[[") {
+            return None;
+        }
+        let inner = line.strip_prefix('[')?.split(']').next()?;
+        Some(split_toml_key(inner))
+    };
+
+    if let Some(line) = lines.iter().position(|line| header(line).is_some_and(|keys| keys == path)) {
+        return Some(line);
+    }
+
+    let (server, table) = path.split_last()?;
+    let table_line = lines.iter().position(|line| header(line).is_some_and(|keys| keys == table))?;
+    (table_line + 1..lines.len())
+        .take_while(|&i| header(&lines[i]).is_none())
+        .find(|&i| {
+            lines[i]
+                .split_once('=')
+                .is_some_and(|(key, _)| split_toml_key(key) == [*server])
+        })
+}
+
+/// Split a dotted TOML key, unquoting its parts
+fn split_toml_key(key: &str) -> Vec<String> {
+    let mut parts = Vec::new();
+    let mut current = String::new();
+    let mut quote = None;
+    for c in key.chars() {
+        match (quote, c) {
+            (None, '"' | '\'') => quote = Some(c),
+            (Some(q), c) if c == q => quote = None,
+            (None, '.') => parts.push(std::mem::take(&mut current).trim().to_string()),
+            _ => current.push(c),
+        }
+    }
+    parts.push(current.trim().to_string());
+    parts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::ApplicationRegistry;
+    use tempfile::TempDir;
+
+    const YAML: &str = "# Servers for the team\nmcpServers:\n  fetch:\n    command: uvx\n    args: [mcp-server-fetch]\n  weather:\n    command: npx\n    args: [-y, \"@acme/weather-mcp\"]\n";
+
+    const TOML: &str = "model = \"o3\"\n\n[mcpServers.weather]\ncommand = \"npx\"\nargs = [\"-y\", \"@acme/weather-mcp\"]\n";
+
+    fn profile(format: ConfigFormat) -> ApplicationProfile {
+        let mut profile = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        profile.id = format!("{:?}-app", format).to_lowercase();
+        profile.config_format = format;
+        profile
+    }
+
+    fn comment(source: &str, added: &str) -> ManagedComment {
+        ManagedComment {
+            source: source.to_string(),
+            added: NaiveDate::parse_from_str(added, "%Y-%m-%d").unwrap(),
+        }
+    }
+
+    fn sidecar(temp_dir: &TempDir) -> ProvenanceSidecar {
+        ProvenanceSidecar::load(&temp_dir.path().join("provenance.json")).unwrap()
+    }
+
+    #[test]
+    fn test_inserts_comment_above_yaml_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let profile = profile(ConfigFormat::Yaml);
+        let stamped = CommentStamper::default()
+            .stamp(&profile, YAML, "weather", &comment("npm:@acme/weather-mcp@1.2.3", "2025-01-10"), &mut sidecar(&temp_dir))
+            .unwrap();
+
+        assert_eq!(
+            stamped,
+            YAML.replace(
+                "  weather:\n",
+                "  # managed by MCP Control — source: npm:@acme/weather-mcp@1.2.3, added 2025-01-10\n  weather:\n"
+            )
+        );
+        assert_eq!(parse_yaml_view(&stamped).unwrap(), parse_yaml_view(YAML).unwrap());
+    }
+
+    #[test]
+    fn test_version_bump_updates_comment_in_place_and_removal_is_clean() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut sidecar = sidecar(&temp_dir);
+        let profile = profile(ConfigFormat::Toml);
+        let stamper = CommentStamper::default();
+
+        let first = stamper
+            .stamp(&profile, TOML, "weather", &comment("npm:@acme/weather-mcp@1.2.3", "2025-01-10"), &mut sidecar)
+            .unwrap();
+        let bumped = stamper
+            .stamp(&profile, &first, "weather", &comment("npm:@acme/weather-mcp@1.3.0", "2025-03-02"), &mut sidecar)
+            .unwrap();
+
+        assert_eq!(bumped.matches(MARKER).count(), 1);
+        assert!(bumped.contains(
+            "# managed by MCP Control — source: npm:@acme/weather-mcp@1.3.0, added 2025-01-10\n[mcpServers.weather]"
+        ));
+
+        assert_eq!(stamper.unstamp(&profile, &bumped, "weather", &mut sidecar).unwrap(), TOML);
+    }
+
+    #[test]
+    fn test_json_and_disabled_stamping_use_the_sidecar() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut sidecar = sidecar(&temp_dir);
+        let json = r#"{"mcpServers": {"weather": {"command": "npx", "args": ["-y", "@acme/weather-mcp@1.2.3"]}}}"#;
+        let note = comment("npm:@acme/weather-mcp@1.2.3", "2025-01-10");
+
+        let written = CommentStamper::default().stamp(&profile(ConfigFormat::Json), json, "weather", &note, &mut sidecar).unwrap();
+        assert_eq!(written, json);
+        assert_eq!(sidecar.records.len(), 1);
+
+        let yaml_profile = profile(ConfigFormat::Yaml);
+        let written = CommentStamper::new(false).stamp(&yaml_profile, YAML, "weather", &note, &mut sidecar).unwrap();
+        assert_eq!(written, YAML);
+        assert_eq!(sidecar.records.len(), 2);
+
+        CommentStamper::new(false).unstamp(&yaml_profile, YAML, "weather", &mut sidecar).unwrap();
+        assert_eq!(sidecar.records.len(), 1);
+    }
+
+    fn server(name: &str, command: &str, args: &[&str]) -> McpServerConfig {
+        serde_json::from_value(serde_json::json!({
+            "name": name,
+            "command": command,
+            "args": args,
+            "env": {},
+            "cwd": null,
+            "server_type": "Stdio",
+            "metadata": {"description": null, "version": null, "author": null, "capabilities": [], "enabled": true, "source": "MainConfig"},
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_jsonc_configs_get_line_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut profile = profile(ConfigFormat::Json);
+        profile.metadata.accepts_jsonc = true;
+        let content = "{\n  // editor settings\n  \"mcpServers\": {\n    \"fetch\": {\n      \"command\": \"uvx\"\n    },\n    \"weather\": {\n      \"command\": \"npx\"\n    }\n  }\n}\n";
+
+        let stamped = CommentStamper::default()
+            .stamp(&profile, content, "weather", &comment("npm:@acme/weather-mcp@1.2.3", "2025-01-10"), &mut sidecar(&temp_dir))
+            .unwrap();
+
+        assert!(stamped.contains(
+            "    },\n    // managed by MCP Control — source: npm:@acme/weather-mcp@1.2.3, added 2025-01-10\n    \"weather\": {"
+        ));
+        assert_eq!(parse_jsonc(&stamped).unwrap(), parse_jsonc(content).unwrap());
+    }
+
+    #[test]
+    fn test_stamp_managed_keeps_added_date_across_rewrites() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut sidecar = sidecar(&temp_dir);
+        let profile = profile(ConfigFormat::Toml);
+        let stamper = CommentStamper::default();
+        let servers = [server("weather", "npx", &["-y", "@acme/weather-mcp@1.3.0"])];
+        let previous = TOML.replace(
+            "[mcpServers.weather]",
+            "# managed by MCP Control — source: npm:@acme/weather-mcp@1.2.3, added 2025-01-10\n[mcpServers.weather]",
+        );
+        let rewritten = "[mcpServers.weather]\ncommand = \"npx\"\nargs = [\"-y\", \"@acme/weather-mcp@1.3.0\"]\n\n[mcpServers.notes]\ncommand = \"uvx\"\n";
+
+        // Only entries MCP Control manages are stamped
+        let today = NaiveDate::from_ymd_opt(2025, 3, 2).unwrap();
+        let unmanaged = stamper.stamp_managed(&profile, rewritten, Some(&previous), &servers, &mut sidecar, today).unwrap();
+        assert_eq!(unmanaged, rewritten);
+
+        let entry = serde_json::json!({"command": "npx", "args": ["-y", "@acme/weather-mcp@1.3.0"]});
+        sidecar.record(&profile.id, "weather", &entry_hash(&entry));
+        let stamped = stamper.stamp_managed(&profile, rewritten, Some(&previous), &servers, &mut sidecar, today).unwrap();
+        assert!(stamped.starts_with(
+            "# managed by MCP Control — source: npm:@acme/weather-mcp@1.3.0, added 2025-01-10\n[mcpServers.weather]"
+        ));
+        assert_eq!(stamped.matches(MARKER).count(), 1);
+    }
+}
@@ -0,0 +1,298 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::detection::ApplicationProfile;
+use crate::filesystem::jsonc::{has_jsonc_syntax, parse_jsonc, update_jsonc_preserving};
+use crate::filesystem::{BackupService, BackupType, ConfigFileService};
+
+/// Where an installation keeps its Cursor MCP servers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum CursorInstallKind {
+    /// Servers only in ~/.cursor/mcp.json
+    Fresh,
+    /// Servers only in the nested `mcp.servers` block of a legacy settings.json
+    Legacy,
+    /// Servers in both places; the legacy entries need migrating
+    Mixed,
+    /// No servers configured anywhere
+    Unconfigured,
+}
+
+impl CursorInstallKind {
+    /// Whether legacy entries are waiting to be moved
+    pub fn needs_migration(&self) -> bool {
+        matches!(self, Self::Legacy | Self::Mixed)
+    }
+}
+
+/// Outcome of moving legacy entries into ~/.cursor/mcp.json
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CursorMigrationReport {
+    /// File the entries were moved into
+    pub target: PathBuf,
+    /// Servers copied into the target
+    pub migrated: Vec<String>,
+    /// Servers already present in the target with different settings; left in the legacy file
+    pub conflicts: Vec<String>,
+    /// Legacy files whose nested block was removed or trimmed
+    pub cleaned: Vec<PathBuf>,
+    /// Backups taken of each file before it was rewritten
+    pub backups: Vec<PathBuf>,
+}
+
+/// A Cursor config file as read from disk
+struct ConfigFile {
+    path: PathBuf,
+    /// Original text, kept so comments survive the rewrite
+    content: String,
+    config: JsonValue,
+}
+
+/// Classify an installation by where its servers are
+pub fn classify_install(profile: &ApplicationProfile) -> Result<CursorInstallKind> {
+    let target = profile.resolved_config_path();
+    let has_current = read_json(&target)?
+        .and_then(|file| file.config.get("mcpServers").and_then(|s| s.as_object()).map(|s| !s.is_empty()))
+        .unwrap_or(false);
+    let has_legacy = !legacy_files(profile)?.is_empty();
+
+    Ok(match (has_current, has_legacy) {
+        (true, false) => CursorInstallKind::Fresh,
+        (false, true) => CursorInstallKind::Legacy,
+        (true, true) => CursorInstallKind::Mixed,
+        (false, false) => CursorInstallKind::Unconfigured,
+    })
+}
+
+/// Move servers from legacy settings.json files into ~/.cursor/mcp.json.
+///
+/// Entries already in the target are kept; identical legacy copies are dropped and
+/// differing ones are reported as conflicts. Every file is backed up before it is
+/// rewritten, and the stale nested block is only removed from a legacy file after the
+/// target has been written successfully.
+pub fn migrate_legacy_servers(profile: &ApplicationProfile, backups: &BackupService) -> Result<CursorMigrationReport> {
+    let target = profile.resolved_config_path();
+    let mut report = CursorMigrationReport { target: target.clone(), ..Default::default() };
+
+    let legacy = legacy_files(profile)?;
+    if legacy.is_empty() {
+        return Ok(report);
+    }
+
+    let current = read_json(&target)?;
+    let mut config = current.as_ref().map_or_else(|| serde_json::json!({}), |file| file.config.clone());
+    if !config.get("mcpServers").is_some_and(|s| s.is_object()) {
+        config["mcpServers"] = serde_json::json!({});
+    }
+
+    // Names to drop from each legacy file once the target is safely written
+    let mut settled: Vec<(ConfigFile, Vec<String>)> = Vec::new();
+    for file in legacy {
+        let servers = config["mcpServers"].as_object_mut().expect("mcpServers is an object");
+        let mut drop = Vec::new();
+
+        for (name, entry) in legacy_servers(&file.config).into_iter().flatten() {
+            match servers.get(name) {
+                None => {
+                    servers.insert(name.clone(), entry.clone());
+                    report.migrated.push(name.clone());
+                    drop.push(name.clone());
+                }
+                Some(existing) if existing == entry => drop.push(name.clone()),
+                Some(_) => report.conflicts.push(name.clone()),
+            }
+        }
+        settled.push((file, drop));
+    }
+
+    if !report.migrated.is_empty() {
+        if let Some(current) = &current {
+            report.backups.push(backup(backups, &current.path)?);
+        }
+        write_json(&target, &config, current.as_ref().map(|file| file.content.as_str()))?;
+    }
+
+    for (mut file, drop) in settled {
+        if drop.is_empty() || !remove_legacy_entries(&mut file.config, &drop) {
+            continue;
+        }
+        report.backups.push(backup(backups, &file.path)?);
+        write_json(&file.path, &file.config, Some(&file.content))?;
+        report.cleaned.push(file.path);
+    }
+
+    Ok(report)
+}
+
+/// Legacy settings files that still hold servers, with their parsed contents
+fn legacy_files(profile: &ApplicationProfile) -> Result<Vec<ConfigFile>> {
+    let mut files = Vec::new();
+    for path in profile.expanded_config_paths().into_iter().skip(1) {
+        if let Some(file) = read_json(&path)? {
+            if legacy_servers(&file.config).is_some_and(|servers| !servers.is_empty()) {
+                files.push(file);
+            }
+        }
+    }
+    Ok(files)
+}
+
+fn legacy_servers(config: &JsonValue) -> Option<&serde_json::Map<String, JsonValue>> {
+    config.get("mcp").and_then(|m| m.get("servers")).and_then(|s| s.as_object())
+}
+
+/// Remove migrated entries, dropping the whole block once it is empty.
+///
+/// Returns whether anything changed.
+fn remove_legacy_entries(config: &mut JsonValue, names: &[String]) -> bool {
+    let Some(mcp) = config.get_mut("mcp").and_then(|m| m.as_object_mut()) else {
+        return false;
+    };
+    let Some(servers) = mcp.get_mut("servers").and_then(|s| s.as_object_mut()) else {
+        return false;
+    };
+
+    let before = servers.len();
+    servers.retain(|name, _| !names.contains(name));
+    let changed = servers.len() != before;
+
+    if servers.is_empty() {
+        mcp.remove("servers");
+    }
+    if mcp.is_empty() {
+        if let Some(root) = config.as_object_mut() {
+            root.remove("mcp");
+        }
+    }
+    changed
+}
+
+/// Read a config that may be JSONC; Cursor's settings.json usually has comments
+fn read_json(path: &Path) -> Result<Option<ConfigFile>> {
+    if !path.is_file() {
+        return Ok(None);
+    }
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read Cursor config: {}", path.display()))?;
+    let config = parse_jsonc(&content)
+        .with_context(|| format!("Failed to parse Cursor config: {}", path.display()))?;
+    Ok(Some(ConfigFile { path: path.to_path_buf(), content, config }))
+}
+
+fn backup(backups: &BackupService, path: &Path) -> Result<PathBuf> {
+    let metadata = backups.create_backup(path, BackupType::PreUpdate, Some("Before moving Cursor MCP servers".to_string()))?;
+    Ok(metadata.backup_path)
+}
+
+/// Write a config atomically; when the original has comments, only the changed members are rewritten
+fn write_json(path: &Path, config: &JsonValue, original: Option<&str>) -> Result<()> {
+    let content = match original {
+        Some(original) if has_jsonc_syntax(original) => update_jsonc_preserving(original, config)?,
+        _ => serde_json::to_string_pretty(config).with_context(|| "Failed to serialize Cursor config")?,
+    };
+    ConfigFileService::write_atomic(path, content)
+        .with_context(|| format!("Failed to write Cursor config: {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::ApplicationRegistry;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn cursor(temp_dir: &TempDir) -> ApplicationProfile {
+        let mut profile = ApplicationRegistry::new().get_application("cursor").unwrap().clone();
+        profile.set_config_path(temp_dir.path().join(".cursor/mcp.json").to_string_lossy());
+        profile.alt_config_paths = vec![temp_dir.path().join("User/settings.json").to_string_lossy().to_string()];
+        profile
+    }
+
+    fn backups(temp_dir: &TempDir) -> BackupService {
+        BackupService::new(temp_dir.path().join("backups"), "test_user".to_string()).unwrap()
+    }
+
+    fn write(path: PathBuf, config: JsonValue) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, config.to_string()).unwrap();
+    }
+
+    fn read(path: &Path) -> JsonValue {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_fresh_install_uses_new_path_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let profile = cursor(&temp_dir);
+        assert_eq!(classify_install(&profile).unwrap(), CursorInstallKind::Unconfigured);
+
+        write(temp_dir.path().join(".cursor/mcp.json"), json!({"mcpServers": {"github": {"command": "npx"}}}));
+        assert_eq!(classify_install(&profile).unwrap(), CursorInstallKind::Fresh);
+        assert_eq!(profile.get_mcp_servers_path(), vec!["mcpServers"]);
+
+        let report = migrate_legacy_servers(&profile, &backups(&temp_dir)).unwrap();
+        assert!(report.migrated.is_empty() && report.cleaned.is_empty());
+    }
+
+    #[test]
+    fn test_legacy_install_is_moved_to_new_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let profile = cursor(&temp_dir);
+        let settings = temp_dir.path().join("User/settings.json");
+        let original = r#"{
+  // Bigger text for demos
+  "editor.fontSize": 14,
+  "mcp": {
+    "servers": {
+      "github": {"command": "npx", "args": ["-y", "server-github"]},
+    },
+  },
+}
+"#;
+        fs::create_dir_all(settings.parent().unwrap()).unwrap();
+        fs::write(&settings, original).unwrap();
+        assert_eq!(classify_install(&profile).unwrap(), CursorInstallKind::Legacy);
+
+        let report = migrate_legacy_servers(&profile, &backups(&temp_dir)).unwrap();
+        assert_eq!(report.migrated, vec!["github"]);
+        assert_eq!(report.cleaned, vec![settings.clone()]);
+        assert_eq!(report.backups.len(), 1);
+        assert_eq!(fs::read_to_string(&report.backups[0]).unwrap(), original);
+
+        let migrated = read(&temp_dir.path().join(".cursor/mcp.json"));
+        assert_eq!(migrated["mcpServers"]["github"]["args"], json!(["-y", "server-github"]));
+        let cleaned = fs::read_to_string(&settings).unwrap();
+        assert!(cleaned.contains("// Bigger text for demos"), "{}", cleaned);
+        assert_eq!(parse_jsonc(&cleaned).unwrap(), json!({"editor.fontSize": 14}));
+        assert_eq!(classify_install(&profile).unwrap(), CursorInstallKind::Fresh);
+    }
+
+    #[test]
+    fn test_mixed_install_keeps_conflicts_in_legacy_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let profile = cursor(&temp_dir);
+        let settings = temp_dir.path().join("User/settings.json");
+        write(temp_dir.path().join(".cursor/mcp.json"), json!({
+            "mcpServers": {"github": {"command": "npx"}, "slack": {"command": "npx"}}
+        }));
+        write(settings.clone(), json!({"mcp": {"servers": {
+            "github": {"command": "npx"},
+            "slack": {"command": "uvx"},
+            "notes": {"command": "notes-mcp"}
+        }}}));
+        assert!(classify_install(&profile).unwrap().needs_migration());
+
+        let report = migrate_legacy_servers(&profile, &backups(&temp_dir)).unwrap();
+        assert_eq!(report.migrated, vec!["notes"]);
+        assert_eq!(report.conflicts, vec!["slack"]);
+
+        let servers = read(&temp_dir.path().join(".cursor/mcp.json"))["mcpServers"].clone();
+        assert_eq!(servers["slack"]["command"], "npx");
+        assert_eq!(servers["notes"]["command"], "notes-mcp");
+        assert_eq!(read(&settings), json!({"mcp": {"servers": {"slack": {"command": "uvx"}}}}));
+    }
+}
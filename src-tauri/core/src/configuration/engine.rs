@@ -6,6 +6,7 @@ use uuid::Uuid;
 
 use crate::detection::{ApplicationDetector, ConfigValidator, McpServerConfig, ApplicationProfile};
 use crate::filesystem::ConfigFileService;
+use super::home_paths::HomeRelocator;
 use super::{ConfigurationStore, SyncManager};
 
 /// Central configuration management engine
@@ -87,8 +88,16 @@ impl ConfigurationEngine {
         let validation_result = self.validator.validate_application_config(app).await?;
         
         if validation_result.is_valid {
-            // Add extracted servers to our store
-            for server in validation_result.mcp_servers {
+            // Add extracted servers to our store, re-homing paths copied from another user's machine
+            let relocator = HomeRelocator::new();
+            for mut server in validation_result.mcp_servers {
+                if let Some(relocator) = &relocator {
+                    let (rehomed, findings) = relocator.rehome_server(&server);
+                    for finding in findings.iter().filter(|f| f.needs_attention()) {
+                        log::warn!("Imported from {}: {}", app.name, finding);
+                    }
+                    server = rehomed;
+                }
                 self.store.add_server(server, Some(app.id.to_string()))?;
             }
             
@@ -237,6 +246,8 @@ mod tests {
             args: vec!["server.js".to_string()],
             env: HashMap::new(),
             cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
             server_type: crate::detection::ServerType::Stdio,
             metadata: crate::detection::ServerMetadata {
                 version: Some("1.0.0".to_string()),
@@ -0,0 +1,227 @@
+use serde::{Deserialize, Serialize};
+
+use crate::detection::{ApplicationProfile, ExtensionRule, McpServerConfig};
+
+/// Options only some clients understand; clients that don't declare them would misread them
+const CLIENT_SPECIFIC_OPTIONS: &[&str] = &["autoApprove", "alwaysAllow", "timeout"];
+
+/// Per-server option that was not carried over to a client
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StrippedOption {
+    pub server_name: String,
+    pub option: String,
+    pub reason: String,
+}
+
+/// How an application handles a per-server option, from its profile's `extension_options`.
+///
+/// Options the profile doesn't list are preserved, so fields such as `url`, `headers`
+/// or Zed's `settings` survive a read-then-sync; only the client-specific approval and
+/// timeout options are stripped unless the profile declares them.
+pub fn extension_rule(app: &ApplicationProfile, option: &str) -> ExtensionRule {
+    match app.metadata.extension_options.get(option) {
+        Some(rule) => rule.clone(),
+        None if CLIENT_SPECIFIC_OPTIONS.contains(&option) => ExtensionRule::Strip,
+        None => ExtensionRule::Preserve,
+    }
+}
+
+/// Rewrite a server's extra options for the target application.
+///
+/// Returns the translated server and the options that were dropped.
+pub fn translate_extension_fields(
+    app: &ApplicationProfile,
+    server: &McpServerConfig,
+) -> (McpServerConfig, Vec<StrippedOption>) {
+    let mut translated = server.clone();
+    translated.extra_options.clear();
+    let mut stripped = Vec::new();
+
+    let mut keys: Vec<&String> = server.extra_options.keys().collect();
+    keys.sort();
+
+    for key in keys {
+        let value = &server.extra_options[key];
+        match extension_rule(app, key) {
+            ExtensionRule::Preserve => {
+                translated.extra_options.insert(key.clone(), value.clone());
+            }
+            ExtensionRule::Translate(target) => {
+                // An option already written under the target key takes precedence
+                translated.extra_options
+                    .entry(target)
+                    .or_insert_with(|| value.clone());
+            }
+            ExtensionRule::Strip => stripped.push(StrippedOption {
+                server_name: server.name.clone(),
+                option: key.clone(),
+                reason: format!("'{}' is not supported by {}", key, app.name),
+            }),
+        }
+    }
+
+    for option in &stripped {
+        log::warn!("Not syncing option '{}' of server '{}': {}", option.option, option.server_name, option.reason);
+    }
+
+    (translated, stripped)
+}
+
+/// Carry a server's working directory over to the target application.
+///
+/// Clients without native support either get a shell wrapper that changes
+/// directory before launching (only when `allow_wrapper` is set) or lose the
+/// cwd, which is reported as a stripped option.
+pub fn translate_cwd(
+    app: &ApplicationProfile,
+    server: &McpServerConfig,
+    allow_wrapper: bool,
+) -> (McpServerConfig, Option<StrippedOption>) {
+    let mut translated = server.clone();
+    let Some(cwd) = server.cwd.as_deref() else {
+        return (translated, None);
+    };
+    if app.metadata.supports_cwd {
+        return (translated, None);
+    }
+
+    translated.cwd = None;
+    match server.command.as_deref() {
+        Some(command) if allow_wrapper => {
+            let (shell, args) = cwd_wrapper(cwd, command, &server.args);
+            translated.command = Some(shell);
+            translated.args = args;
+            (translated, None)
+        }
+        _ => {
+            let stripped = StrippedOption {
+                server_name: server.name.clone(),
+                option: "cwd".to_string(),
+                reason: format!("{} does not support a working directory; enable the cwd wrapper to keep it", app.name),
+            };
+            log::warn!("Not syncing option 'cwd' of server '{}': {}", stripped.server_name, stripped.reason);
+            (translated, Some(stripped))
+        }
+    }
+}
+
+/// Build a shell command that changes directory and then runs the server
+fn cwd_wrapper(cwd: &str, command: &str, args: &[String]) -> (String, Vec<String>) {
+    let words = std::iter::once(command).chain(args.iter().map(String::as_str));
+
+    if cfg!(windows) {
+        let line: Vec<String> = words
+            .map(|w| if w.contains(' ') { format!("\"{}\"", w) } else { w.to_string() })
+            .collect();
+        ("cmd".to_string(), vec!["/C".to_string(), format!("cd /d \"{}\" && {}", cwd, line.join(" "))])
+    } else {
+        let line: Vec<String> = words.map(shell_quote).collect();
+        ("sh".to_string(), vec!["-c".to_string(), format!("cd {} && exec {}", shell_quote(cwd), line.join(" "))])
+    }
+}
+
+/// Quote a word for POSIX sh when it contains special characters
+pub(crate) fn shell_quote(word: &str) -> String {
+    let safe = !word.is_empty()
+        && word.chars().all(|c| c.is_ascii_alphanumeric() || "-_./=@:,+".contains(c));
+    if safe {
+        word.to_string()
+    } else {
+        format!("'{}'", word.replace('\'', "'\\''"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{ApplicationRegistry, ConfigSource, ServerMetadata, ServerType};
+    use std::collections::HashMap;
+
+    fn cline_style_server() -> McpServerConfig {
+        let mut server = McpServerConfig {
+            name: "github".to_string(),
+            command: Some("npx".to_string()),
+            args: vec!["-y".to_string(), "@modelcontextprotocol/server-github".to_string()],
+            env: HashMap::new(),
+            cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
+            server_type: ServerType::Stdio,
+            metadata: ServerMetadata {
+                description: None,
+                version: None,
+                author: None,
+                capabilities: vec![],
+                enabled: true,
+                source: ConfigSource::MainConfig,
+            },
+        };
+        server.extra_options.insert("autoApprove".to_string(), serde_json::json!(["list_issues"]));
+        server.extra_options.insert("timeout".to_string(), serde_json::json!(60));
+        server
+    }
+
+    /// Profile whose registry entry declares `extension_options`
+    fn profile(id: &str, extension_options: serde_json::Value) -> ApplicationProfile {
+        let mut profile = ApplicationRegistry::new()
+            .get_application("claude-desktop")
+            .unwrap()
+            .clone();
+        profile.id = id.to_string();
+        profile.metadata.extension_options = serde_json::from_value(extension_options).unwrap();
+        profile
+    }
+
+    fn cline() -> ApplicationProfile {
+        profile("cline", serde_json::json!({
+            "autoApprove": "preserve",
+            "alwaysAllow": {"translate": "autoApprove"},
+            "timeout": "preserve"
+        }))
+    }
+
+    fn roo_code() -> ApplicationProfile {
+        profile("roo-code", serde_json::json!({
+            "alwaysAllow": "preserve",
+            "autoApprove": {"translate": "alwaysAllow"},
+            "timeout": "preserve"
+        }))
+    }
+
+    #[test]
+    fn test_strict_client_strips_options() {
+        let (translated, stripped) = translate_extension_fields(&profile("claude-desktop", serde_json::json!({})), &cline_style_server());
+
+        assert!(translated.extra_options.is_empty());
+        let options: Vec<&str> = stripped.iter().map(|s| s.option.as_str()).collect();
+        assert_eq!(options, vec!["autoApprove", "timeout"]);
+    }
+
+    #[test]
+    fn test_unknown_options_round_trip() {
+        let mut server = cline_style_server();
+        server.extra_options.insert("url".to_string(), serde_json::json!("https://mcp.example.com/sse"));
+        server.extra_options.insert("headers".to_string(), serde_json::json!({"X-Team": "docs"}));
+        server.extra_options.insert("settings".to_string(), serde_json::json!({"timeout": 30}));
+
+        for app in [profile("claude-desktop", serde_json::json!({})), profile("zed", serde_json::json!({})), cline()] {
+            let (translated, _) = translate_extension_fields(&app, &server);
+            for key in ["url", "headers", "settings"] {
+                assert_eq!(translated.extra_options.get(key), server.extra_options.get(key), "{} / {}", app.id, key);
+            }
+        }
+        let (translated, stripped) = translate_extension_fields(&cline(), &server);
+        assert!(stripped.is_empty());
+        assert_eq!(translated.extra_options["autoApprove"], serde_json::json!(["list_issues"]));
+    }
+
+    #[test]
+    fn test_equivalent_option_is_translated() {
+        let (translated, stripped) = translate_extension_fields(&roo_code(), &cline_style_server());
+
+        assert!(stripped.is_empty());
+        assert_eq!(translated.extra_options["alwaysAllow"], serde_json::json!(["list_issues"]));
+        assert_eq!(translated.extra_options["timeout"], serde_json::json!(60));
+        assert!(!translated.extra_options.contains_key("autoApprove"));
+    }
+}
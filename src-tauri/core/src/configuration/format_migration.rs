@@ -0,0 +1,325 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::analysis::compatibility::parse_semver;
+use crate::detection::{ApplicationProfile, ConfigFormat, ConfigSchema, FormatTransition};
+use crate::filesystem::jsonc::parse_jsonc;
+use crate::filesystem::yaml_edit::parse_yaml_view;
+use crate::filesystem::{BackupService, BackupType, ConfigFileService, FsProvider, PathUtils};
+
+/// Suffix of the note left where a converted config used to be
+const TOMBSTONE_SUFFIX: &str = "moved.txt";
+
+/// Name Continue gives a local config.yaml
+const CONTINUE_CONFIG_NAME: &str = "Local Assistant";
+
+/// A config still in a format the installed application version no longer reads
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FormatConversion {
+    pub from_path: PathBuf,
+    pub from_format: ConfigFormat,
+    pub to_path: PathBuf,
+    pub to_format: ConfigFormat,
+    /// Document layout the new format expects
+    #[serde(default)]
+    pub to_schema: Option<ConfigSchema>,
+    /// Application version that introduced the new format
+    pub since_version: Option<String>,
+}
+
+/// Outcome of a completed conversion
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversionReport {
+    /// File written in the new format
+    pub target: PathBuf,
+    /// Backup of the legacy file
+    pub backup_path: PathBuf,
+    /// Note explaining where the config went; None when the file was converted in place
+    pub tombstone: Option<PathBuf>,
+    /// Top-level settings the new layout has no place for; they are only kept in the backup
+    #[serde(default)]
+    pub not_carried: Vec<String>,
+}
+
+/// The history entry the installed version expects; None when the version is unknown
+pub fn expected_format<'a>(profile: &'a ApplicationProfile, installed_version: &str) -> Option<&'a FormatTransition> {
+    let installed = parse_semver(installed_version)?;
+    profile.metadata.format_history.iter().rev().find(|entry| match &entry.min_version {
+        Some(min_version) => parse_semver(min_version).is_some_and(|min| installed >= min),
+        None => true,
+    })
+}
+
+/// Find a legacy config that should be converted for the installed application version.
+///
+/// Uses the version from application detection; nothing is offered when it is unknown,
+/// or when a config already exists in the expected location and format.
+pub async fn pending_conversion(profile: &ApplicationProfile, fs: &dyn FsProvider) -> Result<Option<FormatConversion>> {
+    let Some(installed_version) = profile.metadata.version.as_deref() else {
+        return Ok(None);
+    };
+    let Some(expected) = expected_format(profile, installed_version) else {
+        return Ok(None);
+    };
+
+    let to_path = PathUtils::expand_tilde(&expected.path)?;
+    let current = fs.read_to_string(&to_path).await.ok();
+    if current.as_deref().is_some_and(|content| read_config(content, &expected.format).is_ok()) {
+        return Ok(None);
+    }
+
+    for legacy in profile.metadata.format_history.iter().rev().filter(|entry| *entry != expected) {
+        let from_path = PathUtils::expand_tilde(&legacy.path)?;
+        // Something unreadable already at the new location can only be an older format of the same file
+        if from_path != to_path && current.is_some() {
+            continue;
+        }
+        let Ok(content) = fs.read_to_string(&from_path).await else {
+            continue;
+        };
+        if read_config(&content, &legacy.format).is_ok() {
+            return Ok(Some(FormatConversion {
+                from_path,
+                from_format: legacy.format.clone(),
+                to_path,
+                to_format: expected.format.clone(),
+                to_schema: expected.schema,
+                since_version: expected.min_version.clone(),
+            }));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Rewrite a legacy config in the new format and layout.
+///
+/// The legacy file is backed up first and the new one is written through `file_service`,
+/// so externally managed configs are left alone. When the location changes, the legacy
+/// file is replaced by a short note pointing at the new one so nobody edits a dead file.
+pub async fn convert_legacy_config(
+    conversion: &FormatConversion,
+    backups: &BackupService,
+    file_service: &mut ConfigFileService,
+) -> Result<ConversionReport> {
+    file_service.ensure_writable(&conversion.from_path)?;
+    let content = fs::read_to_string(&conversion.from_path)
+        .with_context(|| format!("Failed to read {}", conversion.from_path.display()))?;
+    let config = read_config(&content, &conversion.from_format)
+        .with_context(|| format!("Failed to parse {}", conversion.from_path.display()))?;
+    let (config, not_carried) = match conversion.to_schema {
+        Some(ConfigSchema::ContinueBlocks) => to_continue_blocks(&config),
+        None => (config, Vec::new()),
+    };
+    let converted = write_config(&config, &conversion.to_format)?;
+
+    let backup = backups.create_backup(
+        &conversion.from_path,
+        BackupType::PreUpdate,
+        Some(format!("Before converting to {:?}", conversion.to_format)),
+    )?;
+
+    file_service.write_content(&conversion.to_path, converted).await
+        .with_context(|| format!("Failed to write {}", conversion.to_path.display()))?;
+
+    let mut tombstone = None;
+    if conversion.from_path != conversion.to_path {
+        let note_path = tombstone_path(&conversion.from_path);
+        let note = format!(
+            "This configuration was converted to {:?} and moved to:\n{}\n\nThe original is backed up at:\n{}\n",
+            conversion.to_format,
+            conversion.to_path.display(),
+            backup.backup_path.display()
+        );
+        fs::write(&note_path, note).with_context(|| format!("Failed to write {}", note_path.display()))?;
+        fs::remove_file(&conversion.from_path)
+            .with_context(|| format!("Failed to remove {}", conversion.from_path.display()))?;
+        tombstone = Some(note_path);
+    }
+
+    Ok(ConversionReport {
+        target: conversion.to_path.clone(),
+        backup_path: backup.backup_path,
+        tombstone,
+        not_carried,
+    })
+}
+
+/// Lay out a legacy Continue config.json the way config.yaml expects.
+///
+/// Servers come from `mcpServers` and from the older `experimental.modelContextProtocolServers`
+/// list. Returns the converted config and the top-level keys that were left behind.
+fn to_continue_blocks(config: &JsonValue) -> (JsonValue, Vec<String>) {
+    let mut servers = Vec::new();
+    for (name, entry) in config.get("mcpServers").and_then(|s| s.as_object()).into_iter().flatten() {
+        let mut block = entry.as_object().cloned().unwrap_or_default();
+        block.insert("name".to_string(), JsonValue::String(name.clone()));
+        servers.push(JsonValue::Object(block));
+    }
+
+    let experimental = config.get("experimental").and_then(|e| e.as_object());
+    let legacy_servers = experimental.and_then(|e| e.get("modelContextProtocolServers")).and_then(|s| s.as_array());
+    for (index, entry) in legacy_servers.into_iter().flatten().enumerate() {
+        // Entries hold the launch details under `transport`; stdio is the YAML default
+        let mut block = entry.get("transport").and_then(|t| t.as_object()).cloned().unwrap_or_default();
+        if block.get("type").and_then(|t| t.as_str()) == Some("stdio") {
+            block.remove("type");
+        }
+        let name = entry.get("name").and_then(|n| n.as_str()).map_or_else(|| format!("mcp-server-{}", index + 1), str::to_string);
+        block.insert("name".to_string(), JsonValue::String(name));
+        servers.push(JsonValue::Object(block));
+    }
+
+    let not_carried = config
+        .as_object()
+        .into_iter()
+        .flatten()
+        .filter(|(key, value)| match key.as_str() {
+            "name" | "version" | "schema" | "mcpServers" => false,
+            "experimental" => !value.as_object().is_some_and(|e| e.keys().all(|k| k == "modelContextProtocolServers")),
+            _ => true,
+        })
+        .map(|(key, _)| key.clone())
+        .collect();
+
+    let converted = serde_json::json!({
+        "name": config.get("name").and_then(|n| n.as_str()).unwrap_or(CONTINUE_CONFIG_NAME),
+        "version": config.get("version").and_then(|v| v.as_str()).unwrap_or("1.0.0"),
+        "schema": "v1",
+        "mcpServers": servers,
+    });
+    (converted, not_carried)
+}
+
+fn tombstone_path(path: &Path) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(TOMBSTONE_SUFFIX);
+    path.with_file_name(name)
+}
+
+/// Parse config content with the reader for its format
+fn read_config(content: &str, format: &ConfigFormat) -> Result<JsonValue> {
+    match format {
+        ConfigFormat::Json => parse_jsonc(content),
+        ConfigFormat::Yaml => Ok(serde_json::to_value(parse_yaml_view(content)?)?),
+        ConfigFormat::Toml => Ok(serde_json::to_value(content.parse::<toml::Value>()?)?),
+        other => Err(anyhow!("No reader for {:?} configs", other)),
+    }
+}
+
+/// Serialize config with the writer for its format
+fn write_config(config: &JsonValue, format: &ConfigFormat) -> Result<String> {
+    match format {
+        ConfigFormat::Json => Ok(serde_json::to_string_pretty(config)?),
+        ConfigFormat::Yaml => Ok(serde_yaml::to_string(config)?),
+        ConfigFormat::Toml => {
+            let value = toml::Value::try_from(config).context("Config cannot be represented as TOML")?;
+            Ok(toml::to_string_pretty(&value)?)
+        }
+        other => bail!("No writer for {:?} configs", other),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::ApplicationRegistry;
+    use crate::filesystem::RealFs;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    /// Profile whose app moved from config.json to config.toml in 2.0
+    fn toml_app(temp_dir: &TempDir, version: &str) -> ApplicationProfile {
+        let mut profile = ApplicationRegistry::new().get_application("zed").unwrap().clone();
+        profile.id = "toml-app".to_string();
+        profile.metadata.version = Some(version.to_string());
+        profile.metadata.format_history = vec![
+            FormatTransition {
+                format: ConfigFormat::Json,
+                path: temp_dir.path().join("config.json").to_string_lossy().to_string(),
+                min_version: None,
+                schema: None,
+            },
+            FormatTransition {
+                format: ConfigFormat::Toml,
+                path: temp_dir.path().join("config.toml").to_string_lossy().to_string(),
+                min_version: Some("2.0".to_string()),
+                schema: None,
+            },
+        ];
+        profile
+    }
+
+    #[tokio::test]
+    async fn test_converts_populated_json_config_to_toml() {
+        let temp_dir = TempDir::new().unwrap();
+        let legacy = temp_dir.path().join("config.json");
+        let config = json!({
+            "theme": "dark",
+            "mcpServers": {
+                "github": {"command": "npx", "args": ["-y", "server-github"], "env": {"GITHUB_HOST": "github.com"}},
+                "notes": {"command": "uvx", "args": ["notes-mcp"]}
+            }
+        });
+        fs::write(&legacy, config.to_string()).unwrap();
+
+        // Versions before the switch still read the JSON file
+        assert_eq!(pending_conversion(&toml_app(&temp_dir, "1.9.3"), &RealFs).await.unwrap(), None);
+
+        let profile = toml_app(&temp_dir, "2.1.0");
+        let conversion = pending_conversion(&profile, &RealFs).await.unwrap().expect("conversion offered");
+        assert_eq!(conversion.from_path, legacy);
+        assert_eq!(conversion.to_format, ConfigFormat::Toml);
+        assert_eq!(conversion.since_version.as_deref(), Some("2.0"));
+
+        let backups = BackupService::new(temp_dir.path().join("backups"), "test_user".to_string()).unwrap();
+        let mut file_service = ConfigFileService::new("test_user".to_string(), temp_dir.path().join("service-backups"));
+        let report = convert_legacy_config(&conversion, &backups, &mut file_service).await.unwrap();
+
+        let converted = read_config(&fs::read_to_string(&report.target).unwrap(), &ConfigFormat::Toml).unwrap();
+        assert_eq!(converted, config);
+        assert_eq!(serde_json::from_slice::<JsonValue>(&fs::read(&report.backup_path).unwrap()).unwrap(), config);
+        assert!(!legacy.exists());
+        let note = fs::read_to_string(report.tombstone.unwrap()).unwrap();
+        assert!(note.contains("config.toml"));
+
+        assert_eq!(pending_conversion(&profile, &RealFs).await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_continue_json_becomes_config_yaml_blocks() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut profile = ApplicationRegistry::new().get_application("continue-dev").unwrap().clone();
+        profile.metadata.version = Some("1.2.0".to_string());
+        for (entry, name) in profile.metadata.format_history.iter_mut().zip(["config.json", "config.yaml"]) {
+            entry.path = temp_dir.path().join(name).to_string_lossy().to_string();
+        }
+        fs::write(temp_dir.path().join("config.json"), json!({
+            "models": [{"title": "GPT-4", "provider": "openai"}],
+            "mcpServers": {"github": {"command": "npx", "args": ["-y", "server-github"]}},
+            "experimental": {"modelContextProtocolServers": [
+                {"transport": {"type": "stdio", "command": "uvx", "args": ["mcp-server-sqlite"]}}
+            ]}
+        }).to_string()).unwrap();
+
+        let conversion = pending_conversion(&profile, &RealFs).await.unwrap().expect("conversion offered");
+        assert_eq!(conversion.to_schema, Some(ConfigSchema::ContinueBlocks));
+        let backups = BackupService::new(temp_dir.path().join("backups"), "test_user".to_string()).unwrap();
+        let mut file_service = ConfigFileService::new("test_user".to_string(), temp_dir.path().join("service-backups"));
+        let report = convert_legacy_config(&conversion, &backups, &mut file_service).await.unwrap();
+        assert_eq!(report.not_carried, vec!["models"]);
+
+        let converted = read_config(&fs::read_to_string(&report.target).unwrap(), &ConfigFormat::Yaml).unwrap();
+        assert_eq!(converted["name"], CONTINUE_CONFIG_NAME);
+        assert_eq!(converted["version"], "1.0.0");
+        assert_eq!(converted["schema"], "v1");
+        assert_eq!(converted["mcpServers"], json!([
+            {"name": "github", "command": "npx", "args": ["-y", "server-github"]},
+            {"name": "mcp-server-1", "command": "uvx", "args": ["mcp-server-sqlite"]}
+        ]));
+    }
+}
@@ -0,0 +1,496 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::detection::{ApplicationProfile, DisableStrategy};
+use crate::filesystem::jsonc::parse_jsonc;
+use crate::filesystem::ConfigFileService;
+
+/// Named set of servers switched on and off together, e.g. "work" or "personal"
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServerGroup {
+    pub name: String,
+    /// Server names as they appear in application configs
+    pub members: Vec<String>,
+    /// Ids of the applications the group applies to
+    pub apps: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// What happened to one member in one application
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum MemberOutcome {
+    Enabled,
+    Disabled,
+    /// Already in the requested state
+    Unchanged,
+    /// The application has no entry for this server
+    Missing,
+}
+
+/// Result for a single member in a single application
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MemberResult {
+    pub app_id: String,
+    pub server_name: String,
+    pub outcome: MemberOutcome,
+}
+
+/// Outcome of activating or deactivating a group
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GroupReport {
+    pub group: String,
+    pub active: bool,
+    pub results: Vec<MemberResult>,
+    pub files_written: Vec<PathBuf>,
+}
+
+impl GroupReport {
+    /// Members that could not be found in one of the group's applications
+    pub fn missing(&self) -> impl Iterator<Item = &MemberResult> {
+        self.results.iter().filter(|r| r.outcome == MemberOutcome::Missing)
+    }
+}
+
+/// Entry taken out of a config whose application has no per-entry switch, kept until
+/// its group is activated again
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ParkedEntry {
+    pub app_id: String,
+    pub server_name: String,
+    pub entry: JsonValue,
+}
+
+/// Server groups persisted in the data directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GroupStore {
+    #[serde(skip)]
+    path: PathBuf,
+    pub groups: Vec<ServerGroup>,
+    /// Members of deactivated groups moved out of `MoveAside` applications' configs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub parked: Vec<ParkedEntry>,
+}
+
+impl GroupStore {
+    /// Load groups from disk, starting empty if the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut store = if path.exists() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read server groups: {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| "Failed to parse server groups")?
+        } else {
+            Self::default()
+        };
+
+        store.path = path.to_path_buf();
+        Ok(store)
+    }
+
+    /// Load groups from their default location in the user's home directory
+    pub fn load_default() -> Result<Self> {
+        let path = dirs::home_dir()
+            .ok_or_else(|| anyhow!("Could not find home directory"))?
+            .join(".mcp-control")
+            .join("groups.json");
+        Self::load(&path)
+    }
+
+    /// Save groups to disk
+    pub fn save(&self) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .with_context(|| "Failed to serialize server groups")?;
+        ConfigFileService::write_atomic(&self.path, content)
+            .with_context(|| format!("Failed to write server groups: {}", self.path.display()))
+    }
+
+    /// Get a group by name
+    pub fn get(&self, name: &str) -> Option<&ServerGroup> {
+        self.groups.iter().find(|g| g.name == name)
+    }
+
+    /// Create a group from the servers and applications currently selected in the UI
+    pub fn create_group(&mut self, name: &str, selected_servers: &[String], apps: &[String]) -> Result<&ServerGroup> {
+        if self.get(name).is_some() {
+            return Err(anyhow!("A server group named '{}' already exists", name));
+        }
+        if selected_servers.is_empty() {
+            return Err(anyhow!("Select at least one server to create a group"));
+        }
+
+        let mut members = selected_servers.to_vec();
+        members.sort();
+        members.dedup();
+
+        self.groups.push(ServerGroup {
+            name: name.to_string(),
+            members,
+            apps: apps.to_vec(),
+            created_at: Utc::now(),
+        });
+        Ok(self.groups.last().expect("group was just added"))
+    }
+
+    /// Delete a group, returning whether it existed
+    pub fn delete_group(&mut self, name: &str) -> bool {
+        let before = self.groups.len();
+        self.groups.retain(|g| g.name != name);
+        self.groups.len() != before
+    }
+
+    /// Follow a server rename in every group that contains it
+    pub fn rename_member(&mut self, old_name: &str, new_name: &str) -> usize {
+        let mut renamed = 0;
+        for group in &mut self.groups {
+            for member in group.members.iter_mut().filter(|m| *m == old_name) {
+                *member = new_name.to_string();
+                renamed += 1;
+            }
+        }
+        renamed
+    }
+}
+
+/// Pending write to one application config, kept so the whole batch can be rolled back
+struct FileChange {
+    path: PathBuf,
+    original: String,
+    config: JsonValue,
+}
+
+/// Read an application config along with its server entries
+fn load_app_config(app: &ApplicationProfile) -> Result<Option<(PathBuf, String, JsonValue)>> {
+    let Some(path) = app.expanded_config_paths().into_iter().find(|path| path.exists()) else {
+        return Ok(None);
+    };
+
+    let content = fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {} config: {}", app.name, path.display()))?;
+    let config = parse_jsonc(&content)
+        .with_context(|| format!("Failed to parse {} config: {}", app.name, path.display()))?;
+    Ok(Some((path, content, config)))
+}
+
+/// Write every change through `file_service`, restoring the already-written files if any
+/// write fails. Nothing is written when one of the files is externally managed.
+async fn commit(changes: &[FileChange], file_service: &mut ConfigFileService) -> Result<()> {
+    for change in changes {
+        file_service.ensure_writable(&change.path)?;
+    }
+    for (index, change) in changes.iter().enumerate() {
+        if let Err(e) = file_service.write_config(&change.path, &change.config).await {
+            for done in &changes[..index] {
+                if let Err(restore) = ConfigFileService::write_atomic(&done.path, &done.original) {
+                    log::error!("Failed to roll back {}: {}", done.path.display(), restore);
+                }
+            }
+            return Err(e);
+        }
+    }
+    Ok(())
+}
+
+/// Enable or disable every member of a group across the group's applications.
+///
+/// Each application's `disable_strategy` decides how: a `disabled` or `enabled` flag on the
+/// entry, or for applications without a switch, moving the entry into the store's parked
+/// entries and back. All configs are written together through `file_service` or not at all;
+/// members missing from an application are reported rather than treated as errors.
+pub async fn set_group_active(
+    store: &mut GroupStore,
+    group_name: &str,
+    apps: &[ApplicationProfile],
+    active: bool,
+    file_service: &mut ConfigFileService,
+) -> Result<GroupReport> {
+    let group = store
+        .get(group_name)
+        .cloned()
+        .ok_or_else(|| anyhow!("No server group named '{}'", group_name))?;
+    let parked_before = store.parked.clone();
+    let mut results = Vec::new();
+    let mut changes = Vec::new();
+
+    for app_id in &group.apps {
+        let app = apps.iter().find(|a| &a.id == app_id);
+        let loaded = match app {
+            Some(app) => load_app_config(app)?,
+            None => None,
+        };
+        let missing = |server_name: &String| MemberResult {
+            app_id: app_id.clone(),
+            server_name: server_name.clone(),
+            outcome: MemberOutcome::Missing,
+        };
+
+        let (Some(app), Some((path, original, mut config))) = (app, loaded) else {
+            results.extend(group.members.iter().map(missing));
+            continue;
+        };
+
+        let has_parked = store.parked.iter().any(|p| &p.app_id == app_id && group.members.contains(&p.server_name));
+        if !config.is_object() || (app.servers_path().get(&config).is_none() && !has_parked) {
+            results.extend(group.members.iter().map(missing));
+            continue;
+        }
+        let servers = app.servers_path().get_or_insert(&mut config);
+
+        let mut changed = false;
+        for member in &group.members {
+            let outcome = match app.metadata.disable_strategy {
+                DisableStrategy::MoveAside => {
+                    let parked = store.parked.iter().position(|p| &p.app_id == app_id && &p.server_name == member);
+                    match (servers.remove(member), parked) {
+                        (Some(entry), _) if active => {
+                            servers.insert(member.clone(), entry);
+                            MemberOutcome::Unchanged
+                        }
+                        (Some(entry), parked) => {
+                            if let Some(index) = parked {
+                                store.parked.remove(index);
+                            }
+                            store.parked.push(ParkedEntry { app_id: app_id.clone(), server_name: member.clone(), entry });
+                            MemberOutcome::Disabled
+                        }
+                        (None, Some(index)) if active => {
+                            servers.insert(member.clone(), store.parked.remove(index).entry);
+                            MemberOutcome::Enabled
+                        }
+                        (None, Some(_)) => MemberOutcome::Unchanged,
+                        (None, None) => MemberOutcome::Missing,
+                    }
+                }
+                strategy => {
+                    let Some(entry) = servers.get_mut(member).and_then(|e| e.as_object_mut()) else {
+                        results.push(missing(member));
+                        continue;
+                    };
+                    let (flag, off) = match strategy {
+                        DisableStrategy::EnabledFlag => ("enabled", false),
+                        _ => ("disabled", true),
+                    };
+
+                    let is_active = entry.get(flag).and_then(|f| f.as_bool()) != Some(off);
+                    if is_active == active {
+                        MemberOutcome::Unchanged
+                    } else if active {
+                        entry.remove(flag);
+                        MemberOutcome::Enabled
+                    } else {
+                        entry.insert(flag.to_string(), JsonValue::Bool(off));
+                        MemberOutcome::Disabled
+                    }
+                }
+            };
+            changed |= matches!(outcome, MemberOutcome::Enabled | MemberOutcome::Disabled);
+            results.push(MemberResult { app_id: app_id.clone(), server_name: member.clone(), outcome });
+        }
+
+        if changed {
+            changes.push(FileChange { path, original, config });
+        }
+    }
+
+    if let Err(e) = commit(&changes, file_service).await {
+        store.parked = parked_before;
+        return Err(e);
+    }
+    if store.parked != parked_before {
+        store.save()?;
+    }
+
+    Ok(GroupReport {
+        group: group.name.clone(),
+        active,
+        results,
+        files_written: changes.into_iter().map(|c| c.path).collect(),
+    })
+}
+
+/// Rename a server in every application config and keep group membership in step
+pub async fn rename_server(
+    store: &mut GroupStore,
+    apps: &[ApplicationProfile],
+    old_name: &str,
+    new_name: &str,
+    file_service: &mut ConfigFileService,
+) -> Result<Vec<PathBuf>> {
+    let mut changes = Vec::new();
+
+    for app in apps {
+        let Some((path, original, mut config)) = load_app_config(app)? else {
+            continue;
+        };
+        let Some(servers) = app.servers_path().get_mut(&mut config) else {
+            continue;
+        };
+        if servers.contains_key(new_name) {
+            return Err(anyhow!("{} already has a server named '{}'", app.name, new_name));
+        }
+        let Some(entry) = servers.remove(old_name) else {
+            continue;
+        };
+        servers.insert(new_name.to_string(), entry);
+        changes.push(FileChange { path, original, config });
+    }
+
+    commit(&changes, file_service).await?;
+    if store.rename_member(old_name, new_name) > 0 {
+        store.save()?;
+    }
+
+    Ok(changes.into_iter().map(|c| c.path).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::ApplicationRegistry;
+    use tempfile::TempDir;
+
+    fn app(id: &str, config_path: &Path, content: &str) -> ApplicationProfile {
+        fs::write(config_path, content).unwrap();
+        let mut profile = ApplicationRegistry::new().get_application(id).unwrap().clone();
+        profile.set_config_path(config_path.to_string_lossy());
+        profile
+    }
+
+    fn setup(temp_dir: &TempDir) -> (GroupStore, Vec<ApplicationProfile>) {
+        let apps = vec![
+            app(
+                "claude-desktop",
+                &temp_dir.path().join("claude.json"),
+                r#"{"mcpServers": {"jira": {"command": "npx"}, "slack": {"command": "npx", "disabled": true}}}"#,
+            ),
+            app(
+                "jetbrains-idea",
+                &temp_dir.path().join("idea.json"),
+                r#"{"mcp": {"servers": {"jira": {"command": "npx", "disabled": true}}}}"#,
+            ),
+        ];
+
+        let mut store = GroupStore::load(&temp_dir.path().join("groups.json")).unwrap();
+        store
+            .create_group("work", &["slack".to_string(), "jira".to_string()], &["claude-desktop".to_string(), "jetbrains-idea".to_string()])
+            .unwrap();
+        (store, apps)
+    }
+
+    fn read(path: &Path) -> JsonValue {
+        serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap()
+    }
+
+    fn file_service(temp_dir: &TempDir) -> ConfigFileService {
+        ConfigFileService::new("test".to_string(), temp_dir.path().join("backups"))
+    }
+
+    #[tokio::test]
+    async fn test_activate_group_across_apps_reports_missing_member() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut store, apps) = setup(&temp_dir);
+        let mut file_service = file_service(&temp_dir);
+
+        let report = set_group_active(&mut store, "work", &apps, true, &mut file_service).await.unwrap();
+
+        let outcome = |app: &str, server: &str| {
+            report.results.iter().find(|r| r.app_id == app && r.server_name == server).unwrap().outcome.clone()
+        };
+        assert_eq!(outcome("claude-desktop", "jira"), MemberOutcome::Unchanged);
+        assert_eq!(outcome("claude-desktop", "slack"), MemberOutcome::Enabled);
+        assert_eq!(outcome("jetbrains-idea", "jira"), MemberOutcome::Enabled);
+        assert_eq!(outcome("jetbrains-idea", "slack"), MemberOutcome::Missing);
+        assert_eq!(report.missing().count(), 1);
+        assert_eq!(report.files_written.len(), 2);
+
+        assert!(read(&temp_dir.path().join("claude.json"))["mcpServers"]["slack"].get("disabled").is_none());
+        assert!(read(&temp_dir.path().join("idea.json"))["mcp"]["servers"]["jira"].get("disabled").is_none());
+        assert!(fs::read_dir(temp_dir.path().join("backups")).unwrap().count() >= 2);
+
+        let report = set_group_active(&mut store, "work", &apps, false, &mut file_service).await.unwrap();
+        assert_eq!(report.results.iter().filter(|r| r.outcome == MemberOutcome::Disabled).count(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_group_follows_each_apps_disable_strategy() {
+        let temp_dir = TempDir::new().unwrap();
+        let zed = app("zed", &temp_dir.path().join("zed.json"), r#"{"context_servers": {"jira": {"command": {"path": "npx"}}}}"#);
+        let vscode = app("vscode", &temp_dir.path().join("vscode.json"), r#"{"mcpServers": {"jira": {"command": "npx"}}}"#);
+        let apps = vec![zed.clone(), vscode.clone()];
+        let mut store = GroupStore::load(&temp_dir.path().join("groups.json")).unwrap();
+        store.create_group("work", &["jira".to_string()], &["zed".to_string(), "vscode".to_string()]).unwrap();
+        let mut file_service = file_service(&temp_dir);
+
+        let report = set_group_active(&mut store, "work", &apps, false, &mut file_service).await.unwrap();
+        assert!(report.results.iter().all(|r| r.outcome == MemberOutcome::Disabled));
+
+        let zed_config = read(&temp_dir.path().join("zed.json"));
+        let zed_entry = &zed.servers_path().get(&zed_config).unwrap()["jira"];
+        assert_eq!(zed_entry["enabled"], false);
+        assert!(zed_entry.get("disabled").is_none());
+        let vscode_config = read(&temp_dir.path().join("vscode.json"));
+        assert!(!vscode.servers_path().get(&vscode_config).unwrap().contains_key("jira"));
+
+        // The moved-aside entry is kept in the store until the group comes back
+        let reloaded = GroupStore::load(&temp_dir.path().join("groups.json")).unwrap();
+        assert_eq!(reloaded.parked.len(), 1);
+        assert_eq!(reloaded.parked[0].entry["command"], "npx");
+
+        let report = set_group_active(&mut store, "work", &apps, true, &mut file_service).await.unwrap();
+        assert!(report.results.iter().all(|r| r.outcome == MemberOutcome::Enabled));
+        assert!(zed.servers_path().get(&read(&temp_dir.path().join("zed.json"))).unwrap()["jira"].get("enabled").is_none());
+        let vscode_config = read(&temp_dir.path().join("vscode.json"));
+        assert_eq!(vscode.servers_path().get(&vscode_config).unwrap()["jira"]["command"], "npx");
+        assert!(store.parked.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_managed_config_blocks_the_whole_batch() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut store, apps) = setup(&temp_dir);
+        let original = fs::read_to_string(temp_dir.path().join("claude.json")).unwrap();
+        let idea = temp_dir.path().join("idea.json");
+        fs::write(&idea, format!("// @managed\n{}", fs::read_to_string(&idea).unwrap())).unwrap();
+        let mut file_service = file_service(&temp_dir);
+
+        assert!(set_group_active(&mut store, "work", &apps, true, &mut file_service).await.is_err());
+        assert_eq!(fs::read_to_string(temp_dir.path().join("claude.json")).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_membership_survives_rename() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut store, apps) = setup(&temp_dir);
+        let mut file_service = file_service(&temp_dir);
+
+        let written = rename_server(&mut store, &apps, "jira", "atlassian", &mut file_service).await.unwrap();
+        assert_eq!(written.len(), 2);
+
+        let mut reloaded = GroupStore::load(&temp_dir.path().join("groups.json")).unwrap();
+        assert_eq!(reloaded.get("work").unwrap().members, vec!["atlassian", "slack"]);
+
+        let report = set_group_active(&mut reloaded, "work", &apps, true, &mut file_service).await.unwrap();
+        assert!(report.results.iter().any(|r| r.server_name == "atlassian" && r.outcome == MemberOutcome::Enabled));
+        assert!(store.create_group("work", &["slack".to_string()], &[]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_rename_keeps_config_comments() {
+        let temp_dir = TempDir::new().unwrap();
+        let (mut store, mut apps) = setup(&temp_dir);
+        apps[0] = app(
+            "claude-desktop",
+            &temp_dir.path().join("claude.json"),
+            "{\n  // shared with the team\n  \"mcpServers\": {\n    \"jira\": {\"command\": \"npx\"}, // tracker\n  },\n}\n",
+        );
+
+        rename_server(&mut store, &apps, "jira", "atlassian", &mut file_service(&temp_dir)).await.unwrap();
+
+        let written = fs::read_to_string(temp_dir.path().join("claude.json")).unwrap();
+        assert!(written.contains("// shared with the team"), "{}", written);
+        assert_eq!(parse_jsonc(&written).unwrap()["mcpServers"]["atlassian"]["command"], "npx");
+    }
+}
@@ -0,0 +1,276 @@
+use std::path::{Component, Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+use crate::detection::McpServerConfig;
+
+/// Directories that hold per-user home directories
+const HOME_ROOTS: &[&str] = &["/Users", "/home", "C:\\Users", "C:/Users"];
+
+/// What happens to a path found under another user's home
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum HomePathAction {
+    /// The same relative path exists under the current home and was substituted
+    Rehomed { path: String },
+    /// No equivalent exists here, so the user has to choose a replacement
+    PromptRequired,
+    /// The path points into another user's world-readable directory, which is almost certainly a mistake
+    WorldReadable,
+}
+
+/// Absolute path in a server entry that belongs to another user's home
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ForeignHomePath {
+    pub server_name: String,
+    /// Where the path was found, e.g. `args[1]` or `env.CONFIG_DIR`
+    pub location: String,
+    pub original: String,
+    pub foreign_home: String,
+    pub action: HomePathAction,
+}
+
+impl ForeignHomePath {
+    /// Whether this path needs user attention after automatic re-homing
+    pub fn needs_attention(&self) -> bool {
+        !matches!(self.action, HomePathAction::Rehomed { .. })
+    }
+}
+
+impl std::fmt::Display for ForeignHomePath {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.action {
+            HomePathAction::Rehomed { path } => {
+                write!(f, "{} {}: re-homed {} to {}", self.server_name, self.location, self.original, path)
+            }
+            HomePathAction::PromptRequired => write!(
+                f,
+                "{} {}: {} is under {} and has no equivalent in your home directory",
+                self.server_name, self.location, self.original, self.foreign_home
+            ),
+            HomePathAction::WorldReadable => write!(
+                f,
+                "{} {}: {} points into another user's world-readable directory",
+                self.server_name, self.location, self.original
+            ),
+        }
+    }
+}
+
+/// Detects and re-homes paths that point into other users' home directories
+#[derive(Debug, Clone)]
+pub struct HomeRelocator {
+    home: PathBuf,
+    home_roots: Vec<PathBuf>,
+}
+
+impl HomeRelocator {
+    /// Create a relocator for the current user
+    pub fn new() -> Option<Self> {
+        let home = dirs::home_dir()?;
+        Some(Self::with_home(home, HOME_ROOTS.iter().map(PathBuf::from).collect()))
+    }
+
+    /// Create a relocator for a specific home directory and set of home roots
+    pub fn with_home(home: PathBuf, home_roots: Vec<PathBuf>) -> Self {
+        Self { home, home_roots }
+    }
+
+    /// Split a path under another user's home into that home and the relative remainder
+    fn split_foreign(&self, path: &Path) -> Option<(PathBuf, PathBuf)> {
+        if path.starts_with(&self.home) {
+            return None;
+        }
+
+        self.home_roots.iter().find_map(|root| {
+            let rest = path.strip_prefix(root).ok()?;
+            let mut components = rest.components();
+            let user = match components.next()? {
+                Component::Normal(user) => user,
+                _ => return None,
+            };
+            Some((root.join(user), components.as_path().to_path_buf()))
+        })
+    }
+
+    /// Classify a single value, returning None if it is not a foreign-home path
+    fn classify(&self, value: &str) -> Option<(String, HomePathAction)> {
+        let (foreign_home, relative) = self.split_foreign(Path::new(value))?;
+
+        let candidate = self.home.join(&relative);
+        let action = if !relative.as_os_str().is_empty() && candidate.exists() {
+            HomePathAction::Rehomed { path: candidate.to_string_lossy().to_string() }
+        } else if is_world_readable(Path::new(value)) {
+            HomePathAction::WorldReadable
+        } else {
+            HomePathAction::PromptRequired
+        };
+
+        Some((foreign_home.to_string_lossy().to_string(), action))
+    }
+
+    /// Find foreign-home paths in a server's command, args, env, and cwd
+    pub fn scan_server(&self, server: &McpServerConfig) -> Vec<ForeignHomePath> {
+        let mut values: Vec<(String, &str)> = Vec::new();
+        if let Some(command) = &server.command {
+            values.push(("command".to_string(), command));
+        }
+        for (i, arg) in server.args.iter().enumerate() {
+            values.push((format!("args[{}]", i), path_part(arg)));
+        }
+        let mut env_keys: Vec<&String> = server.env.keys().collect();
+        env_keys.sort();
+        for key in env_keys {
+            values.push((format!("env.{}", key), &server.env[key]));
+        }
+        if let Some(cwd) = &server.cwd {
+            values.push(("cwd".to_string(), cwd));
+        }
+
+        values
+            .into_iter()
+            .filter_map(|(location, value)| {
+                let (foreign_home, action) = self.classify(value)?;
+                Some(ForeignHomePath {
+                    server_name: server.name.clone(),
+                    location,
+                    original: value.to_string(),
+                    foreign_home,
+                    action,
+                })
+            })
+            .collect()
+    }
+
+    /// Substitute re-homable paths and report every foreign-home path found
+    pub fn rehome_server(&self, server: &McpServerConfig) -> (McpServerConfig, Vec<ForeignHomePath>) {
+        let findings = self.scan_server(server);
+        let mut rehomed = server.clone();
+
+        for finding in &findings {
+            let HomePathAction::Rehomed { path } = &finding.action else {
+                continue;
+            };
+            let target = match finding.location.as_str() {
+                "command" => rehomed.command.as_mut(),
+                "cwd" => rehomed.cwd.as_mut(),
+                location => match location.strip_prefix("env.") {
+                    Some(key) => rehomed.env.get_mut(key),
+                    None => location
+                        .strip_prefix("args[")
+                        .and_then(|rest| rest.strip_suffix(']'))
+                        .and_then(|i| i.parse::<usize>().ok())
+                        .and_then(|index| rehomed.args.get_mut(index)),
+                },
+            };
+
+            if let Some(value) = target {
+                *value = value.replacen(&finding.original, path, 1);
+            }
+        }
+
+        (rehomed, findings)
+    }
+}
+
+/// Path portion of an argument, handling `--flag=/path` forms
+fn path_part(arg: &str) -> &str {
+    match arg.split_once('=') {
+        Some((flag, value)) if flag.starts_with('-') => value,
+        _ => arg,
+    }
+}
+
+#[cfg(unix)]
+fn is_world_readable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    std::fs::metadata(path)
+        .map(|m| m.permissions().mode() & 0o004 != 0)
+        .unwrap_or(false)
+}
+
+#[cfg(not(unix))]
+fn is_world_readable(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{ConfigSource, ServerMetadata, ServerType};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    fn server(args: Vec<String>, env: HashMap<String, String>) -> McpServerConfig {
+        McpServerConfig {
+            name: "notes".to_string(),
+            command: Some("node".to_string()),
+            args,
+            env,
+            cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
+            server_type: ServerType::Stdio,
+            metadata: ServerMetadata {
+                description: None,
+                version: None,
+                author: None,
+                capabilities: vec![],
+                enabled: true,
+                source: ConfigSource::MainConfig,
+            },
+        }
+    }
+
+    /// Fake home root with the current user `me` and a colleague
+    fn homes() -> (TempDir, HomeRelocator) {
+        let root = TempDir::new().unwrap();
+        std::fs::create_dir_all(root.path().join("me")).unwrap();
+        std::fs::create_dir_all(root.path().join("colleague")).unwrap();
+        let relocator = HomeRelocator::with_home(root.path().join("me"), vec![root.path().to_path_buf()]);
+        (root, relocator)
+    }
+
+    #[test]
+    fn test_rehomes_when_same_relative_path_exists() {
+        let (root, relocator) = homes();
+        std::fs::create_dir_all(root.path().join("me/notes")).unwrap();
+        let foreign = root.path().join("colleague/notes").to_string_lossy().to_string();
+
+        let (rehomed, findings) = relocator.rehome_server(&server(vec![format!("--root={}", foreign)], HashMap::new()));
+
+        let expected = root.path().join("me/notes").to_string_lossy().to_string();
+        assert_eq!(findings[0].action, HomePathAction::Rehomed { path: expected.clone() });
+        assert_eq!(rehomed.args, vec![format!("--root={}", expected)]);
+        assert!(!findings[0].needs_attention());
+    }
+
+    #[test]
+    fn test_prompt_when_no_equivalent_exists() {
+        let (root, relocator) = homes();
+        let foreign = root.path().join("colleague/private/db.sqlite").to_string_lossy().to_string();
+        let env = HashMap::from([("DB_PATH".to_string(), foreign.clone())]);
+
+        let (rehomed, findings) = relocator.rehome_server(&server(vec![], env));
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].location, "env.DB_PATH");
+        assert_eq!(findings[0].action, HomePathAction::PromptRequired);
+        assert_eq!(rehomed.env["DB_PATH"], foreign);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_warns_about_world_readable_foreign_directory() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let (root, relocator) = homes();
+        let shared = root.path().join("colleague/shared");
+        std::fs::create_dir_all(&shared).unwrap();
+        std::fs::set_permissions(&shared, std::fs::Permissions::from_mode(0o755)).unwrap();
+
+        let findings = relocator.scan_server(&server(vec![shared.to_string_lossy().to_string()], HashMap::new()));
+
+        assert_eq!(findings[0].action, HomePathAction::WorldReadable);
+        assert!(findings[0].to_string().contains("world-readable"));
+    }
+}
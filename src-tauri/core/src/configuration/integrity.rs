@@ -0,0 +1,284 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Context, Result};
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+
+use crate::configuration::annotations::AnnotationStore;
+use crate::configuration::groups::GroupStore;
+use crate::configuration::provenance::ProvenanceSidecar;
+use crate::configuration::resolution::PendingResolutionStore;
+use crate::configuration::sync::default_backup_dir;
+use crate::filesystem::{BackupMetadata, ConfigFileService};
+use crate::server::health::HealthHistory;
+
+/// Subfolder of the data directory that unreadable files are moved into
+pub const CORRUPT_DIR: &str = "corrupt";
+
+/// Loads a store from disk, failing when it cannot be read
+type StoreCheck = fn(&Path) -> Result<()>;
+
+/// Sidecar stores in the data directory, with the loader that must accept each one
+const STORES: &[(&str, StoreCheck)] = &[
+    ("annotations.json", |path| AnnotationStore::load(path).map(|_| ())),
+    ("provenance.json", |path| ProvenanceSidecar::load(path).map(|_| ())),
+    ("groups.json", |path| GroupStore::load(path).map(|_| ())),
+    ("health_history.json", |path| HealthHistory::load(path).map(|_| ())),
+    ("pending_resolutions.json", |path| PendingResolutionStore::load(path).map(|_| ())),
+];
+
+/// Something the startup self-check found and repaired
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum IntegrityFinding {
+    /// A file could not be read and was moved aside; the store starts over empty
+    Quarantined { path: PathBuf, moved_to: PathBuf, reason: String },
+    /// A write interrupted before its rename was completed from the temporary file
+    RecoveredWrite { path: PathBuf, from: PathBuf },
+    /// A temporary file left by an interrupted write was not needed and was removed
+    DiscardedTemp { path: PathBuf },
+    /// A backup index entry pointed at a backup file that no longer exists
+    DanglingBackupEntry { metadata_path: PathBuf, backup_path: PathBuf, moved_to: PathBuf },
+    /// A backup file that no index entry refers to; it is kept but cannot be restored from the UI
+    UnindexedBackup { path: PathBuf },
+}
+
+impl fmt::Display for IntegrityFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Quarantined { path, moved_to, reason } => {
+                write!(f, "{} was unreadable ({}) and moved to {}", path.display(), reason, moved_to.display())
+            }
+            Self::RecoveredWrite { path, from } => {
+                write!(f, "{} was recovered from the interrupted write {}", path.display(), from.display())
+            }
+            Self::DiscardedTemp { path } => write!(f, "leftover temporary file {} was removed", path.display()),
+            Self::DanglingBackupEntry { metadata_path, backup_path, moved_to } => write!(
+                f,
+                "backup entry {} refers to missing file {} and was moved to {}",
+                metadata_path.display(),
+                backup_path.display(),
+                moved_to.display()
+            ),
+            Self::UnindexedBackup { path } => write!(f, "backup file {} is not in the backup index", path.display()),
+        }
+    }
+}
+
+/// Outcome of checking the data directory
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IntegrityReport {
+    /// Stores that were present and loaded after any repair
+    pub checked: Vec<PathBuf>,
+    pub findings: Vec<IntegrityFinding>,
+}
+
+impl IntegrityReport {
+    /// Whether nothing needed repairing
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Lines for the diagnostics view, one per finding
+    pub fn diagnostics(&self) -> Vec<String> {
+        self.findings.iter().map(|finding| finding.to_string()).collect()
+    }
+}
+
+/// Check MCP Control's own data directory and the backups in `backup_dir`, and repair
+/// what would break startup.
+///
+/// Unreadable files are never deleted: they are moved into [`CORRUPT_DIR`] so the app
+/// starts with an empty store and the original bytes remain available for support.
+pub fn check_data_dir(data_dir: &Path, backup_dir: &Path) -> Result<IntegrityReport> {
+    let mut report = IntegrityReport::default();
+    if data_dir.exists() {
+        check_stores(data_dir, &mut report)?;
+    }
+    check_backups(data_dir, backup_dir, &mut report)?;
+
+    for finding in &report.findings {
+        log::warn!("Data directory self-check: {}", finding);
+    }
+    Ok(report)
+}
+
+/// Check the data directory and backups in their default locations in the user's home directory
+pub fn check_default_data_dir() -> Result<IntegrityReport> {
+    let data_dir = dirs::home_dir()
+        .ok_or_else(|| anyhow!("Could not find home directory"))?
+        .join(".mcp-control");
+    check_data_dir(&data_dir, &default_backup_dir()?)
+}
+
+/// Load every sidecar store, moving unreadable ones aside
+fn check_stores(data_dir: &Path, report: &mut IntegrityReport) -> Result<()> {
+    for (file_name, load) in STORES {
+        let path = data_dir.join(file_name);
+        recover_interrupted_write(&path, *load, report)?;
+
+        if !path.exists() {
+            continue;
+        }
+        if let Err(e) = load(&path) {
+            let moved_to = quarantine(data_dir, &path)?;
+            report.findings.push(IntegrityFinding::Quarantined {
+                path: path.clone(),
+                moved_to,
+                reason: format!("{:#}", e),
+            });
+        } else {
+            report.checked.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Finish or discard a write to `path` that stopped before its temporary file was renamed
+fn recover_interrupted_write(path: &Path, load: StoreCheck, report: &mut IntegrityReport) -> Result<()> {
+    let temp_path = ConfigFileService::temp_path(path);
+    if !temp_path.exists() {
+        return Ok(());
+    }
+
+    let primary_usable = path.exists() && load(path).is_ok();
+    if !primary_usable && load(&temp_path).is_ok() {
+        fs::rename(&temp_path, path)
+            .with_context(|| format!("Failed to recover {}", path.display()))?;
+        report.findings.push(IntegrityFinding::RecoveredWrite {
+            path: path.to_path_buf(),
+            from: temp_path,
+        });
+    } else {
+        fs::remove_file(&temp_path)
+            .with_context(|| format!("Failed to remove {}", temp_path.display()))?;
+        report.findings.push(IntegrityFinding::DiscardedTemp { path: temp_path });
+    }
+    Ok(())
+}
+
+/// Make the backup index and the backup files agree; unreadable index entries go to the data directory's corrupt folder
+fn check_backups(data_dir: &Path, backup_dir: &Path, report: &mut IntegrityReport) -> Result<()> {
+    let metadata_dir = backup_dir.join("metadata");
+    let mut indexed = HashSet::new();
+
+    if metadata_dir.exists() {
+        for entry in fs::read_dir(&metadata_dir)? {
+            let metadata_path = entry?.path();
+            if metadata_path.extension().and_then(|e| e.to_str()) != Some("json") {
+                continue;
+            }
+
+            let parsed = fs::read_to_string(&metadata_path)
+                .map_err(anyhow::Error::from)
+                .and_then(|content| Ok(serde_json::from_str::<BackupMetadata>(&content)?));
+            match parsed {
+                Ok(metadata) if metadata.backup_path.exists() => {
+                    indexed.insert(metadata.backup_path);
+                }
+                Ok(metadata) => {
+                    let moved_to = quarantine(data_dir, &metadata_path)?;
+                    report.findings.push(IntegrityFinding::DanglingBackupEntry {
+                        metadata_path,
+                        backup_path: metadata.backup_path,
+                        moved_to,
+                    });
+                }
+                Err(e) => {
+                    let moved_to = quarantine(data_dir, &metadata_path)?;
+                    report.findings.push(IntegrityFinding::Quarantined {
+                        path: metadata_path,
+                        moved_to,
+                        reason: format!("{:#}", e),
+                    });
+                }
+            }
+        }
+    }
+
+    if backup_dir.exists() {
+        for entry in fs::read_dir(backup_dir)? {
+            let path = entry?.path();
+            if path.is_file() && !indexed.contains(&path) {
+                report.findings.push(IntegrityFinding::UnindexedBackup { path });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Move a file into the corrupt folder under a timestamped name, returning where it went
+fn quarantine(data_dir: &Path, path: &Path) -> Result<PathBuf> {
+    let corrupt_dir = data_dir.join(CORRUPT_DIR);
+    fs::create_dir_all(&corrupt_dir)
+        .with_context(|| format!("Failed to create directory: {}", corrupt_dir.display()))?;
+
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("unknown");
+    let moved_to = corrupt_dir.join(format!("{}.{}", file_name, Utc::now().format("%Y%m%dT%H%M%S%.3f")));
+    fs::rename(path, &moved_to)
+        .with_context(|| format!("Failed to move {} aside", path.display()))?;
+    Ok(moved_to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filesystem::BackupService;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_self_check_repairs_each_corruption() {
+        let temp_dir = TempDir::new().unwrap();
+        let data_dir = &temp_dir.path().join(".mcp-control");
+        fs::create_dir_all(data_dir).unwrap();
+
+        // Truncated sidecar
+        fs::write(data_dir.join("annotations.json"), "{\"servers\": {\"github\": {\"no").unwrap();
+        // Primary missing, interrupted write left a complete temp file
+        fs::write(data_dir.join("groups.json.tmp"), r#"{"groups": []}"#).unwrap();
+        // Healthy primary with a stale temp next to it
+        fs::write(data_dir.join("provenance.json"), r#"{"records": {}}"#).unwrap();
+        fs::write(data_dir.join("provenance.json.tmp"), "{\"rec").unwrap();
+        // Truncated pending resolutions
+        fs::write(data_dir.join("pending_resolutions.json"), "{\"pending").unwrap();
+
+        // Backup index with one good entry, one dangling, one unparseable, plus an unindexed file
+        let config = data_dir.join("claude_desktop_config.json");
+        fs::write(&config, "{}").unwrap();
+        let backup_dir = temp_dir.path().join(".mcp-control-backups");
+        let service = BackupService::new(backup_dir.clone(), "test_user".to_string()).unwrap();
+        let kept = service.create_backup(&config, crate::filesystem::BackupType::Manual, None).unwrap();
+        let lost = service.create_backup(&config, crate::filesystem::BackupType::Manual, None).unwrap();
+        fs::remove_file(&lost.backup_path).unwrap();
+        fs::write(backup_dir.join("metadata/broken.json"), "not json").unwrap();
+        fs::write(backup_dir.join("stray.bak"), "{}").unwrap();
+
+        let report = check_data_dir(data_dir, &backup_dir).unwrap();
+        let diagnostics = report.diagnostics();
+        assert_eq!(report.findings.len(), 7, "{:#?}", diagnostics);
+        assert!(report.findings.iter().any(|f| matches!(f, IntegrityFinding::Quarantined { path, .. } if path.ends_with("annotations.json"))));
+        assert!(report.findings.iter().any(|f| matches!(f, IntegrityFinding::Quarantined { path, .. } if path.ends_with("broken.json"))));
+        assert!(report.findings.iter().any(|f| matches!(f, IntegrityFinding::Quarantined { path, .. } if path.ends_with("pending_resolutions.json"))));
+        assert!(report.findings.contains(&IntegrityFinding::RecoveredWrite {
+            path: data_dir.join("groups.json"),
+            from: data_dir.join("groups.json.tmp"),
+        }));
+        assert!(report.findings.contains(&IntegrityFinding::DiscardedTemp { path: data_dir.join("provenance.json.tmp") }));
+        assert!(report.findings.iter().any(|f| matches!(f, IntegrityFinding::DanglingBackupEntry { backup_path, .. } if *backup_path == lost.backup_path)));
+        assert!(report.findings.contains(&IntegrityFinding::UnindexedBackup { path: backup_dir.join("stray.bak") }));
+
+        // The corrupt bytes are kept, and every store now loads
+        assert_eq!(fs::read_dir(data_dir.join(CORRUPT_DIR)).unwrap().count(), 4);
+        assert!(AnnotationStore::load(&data_dir.join("annotations.json")).unwrap().servers.is_empty());
+        assert!(GroupStore::load(&data_dir.join("groups.json")).is_ok());
+        assert_eq!(service.list_all_backups().unwrap().len(), 1);
+        assert!(service.read_backup(&kept).is_ok());
+
+        // A second run finds only the stray backup it deliberately leaves alone
+        let report = check_data_dir(data_dir, &backup_dir).unwrap();
+        assert_eq!(report.findings, vec![IntegrityFinding::UnindexedBackup { path: backup_dir.join("stray.bak") }]);
+        assert_eq!(report.checked.len(), 2);
+    }
+}
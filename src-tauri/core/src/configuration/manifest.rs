@@ -0,0 +1,521 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::detection::McpServerConfig;
+use crate::net;
+
+/// Current team manifest format version
+pub const MANIFEST_VERSION: &str = "1";
+
+/// Shareable set of server configurations for a team
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TeamManifest {
+    pub version: String,
+    pub servers: Vec<McpServerConfig>,
+    /// Optional lock pinning every server to an exact, verified package release
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock: Option<ManifestLock>,
+}
+
+/// Lock section of a team manifest
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestLock {
+    pub generated_at: DateTime<Utc>,
+    /// Locked servers keyed by server name
+    pub servers: BTreeMap<String, LockedServer>,
+}
+
+/// Package registry a server is installed from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum PackageRegistry {
+    Npm,
+    PyPI,
+}
+
+/// Exact release a server was locked to
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LockedServer {
+    pub registry: PackageRegistry,
+    pub package: String,
+    pub version: String,
+    /// npm `dist.integrity` or PyPI sha256 digest
+    pub integrity: Option<String>,
+    /// Hash of the server configuration at lock time
+    pub content_hash: String,
+}
+
+/// Problem found while verifying a manifest against its lock
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum LockIssue {
+    /// A newer release was published since the manifest was locked
+    VersionDrifted { locked: String, latest: String },
+    /// The registry reports a different artifact for the locked version
+    IntegrityChanged { expected: String, actual: String },
+    /// The locked version is no longer available from the registry
+    VersionMissing { locked: String },
+    /// The server configuration was edited after locking
+    ContentChanged,
+    /// The server has no lock entry
+    NotLocked,
+    /// The registry could not be queried
+    RegistryUnavailable(String),
+}
+
+/// Result of verifying a manifest lock at import time
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LockReport {
+    pub issues: Vec<(String, LockIssue)>,
+}
+
+impl LockReport {
+    /// Whether importing would reproduce the locked configuration exactly.
+    ///
+    /// Version drift is informational: the lock still pins the old release.
+    pub fn is_reproducible(&self) -> bool {
+        self.issues
+            .iter()
+            .all(|(_, issue)| matches!(issue, LockIssue::VersionDrifted { .. }))
+    }
+}
+
+impl fmt::Display for LockReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.issues.is_empty() {
+            return write!(f, "Lock verified");
+        }
+
+        for (server, issue) in &self.issues {
+            match issue {
+                LockIssue::VersionDrifted { locked, latest } => {
+                    writeln!(f, "{}: version drifted (locked {}, latest {})", server, locked, latest)?
+                }
+                LockIssue::IntegrityChanged { expected, actual } => {
+                    writeln!(f, "{}: integrity changed (expected {}, got {})", server, expected, actual)?
+                }
+                LockIssue::VersionMissing { locked } => {
+                    writeln!(f, "{}: locked version {} is no longer published", server, locked)?
+                }
+                LockIssue::ContentChanged => writeln!(f, "{}: configuration changed since locking", server)?,
+                LockIssue::NotLocked => writeln!(f, "{}: not locked", server)?,
+                LockIssue::RegistryUnavailable(e) => writeln!(f, "{}: registry unavailable ({})", server, e)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Release information fetched from a package registry
+#[derive(Debug, Clone, PartialEq)]
+struct ReleaseInfo {
+    latest: String,
+    /// Requested version, or the latest one if none was requested
+    version: String,
+    /// Whether the requested version is published
+    found: bool,
+    /// Integrity of the requested version
+    integrity: Option<String>,
+}
+
+/// Generates and verifies manifest locks against the npm and PyPI registries
+pub struct ManifestLocker {
+    npm_registry: String,
+    pypi_registry: String,
+}
+
+impl ManifestLocker {
+    pub fn new() -> Self {
+        Self::with_registries("https://registry.npmjs.org", "https://pypi.org")
+    }
+
+    /// Create a locker using custom registry base URLs
+    pub fn with_registries(npm_registry: &str, pypi_registry: &str) -> Self {
+        Self {
+            npm_registry: npm_registry.trim_end_matches('/').to_string(),
+            pypi_registry: pypi_registry.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Export servers as a manifest, optionally generating a lock
+    pub async fn export(&self, servers: &[McpServerConfig], lock: bool) -> Result<TeamManifest> {
+        let mut manifest = TeamManifest {
+            version: MANIFEST_VERSION.to_string(),
+            servers: servers.to_vec(),
+            lock: None,
+        };
+        if lock {
+            self.relock(&mut manifest).await?;
+        }
+        Ok(manifest)
+    }
+
+    /// Regenerate the lock section from the current registry state
+    pub async fn relock(&self, manifest: &mut TeamManifest) -> Result<()> {
+        let mut locked = BTreeMap::new();
+
+        for server in &manifest.servers {
+            let Some((registry, package, pinned)) = package_spec(server) else {
+                continue;
+            };
+
+            let release = self.fetch_release(registry, &package, pinned.as_deref()).await?;
+            if !release.found {
+                anyhow::bail!("Version {} of {} is not published", release.version, package);
+            }
+
+            locked.insert(server.name.clone(), LockedServer {
+                registry,
+                package,
+                version: release.version,
+                integrity: release.integrity,
+                content_hash: content_hash(server),
+            });
+        }
+
+        manifest.lock = Some(ManifestLock {
+            generated_at: Utc::now(),
+            servers: locked,
+        });
+        Ok(())
+    }
+
+    /// Verify a manifest's lock against the registries
+    pub async fn verify(&self, manifest: &TeamManifest) -> LockReport {
+        let mut report = LockReport::default();
+        let Some(lock) = &manifest.lock else {
+            return report;
+        };
+
+        for server in &manifest.servers {
+            let Some(entry) = lock.servers.get(&server.name) else {
+                if package_spec(server).is_some() {
+                    report.issues.push((server.name.clone(), LockIssue::NotLocked));
+                }
+                continue;
+            };
+
+            if content_hash(server) != entry.content_hash {
+                report.issues.push((server.name.clone(), LockIssue::ContentChanged));
+            }
+
+            let release = match self.fetch_release(entry.registry, &entry.package, Some(&entry.version)).await {
+                Ok(release) => release,
+                Err(e) => {
+                    report.issues.push((server.name.clone(), LockIssue::RegistryUnavailable(e.to_string())));
+                    continue;
+                }
+            };
+
+            if !release.found {
+                report.issues.push((server.name.clone(), LockIssue::VersionMissing {
+                    locked: entry.version.clone(),
+                }));
+            } else if let Some(expected) = &entry.integrity {
+                if release.integrity.as_ref() != Some(expected) {
+                    report.issues.push((server.name.clone(), LockIssue::IntegrityChanged {
+                        expected: expected.clone(),
+                        actual: release.integrity.clone().unwrap_or_else(|| "none".to_string()),
+                    }));
+                }
+            }
+
+            if release.latest != entry.version {
+                report.issues.push((server.name.clone(), LockIssue::VersionDrifted {
+                    locked: entry.version.clone(),
+                    latest: release.latest,
+                }));
+            }
+        }
+
+        report
+    }
+
+    async fn fetch_release(&self, registry: PackageRegistry, package: &str, version: Option<&str>) -> Result<ReleaseInfo> {
+        let url = match registry {
+            PackageRegistry::Npm => format!("{}/{}", self.npm_registry, package),
+            PackageRegistry::PyPI => format!("{}/pypi/{}/json", self.pypi_registry, package),
+        };
+
+        let response = net::get(&url, &[]).await?;
+        if !response.is_success() {
+            return Err(anyhow::anyhow!("Failed to fetch {}: {}", package, response.status_text));
+        }
+        let data: JsonValue = response.json()?;
+
+        let latest = match registry {
+            PackageRegistry::Npm => data.pointer("/dist-tags/latest"),
+            PackageRegistry::PyPI => data.pointer("/info/version"),
+        }
+        .and_then(|v| v.as_str())
+        .context("Registry response has no latest version")?
+        .to_string();
+
+        let version = version.unwrap_or(&latest).to_string();
+        let release = match registry {
+            PackageRegistry::Npm => data.get("versions").and_then(|v| v.get(&version)),
+            PackageRegistry::PyPI => data.get("releases").and_then(|r| r.get(&version)),
+        };
+
+        let integrity = release.and_then(|release| match registry {
+            PackageRegistry::Npm => release.pointer("/dist/integrity")
+                .and_then(|i| i.as_str())
+                .map(String::from),
+            PackageRegistry::PyPI => release.pointer("/0/digests/sha256")
+                .and_then(|d| d.as_str())
+                .map(|d| format!("sha256:{}", d)),
+        });
+
+        Ok(ReleaseInfo {
+            found: release.is_some(),
+            latest,
+            version,
+            integrity,
+        })
+    }
+}
+
+impl Default for ManifestLocker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TeamManifest {
+    /// Parse a manifest from JSON
+    pub fn from_json(content: &str) -> Result<Self> {
+        serde_json::from_str(content).context("Failed to parse team manifest")
+    }
+
+    /// Serialize a manifest to JSON with stable key order
+    pub fn to_json(&self) -> Result<String> {
+        let value = serde_json::to_value(self).context("Failed to serialize team manifest")?;
+        Ok(serde_json::to_string_pretty(&value)?)
+    }
+
+    /// Servers with package specs pinned to their locked versions
+    pub fn locked_servers(&self) -> Vec<McpServerConfig> {
+        let Some(lock) = &self.lock else {
+            return self.servers.clone();
+        };
+
+        self.servers
+            .iter()
+            .map(|server| {
+                let mut server = server.clone();
+                if let (Some(entry), Some(index)) = (lock.servers.get(&server.name), package_arg_index(&server)) {
+                    server.args[index] = match entry.registry {
+                        PackageRegistry::Npm => format!("{}@{}", entry.package, entry.version),
+                        PackageRegistry::PyPI => format!("{}=={}", entry.package, entry.version),
+                    };
+                }
+                server
+            })
+            .collect()
+    }
+}
+
+/// Hash of a server configuration, independent of map iteration order
+pub fn content_hash(server: &McpServerConfig) -> String {
+    use sha2::{Digest, Sha256};
+
+    // Round-trip through Value so object keys are sorted
+    let canonical = serde_json::to_value(server)
+        .map(|v| v.to_string())
+        .unwrap_or_default();
+
+    let mut hasher = Sha256::new();
+    hasher.update(canonical.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Index of the package argument for registry-installed servers
+fn package_arg_index(server: &McpServerConfig) -> Option<usize> {
+    let command = server.command.as_deref()?;
+    let command = command.rsplit(['/', '\\']).next().unwrap_or(command);
+    if !matches!(command, "npx" | "uvx") {
+        return None;
+    }
+    server.args.iter().position(|arg| !arg.starts_with('-'))
+}
+
+/// Registry, package name and pinned version of a server, if it runs a registry package
+pub(crate) fn package_spec(server: &McpServerConfig) -> Option<(PackageRegistry, String, Option<String>)> {
+    let spec = &server.args[package_arg_index(server)?];
+    let command = server.command.as_deref().unwrap_or_default();
+
+    if command.ends_with("uvx") {
+        return Some(match spec.split_once("==") {
+            Some((name, version)) => (PackageRegistry::PyPI, name.to_string(), Some(version.to_string())),
+            None => (PackageRegistry::PyPI, spec.clone(), None),
+        });
+    }
+
+    // Split "name@version", keeping the leading @ of scoped packages
+    Some(match spec.rfind('@').filter(|&i| i > 0) {
+        Some(i) => (PackageRegistry::Npm, spec[..i].to_string(), Some(spec[i + 1..].to_string())),
+        None => (PackageRegistry::Npm, spec.clone(), None),
+    })
+}
+
+// Every test talks to a local registry stub over HTTP
+#[cfg(all(test, feature = "online"))]
+mod tests {
+    use super::*;
+    use crate::detection::{ConfigSource, ServerMetadata, ServerType};
+    use std::collections::HashMap;
+    use std::io::{Read, Write};
+    use std::net::TcpListener;
+
+    /// Serve the same JSON body to every request, returning the base URL
+    fn serve_registry(body: JsonValue) -> String {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let body = body.to_string();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            body.len(),
+            body
+        );
+
+        std::thread::spawn(move || {
+            for mut stream in listener.incoming().flatten() {
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        format!("http://{}", addr)
+    }
+
+    fn npm_packument(latest: &str, versions: &[(&str, &str)]) -> JsonValue {
+        let versions: serde_json::Map<String, JsonValue> = versions
+            .iter()
+            .map(|(version, integrity)| {
+                (version.to_string(), serde_json::json!({ "dist": { "integrity": integrity } }))
+            })
+            .collect();
+        serde_json::json!({ "dist-tags": { "latest": latest }, "versions": versions })
+    }
+
+    fn npx_server(name: &str, package: &str) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            command: Some("npx".to_string()),
+            args: vec!["-y".to_string(), package.to_string()],
+            env: HashMap::new(),
+            cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
+            server_type: ServerType::Stdio,
+            metadata: ServerMetadata {
+                description: None,
+                version: None,
+                author: None,
+                capabilities: vec![],
+                enabled: true,
+                source: ConfigSource::MainConfig,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_superseded_version_reports_drift_and_stays_pinned() {
+        let servers = vec![npx_server("notes", "@acme/notes-mcp")];
+        let at_export = serve_registry(npm_packument("1.0.0", &[("1.0.0", "sha512-aaa")]));
+        let manifest = ManifestLocker::with_registries(&at_export, &at_export)
+            .export(&servers, true)
+            .await
+            .unwrap();
+
+        // Round-trip through JSON as a teammate importing the file would
+        let manifest = TeamManifest::from_json(&manifest.to_json().unwrap()).unwrap();
+        let at_import = serve_registry(npm_packument("1.1.0", &[("1.0.0", "sha512-aaa"), ("1.1.0", "sha512-bbb")]));
+        let report = ManifestLocker::with_registries(&at_import, &at_import).verify(&manifest).await;
+
+        assert_eq!(report.issues, vec![(
+            "notes".to_string(),
+            LockIssue::VersionDrifted { locked: "1.0.0".to_string(), latest: "1.1.0".to_string() },
+        )]);
+        assert!(report.is_reproducible());
+        assert_eq!(manifest.locked_servers()[0].args, vec!["-y", "@acme/notes-mcp@1.0.0"]);
+    }
+
+    #[tokio::test]
+    async fn test_integrity_mismatch_is_reported_until_relocked() {
+        let server = npx_server("notes", "@acme/notes-mcp@1.0.0");
+        let mut manifest = TeamManifest {
+            version: MANIFEST_VERSION.to_string(),
+            servers: vec![server.clone()],
+            lock: Some(ManifestLock {
+                generated_at: Utc::now(),
+                servers: BTreeMap::from([("notes".to_string(), LockedServer {
+                    registry: PackageRegistry::Npm,
+                    package: "@acme/notes-mcp".to_string(),
+                    version: "1.0.0".to_string(),
+                    integrity: Some("sha512-original".to_string()),
+                    content_hash: content_hash(&server),
+                })]),
+            }),
+        };
+
+        let registry = serve_registry(npm_packument("1.0.0", &[("1.0.0", "sha512-tampered")]));
+        let locker = ManifestLocker::with_registries(&registry, &registry);
+
+        let report = locker.verify(&manifest).await;
+        assert_eq!(report.issues, vec![(
+            "notes".to_string(),
+            LockIssue::IntegrityChanged {
+                expected: "sha512-original".to_string(),
+                actual: "sha512-tampered".to_string(),
+            },
+        )]);
+        assert!(!report.is_reproducible());
+        assert!(report.to_string().contains("integrity changed"));
+
+        locker.relock(&mut manifest).await.unwrap();
+        assert!(locker.verify(&manifest).await.issues.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_import_refuses_a_lock_that_no_longer_reproduces() {
+        use crate::configuration::resolution::PendingResolutionStore;
+        use crate::configuration::SyncManager;
+        use crate::detection::ApplicationRegistry;
+        use crate::filesystem::ConfigFileService;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("claude_desktop_config.json");
+        std::fs::write(&config_path, r#"{"mcpServers": {}}"#).unwrap();
+        let mut app = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        app.set_config_path(config_path.to_string_lossy());
+        app.alt_config_paths.clear();
+
+        let at_export = serve_registry(npm_packument("1.0.0", &[("1.0.0", "sha512-original")]));
+        let manifest = ManifestLocker::with_registries(&at_export, &at_export)
+            .export(&[npx_server("notes", "@acme/notes-mcp")], true)
+            .await
+            .unwrap();
+
+        let at_import = serve_registry(npm_packument("1.0.0", &[("1.0.0", "sha512-tampered")]));
+        let mut store = PendingResolutionStore::load(&temp_dir.path().join("pending.json")).unwrap();
+        let mut file_service = ConfigFileService::new("test".to_string(), temp_dir.path().join("backups"));
+        let import = SyncManager::new()
+            .import_manifest(
+                &app,
+                &manifest,
+                &ManifestLocker::with_registries(&at_import, &at_import),
+                &mut file_service,
+                &mut store,
+            )
+            .await
+            .unwrap();
+
+        assert!(import.outcome.is_none());
+        assert!(matches!(import.lock_report.issues[0].1, LockIssue::IntegrityChanged { .. }));
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), r#"{"mcpServers": {}}"#);
+    }
+}
@@ -0,0 +1,21 @@
+pub mod annotations;
+pub mod comment_headers;
+pub mod credentials;
+pub mod cursor_migration;
+pub mod engine;
+pub mod extensions;
+pub mod format_migration;
+pub mod groups;
+pub mod home_paths;
+pub mod integrity;
+pub mod manifest;
+pub mod provenance;
+pub mod quarantine;
+pub mod resolution;
+pub mod store;
+pub mod sync;
+pub mod undo;
+
+pub use engine::*;
+pub use store::*;
+pub use sync::*;
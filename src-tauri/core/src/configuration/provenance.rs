@@ -0,0 +1,376 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::detection::{is_metadata_key, ApplicationProfile};
+
+/// Key used to stamp server entries inside application configs
+pub const PROVENANCE_KEY: &str = "_mcpControl";
+
+/// Provenance metadata attached to a server entry written by MCP Control
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProvenanceStamp {
+    pub managed: bool,
+    pub source_hash: String,
+    pub written_at: DateTime<Utc>,
+}
+
+/// Whether a server entry was written by MCP Control
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ManagedStatus {
+    /// Written by MCP Control and unchanged since
+    Managed,
+    /// Written by MCP Control but edited by hand afterwards
+    ModifiedExternally,
+    /// Added by the user or another tool
+    Unmanaged,
+}
+
+/// Sidecar record for clients that reject unknown keys in server entries
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct SidecarRecord {
+    pub application_id: String,
+    pub server_name: String,
+    pub stamp: ProvenanceStamp,
+}
+
+/// Provenance sidecar file, keyed by application id and server name
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProvenanceSidecar {
+    #[serde(skip)]
+    path: PathBuf,
+    pub records: HashMap<String, SidecarRecord>,
+}
+
+/// Sidecar key of a server in an application; application ids never contain `:`
+fn record_key(application_id: &str, server_name: &str) -> String {
+    format!("{}:{}", application_id, server_name)
+}
+
+impl ProvenanceSidecar {
+    /// Load the sidecar from disk, starting empty if it does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut sidecar = if path.exists() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read provenance sidecar: {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| "Failed to parse provenance sidecar")?
+        } else {
+            Self::default()
+        };
+
+        // Older sidecars keyed records by content hash
+        sidecar.records = sidecar
+            .records
+            .into_values()
+            .map(|record| (record_key(&record.application_id, &record.server_name), record))
+            .collect();
+        sidecar.path = path.to_path_buf();
+        Ok(sidecar)
+    }
+
+    /// Load the sidecar from its default location in the user's home directory
+    pub fn load_default() -> Result<Self> {
+        let path = dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
+            .join(".mcp-control")
+            .join("provenance.json");
+        Self::load(&path)
+    }
+
+    /// Save the sidecar to disk
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create sidecar directory: {}", parent.display()))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .with_context(|| "Failed to serialize provenance sidecar")?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write provenance sidecar: {}", self.path.display()))?;
+
+        Ok(())
+    }
+
+    /// Record that an entry with the given hash was written for a server.
+    ///
+    /// Rewriting the same content keeps the original timestamp.
+    pub fn record(&mut self, application_id: &str, server_name: &str, source_hash: &str) {
+        let key = record_key(application_id, server_name);
+        if self.records.get(&key).is_some_and(|record| record.stamp.source_hash == source_hash) {
+            return;
+        }
+
+        self.records.insert(key, SidecarRecord {
+            application_id: application_id.to_string(),
+            server_name: server_name.to_string(),
+            stamp: ProvenanceStamp {
+                managed: true,
+                source_hash: source_hash.to_string(),
+                written_at: Utc::now(),
+            },
+        });
+    }
+
+    /// Drop the record of a server, e.g. when it is no longer managed
+    pub fn forget(&mut self, application_id: &str, server_name: &str) {
+        self.records.remove(&record_key(application_id, server_name));
+    }
+
+    /// Look up the managed status of an entry recorded in the sidecar
+    pub fn status_of(&self, application_id: &str, server_name: &str, entry: &JsonValue) -> ManagedStatus {
+        match self.records.get(&record_key(application_id, server_name)) {
+            Some(record) if record.stamp.source_hash == entry_hash(entry) => ManagedStatus::Managed,
+            Some(_) => ManagedStatus::ModifiedExternally,
+            None => ManagedStatus::Unmanaged,
+        }
+    }
+}
+
+/// Compute a short content hash of a server entry, ignoring any provenance stamp
+pub fn entry_hash(entry: &JsonValue) -> String {
+    use sha2::{Sha256, Digest};
+
+    let mut unstamped = entry.clone();
+    if let Some(obj) = unstamped.as_object_mut() {
+        obj.remove(PROVENANCE_KEY);
+    }
+
+    let mut hasher = Sha256::new();
+    hasher.update(unstamped.to_string().as_bytes());
+    let hash = hasher.finalize();
+
+    format!("{:x}", hash)[..16].to_string()
+}
+
+/// Stamp every server entry in an application config as managed by MCP Control.
+///
+/// Clients that tolerate unknown keys get an inline `_mcpControl` object;
+/// for the rest the provenance is recorded in the sidecar instead.
+pub fn stamp_servers(profile: &ApplicationProfile, config: &mut JsonValue, sidecar: &mut ProvenanceSidecar) {
+    let inline = profile.metadata.tolerates_extra_keys;
+    let Some(servers) = profile.servers_path().get_mut(config) else {
+        return;
+    };
+
+    for (name, entry) in servers.iter_mut().filter(|(name, _)| !is_metadata_key(name)) {
+        let hash = entry_hash(entry);
+
+        if !inline {
+            sidecar.record(&profile.id, name, &hash);
+            continue;
+        }
+
+        let Some(obj) = entry.as_object_mut() else {
+            continue;
+        };
+
+        // Keep the original timestamp when the entry content is unchanged
+        let unchanged = obj
+            .get(PROVENANCE_KEY)
+            .and_then(|s| serde_json::from_value::<ProvenanceStamp>(s.clone()).ok())
+            .map(|stamp| stamp.source_hash == hash)
+            .unwrap_or(false);
+
+        if !unchanged {
+            let stamp = ProvenanceStamp {
+                managed: true,
+                source_hash: hash,
+                written_at: Utc::now(),
+            };
+            obj.insert(PROVENANCE_KEY.to_string(), serde_json::json!(stamp));
+        }
+    }
+}
+
+/// Determine the managed status of a single server entry
+pub fn managed_status(
+    profile: &ApplicationProfile,
+    server_name: &str,
+    entry: &JsonValue,
+    sidecar: &ProvenanceSidecar,
+) -> ManagedStatus {
+    match entry.get(PROVENANCE_KEY) {
+        Some(stamp) => match serde_json::from_value::<ProvenanceStamp>(stamp.clone()) {
+            Ok(stamp) if stamp.source_hash == entry_hash(entry) => ManagedStatus::Managed,
+            _ => ManagedStatus::ModifiedExternally,
+        },
+        None => sidecar.status_of(&profile.id, server_name, entry),
+    }
+}
+
+/// Report the managed status of every server in an application config, sorted by name
+pub fn managed_statuses(
+    profile: &ApplicationProfile,
+    config: &JsonValue,
+    sidecar: &ProvenanceSidecar,
+) -> Vec<(String, ManagedStatus)> {
+    let mut statuses: Vec<(String, ManagedStatus)> = profile
+        .servers_path()
+        .get(config)
+        .map(|servers| {
+            servers
+                .iter()
+                .filter(|(name, _)| !is_metadata_key(name))
+                .map(|(name, entry)| (name.clone(), managed_status(profile, name, entry, sidecar)))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    statuses.sort_by(|a, b| a.0.cmp(&b.0));
+    statuses
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::ApplicationRegistry;
+    use tempfile::TempDir;
+
+    fn sample_config() -> JsonValue {
+        serde_json::json!({
+            "theme": "dark",
+            "mcpServers": {
+                "filesystem": { "command": "npx", "args": ["-y", "@mcp/filesystem"] },
+                "memory": { "command": "npx", "args": ["-y", "@mcp/memory"] }
+            }
+        })
+    }
+
+    fn profile(tolerates_extra_keys: bool) -> ApplicationProfile {
+        let mut profile = ApplicationRegistry::new()
+            .get_application("claude-desktop")
+            .unwrap()
+            .clone();
+        profile.metadata.tolerates_extra_keys = tolerates_extra_keys;
+        profile
+    }
+
+    #[test]
+    fn test_inline_stamping() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut sidecar = ProvenanceSidecar::load(&temp_dir.path().join("provenance.json")).unwrap();
+        let profile = profile(true);
+        let mut config = sample_config();
+
+        stamp_servers(&profile, &mut config, &mut sidecar);
+
+        assert!(sidecar.records.is_empty());
+        assert_eq!(config["mcpServers"]["filesystem"][PROVENANCE_KEY]["managed"], true);
+        assert_eq!(config["theme"], "dark");
+
+        let statuses = managed_statuses(&profile, &config, &sidecar);
+        assert_eq!(statuses, vec![
+            ("filesystem".to_string(), ManagedStatus::Managed),
+            ("memory".to_string(), ManagedStatus::Managed),
+        ]);
+
+        // Hand-edit a managed entry
+        config["mcpServers"]["memory"]["args"] = serde_json::json!(["-y", "@mcp/memory@2"]);
+        config["mcpServers"]["manual"] = serde_json::json!({ "command": "python3" });
+
+        let statuses = managed_statuses(&profile, &config, &sidecar);
+        assert_eq!(statuses, vec![
+            ("filesystem".to_string(), ManagedStatus::Managed),
+            ("manual".to_string(), ManagedStatus::Unmanaged),
+            ("memory".to_string(), ManagedStatus::ModifiedExternally),
+        ]);
+    }
+
+    #[test]
+    fn test_sidecar_stamping() {
+        let temp_dir = TempDir::new().unwrap();
+        let sidecar_path = temp_dir.path().join("provenance.json");
+        let mut sidecar = ProvenanceSidecar::load(&sidecar_path).unwrap();
+        let profile = profile(false);
+        let mut config = sample_config();
+
+        stamp_servers(&profile, &mut config, &mut sidecar);
+        sidecar.save().unwrap();
+
+        // Entries are left untouched for strict clients
+        assert_eq!(config, sample_config());
+        assert_eq!(sidecar.records.len(), 2);
+
+        let sidecar = ProvenanceSidecar::load(&sidecar_path).unwrap();
+        config["mcpServers"]["filesystem"]["env"] = serde_json::json!({ "ROOT": "/tmp" });
+        config["mcpServers"]["manual"] = serde_json::json!({ "command": "python3" });
+
+        let statuses = managed_statuses(&profile, &config, &sidecar);
+        assert_eq!(statuses, vec![
+            ("filesystem".to_string(), ManagedStatus::ModifiedExternally),
+            ("manual".to_string(), ManagedStatus::Unmanaged),
+            ("memory".to_string(), ManagedStatus::Managed),
+        ]);
+    }
+
+    #[test]
+    fn test_one_server_synced_to_two_strict_apps() {
+        let mut sidecar = ProvenanceSidecar::default();
+        let claude = profile(false);
+        let mut cursor = ApplicationRegistry::new().get_application("cursor").unwrap().clone();
+        cursor.metadata.tolerates_extra_keys = false;
+        let mut claude_config = sample_config();
+        let mut cursor_config = sample_config();
+
+        stamp_servers(&claude, &mut claude_config, &mut sidecar);
+        stamp_servers(&cursor, &mut cursor_config, &mut sidecar);
+        assert_eq!(sidecar.records.len(), 4);
+
+        // A hand-edit in the second app is told apart from the first app's identical entry
+        cursor_config["mcpServers"]["memory"]["args"] = serde_json::json!(["-y", "@mcp/memory@2"]);
+        assert_eq!(managed_status(&cursor, "memory", &cursor_config["mcpServers"]["memory"], &sidecar), ManagedStatus::ModifiedExternally);
+        assert_eq!(managed_status(&claude, "memory", &claude_config["mcpServers"]["memory"], &sidecar), ManagedStatus::Managed);
+
+        // A hand-added copy under another name matches a record's hash but is not managed
+        let copy = claude_config["mcpServers"]["filesystem"].clone();
+        assert_eq!(managed_status(&claude, "files", &copy, &sidecar), ManagedStatus::Unmanaged);
+    }
+
+    #[test]
+    fn test_metadata_keys_are_not_stamped() {
+        let mut sidecar = ProvenanceSidecar::default();
+        let mut config = sample_config();
+        config["mcpServers"]["$schema"] = serde_json::json!({ "$ref": "https://example.com/schema.json" });
+
+        stamp_servers(&profile(false), &mut config, &mut sidecar);
+        assert!(sidecar.records.values().all(|record| record.server_name != "$schema"));
+
+        stamp_servers(&profile(true), &mut config, &mut sidecar);
+        assert!(config["mcpServers"]["$schema"].get(PROVENANCE_KEY).is_none());
+    }
+
+    #[test]
+    fn test_stamps_flat_settings_key() {
+        let mut vscode = ApplicationRegistry::new().get_application("vscode").unwrap().clone();
+        vscode.metadata.version = Some("1.102.0".to_string());
+        vscode.metadata.tolerates_extra_keys = true;
+        let mut config = serde_json::json!({
+            "chat.mcp.servers": { "github": { "command": "npx" } }
+        });
+
+        stamp_servers(&vscode, &mut config, &mut ProvenanceSidecar::default());
+
+        assert_eq!(config["chat.mcp.servers"]["github"][PROVENANCE_KEY]["managed"], true);
+        let statuses = managed_statuses(&vscode, &config, &ProvenanceSidecar::default());
+        assert_eq!(statuses, vec![("github".to_string(), ManagedStatus::Managed)]);
+    }
+
+    #[test]
+    fn test_restamping_unchanged_entry_keeps_timestamp() {
+        let mut sidecar = ProvenanceSidecar::default();
+        let profile = profile(true);
+        let mut config = sample_config();
+
+        stamp_servers(&profile, &mut config, &mut sidecar);
+        let first = config["mcpServers"]["memory"][PROVENANCE_KEY].clone();
+        stamp_servers(&profile, &mut config, &mut sidecar);
+
+        assert_eq!(config["mcpServers"]["memory"][PROVENANCE_KEY], first);
+    }
+}
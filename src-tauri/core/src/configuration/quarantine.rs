@@ -0,0 +1,500 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use super::annotations::AnnotationStore;
+use crate::analysis::readme_parser::is_sample_value;
+use crate::analysis::server_analyzer::is_secret_name;
+use crate::detection::{is_metadata_key, ApplicationProfile, DisableStrategy};
+
+/// Containers tried after the profile's own path when locating a server entry
+const FALLBACK_CONTAINERS: &[&[&str]] = &[&["mcpServers"], &["mcp", "servers"], &["context_servers"]];
+
+/// Launchers whose first positional argument is the package they run
+const PACKAGE_RUNNERS: &[&str] = &["npx", "bunx", "uvx", "pnpx"];
+
+/// Why a server was judged too risky to keep running
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum QuarantineReason {
+    /// A credential env var still holds a documentation placeholder
+    PlaceholderCredential { env_name: String },
+    /// The command runs a file any user on the machine can modify
+    WorldWritableCommand { path: String },
+    /// The package was deprecated by its publisher for a security issue
+    SecurityDeprecation { package: String, message: String },
+}
+
+impl fmt::Display for QuarantineReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            QuarantineReason::PlaceholderCredential { env_name } => {
+                write!(f, "{} still holds a placeholder value", env_name)
+            }
+            QuarantineReason::WorldWritableCommand { path } => write!(f, "{} is writable by any user", path),
+            QuarantineReason::SecurityDeprecation { package, message } => {
+                write!(f, "{} is deprecated for security: {}", package, message)
+            }
+        }
+    }
+}
+
+/// A server disabled in one application because of audit findings
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuarantineRecord {
+    pub app_id: String,
+    pub reasons: Vec<QuarantineReason>,
+    pub quarantined_at: DateTime<Utc>,
+    /// How the server was switched off, and so how it is switched back on
+    pub strategy: DisableStrategy,
+    /// The entry exactly as it was before quarantine
+    pub original_entry: JsonValue,
+}
+
+/// A quarantined server as listed in summaries
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct QuarantinedServer {
+    pub server: String,
+    pub app_id: String,
+    pub reasons: Vec<QuarantineReason>,
+    pub quarantined_at: DateTime<Utc>,
+}
+
+/// When a quarantined server may be put back
+#[derive(Debug, Clone, Copy)]
+pub enum RestoreCondition<'a> {
+    /// The user has seen the findings and accepts the risk
+    Acknowledged,
+    /// Only if the audit no longer finds anything wrong with the entry
+    Fixed(&'a ServerAudit),
+}
+
+/// Checks for server setups that are compromised rather than merely untidy
+#[derive(Debug, Clone, Default)]
+pub struct ServerAudit {
+    security_deprecations: HashMap<String, String>,
+}
+
+impl ServerAudit {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Flag a package as deprecated for security, with the publisher's deprecation message
+    pub fn with_security_deprecation(mut self, package: &str, message: &str) -> Self {
+        self.security_deprecations.insert(package.to_string(), message.to_string());
+        self
+    }
+
+    /// Findings that warrant quarantining a server entry; empty when it looks safe to run
+    pub fn check(&self, entry: &JsonValue) -> Vec<QuarantineReason> {
+        let mut reasons = Vec::new();
+
+        if let Some(env) = entry.get("env").and_then(|e| e.as_object()) {
+            let mut names: Vec<&String> = env
+                .iter()
+                .filter(|(name, value)| is_secret_name(name) && value.as_str().is_some_and(is_sample_value))
+                .map(|(name, _)| name)
+                .collect();
+            names.sort();
+            reasons.extend(names.into_iter().map(|name| QuarantineReason::PlaceholderCredential { env_name: name.clone() }));
+        }
+
+        let command = entry.get("command").and_then(|c| c.as_str()).unwrap_or_default();
+        let first_arg = entry
+            .get("args")
+            .and_then(|a| a.as_array())
+            .and_then(|args| args.iter().filter_map(|a| a.as_str()).find(|a| !a.starts_with('-')));
+
+        for path in std::iter::once(command).chain(first_arg) {
+            if is_world_writable(Path::new(path)) {
+                reasons.push(QuarantineReason::WorldWritableCommand { path: path.to_string() });
+            }
+        }
+
+        let runner = Path::new(command).file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        if let Some(package) = first_arg.filter(|_| PACKAGE_RUNNERS.contains(&runner)) {
+            let package = strip_version(package);
+            if let Some(message) = self.security_deprecations.get(package) {
+                reasons.push(QuarantineReason::SecurityDeprecation {
+                    package: package.to_string(),
+                    message: message.clone(),
+                });
+            }
+        }
+
+        reasons
+    }
+}
+
+/// Switch a risky server off using the application's convention and record why in its annotations.
+///
+/// The entry as it was is stored in the annotation, so nothing is lost even when the
+/// application has no disable flag and the entry has to leave the config.
+pub fn quarantine_server(
+    app: &ApplicationProfile,
+    config: &mut JsonValue,
+    server: &str,
+    reasons: Vec<QuarantineReason>,
+    annotations: &mut AnnotationStore,
+) -> Result<()> {
+    if annotations.quarantine_record(server, &app.id).is_some() {
+        bail!("{} is already quarantined in {}", server, app.name);
+    }
+    let Some(servers) = server_container(app, config, server) else {
+        bail!("{} has no server named {}", app.name, server);
+    };
+
+    let Some(entry) = servers.get_mut(server).and_then(|e| e.as_object_mut()) else {
+        bail!("Server '{}' in {} is not an object", server, app.name);
+    };
+
+    let strategy = app.metadata.disable_strategy;
+    let original_entry = JsonValue::Object(entry.clone());
+    match strategy {
+        DisableStrategy::DisabledFlag => {
+            entry.insert("disabled".to_string(), JsonValue::Bool(true));
+        }
+        DisableStrategy::EnabledFlag => {
+            entry.insert("enabled".to_string(), JsonValue::Bool(false));
+        }
+        DisableStrategy::MoveAside => {
+            servers.remove(server);
+        }
+    }
+
+    log::warn!(
+        "Quarantined {} in {}: {}",
+        server,
+        app.name,
+        reasons.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+    );
+    annotations.entry(server).quarantines.push(QuarantineRecord {
+        app_id: app.id.clone(),
+        reasons,
+        quarantined_at: Utc::now(),
+        strategy,
+        original_entry,
+    });
+
+    Ok(())
+}
+
+/// Audit every server in the application's config and quarantine those with findings.
+///
+/// Servers already quarantined in this application are left alone. Returns the newly
+/// quarantined servers with their findings, sorted by name.
+pub fn quarantine_risky_servers(
+    app: &ApplicationProfile,
+    config: &mut JsonValue,
+    audit: &ServerAudit,
+    annotations: &mut AnnotationStore,
+) -> Result<Vec<(String, Vec<QuarantineReason>)>> {
+    let mut risky: Vec<(String, Vec<QuarantineReason>)> = app
+        .servers_path()
+        .get(config)
+        .into_iter()
+        .flatten()
+        .filter(|(name, _)| !is_metadata_key(name) && annotations.quarantine_record(name, &app.id).is_none())
+        .map(|(name, entry)| (name.clone(), audit.check(entry)))
+        .filter(|(_, reasons)| !reasons.is_empty())
+        .collect();
+    risky.sort_by(|a, b| a.0.cmp(&b.0));
+
+    for (server, reasons) in &risky {
+        quarantine_server(app, config, server, reasons.clone(), annotations)?;
+    }
+    Ok(risky)
+}
+
+/// Put a quarantined server back the way it was, or as the user has since fixed it.
+///
+/// With a flag strategy, edits made to the disabled entry are kept and only the flag is
+/// returned to its original state; a moved-aside entry comes back as it was stored.
+pub fn restore_server(
+    app: &ApplicationProfile,
+    config: &mut JsonValue,
+    server: &str,
+    condition: RestoreCondition<'_>,
+    annotations: &mut AnnotationStore,
+) -> Result<()> {
+    let Some(record) = annotations.quarantine_record(server, &app.id).cloned() else {
+        bail!("{} is not quarantined in {}", server, app.name);
+    };
+
+    let current = server_container(app, config, server).map(|servers| servers[server].clone());
+    let restored = match (record.strategy, current) {
+        (DisableStrategy::DisabledFlag, Some(entry)) => with_flag_from(entry, &record.original_entry, "disabled"),
+        (DisableStrategy::EnabledFlag, Some(entry)) => with_flag_from(entry, &record.original_entry, "enabled"),
+        (DisableStrategy::MoveAside, Some(_)) => {
+            bail!("{} was added back to {} while quarantined; remove it before restoring", server, app.name)
+        }
+        (_, None) => record.original_entry.clone(),
+    };
+
+    if let RestoreCondition::Fixed(audit) = condition {
+        let remaining = audit.check(&restored);
+        if !remaining.is_empty() {
+            bail!(
+                "{} is still risky: {}",
+                server,
+                remaining.iter().map(ToString::to_string).collect::<Vec<_>>().join("; ")
+            );
+        }
+    }
+
+    let container_path = app.servers_path().keys_in(config);
+    let servers = match server_container(app, config, server) {
+        Some(servers) => servers,
+        None => ensure_container(config, &container_path)?,
+    };
+    servers.insert(server.to_string(), restored);
+
+    let annotation = annotations.entry(server);
+    annotation.quarantines.retain(|r| r.app_id != app.id);
+    log::info!("Restored quarantined {} in {}", server, app.name);
+
+    Ok(())
+}
+
+impl AnnotationStore {
+    /// Quarantine of a server in one application, if any
+    pub fn quarantine_record(&self, server: &str, app_id: &str) -> Option<&QuarantineRecord> {
+        self.get(server)?.quarantines.iter().find(|r| r.app_id == app_id)
+    }
+
+    /// Every quarantined server, most recently quarantined first
+    pub fn quarantined_servers(&self) -> Vec<QuarantinedServer> {
+        let mut quarantined: Vec<QuarantinedServer> = self
+            .servers
+            .iter()
+            .flat_map(|(server, annotations)| {
+                annotations.quarantines.iter().map(move |record| QuarantinedServer {
+                    server: server.clone(),
+                    app_id: record.app_id.clone(),
+                    reasons: record.reasons.clone(),
+                    quarantined_at: record.quarantined_at,
+                })
+            })
+            .collect();
+        quarantined.sort_by(|a, b| b.quarantined_at.cmp(&a.quarantined_at).then_with(|| a.server.cmp(&b.server)));
+        quarantined
+    }
+}
+
+/// The servers object holding `server`: the profile's own container first, then common ones
+fn server_container<'a>(
+    app: &ApplicationProfile,
+    config: &'a mut JsonValue,
+    server: &str,
+) -> Option<&'a mut serde_json::Map<String, JsonValue>> {
+    let own = app.servers_path().keys_in(config);
+    let own: Vec<&str> = own.iter().map(String::as_str).collect();
+    let path = std::iter::once(own.as_slice())
+        .chain(FALLBACK_CONTAINERS.iter().copied())
+        .find(|path| {
+            path.iter()
+                .try_fold(&*config, |node, key| node.get(*key))
+                .is_some_and(|servers| servers.get(server).is_some())
+        })?
+        .to_vec();
+
+    path.into_iter()
+        .try_fold(config, |node, key| node.get_mut(key))
+        .and_then(|servers| servers.as_object_mut())
+}
+
+fn ensure_container<'a>(config: &'a mut JsonValue, path: &[String]) -> Result<&'a mut serde_json::Map<String, JsonValue>> {
+    let mut node = config;
+    for key in path {
+        let Some(object) = node.as_object_mut() else {
+            bail!("Expected an object at {} in the config", key);
+        };
+        node = object.entry(key.to_string()).or_insert_with(|| JsonValue::Object(serde_json::Map::new()));
+    }
+    node.as_object_mut()
+        .ok_or_else(|| anyhow::anyhow!("Server container {} is not an object", path.join(".")))
+}
+
+/// `entry` with `flag` set as it was in `original`, or absent if the original had no such key
+fn with_flag_from(mut entry: JsonValue, original: &JsonValue, flag: &str) -> JsonValue {
+    if let Some(fields) = entry.as_object_mut() {
+        match original.get(flag) {
+            Some(value) => {
+                fields.insert(flag.to_string(), value.clone());
+            }
+            None => {
+                fields.remove(flag);
+            }
+        }
+    }
+    entry
+}
+
+/// Drop a version or tag suffix: `@scope/pkg@1.2` becomes `@scope/pkg`, `pkg==1.0` becomes `pkg`
+fn strip_version(package: &str) -> &str {
+    let package = package.split("==").next().unwrap_or(package);
+    match package.rfind('@') {
+        Some(index) if index > 0 => &package[..index],
+        _ => package,
+    }
+}
+
+#[cfg(unix)]
+fn is_world_writable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    path.is_absolute()
+        && std::fs::metadata(path).is_ok_and(|metadata| metadata.is_file() && metadata.permissions().mode() & 0o002 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_world_writable(_path: &Path) -> bool {
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::ApplicationRegistry;
+    use tempfile::TempDir;
+
+    fn leaky_entry() -> JsonValue {
+        serde_json::json!({
+            "command": "npx",
+            "args": ["-y", "@acme/weather-mcp@1.2.0"],
+            "env": {"WEATHER_API_KEY": "your-api-key-here", "REGION": "eu"}
+        })
+    }
+
+    #[test]
+    fn test_quarantine_and_restore_across_strategies() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut annotations = AnnotationStore::load(&temp_dir.path().join("annotations.json")).unwrap();
+        let registry = ApplicationRegistry::new();
+        let audit = ServerAudit::new().with_security_deprecation("@acme/weather-mcp", "token exfiltration, upgrade to 2.x");
+
+        let cases = [
+            ("claude-desktop", DisableStrategy::DisabledFlag, serde_json::json!({"mcpServers": {"weather": leaky_entry()}})),
+            ("zed", DisableStrategy::EnabledFlag, serde_json::json!({"context_servers": {"weather": leaky_entry()}})),
+            ("vscode", DisableStrategy::MoveAside, serde_json::json!({"mcpServers": {"weather": leaky_entry()}})),
+        ];
+
+        for (app_id, strategy, original) in cases {
+            let app = registry.get_application(app_id).unwrap();
+            assert_eq!(app.metadata.disable_strategy, strategy);
+
+            let mut config = original.clone();
+            let reasons = audit.check(&leaky_entry());
+            assert_eq!(reasons.len(), 2);
+            quarantine_server(app, &mut config, "weather", reasons, &mut annotations).unwrap();
+
+            let servers = server_container(app, &mut config, "weather").map(|s| s["weather"].clone());
+            match strategy {
+                DisableStrategy::DisabledFlag => assert_eq!(servers.unwrap()["disabled"], true),
+                DisableStrategy::EnabledFlag => assert_eq!(servers.unwrap()["enabled"], false),
+                DisableStrategy::MoveAside => assert!(servers.is_none()),
+            }
+            let record = annotations.quarantine_record("weather", app_id).unwrap();
+            assert_eq!(record.original_entry, leaky_entry());
+            assert!(quarantine_server(app, &mut config, "weather", Vec::new(), &mut annotations).is_err());
+
+            // Still risky, so only an acknowledgement brings it back
+            assert!(restore_server(app, &mut config, "weather", RestoreCondition::Fixed(&audit), &mut annotations).is_err());
+            restore_server(app, &mut config, "weather", RestoreCondition::Acknowledged, &mut annotations).unwrap();
+            assert_eq!(config, original, "{} did not round-trip", app_id);
+            assert!(annotations.quarantine_record("weather", app_id).is_none());
+        }
+    }
+
+    #[test]
+    fn test_quarantine_refuses_non_object_entry() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut annotations = AnnotationStore::load(&temp_dir.path().join("annotations.json")).unwrap();
+        let registry = ApplicationRegistry::new();
+
+        for app_id in ["claude-desktop", "zed"] {
+            let app = registry.get_application(app_id).unwrap();
+            let container = app.get_mcp_servers_path()[0].clone();
+            let mut config = serde_json::json!({ container: {"weather": "npx -y @acme/weather-mcp"} });
+            let original = config.clone();
+
+            let err = quarantine_server(app, &mut config, "weather", Vec::new(), &mut annotations).unwrap_err();
+            assert!(err.to_string().contains("not an object"), "{}", err);
+            assert_eq!(config, original);
+            assert!(annotations.quarantine_record("weather", app_id).is_none());
+        }
+    }
+
+    #[test]
+    fn test_restore_after_fix_keeps_edits_and_summary_lists_quarantine() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("annotations.json");
+        let mut annotations = AnnotationStore::load(&path).unwrap();
+        let registry = ApplicationRegistry::new();
+        let app = registry.get_application("claude-desktop").unwrap();
+        let audit = ServerAudit::new();
+
+        let mut config = serde_json::json!({"mcpServers": {"weather": leaky_entry()}});
+        quarantine_server(app, &mut config, "weather", audit.check(&leaky_entry()), &mut annotations).unwrap();
+        annotations.save().unwrap();
+
+        let annotations_on_disk = AnnotationStore::load(&path).unwrap();
+        let quarantined = annotations_on_disk.quarantined_servers();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(quarantined[0].app_id, "claude-desktop");
+        assert_eq!(quarantined[0].reasons[0].to_string(), "WEATHER_API_KEY still holds a placeholder value");
+
+        // The user fills in a real key while the server is quarantined
+        config["mcpServers"]["weather"]["env"]["WEATHER_API_KEY"] = "wk_4f9a1c".into();
+        restore_server(app, &mut config, "weather", RestoreCondition::Fixed(&audit), &mut annotations).unwrap();
+        assert_eq!(config["mcpServers"]["weather"]["env"]["WEATHER_API_KEY"], "wk_4f9a1c");
+        assert!(config["mcpServers"]["weather"].get("disabled").is_none());
+        assert!(annotations.quarantined_servers().is_empty());
+    }
+
+    #[test]
+    fn test_quarantine_risky_servers_skips_safe_and_quarantined() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut annotations = AnnotationStore::load(&temp_dir.path().join("annotations.json")).unwrap();
+        let registry = ApplicationRegistry::new();
+        let app = registry.get_application("claude-desktop").unwrap();
+        let audit = ServerAudit::new();
+
+        let mut config = serde_json::json!({"mcpServers": {
+            "weather": leaky_entry(),
+            "github": {"command": "npx", "env": {"GITHUB_TOKEN": "ghp_4f9a1c"}},
+            "aws": {"command": "uvx", "env": {"AWS_SECRET_ACCESS_KEY": "WJALRXUTNFEMI"}}
+        }});
+        let quarantined = quarantine_risky_servers(app, &mut config, &audit, &mut annotations).unwrap();
+        assert_eq!(quarantined.len(), 1);
+        assert_eq!(quarantined[0].0, "weather");
+        assert_eq!(config["mcpServers"]["weather"]["disabled"], true);
+        assert!(config["mcpServers"]["github"].get("disabled").is_none());
+        assert!(config["mcpServers"]["aws"].get("disabled").is_none());
+
+        // A second run does not quarantine the same server again
+        assert!(quarantine_risky_servers(app, &mut config, &audit, &mut annotations).unwrap().is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_world_writable_script_is_flagged() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let script = temp_dir.path().join("server.sh");
+        std::fs::write(&script, "#!/bin/sh\n").unwrap();
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o777)).unwrap();
+
+        let entry = serde_json::json!({"command": "bash", "args": [script.to_str().unwrap()]});
+        let reasons = ServerAudit::new().check(&entry);
+        assert_eq!(reasons, vec![QuarantineReason::WorldWritableCommand { path: script.to_string_lossy().to_string() }]);
+
+        std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(ServerAudit::new().check(&entry).is_empty());
+    }
+}
@@ -0,0 +1,481 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::detection::McpServerConfig;
+
+/// What a user may do about an incoming server that differs from the one already configured
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ResolutionKind {
+    KeepLocal,
+    TakeIncoming,
+    MergeFields,
+    RenameIncoming,
+}
+
+/// The user's choice for one conflict
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "kebab-case")]
+pub enum Resolution {
+    /// Leave the configured server as it is and drop the incoming one
+    KeepLocal,
+    /// Replace the configured server with the incoming one
+    TakeIncoming,
+    /// Keep the configured server, with the incoming command, args, and type and both sets of env and options
+    MergeFields,
+    /// Keep both, adding the incoming server under a new name
+    RenameIncoming { name: String },
+}
+
+impl Resolution {
+    pub fn kind(&self) -> ResolutionKind {
+        match self {
+            Self::KeepLocal => ResolutionKind::KeepLocal,
+            Self::TakeIncoming => ResolutionKind::TakeIncoming,
+            Self::MergeFields => ResolutionKind::MergeFields,
+            Self::RenameIncoming { .. } => ResolutionKind::RenameIncoming,
+        }
+    }
+}
+
+/// Operation that stopped to ask about conflicts
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConflictOperation {
+    /// Syncing servers from the central store into an application
+    Sync,
+    /// Importing a team manifest into an application
+    ManifestImport,
+}
+
+impl ConflictOperation {
+    /// Where the operation's incoming servers come from, as shown next to each conflict
+    pub fn incoming_origin(&self) -> &'static str {
+        match self {
+            Self::Sync => "central store",
+            Self::ManifestImport => "team manifest",
+        }
+    }
+}
+
+/// An incoming server that differs from the configured server of the same name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerConflict {
+    pub id: String,
+    pub server_name: String,
+    pub local: McpServerConfig,
+    pub incoming: McpServerConfig,
+    /// Where each side came from, e.g. "Claude Desktop config" or "team manifest"
+    pub local_origin: String,
+    pub incoming_origin: String,
+    pub allowed: Vec<ResolutionKind>,
+}
+
+/// Why a set of choices cannot be applied; nothing is written in any of these cases
+#[derive(Debug, thiserror::Error, PartialEq)]
+pub enum ResolutionError {
+    #[error("No resolution chosen for {}", .0.join(", "))]
+    Unresolved(Vec<String>),
+    #[error("Unknown conflict '{0}'")]
+    UnknownConflict(String),
+    #[error("Resolution {kind:?} is not allowed for conflict '{id}'")]
+    NotAllowed { id: String, kind: ResolutionKind },
+    #[error("Cannot rename to '{name}': a server with that name already exists")]
+    NameTaken { name: String },
+}
+
+/// Conflicts an operation is waiting on, with everything needed to finish it later
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PendingResolution {
+    /// Handed to the UI to resume the operation, also after a restart
+    pub token: String,
+    pub operation: ConflictOperation,
+    pub app_id: String,
+    pub created_at: DateTime<Utc>,
+    /// Configured servers, in their configured order
+    pub local: Vec<McpServerConfig>,
+    /// Every incoming server, so the operation can be planned again if the config changes
+    #[serde(default)]
+    pub incoming: Vec<McpServerConfig>,
+    /// Incoming servers that are new or identical to the configured ones
+    pub clean: Vec<McpServerConfig>,
+    pub conflicts: Vec<ServerConflict>,
+}
+
+impl PendingResolution {
+    /// Compare incoming servers against the configured ones by name and content
+    pub fn plan(
+        operation: ConflictOperation,
+        app_id: &str,
+        local: Vec<McpServerConfig>,
+        incoming: Vec<McpServerConfig>,
+        local_origin: &str,
+        incoming_origin: &str,
+    ) -> Self {
+        let mut clean = Vec::new();
+        let mut conflicts = Vec::new();
+
+        for server in incoming.iter().cloned() {
+            match local.iter().find(|existing| existing.name == server.name) {
+                Some(existing) if !same_launch(existing, &server) => {
+                    conflicts.push(ServerConflict {
+                        id: format!("{}:{}", app_id, server.name),
+                        server_name: server.name.clone(),
+                        local: existing.clone(),
+                        incoming: server,
+                        local_origin: local_origin.to_string(),
+                        incoming_origin: incoming_origin.to_string(),
+                        allowed: vec![
+                            ResolutionKind::KeepLocal,
+                            ResolutionKind::TakeIncoming,
+                            ResolutionKind::MergeFields,
+                            ResolutionKind::RenameIncoming,
+                        ],
+                    });
+                }
+                Some(_) => {}
+                None => clean.push(server),
+            }
+        }
+
+        Self {
+            token: Uuid::new_v4().to_string(),
+            operation,
+            app_id: app_id.to_string(),
+            created_at: Utc::now(),
+            local,
+            incoming,
+            clean,
+            conflicts,
+        }
+    }
+
+    /// Plan the same incoming servers again against the configured servers as they are now.
+    ///
+    /// The token is kept, so the UI's handle stays valid.
+    pub fn replan(&self, local: Vec<McpServerConfig>, local_origin: &str) -> Self {
+        let incoming = if self.incoming.is_empty() {
+            // Saved before incoming servers were kept; identical servers were not recorded
+            self.clean.iter().cloned().chain(self.conflicts.iter().map(|c| c.incoming.clone())).collect()
+        } else {
+            self.incoming.clone()
+        };
+        Self {
+            token: self.token.clone(),
+            ..Self::plan(self.operation.clone(), &self.app_id, local, incoming, local_origin, self.operation.incoming_origin())
+        }
+    }
+
+    pub fn has_conflicts(&self) -> bool {
+        !self.conflicts.is_empty()
+    }
+
+    /// The full server list to write once every conflict has a choice.
+    ///
+    /// Choices are checked before anything is built, so an incomplete or invalid set
+    /// never yields a partly resolved list.
+    pub fn resolve(&self, choices: &HashMap<String, Resolution>) -> Result<Vec<McpServerConfig>, ResolutionError> {
+        if let Some(id) = choices.keys().find(|id| !self.conflicts.iter().any(|c| &c.id == *id)) {
+            return Err(ResolutionError::UnknownConflict(id.clone()));
+        }
+        let unresolved: Vec<String> = self
+            .conflicts
+            .iter()
+            .filter(|c| !choices.contains_key(&c.id))
+            .map(|c| c.id.clone())
+            .collect();
+        if !unresolved.is_empty() {
+            return Err(ResolutionError::Unresolved(unresolved));
+        }
+
+        let mut servers = self.local.clone();
+        servers.extend(self.clean.iter().cloned());
+
+        for conflict in &self.conflicts {
+            let choice = &choices[&conflict.id];
+            if !conflict.allowed.contains(&choice.kind()) {
+                return Err(ResolutionError::NotAllowed { id: conflict.id.clone(), kind: choice.kind() });
+            }
+
+            let position = servers.iter().position(|s| s.name == conflict.server_name);
+            match choice {
+                Resolution::KeepLocal => {}
+                Resolution::TakeIncoming => {
+                    if let Some(position) = position {
+                        servers[position] = conflict.incoming.clone();
+                    }
+                }
+                Resolution::MergeFields => {
+                    if let Some(position) = position {
+                        servers[position] = merge_fields(&conflict.local, &conflict.incoming);
+                    }
+                }
+                Resolution::RenameIncoming { name } => {
+                    if servers.iter().any(|s| &s.name == name) {
+                        return Err(ResolutionError::NameTaken { name: name.clone() });
+                    }
+                    servers.push(McpServerConfig { name: name.clone(), ..conflict.incoming.clone() });
+                }
+            }
+        }
+
+        Ok(servers)
+    }
+}
+
+/// Whether two servers start the same way, ignoring descriptive metadata
+fn same_launch(a: &McpServerConfig, b: &McpServerConfig) -> bool {
+    a.command == b.command
+        && a.args == b.args
+        && a.env == b.env
+        && a.cwd == b.cwd
+        && a.inherit_env == b.inherit_env
+        && a.server_type == b.server_type
+        && a.extra_options == b.extra_options
+}
+
+/// Incoming launch settings on top of the configured server, keeping both sides' env and options
+fn merge_fields(local: &McpServerConfig, incoming: &McpServerConfig) -> McpServerConfig {
+    let mut merged = local.clone();
+    merged.command = incoming.command.clone().or(merged.command);
+    merged.args = incoming.args.clone();
+    merged.server_type = incoming.server_type.clone();
+    merged.env.extend(incoming.env.clone());
+    merged.extra_options.extend(incoming.extra_options.clone());
+    if incoming.cwd.is_some() {
+        merged.cwd = incoming.cwd.clone();
+    }
+    merged
+}
+
+/// Pending resolutions sidecar file, keyed by token
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PendingResolutionStore {
+    #[serde(skip)]
+    path: PathBuf,
+    pub pending: HashMap<String, PendingResolution>,
+}
+
+impl PendingResolutionStore {
+    /// Load pending resolutions from disk, starting empty if the file does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut store = if path.exists() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read pending resolutions: {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| "Failed to parse pending resolutions")?
+        } else {
+            Self::default()
+        };
+
+        store.path = path.to_path_buf();
+        Ok(store)
+    }
+
+    /// Load pending resolutions from their default location in the user's home directory
+    pub fn load_default() -> Result<Self> {
+        let path = dirs::home_dir()
+            .ok_or_else(|| anyhow!("Could not find home directory"))?
+            .join(".mcp-control")
+            .join("pending_resolutions.json");
+        Self::load(&path)
+    }
+
+    /// Save pending resolutions to disk
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .with_context(|| "Failed to serialize pending resolutions")?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write pending resolutions: {}", self.path.display()))?;
+
+        Ok(())
+    }
+
+    /// Keep a resolution until the user comes back to it
+    pub fn insert(&mut self, pending: PendingResolution) {
+        self.pending.insert(pending.token.clone(), pending);
+    }
+
+    pub fn get(&self, token: &str) -> Option<&PendingResolution> {
+        self.pending.get(token)
+    }
+
+    /// Forget a resolution once its operation has been applied
+    pub fn remove(&mut self, token: &str) -> Option<PendingResolution> {
+        self.pending.remove(token)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::configuration::{ResolutionOutcome, SyncManager};
+    use crate::configuration::manifest::{ManifestLocker, TeamManifest};
+    use crate::detection::{ApplicationRegistry, ConfigSource, ServerMetadata, ServerType};
+    use crate::filesystem::ConfigFileService;
+
+    fn server(name: &str, command: &str, env: &[(&str, &str)]) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            command: Some(command.to_string()),
+            args: vec![format!("{}-server", name)],
+            env: env.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
+            server_type: ServerType::Stdio,
+            metadata: ServerMetadata {
+                description: None,
+                version: None,
+                author: None,
+                capabilities: Vec::new(),
+                enabled: true,
+                source: ConfigSource::MainConfig,
+            },
+        }
+    }
+
+    #[test]
+    fn test_resolve_mixed_batch() {
+        let local = vec![
+            server("github", "npx", &[]),
+            server("slack", "npx", &[("SLACK_TOKEN", "local")]),
+            server("fetch", "uvx", &[("LOCAL_ONLY", "1")]),
+            server("notes", "node", &[]),
+            server("time", "uvx", &[]),
+            server("memory", "npx", &[]),
+        ];
+        let incoming = vec![
+            server("github", "bunx", &[]),
+            server("slack", "bunx", &[("SLACK_TOKEN", "team")]),
+            server("fetch", "pipx", &[("TEAM_ONLY", "1")]),
+            server("notes", "deno", &[]),
+            server("time", "docker", &[]),
+            server("memory", "npx", &[]),
+            server("search", "npx", &[]),
+        ];
+        let pending = PendingResolution::plan(
+            ConflictOperation::ManifestImport,
+            "cursor",
+            local,
+            incoming,
+            "Cursor config",
+            "team manifest",
+        );
+        assert_eq!(pending.conflicts.len(), 5);
+        assert_eq!(pending.clean.iter().map(|s| s.name.as_str()).collect::<Vec<_>>(), vec!["search"]);
+
+        let mut choices = HashMap::from([
+            ("cursor:github".to_string(), Resolution::KeepLocal),
+            ("cursor:slack".to_string(), Resolution::TakeIncoming),
+            ("cursor:fetch".to_string(), Resolution::MergeFields),
+            ("cursor:notes".to_string(), Resolution::RenameIncoming { name: "notes-team".to_string() }),
+        ]);
+
+        // One conflict left open: nothing is produced for any server
+        assert_eq!(pending.resolve(&choices), Err(ResolutionError::Unresolved(vec!["cursor:time".to_string()])));
+
+        choices.insert("cursor:time".to_string(), Resolution::RenameIncoming { name: "github".to_string() });
+        assert_eq!(pending.resolve(&choices), Err(ResolutionError::NameTaken { name: "github".to_string() }));
+
+        choices.insert("cursor:time".to_string(), Resolution::KeepLocal);
+        let servers = pending.resolve(&choices).unwrap();
+        let by_name = |name: &str| servers.iter().find(|s| s.name == name).unwrap();
+        assert_eq!(servers.len(), 8);
+        assert_eq!(by_name("github").command.as_deref(), Some("npx"));
+        assert_eq!(by_name("slack").env["SLACK_TOKEN"], "team");
+        let fetch = by_name("fetch");
+        assert_eq!(fetch.command.as_deref(), Some("pipx"));
+        assert_eq!((fetch.env["LOCAL_ONLY"].as_str(), fetch.env["TEAM_ONLY"].as_str()), ("1", "1"));
+        assert_eq!(by_name("notes").command.as_deref(), Some("node"));
+        assert_eq!(by_name("notes-team").command.as_deref(), Some("deno"));
+        assert_eq!(by_name("time").command.as_deref(), Some("uvx"));
+        assert!(servers.iter().any(|s| s.name == "search"));
+    }
+
+    #[tokio::test]
+    async fn test_import_waits_for_resolution_and_survives_restart() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("claude_desktop_config.json");
+        let original = r#"{"mcpServers": {"github": {"command": "npx", "args": ["github-server"]}}}"#;
+        fs::write(&config_path, original).unwrap();
+
+        let mut app = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        app.set_config_path(config_path.to_string_lossy());
+        app.alt_config_paths.clear();
+
+        let manifest = TeamManifest {
+            version: "1".to_string(),
+            servers: vec![server("github", "bunx", &[]), server("search", "npx", &[])],
+            lock: None,
+        };
+        let store_path = temp_dir.path().join("pending_resolutions.json");
+        let mut store = PendingResolutionStore::load(&store_path).unwrap();
+        let mut file_service = ConfigFileService::new("test".to_string(), temp_dir.path().join("backups"));
+
+        let import = SyncManager::new()
+            .import_manifest(&app, &manifest, &ManifestLocker::new(), &mut file_service, &mut store)
+            .await
+            .unwrap();
+        assert!(import.lock_report.issues.is_empty());
+        let Some(ResolutionOutcome::NeedsResolution(pending)) = import.outcome else {
+            panic!("the differing github server needs a decision");
+        };
+        assert_eq!(pending.conflicts[0].id, "claude-desktop:github");
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+
+        // The UI can pick the decision back up after a restart, and a partial answer writes nothing
+        let mut store = PendingResolutionStore::load(&store_path).unwrap();
+        assert_eq!(store.get(&pending.token), Some(&pending));
+        let error = SyncManager::new()
+            .resume_resolution(&app, &pending.token, &HashMap::new(), &mut file_service, &mut store)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ResolutionError>(),
+            Some(&ResolutionError::Unresolved(vec!["claude-desktop:github".to_string()]))
+        );
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), original);
+        assert!(store.get(&pending.token).is_some());
+
+        // The config changed while the user was deciding: a differing search server appeared
+        fs::write(
+            &config_path,
+            r#"{"mcpServers": {"github": {"command": "npx", "args": ["github-server"]}, "search": {"command": "uvx"}}}"#,
+        )
+        .unwrap();
+        let choices = HashMap::from([("claude-desktop:github".to_string(), Resolution::TakeIncoming)]);
+        let error = SyncManager::new()
+            .resume_resolution(&app, &pending.token, &choices, &mut file_service, &mut store)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.downcast_ref::<ResolutionError>(),
+            Some(&ResolutionError::Unresolved(vec!["claude-desktop:search".to_string()]))
+        );
+        let replanned = PendingResolutionStore::load(&store_path).unwrap().get(&pending.token).unwrap().clone();
+        assert_eq!(replanned.conflicts.len(), 2);
+
+        let mut choices = choices;
+        choices.insert("claude-desktop:search".to_string(), Resolution::KeepLocal);
+        let result = SyncManager::new()
+            .resume_resolution(&app, &pending.token, &choices, &mut file_service, &mut store)
+            .await
+            .unwrap();
+        assert!(result.success);
+        let written: serde_json::Value = serde_json::from_str(&fs::read_to_string(&config_path).unwrap()).unwrap();
+        assert_eq!(written["mcpServers"]["github"]["command"], "bunx");
+        assert_eq!(written["mcpServers"]["search"]["command"], "uvx");
+        assert!(store.get(&pending.token).is_none());
+    }
+}
@@ -275,6 +275,8 @@ mod tests {
             args: vec!["server.js".to_string()],
             env: HashMap::new(),
             cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
             server_type: crate::detection::ServerType::Stdio,
             metadata: crate::detection::ServerMetadata {
                 version: Some("1.0.0".to_string()),
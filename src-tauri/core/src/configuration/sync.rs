@@ -0,0 +1,1278 @@
+use std::collections::HashMap;
+use anyhow::{Result, Context};
+use serde_json::Value as JsonValue;
+
+use crate::detection::{merge_config_layers, ApplicationProfile, ConfigLayer, ConfigValidator, DedupeReport, McpServerConfig, SelfReferenceGuard, StructureIssue};
+use crate::filesystem::config::sort_json_keys;
+use crate::filesystem::ConfigFileService;
+use crate::adapters::AdapterFactory;
+use crate::adapters::declarative::declared_adapter;
+use crate::adapters::jetbrains_options::{is_options_xml_backed, read_options_xml, write_options_xml};
+use crate::adapters::warp_store::{is_sqlite_backed, WarpStateStore};
+use super::extensions::{translate_cwd, translate_extension_fields, StrippedOption};
+use super::home_paths::{ForeignHomePath, HomeRelocator};
+use super::annotations::AnnotationStore;
+use super::comment_headers::CommentStamper;
+use super::manifest::{LockReport, ManifestLocker, TeamManifest};
+use super::quarantine::{quarantine_risky_servers, restore_server, QuarantineReason, RestoreCondition, ServerAudit};
+use super::provenance::{stamp_servers, ProvenanceSidecar};
+use super::resolution::{ConflictOperation, PendingResolution, PendingResolutionStore, Resolution};
+
+/// Manages synchronization between central store and application configurations
+#[derive(Debug)]
+pub struct SyncManager {
+    /// Keep a server's cwd in clients without native support by wrapping its command in a shell
+    wrap_unsupported_cwd: bool,
+    /// Refreshes provenance comments above managed entries after every write
+    comment_stamper: Option<CommentStamper>,
+    /// Write application configs with alphabetically sorted keys for minimal diffs
+    sort_keys: bool,
+}
+
+/// Synchronization result
+#[derive(Debug, Clone)]
+pub struct SyncResult {
+    pub success: bool,
+    pub servers_synced: usize,
+    pub conflicts: Vec<SyncConflict>,
+    pub errors: Vec<String>,
+    /// Client-specific options that the target application does not support
+    pub stripped_options: Vec<StrippedOption>,
+    /// Paths under other users' homes, including ones that were re-homed automatically
+    pub foreign_home_paths: Vec<ForeignHomePath>,
+}
+
+/// Outcome of an operation that stops for the user's decision on conflicting servers
+#[derive(Debug, Clone)]
+pub enum ResolutionOutcome {
+    Applied(SyncResult),
+    /// Nothing was written; resume with the token once every conflict has a choice
+    NeedsResolution(PendingResolution),
+}
+
+/// Outcome of importing a team manifest
+#[derive(Debug, Clone)]
+pub struct ManifestImport {
+    /// Lock verification against the registries; version drift alone does not stop the import
+    pub lock_report: LockReport,
+    /// None when the lock no longer reproduces and nothing was written; relock to accept the registries
+    pub outcome: Option<ResolutionOutcome>,
+}
+
+/// Config snippet for a single server, ready to paste into an application config
+#[derive(Debug, Clone)]
+pub struct RenderedSnippet {
+    pub content: String,
+    pub stripped_options: Vec<StrippedOption>,
+}
+
+/// Configuration synchronization conflict
+#[derive(Debug, Clone)]
+pub struct SyncConflict {
+    pub server_name: String,
+    pub conflict_type: ConflictType,
+    pub central_version: Option<McpServerConfig>,
+    pub app_version: Option<McpServerConfig>,
+    pub resolution: ConflictResolution,
+}
+
+/// Type of synchronization conflict
+#[derive(Debug, Clone)]
+pub enum ConflictType {
+    ServerModifiedInBoth,
+    ServerRemovedFromApp,
+    ServerAddedToApp,
+    ConfigurationMismatch,
+}
+
+/// How to resolve a conflict
+#[derive(Debug, Clone)]
+pub enum ConflictResolution {
+    UseCentral,
+    UseApplication,
+    Merge,
+    Skip,
+}
+
+impl SyncManager {
+    /// Create a new sync manager
+    pub fn new() -> Self {
+        Self {
+            wrap_unsupported_cwd: false,
+            comment_stamper: None,
+            sort_keys: false,
+        }
+    }
+
+    /// Write configs with stable, sorted keys so version-controlled configs diff cleanly
+    pub fn with_sorted_keys(mut self, enabled: bool) -> Self {
+        self.sort_keys = enabled;
+        self
+    }
+
+    /// Write provenance comments above managed servers in configs that allow comments
+    pub fn with_comment_stamper(mut self, stamper: CommentStamper) -> Self {
+        self.comment_stamper = Some(stamper);
+        self
+    }
+
+    /// Opt in to the shell wrapper for clients that cannot set a working directory
+    pub fn with_cwd_wrapper(mut self, enabled: bool) -> Self {
+        self.wrap_unsupported_cwd = enabled;
+        self
+    }
+
+    /// Adapt a server to what the target application supports
+    fn translate_server(&self, app: &ApplicationProfile, server: &McpServerConfig) -> (McpServerConfig, Vec<StrippedOption>) {
+        let (server, mut stripped) = translate_extension_fields(app, server);
+        let (server, cwd_stripped) = translate_cwd(app, &server, self.wrap_unsupported_cwd);
+        stripped.extend(cwd_stripped);
+        (server, stripped)
+    }
+
+    /// Sync servers from central store to application configuration
+    pub async fn sync_to_application(
+        &self,
+        app: &ApplicationProfile,
+        servers: &[McpServerConfig],
+        file_service: &mut ConfigFileService,
+    ) -> Result<SyncResult> {
+        let mut result = SyncResult {
+            success: false,
+            servers_synced: 0,
+            conflicts: Vec::new(),
+            errors: Vec::new(),
+            stripped_options: Vec::new(),
+            foreign_home_paths: Vec::new(),
+        };
+
+        // Read current application configuration
+        let current_config = match self.read_app_config(app, file_service).await {
+            Ok(config) => config,
+            Err(e) => {
+                result.errors.push(format!("Failed to read app config: {}", e));
+                return Ok(result);
+            }
+        };
+
+        // Apply servers to configuration based on application type
+        let updated_config = match self.apply_servers_to_config(app, &current_config, servers) {
+            Ok(config) => config,
+            Err(e) => {
+                result.errors.push(format!("Failed to apply servers: {}", e));
+                return Ok(result);
+            }
+        };
+
+        // Write updated configuration back to application
+        match self.write_app_config(app, &updated_config, file_service).await {
+            Ok(_) => {
+                result.success = true;
+                result.servers_synced = servers.len();
+            }
+            Err(e) => {
+                result.errors.push(format!("Failed to write app config: {}", e));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Read application configuration
+    async fn read_app_config(
+        &self,
+        app: &ApplicationProfile,
+        file_service: &mut ConfigFileService,
+    ) -> Result<JsonValue> {
+        let active_path = ConfigValidator::new()?.resolve_active_config(app).await?;
+        self.read_config_at(app, &active_path, file_service).await
+    }
+
+    /// Read one of an application's config files
+    pub async fn read_config_at(
+        &self,
+        app: &ApplicationProfile,
+        path: &std::path::Path,
+        file_service: &mut ConfigFileService,
+    ) -> Result<JsonValue> {
+        if is_sqlite_backed(app) {
+            return WarpStateStore::open(path)?.read_config();
+        }
+        if is_options_xml_backed(app) {
+            let content = read_options_file(path).await?;
+            return read_options_xml(&content);
+        }
+        file_service.read_config(path).await
+    }
+
+    /// Write application configuration
+    async fn write_app_config(
+        &self,
+        app: &ApplicationProfile,
+        config: &JsonValue,
+        file_service: &mut ConfigFileService,
+    ) -> Result<()> {
+        // Writes always go to the file whose entries take precedence
+        let expanded_path = ConfigValidator::new()?.resolve_active_config(app).await?;
+        self.write_config_at(app, &expanded_path, config, file_service).await
+    }
+
+    /// Write one of an application's config files
+    ///
+    /// Configs with entries that would launch MCP Control or loop back into this file are
+    /// refused, and so are externally managed configs unless `file_service` forces writes.
+    /// The file is backed up once it is known the write may go ahead.
+    pub async fn write_config_at(
+        &self,
+        app: &ApplicationProfile,
+        expanded_path: &std::path::Path,
+        config: &JsonValue,
+        file_service: &mut ConfigFileService,
+    ) -> Result<()> {
+        if !app.metadata.supports_config_writes {
+            anyhow::bail!(
+                "{} does not support writing its MCP configuration; add servers by pasting the rendered snippet into its settings",
+                app.name
+            );
+        }
+
+        // Refuse entries that would launch us or loop back into the file being written
+        let servers = ConfigValidator::new()?.extract_mcp_servers(config, app, expanded_path)?;
+        let findings = SelfReferenceGuard::new().with_managed_config(expanded_path).check_servers(&servers);
+        if !findings.is_empty() {
+            let findings: Vec<String> = findings.iter().map(ToString::to_string).collect();
+            anyhow::bail!("Refusing to write {}: {}", expanded_path.display(), findings.join("; "));
+        }
+        
+        // A refused write must not leave a backup behind
+        file_service.ensure_writable(expanded_path)?;
+        create_backup_before_write(expanded_path, &default_backup_dir()?).await?;
+        
+        if is_options_xml_backed(app) {
+            // Splice the server list into the IDE's options file, leaving its other components alone
+            let rendered = write_options_xml(&read_options_file(expanded_path).await?, config)?;
+            return file_service.write_content(expanded_path, rendered).await;
+        }
+        
+        let Some(stamper) = &self.comment_stamper else {
+            return if self.sort_keys {
+                file_service.write_config_sorted(expanded_path, config).await
+            } else {
+                file_service.write_config(expanded_path, config).await
+            };
+        };
+
+        // Stamp the rendered text so the config is written once, comments included.
+        // Comments the write may drop, such as in rewritten TOML, keep their original date.
+        let previous = tokio::fs::read_to_string(expanded_path).await.ok();
+        let sorted = self.sort_keys.then(|| sort_json_keys(config.clone()));
+        let rendered = file_service.render_config(expanded_path, sorted.as_ref().unwrap_or(config))?;
+        let stamped = ProvenanceSidecar::load_default().and_then(|mut sidecar| {
+            let content = stamp_rendered(app, &rendered, previous.as_deref(), &servers, stamper, &mut sidecar)?;
+            Ok((content, sidecar))
+        });
+        match stamped {
+            Ok((content, sidecar)) => {
+                file_service.write_content(expanded_path, content).await?;
+                if let Err(e) = sidecar.save() {
+                    log::warn!("Failed to save provenance records: {}", e);
+                }
+            }
+            Err(e) => {
+                log::warn!("Failed to stamp provenance comments in {}: {}", expanded_path.display(), e);
+                file_service.write_content(expanded_path, rendered).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Merge servers listed more than once under differently-cased names.
+    ///
+    /// The config is backed up before the merged result is written. Returns the removed names
+    /// and any duplicates whose enabled state disagreed with the kept entry.
+    pub async fn dedupe_application_servers(
+        &self,
+        app: &ApplicationProfile,
+        file_service: &mut ConfigFileService,
+    ) -> Result<DedupeReport> {
+        let mut config = self.read_app_config(app, file_service).await?;
+        let report = app.dedupe_servers(&mut config);
+
+        if !report.removed.is_empty() {
+            self.write_app_config(app, &config, file_service).await?;
+        }
+        Ok(report)
+    }
+
+    /// Audit an application's servers and switch off the risky ones.
+    ///
+    /// The config is backed up before it is written. Each quarantine is recorded in
+    /// `annotations`, which the caller saves. Returns the quarantined servers with their findings.
+    pub async fn quarantine_application_servers(
+        &self,
+        app: &ApplicationProfile,
+        audit: &ServerAudit,
+        annotations: &mut AnnotationStore,
+        file_service: &mut ConfigFileService,
+    ) -> Result<Vec<(String, Vec<QuarantineReason>)>> {
+        let mut config = self.read_app_config(app, file_service).await?;
+        let quarantined = quarantine_risky_servers(app, &mut config, audit, annotations)?;
+
+        if !quarantined.is_empty() {
+            self.write_app_config(app, &config, file_service).await?;
+        }
+        Ok(quarantined)
+    }
+
+    /// Put a quarantined server back into an application's config, backing the config up first
+    pub async fn restore_quarantined_server(
+        &self,
+        app: &ApplicationProfile,
+        server: &str,
+        condition: RestoreCondition<'_>,
+        annotations: &mut AnnotationStore,
+        file_service: &mut ConfigFileService,
+    ) -> Result<()> {
+        let mut config = self.read_app_config(app, file_service).await?;
+        restore_server(app, &mut config, server, condition, annotations)?;
+        self.write_app_config(app, &config, file_service).await
+    }
+
+    /// Apply MCP servers to application configuration based on app type
+    fn apply_servers_to_config(
+        &self,
+        app: &ApplicationProfile,
+        current_config: &JsonValue,
+        servers: &[McpServerConfig],
+    ) -> Result<JsonValue> {
+        let mut config = current_config.clone();
+
+        // Minimal configs get a proper servers object first: a JSON `null` document becomes an
+        // empty one, and a null, string or array servers value is converted without touching
+        // sibling keys
+        if config.is_null() {
+            config = serde_json::json!({});
+        }
+        // Declared layouts may hold servers in an array, so only the built-in shapes are repaired
+        if app.metadata.declarative_adapter.is_none() {
+            for issue in app.detect_structure_issues(&config) {
+                if matches!(issue, StructureIssue::ServersNotObject(_) | StructureIssue::ServersArray) {
+                    app.repair_structure_issue(&mut config, &issue);
+                }
+            }
+        }
+
+        match app.id.as_str() {
+            _ if app.metadata.declarative_adapter.is_some() => {
+                if let Some(adapter) = declared_adapter(app)? {
+                    config = adapter.apply(&config, servers).config;
+                }
+            }
+            "claude-desktop" => self.apply_claude_desktop_servers(&mut config, servers)?,
+            "cursor" => self.apply_cursor_servers(&mut config, servers)?,
+            "zed" => self.apply_zed_servers(app, &mut config, servers)?,
+            "vscode" => self.apply_vscode_servers(app, &mut config, servers)?,
+            "amazon-q" => self.apply_amazon_q_servers(&mut config, servers)?,
+            id if id.starts_with("jetbrains-") => self.apply_jetbrains_servers(app, &mut config, servers)?,
+            _ => self.apply_generic_servers(app, &mut config, servers)?,
+        }
+
+        Ok(config)
+    }
+
+    /// Apply servers to Claude Desktop configuration
+    fn apply_claude_desktop_servers(&self, config: &mut JsonValue, servers: &[McpServerConfig]) -> Result<()> {
+        // Ensure mcpServers object exists
+        if config.get("mcpServers").is_none() {
+            config["mcpServers"] = serde_json::json!({});
+        }
+        
+        let mcp_servers = config.get_mut("mcpServers")
+            .context("Failed to get mcpServers object")?;
+
+        // Clear existing servers and add new ones
+        *mcp_servers = serde_json::json!({});
+        
+        for server in servers {
+            let server_config = serde_json::json!({
+                "command": server.command,
+                "args": server.args,
+                "env": server.env
+            });
+            
+            mcp_servers[&server.name] = server_config;
+        }
+
+        Ok(())
+    }
+
+    /// Apply servers to Cursor configuration
+    fn apply_cursor_servers(&self, config: &mut JsonValue, servers: &[McpServerConfig]) -> Result<()> {
+        // Cursor reads servers from the top-level mcpServers object of ~/.cursor/mcp.json
+        let mut servers_obj = serde_json::Map::new();
+        for server in servers {
+            servers_obj.insert(server.name.clone(), serde_json::json!({
+                "command": server.command,
+                "args": server.args,
+                "env": server.env
+            }));
+        }
+        config["mcpServers"] = JsonValue::Object(servers_obj);
+
+        Ok(())
+    }
+
+    /// Apply servers to Zed configuration
+    fn apply_zed_servers(&self, app: &ApplicationProfile, config: &mut JsonValue, servers: &[McpServerConfig]) -> Result<()> {
+        // Zed reads servers from context_servers; the rest of settings.json is left alone
+        let context_servers = app.servers_path().get_or_insert(config);
+
+        for server in servers {
+            context_servers.insert(server.name.clone(), serde_json::json!({
+                "command": server.command,
+                "args": server.args,
+                "env": server.env
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Apply servers to VS Code configuration
+    fn apply_vscode_servers(&self, app: &ApplicationProfile, config: &mut JsonValue, servers: &[McpServerConfig]) -> Result<()> {
+        // Newer versions read chat.mcp.servers, stored as a flat settings key unless the user
+        // nested it. Only the servers container is replaced, so `$schema` and other settings survive
+        let mcp_config = app.servers_path().get_or_insert(config);
+        mcp_config.clear();
+
+        for server in servers {
+            mcp_config.insert(server.name.clone(), serde_json::json!({
+                "command": server.command,
+                "args": server.args,
+                "env": server.env
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Apply servers to a JetBrains IDE's mcp.servers, also the shape its options XML is read in
+    fn apply_jetbrains_servers(&self, app: &ApplicationProfile, config: &mut JsonValue, servers: &[McpServerConfig]) -> Result<()> {
+        let container = app.servers_path().get_or_insert(config);
+        container.clear();
+
+        for server in servers {
+            let mut entry = serde_json::json!({
+                "command": server.command,
+                "args": server.args
+            });
+            if !server.env.is_empty() {
+                entry["env"] = serde_json::json!(server.env);
+            }
+            if !server.metadata.enabled {
+                entry["disabled"] = serde_json::json!(true);
+            }
+            container.insert(server.name.clone(), entry);
+        }
+
+        Ok(())
+    }
+
+    /// Apply servers to Amazon Q Developer configuration
+    fn apply_amazon_q_servers(&self, config: &mut JsonValue, servers: &[McpServerConfig]) -> Result<()> {
+        // Amazon Q Developer uses mcpServers format
+        if config.get("mcpServers").is_none() {
+            config["mcpServers"] = serde_json::json!({});
+        }
+        
+        let servers_obj = config.get_mut("mcpServers")
+            .context("Failed to get mcpServers object")?;
+
+        *servers_obj = serde_json::json!({});
+        
+        for server in servers {
+            let mut server_config = serde_json::json!({
+                "command": server.command,
+                "args": server.args
+            });
+            
+            if !server.env.is_empty() {
+                server_config["env"] = serde_json::json!(server.env);
+            }
+            
+            servers_obj[&server.name] = server_config;
+        }
+
+        Ok(())
+    }
+
+    /// Apply servers to generic application configuration, at the profile's servers path
+    fn apply_generic_servers(&self, app: &ApplicationProfile, config: &mut JsonValue, servers: &[McpServerConfig]) -> Result<()> {
+        let container = app.servers_path().get_or_insert(config);
+        container.clear();
+
+        for server in servers {
+            container.insert(server.name.clone(), serde_json::json!({
+                "command": server.command,
+                "args": server.args,
+                "env": server.env
+            }));
+        }
+
+        Ok(())
+    }
+
+    /// Synchronize servers to application using adapters (new method)
+    pub async fn sync_to_application_with_adapter(
+        &self,
+        app: &ApplicationProfile,
+        servers: &[McpServerConfig],
+        file_service: &mut ConfigFileService,
+    ) -> Result<SyncResult> {
+        let mut result = SyncResult {
+            success: false,
+            servers_synced: 0,
+            conflicts: Vec::new(),
+            errors: Vec::new(),
+            stripped_options: Vec::new(),
+            foreign_home_paths: Vec::new(),
+        };
+
+        // Create adapter for this application
+        let adapter = match AdapterFactory::create_adapter(app) {
+            Ok(adapter) => adapter,
+            Err(e) => {
+                result.errors.push(format!("Failed to create adapter: {}", e));
+                return Ok(result);
+            }
+        };
+
+        // Read current application config
+        let current_config = match self.read_app_config(app, file_service).await {
+            Ok(config) => config,
+            Err(e) => {
+                result.errors.push(format!("Failed to read config: {}", e));
+                return Ok(result);
+            }
+        };
+
+        // Translate client-specific options the target understands and drop the rest
+        let relocator = HomeRelocator::new();
+        let mut translated = Vec::with_capacity(servers.len());
+        for server in servers {
+            let (mut server, stripped) = self.translate_server(app, server);
+            if let Some(relocator) = &relocator {
+                let (rehomed, findings) = relocator.rehome_server(&server);
+                server = rehomed;
+                result.foreign_home_paths.extend(findings);
+            }
+            translated.push(server);
+            result.stripped_options.extend(stripped);
+        }
+
+        // Apply servers using adapter
+        match adapter.apply_server_configs(&current_config, &translated).await {
+            Ok(adapter_result) => {
+                if adapter_result.success {
+                    // Stamp written entries so later audits can tell them apart from hand-added ones
+                    let mut updated_config = adapter_result.config;
+                    // The sidecar is saved first so comment stamping during the write sees these entries
+                    let mut sidecar = ProvenanceSidecar::load_default().ok();
+                    if let Some(sidecar) = sidecar.as_mut() {
+                        stamp_servers(app, &mut updated_config, sidecar);
+                    }
+                    if let Some(Err(e)) = sidecar.map(|s| s.save()) {
+                        log::warn!("Failed to save provenance sidecar: {}", e);
+                    }
+
+                    // Write updated config back
+                    if let Err(e) = self.write_app_config(app, &updated_config, file_service).await {
+                        result.errors.push(format!("Failed to write config: {}", e));
+                        return Ok(result);
+                    }
+                    
+                    result.success = true;
+                    result.servers_synced = servers.len();
+                } else {
+                    result.errors.extend(adapter_result.messages);
+                }
+            }
+            Err(e) => {
+                result.errors.push(format!("Adapter failed to apply config: {}", e));
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Sync servers into an application, asking before replacing any that differ from the app's own.
+    ///
+    /// Unlike [`Self::sync_to_application_with_adapter`], servers only the application has are kept.
+    pub async fn sync_with_resolution(
+        &self,
+        app: &ApplicationProfile,
+        servers: &[McpServerConfig],
+        file_service: &mut ConfigFileService,
+        pending: &mut PendingResolutionStore,
+    ) -> Result<ResolutionOutcome> {
+        self.apply_or_defer(ConflictOperation::Sync, app, servers.to_vec(), file_service, pending)
+            .await
+    }
+
+    /// Import a team manifest's servers, pinned to their locked versions, into an application.
+    ///
+    /// The lock is verified first; when it no longer reproduces, nothing is written and the
+    /// report says why.
+    pub async fn import_manifest(
+        &self,
+        app: &ApplicationProfile,
+        manifest: &TeamManifest,
+        locker: &ManifestLocker,
+        file_service: &mut ConfigFileService,
+        pending: &mut PendingResolutionStore,
+    ) -> Result<ManifestImport> {
+        let lock_report = locker.verify(manifest).await;
+        if !lock_report.is_reproducible() {
+            return Ok(ManifestImport { lock_report, outcome: None });
+        }
+
+        let outcome = self
+            .apply_or_defer(ConflictOperation::ManifestImport, app, manifest.locked_servers(), file_service, pending)
+            .await?;
+        Ok(ManifestImport { lock_report, outcome: Some(outcome) })
+    }
+
+    /// Finish an operation that stopped for conflicts, once every conflict has a choice.
+    ///
+    /// The config is read and compared again first, since it may have changed while the
+    /// user was deciding. Choices for conflicts that have gone away are dropped; new
+    /// conflicts are saved to the pending resolution and reported as unresolved. Incomplete
+    /// or invalid choices fail with a [`super::resolution::ResolutionError`] before anything
+    /// is written, and the resolution stays pending.
+    pub async fn resume_resolution(
+        &self,
+        app: &ApplicationProfile,
+        token: &str,
+        choices: &HashMap<String, Resolution>,
+        file_service: &mut ConfigFileService,
+        pending: &mut PendingResolutionStore,
+    ) -> Result<SyncResult> {
+        let resolution = pending
+            .get(token)
+            .ok_or_else(|| anyhow::anyhow!("No pending resolution with token {}", token))?;
+        if resolution.app_id != app.id {
+            anyhow::bail!("Pending resolution {} belongs to {}, not {}", token, resolution.app_id, app.id);
+        }
+
+        let local = self.extract_from_application_with_adapter(app, file_service).await?;
+        let current = resolution.replan(local, &format!("{} config", app.name));
+        let choices: HashMap<String, Resolution> = choices
+            .iter()
+            .filter(|(id, _)| {
+                current.conflicts.iter().any(|c| &c.id == *id) || !resolution.conflicts.iter().any(|c| &c.id == *id)
+            })
+            .map(|(id, choice)| (id.clone(), choice.clone()))
+            .collect();
+
+        let servers = match current.resolve(&choices) {
+            Ok(servers) => servers,
+            Err(e) => {
+                if current.conflicts != resolution.conflicts {
+                    pending.insert(current);
+                    pending.save()?;
+                }
+                return Err(e.into());
+            }
+        };
+        let result = self.sync_to_application_with_adapter(app, &servers, file_service).await?;
+        if result.success {
+            pending.remove(token);
+            pending.save()?;
+        }
+        Ok(result)
+    }
+
+    /// Apply incoming servers straight away, or park them with their conflicts
+    async fn apply_or_defer(
+        &self,
+        operation: ConflictOperation,
+        app: &ApplicationProfile,
+        incoming: Vec<McpServerConfig>,
+        file_service: &mut ConfigFileService,
+        pending: &mut PendingResolutionStore,
+    ) -> Result<ResolutionOutcome> {
+        let local = self.extract_from_application_with_adapter(app, file_service).await?;
+        let incoming_origin = operation.incoming_origin();
+        let resolution = PendingResolution::plan(
+            operation,
+            &app.id,
+            local,
+            incoming,
+            &format!("{} config", app.name),
+            incoming_origin,
+        );
+
+        if resolution.has_conflicts() {
+            pending.insert(resolution.clone());
+            pending.save()?;
+            return Ok(ResolutionOutcome::NeedsResolution(resolution));
+        }
+
+        let servers = resolution.resolve(&HashMap::new())?;
+        let result = self.sync_to_application_with_adapter(app, &servers, file_service).await?;
+        Ok(ResolutionOutcome::Applied(result))
+    }
+
+    /// Render a single server as a config snippet for the given application
+    pub async fn render_snippet(&self, app: &ApplicationProfile, server: &McpServerConfig) -> Result<RenderedSnippet> {
+        let adapter = AdapterFactory::create_adapter(app)?;
+        let (server, stripped_options) = self.translate_server(app, server);
+
+        let result = adapter.apply_server_configs(&serde_json::json!({}), std::slice::from_ref(&server)).await?;
+        if !result.success {
+            anyhow::bail!("Failed to render snippet: {}", result.messages.join("; "));
+        }
+
+        let content = serde_json::to_string_pretty(&result.config)
+            .context("Failed to serialize snippet")?;
+
+        Ok(RenderedSnippet { content, stripped_options })
+    }
+
+    /// Extract servers from application using adapters (new method)
+    pub async fn extract_from_application_with_adapter(
+        &self,
+        app: &ApplicationProfile,
+        file_service: &mut ConfigFileService,
+    ) -> Result<Vec<McpServerConfig>> {
+        // Create adapter for this application
+        let adapter = AdapterFactory::create_adapter(app)?;
+        
+        // Read every existing config file, highest precedence first
+        let mut layers = Vec::new();
+        for path in ConfigValidator::new()?.existing_config_files(app).await? {
+            let config = self.read_config_at(app, &path, file_service).await?;
+            let result = adapter.extract_server_configs(&config).await?;
+            
+            // Skip files whose extraction failed but don't error
+            if result.success {
+                layers.push(ConfigLayer::new(path, result.servers));
+            }
+        }
+
+        let effective = merge_config_layers(layers);
+        for shadowed in &effective.shadowed {
+            log::info!(
+                "Server '{}' in {} is shadowed by {}",
+                shadowed.name,
+                shadowed.path.display(),
+                shadowed.shadowed_by.display()
+            );
+        }
+        Ok(effective.servers)
+    }
+}
+
+impl Default for SyncManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Directory configs are copied to before MCP Control overwrites them
+pub fn default_backup_dir() -> Result<std::path::PathBuf> {
+    Ok(dirs::home_dir()
+        .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
+        .join(".mcp-control-backups"))
+}
+
+/// Copy a config file into `backup_dir` under a timestamped name before it is modified
+///
+/// Returns the backup's path, or `None` when the file doesn't exist yet.
+pub async fn create_backup_before_write(
+    config_path: &std::path::Path,
+    backup_dir: &std::path::Path,
+) -> Result<Option<std::path::PathBuf>> {
+    if !config_path.exists() {
+        return Ok(None); // No backup needed if file doesn't exist
+    }
+
+    tokio::fs::create_dir_all(backup_dir).await
+        .context("Failed to create backup directory")?;
+
+    // Generate backup filename with timestamp
+    let timestamp = chrono::Utc::now().format("%Y%m%d_%H%M%S");
+    let original_name = config_path.file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or("config");
+    let backup_name = format!("{}_{}.backup", original_name, timestamp);
+    let backup_path = backup_dir.join(backup_name);
+
+    // Copy original file to backup
+    tokio::fs::copy(config_path, &backup_path).await
+        .with_context(|| format!("Failed to create backup: {} -> {}",
+            config_path.display(), backup_path.display()))?;
+
+    log::info!("Backup created: {}", backup_path.display());
+    Ok(Some(backup_path))
+}
+
+/// Refresh the provenance comments of a config about to be written.
+///
+/// `servers` is everything the new config lists. Servers the sidecar still remembers for
+/// the application but the config no longer lists are unstamped, which forgets their records.
+fn stamp_rendered(
+    app: &ApplicationProfile,
+    rendered: &str,
+    previous: Option<&str>,
+    servers: &[McpServerConfig],
+    stamper: &CommentStamper,
+    sidecar: &mut ProvenanceSidecar,
+) -> Result<String> {
+    let mut content = stamper.stamp_managed(app, rendered, previous, servers, sidecar, chrono::Utc::now().date_naive())?;
+
+    let removed: Vec<String> = sidecar
+        .records
+        .values()
+        .filter(|record| record.application_id == app.id && !servers.iter().any(|server| server.name == record.server_name))
+        .map(|record| record.server_name.clone())
+        .collect();
+    for server in removed {
+        content = stamper.unstamp(app, &content, &server, sidecar)?;
+    }
+    Ok(content)
+}
+
+/// Contents of an IDE options file; the IDE only creates it once a server is configured
+async fn read_options_file(path: &std::path::Path) -> Result<String> {
+    match tokio::fs::read_to_string(path).await {
+        Ok(content) => Ok(content),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(String::new()),
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{ConfigStructure, ExtensionRule};
+    use std::collections::HashMap;
+
+    fn create_test_server(name: &str) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            command: Some("node".to_string()),
+            args: vec!["server.js".to_string()],
+            env: HashMap::new(),
+            cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
+            server_type: crate::detection::ServerType::Stdio,
+            metadata: crate::detection::ServerMetadata {
+                version: Some("1.0.0".to_string()),
+                description: Some("Test server".to_string()),
+                author: None,
+                capabilities: Vec::new(),
+                enabled: true,
+                source: crate::detection::ConfigSource::MainConfig,
+            },
+        }
+    }
+
+    fn create_test_app(id: &str, name: &str) -> ApplicationProfile {
+        ApplicationProfile {
+            id: id.to_string(),
+            name: name.to_string(),
+            bundle_id: format!("com.test.{}", id),
+            config_path: format!("~/Library/Application Support/{}/config.json", name),
+            alt_config_paths: Vec::new(),
+            config_format: crate::detection::ConfigFormat::Json,
+            config_structure: crate::detection::ConfigStructure::DirectMcpServers,
+            executable_paths: vec![format!("/Applications/{}.app", name)],
+            alt_executable_paths: Vec::new(),
+            detection_strategy: crate::detection::DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: false,
+                priority_order: vec![
+                    crate::detection::DetectionMethod::BundleLookup,
+                    crate::detection::DetectionMethod::ExecutableCheck,
+                ],
+            },
+            metadata: crate::detection::ApplicationMetadata {
+                version: Some("1.0.0".to_string()),
+                developer: "Test Developer".to_string(),
+                category: crate::detection::ApplicationCategory::CodeEditor,
+                mcp_version: "1.0".to_string(),
+                notes: None,
+                requires_permissions: false,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[test]
+    fn test_sync_manager_creation() {
+        // Just verify it can be created
+        let _sync_manager = SyncManager::new();
+    }
+
+    #[test]
+    fn test_claude_desktop_config_application() {
+        let sync_manager = SyncManager::new();
+        let mut config = serde_json::json!({});
+        let servers = vec![create_test_server("test-server")];
+
+        sync_manager.apply_claude_desktop_servers(&mut config, &servers).unwrap();
+
+        assert!(config.get("mcpServers").is_some());
+        assert!(config["mcpServers"].get("test-server").is_some());
+        assert_eq!(config["mcpServers"]["test-server"]["command"], "node");
+    }
+
+    #[test]
+    fn test_cursor_config_application() {
+        let sync_manager = SyncManager::new();
+        let mut config = serde_json::json!({});
+        let servers = vec![create_test_server("test-server")];
+
+        sync_manager.apply_cursor_servers(&mut config, &servers).unwrap();
+
+        assert!(config.get("mcp").is_none());
+        assert!(config["mcpServers"].get("test-server").is_some());
+    }
+
+    #[test]
+    fn test_generic_config_application() {
+        let sync_manager = SyncManager::new();
+        let mut config = serde_json::json!({});
+        let servers = vec![create_test_server("test-server")];
+        let mut app = create_test_app("custom-app", "Custom App");
+
+        sync_manager.apply_generic_servers(&app, &mut config, &servers).unwrap();
+
+        assert!(config.get("mcpServers").is_some());
+        assert!(config["mcpServers"].get("test-server").is_some());
+
+        // A custom path is honored, JSON pointer escapes included
+        app.config_structure = ConfigStructure::Custom("/tools~1mcp/servers".to_string());
+        let mut config = serde_json::json!({"theme": "dark"});
+        sync_manager.apply_generic_servers(&app, &mut config, &servers).unwrap();
+        assert!(config["tools/mcp"]["servers"].get("test-server").is_some());
+        assert_eq!(config["theme"], "dark");
+    }
+
+    #[test]
+    fn test_declared_layout_is_applied() {
+        let sync_manager = SyncManager::new();
+        let mut app = create_test_app("orbit", "Orbit");
+        app.metadata.declarative_adapter = Some(serde_json::from_value(serde_json::json!({
+            "container": "/assistant/tools",
+            "layout": {"kind": "list", "name": "/id"},
+            "fields": {"command": "/launch/program", "args": "/launch/arguments"}
+        })).unwrap());
+        let config = serde_json::json!({"assistant": {"model": "large", "tools": []}});
+
+        let config = sync_manager.apply_servers_to_config(&app, &config, &[create_test_server("test-server")]).unwrap();
+        assert_eq!(config["assistant"]["tools"][0]["id"], "test-server");
+        assert!(config["assistant"]["tools"][0]["launch"].get("program").is_some());
+        assert_eq!(config["assistant"]["model"], "large");
+        assert!(config.get("mcpServers").is_none());
+    }
+
+    #[test]
+    fn test_zed_servers_round_trip_through_context_servers() {
+        let sync_manager = SyncManager::new();
+        let zed = crate::detection::ApplicationRegistry::new().get_application("zed").unwrap().clone();
+        let config = serde_json::json!({"theme": "One Dark", "language_servers": {"rust-analyzer": {}}});
+
+        let config = sync_manager.apply_servers_to_config(&zed, &config, &[create_test_server("test-server")]).unwrap();
+        assert_eq!(config["context_servers"]["test-server"]["command"], "node");
+        assert_eq!(config["language_servers"], serde_json::json!({"rust-analyzer": {}}));
+        assert_eq!(config["theme"], "One Dark");
+
+        let servers = ConfigValidator::new()
+            .unwrap()
+            .extract_mcp_servers(&config, &zed, std::path::Path::new(&zed.config_path))
+            .unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].args, vec!["server.js"]);
+    }
+
+    /// The five minimal shapes for a servers container at `path`, each with a sibling key where
+    /// the shape allows one: blank file, `{}`, and a null, string or array servers value
+    fn minimal_config_shapes(path: &[&str]) -> Vec<(&'static str, String, Option<&'static str>)> {
+        let with_servers = |value: JsonValue| {
+            let mut config = serde_json::json!({"theme": "dark"});
+            let mut node = &mut config;
+            for key in &path[..path.len() - 1] {
+                node = &mut node[*key];
+            }
+            node[path[path.len() - 1]] = value;
+            config.to_string()
+        };
+        vec![
+            ("empty file", String::new(), None),
+            ("empty object", "{}".to_string(), None),
+            ("null servers", with_servers(JsonValue::Null), Some("MCP_SERVERS_NOT_OBJECT")),
+            ("string servers", with_servers("none yet".into()), Some("MCP_SERVERS_NOT_OBJECT")),
+            ("array servers", with_servers(serde_json::json!([])), Some("MCP_SERVERS_ARRAY")),
+        ]
+    }
+
+    #[tokio::test]
+    async fn test_minimal_configs_conformance() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let registry = crate::detection::ApplicationRegistry::new();
+
+        // Direct `mcpServers` and nested `mcp.servers`, both read and written as JSON
+        let direct = registry.get_application("claude-desktop").unwrap().clone();
+        let mut nested = registry.get_application("jetbrains-idea").unwrap().clone();
+        nested.config_format = crate::detection::ConfigFormat::Json;
+
+        let sync_manager = SyncManager::new();
+        let validator = ConfigValidator::new().unwrap();
+        let mut file_service = ConfigFileService::new("test".to_string(), temp_dir.path().join("backups"));
+
+        for mut app in [direct, nested] {
+            let path = app.get_mcp_servers_path();
+            let keys: Vec<&str> = path.iter().map(String::as_str).collect();
+            for (index, (shape, content, expected_issue)) in minimal_config_shapes(&keys).into_iter().enumerate() {
+                let case = format!("{} / {}", app.id, shape);
+                let file = temp_dir.path().join(format!("{}-{}.json", app.id, index));
+                std::fs::write(&file, &content).unwrap();
+                app.set_config_path(file.to_string_lossy());
+                app.alt_config_paths.clear();
+
+                // Reader: every shape reads, and none has servers
+                let config = sync_manager.read_config_at(&app, &file, &mut file_service).await.unwrap();
+                assert!(config.is_object(), "{}", case);
+
+                // Validator: valid with zero servers; only broken containers warn, with a repair
+                let result = validator.validate_application_config(&app).await.unwrap();
+                assert!(result.is_valid, "{}", case);
+                assert!(result.mcp_servers.is_empty(), "{}", case);
+                let codes: Vec<&str> = result.messages.iter().filter_map(|m| m.code.as_deref()).collect();
+                assert!(!codes.contains(&"CONFIG_PARSE_FAILED"), "{}", case);
+                assert_eq!(codes.iter().any(|c| c.starts_with("MCP_SERVERS_")), expected_issue.is_some(), "{}", case);
+                let structure = app.validate_config_structure(&config);
+                match expected_issue {
+                    Some(code) => {
+                        assert!(codes.contains(&code), "{}: {:?}", case, codes);
+                        assert!(structure.unwrap_err().contains(code), "{}", case);
+                    }
+                    None => assert!(structure.is_ok(), "{}", case),
+                }
+
+                let mut repaired = config.clone();
+                app.repair_config_structure(&mut repaired);
+                assert!(app.detect_structure_issues(&repaired).is_empty(), "{}", case);
+
+                // Writer: the servers object is created or upgraded, and sibling keys survive
+                let written = sync_manager
+                    .apply_servers_to_config(&app, &config, &[create_test_server("test-server")])
+                    .unwrap();
+                let servers = keys.iter().try_fold(&written, |node, key| node.get(*key)).unwrap();
+                assert_eq!(servers["test-server"]["command"], "node", "{}", case);
+                assert_eq!(written.get("theme"), config.get("theme"), "{}", case);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_schema_key_is_not_a_server() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("settings.json");
+        let schema = "https://json.schemastore.org/vscode-settings.json";
+        std::fs::write(&config_path, serde_json::json!({
+            "$schema": schema,
+            "mcpServers": {
+                "github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}
+            }
+        }).to_string()).unwrap();
+
+        let mut vscode = crate::detection::ApplicationRegistry::new().get_application("vscode").unwrap().clone();
+        vscode.set_config_path(config_path.to_string_lossy());
+        vscode.alt_config_paths.clear();
+
+        let listed = ConfigValidator::new().unwrap().validate_application_config(&vscode).await.unwrap();
+        let names: Vec<&str> = listed.mcp_servers.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["github"]);
+
+        // Install and remove touch only the servers container
+        let config: JsonValue = serde_json::from_str(&std::fs::read_to_string(&config_path).unwrap()).unwrap();
+        let mut written = SyncManager::new()
+            .apply_servers_to_config(&vscode, &config, &[create_test_server("github"), create_test_server("fetch")])
+            .unwrap();
+        assert_eq!(written["$schema"], schema);
+        assert_eq!(written["mcpServers"].as_object().unwrap().len(), 2);
+
+        assert!(vscode.remove_server(&mut written, "fetch"));
+        assert!(!vscode.remove_server(&mut written, "$schema"));
+        assert_eq!(written["$schema"], schema);
+    }
+
+    #[tokio::test]
+    async fn test_self_referential_server_is_blocked() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("claude_desktop_config.json");
+        let wrapper = temp_dir.path().join("start.sh");
+        std::fs::write(&wrapper, format!("#!/bin/sh\nexec node server.js --config {}\n", config_path.display())).unwrap();
+        let mut looping = create_test_server("looping");
+        looping.command = Some(wrapper.to_string_lossy().to_string());
+        looping.args.clear();
+        let original = serde_json::json!({"mcpServers": {"wrapped": {"command": looping.command}}}).to_string();
+        std::fs::write(&config_path, &original).unwrap();
+
+        let mut app = crate::detection::ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        app.set_config_path(config_path.to_string_lossy());
+        app.alt_config_paths.clear();
+
+        let listed = ConfigValidator::new().unwrap().validate_application_config(&app).await.unwrap();
+        assert!(listed.messages.iter().any(|m| m.code.as_deref() == Some("SELF_REFERENTIAL_SERVER")));
+
+        let mut file_service = ConfigFileService::new("test".to_string(), temp_dir.path().join("backups"));
+        let servers = [create_test_server("github"), looping];
+        let result = SyncManager::new()
+            .sync_to_application_with_adapter(&app, &servers, &mut file_service)
+            .await
+            .unwrap();
+        assert!(!result.success);
+        assert!(result.errors[0].contains("looping"));
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+
+        // Every write goes through the same check, whichever path builds the config
+        let result = SyncManager::new().sync_to_application(&app, &servers, &mut file_service).await.unwrap();
+        assert!(!result.success);
+        assert!(result.errors[0].contains("looping"));
+        let config = serde_json::json!({"mcpServers": {"looping": {"command": servers[1].command}}});
+        assert!(SyncManager::new().write_config_at(&app, &config_path, &config, &mut file_service).await.is_err());
+        assert_eq!(std::fs::read_to_string(&config_path).unwrap(), original);
+    }
+
+    #[tokio::test]
+    async fn test_options_xml_write_is_guarded_and_backed_up() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let options_path = temp_dir.path().join("llm.mcpServers.xml");
+        let mut app = crate::detection::ApplicationRegistry::new().get_application("jetbrains-idea").unwrap().clone();
+        app.set_config_path(options_path.to_string_lossy());
+        app.alt_config_paths.clear();
+        let config = serde_json::json!({"mcp": {"servers": {"github": {"command": "npx"}}}});
+        let mut file_service = ConfigFileService::new("test".to_string(), temp_dir.path().join("backups"));
+
+        let managed = "<!-- managed by policy -->\n<application>\n</application>\n";
+        std::fs::write(&options_path, managed).unwrap();
+        let error = SyncManager::new().write_config_at(&app, &options_path, &config, &mut file_service).await.unwrap_err();
+        assert!(error.downcast_ref::<crate::filesystem::ExternallyManaged>().is_some(), "{}", error);
+        assert_eq!(std::fs::read_to_string(&options_path).unwrap(), managed);
+
+        std::fs::write(&options_path, "<application>\n</application>\n").unwrap();
+        SyncManager::new().write_config_at(&app, &options_path, &config, &mut file_service).await.unwrap();
+        assert!(std::fs::read_to_string(&options_path).unwrap().contains("github"));
+        assert!(file_service.get_operations().last().unwrap().backup_path.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_refused_managed_write_leaves_no_backup() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file_name = format!("{}.json", uuid::Uuid::new_v4());
+        let config_path = temp_dir.path().join("policy").join(&file_name);
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        std::fs::write(&config_path, "{}").unwrap();
+        let mut app = crate::detection::ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        app.set_config_path(config_path.to_string_lossy());
+        app.alt_config_paths.clear();
+
+        let mut file_service = ConfigFileService::new("test".to_string(), temp_dir.path().join("backups"));
+        file_service.set_managed_detector(crate::filesystem::ManagedConfigDetector::with_locations(
+            vec![temp_dir.path().join("policy")],
+            vec![],
+            None,
+        ));
+        let config = serde_json::json!({"mcpServers": {"github": {"command": "npx"}}});
+        let error = SyncManager::new().write_config_at(&app, &config_path, &config, &mut file_service).await.unwrap_err();
+        assert!(error.downcast_ref::<crate::filesystem::ExternallyManaged>().is_some(), "{}", error);
+
+        let backups = std::fs::read_dir(default_backup_dir().unwrap()).into_iter().flatten().flatten();
+        assert!(!backups.into_iter().any(|entry| entry.file_name().to_string_lossy().starts_with(&file_name)));
+        assert!(!temp_dir.path().join("backups").exists());
+    }
+
+    #[test]
+    fn test_stamping_happens_before_the_write_and_forgets_removed_servers() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let app = crate::detection::ApplicationRegistry::new().get_application("vscode").unwrap().clone();
+        let mut sidecar = ProvenanceSidecar::load(&temp_dir.path().join("provenance.json")).unwrap();
+        let entry = serde_json::json!({"args": ["server.js"], "command": "node"});
+        sidecar.record(&app.id, "github", &crate::configuration::provenance::entry_hash(&entry));
+        sidecar.record(&app.id, "retired", "0123456789abcdef");
+
+        let rendered = format!("{{\n  \"mcpServers\": {{\n    \"github\": {}\n  }}\n}}\n", entry);
+        let stamped = stamp_rendered(&app, &rendered, None, &[create_test_server("github")], &CommentStamper::new(true), &mut sidecar).unwrap();
+
+        assert!(stamped.contains("// managed by MCP Control"), "{}", stamped);
+        assert_eq!(crate::filesystem::jsonc::parse_jsonc(&stamped).unwrap(), crate::filesystem::jsonc::parse_jsonc(&rendered).unwrap());
+        assert!(sidecar.records.values().all(|record| record.server_name != "retired"));
+        assert!(sidecar.records.values().any(|record| record.server_name == "github"));
+    }
+
+    #[tokio::test]
+    async fn test_render_snippet_handles_client_options() {
+        let sync_manager = SyncManager::new();
+        let mut server = create_test_server("github");
+        server.extra_options.insert("autoApprove".to_string(), serde_json::json!(["list_issues"]));
+
+        // Claude Desktop has no equivalent, so the option is stripped and reported
+        let snippet = sync_manager
+            .render_snippet(&create_test_app("claude-desktop", "Claude"), &server)
+            .await
+            .unwrap();
+        assert!(!snippet.content.contains("autoApprove"));
+        assert_eq!(snippet.stripped_options.len(), 1);
+        assert_eq!(snippet.stripped_options[0].option, "autoApprove");
+
+        // Roo Code declares the same behavior under alwaysAllow
+        let mut roo_code = create_test_app("roo-code", "Roo Code");
+        roo_code.metadata.extension_options =
+            HashMap::from([("autoApprove".to_string(), ExtensionRule::Translate("alwaysAllow".to_string()))]);
+        let snippet = sync_manager
+            .render_snippet(&roo_code, &server)
+            .await
+            .unwrap();
+        let rendered: JsonValue = serde_json::from_str(&snippet.content).unwrap();
+        assert!(snippet.stripped_options.is_empty());
+        assert_eq!(rendered["mcpServers"]["github"]["alwaysAllow"], serde_json::json!(["list_issues"]));
+    }
+
+    #[tokio::test]
+    async fn test_render_snippet_handles_cwd() {
+        let mut server = create_test_server("docs");
+        server.cwd = Some("/srv/docs server".to_string());
+
+        // Warp has a native working_directory key
+        let mut warp = create_test_app("warp", "Warp");
+        warp.metadata.supports_cwd = true;
+        let snippet = SyncManager::new().render_snippet(&warp, &server).await.unwrap();
+        let rendered: JsonValue = serde_json::from_str(&snippet.content).unwrap();
+        assert!(snippet.stripped_options.is_empty());
+        assert!(snippet.content.contains("\"working_directory\": \"/srv/docs server\""), "{}", rendered);
+
+        // Without native support the cwd is dropped and reported unless the wrapper is enabled
+        let claude = create_test_app("claude-desktop", "Claude");
+        let snippet = SyncManager::new().render_snippet(&claude, &server).await.unwrap();
+        assert!(!snippet.content.contains("/srv/docs"));
+        assert_eq!(snippet.stripped_options[0].option, "cwd");
+
+        let snippet = SyncManager::new()
+            .with_cwd_wrapper(true)
+            .render_snippet(&claude, &server)
+            .await
+            .unwrap();
+        let rendered: JsonValue = serde_json::from_str(&snippet.content).unwrap();
+        assert!(snippet.stripped_options.is_empty());
+        assert_eq!(rendered["mcpServers"]["docs"]["command"], "sh");
+        assert_eq!(rendered["mcpServers"]["docs"]["args"][0], "-c");
+        assert!(rendered["mcpServers"]["docs"]["args"][1].as_str().unwrap().starts_with("cd '/srv/docs server' && exec node"));
+    }
+}
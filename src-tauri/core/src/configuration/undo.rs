@@ -0,0 +1,405 @@
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use anyhow::{anyhow, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use crate::filesystem::ConfigFileService;
+use super::sync::{create_backup_before_write, default_backup_dir};
+
+/// How many operations are kept per application before the oldest is dropped
+pub const DEFAULT_UNDO_DEPTH: usize = 50;
+
+/// A single change to the servers of an application config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ConfigMutation {
+    /// A server entry was added
+    AddServer { name: String, entry: JsonValue },
+    /// A server entry was removed; the entry is stashed so it can be re-added
+    RemoveServer { name: String, entry: JsonValue },
+    /// A server was renamed
+    RenameServer { old_name: String, new_name: String },
+    /// A server was enabled or disabled
+    SetEnabled { name: String, enabled: bool },
+    /// Several changes written together, undone and redone as one operation
+    Batch { mutations: Vec<ConfigMutation> },
+}
+
+impl ConfigMutation {
+    /// The operation that reverses this one
+    pub fn inverse(&self) -> Self {
+        match self {
+            Self::AddServer { name, entry } => Self::RemoveServer { name: name.clone(), entry: entry.clone() },
+            Self::RemoveServer { name, entry } => Self::AddServer { name: name.clone(), entry: entry.clone() },
+            Self::RenameServer { old_name, new_name } => Self::RenameServer {
+                old_name: new_name.clone(),
+                new_name: old_name.clone(),
+            },
+            Self::SetEnabled { name, enabled } => Self::SetEnabled { name: name.clone(), enabled: !enabled },
+            Self::Batch { mutations } => Self::Batch {
+                mutations: mutations.iter().rev().map(Self::inverse).collect(),
+            },
+        }
+    }
+
+    /// Short description for notifications, e.g. "remove 'github'"
+    pub fn describe(&self) -> String {
+        match self {
+            Self::AddServer { name, .. } => format!("add '{}'", name),
+            Self::RemoveServer { name, .. } => format!("remove '{}'", name),
+            Self::RenameServer { old_name, new_name } => format!("rename '{}' to '{}'", old_name, new_name),
+            Self::SetEnabled { name, enabled: true } => format!("enable '{}'", name),
+            Self::SetEnabled { name, enabled: false } => format!("disable '{}'", name),
+            Self::Batch { mutations } => mutations.iter().map(Self::describe).collect::<Vec<_>>().join(", "),
+        }
+    }
+
+    /// Apply the mutation to a servers object
+    fn apply(&self, servers: &mut serde_json::Map<String, JsonValue>) -> Result<()> {
+        match self {
+            Self::AddServer { name, entry } => {
+                if servers.contains_key(name) {
+                    return Err(anyhow!("A server named '{}' already exists", name));
+                }
+                servers.insert(name.clone(), entry.clone());
+            }
+            Self::RemoveServer { name, .. } => {
+                servers.remove(name).ok_or_else(|| anyhow!("Server '{}' not found", name))?;
+            }
+            Self::RenameServer { old_name, new_name } => {
+                if servers.contains_key(new_name) {
+                    return Err(anyhow!("A server named '{}' already exists", new_name));
+                }
+                let entry = servers.remove(old_name).ok_or_else(|| anyhow!("Server '{}' not found", old_name))?;
+                servers.insert(new_name.clone(), entry);
+            }
+            Self::SetEnabled { name, enabled } => {
+                let entry = servers
+                    .get_mut(name)
+                    .and_then(|e| e.as_object_mut())
+                    .ok_or_else(|| anyhow!("Server '{}' not found", name))?;
+                if *enabled {
+                    entry.remove("disabled");
+                } else {
+                    entry.insert("disabled".to_string(), JsonValue::Bool(true));
+                }
+            }
+            Self::Batch { mutations } => {
+                for mutation in mutations {
+                    mutation.apply(servers)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returned when a config changed outside the app after the operation being undone or redone
+#[derive(Debug, thiserror::Error)]
+#[error("{} was changed outside MCP Control after '{}'; resolve the change manually", path.display(), operation)]
+pub struct UndoConflict {
+    pub path: PathBuf,
+    pub operation: String,
+}
+
+/// A recorded operation together with the file state it left behind
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UndoEntry {
+    pub mutation: ConfigMutation,
+    pub config_path: PathBuf,
+    /// Keys leading to the servers object, e.g. `["mcp", "servers"]`
+    pub servers_path: Vec<String>,
+    /// Hash of the config file right after the operation
+    pub content_hash: String,
+}
+
+#[derive(Debug, Default)]
+struct History {
+    undo: VecDeque<UndoEntry>,
+    redo: Vec<UndoEntry>,
+}
+
+/// Per-application undo and redo stacks for the current session
+#[derive(Debug)]
+pub struct UndoManager {
+    depth: usize,
+    histories: HashMap<String, History>,
+    /// Where configs are backed up before a replay; `~/.mcp-control-backups` when unset
+    backup_dir: Option<PathBuf>,
+}
+
+impl Default for UndoManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UndoManager {
+    /// Create a manager with the default depth limit
+    pub fn new() -> Self {
+        Self::with_depth(DEFAULT_UNDO_DEPTH)
+    }
+
+    /// Create a manager keeping at most `depth` operations per application
+    pub fn with_depth(depth: usize) -> Self {
+        Self { depth: depth.max(1), histories: HashMap::new(), backup_dir: None }
+    }
+
+    /// Back configs up to `backup_dir` before undoing or redoing into them
+    pub fn with_backup_dir(mut self, backup_dir: PathBuf) -> Self {
+        self.backup_dir = Some(backup_dir);
+        self
+    }
+
+    /// Record an operation that was just written to `config_path`.
+    ///
+    /// Call after the write succeeded so the stored hash matches the file. Recording
+    /// a new operation clears the application's redo stack.
+    pub fn record(&mut self, app_id: &str, config_path: &Path, servers_path: &[impl AsRef<str>], mutation: ConfigMutation) -> Result<()> {
+        let content_hash = file_hash(config_path)?;
+        let history = self.histories.entry(app_id.to_string()).or_default();
+
+        history.redo.clear();
+        history.undo.push_back(UndoEntry {
+            mutation,
+            config_path: config_path.to_path_buf(),
+            servers_path: servers_path.iter().map(|k| k.as_ref().to_string()).collect(),
+            content_hash,
+        });
+        while history.undo.len() > self.depth {
+            history.undo.pop_front();
+        }
+        Ok(())
+    }
+
+    /// Whether an application has anything to undo
+    pub fn can_undo(&self, app_id: &str) -> bool {
+        self.histories.get(app_id).is_some_and(|h| !h.undo.is_empty())
+    }
+
+    /// Whether an application has anything to redo
+    pub fn can_redo(&self, app_id: &str) -> bool {
+        self.histories.get(app_id).is_some_and(|h| !h.redo.is_empty())
+    }
+
+    /// Reverse the application's most recent operation, returning it.
+    ///
+    /// Returns `Ok(None)` when there is nothing to undo. Fails with [`UndoConflict`]
+    /// if the config no longer matches the state the operation left behind; the
+    /// operation then stays on the stack. The config is backed up first and written
+    /// through `file_service`, so externally managed configs are refused.
+    pub async fn undo_last(&mut self, app_id: &str, file_service: &mut ConfigFileService) -> Result<Option<ConfigMutation>> {
+        let backup_dir = self.backup_dir()?;
+        let Some(history) = self.histories.get_mut(app_id) else {
+            return Ok(None);
+        };
+        let Some(entry) = history.undo.pop_back() else {
+            return Ok(None);
+        };
+
+        match replay(&entry, &entry.mutation.inverse(), &backup_dir, file_service).await {
+            Ok(content_hash) => {
+                let mutation = entry.mutation.clone();
+                history.redo.push(UndoEntry { content_hash, ..entry });
+                Ok(Some(mutation))
+            }
+            Err(e) => {
+                history.undo.push_back(entry);
+                Err(e)
+            }
+        }
+    }
+
+    /// Re-apply the application's most recently undone operation, returning it
+    pub async fn redo_last(&mut self, app_id: &str, file_service: &mut ConfigFileService) -> Result<Option<ConfigMutation>> {
+        let backup_dir = self.backup_dir()?;
+        let Some(history) = self.histories.get_mut(app_id) else {
+            return Ok(None);
+        };
+        let Some(entry) = history.redo.pop() else {
+            return Ok(None);
+        };
+
+        match replay(&entry, &entry.mutation, &backup_dir, file_service).await {
+            Ok(content_hash) => {
+                let mutation = entry.mutation.clone();
+                history.undo.push_back(UndoEntry { content_hash, ..entry });
+                Ok(Some(mutation))
+            }
+            Err(e) => {
+                history.redo.push(entry);
+                Err(e)
+            }
+        }
+    }
+
+    fn backup_dir(&self) -> Result<PathBuf> {
+        match &self.backup_dir {
+            Some(dir) => Ok(dir.clone()),
+            None => default_backup_dir(),
+        }
+    }
+}
+
+/// Apply `mutation` to the entry's config after checking it was not edited externally,
+/// returning the hash of the written file
+async fn replay(
+    entry: &UndoEntry,
+    mutation: &ConfigMutation,
+    backup_dir: &Path,
+    file_service: &mut ConfigFileService,
+) -> Result<String> {
+    let path = &entry.config_path;
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config: {}", path.display()))?;
+    if content_hash(&content) != entry.content_hash {
+        return Err(UndoConflict { path: path.clone(), operation: entry.mutation.describe() }.into());
+    }
+
+    let mut config: JsonValue = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse config: {}", path.display()))?;
+    let servers = entry
+        .servers_path
+        .iter()
+        .try_fold(&mut config, |node, key| node.get_mut(key))
+        .and_then(|servers| servers.as_object_mut())
+        .ok_or_else(|| anyhow!("No servers section in {}", path.display()))?;
+    mutation.apply(servers)?;
+
+    file_service.ensure_writable(path)?;
+    create_backup_before_write(path, backup_dir).await?;
+    file_service.write_config(path, &config).await?;
+
+    file_hash(path)
+}
+
+fn file_hash(path: &Path) -> Result<String> {
+    let content = fs::read_to_string(path)
+        .with_context(|| format!("Failed to read config: {}", path.display()))?;
+    Ok(content_hash(&content))
+}
+
+fn content_hash(content: &str) -> String {
+    use sha2::{Sha256, Digest};
+
+    let mut hasher = Sha256::new();
+    hasher.update(content.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    const SERVERS_PATH: &[&str] = &["mcpServers"];
+
+    /// Write `config` after applying `mutation` to it, the way a command would, and record it
+    fn perform(manager: &mut UndoManager, path: &Path, mutation: ConfigMutation) {
+        let mut config: JsonValue = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        mutation.apply(config["mcpServers"].as_object_mut().unwrap()).unwrap();
+        fs::write(path, serde_json::to_string_pretty(&config).unwrap()).unwrap();
+        manager.record("claude-desktop", path, SERVERS_PATH, mutation).unwrap();
+    }
+
+    fn servers(path: &Path) -> JsonValue {
+        let config: JsonValue = serde_json::from_str(&fs::read_to_string(path).unwrap()).unwrap();
+        config["mcpServers"].clone()
+    }
+
+    fn setup() -> (TempDir, PathBuf, UndoManager, ConfigFileService) {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("claude.json");
+        fs::write(&path, r#"{"mcpServers": {"github": {"command": "npx", "args": ["-y", "server-github"]}}}"#).unwrap();
+        let manager = UndoManager::new().with_backup_dir(temp_dir.path().join("backups"));
+        let file_service = ConfigFileService::new("test".to_string(), temp_dir.path().join("service-backups"));
+        (temp_dir, path, manager, file_service)
+    }
+
+    #[tokio::test]
+    async fn test_undo_and_redo_add_remove_rename() {
+        let (temp_dir, path, mut manager, mut file_service) = setup();
+        let original = servers(&path);
+
+        perform(&mut manager, &path, ConfigMutation::AddServer { name: "notes".to_string(), entry: json!({"command": "uvx"}) });
+        perform(&mut manager, &path, ConfigMutation::RemoveServer { name: "github".to_string(), entry: original["github"].clone() });
+        perform(&mut manager, &path, ConfigMutation::RenameServer { old_name: "notes".to_string(), new_name: "notebook".to_string() });
+        assert_eq!(servers(&path), json!({"notebook": {"command": "uvx"}}));
+
+        let undone = manager.undo_last("claude-desktop", &mut file_service).await.unwrap().unwrap();
+        assert!(matches!(undone, ConfigMutation::RenameServer { .. }));
+        assert!(servers(&path).get("notes").is_some());
+
+        manager.undo_last("claude-desktop", &mut file_service).await.unwrap();
+        assert_eq!(servers(&path)["github"], original["github"]);
+
+        manager.undo_last("claude-desktop", &mut file_service).await.unwrap();
+        assert_eq!(servers(&path), original);
+        assert!(manager.undo_last("claude-desktop", &mut file_service).await.unwrap().is_none());
+
+        manager.redo_last("claude-desktop", &mut file_service).await.unwrap();
+        assert_eq!(servers(&path)["notes"], json!({"command": "uvx"}));
+        assert!(manager.can_redo("claude-desktop"));
+
+        perform(&mut manager, &path, ConfigMutation::SetEnabled { name: "notes".to_string(), enabled: false });
+        assert!(!manager.can_redo("claude-desktop"));
+        manager.undo_last("claude-desktop", &mut file_service).await.unwrap();
+        assert!(servers(&path)["notes"].get("disabled").is_none());
+
+        // Every replay backs the config up first
+        assert!(fs::read_dir(temp_dir.path().join("backups")).unwrap().next().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_batch_is_undone_as_one_operation() {
+        let (_temp_dir, path, mut manager, mut file_service) = setup();
+        let original = servers(&path);
+        perform(&mut manager, &path, ConfigMutation::AddServer { name: "github-work".to_string(), entry: json!({"command": "npx"}) });
+
+        // Deleting a server removes every variation key in a single write
+        let batch = ConfigMutation::Batch {
+            mutations: vec![
+                ConfigMutation::RemoveServer { name: "github".to_string(), entry: original["github"].clone() },
+                ConfigMutation::RemoveServer { name: "github-work".to_string(), entry: json!({"command": "npx"}) },
+            ],
+        };
+        assert_eq!(batch.describe(), "remove 'github', remove 'github-work'");
+        perform(&mut manager, &path, batch);
+        assert_eq!(servers(&path), json!({}));
+
+        let undone = manager.undo_last("claude-desktop", &mut file_service).await.unwrap().unwrap();
+        assert!(matches!(undone, ConfigMutation::Batch { .. }));
+        assert_eq!(servers(&path)["github"], original["github"]);
+        assert_eq!(servers(&path)["github-work"], json!({"command": "npx"}));
+
+        manager.redo_last("claude-desktop", &mut file_service).await.unwrap();
+        assert_eq!(servers(&path), json!({}));
+    }
+
+    #[tokio::test]
+    async fn test_external_change_is_a_conflict() {
+        let (temp_dir, path, _, mut file_service) = setup();
+        let mut manager = UndoManager::with_depth(1).with_backup_dir(temp_dir.path().join("backups"));
+
+        perform(&mut manager, &path, ConfigMutation::SetEnabled { name: "github".to_string(), enabled: false });
+        perform(&mut manager, &path, ConfigMutation::AddServer { name: "notes".to_string(), entry: json!({"command": "uvx"}) });
+        let recorded = fs::read_to_string(&path).unwrap();
+
+        fs::write(&path, r#"{"mcpServers": {"notes": {"command": "uvx"}}}"#).unwrap();
+        let error = manager.undo_last("claude-desktop", &mut file_service).await.unwrap_err();
+        assert!(error.downcast_ref::<UndoConflict>().is_some());
+        assert_eq!(servers(&path), json!({"notes": {"command": "uvx"}}));
+
+        // The conflicting operation stays available once the file is back as it was left
+        fs::write(&path, recorded).unwrap();
+        assert!(manager.undo_last("claude-desktop", &mut file_service).await.unwrap().is_some());
+        assert!(servers(&path).get("notes").is_none());
+
+        // Only the latest operation fits within the depth limit
+        assert!(!manager.can_undo("claude-desktop"));
+        assert!(manager.undo_last("other-app", &mut file_service).await.unwrap().is_none());
+    }
+}
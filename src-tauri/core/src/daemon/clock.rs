@@ -0,0 +1,97 @@
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::watch;
+
+/// Source of timer sleeps for background tasks.
+///
+/// Abstracted so the daemon's timers can be driven deterministically in tests.
+#[async_trait]
+pub trait Clock: Send + Sync {
+    /// Wait until the given duration has elapsed on this clock
+    async fn sleep(&self, duration: Duration);
+}
+
+/// Clock backed by the tokio timer
+#[derive(Debug, Clone, Default)]
+pub struct SystemClock;
+
+#[async_trait]
+impl Clock for SystemClock {
+    async fn sleep(&self, duration: Duration) {
+        tokio::time::sleep(duration).await;
+    }
+}
+
+/// Clock that only moves when advanced by hand
+#[derive(Debug)]
+pub struct ManualClock {
+    elapsed: watch::Sender<Duration>,
+    sleepers: Arc<AtomicUsize>,
+}
+
+impl ManualClock {
+    /// Create a clock starting at zero
+    pub fn new() -> Self {
+        let (elapsed, _) = watch::channel(Duration::ZERO);
+        Self {
+            elapsed,
+            sleepers: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    /// Move the clock forward, waking every sleep whose deadline has passed
+    pub fn advance(&self, by: Duration) {
+        self.elapsed.send_modify(|elapsed| *elapsed += by);
+    }
+
+    /// Time elapsed since the clock was created
+    pub fn elapsed(&self) -> Duration {
+        *self.elapsed.borrow()
+    }
+
+    /// Number of tasks currently sleeping on this clock
+    pub fn sleepers(&self) -> usize {
+        self.sleepers.load(Ordering::SeqCst)
+    }
+
+    /// Wait (in real time) until at least `count` tasks are sleeping on this clock
+    pub async fn wait_for_sleepers(&self, count: usize) {
+        while self.sleepers() < count {
+            tokio::time::sleep(Duration::from_millis(1)).await;
+        }
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Keeps the sleeper count accurate even when a sleep is cancelled
+struct SleeperGuard(Arc<AtomicUsize>);
+
+impl Drop for SleeperGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[async_trait]
+impl Clock for ManualClock {
+    async fn sleep(&self, duration: Duration) {
+        let mut elapsed = self.elapsed.subscribe();
+        let deadline = *elapsed.borrow_and_update() + duration;
+
+        self.sleepers.fetch_add(1, Ordering::SeqCst);
+        let _guard = SleeperGuard(Arc::clone(&self.sleepers));
+
+        while *elapsed.borrow_and_update() < deadline {
+            if elapsed.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod clock;
+pub mod service;
+
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use service::{
+    AppState, DaemonConfig, DaemonSources, DetectionDaemon, HealthSource, ShutdownReport, SourceRefresh, StateDelta,
+    SystemSourceRefresh,
+};
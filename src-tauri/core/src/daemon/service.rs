@@ -0,0 +1,736 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::{broadcast, watch, Notify};
+use tokio::task::JoinHandle;
+
+use super::clock::{Clock, SystemClock};
+use crate::configuration::annotations::AnnotationStore;
+use crate::detection::{ApplicationDetector, ApplicationRegistry, ConfigValidator, McpServerConfig};
+use crate::filesystem::{FsProvider, RealFs};
+use crate::server::health::{HealthHistory, HealthRun, HealthScheduler};
+
+/// Timing and backpressure settings for the detection daemon
+#[derive(Debug, Clone)]
+pub struct DaemonConfig {
+    /// How often watched config files are checked for changes
+    pub watch_interval: Duration,
+    /// How often applications are re-detected
+    pub detection_interval: Duration,
+    /// How often server health checks run
+    pub health_interval: Duration,
+    /// How often coalesced deltas held back for a slow consumer are retried
+    pub flush_interval: Duration,
+    /// Deltas the slowest subscriber may have queued before new updates are coalesced
+    pub max_in_flight: usize,
+    /// Capacity of the broadcast channel
+    pub channel_capacity: usize,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            watch_interval: Duration::from_secs(2),
+            detection_interval: Duration::from_secs(60),
+            health_interval: Duration::from_secs(15 * 60),
+            flush_interval: Duration::from_millis(250),
+            max_in_flight: 4,
+            channel_capacity: 16,
+        }
+    }
+}
+
+/// Consolidated state change pushed to subscribers
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct StateDelta {
+    /// Config files whose contents changed, appeared, or disappeared
+    pub changed_configs: BTreeSet<PathBuf>,
+    /// Applications whose detection state changed, mapped to whether they are now detected
+    pub applications: BTreeMap<String, bool>,
+    /// Latest health result of every checked server
+    pub health: BTreeMap<String, HealthRun>,
+    /// Number of individual updates folded into this delta
+    pub coalesced: usize,
+}
+
+impl StateDelta {
+    /// Whether the delta carries no changes
+    pub fn is_empty(&self) -> bool {
+        self.changed_configs.is_empty() && self.applications.is_empty() && self.health.is_empty()
+    }
+
+    /// Fold a newer delta into this one, keeping the latest value of every entry
+    pub fn merge(&mut self, newer: StateDelta) {
+        self.changed_configs.extend(newer.changed_configs);
+        self.applications.extend(newer.applications);
+        self.health.extend(newer.health);
+        self.coalesced += newer.coalesced;
+    }
+}
+
+/// Servers and bookkeeping used by the periodic health checks
+pub struct HealthSource {
+    pub scheduler: HealthScheduler,
+    /// Servers checked until a config change brings a fresh list
+    pub servers: Vec<McpServerConfig>,
+    pub annotations: AnnotationStore,
+    pub history: HealthHistory,
+}
+
+/// Inputs the daemon watches
+pub struct DaemonSources {
+    pub fs: Arc<dyn FsProvider>,
+    pub detector: ApplicationDetector,
+    /// Config files to watch for changes
+    pub watch_paths: Vec<PathBuf>,
+    /// Health checks to run, if enabled
+    pub health: Option<HealthSource>,
+    /// Re-reads the watch paths and servers after a config changes; without it both stay fixed
+    pub refresh: Option<Arc<dyn SourceRefresh>>,
+}
+
+/// Re-reads what the daemon watches once a watched config has changed
+#[async_trait]
+pub trait SourceRefresh: Send + Sync {
+    /// Config files to watch
+    async fn watch_paths(&self) -> Result<Vec<PathBuf>>;
+
+    /// Servers to health check
+    async fn servers(&self) -> Result<Vec<McpServerConfig>>;
+}
+
+/// Reads watch paths and servers from every known application on this machine
+#[derive(Debug, Clone, Default)]
+pub struct SystemSourceRefresh;
+
+#[async_trait]
+impl SourceRefresh for SystemSourceRefresh {
+    async fn watch_paths(&self) -> Result<Vec<PathBuf>> {
+        Ok(ApplicationRegistry::with_auto_load()
+            .get_all_applications()
+            .iter()
+            .flat_map(|p| p.expanded_config_paths())
+            .collect())
+    }
+
+    async fn servers(&self) -> Result<Vec<McpServerConfig>> {
+        let profiles: Vec<_> = ApplicationRegistry::with_auto_load().get_all_applications().into_iter().cloned().collect();
+        Ok(ConfigValidator::new()?
+            .validate_multiple_configs(&profiles)
+            .await?
+            .into_iter()
+            .flat_map(|result| result.mcp_servers)
+            .collect())
+    }
+}
+
+impl DaemonSources {
+    /// Watch the real filesystem, every known application, and the servers they configure
+    pub async fn for_system() -> Result<Self> {
+        let refresh = SystemSourceRefresh;
+
+        Ok(Self {
+            fs: Arc::new(RealFs),
+            detector: ApplicationDetector::new()?,
+            watch_paths: refresh.watch_paths().await?,
+            refresh: Some(Arc::new(SystemSourceRefresh)),
+            health: Some(HealthSource {
+                scheduler: HealthScheduler::new(),
+                servers: refresh.servers().await?,
+                annotations: AnnotationStore::load_default().unwrap_or_default(),
+                history: HealthHistory::load_default().unwrap_or_default(),
+            }),
+        })
+    }
+}
+
+/// Outcome of stopping the daemon
+#[derive(Debug, Clone, PartialEq)]
+pub struct ShutdownReport {
+    /// Subtasks that stopped on their own
+    pub stopped: usize,
+    /// Subtasks that had to be aborted after the timeout
+    pub aborted: usize,
+}
+
+/// Updates waiting to be published
+struct Pending {
+    delta: Mutex<StateDelta>,
+    notify: Notify,
+}
+
+impl Pending {
+    fn push(&self, mut delta: StateDelta) {
+        delta.coalesced = 1;
+        self.delta.lock().unwrap().merge(delta);
+        self.notify.notify_one();
+    }
+
+    fn take(&self) -> StateDelta {
+        std::mem::take(&mut *self.delta.lock().unwrap())
+    }
+}
+
+/// Background service combining config watching, incremental detection, and health checks.
+///
+/// Updates are published as [`StateDelta`]s over a broadcast channel. When the slowest
+/// subscriber falls behind, new updates are merged into a single pending delta instead
+/// of piling up in the channel.
+pub struct DetectionDaemon {
+    sender: broadcast::Sender<StateDelta>,
+    shutdown: watch::Sender<bool>,
+    pending: Arc<Pending>,
+    tasks: Vec<JoinHandle<()>>,
+}
+
+impl DetectionDaemon {
+    /// Start the daemon on the tokio timer
+    pub fn start(config: DaemonConfig, sources: DaemonSources) -> Self {
+        Self::start_with_clock(config, sources, Arc::new(SystemClock))
+    }
+
+    /// Start the daemon with a custom clock driving its timers
+    pub fn start_with_clock(config: DaemonConfig, sources: DaemonSources, clock: Arc<dyn Clock>) -> Self {
+        let (sender, _) = broadcast::channel(config.channel_capacity.max(1));
+        let (shutdown, _) = watch::channel(false);
+        let pending = Arc::new(Pending {
+            delta: Mutex::new(StateDelta::default()),
+            notify: Notify::new(),
+        });
+
+        // Health checks pick up the server list the watcher re-reads after a config change
+        let (servers, health) = match sources.health {
+            Some(health) => {
+                let (sender, receiver) = watch::channel(health.servers.clone());
+                (Some(sender), Some((health, receiver)))
+            }
+            None => (None, None),
+        };
+
+        let mut tasks = vec![
+            tokio::spawn(watch_configs(
+                sources.fs,
+                sources.watch_paths,
+                sources.refresh.map(|source| Refresher { source, servers }),
+                config.watch_interval,
+                Arc::clone(&clock),
+                Arc::clone(&pending),
+                shutdown.subscribe(),
+            )),
+            tokio::spawn(detect_applications(
+                sources.detector,
+                config.detection_interval,
+                Arc::clone(&clock),
+                Arc::clone(&pending),
+                shutdown.subscribe(),
+            )),
+            tokio::spawn(publish(
+                sender.clone(),
+                config.max_in_flight.max(1),
+                config.flush_interval,
+                Arc::clone(&clock),
+                Arc::clone(&pending),
+                shutdown.subscribe(),
+            )),
+        ];
+
+        if let Some((health, servers)) = health {
+            tasks.push(tokio::spawn(check_health(
+                health,
+                servers,
+                config.health_interval,
+                Arc::clone(&clock),
+                Arc::clone(&pending),
+                shutdown.subscribe(),
+            )));
+        }
+
+        Self { sender, shutdown, pending, tasks }
+    }
+
+    /// Subscribe to state deltas
+    pub fn subscribe(&self) -> broadcast::Receiver<StateDelta> {
+        self.sender.subscribe()
+    }
+
+    /// Number of updates merged and waiting to be published
+    pub fn pending_updates(&self) -> usize {
+        self.pending.delta.lock().unwrap().coalesced
+    }
+
+    /// Cancel every subtask, aborting any that have not stopped within `timeout`
+    pub async fn shutdown(self, timeout: Duration) -> ShutdownReport {
+        let _ = self.shutdown.send(true);
+        let deadline = tokio::time::Instant::now() + timeout;
+        let mut report = ShutdownReport { stopped: 0, aborted: 0 };
+
+        for mut task in self.tasks {
+            match tokio::time::timeout_at(deadline, &mut task).await {
+                Ok(_) => report.stopped += 1,
+                Err(_) => {
+                    task.abort();
+                    report.aborted += 1;
+                }
+            }
+        }
+
+        report
+    }
+}
+
+/// Resolve once shutdown has been requested
+async fn stopped(shutdown: &mut watch::Receiver<bool>) {
+    while !*shutdown.borrow_and_update() {
+        if shutdown.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Content hash of every watched path, or None where the file is missing
+async fn fingerprint(fs: &Arc<dyn FsProvider>, paths: &[PathBuf]) -> HashMap<PathBuf, Option<String>> {
+    let mut hashes = HashMap::new();
+    for path in paths {
+        let hash = fs.read(path).await.ok().map(|bytes| format!("{:x}", Sha256::digest(&bytes)));
+        hashes.insert(path.clone(), hash);
+    }
+    hashes
+}
+
+/// Where the config watcher sends what it re-reads after a change
+struct Refresher {
+    source: Arc<dyn SourceRefresh>,
+    /// Server list read by the health checks, if they are enabled
+    servers: Option<watch::Sender<Vec<McpServerConfig>>>,
+}
+
+async fn watch_configs(
+    fs: Arc<dyn FsProvider>,
+    mut paths: Vec<PathBuf>,
+    refresher: Option<Refresher>,
+    interval: Duration,
+    clock: Arc<dyn Clock>,
+    pending: Arc<Pending>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut known = fingerprint(&fs, &paths).await;
+
+    loop {
+        tokio::select! {
+            _ = clock.sleep(interval) => {}
+            _ = stopped(&mut shutdown) => return,
+        }
+
+        let current = fingerprint(&fs, &paths).await;
+        let changed_configs: BTreeSet<PathBuf> = paths
+            .iter()
+            .filter(|path| known.get(*path) != current.get(*path))
+            .cloned()
+            .collect();
+        known = current;
+
+        if changed_configs.is_empty() {
+            continue;
+        }
+        pending.push(StateDelta { changed_configs, ..Default::default() });
+
+        if let Some(refresher) = &refresher {
+            match refresher.source.watch_paths().await {
+                Ok(refreshed) if refreshed != paths => {
+                    paths = refreshed;
+                    known = fingerprint(&fs, &paths).await;
+                }
+                Ok(_) => {}
+                Err(e) => log::warn!("Failed to refresh watched configs: {}", e),
+            }
+            if let Some(servers) = &refresher.servers {
+                match refresher.source.servers().await {
+                    Ok(refreshed) => {
+                        servers.send_replace(refreshed);
+                    }
+                    Err(e) => log::warn!("Failed to refresh servers for health checks: {}", e),
+                }
+            }
+        }
+    }
+}
+
+async fn detect_applications(
+    mut detector: ApplicationDetector,
+    interval: Duration,
+    clock: Arc<dyn Clock>,
+    pending: Arc<Pending>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    let mut known: BTreeMap<String, bool> = BTreeMap::new();
+
+    loop {
+        // Detection caches its results; clear them so every pass sees the current disk state
+        detector.clear_cache();
+        let results = tokio::select! {
+            results = detector.detect_all_applications() => results,
+            _ = stopped(&mut shutdown) => return,
+        };
+
+        match results {
+            Ok(results) => {
+                let applications: BTreeMap<String, bool> = results
+                    .into_iter()
+                    .filter(|r| known.get(&r.profile.id) != Some(&r.detected))
+                    .map(|r| (r.profile.id, r.detected))
+                    .collect();
+
+                if !applications.is_empty() {
+                    known.extend(applications.clone());
+                    pending.push(StateDelta { applications, ..Default::default() });
+                }
+            }
+            Err(e) => log::warn!("Background detection failed: {}", e),
+        }
+
+        tokio::select! {
+            _ = clock.sleep(interval) => {}
+            _ = stopped(&mut shutdown) => return,
+        }
+    }
+}
+
+async fn check_health(
+    mut source: HealthSource,
+    servers: watch::Receiver<Vec<McpServerConfig>>,
+    interval: Duration,
+    clock: Arc<dyn Clock>,
+    pending: Arc<Pending>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    loop {
+        tokio::select! {
+            _ = clock.sleep(interval) => {}
+            _ = stopped(&mut shutdown) => return,
+        }
+
+        source.servers = servers.borrow().clone();
+
+        // Dropping an unfinished batch cancels its in-flight probes
+        let report = tokio::select! {
+            report = source.scheduler.run_all(&source.servers, &source.annotations, &mut source.history) => report,
+            _ = stopped(&mut shutdown) => return,
+        };
+
+        if let Err(e) = source.history.save() {
+            log::warn!("Failed to save health history: {}", e);
+        }
+
+        let health: BTreeMap<String, HealthRun> = report.results.into_iter().collect();
+        if !health.is_empty() {
+            pending.push(StateDelta { health, ..Default::default() });
+        }
+    }
+}
+
+async fn publish(
+    sender: broadcast::Sender<StateDelta>,
+    max_in_flight: usize,
+    flush_interval: Duration,
+    clock: Arc<dyn Clock>,
+    pending: Arc<Pending>,
+    mut shutdown: watch::Receiver<bool>,
+) {
+    loop {
+        tokio::select! {
+            _ = pending.notify.notified() => {}
+            _ = clock.sleep(flush_interval) => {}
+            _ = stopped(&mut shutdown) => return,
+        }
+
+        // A subscriber is still behind; keep merging until it catches up
+        if sender.len() >= max_in_flight {
+            continue;
+        }
+
+        let delta = pending.take();
+        if !delta.is_empty() {
+            // Sending only fails when nobody is subscribed, which is fine
+            let _ = sender.send(delta);
+        }
+    }
+}
+
+/// Long-lived application state shared with the Tauri commands
+#[derive(Default)]
+pub struct AppState {
+    daemon: tokio::sync::Mutex<Option<DetectionDaemon>>,
+}
+
+impl AppState {
+    /// Start the detection daemon if it is not running and subscribe to its deltas
+    pub async fn start_daemon(&self, config: DaemonConfig) -> Result<broadcast::Receiver<StateDelta>> {
+        let mut daemon = self.daemon.lock().await;
+        if let Some(running) = daemon.as_ref() {
+            return Ok(running.subscribe());
+        }
+
+        let started = DetectionDaemon::start(config, DaemonSources::for_system().await?);
+        let receiver = started.subscribe();
+        *daemon = Some(started);
+        Ok(receiver)
+    }
+
+    /// Stop the detection daemon, if it is running
+    pub async fn stop_daemon(&self, timeout: Duration) -> Option<ShutdownReport> {
+        let daemon = self.daemon.lock().await.take()?;
+        Some(daemon.shutdown(timeout).await)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::daemon::clock::ManualClock;
+    use crate::detection::profiles::{
+        ApplicationCategory, ApplicationMetadata, ApplicationProfile, ConfigFormat, ConfigStructure, DetectionMethod, DetectionStrategy,
+    };
+    use crate::detection::{ApplicationRegistry, ConfigSource, ServerMetadata, ServerType};
+    use crate::filesystem::InMemoryFs;
+    use crate::server::health::HealthProbe;
+    use async_trait::async_trait;
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    const CONFIG: &str = "/home/me/.test/config.json";
+
+    fn test_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "test-app".to_string(),
+            name: "Test Application".to_string(),
+            bundle_id: "com.test.app".to_string(),
+            config_path: CONFIG.to_string(),
+            alt_config_paths: vec![],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec!["/Applications/Test.app".to_string()],
+            alt_executable_paths: vec![],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: false,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: false,
+                priority_order: vec![DetectionMethod::ExecutableCheck, DetectionMethod::ConfigCheck],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Test Developer".to_string(),
+                category: ApplicationCategory::Other("Test".to_string()),
+                mcp_version: "1.0".to_string(),
+                notes: None,
+                requires_permissions: false,
+                ..Default::default()
+            },
+        }
+    }
+
+    fn sources(fs: Arc<InMemoryFs>, health: Option<HealthSource>) -> DaemonSources {
+        let mut registry = ApplicationRegistry::new();
+        let builtin: Vec<String> = registry.get_all_applications().iter().map(|p| p.id.clone()).collect();
+        for id in builtin {
+            registry.remove_application(&id);
+        }
+        registry.add_application(test_profile());
+
+        DaemonSources {
+            fs: fs.clone(),
+            detector: ApplicationDetector::with_fs(registry, fs).unwrap(),
+            watch_paths: vec![PathBuf::from(CONFIG)],
+            health,
+            refresh: None,
+        }
+    }
+
+    fn config() -> DaemonConfig {
+        DaemonConfig {
+            watch_interval: Duration::from_secs(1),
+            detection_interval: Duration::from_secs(3600),
+            health_interval: Duration::from_secs(1),
+            flush_interval: Duration::from_secs(10),
+            max_in_flight: 1,
+            channel_capacity: 8,
+        }
+    }
+
+    /// Wait in real time for a condition the daemon's tasks will reach shortly
+    async fn eventually(condition: impl Fn() -> bool) {
+        tokio::time::timeout(Duration::from_secs(5), async {
+            while !condition() {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        })
+        .await
+        .expect("condition not reached in time");
+    }
+
+    #[tokio::test]
+    async fn test_deltas_coalesce_while_consumer_is_slow() {
+        let fs = Arc::new(InMemoryFs::case_sensitive());
+        fs.add_dir("/Applications/Test.app");
+        let clock = Arc::new(ManualClock::new());
+        let daemon = DetectionDaemon::start_with_clock(config(), sources(fs.clone(), None), clock.clone());
+        let mut deltas = daemon.subscribe();
+
+        // The initial detection pass is published right away and left unread
+        eventually(|| deltas.len() == 1).await;
+
+        for version in 1..=3 {
+            clock.wait_for_sleepers(3).await;
+            fs.add_file(CONFIG, format!("{{\"version\": {}}}", version));
+            clock.advance(Duration::from_secs(1));
+            eventually(|| daemon.pending_updates() == version).await;
+        }
+        assert_eq!(deltas.len(), 1);
+
+        let initial = deltas.recv().await.unwrap();
+        assert_eq!(initial.applications.get("test-app"), Some(&true));
+
+        // Once the consumer catches up, the held-back updates arrive as one delta
+        clock.wait_for_sleepers(3).await;
+        clock.advance(Duration::from_secs(10));
+        let merged = tokio::time::timeout(Duration::from_secs(5), deltas.recv()).await.unwrap().unwrap();
+        assert_eq!(merged.coalesced, 3);
+        assert_eq!(merged.changed_configs, BTreeSet::from([PathBuf::from(CONFIG)]));
+        assert!(merged.applications.is_empty());
+
+        daemon.shutdown(Duration::from_secs(1)).await;
+    }
+
+    /// Probe that never returns, recording that it was called
+    struct HangingProbe(Arc<AtomicBool>);
+
+    #[async_trait]
+    impl HealthProbe for HangingProbe {
+        async fn health_check(&self, _server: &McpServerConfig) -> HealthRun {
+            self.0.store(true, Ordering::SeqCst);
+            std::future::pending().await
+        }
+    }
+
+    fn server(name: &str) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            command: Some("node".to_string()),
+            args: vec![],
+            env: HashMap::new(),
+            cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
+            server_type: ServerType::Stdio,
+            metadata: ServerMetadata {
+                description: None,
+                version: None,
+                author: None,
+                capabilities: vec![],
+                enabled: true,
+                source: ConfigSource::MainConfig,
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_cancels_subtasks_within_bound() {
+        let fs = Arc::new(InMemoryFs::case_sensitive());
+        let clock = Arc::new(ManualClock::new());
+        let probing = Arc::new(AtomicBool::new(false));
+        let health = HealthSource {
+            scheduler: HealthScheduler::with_probe(Arc::new(HangingProbe(probing.clone()))).with_jitter(Duration::ZERO),
+            servers: vec![server("stuck")],
+            annotations: AnnotationStore::default(),
+            history: HealthHistory::default(),
+        };
+
+        let daemon = DetectionDaemon::start_with_clock(config(), sources(fs, Some(health)), clock.clone());
+        let mut deltas = daemon.subscribe();
+
+        // Start a health batch whose probe never returns
+        clock.wait_for_sleepers(4).await;
+        clock.advance(Duration::from_secs(1));
+        eventually(|| probing.load(Ordering::SeqCst)).await;
+
+        let started = std::time::Instant::now();
+        let report = daemon.shutdown(Duration::from_secs(2)).await;
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert_eq!(report, ShutdownReport { stopped: 4, aborted: 0 });
+        // Every sender is gone once the publisher has stopped
+        let closed = tokio::time::timeout(Duration::from_secs(1), async {
+            while !matches!(deltas.recv().await, Err(broadcast::error::RecvError::Closed)) {}
+        })
+        .await;
+        assert!(closed.is_ok());
+    }
+
+    /// Probe that records which servers it checked
+    struct RecordingProbe(Arc<Mutex<Vec<String>>>);
+
+    #[async_trait]
+    impl HealthProbe for RecordingProbe {
+        async fn health_check(&self, server: &McpServerConfig) -> HealthRun {
+            self.0.lock().unwrap().push(server.name.clone());
+            HealthRun {
+                timestamp: chrono::Utc::now(),
+                healthy: true,
+                latency_ms: 0,
+                error: None,
+                framing: None,
+            }
+        }
+    }
+
+    /// Serves the servers currently listed in the test config, counting reads
+    struct ConfigRefresh(Arc<InMemoryFs>, Arc<AtomicUsize>);
+
+    #[async_trait]
+    impl SourceRefresh for ConfigRefresh {
+        async fn watch_paths(&self) -> Result<Vec<PathBuf>> {
+            Ok(vec![PathBuf::from(CONFIG)])
+        }
+
+        async fn servers(&self) -> Result<Vec<McpServerConfig>> {
+            self.1.fetch_add(1, Ordering::SeqCst);
+            let config: serde_json::Value = serde_json::from_slice(&self.0.read(std::path::Path::new(CONFIG)).await?)?;
+            Ok(config["servers"].as_array().into_iter().flatten().filter_map(|n| n.as_str()).map(server).collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_checks_follow_config_changes() {
+        let fs = Arc::new(InMemoryFs::case_sensitive());
+        let clock = Arc::new(ManualClock::new());
+        let checked = Arc::new(Mutex::new(Vec::new()));
+        let health = HealthSource {
+            scheduler: HealthScheduler::with_probe(Arc::new(RecordingProbe(checked.clone()))).with_jitter(Duration::ZERO),
+            servers: vec![server("removed")],
+            annotations: AnnotationStore::default(),
+            history: HealthHistory::default(),
+        };
+        let mut sources = sources(fs.clone(), Some(health));
+        let refreshes = Arc::new(AtomicUsize::new(0));
+        sources.refresh = Some(Arc::new(ConfigRefresh(fs.clone(), refreshes.clone())));
+        let config = DaemonConfig { health_interval: Duration::from_secs(2), ..config() };
+        let daemon = DetectionDaemon::start_with_clock(config, sources, clock.clone());
+
+        // The watcher sees the new config and re-reads its servers before the next health run
+        clock.wait_for_sleepers(4).await;
+        fs.add_file(CONFIG, r#"{"servers": ["added"]}"#);
+        clock.advance(Duration::from_secs(1));
+        eventually(|| refreshes.load(Ordering::SeqCst) == 1).await;
+
+        clock.wait_for_sleepers(4).await;
+        clock.advance(Duration::from_secs(1));
+        eventually(|| !checked.lock().unwrap().is_empty()).await;
+        assert_eq!(*checked.lock().unwrap(), vec!["added".to_string()]);
+
+        daemon.shutdown(Duration::from_secs(1)).await;
+    }
+}
@@ -0,0 +1,172 @@
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::fmt;
+
+use super::profiles::ApplicationRegistry;
+
+/// One field that differs between a stored canonical registry and the current one
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RegistryFieldChange {
+    /// Dotted path such as `cursor.metadata.supports_cwd`; applications are keyed by id
+    pub path: String,
+    /// Value in the stored golden, None when the field was added
+    pub golden: Option<Value>,
+    /// Value in the current registry, None when the field was removed
+    pub current: Option<Value>,
+}
+
+impl fmt::Display for RegistryFieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.golden, &self.current) {
+            (Some(golden), Some(current)) => write!(f, "{}: {} -> {}", self.path, golden, current),
+            (None, Some(current)) => write!(f, "{}: added {}", self.path, current),
+            (Some(golden), None) => write!(f, "{}: removed {}", self.path, golden),
+            (None, None) => write!(f, "{}: unchanged", self.path),
+        }
+    }
+}
+
+impl ApplicationRegistry {
+    /// Serialize the registry in a canonical form suitable for committing as a golden file.
+    ///
+    /// Applications are ordered by id, object keys are sorted, serde defaults are written
+    /// out explicitly and `last_updated` is left out, so two builds with the same profiles
+    /// produce byte-identical output.
+    pub fn export_registry_canonical(&self) -> Result<String> {
+        let mut ids: Vec<&String> = self.applications.keys().collect();
+        ids.sort();
+
+        let applications = ids
+            .into_iter()
+            .map(|id| serde_json::to_value(&self.applications[id]).map(sort_keys))
+            .collect::<serde_json::Result<Vec<_>>>()?;
+
+        let mut canonical = Map::new();
+        canonical.insert("applications".to_string(), Value::Array(applications));
+        canonical.insert("version".to_string(), Value::String(self.metadata.version.clone()));
+
+        let mut output = serde_json::to_string_pretty(&Value::Object(canonical))?;
+        output.push('\n');
+        Ok(output)
+    }
+
+    /// Compare the registry against a stored canonical export, field by field.
+    ///
+    /// An empty result means the registry is unchanged.
+    pub fn check_registry_canonical(&self, golden: &str) -> Result<Vec<RegistryFieldChange>> {
+        let golden: Value = serde_json::from_str(golden).context("Stored canonical registry is not valid JSON")?;
+        let current: Value = serde_json::from_str(&self.export_registry_canonical()?)?;
+
+        let mut changes = Vec::new();
+        diff_values("", Some(&golden), Some(&current), &mut changes);
+        Ok(changes)
+    }
+}
+
+/// Rebuild every object with its keys in sorted order
+fn sort_keys(value: Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(String, Value)> = map.into_iter().collect();
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            Value::Object(entries.into_iter().map(|(key, value)| (key, sort_keys(value))).collect())
+        }
+        Value::Array(items) => Value::Array(items.into_iter().map(sort_keys).collect()),
+        other => other,
+    }
+}
+
+fn diff_values(path: &str, golden: Option<&Value>, current: Option<&Value>, changes: &mut Vec<RegistryFieldChange>) {
+    match (golden, current) {
+        (Some(Value::Object(golden)), Some(Value::Object(current))) => {
+            let mut keys: Vec<&String> = golden.keys().chain(current.keys()).collect();
+            keys.sort();
+            keys.dedup();
+            for key in keys {
+                diff_values(&join_path(path, key), golden.get(key), current.get(key), changes);
+            }
+        }
+        (Some(Value::Array(golden)), Some(Value::Array(current))) if path == "applications" => {
+            // Key applications by id so one added profile doesn't shift every later one
+            let golden = by_id(golden);
+            let current = by_id(current);
+            let mut ids: Vec<&str> = golden.iter().chain(current.iter()).map(|(id, _)| *id).collect();
+            ids.sort();
+            ids.dedup();
+            for id in ids {
+                diff_values(id, find_app(&golden, id), find_app(&current, id), changes);
+            }
+        }
+        (golden, current) if golden != current => changes.push(RegistryFieldChange {
+            path: path.to_string(),
+            golden: golden.cloned(),
+            current: current.cloned(),
+        }),
+        _ => {}
+    }
+}
+
+fn by_id(applications: &[Value]) -> Vec<(&str, &Value)> {
+    applications
+        .iter()
+        .map(|app| (app.get("id").and_then(Value::as_str).unwrap_or_default(), app))
+        .collect()
+}
+
+fn find_app<'a>(applications: &[(&str, &'a Value)], id: &str) -> Option<&'a Value> {
+    applications.iter().find(|(app_id, _)| *app_id == id).map(|(_, app)| *app)
+}
+
+fn join_path(path: &str, key: &str) -> String {
+    if path.is_empty() {
+        key.to_string()
+    } else {
+        format!("{}.{}", path, key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_builtin_registry_matches_golden() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/registry.json");
+        let registry = ApplicationRegistry::new();
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(&path, registry.export_registry_canonical().unwrap()).unwrap();
+            return;
+        }
+
+        let golden = std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("Missing golden file {}; run with UPDATE_GOLDEN=1", path.display()));
+        let changes = registry.check_registry_canonical(&golden).unwrap();
+        let report: Vec<String> = changes.iter().map(ToString::to_string).collect();
+        assert!(
+            changes.is_empty(),
+            "Built-in profiles changed; rerun with UPDATE_GOLDEN=1 if intended:\n{}",
+            report.join("\n")
+        );
+    }
+
+    #[test]
+    fn test_check_reports_field_level_changes() {
+        let registry = ApplicationRegistry::new();
+        let golden = registry.export_registry_canonical().unwrap();
+        assert_eq!(registry.export_registry_canonical().unwrap(), golden);
+
+        let mut changed = registry.clone();
+        changed.applications.get_mut("cursor").unwrap().metadata.supports_cwd = true;
+        changed.remove_application("warp");
+
+        let changes = changed.check_registry_canonical(&golden).unwrap();
+        let report: Vec<String> = changes.iter().map(ToString::to_string).collect();
+        assert_eq!(report.len(), 2);
+        assert_eq!(report[0], "cursor.metadata.supports_cwd: false -> true");
+        assert!(report[1].starts_with("warp: removed {"));
+    }
+}
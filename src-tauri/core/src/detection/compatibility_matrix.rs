@@ -0,0 +1,160 @@
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use super::profiles::{ApplicationProfile, ApplicationRegistry, ConfigFormat, DisableStrategy};
+use super::validator::ServerRuntime;
+
+/// What one client supports, flattened from its profile for docs and the website
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ClientCompatibility {
+    pub id: String,
+    pub name: String,
+    pub remote_servers: bool,
+    /// Server entries can set environment variables
+    pub env: bool,
+    /// Prompted `inputs` can be declared and referenced as `${input:id}`
+    pub inputs: bool,
+    pub cwd: bool,
+    /// Reads project-scoped config in addition to global settings
+    pub workspace_config: bool,
+    pub config_writes: bool,
+    pub extra_keys: bool,
+    pub disable_strategy: DisableStrategy,
+    /// Dotted path to the servers container, e.g. `mcp.servers`
+    pub servers_path: String,
+    pub format: String,
+    pub min_version: Option<String>,
+    pub platforms: Vec<String>,
+    pub unsupported_runtimes: Vec<ServerRuntime>,
+}
+
+impl ClientCompatibility {
+    fn from_profile(profile: &ApplicationProfile) -> Self {
+        let metadata = &profile.metadata;
+        Self {
+            id: profile.id.clone(),
+            name: profile.name.clone(),
+            remote_servers: metadata.supports_remote_servers,
+            env: metadata.supports_env,
+            inputs: metadata.supports_inputs,
+            cwd: metadata.supports_cwd,
+            workspace_config: metadata.supports_project_config,
+            config_writes: metadata.supports_config_writes,
+            extra_keys: metadata.tolerates_extra_keys,
+            disable_strategy: metadata.disable_strategy,
+            servers_path: profile.get_mcp_servers_path().join("."),
+            format: format_name(&profile.config_format).to_string(),
+            min_version: metadata.min_version.clone(),
+            platforms: metadata.platforms.clone(),
+            unsupported_runtimes: metadata.unsupported_runtimes.clone(),
+        }
+    }
+}
+
+/// Clients × features table, ordered by client id so regenerating it is stable
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CompatibilityMatrix {
+    pub clients: Vec<ClientCompatibility>,
+}
+
+/// Build the matrix from a registry, including any profiles the user added to it
+pub fn generate_compatibility_matrix(registry: &ApplicationRegistry) -> CompatibilityMatrix {
+    let mut clients: Vec<ClientCompatibility> =
+        registry.applications.values().map(ClientCompatibility::from_profile).collect();
+    clients.sort_by(|a, b| a.id.cmp(&b.id));
+    CompatibilityMatrix { clients }
+}
+
+impl CompatibilityMatrix {
+    /// Pretty JSON with a trailing newline, suitable for committing
+    pub fn to_json(&self) -> Result<String> {
+        let mut output = serde_json::to_string_pretty(self)?;
+        output.push('\n');
+        Ok(output)
+    }
+
+    /// Markdown table for the docs, one row per client
+    pub fn to_markdown(&self) -> String {
+        let mut output = String::from(
+            "| Client | Remote servers | env | inputs | cwd | Workspace config | Config writes | Disable | Servers path | Format |\n\
+             |--------|----------------|-----|--------|-----|------------------|---------------|---------|--------------|--------|\n",
+        );
+        let mark = |supported: bool| if supported { "✓" } else { "—" };
+
+        for client in &self.clients {
+            let disable = match client.disable_strategy {
+                DisableStrategy::DisabledFlag => "`disabled: true`",
+                DisableStrategy::EnabledFlag => "`enabled: false`",
+                DisableStrategy::MoveAside => "moved aside",
+            };
+            output.push_str(&format!(
+                "| {} | {} | {} | {} | {} | {} | {} | {} | `{}` | {} |\n",
+                client.name,
+                mark(client.remote_servers),
+                mark(client.env),
+                mark(client.inputs),
+                mark(client.cwd),
+                mark(client.workspace_config),
+                mark(client.config_writes),
+                disable,
+                client.servers_path,
+                client.format,
+            ));
+        }
+        output
+    }
+}
+
+fn format_name(format: &ConfigFormat) -> &str {
+    match format {
+        ConfigFormat::Json => "json",
+        ConfigFormat::Yaml => "yaml",
+        ConfigFormat::Toml => "toml",
+        ConfigFormat::Plist => "plist",
+        ConfigFormat::Custom(name) => name,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    #[test]
+    fn test_builtin_compatibility_matrix_matches_golden() {
+        let path = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/compatibility.json");
+        let matrix = generate_compatibility_matrix(&ApplicationRegistry::new());
+        let current = matrix.to_json().unwrap();
+
+        if std::env::var_os("UPDATE_GOLDEN").is_some() {
+            std::fs::write(&path, &current).unwrap();
+            return;
+        }
+
+        let golden = std::fs::read_to_string(&path)
+            .unwrap_or_else(|_| panic!("Missing golden file {}; run with UPDATE_GOLDEN=1", path.display()));
+        assert_eq!(current, golden, "Client capabilities changed; rerun with UPDATE_GOLDEN=1 if intended");
+
+        let markdown = matrix.to_markdown();
+        assert_eq!(markdown.lines().count(), matrix.clients.len() + 2);
+        assert!(markdown.contains("| Zed | "));
+        let vscode = matrix.clients.iter().find(|c| c.id == "vscode").unwrap();
+        assert!(vscode.env && vscode.inputs);
+        assert!(matrix.clients.iter().filter(|c| c.id != "vscode").all(|c| !c.inputs));
+    }
+
+    #[test]
+    fn test_matrix_includes_user_profiles() {
+        let mut registry = ApplicationRegistry::new();
+        let mut orbit = registry.get_application("cursor").unwrap().clone();
+        orbit.id = "orbit".to_string();
+        orbit.name = "Orbit".to_string();
+        orbit.metadata.supports_remote_servers = false;
+        registry.add_application(orbit);
+
+        let matrix = generate_compatibility_matrix(&registry);
+        let client = matrix.clients.iter().find(|c| c.id == "orbit").unwrap();
+        assert!(!client.remote_servers);
+        assert!(matrix.to_markdown().contains("| Orbit | — |"));
+    }
+}
@@ -1,9 +1,12 @@
+use crate::configuration::format_migration::{pending_conversion, FormatConversion};
 use crate::detection::profiles::{ApplicationProfile, ApplicationRegistry, DetectionMethod};
+use crate::filesystem::{FsProvider, PathUtils, RealFs};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
+use std::sync::Arc;
 
 /// Result of application detection with detailed status information
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -22,6 +25,9 @@ pub struct DetectionResult {
     pub messages: Vec<DetectionMessage>,
     /// Timestamp of detection
     pub detected_at: chrono::DateTime<chrono::Utc>,
+    /// Legacy config the installed version no longer reads, offered for conversion
+    #[serde(default)]
+    pub format_conversion: Option<FormatConversion>,
 }
 
 /// Paths found during application detection
@@ -70,6 +76,8 @@ pub struct ApplicationDetector {
     registry: ApplicationRegistry,
     /// Detection cache to avoid repeated checks
     detection_cache: HashMap<String, DetectionResult>,
+    /// Filesystem used for executable and config checks
+    fs: Arc<dyn FsProvider>,
 }
 
 impl ApplicationDetector {
@@ -78,6 +86,7 @@ impl ApplicationDetector {
         Ok(Self {
             registry: ApplicationRegistry::with_auto_load(),
             detection_cache: HashMap::new(),
+            fs: Arc::new(RealFs),
         })
     }
 
@@ -86,20 +95,17 @@ impl ApplicationDetector {
         Ok(Self {
             registry,
             detection_cache: HashMap::new(),
+            fs: Arc::new(RealFs),
         })
     }
 
-    /// Expand path with ~ to home directory
-    fn expand_path(&self, path: &str) -> Result<PathBuf> {
-        if path.starts_with('~') {
-            if let Some(home) = dirs::home_dir() {
-                Ok(home.join(&path[2..]))
-            } else {
-                Err(anyhow::anyhow!("Could not find home directory"))
-            }
-        } else {
-            Ok(PathBuf::from(path))
-        }
+    /// Create detector with custom registry and filesystem provider
+    pub fn with_fs(registry: ApplicationRegistry, fs: Arc<dyn FsProvider>) -> Result<Self> {
+        Ok(Self {
+            registry,
+            detection_cache: HashMap::new(),
+            fs,
+        })
     }
 
     /// Detect all known applications
@@ -228,17 +234,63 @@ impl ApplicationDetector {
             });
         }
 
+        let mut profile = profile.clone();
+        if profile.metadata.version.is_none() {
+            if let Some(executable) = &found_paths.executable {
+                profile.metadata.version = self.detect_version(executable).await;
+            }
+        }
+
+        let mut format_conversion = None;
+        if detected {
+            match pending_conversion(&profile, &*self.fs).await {
+                Ok(Some(conversion)) => {
+                    messages.push(DetectionMessage {
+                        level: MessageLevel::Warning,
+                        message: format!(
+                            "{} {} no longer reads {}; convert it to {:?} at {}",
+                            profile.name,
+                            profile.metadata.version.as_deref().unwrap_or_default(),
+                            conversion.from_path.display(),
+                            conversion.to_format,
+                            conversion.to_path.display()
+                        ),
+                        method: None,
+                    });
+                    format_conversion = Some(conversion);
+                }
+                Ok(None) => {}
+                Err(e) => messages.push(DetectionMessage {
+                    level: MessageLevel::Warning,
+                    message: format!("Could not check for a legacy config format: {}", e),
+                    method: None,
+                }),
+            }
+        }
+
         Ok(DetectionResult {
-            profile: profile.clone(),
+            profile,
             detected,
             detection_method,
             found_paths,
             confidence,
             messages,
             detected_at: chrono::Utc::now(),
+            format_conversion,
         })
     }
 
+    /// Installed version, from an app bundle's Info.plist or an editor extension's package.json
+    async fn detect_version(&self, executable: &Path) -> Option<String> {
+        if executable.extension().is_some_and(|ext| ext == "app") {
+            let plist = self.fs.read_to_string(&executable.join("Contents/Info.plist")).await.ok()?;
+            return plist_string(&plist, "CFBundleShortVersionString");
+        }
+        let manifest = self.fs.read_to_string(&executable.join("package.json")).await.ok()?;
+        let manifest: serde_json::Value = serde_json::from_str(&manifest).ok()?;
+        manifest.get("version")?.as_str().map(str::to_string)
+    }
+
     /// Detect application via macOS bundle lookup
     async fn detect_via_bundle_lookup(&self, profile: &ApplicationProfile) -> Result<(bool, Option<PathBuf>)> {
         // Use mdfind to search for the bundle ID
@@ -266,19 +318,17 @@ impl ApplicationDetector {
     }
 
     /// Detect application via executable file checks
+    ///
+    /// Paths may be glob patterns such as `/opt/homebrew/Cellar/*/bin/claude`.
     async fn detect_via_executable_check(&self, profile: &ApplicationProfile) -> Result<Option<PathBuf>> {
-        // Check primary executable paths
-        for path_str in &profile.executable_paths {
-            let resolved_path = self.expand_path(path_str)?;
-            if resolved_path.exists() {
-                return Ok(Some(resolved_path));
-            }
-        }
-
-        // Check alternative executable paths
-        for path_str in &profile.alt_executable_paths {
-            let resolved_path = self.expand_path(path_str)?;
-            if resolved_path.exists() {
+        // Check primary executable paths, then alternative ones
+        for path_str in profile.executable_paths.iter().chain(&profile.alt_executable_paths) {
+            let resolved_path = PathUtils::expand_tilde(path_str)?;
+            if is_glob_pattern(path_str) {
+                if let Some(found) = expand_glob(&resolved_path) {
+                    return Ok(Some(found));
+                }
+            } else if self.fs.exists(&resolved_path).await {
                 return Ok(Some(resolved_path));
             }
         }
@@ -287,22 +337,11 @@ impl ApplicationDetector {
     }
 
     /// Detect application via configuration file checks
+    ///
+    /// Paths may contain a versioned directory such as `IntelliJIdea*`, resolved to the newest release.
     async fn detect_via_config_check(&self, profile: &ApplicationProfile) -> Result<Option<PathBuf>> {
-        // Check primary config path
-        let resolved_path = self.expand_path(&profile.config_path)?;
-        if resolved_path.exists() {
-            return Ok(Some(resolved_path));
-        }
-
-        // Check alternative config paths
-        for path_str in &profile.alt_config_paths {
-            let resolved_path = self.expand_path(path_str)?;
-            if resolved_path.exists() {
-                return Ok(Some(resolved_path));
-            }
-        }
-
-        Ok(None)
+        // Check primary config path, then alternative ones
+        Ok(profile.find_config_file(&*self.fs).await)
     }
 
     /// Detect application via macOS Spotlight search
@@ -363,6 +402,36 @@ impl ApplicationDetector {
     }
 }
 
+/// String value following `<key>{key}</key>` in an XML property list
+fn plist_string(plist: &str, key: &str) -> Option<String> {
+    let after_key = &plist[plist.find(&format!("<key>{}</key>", key))?..];
+    let start = after_key.find("<string>")? + "<string>".len();
+    let end = after_key[start..].find("</string>")?;
+    Some(after_key[start..start + end].trim().to_string())
+}
+
+/// Whether a path contains glob metacharacters
+fn is_glob_pattern(path: &str) -> bool {
+    path.contains(['*', '?', '['])
+}
+
+/// Last match of a glob pattern in sorted order, which for versioned
+/// directories like Homebrew's Cellar is usually the newest install
+fn expand_glob(pattern: &std::path::Path) -> Option<PathBuf> {
+    let pattern = pattern.to_str()?;
+    match glob::glob(pattern) {
+        Ok(paths) => {
+            let mut matches: Vec<PathBuf> = paths.filter_map(|p| p.ok()).collect();
+            matches.sort();
+            matches.pop()
+        }
+        Err(e) => {
+            log::warn!("Invalid executable path pattern {}: {}", pattern, e);
+            None
+        }
+    }
+}
+
 impl Default for ApplicationDetector {
     fn default() -> Self {
         Self::new().expect("Failed to create default ApplicationDetector")
@@ -372,7 +441,7 @@ impl Default for ApplicationDetector {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::detection::profiles::{ApplicationCategory, ApplicationMetadata, ConfigFormat, DetectionStrategy};
+    use crate::detection::profiles::{ApplicationCategory, ApplicationMetadata, ConfigFormat, ConfigStructure, DetectionStrategy, FormatTransition};
 
     fn create_test_profile() -> ApplicationProfile {
         ApplicationProfile {
@@ -382,6 +451,7 @@ mod tests {
             config_path: "~/test/config.json".to_string(),
             alt_config_paths: vec!["~/.config/test/config.json".to_string()],
             config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
             executable_paths: vec!["/Applications/Test.app".to_string()],
             alt_executable_paths: vec!["~/Applications/Test.app".to_string()],
             detection_strategy: DetectionStrategy {
@@ -402,6 +472,7 @@ mod tests {
                 mcp_version: "1.0".to_string(),
                 notes: None,
                 requires_permissions: false,
+                ..Default::default()
             },
         }
     }
@@ -434,6 +505,81 @@ mod tests {
         assert!(!detection_result.detected); // Won't be detected since paths don't exist
     }
 
+    #[tokio::test]
+    async fn test_detect_application_with_in_memory_fs() {
+        let home = dirs::home_dir().unwrap();
+        let fs = crate::filesystem::InMemoryFs::case_insensitive()
+            .with_file(home.join(".config/test/config.json"), "{}");
+        fs.add_dir("/Applications/Test.app");
+
+        let mut registry = ApplicationRegistry::new();
+        registry.add_application(create_test_profile());
+        let mut detector = ApplicationDetector::with_fs(registry, Arc::new(fs)).unwrap();
+
+        let result = detector.detect_application("test-app").await.unwrap();
+        assert!(result.detected);
+        assert_eq!(result.found_paths.executable, Some(PathBuf::from("/Applications/Test.app")));
+        assert_eq!(result.found_paths.config_file, Some(home.join(".config/test/config.json")));
+    }
+
+    #[tokio::test]
+    async fn test_detection_offers_conversion_for_installed_version() {
+        let home = dirs::home_dir().unwrap();
+        let fs = crate::filesystem::InMemoryFs::case_insensitive()
+            .with_file(home.join(".config/test/config.json"), r#"{"mcpServers": {}}"#)
+            .with_file(
+                "/Applications/Test.app/Contents/Info.plist",
+                "<plist><dict>\n<key>CFBundleShortVersionString</key>\n<string>2.1.0</string>\n</dict></plist>",
+            );
+
+        let mut profile = create_test_profile();
+        profile.metadata.format_history = vec![
+            FormatTransition {
+                format: ConfigFormat::Json,
+                path: "~/.config/test/config.json".to_string(),
+                min_version: None,
+                schema: None,
+            },
+            FormatTransition {
+                format: ConfigFormat::Yaml,
+                path: "~/.config/test/config.yaml".to_string(),
+                min_version: Some("2.0".to_string()),
+                schema: None,
+            },
+        ];
+        let mut registry = ApplicationRegistry::new();
+        registry.add_application(profile);
+        let mut detector = ApplicationDetector::with_fs(registry, Arc::new(fs)).unwrap();
+
+        let result = detector.detect_application("test-app").await.unwrap();
+        assert_eq!(result.profile.metadata.version.as_deref(), Some("2.1.0"));
+        let conversion = result.format_conversion.expect("conversion offered");
+        assert_eq!(conversion.from_path, home.join(".config/test/config.json"));
+        assert_eq!(conversion.to_path, home.join(".config/test/config.yaml"));
+        assert!(result.messages.iter().any(|m| m.level == MessageLevel::Warning && m.message.contains("config.yaml")));
+    }
+
+    #[tokio::test]
+    async fn test_detect_executable_via_glob() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        for version in ["1.0.3", "1.2.0"] {
+            let bin = temp_dir.path().join("Cellar/claude").join(version).join("bin");
+            std::fs::create_dir_all(&bin).unwrap();
+            std::fs::write(bin.join("claude"), "").unwrap();
+        }
+
+        let mut profile = create_test_profile();
+        profile.executable_paths = vec!["/nonexistent/bin/claude".to_string()];
+        profile.alt_executable_paths = vec![format!("{}/Cellar/*/*/bin/claude", temp_dir.path().display())];
+        let detector = ApplicationDetector::with_registry(ApplicationRegistry::new()).unwrap();
+
+        let found = detector.detect_via_executable_check(&profile).await.unwrap();
+        assert_eq!(found, Some(temp_dir.path().join("Cellar/claude/1.2.0/bin/claude")));
+
+        profile.alt_executable_paths = vec![format!("{}/Cellar/*/bin/missing", temp_dir.path().display())];
+        assert_eq!(detector.detect_via_executable_check(&profile).await.unwrap(), None);
+    }
+
     #[tokio::test]
     async fn test_detection_result_serialization() {
         let profile = create_test_profile();
@@ -455,6 +601,7 @@ mod tests {
                 }
             ],
             detected_at: chrono::Utc::now(),
+            format_conversion: None,
         };
 
         let serialized = serde_json::to_string(&result).unwrap();
@@ -6,9 +6,19 @@ pub mod detector;
 pub mod registry;
 pub mod validator;
 pub mod reporter;
+pub mod suggestions;
+pub mod canonical;
+pub mod snapshot;
+pub mod compatibility_matrix;
+pub mod self_reference;
 
 pub use profiles::*;
 pub use detector::{ApplicationDetector, DetectionResult, DetectionPaths, DetectionMessage as DetectorMessage, MessageLevel as DetectorMessageLevel};
 pub use registry::*;
 pub use validator::*;
 pub use reporter::*;
+pub use suggestions::*;
+pub use canonical::*;
+pub use snapshot::ConfigSnapshot;
+pub use compatibility_matrix::{generate_compatibility_matrix, ClientCompatibility, CompatibilityMatrix};
+pub use self_reference::{inspect_wrapper, SelfReference, SelfReferenceGuard, WrapperScript};
@@ -0,0 +1,2554 @@
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use crate::adapters::declarative::{pointer_tokens, DeclarativeAdapterSpec};
+use crate::adapters::jetbrains_options::{resolve_versioned_path, OPTIONS_XML_FORMAT};
+use crate::adapters::warp_store::{SQLITE_FORMAT, WARP_DATABASE_PATHS};
+use crate::analysis::DetectedConfig;
+use crate::analysis::compatibility::parse_semver;
+use crate::filesystem::{ConfigFileService, FsProvider, PathUtils};
+use crate::detection::validator::{classify_runtime, ConfigValidationResult, ServerRef, ServerRuntime};
+
+/// Configuration structure type for MCP servers
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ConfigStructure {
+    /// Direct mcpServers object (e.g., Claude Desktop, Amazon Q)
+    DirectMcpServers,
+    /// Nested mcp.servers object (e.g., Cursor, Warp)
+    NestedMcpServers,
+    /// Servers object at a custom key path, dot-separated (`chat.mcp.servers`)
+    /// or a JSON pointer (`/settings/mcp/servers`)
+    Custom(String),
+}
+
+impl ConfigStructure {
+    /// Split a custom key path into the keys leading to the servers object
+    ///
+    /// Paths starting with `/` are JSON pointers, with `~1` and `~0` escaping `/` and `~`;
+    /// anything else is dot-separated.
+    pub fn parse_custom_path(path: &str) -> Result<Vec<String>, String> {
+        if path.is_empty() || path == "/" {
+            return Err("servers path is empty".to_string());
+        }
+        let segments = if path.starts_with('/') {
+            if path.split('~').skip(1).any(|rest| !rest.starts_with(['0', '1'])) {
+                return Err(format!("servers path '{}' has an invalid JSON pointer escape; use ~0 for '~' and ~1 for '/'", path));
+            }
+            pointer_tokens(path)
+        } else {
+            path.split('.').map(String::from).collect()
+        };
+        if segments.iter().any(String::is_empty) {
+            return Err(format!("servers path '{}' has an empty segment", path));
+        }
+        Ok(segments)
+    }
+}
+
+/// Where an application keeps its servers object inside a config.
+///
+/// A dotted custom path such as `chat.mcp.servers` names a settings key, which settings
+/// files like VS Code's store flat as `"chat.mcp.servers"`. The flat key is tried first and
+/// is what gets created in a config that has neither spelling; nested objects are only
+/// used when the config already has them.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServersPath {
+    /// Keys leading from the root to the servers object
+    pub segments: Vec<String>,
+    /// The same location spelled as one settings key, for dotted custom paths
+    pub flat_key: Option<String>,
+}
+
+impl ServersPath {
+    /// A nested location with no flat spelling
+    pub fn nested(segments: Vec<String>) -> Self {
+        Self { segments, flat_key: None }
+    }
+
+    /// The keys leading to the servers object in `config`
+    pub fn keys_in(&self, config: &serde_json::Value) -> Vec<String> {
+        match &self.flat_key {
+            Some(flat) if config.get(flat).is_some() || self.walk(config).is_none() => vec![flat.clone()],
+            _ => self.segments.clone(),
+        }
+    }
+
+    /// The value at the servers location, whatever its type
+    pub fn lookup<'a>(&self, config: &'a serde_json::Value) -> Option<&'a serde_json::Value> {
+        self.flat_key
+            .as_ref()
+            .and_then(|flat| config.get(flat))
+            .or_else(|| self.walk(config))
+    }
+
+    /// The servers object in `config`, if it has one
+    pub fn get<'a>(&self, config: &'a serde_json::Value) -> Option<&'a serde_json::Map<String, serde_json::Value>> {
+        self.lookup(config).and_then(|servers| servers.as_object())
+    }
+
+    /// The servers object in `config`, if it has one, for editing
+    pub fn get_mut<'a>(&self, config: &'a mut serde_json::Value) -> Option<&'a mut serde_json::Map<String, serde_json::Value>> {
+        self.keys_in(config)
+            .iter()
+            .try_fold(config, |node, key| node.get_mut(key))
+            .and_then(|servers| servers.as_object_mut())
+    }
+
+    /// The servers object in `config`, creating it and replacing anything that isn't an
+    /// object on the way. `config` itself must be an object.
+    pub fn get_or_insert<'a>(&self, config: &'a mut serde_json::Value) -> &'a mut serde_json::Map<String, serde_json::Value> {
+        let mut container = config;
+        for key in self.keys_in(container) {
+            if !container.get(&key).is_some_and(|c| c.is_object()) {
+                container[&key] = serde_json::json!({});
+            }
+            container = &mut container[&key];
+        }
+        container.as_object_mut().expect("servers container was just made an object")
+    }
+
+    fn walk<'a>(&self, config: &'a serde_json::Value) -> Option<&'a serde_json::Value> {
+        self.segments.iter().try_fold(config, |node, key| node.get(key))
+    }
+}
+
+/// How an application switches a server off while keeping its entry
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub enum DisableStrategy {
+    /// `"disabled": true` on the entry (e.g., Claude Desktop, Cursor, Amazon Q)
+    #[default]
+    DisabledFlag,
+    /// `"enabled": false` on the entry (e.g., Zed)
+    EnabledFlag,
+    /// No per-entry switch; the entry is taken out of the config and kept aside (e.g., VS Code)
+    MoveAside,
+}
+
+/// How an application handles a client-specific per-server option
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExtensionRule {
+    /// The client understands the option as-is
+    Preserve,
+    /// The client supports the same behavior under a different key
+    Translate(String),
+    /// The client would misread the option, so it is left out
+    Strip,
+}
+
+/// Represents a known MCP-enabled application with detection patterns
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApplicationProfile {
+    /// Unique identifier for the application
+    pub id: String,
+    /// Human-readable name
+    pub name: String,
+    /// macOS bundle identifier
+    pub bundle_id: String,
+    /// Primary configuration file path (with ~ expansion support)
+    pub config_path: String,
+    /// Alternative configuration paths to check
+    pub alt_config_paths: Vec<String>,
+    /// Expected configuration file format
+    pub config_format: ConfigFormat,
+    /// Configuration structure type
+    pub config_structure: ConfigStructure,
+    /// Standard installation paths to check
+    pub executable_paths: Vec<String>,
+    /// Alternative installation paths
+    pub alt_executable_paths: Vec<String>,
+    /// Detection strategy preferences
+    pub detection_strategy: DetectionStrategy,
+    /// Application-specific metadata
+    pub metadata: ApplicationMetadata,
+}
+
+impl ApplicationProfile {
+    /// Check if this application uses nested mcp.servers structure
+    pub fn uses_nested_config(&self) -> bool {
+        matches!(self.active_config_structure(), ConfigStructure::NestedMcpServers)
+    }
+
+    /// Check whether an analyzed server can be installed into this application.
+    ///
+    /// Every reason it cannot is returned, so the UI can explain why the option is unavailable.
+    pub fn can_install(&self, config: &DetectedConfig) -> Result<(), Vec<String>> {
+        let mut reasons = Vec::new();
+        let remote = config.is_remote();
+
+        if remote && !self.metadata.supports_remote_servers {
+            let kind = match config.server_type.as_str() {
+                "stdio" => "a remote".to_string(),
+                other => format!("an {}", other.to_uppercase()),
+            };
+            reasons.push(format!(
+                "{} only supports local stdio servers, but {} is {} server",
+                self.name, config.name, kind
+            ));
+        }
+
+        if config.command.trim().is_empty() {
+            if !remote {
+                reasons.push(format!("{} has no command to launch", config.name));
+            }
+        } else {
+            let runtime = classify_runtime(&config.command);
+            if self.metadata.unsupported_runtimes.contains(&runtime) {
+                reasons.push(format!(
+                    "{} cannot launch {:?} servers (command '{}')",
+                    self.name, runtime, config.command
+                ));
+            }
+        }
+
+        if reasons.is_empty() {
+            Ok(())
+        } else {
+            Err(reasons)
+        }
+    }
+
+    /// Config file locations to check on the platform we are running on, primary first
+    pub fn get_config_paths_for_current_platform(&self) -> Vec<String> {
+        self.config_paths_for_platform(current_platform())
+    }
+
+    /// Point the profile at an explicit config file.
+    ///
+    /// An explicit path wins on every platform, so the built-in per-platform table is
+    /// dropped; `alt_config_paths` stay as fallbacks.
+    pub fn set_config_path(&mut self, path: impl Into<String>) {
+        self.config_path = path.into();
+        self.metadata.config_paths_by_platform.clear();
+    }
+
+    /// Config file locations for a platform, falling back to `config_path` and `alt_config_paths`
+    pub fn config_paths_for_platform(&self, platform: &str) -> Vec<String> {
+        match self.metadata.config_paths_by_platform.get(platform) {
+            Some(paths) if !paths.is_empty() => paths.clone(),
+            _ => std::iter::once(&self.config_path).chain(&self.alt_config_paths).cloned().collect(),
+        }
+    }
+
+    /// This platform's config paths with `~`, `%VAR%` and `$XDG_CONFIG_HOME` expanded, primary first
+    pub fn expanded_config_paths(&self) -> Vec<PathBuf> {
+        self.get_config_paths_for_current_platform()
+            .iter()
+            .filter_map(|path| PathUtils::expand_path(path))
+            .collect()
+    }
+
+    /// The primary config path for this platform, expanded; the file may not exist yet
+    pub fn resolved_config_path(&self) -> PathBuf {
+        let primary = self.get_config_paths_for_current_platform().into_iter().next().unwrap_or_default();
+        PathUtils::expand_path(&primary).unwrap_or_else(|| PathBuf::from(primary))
+    }
+
+    /// The first of this platform's config files that exists, falling back from the primary path
+    /// through the alternates. Versioned directories such as `IntelliJIdea*` resolve to the newest.
+    pub async fn find_config_file(&self, fs: &dyn FsProvider) -> Option<PathBuf> {
+        for path in self.expanded_config_paths() {
+            if let Some(resolved) = resolve_versioned_path(fs, &path).await {
+                if fs.exists(&resolved).await {
+                    return Some(resolved);
+                }
+            }
+        }
+        None
+    }
+
+    /// Get the JSON path to MCP servers configuration, split into nested keys
+    ///
+    /// Custom structures name a dotted path such as `chat.mcp.servers` or a JSON pointer.
+    /// Registries reject invalid custom paths when loading, so the `mcpServers` fallback
+    /// only applies to profiles built in code. To find the servers in an actual config, use
+    /// [`servers_path`](Self::servers_path), which also knows the flat settings-key spelling.
+    pub fn get_mcp_servers_path(&self) -> Vec<String> {
+        match self.active_config_structure() {
+            ConfigStructure::DirectMcpServers => vec!["mcpServers".to_string()],
+            ConfigStructure::NestedMcpServers => vec!["mcp".to_string(), "servers".to_string()],
+            ConfigStructure::Custom(path) => ConfigStructure::parse_custom_path(path).unwrap_or_else(|e| {
+                log::warn!("Application '{}': {}; using mcpServers", self.id, e);
+                vec!["mcpServers".to_string()]
+            }),
+        }
+    }
+
+    /// Where this application keeps its servers object, see [`ServersPath`]
+    pub fn servers_path(&self) -> ServersPath {
+        let segments = self.get_mcp_servers_path();
+        let flat_key = match self.active_config_structure() {
+            ConfigStructure::Custom(path) if !path.starts_with('/') && segments.len() > 1 => Some(path.clone()),
+            _ => None,
+        };
+        ServersPath { segments, flat_key }
+    }
+
+    /// Check the parts of a profile that deserialize but can't be used: declarative adapter
+    /// specs and custom servers paths, including those in `structure_history`
+    pub fn validate_definition(&self) -> anyhow::Result<()> {
+        if let Some(spec) = &self.metadata.declarative_adapter {
+            spec.validate()
+                .with_context(|| format!("Invalid declarative adapter for {}", self.id))?;
+        }
+        let structures = std::iter::once(&self.config_structure)
+            .chain(self.metadata.structure_history.iter().map(|entry| &entry.structure));
+        for structure in structures {
+            if let ConfigStructure::Custom(path) = structure {
+                ConfigStructure::parse_custom_path(path)
+                    .map_err(|e| anyhow::anyhow!("Invalid config structure for {}: {}", self.id, e))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether the declared structure finds at least one server entry in a real config sample.
+    ///
+    /// Used by tests to catch profiles whose servers path no longer matches what the app writes.
+    pub fn structure_matches_sample(&self, sample: &serde_json::Value) -> bool {
+        self.servers_path()
+            .get(sample)
+            .is_some_and(|servers| {
+                servers.iter().any(|(name, entry)| {
+                    !crate::detection::is_metadata_key(name)
+                        && (entry.get("command").is_some() || entry.get("url").is_some())
+                })
+            })
+    }
+
+    /// The config structure read by the detected application version
+    pub fn active_config_structure(&self) -> &ConfigStructure {
+        self.structure_for_version(self.metadata.version.as_deref())
+    }
+
+    /// The config structure an application version reads.
+    ///
+    /// Picks the newest `structure_history` entry the version has reached; unknown
+    /// versions and profiles without history use `config_structure`.
+    pub fn structure_for_version(&self, version: Option<&str>) -> &ConfigStructure {
+        let Some(installed) = version.and_then(parse_semver) else {
+            return &self.config_structure;
+        };
+        self.metadata
+            .structure_history
+            .iter()
+            .rev()
+            .find(|entry| match &entry.min_version {
+                Some(min_version) => parse_semver(min_version).is_some_and(|min| installed >= min),
+                None => true,
+            })
+            .map_or(&self.config_structure, |entry| &entry.structure)
+    }
+
+    /// Get the project-scoped config file for a workspace, if the application reads one
+    ///
+    /// Returns None for applications that only support global settings.
+    pub fn workspace_config_path(&self, workspace_root: &std::path::Path) -> Option<std::path::PathBuf> {
+        if !self.metadata.supports_project_config {
+            return None;
+        }
+
+        let relative = match self.id.as_str() {
+            "claude-code" => ".mcp.json",
+            "cursor" => ".cursor/mcp.json",
+            "vscode" => ".vscode/mcp.json",
+            "zed" => ".zed/settings.json",
+            "continue-dev" => ".continue/config.json",
+            _ => return None,
+        };
+
+        Some(workspace_root.join(relative))
+    }
+
+    /// Find the nearest existing project-scoped config, searching `start` and its ancestors
+    ///
+    /// Mirrors how clients locate the project root, e.g. Claude Code's `.mcp.json`.
+    pub fn find_workspace_config(&self, start: &std::path::Path) -> Option<std::path::PathBuf> {
+        start
+            .ancestors()
+            .filter_map(|dir| self.workspace_config_path(dir))
+            .find(|path| path.is_file())
+    }
+
+    /// Validate that a config file matches the declared structure
+    ///
+    /// Returns a result with validation details:
+    /// - Ok(()) if structure matches
+    /// - Err(message) with description if mismatch detected
+    pub fn validate_config_structure(&self, config: &serde_json::Value) -> Result<(), String> {
+        let declared: Result<(), String> = match self.active_config_structure() {
+            ConfigStructure::DirectMcpServers => {
+                // Should have mcpServers at root level
+                let has_direct = config.get("mcpServers").is_some();
+                let has_nested = config.get("mcp")
+                    .and_then(|m| m.get("servers"))
+                    .is_some();
+
+                if !has_direct && has_nested {
+                    return Err(format!(
+                        "Application '{}' is configured as DirectMcpServers but config uses nested mcp.servers structure",
+                        self.name
+                    ));
+                }
+
+                if !has_direct && !has_nested {
+                    // Neither structure found - might be empty config
+                    log::debug!("No MCP servers configuration found in {} config", self.name);
+                }
+
+                Ok(())
+            }
+            ConfigStructure::NestedMcpServers => {
+                // Should have mcp.servers nested structure
+                let has_nested = config.get("mcp")
+                    .and_then(|m| m.get("servers"))
+                    .is_some();
+                let has_direct = config.get("mcpServers").is_some();
+
+                if !has_nested && has_direct {
+                    return Err(format!(
+                        "Application '{}' is configured as NestedMcpServers but config uses direct mcpServers structure",
+                        self.name
+                    ));
+                }
+
+                if !has_nested && !has_direct {
+                    // Neither structure found - might be empty config
+                    log::debug!("No MCP servers configuration found in {} config", self.name);
+                }
+
+                Ok(())
+            }
+            ConfigStructure::Custom(raw) => {
+                ConfigStructure::parse_custom_path(raw)
+                    .map_err(|e| format!("Application '{}' has an invalid custom structure: {}", self.name, e))?;
+
+                // The same lookup extraction and writes use, flat settings keys included
+                match self.servers_path().lookup(config) {
+                    Some(servers) if servers.is_object() => Ok(()),
+                    Some(_) => Err(format!(
+                        "Application '{}' expects an object of MCP servers at '{}', but found another value",
+                        self.name, raw
+                    )),
+                    None => Err(format!(
+                        "Application '{}' expects MCP servers at '{}', but the config has no such path",
+                        self.name, raw
+                    )),
+                }
+            }
+        };
+        declared?;
+
+        // Check for common pasting mistakes inside the declared structure
+        if let Some(issue) = self.detect_structure_issues(config).first() {
+            return Err(format!(
+                "[{}] Application '{}': {}",
+                issue.code(),
+                self.name,
+                issue.description()
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// Detect malformed server containers that can be repaired automatically
+    pub fn detect_structure_issues(&self, config: &serde_json::Value) -> Vec<StructureIssue> {
+        let mut issues = Vec::new();
+        let path = self.servers_path().keys_in(config);
+        let segments = self.get_mcp_servers_path();
+        let container_key = segments.last().map_or("mcpServers", String::as_str);
+
+        // A non-object on the way to the container, such as `"mcp": null`, hides it entirely
+        let mut container = Some(config);
+        for (depth, key) in path.iter().enumerate() {
+            container = container.and_then(|c| c.get(key));
+            if let Some(value) = container.filter(|v| depth + 1 < path.len() && !v.is_object()) {
+                issues.push(StructureIssue::ServersNotObject(json_type_name(value).to_string()));
+                container = None;
+                break;
+            }
+        }
+
+        match container {
+            Some(serde_json::Value::Array(_)) => issues.push(StructureIssue::ServersArray),
+            Some(value @ (serde_json::Value::Null | serde_json::Value::String(_) | serde_json::Value::Number(_) | serde_json::Value::Bool(_))) => {
+                issues.push(StructureIssue::ServersNotObject(json_type_name(value).to_string()))
+            }
+            Some(serde_json::Value::Object(servers)) => {
+                let double_nested = [container_key, "mcpServers"]
+                    .iter()
+                    .any(|key| servers.get(*key).is_some_and(is_pasted_servers_object));
+                if double_nested {
+                    issues.push(StructureIssue::DoubleNested);
+                }
+            }
+            _ => {}
+        }
+
+        if let Some(root) = config.as_object() {
+            let mut misplaced: Vec<String> = root
+                .iter()
+                .filter(|(key, value)| key.as_str() != path[0] && is_server_entry(value))
+                .map(|(key, _)| key.clone())
+                .collect();
+            misplaced.sort();
+            if !misplaced.is_empty() {
+                issues.push(StructureIssue::ServersAtRoot(misplaced));
+            }
+        }
+
+        issues
+    }
+
+    /// Apply the automatic repair for every detected structure issue.
+    ///
+    /// Returns the issues that were repaired.
+    pub fn repair_config_structure(&self, config: &mut serde_json::Value) -> Vec<StructureIssue> {
+        let issues = self.detect_structure_issues(config);
+        issues
+            .into_iter()
+            .filter(|issue| self.repair_structure_issue(config, issue))
+            .collect()
+    }
+
+    /// Repair a single structure issue in place, returning whether anything changed
+    pub fn repair_structure_issue(&self, config: &mut serde_json::Value, issue: &StructureIssue) -> bool {
+        if !config.is_object() {
+            return false;
+        }
+
+        let path = self.servers_path().keys_in(config);
+        let segments = self.get_mcp_servers_path();
+        let container_key = segments.last().map_or("mcpServers", String::as_str);
+
+        // Root-level entries are moved before the container is looked up
+        let mut moved = serde_json::Map::new();
+        if let StructureIssue::ServersAtRoot(names) = issue {
+            if let Some(root) = config.as_object_mut() {
+                for name in names {
+                    if let Some(entry) = root.remove(name) {
+                        moved.insert(name.clone(), entry);
+                    }
+                }
+            }
+        }
+
+        // Anything other than an object (or an array still to be converted) is replaced by an
+        // empty servers object; sibling keys are left alone
+        let mut container = &mut *config;
+        for (depth, key) in path.iter().enumerate() {
+            let keep = match container.get(key) {
+                Some(value) if depth + 1 < path.len() => value.is_object(),
+                Some(value) => value.is_object() || value.is_array(),
+                None => false,
+            };
+            if !keep {
+                container[key] = serde_json::json!({});
+            }
+            container = &mut container[key];
+        }
+
+        match issue {
+            StructureIssue::ServersNotObject(_) => true,
+            StructureIssue::DoubleNested => {
+                let Some(servers) = container.as_object_mut() else {
+                    return false;
+                };
+                let Some(nested_key) = [container_key, "mcpServers"]
+                    .into_iter()
+                    .find(|key| servers.get(*key).is_some_and(is_pasted_servers_object))
+                else {
+                    return false;
+                };
+                let Some(serde_json::Value::Object(inner)) = servers.remove(nested_key) else {
+                    return false;
+                };
+                for (name, entry) in inner {
+                    servers.insert(name, entry);
+                }
+                true
+            }
+            StructureIssue::ServersAtRoot(_) => {
+                let Some(servers) = container.as_object_mut() else {
+                    return false;
+                };
+                let changed = !moved.is_empty();
+                for (name, entry) in moved {
+                    servers.entry(name).or_insert(entry);
+                }
+                changed
+            }
+            StructureIssue::ServersArray => {
+                let Some(entries) = container.as_array().cloned() else {
+                    return false;
+                };
+                let mut servers = serde_json::Map::new();
+                for (index, mut entry) in entries.into_iter().enumerate() {
+                    let name = entry
+                        .as_object_mut()
+                        .and_then(|obj| obj.remove("name"))
+                        .and_then(|n| n.as_str().map(|s| s.to_string()))
+                        .unwrap_or_else(|| format!("server-{}", index + 1));
+                    servers.insert(name, entry);
+                }
+                *container = serde_json::Value::Object(servers);
+                true
+            }
+        }
+    }
+
+    /// Merge server entries whose names differ only in case.
+    ///
+    /// The most complete entry of each set is kept under its own name, and keys it lacks
+    /// are filled in from the others. The kept entry's enabled state is never changed;
+    /// duplicates that disagree with it are listed as conflicts. The caller is responsible
+    /// for backing up the file before writing the result.
+    pub fn dedupe_servers(&self, config: &mut serde_json::Value) -> DedupeReport {
+        let Some(servers) = self.servers_path().get_mut(config) else {
+            return DedupeReport::default();
+        };
+
+        let mut by_folded_name: HashMap<String, Vec<String>> = HashMap::new();
+        for name in servers.keys() {
+            by_folded_name.entry(name.to_lowercase()).or_default().push(name.clone());
+        }
+
+        let mut report = DedupeReport::default();
+        for mut names in by_folded_name.into_values().filter(|names| names.len() > 1) {
+            // Most complete first; ties keep the name that sorts first
+            names.sort_by(|a, b| {
+                entry_completeness(&servers[b]).cmp(&entry_completeness(&servers[a])).then_with(|| a.cmp(b))
+            });
+            let (keep, duplicates) = names.split_first().expect("group has duplicates");
+
+            let kept_enabled = entry_enabled(&servers[keep]);
+
+            for duplicate in duplicates {
+                report.removed.push(duplicate.clone());
+                let Some(serde_json::Value::Object(extra)) = servers.remove(duplicate) else {
+                    continue;
+                };
+                if entry_enabled(&serde_json::Value::Object(extra.clone())) != kept_enabled {
+                    report.enabled_conflicts.push(duplicate.clone());
+                }
+                if let Some(kept) = servers.get_mut(keep).and_then(|e| e.as_object_mut()) {
+                    for (key, value) in extra {
+                        if !ENABLED_STATE_KEYS.contains(&key.as_str()) {
+                            kept.entry(key).or_insert(value);
+                        }
+                    }
+                }
+            }
+        }
+
+        report.removed.sort();
+        report.enabled_conflicts.sort();
+        report
+    }
+
+    /// Remove a server entry by name from the application's server container.
+    ///
+    /// Returns whether an entry was removed; the container itself is kept even when it
+    /// becomes empty.
+    pub fn remove_server(&self, config: &mut serde_json::Value, name: &str) -> bool {
+        self.servers_path()
+            .get_mut(config)
+            .is_some_and(|servers| servers.remove(name).is_some())
+    }
+
+    /// Dry run of [`remove_server`](Self::remove_server): the config that removing `name`
+    /// would produce, and whether anything would be removed. Nothing is written to disk.
+    pub fn preview_remove(&self, current: &serde_json::Value, name: &str) -> (serde_json::Value, bool) {
+        let mut preview = current.clone();
+        let removed = self.remove_server(&mut preview, name);
+        (preview, removed)
+    }
+}
+
+/// Outcome of [`ApplicationProfile::dedupe_servers`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DedupeReport {
+    /// Names of the removed duplicates
+    pub removed: Vec<String>,
+    /// Removed duplicates whose enabled state differed from the kept entry
+    pub enabled_conflicts: Vec<String>,
+}
+
+/// Entry keys that switch a server on or off, never copied between duplicates
+const ENABLED_STATE_KEYS: [&str; 2] = ["disabled", "enabled"];
+
+/// Whether a server entry is switched on, under either flag convention
+fn entry_enabled(entry: &serde_json::Value) -> bool {
+    let disabled = entry.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false);
+    let enabled = entry.get("enabled").and_then(|e| e.as_bool()).unwrap_or(true);
+    enabled && !disabled
+}
+
+/// Number of populated fields in a server entry, used to pick which duplicate to keep
+fn entry_completeness(entry: &serde_json::Value) -> usize {
+    entry.as_object().map_or(0, |fields| {
+        fields
+            .values()
+            .filter(|value| match value {
+                serde_json::Value::Null => false,
+                serde_json::Value::String(s) => !s.is_empty(),
+                serde_json::Value::Array(items) => !items.is_empty(),
+                serde_json::Value::Object(map) => !map.is_empty(),
+                _ => true,
+            })
+            .count()
+    })
+}
+
+fn json_type_name(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "an array",
+        serde_json::Value::Object(_) => "an object",
+    }
+}
+
+/// Check whether a JSON value looks like a single MCP server entry
+fn is_server_entry(value: &serde_json::Value) -> bool {
+    value.get("command").map(|c| c.is_string()).unwrap_or(false)
+        || value.get("url").map(|u| u.is_string()).unwrap_or(false)
+}
+
+/// Whether a value inside the servers object is a whole pasted servers object rather than a
+/// server that happens to share the container's name: it has no `command`, `url` or `type` of
+/// its own, and every value in it is a server entry.
+fn is_pasted_servers_object(value: &serde_json::Value) -> bool {
+    value.as_object().is_some_and(|inner| {
+        !inner.is_empty()
+            && !["command", "url", "type"].iter().any(|key| inner.contains_key(*key))
+            && inner.values().all(is_server_entry)
+    })
+}
+
+/// Malformed server configuration layouts with an automatic repair
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum StructureIssue {
+    /// The whole `{"mcpServers": {...}}` object was pasted inside the servers object
+    DoubleNested,
+    /// Server entries placed at the config root without the servers wrapper
+    ServersAtRoot(Vec<String>),
+    /// Servers stored as an array instead of a name-keyed object
+    ServersArray,
+    /// The servers value (or an object on the way to it) is null, a string, a number or a
+    /// boolean; holds the JSON type found
+    ServersNotObject(String),
+}
+
+impl StructureIssue {
+    /// Stable issue code for the UI and reports
+    pub fn code(&self) -> &'static str {
+        match self {
+            StructureIssue::DoubleNested => "MCP_DOUBLE_NESTED",
+            StructureIssue::ServersAtRoot(_) => "MCP_SERVER_AT_ROOT",
+            StructureIssue::ServersArray => "MCP_SERVERS_ARRAY",
+            StructureIssue::ServersNotObject(_) => "MCP_SERVERS_NOT_OBJECT",
+        }
+    }
+
+    /// Human-readable description of the issue
+    pub fn description(&self) -> String {
+        match self {
+            StructureIssue::DoubleNested => {
+                "servers object contains a second servers wrapper (pasted one level too deep)".to_string()
+            }
+            StructureIssue::ServersAtRoot(names) => {
+                format!("server entries found at the config root: {}", names.join(", "))
+            }
+            StructureIssue::ServersArray => {
+                "servers are stored as an array instead of an object keyed by name".to_string()
+            }
+            StructureIssue::ServersNotObject(found) => {
+                format!("servers setting is {} instead of an object; no servers are loaded from it", found)
+            }
+        }
+    }
+}
+
+/// Configuration file formats supported by MCP applications
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ConfigFormat {
+    Json,
+    Yaml,
+    Toml,
+    Plist,
+    Custom(String),
+}
+
+/// Detection strategies for finding applications
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct DetectionStrategy {
+    /// Check for bundle ID using macOS APIs
+    pub use_bundle_lookup: bool,
+    /// Check for executable files in standard paths
+    pub use_executable_check: bool,
+    /// Check for configuration files
+    pub use_config_check: bool,
+    /// Use Spotlight/mdfind for advanced searching
+    pub use_spotlight: bool,
+    /// Priority order for detection methods
+    pub priority_order: Vec<DetectionMethod>,
+}
+
+impl DetectionStrategy {
+    /// Sensible strategy for a new profile of this category.
+    ///
+    /// Command-line tools have no app bundle and are found through their config and
+    /// executable; GUI apps are found by bundle first.
+    pub fn default_for_category(category: &ApplicationCategory) -> DetectionStrategy {
+        match category {
+            ApplicationCategory::Terminal | ApplicationCategory::DeveloperTool => DetectionStrategy {
+                use_bundle_lookup: false,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: false,
+                priority_order: vec![
+                    DetectionMethod::ConfigCheck,
+                    DetectionMethod::ExecutableCheck,
+                ],
+            },
+            _ => DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+        }
+    }
+}
+
+/// Individual detection methods
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum DetectionMethod {
+    BundleLookup,
+    ExecutableCheck,
+    ConfigCheck,
+    SpotlightSearch,
+}
+
+/// Application-specific metadata
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ApplicationMetadata {
+    /// Application version (if detectable)
+    pub version: Option<String>,
+    /// Developer/publisher name
+    pub developer: String,
+    /// Application category
+    pub category: ApplicationCategory,
+    /// MCP protocol version supported (defaults to "1.0")
+    #[serde(default = "default_mcp_version")]
+    pub mcp_version: String,
+    /// Additional notes or special handling requirements
+    pub notes: Option<String>,
+    /// Whether this application requires special permissions
+    #[serde(default)]
+    pub requires_permissions: bool,
+    /// Year the application was first released
+    #[serde(default)]
+    pub release_year: Option<u32>,
+    /// Official documentation URL
+    #[serde(default)]
+    pub official_docs_url: Option<String>,
+    /// MCP configuration documentation URL
+    #[serde(default)]
+    pub config_docs_url: Option<String>,
+    /// Support/help URL
+    #[serde(default)]
+    pub support_url: Option<String>,
+    /// Software license
+    #[serde(default)]
+    pub license: Option<String>,
+    /// Supported platforms
+    #[serde(default)]
+    pub platforms: Vec<String>,
+    /// Minimum version required for MCP support
+    #[serde(default)]
+    pub min_version: Option<String>,
+    /// Whether the client ignores unknown keys inside server entries
+    #[serde(default)]
+    pub tolerates_extra_keys: bool,
+    /// Whether the client can connect to remote servers by url (SSE or streamable HTTP)
+    #[serde(default)]
+    pub supports_remote_servers: bool,
+    /// Runtimes the application cannot launch servers with, such as Docker in sandboxed builds
+    #[serde(default)]
+    pub unsupported_runtimes: Vec<ServerRuntime>,
+    /// Whether server entries can set a working directory
+    #[serde(default)]
+    pub supports_cwd: bool,
+    /// Whether server entries can set environment variables
+    #[serde(default = "default_supports_env")]
+    pub supports_env: bool,
+    /// Whether the config can declare prompted `inputs`, referenced from entries as `${input:id}`
+    #[serde(default)]
+    pub supports_inputs: bool,
+    /// Whether the application reads project-scoped config in addition to global settings
+    #[serde(default = "default_supports_project_config")]
+    pub supports_project_config: bool,
+    /// Whether we can write server entries to the application's config.
+    /// When false, servers are added by pasting a snippet into the application.
+    #[serde(default = "default_supports_config_writes")]
+    pub supports_config_writes: bool,
+    /// Whether the application reads its JSON config as JSONC, so comments can be kept in it
+    #[serde(default)]
+    pub accepts_jsonc: bool,
+    /// Config formats and locations the application has used, oldest first
+    #[serde(default)]
+    pub format_history: Vec<FormatTransition>,
+    /// Server container keys the application has used, oldest first
+    #[serde(default)]
+    pub structure_history: Vec<StructureTransition>,
+    /// How a server entry is switched off without losing it
+    #[serde(default)]
+    pub disable_strategy: DisableStrategy,
+    /// Per-server options the client handles specially, such as Cline's `autoApprove` or
+    /// Roo Code's `alwaysAllow`: `{"alwaysAllow": {"translate": "autoApprove"}}`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub extension_options: HashMap<String, ExtensionRule>,
+    /// Config shape for clients we have no built-in adapter for, declared in the registry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub declarative_adapter: Option<DeclarativeAdapterSpec>,
+    /// Config file paths keyed by platform (`macos`, `windows`, `linux`), primary first.
+    /// Platforms without an entry use `config_path` and `alt_config_paths`. Overriding
+    /// `config_path` drops the table, see `ApplicationProfile::set_config_path`.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub config_paths_by_platform: HashMap<String, Vec<String>>,
+}
+
+impl Default for ApplicationMetadata {
+    /// Metadata with the same values serde fills in for omitted fields
+    fn default() -> Self {
+        Self {
+            version: None,
+            developer: String::new(),
+            category: ApplicationCategory::Other(String::new()),
+            mcp_version: default_mcp_version(),
+            notes: None,
+            requires_permissions: false,
+            release_year: None,
+            official_docs_url: None,
+            config_docs_url: None,
+            support_url: None,
+            license: None,
+            platforms: Vec::new(),
+            min_version: None,
+            tolerates_extra_keys: false,
+            supports_remote_servers: false,
+            unsupported_runtimes: Vec::new(),
+            supports_cwd: false,
+            supports_env: default_supports_env(),
+            supports_inputs: false,
+            supports_project_config: default_supports_project_config(),
+            supports_config_writes: default_supports_config_writes(),
+            accepts_jsonc: false,
+            format_history: Vec::new(),
+            structure_history: Vec::new(),
+            disable_strategy: DisableStrategy::default(),
+            extension_options: HashMap::new(),
+            declarative_adapter: None,
+            config_paths_by_platform: HashMap::new(),
+        }
+    }
+}
+
+/// A config format and location used from an application version onward
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct FormatTransition {
+    pub format: ConfigFormat,
+    /// Config file path (with ~ expansion support)
+    pub path: String,
+    /// First application version using this format; None for the original format
+    #[serde(default)]
+    pub min_version: Option<String>,
+    /// Document layout the format uses; None for servers in a map keyed by name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<ConfigSchema>,
+}
+
+/// Document layout of a config beyond its file syntax
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigSchema {
+    /// Continue's config.yaml: `name`, `version` and `schema` headers, with `mcpServers`
+    /// as a list of entries that carry their own `name`
+    ContinueBlocks,
+}
+
+/// A server container key used from an application version onward
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StructureTransition {
+    pub structure: ConfigStructure,
+    /// First application version using this structure; None for the original structure
+    #[serde(default)]
+    pub min_version: Option<String>,
+}
+
+/// Key of the running platform in `ApplicationMetadata::config_paths_by_platform`
+pub fn current_platform() -> &'static str {
+    if cfg!(target_os = "windows") {
+        "windows"
+    } else if cfg!(target_os = "macos") {
+        "macos"
+    } else {
+        "linux"
+    }
+}
+
+/// Whether a registry entry asks for its application to be left out
+fn is_disabled_entry(entry: &serde_json::Value) -> bool {
+    entry.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false)
+}
+
+/// Apply a registry entry to a serialized profile: top-level fields replace, `metadata` and
+/// `detection_strategy` are patched key by key
+fn overlay_profile(profile: &mut serde_json::Value, entry: &serde_json::Value) {
+    let (Some(profile), Some(entry)) = (profile.as_object_mut(), entry.as_object()) else {
+        return;
+    };
+    // An overridden config path wins over the built-in per-platform table, unless the entry brings its own
+    let path_overridden = entry.contains_key("config_path")
+        && entry.get("metadata").and_then(|metadata| metadata.get("config_paths_by_platform")).is_none();
+    if path_overridden {
+        if let Some(metadata) = profile.get_mut("metadata").and_then(|metadata| metadata.as_object_mut()) {
+            metadata.remove("config_paths_by_platform");
+        }
+    }
+    for (key, value) in entry {
+        match (profile.get_mut(key), value) {
+            (Some(serde_json::Value::Object(current)), serde_json::Value::Object(patch))
+                if key == "metadata" || key == "detection_strategy" =>
+            {
+                current.extend(patch.clone());
+            }
+            _ => {
+                profile.insert(key.clone(), value.clone());
+            }
+        }
+    }
+    profile.remove("disabled");
+}
+
+/// Build a `config_paths_by_platform` table from literal paths
+fn platform_config_paths(entries: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+    entries
+        .iter()
+        .map(|(platform, paths)| (platform.to_string(), paths.iter().map(|p| p.to_string()).collect()))
+        .collect()
+}
+
+fn default_mcp_version() -> String {
+    "1.0".to_string()
+}
+
+fn default_supports_project_config() -> bool {
+    true
+}
+
+fn default_supports_config_writes() -> bool {
+    true
+}
+
+fn default_supports_env() -> bool {
+    true
+}
+
+/// Categories of MCP-enabled applications
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ApplicationCategory {
+    #[serde(rename = "IDE")]
+    IDE,
+    #[serde(rename = "AIAssistant")]
+    AIAssistant,
+    #[serde(rename = "DeveloperTool")]
+    DeveloperTool,
+    #[serde(rename = "Terminal")]
+    Terminal,
+    #[serde(rename = "CodeEditor")]
+    CodeEditor,
+    #[serde(rename = "ChatClient")]
+    ChatClient,
+    #[serde(rename = "ProductivityTool")]
+    ProductivityTool,
+    Other(String),
+}
+
+/// Registry of known MCP-enabled applications
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApplicationRegistry {
+    /// Map of application ID to profile
+    pub applications: HashMap<String, ApplicationProfile>,
+    /// Registry metadata
+    pub metadata: RegistryMetadata,
+}
+
+/// Where VS Code keeps MCP servers in settings.json since it moved them under chat settings
+pub const VSCODE_CHAT_MCP_SERVERS: &str = "chat.mcp.servers";
+
+/// Canonical application profiles, compiled into the binary
+const EMBEDDED_APPLICATIONS: &str = include_str!("../../resources/applications.json");
+
+/// Metadata about the application registry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RegistryMetadata {
+    /// Registry version
+    pub version: String,
+    /// Last updated timestamp
+    pub last_updated: chrono::DateTime<chrono::Utc>,
+    /// Total number of applications
+    pub application_count: usize,
+}
+
+impl ApplicationRegistry {
+    /// Create a registry of the known applications from the embedded applications.json.
+    ///
+    /// The hand-written profiles are only a last resort for when the embedded file fails to parse.
+    pub fn new() -> Self {
+        Self::embedded().unwrap_or_else(|e| {
+            log::warn!("Embedded application registry is invalid, using built-in profiles: {}", e);
+            Self::builtin()
+        })
+    }
+
+    /// Registry of the hand-written profiles, the fallback for an unparsable embedded registry
+    fn builtin() -> Self {
+        let mut applications = HashMap::new();
+        
+        // Add known MCP-enabled applications
+        applications.insert("claude-desktop".to_string(), Self::claude_desktop_profile());
+        applications.insert("claude-code".to_string(), Self::claude_code_profile());
+        applications.insert("cursor".to_string(), Self::cursor_profile());
+        applications.insert("zed".to_string(), Self::zed_profile());
+        applications.insert("vscode".to_string(), Self::vscode_profile());
+        applications.insert("continue-dev".to_string(), Self::continue_dev_profile());
+        applications.insert("amazon-q".to_string(), Self::amazon_q_profile());
+        applications.insert("warp".to_string(), Self::warp_profile());
+        applications.insert("jetbrains-idea".to_string(), Self::jetbrains_idea_profile());
+        applications.insert("jetbrains-phpstorm".to_string(), Self::jetbrains_phpstorm_profile());
+        applications.insert("jetbrains-webstorm".to_string(), Self::jetbrains_webstorm_profile());
+        applications.insert("jetbrains-pycharm".to_string(), Self::jetbrains_pycharm_profile());
+        
+        let application_count = applications.len();
+        
+        Self {
+            applications,
+            metadata: RegistryMetadata {
+                version: "1.0.0".to_string(),
+                last_updated: chrono::Utc::now(),
+                application_count,
+            },
+        }
+    }
+
+    /// Load registry from external JSON file
+    ///
+    /// Attempts to load application profiles from an external applications.json file.
+    /// This allows for configuration without recompilation.
+    pub fn from_json_file(path: &std::path::Path) -> anyhow::Result<Self> {
+        let content = std::fs::read_to_string(path)?;
+        Self::from_json_str(&content)
+    }
+
+    /// Load the registry compiled into the binary from resources/applications.json
+    pub fn embedded() -> anyhow::Result<Self> {
+        Self::from_json_str(EMBEDDED_APPLICATIONS)
+    }
+
+    /// Parse a registry in the applications.json format
+    pub fn from_json_str(content: &str) -> anyhow::Result<Self> {
+        let json: serde_json::Value = serde_json::from_str(content)?;
+
+        let mut applications = HashMap::new();
+
+        if let Some(apps_array) = json.get("applications").and_then(|a| a.as_array()) {
+            for app_json in apps_array {
+                if is_disabled_entry(app_json) {
+                    continue;
+                }
+                let profile: ApplicationProfile = serde_json::from_value(app_json.clone())?;
+                profile.validate_definition()?;
+                applications.insert(profile.id.clone(), profile);
+            }
+        }
+
+        let application_count = applications.len();
+        let version = json.get("version")
+            .and_then(|v| v.as_str())
+            .unwrap_or("1.0.0")
+            .to_string();
+        let last_updated = json.get("last_updated")
+            .and_then(|v| v.as_str())
+            .and_then(|v| chrono::DateTime::parse_from_rfc3339(v).ok())
+            .map(|v| v.with_timezone(&chrono::Utc))
+            .unwrap_or_else(chrono::Utc::now);
+
+        Ok(Self {
+            applications,
+            metadata: RegistryMetadata {
+                version,
+                last_updated,
+                application_count,
+            },
+        })
+    }
+
+    /// Save registry to an external JSON file in the format `from_json_file` reads
+    ///
+    /// Applications are written sorted by id, stamped with the current time as `last_updated`.
+    /// Applications from the base registry (see `new`) missing here are written as
+    /// `"disabled": true` entries, so merging the file over that base removes them again.
+    /// The file is written atomically, so a crash midway leaves the previous registry intact.
+    pub fn to_json_file(&self, path: &std::path::Path) -> anyhow::Result<()> {
+        let base = Self::new();
+        let mut ids: Vec<&String> = self.applications.keys().chain(base.applications.keys()).collect();
+        ids.sort();
+        ids.dedup();
+        let applications = ids
+            .into_iter()
+            .map(|id| match self.applications.get(id) {
+                Some(profile) => serde_json::to_value(profile),
+                None => Ok(serde_json::json!({"id": id, "disabled": true})),
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let json = serde_json::json!({
+            "version": self.metadata.version,
+            "last_updated": chrono::Utc::now(),
+            "applications": applications,
+        });
+        let mut content = serde_json::to_string_pretty(&json)?;
+        content.push('\n');
+
+        ConfigFileService::write_atomic(path, content)
+            .with_context(|| format!("Failed to write registry: {}", path.display()))
+    }
+
+    /// Overlay entries from an applications.json file onto this registry by id
+    ///
+    /// See `merge_json_str` for how entries are applied.
+    pub fn merge_json_file(&mut self, path: &std::path::Path) -> anyhow::Result<()> {
+        let content = std::fs::read_to_string(path)?;
+        self.merge_json_str(&content)
+            .with_context(|| format!("Invalid application registry: {}", path.display()))
+    }
+
+    /// Overlay entries in the applications.json format onto this registry by id
+    ///
+    /// - An entry for a known id only needs the fields it changes. Top-level fields replace
+    ///   the current ones; `metadata` and `detection_strategy` are patched key by key.
+    /// - An entry for a new id must be a complete profile.
+    /// - `"disabled": true` removes the profile with that id.
+    ///
+    /// Nothing is changed if any entry is invalid.
+    pub fn merge_json_str(&mut self, content: &str) -> anyhow::Result<()> {
+        let json: serde_json::Value = serde_json::from_str(content)?;
+        let mut merged = self.clone();
+
+        for entry in json.get("applications").and_then(|a| a.as_array()).into_iter().flatten() {
+            let id = entry.get("id")
+                .and_then(|id| id.as_str())
+                .ok_or_else(|| anyhow::anyhow!("Application entry is missing its id"))?;
+
+            if is_disabled_entry(entry) {
+                merged.remove_application(id);
+                continue;
+            }
+
+            let profile: ApplicationProfile = match merged.applications.get(id) {
+                Some(existing) => {
+                    let mut value = serde_json::to_value(existing)?;
+                    overlay_profile(&mut value, entry);
+                    serde_json::from_value(value).with_context(|| format!("Invalid override for {}", id))?
+                }
+                None => serde_json::from_value(entry.clone())
+                    .with_context(|| format!("{} is not a built-in application, so its entry must be a complete profile", id))?,
+            };
+            profile.validate_definition()?;
+            merged.add_application(profile);
+        }
+
+        *self = merged;
+        Ok(())
+    }
+
+    /// Create registry with automatic loading from external file if available
+    ///
+    /// Starts from the embedded registry (see `new`) and overlays, in order:
+    /// 1. ./core/resources/applications.json or ./src-tauri/core/resources/applications.json (development)
+    /// 2. applications.json in the user's config directory
+    ///
+    /// Each file only overrides or disables the profiles it names; see `merge_json_str`.
+    /// A file that fails to parse is skipped as a whole.
+    pub fn with_auto_load() -> Self {
+        let mut registry = Self::new();
+
+        let dev_path = ["./core/resources/applications.json", "./src-tauri/core/resources/applications.json"]
+            .into_iter()
+            .map(std::path::PathBuf::from)
+            .find(|path| path.exists());
+        let user_path = dirs::config_dir()
+            .map(|config_dir| config_dir.join("mcp-control").join("applications.json"))
+            .filter(|path| path.exists());
+
+        for path in dev_path.into_iter().chain(user_path) {
+            match registry.merge_json_file(&path) {
+                Ok(()) => log::info!("Merged application registry from {}", path.display()),
+                Err(e) => log::warn!("Skipping application registry {}: {:#}", path.display(), e),
+            }
+        }
+
+        registry
+    }
+
+    /// Get Claude Desktop application profile
+    fn claude_desktop_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "claude-desktop".to_string(),
+            name: "Claude Desktop".to_string(),
+            bundle_id: "com.anthropic.claude".to_string(),
+            config_path: "~/Library/Application Support/Claude/claude_desktop_config.json".to_string(),
+            alt_config_paths: vec![
+                "~/.config/claude/claude_desktop_config.json".to_string(),
+            ],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec![
+                "/Applications/Claude.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/Claude.app".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Anthropic".to_string(),
+                category: ApplicationCategory::ChatClient,
+                mcp_version: "1.0".to_string(),
+                notes: Some("Primary MCP client from Anthropic".to_string()),
+                requires_permissions: false,
+                supports_project_config: false,
+                tolerates_extra_keys: true,
+                config_paths_by_platform: platform_config_paths(&[
+                    ("windows", &["%APPDATA%/Claude/claude_desktop_config.json"]),
+                    (
+                        "linux",
+                        &[
+                            "$XDG_CONFIG_HOME/Claude/claude_desktop_config.json",
+                            "$XDG_CONFIG_HOME/claude/claude_desktop_config.json",
+                        ],
+                    ),
+                ]),
+                ..Default::default()
+            },
+        }
+    }
+    
+    /// Get Cursor application profile
+    fn cursor_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "cursor".to_string(),
+            name: "Cursor".to_string(),
+            bundle_id: "com.cursor.Cursor".to_string(),
+            config_path: "~/.cursor/mcp.json".to_string(),
+            // Settings files older releases read `mcp.servers` from; see configuration::cursor_migration
+            alt_config_paths: vec![
+                "~/Library/Application Support/Cursor/User/settings.json".to_string(),
+                "~/.config/cursor/settings.json".to_string(),
+                "~/Library/Application Support/Cursor/User/globalStorage/settings.json".to_string(),
+            ],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec![
+                "/Applications/Cursor.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/Cursor.app".to_string(),
+                "/usr/local/bin/cursor".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Cursor Team".to_string(),
+                category: ApplicationCategory::CodeEditor,
+                mcp_version: "1.0".to_string(),
+                notes: Some("AI-powered code editor with MCP support".to_string()),
+                requires_permissions: false,
+                supports_remote_servers: true,
+                tolerates_extra_keys: true,
+                config_paths_by_platform: platform_config_paths(&[
+                    ("windows", &["%USERPROFILE%/.cursor/mcp.json", "%APPDATA%/Cursor/User/settings.json"]),
+                    (
+                        "linux",
+                        &[
+                            "~/.cursor/mcp.json",
+                            "$XDG_CONFIG_HOME/Cursor/User/settings.json",
+                            "$XDG_CONFIG_HOME/cursor/settings.json",
+                        ],
+                    ),
+                ]),
+                ..Default::default()
+            },
+        }
+    }
+    
+    /// Get Zed application profile
+    fn zed_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "zed".to_string(),
+            name: "Zed".to_string(),
+            bundle_id: "dev.zed.Zed".to_string(),
+            config_path: "~/Library/Application Support/Zed/settings.json".to_string(),
+            alt_config_paths: vec![
+                "~/.config/zed/settings.json".to_string(),
+            ],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::Custom("context_servers".to_string()),
+            executable_paths: vec![
+                "/Applications/Zed.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/Zed.app".to_string(),
+                "/usr/local/bin/zed".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Zed Industries".to_string(),
+                category: ApplicationCategory::CodeEditor,
+                mcp_version: "1.0".to_string(),
+                notes: Some("High-performance collaborative code editor".to_string()),
+                disable_strategy: DisableStrategy::EnabledFlag,
+                requires_permissions: false,
+                accepts_jsonc: true,
+                config_paths_by_platform: platform_config_paths(&[("linux", &["$XDG_CONFIG_HOME/zed/settings.json"])]),
+                ..Default::default()
+            },
+        }
+    }
+    
+    /// Get VS Code application profile
+    fn vscode_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "vscode".to_string(),
+            name: "Visual Studio Code".to_string(),
+            bundle_id: "com.microsoft.VSCode".to_string(),
+            config_path: "~/Library/Application Support/Code/User/settings.json".to_string(),
+            alt_config_paths: vec![
+                "~/.config/Code/User/settings.json".to_string(),
+                "~/Library/Application Support/Code - Insiders/User/settings.json".to_string(),
+            ],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec![
+                "/Applications/Visual Studio Code.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/Visual Studio Code.app".to_string(),
+                "/usr/local/bin/code".to_string(),
+                "/Applications/Visual Studio Code - Insiders.app".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Microsoft".to_string(),
+                category: ApplicationCategory::CodeEditor,
+                mcp_version: "1.0".to_string(),
+                notes: Some("Popular code editor with MCP extension support".to_string()),
+                disable_strategy: DisableStrategy::MoveAside,
+                requires_permissions: false,
+                supports_remote_servers: true,
+                supports_inputs: true,
+                accepts_jsonc: true,
+                structure_history: vec![
+                    StructureTransition { structure: ConfigStructure::DirectMcpServers, min_version: None },
+                    StructureTransition {
+                        structure: ConfigStructure::Custom(VSCODE_CHAT_MCP_SERVERS.to_string()),
+                        min_version: Some("1.102.0".to_string()),
+                    },
+                ],
+                config_paths_by_platform: platform_config_paths(&[
+                    ("windows", &["%APPDATA%/Code/User/settings.json", "%APPDATA%/Code - Insiders/User/settings.json"]),
+                    (
+                        "linux",
+                        &["$XDG_CONFIG_HOME/Code/User/settings.json", "$XDG_CONFIG_HOME/Code - Insiders/User/settings.json"],
+                    ),
+                ]),
+                ..Default::default()
+            },
+        }
+    }
+    
+    /// Get Continue.dev application profile
+    fn continue_dev_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "continue-dev".to_string(),
+            name: "Continue.dev".to_string(),
+            bundle_id: "dev.continue.continue".to_string(),
+            config_path: "~/.continue/config.json".to_string(),
+            alt_config_paths: vec![
+                "~/Library/Application Support/continue/config.json".to_string(),
+            ],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec![
+                "/Applications/Continue.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/Continue.app".to_string(),
+                "~/.vscode/extensions/continue.continue-*".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: false, // Less common application
+                priority_order: vec![
+                    DetectionMethod::ConfigCheck,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::BundleLookup,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Continue.dev".to_string(),
+                category: ApplicationCategory::IDE,
+                mcp_version: "1.0".to_string(),
+                notes: Some("AI coding assistant with MCP integration".to_string()),
+                requires_permissions: false,
+                format_history: vec![
+                    FormatTransition {
+                        format: ConfigFormat::Json,
+                        path: "~/.continue/config.json".to_string(),
+                        min_version: None,
+                        schema: None,
+                    },
+                    FormatTransition {
+                        format: ConfigFormat::Yaml,
+                        path: "~/.continue/config.yaml".to_string(),
+                        min_version: Some("1.0.0".to_string()),
+                        schema: Some(ConfigSchema::ContinueBlocks),
+                    },
+                ],
+                ..Default::default()
+            },
+        }
+    }
+    
+    /// Get Amazon Q Developer application profile
+    fn amazon_q_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "amazon-q".to_string(),
+            name: "Amazon Q Developer".to_string(),
+            bundle_id: "com.amazon.q.developer".to_string(),
+            config_path: "~/.aws/amazonq/mcp.json".to_string(),
+            alt_config_paths: vec![
+                "~/.aws/q/config.json".to_string(),
+                "~/Library/Application Support/Amazon Q/config.json".to_string(),
+            ],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec![
+                "/Applications/Amazon Q.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/Amazon Q.app".to_string(),
+                "/usr/local/bin/q".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Amazon Web Services".to_string(),
+                category: ApplicationCategory::IDE,
+                mcp_version: "1.0".to_string(),
+                notes: Some("AWS AI coding assistant with MCP support (global settings only)".to_string()),
+                requires_permissions: false,
+                supports_project_config: false,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Get Warp terminal application profile
+    fn warp_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "warp".to_string(),
+            name: "Warp".to_string(),
+            bundle_id: "dev.warp.Warp-Stable".to_string(),
+            config_path: WARP_DATABASE_PATHS[0].to_string(),
+            alt_config_paths: WARP_DATABASE_PATHS[1..].iter().map(|p| p.to_string()).collect(),
+            config_format: ConfigFormat::Custom(SQLITE_FORMAT.to_string()),
+            config_structure: ConfigStructure::NestedMcpServers,
+            executable_paths: vec![
+                "/Applications/Warp.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/Warp.app".to_string(),
+                "/usr/local/bin/warp".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Warp".to_string(),
+                category: ApplicationCategory::ProductivityTool,
+                mcp_version: "1.0".to_string(),
+                notes: Some("Modern terminal with AI integration and MCP support; servers live in Warp's database and are added through its settings".to_string()),
+                requires_permissions: false,
+                supports_project_config: false,
+                supports_cwd: true,
+                supports_config_writes: false,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Get Claude Code CLI application profile
+    fn claude_code_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "claude-code".to_string(),
+            name: "Claude Code".to_string(),
+            bundle_id: "com.anthropic.claude-code".to_string(),
+            config_path: "~/.claude/config.json".to_string(),
+            alt_config_paths: vec![
+                "~/.config/claude-code/config.json".to_string(),
+                "~/Library/Application Support/Claude Code/config.json".to_string(),
+            ],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec![
+                "/usr/local/bin/claude".to_string(),
+                "/opt/homebrew/bin/claude".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/.local/bin/claude".to_string(),
+                "/usr/bin/claude".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: false,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: false,
+                priority_order: vec![
+                    DetectionMethod::ConfigCheck,
+                    DetectionMethod::ExecutableCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Anthropic".to_string(),
+                category: ApplicationCategory::CodeEditor,
+                mcp_version: "1.0".to_string(),
+                notes: Some("Claude's official CLI tool with MCP support".to_string()),
+                requires_permissions: false,
+                supports_remote_servers: true,
+                tolerates_extra_keys: true,
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Get IntelliJ IDEA application profile
+    fn jetbrains_idea_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "jetbrains-idea".to_string(),
+            name: "IntelliJ IDEA".to_string(),
+            bundle_id: "com.jetbrains.intellij".to_string(),
+            config_path: "~/Library/Application Support/JetBrains/IntelliJIdea*/options/llm.mcpServers.xml".to_string(),
+            alt_config_paths: vec![
+                "~/.config/JetBrains/IntelliJIdea*/options/llm.mcpServers.xml".to_string(),
+                "~/Library/Application Support/JetBrains/IdeaIC*/options/llm.mcpServers.xml".to_string(),
+            ],
+            config_format: ConfigFormat::Custom(OPTIONS_XML_FORMAT.to_string()),
+            config_structure: ConfigStructure::NestedMcpServers,
+            executable_paths: vec![
+                "/Applications/IntelliJ IDEA.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/IntelliJ IDEA.app".to_string(),
+                "/Applications/IntelliJ IDEA CE.app".to_string(),
+                "/usr/local/bin/idea".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "JetBrains".to_string(),
+                category: ApplicationCategory::IDE,
+                mcp_version: "1.0".to_string(),
+                notes: Some("Java IDE with MCP plugin support".to_string()),
+                requires_permissions: false,
+                config_paths_by_platform: platform_config_paths(&[(
+                    "windows",
+                    &[
+                        "%APPDATA%/JetBrains/IntelliJIdea*/options/llm.mcpServers.xml",
+                        "%APPDATA%/JetBrains/IdeaIC*/options/llm.mcpServers.xml",
+                    ],
+                )]),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Get PHPStorm application profile
+    fn jetbrains_phpstorm_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "jetbrains-phpstorm".to_string(),
+            name: "PHPStorm".to_string(),
+            bundle_id: "com.jetbrains.phpstorm".to_string(),
+            config_path: "~/Library/Application Support/JetBrains/PhpStorm*/options/llm.mcpServers.xml".to_string(),
+            alt_config_paths: vec![
+                "~/.config/JetBrains/PhpStorm*/options/llm.mcpServers.xml".to_string(),
+            ],
+            config_format: ConfigFormat::Custom(OPTIONS_XML_FORMAT.to_string()),
+            config_structure: ConfigStructure::NestedMcpServers,
+            executable_paths: vec![
+                "/Applications/PhpStorm.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/PhpStorm.app".to_string(),
+                "/usr/local/bin/phpstorm".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "JetBrains".to_string(),
+                category: ApplicationCategory::IDE,
+                mcp_version: "1.0".to_string(),
+                notes: Some("PHP IDE with MCP plugin support".to_string()),
+                requires_permissions: false,
+                // Read-only until AI Assistant's options file is verified for this IDE
+                supports_config_writes: false,
+                config_paths_by_platform: platform_config_paths(&[(
+                    "windows",
+                    &["%APPDATA%/JetBrains/PhpStorm*/options/llm.mcpServers.xml"],
+                )]),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Get WebStorm application profile
+    fn jetbrains_webstorm_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "jetbrains-webstorm".to_string(),
+            name: "WebStorm".to_string(),
+            bundle_id: "com.jetbrains.webstorm".to_string(),
+            config_path: "~/Library/Application Support/JetBrains/WebStorm*/options/llm.mcpServers.xml".to_string(),
+            alt_config_paths: vec![
+                "~/.config/JetBrains/WebStorm*/options/llm.mcpServers.xml".to_string(),
+            ],
+            config_format: ConfigFormat::Custom(OPTIONS_XML_FORMAT.to_string()),
+            config_structure: ConfigStructure::NestedMcpServers,
+            executable_paths: vec![
+                "/Applications/WebStorm.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/WebStorm.app".to_string(),
+                "/usr/local/bin/webstorm".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "JetBrains".to_string(),
+                category: ApplicationCategory::IDE,
+                mcp_version: "1.0".to_string(),
+                notes: Some("JavaScript IDE with MCP plugin support".to_string()),
+                requires_permissions: false,
+                // Read-only until AI Assistant's options file is verified for this IDE
+                supports_config_writes: false,
+                config_paths_by_platform: platform_config_paths(&[(
+                    "windows",
+                    &["%APPDATA%/JetBrains/WebStorm*/options/llm.mcpServers.xml"],
+                )]),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Get PyCharm application profile
+    fn jetbrains_pycharm_profile() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "jetbrains-pycharm".to_string(),
+            name: "PyCharm".to_string(),
+            bundle_id: "com.jetbrains.pycharm".to_string(),
+            config_path: "~/Library/Application Support/JetBrains/PyCharm*/options/llm.mcpServers.xml".to_string(),
+            alt_config_paths: vec![
+                "~/.config/JetBrains/PyCharm*/options/llm.mcpServers.xml".to_string(),
+                "~/Library/Application Support/JetBrains/PyCharmCE*/options/llm.mcpServers.xml".to_string(),
+            ],
+            config_format: ConfigFormat::Custom(OPTIONS_XML_FORMAT.to_string()),
+            config_structure: ConfigStructure::NestedMcpServers,
+            executable_paths: vec![
+                "/Applications/PyCharm.app".to_string(),
+            ],
+            alt_executable_paths: vec![
+                "~/Applications/PyCharm.app".to_string(),
+                "/Applications/PyCharm CE.app".to_string(),
+                "/usr/local/bin/pycharm".to_string(),
+            ],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: true,
+                priority_order: vec![
+                    DetectionMethod::BundleLookup,
+                    DetectionMethod::ExecutableCheck,
+                    DetectionMethod::ConfigCheck,
+                ],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "JetBrains".to_string(),
+                category: ApplicationCategory::IDE,
+                mcp_version: "1.0".to_string(),
+                notes: Some("Python IDE with MCP plugin support".to_string()),
+                requires_permissions: false,
+                // Read-only until AI Assistant's options file is verified for this IDE
+                supports_config_writes: false,
+                config_paths_by_platform: platform_config_paths(&[(
+                    "windows",
+                    &[
+                        "%APPDATA%/JetBrains/PyCharm*/options/llm.mcpServers.xml",
+                        "%APPDATA%/JetBrains/PyCharmCE*/options/llm.mcpServers.xml",
+                    ],
+                )]),
+                ..Default::default()
+            },
+        }
+    }
+    
+    /// Add a new application profile to the registry
+    pub fn add_application(&mut self, profile: ApplicationProfile) {
+        self.applications.insert(profile.id.clone(), profile);
+        self.metadata.application_count = self.applications.len();
+        self.metadata.last_updated = chrono::Utc::now();
+    }
+    
+    /// Remove an application profile from the registry
+    pub fn remove_application(&mut self, id: &str) -> Option<ApplicationProfile> {
+        let result = self.applications.remove(id);
+        if result.is_some() {
+            self.metadata.application_count = self.applications.len();
+            self.metadata.last_updated = chrono::Utc::now();
+        }
+        result
+    }
+    
+    /// Get an application profile by ID
+    pub fn get_application(&self, id: &str) -> Option<&ApplicationProfile> {
+        self.applications.get(id)
+    }
+    
+    /// Get all application profiles
+    pub fn get_all_applications(&self) -> Vec<&ApplicationProfile> {
+        self.applications.values().collect()
+    }
+    
+    /// Get applications by category
+    pub fn get_applications_by_category(&self, category: &ApplicationCategory) -> Vec<&ApplicationProfile> {
+        self.applications
+            .values()
+            .filter(|app| &app.metadata.category == category)
+            .collect()
+    }
+    
+    /// Group every configured server by the runtime its stored entry launches with
+    pub fn servers_by_runtime(scan: &[ConfigValidationResult]) -> HashMap<ServerRuntime, Vec<ServerRef>> {
+        let mut grouped: HashMap<ServerRuntime, Vec<ServerRef>> = HashMap::new();
+        for result in scan {
+            for server in &result.mcp_servers {
+                grouped.entry(server.runtime()).or_default().push(ServerRef {
+                    app_id: result.application.id.clone(),
+                    server_name: server.name.clone(),
+                    config_path: result.config_path.clone(),
+                });
+            }
+        }
+
+        for servers in grouped.values_mut() {
+            servers.sort_by(|a, b| (&a.app_id, &a.server_name).cmp(&(&b.app_id, &b.server_name)));
+        }
+        grouped
+    }
+
+    /// Update registry metadata
+    pub fn update_metadata(&mut self) {
+        self.metadata.application_count = self.applications.len();
+        self.metadata.last_updated = chrono::Utc::now();
+    }
+}
+
+impl Default for ApplicationRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analysis::ServerAnalyzer;
+
+    #[test]
+    fn test_application_registry_creation() {
+        let registry = ApplicationRegistry::new();
+        assert!(!registry.applications.is_empty());
+        assert_eq!(registry.metadata.application_count, registry.applications.len());
+    }
+
+    #[test]
+    fn test_cli_category_default_skips_bundle_lookup() {
+        let cli = DetectionStrategy::default_for_category(&ApplicationCategory::Terminal);
+        assert!(!cli.use_bundle_lookup);
+        assert!(!cli.priority_order.contains(&DetectionMethod::BundleLookup));
+
+        let gui = DetectionStrategy::default_for_category(&ApplicationCategory::ChatClient);
+        assert!(gui.use_bundle_lookup);
+        assert_eq!(gui.priority_order[0], DetectionMethod::BundleLookup);
+    }
+
+    #[test]
+    fn test_windows_config_paths() {
+        let registry = ApplicationRegistry::new();
+        let claude = registry.get_application("claude-desktop").unwrap();
+        assert_eq!(claude.config_paths_for_platform("windows"), vec!["%APPDATA%/Claude/claude_desktop_config.json"]);
+        // Platforms without their own entry keep the primary and alternate paths
+        assert_eq!(claude.config_paths_for_platform("macos")[0], claude.config_path);
+
+        for id in ["cursor", "vscode", "jetbrains-idea", "jetbrains-phpstorm", "jetbrains-webstorm", "jetbrains-pycharm"] {
+            let app = registry.get_application(id).unwrap();
+            assert!(app.config_paths_for_platform("windows").iter().all(|p| p.starts_with('%')), "{}", id);
+        }
+
+        // Registries written before the field existed still load
+        let mut json = serde_json::to_value(claude).unwrap();
+        json["metadata"].as_object_mut().unwrap().remove("config_paths_by_platform");
+        let old: ApplicationProfile = serde_json::from_value(json).unwrap();
+        assert_eq!(old.config_paths_for_platform("windows")[0], claude.config_path);
+    }
+
+    #[test]
+    fn test_merge_overrides_builtin_profiles_by_id() {
+        let mut registry = ApplicationRegistry::new();
+        let builtin_count = registry.applications.len();
+        let builtin_cursor = registry.get_application("cursor").unwrap().clone();
+
+        let mut replacement = registry.get_application("zed").unwrap().clone();
+        replacement.name = "Zed Preview".to_string();
+        replacement.config_path = "~/.config/zed-preview/settings.json".to_string();
+        let external = serde_json::json!({
+            "version": "1.0.0",
+            "applications": [
+                replacement,
+                {
+                    "id": "cursor",
+                    "config_path": "~/.cursor/mcp-corrected.json",
+                    "metadata": { "notes": "Corrected path" }
+                }
+            ]
+        });
+        registry.merge_json_str(&external.to_string()).unwrap();
+
+        // The rest of the built-in profiles survive a two-app file
+        assert_eq!(registry.applications.len(), builtin_count);
+        assert_eq!(registry.get_application("zed").unwrap(), &replacement);
+
+        // Only the named fields change; the rest of metadata is kept
+        let cursor = registry.get_application("cursor").unwrap();
+        assert_eq!(cursor.config_path, "~/.cursor/mcp-corrected.json");
+        assert_eq!(cursor.get_config_paths_for_current_platform()[0], "~/.cursor/mcp-corrected.json");
+        assert!(cursor.metadata.config_paths_by_platform.is_empty());
+        assert_eq!(cursor.metadata.notes.as_deref(), Some("Corrected path"));
+        assert_eq!(cursor.metadata.developer, builtin_cursor.metadata.developer);
+        assert_eq!(cursor.alt_config_paths, builtin_cursor.alt_config_paths);
+        assert_eq!(cursor.detection_strategy, builtin_cursor.detection_strategy);
+    }
+
+    #[test]
+    fn test_merge_disables_and_rejects_incomplete_profiles() {
+        let mut registry = ApplicationRegistry::new();
+        let builtin_count = registry.applications.len();
+
+        let disable = r#"{"applications": [{"id": "warp", "disabled": true}, {"id": "not-installed", "disabled": true}]}"#;
+        registry.merge_json_str(disable).unwrap();
+        assert!(registry.get_application("warp").is_none());
+        assert_eq!(registry.applications.len(), builtin_count - 1);
+
+        // A new id has nothing to patch, and a bad entry leaves the registry untouched
+        let partial = r#"{"applications": [{"id": "cursor", "name": "Renamed"}, {"id": "acme", "name": "Acme"}]}"#;
+        assert!(registry.merge_json_str(partial).is_err());
+        assert_eq!(registry.get_application("cursor").unwrap().name, "Cursor");
+        assert_eq!(registry.applications.len(), builtin_count - 1);
+    }
+
+    #[test]
+    fn test_registry_json_file_round_trip() {
+        let temp_dir = tempfile::tempdir().unwrap();
+        let path = temp_dir.path().join("applications.json");
+        let mut registry = ApplicationRegistry::embedded().unwrap();
+
+        // A profile with a custom servers path and every optional metadata field filled in
+        let mut custom = registry.get_application("vscode").unwrap().clone();
+        custom.id = "acme-editor".to_string();
+        custom.config_format = ConfigFormat::Custom("acme-json".to_string());
+        custom.config_structure = ConfigStructure::Custom("acme.ai.servers".to_string());
+        custom.metadata.version = Some("2.1.0".to_string());
+        custom.metadata.release_year = Some(2025);
+        custom.metadata.official_docs_url = Some("https://acme.dev/docs".to_string());
+        custom.metadata.config_docs_url = Some("https://acme.dev/docs/mcp".to_string());
+        custom.metadata.support_url = Some("https://acme.dev/support".to_string());
+        custom.metadata.license = Some("MIT".to_string());
+        custom.metadata.platforms = vec!["macOS".to_string(), "Linux".to_string()];
+        custom.metadata.min_version = Some("2.0.0".to_string());
+        custom.metadata.unsupported_runtimes = vec![ServerRuntime::Docker, ServerRuntime::Other("java".to_string())];
+        custom.metadata.supports_cwd = true;
+        custom.metadata.supports_config_writes = false;
+        custom.metadata.format_history = vec![FormatTransition {
+            format: ConfigFormat::Yaml,
+            path: "~/.acme/mcp.yaml".to_string(),
+            min_version: Some("1.0.0".to_string()),
+            schema: None,
+        }];
+        custom.metadata.disable_strategy = DisableStrategy::EnabledFlag;
+        registry.add_application(custom);
+        registry.remove_application("warp");
+
+        registry.to_json_file(&path).unwrap();
+        assert!(!path.with_extension("json.tmp").exists());
+        let reloaded = ApplicationRegistry::from_json_file(&path).unwrap();
+
+        assert_eq!(reloaded.applications, registry.applications);
+        assert_eq!(reloaded.metadata.version, registry.metadata.version);
+        assert_eq!(reloaded.metadata.application_count, registry.applications.len());
+        assert!(reloaded.metadata.last_updated >= registry.metadata.last_updated);
+
+        // Saving again replaces the file rather than appending to it
+        reloaded.to_json_file(&path).unwrap();
+        assert_eq!(ApplicationRegistry::from_json_file(&path).unwrap().applications, registry.applications);
+
+        // Merged over the built-ins, as `with_auto_load` does, removed built-ins stay removed
+        let content: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert!(content["applications"].as_array().unwrap().contains(&serde_json::json!({"id": "warp", "disabled": true})));
+        let mut merged = ApplicationRegistry::new();
+        merged.merge_json_file(&path).unwrap();
+        assert!(merged.get_application("warp").is_none());
+        assert_eq!(merged.applications, registry.applications);
+    }
+
+    #[test]
+    fn test_linux_paths_follow_xdg_config_home() {
+        let Some(home) = dirs::home_dir() else { return };
+        let registry = ApplicationRegistry::new();
+        for id in ["claude-desktop", "cursor", "vscode", "zed"] {
+            assert!(!registry.get_application(id).unwrap().config_paths_for_platform("linux").is_empty(), "{}", id);
+        }
+        let zed_path = registry.get_application("zed").unwrap().config_paths_for_platform("linux").remove(0);
+        let xdg = |name: &str| (name == "XDG_CONFIG_HOME").then(|| "/srv/xdg".to_string());
+        assert_eq!(
+            PathUtils::expand_path_with(&zed_path, xdg),
+            Some(PathBuf::from("/srv/xdg/zed/settings.json"))
+        );
+
+        // Unset or relative falls back to ~/.config
+        for lookup in [None, Some("relative/xdg")] {
+            assert_eq!(
+                PathUtils::expand_path_with("${XDG_CONFIG_HOME}/zed/settings.json", |_| lookup.map(String::from)),
+                Some(home.join(".config/zed/settings.json"))
+            );
+        }
+    }
+
+    #[test]
+    fn test_resolved_config_path_expands_primary() {
+        let Some(home) = dirs::home_dir() else { return };
+        let mut app = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        app.metadata.config_paths_by_platform.clear();
+        assert_eq!(
+            app.resolved_config_path(),
+            home.join("Library/Application Support/Claude/claude_desktop_config.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_find_config_file_falls_back_to_alternates() {
+        let Some(home) = dirs::home_dir() else { return };
+        let mut app = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        app.metadata.config_paths_by_platform = platform_config_paths(&[(
+            current_platform(),
+            &["~/primary/config.json", "~/alt/config.json", "~/JetBrains/IntelliJIdea*/options/llm.mcpServers.xml"],
+        )]);
+
+        let fs = crate::filesystem::InMemoryFs::case_sensitive();
+        assert_eq!(app.find_config_file(&fs).await, None);
+
+        // Primary missing: the first existing alternate wins, versioned directories included
+        fs.add_file(home.join("JetBrains/IntelliJIdea2025.1/options/llm.mcpServers.xml"), "<application/>");
+        assert_eq!(
+            app.find_config_file(&fs).await,
+            Some(home.join("JetBrains/IntelliJIdea2025.1/options/llm.mcpServers.xml"))
+        );
+        fs.add_file(home.join("alt/config.json"), "{}");
+        assert_eq!(app.find_config_file(&fs).await, Some(home.join("alt/config.json")));
+        fs.add_file(home.join("primary/config.json"), "{}");
+        assert_eq!(app.find_config_file(&fs).await, Some(home.join("primary/config.json")));
+    }
+
+    #[test]
+    fn test_declared_structures_match_config_samples() {
+        let samples = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/config_samples");
+        let registry = ApplicationRegistry::new();
+        let mut checked = 0;
+
+        for entry in std::fs::read_dir(&samples).unwrap() {
+            let path = entry.unwrap().path();
+            let id = path.file_stem().unwrap().to_string_lossy().to_string();
+            let profile = registry.get_application(&id)
+                .unwrap_or_else(|| panic!("Sample {} has no matching profile", path.display()));
+            let sample: serde_json::Value = serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+            assert!(profile.structure_matches_sample(&sample), "{} does not find servers in {}", id, path.display());
+            checked += 1;
+        }
+        assert!(checked >= 2);
+
+        // A Zed container is not found by an app that reads mcpServers
+        let claude = registry.get_application("claude-desktop").unwrap();
+        let zed_style = serde_json::json!({"context_servers": {"github": {"command": "npx"}}});
+        assert!(!claude.structure_matches_sample(&zed_style));
+    }
+
+    #[test]
+    fn test_embedded_registry_parses() {
+        let registry = ApplicationRegistry::embedded().unwrap();
+        for id in ApplicationRegistry::new().applications.keys() {
+            assert!(registry.get_application(id).is_some(), "embedded registry is missing {}", id);
+        }
+        assert_eq!(registry.metadata.application_count, registry.applications.len());
+        assert_eq!(registry.get_application("claude-desktop").unwrap().name, "Claude Desktop");
+    }
+
+    #[test]
+    fn test_claude_desktop_profile() {
+        let registry = ApplicationRegistry::new();
+        let claude = registry.get_application("claude-desktop").unwrap();
+        
+        assert_eq!(claude.name, "Claude Desktop");
+        assert_eq!(claude.bundle_id, "com.anthropic.claudedesktop");
+        assert_eq!(claude.config_format, ConfigFormat::Json);
+        assert!(claude.detection_strategy.use_bundle_lookup);
+    }
+
+    #[test]
+    fn test_add_remove_application() {
+        let mut registry = ApplicationRegistry::new();
+        let initial_count = registry.metadata.application_count;
+        
+        let custom_app = ApplicationProfile {
+            id: "test-app".to_string(),
+            name: "Test App".to_string(),
+            bundle_id: "com.test.app".to_string(),
+            config_path: "~/test/config.json".to_string(),
+            alt_config_paths: vec![],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec!["/Applications/Test.app".to_string()],
+            alt_executable_paths: vec![],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: true,
+                use_executable_check: true,
+                use_config_check: true,
+                use_spotlight: false,
+                priority_order: vec![DetectionMethod::BundleLookup],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Test Developer".to_string(),
+                category: ApplicationCategory::Other("Test".to_string()),
+                mcp_version: "1.0".to_string(),
+                notes: None,
+                requires_permissions: false,
+                ..Default::default()
+            },
+        };
+        
+        registry.add_application(custom_app);
+        assert_eq!(registry.metadata.application_count, initial_count + 1);
+        assert!(registry.get_application("test-app").is_some());
+        
+        let removed = registry.remove_application("test-app");
+        assert!(removed.is_some());
+        assert_eq!(registry.metadata.application_count, initial_count);
+        assert!(registry.get_application("test-app").is_none());
+    }
+
+    #[test]
+    fn test_get_applications_by_category() {
+        let registry = ApplicationRegistry::new();
+        let ides = registry.get_applications_by_category(&ApplicationCategory::IDE);
+        
+        assert!(!ides.is_empty());
+        assert!(ides.iter().any(|app| app.id == "cursor"));
+        assert!(ides.iter().any(|app| app.id == "zed"));
+    }
+
+    #[test]
+    fn test_custom_structure_paths() {
+        assert_eq!(ConfigStructure::parse_custom_path("chat.mcp.servers").unwrap(), vec!["chat", "mcp", "servers"]);
+        assert_eq!(ConfigStructure::parse_custom_path("/settings/mcp/servers").unwrap(), vec!["settings", "mcp", "servers"]);
+        assert_eq!(ConfigStructure::parse_custom_path("/tools~1mcp/a~0b").unwrap(), vec!["tools/mcp", "a~b"]);
+        for invalid in ["", "/", "chat..servers", "/settings//servers", "/a~2b", "/trailing~"] {
+            assert!(ConfigStructure::parse_custom_path(invalid).is_err(), "{:?}", invalid);
+        }
+
+        let mut profile = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        profile.config_structure = ConfigStructure::Custom("/settings/mcp/servers".to_string());
+        assert_eq!(profile.get_mcp_servers_path(), vec!["settings", "mcp", "servers"]);
+        assert!(profile.validate_config_structure(&serde_json::json!({ "settings": { "mcp": { "servers": {} } } })).is_ok());
+        assert!(profile.validate_config_structure(&serde_json::json!({ "settings": { "mcp": { "servers": [] } } })).is_err());
+        assert!(profile.validate_config_structure(&serde_json::json!({ "mcpServers": {} })).is_err());
+
+        // Invalid paths are rejected when the registry loads, naming the profile
+        profile.id = "acme-editor".to_string();
+        profile.config_structure = ConfigStructure::Custom("acme..servers".to_string());
+        let json = serde_json::json!({ "applications": [profile] }).to_string();
+        let err = ApplicationRegistry::from_json_str(&json).unwrap_err();
+        assert!(format!("{:#}", err).contains("acme-editor"), "{:#}", err);
+        let mut registry = ApplicationRegistry::new();
+        assert!(registry.merge_json_str(&json).is_err());
+        assert!(registry.get_application("acme-editor").is_none());
+    }
+
+    #[test]
+    fn test_double_nested_servers_detected_and_repaired() {
+        let registry = ApplicationRegistry::new();
+        let claude = registry.get_application("claude-desktop").unwrap();
+        let mut config = serde_json::json!({
+            "mcpServers": {
+                "mcpServers": {
+                    "memory": { "command": "npx", "args": ["-y", "@mcp/memory"] }
+                }
+            }
+        });
+
+        assert_eq!(claude.detect_structure_issues(&config), vec![StructureIssue::DoubleNested]);
+        let err = claude.validate_config_structure(&config).unwrap_err();
+        assert!(err.contains("MCP_DOUBLE_NESTED"));
+
+        let repaired = claude.repair_config_structure(&mut config);
+        assert_eq!(repaired, vec![StructureIssue::DoubleNested]);
+        assert_eq!(config["mcpServers"]["memory"]["command"], "npx");
+        assert!(claude.validate_config_structure(&config).is_ok());
+    }
+
+    #[test]
+    fn test_server_named_like_container_is_not_double_nested() {
+        let registry = ApplicationRegistry::new();
+        let claude = registry.get_application("claude-desktop").unwrap();
+        let zed = registry.get_application("zed").unwrap();
+
+        let mut config = serde_json::json!({
+            "mcpServers": {
+                "mcpServers": { "command": "npx", "args": ["-y", "@acme/servers-mcp"], "env": { "REGION": "eu" } }
+            }
+        });
+        assert!(claude.detect_structure_issues(&config).is_empty());
+        assert!(!claude.repair_structure_issue(&mut config, &StructureIssue::DoubleNested));
+        assert_eq!(config["mcpServers"]["mcpServers"]["command"], "npx");
+
+        // The same holds under a custom container key, for a server with only a transport type
+        let config = serde_json::json!({
+            "context_servers": {
+                "context_servers": { "type": "http", "headers": { "Authorization": "Bearer ${TOKEN}" } }
+            }
+        });
+        assert!(zed.detect_structure_issues(&config).is_empty());
+    }
+
+    #[test]
+    fn test_server_at_root_detected_and_repaired() {
+        let registry = ApplicationRegistry::new();
+        let idea = registry.get_application("jetbrains-idea").unwrap();
+        let mut config = serde_json::json!({
+            "editor.fontSize": 14,
+            "github": { "command": "npx", "args": ["-y", "@mcp/github"] }
+        });
+
+        let issues = idea.detect_structure_issues(&config);
+        assert_eq!(issues, vec![StructureIssue::ServersAtRoot(vec!["github".to_string()])]);
+        assert_eq!(issues[0].code(), "MCP_SERVER_AT_ROOT");
+
+        idea.repair_config_structure(&mut config);
+        assert!(config.get("github").is_none());
+        assert_eq!(config["mcp"]["servers"]["github"]["command"], "npx");
+        assert_eq!(config["editor.fontSize"], 14);
+        assert!(idea.detect_structure_issues(&config).is_empty());
+    }
+
+    #[test]
+    fn test_servers_array_detected_and_repaired() {
+        let registry = ApplicationRegistry::new();
+        let claude = registry.get_application("claude-desktop").unwrap();
+        let mut config = serde_json::json!({
+            "mcpServers": [
+                { "name": "memory", "command": "npx", "args": ["-y", "@mcp/memory"] },
+                { "url": "https://example.com/sse" }
+            ]
+        });
+
+        assert_eq!(claude.detect_structure_issues(&config), vec![StructureIssue::ServersArray]);
+
+        claude.repair_config_structure(&mut config);
+        assert_eq!(config["mcpServers"]["memory"]["command"], "npx");
+        assert!(config["mcpServers"]["memory"].get("name").is_none());
+        assert_eq!(config["mcpServers"]["server-2"]["url"], "https://example.com/sse");
+        assert!(claude.detect_structure_issues(&config).is_empty());
+    }
+
+    #[test]
+    fn test_detection_strategy_serialization() {
+        let strategy = DetectionStrategy {
+            use_bundle_lookup: true,
+            use_executable_check: false,
+            use_config_check: true,
+            use_spotlight: false,
+            priority_order: vec![
+                DetectionMethod::BundleLookup,
+                DetectionMethod::ConfigCheck,
+            ],
+        };
+        
+        let serialized = serde_json::to_string(&strategy).unwrap();
+        let deserialized: DetectionStrategy = serde_json::from_str(&serialized).unwrap();
+        
+        assert_eq!(strategy, deserialized);
+    }
+
+    #[test]
+    fn test_global_only_apps_have_no_workspace_config() {
+        let registry = ApplicationRegistry::new();
+        let workspace = std::path::Path::new("/projects/demo");
+
+        let amazon_q = registry.get_application("amazon-q").unwrap();
+        assert!(!amazon_q.metadata.supports_project_config);
+        assert_eq!(amazon_q.workspace_config_path(workspace), None);
+
+        let cursor = registry.get_application("cursor").unwrap();
+        assert_eq!(cursor.workspace_config_path(workspace), Some(workspace.join(".cursor/mcp.json")));
+    }
+
+    #[test]
+    fn test_find_claude_code_project_config() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let nested = temp_dir.path().join("packages/api/src");
+        std::fs::create_dir_all(&nested).unwrap();
+        std::fs::write(temp_dir.path().join(".mcp.json"), r#"{"mcpServers": {}}"#).unwrap();
+
+        let registry = ApplicationRegistry::new();
+        let claude_code = registry.get_application("claude-code").unwrap();
+        assert_eq!(claude_code.find_workspace_config(&nested), Some(temp_dir.path().join(".mcp.json")));
+
+        let cursor = registry.get_application("cursor").unwrap();
+        assert_eq!(cursor.find_workspace_config(&nested), None);
+    }
+
+    #[test]
+    fn test_dedupe_servers_merges_case_variants() {
+        let registry = ApplicationRegistry::new();
+        let claude = registry.get_application("claude-desktop").unwrap();
+        let mut config = serde_json::json!({
+            "mcpServers": {
+                "github": {"command": "npx"},
+                "GitHub": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-github"],
+                    "env": {"GITHUB_TOKEN": "abc"}
+                },
+                "GITHUB": {"command": "npx", "disabled": true},
+                "slack": {"command": "npx"}
+            }
+        });
+
+        let report = claude.dedupe_servers(&mut config);
+        assert_eq!(report.removed, vec!["GITHUB", "github"]);
+        assert_eq!(report.enabled_conflicts, vec!["GITHUB"]);
+
+        let servers = config["mcpServers"].as_object().unwrap();
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers["GitHub"]["env"]["GITHUB_TOKEN"], "abc");
+        // The kept entry stays enabled even though a duplicate was disabled
+        assert!(servers["GitHub"].get("disabled").is_none());
+        assert_eq!(claude.dedupe_servers(&mut config), DedupeReport::default());
+    }
+
+    #[test]
+    fn test_can_install_rejects_remote_server_for_stdio_only_app() {
+        let registry = ApplicationRegistry::new();
+        let claude = registry.get_application("claude-desktop").unwrap();
+        let cursor = registry.get_application("cursor").unwrap();
+
+        let mut docs = ServerAnalyzer::basic_config("docs", "");
+        docs.server_type = "http".to_string();
+        docs.url = Some("https://docs.example.com/mcp".to_string());
+
+        let reasons = claude.can_install(&docs).unwrap_err();
+        assert_eq!(reasons, vec!["Claude Desktop only supports local stdio servers, but docs is an HTTP server"]);
+        assert_eq!(cursor.can_install(&docs), Ok(()));
+
+        let mut sandboxed = cursor.clone();
+        sandboxed.metadata.unsupported_runtimes = vec![ServerRuntime::Docker];
+        let postgres = ServerAnalyzer::basic_config("postgres", "docker");
+        assert_eq!(sandboxed.can_install(&postgres).unwrap_err().len(), 1);
+        assert_eq!(cursor.can_install(&postgres), Ok(()));
+    }
+
+    #[test]
+    fn test_preview_remove_matches_removal() {
+        let registry = ApplicationRegistry::new();
+        let idea = registry.get_application("jetbrains-idea").unwrap();
+        let config = serde_json::json!({
+            "theme": "dark",
+            "mcpServers": {"github": {"command": "npx"}},
+            "mcp": {"servers": {"github": {"command": "npx"}, "fetch": {"command": "uvx"}}}
+        });
+
+        let (preview, would_remove) = idea.preview_remove(&config, "github");
+        let mut removed = config.clone();
+        assert!(would_remove && idea.remove_server(&mut removed, "github"));
+        assert_eq!(preview, removed);
+        assert_eq!(preview["mcpServers"]["github"]["command"], "npx");
+        assert!(preview["mcp"]["servers"].get("github").is_none());
+
+        assert_eq!(idea.preview_remove(&config, "missing"), (config.clone(), false));
+    }
+
+    #[test]
+    fn test_servers_path_prefers_flat_settings_key() {
+        let mut vscode = ApplicationRegistry::new().get_application("vscode").unwrap().clone();
+        vscode.metadata.version = Some("1.102.0".to_string());
+        let path = vscode.servers_path();
+        assert_eq!(path.flat_key.as_deref(), Some(VSCODE_CHAT_MCP_SERVERS));
+
+        // VS Code's own spelling is one flat key
+        let mut flat = serde_json::json!({ "chat.mcp.servers": { "github": { "command": "npx" } } });
+        assert_eq!(path.keys_in(&flat), vec![VSCODE_CHAT_MCP_SERVERS]);
+        assert!(vscode.structure_matches_sample(&flat));
+        assert!(vscode.remove_server(&mut flat, "github"));
+        assert_eq!(flat, serde_json::json!({ "chat.mcp.servers": {} }));
+
+        // Nested objects are used only when the config already has them
+        let nested = serde_json::json!({ "chat": { "mcp": { "servers": { "fetch": { "command": "uvx" } } } } });
+        assert_eq!(path.keys_in(&nested), vec!["chat", "mcp", "servers"]);
+        assert!(path.get(&nested).unwrap().contains_key("fetch"));
+
+        // A config with neither gets the flat key
+        let mut empty = serde_json::json!({ "editor.fontSize": 14 });
+        path.get_or_insert(&mut empty).insert("github".to_string(), serde_json::json!({ "command": "npx" }));
+        assert_eq!(empty[VSCODE_CHAT_MCP_SERVERS]["github"]["command"], "npx");
+        assert!(empty.get("chat").is_none());
+
+        // JSON pointers and built-in structures have no flat spelling
+        let claude = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        assert_eq!(claude.servers_path(), ServersPath::nested(vec!["mcpServers".to_string()]));
+    }
+}
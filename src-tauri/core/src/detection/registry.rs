@@ -1,4 +1,5 @@
-use crate::detection::profiles::{ApplicationProfile, ApplicationRegistry, ConfigFormat, DetectionStrategy, DetectionMethod, ApplicationCategory, ApplicationMetadata};
+use crate::detection::profiles::{ApplicationProfile, ApplicationRegistry, ConfigFormat, ConfigStructure, DetectionStrategy, DetectionMethod, ApplicationCategory, ApplicationMetadata};
+use crate::filesystem::PathUtils;
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -321,6 +322,20 @@ impl ManualRegistryManager {
             .or_else(|| self.base_registry.get_application(id))
     }
 
+    /// The registry as the app sees it: built-in profiles with custom applications layered on top
+    pub fn effective_registry(&self) -> ApplicationRegistry {
+        let mut registry = self.base_registry.clone();
+        for profile in self.custom_applications.values() {
+            registry.add_application(profile.clone());
+        }
+        registry
+    }
+
+    /// Canonical export of the effective registry, see [`ApplicationRegistry::export_registry_canonical`]
+    pub fn export_registry_canonical(&self) -> Result<String> {
+        self.effective_registry().export_registry_canonical()
+    }
+
     /// Check if an application ID is available
     pub fn is_id_available(&self, id: &str) -> bool {
         self.base_registry.get_application(id).is_none() && 
@@ -389,7 +404,7 @@ impl ManualRegistryManager {
         }
 
         // Check if paths exist (non-blocking)
-        let expanded_config_path = self.expand_path(&request.config_path);
+        let expanded_config_path = PathUtils::expand_tilde(&request.config_path);
         if let Ok(path) = expanded_config_path {
             if !path.exists() {
                 warnings.push(ValidationWarning {
@@ -461,6 +476,7 @@ impl ManualRegistryManager {
             config_path: request.config_path,
             alt_config_paths: request.alt_config_paths,
             config_format: request.config_format,
+            config_structure: ConfigStructure::DirectMcpServers,
             executable_paths: request.executable_paths,
             alt_executable_paths: request.alt_executable_paths,
             detection_strategy,
@@ -471,22 +487,11 @@ impl ManualRegistryManager {
                 mcp_version: request.mcp_version,
                 notes: request.notes,
                 requires_permissions: request.requires_permissions,
+                ..Default::default()
             },
         })
     }
 
-    fn expand_path(&self, path: &str) -> Result<PathBuf> {
-        if path.starts_with('~') {
-            if let Some(home) = dirs::home_dir() {
-                Ok(home.join(&path[2..]))
-            } else {
-                Err(anyhow::anyhow!("Could not find home directory"))
-            }
-        } else {
-            Ok(PathBuf::from(path))
-        }
-    }
-
     fn get_category_breakdown(&self) -> HashMap<String, usize> {
         let mut categories = HashMap::new();
         
@@ -494,6 +499,9 @@ impl ManualRegistryManager {
             let category_name = match &app.metadata.category {
                 ApplicationCategory::CodeEditor => "Code Editor".to_string(),
                 ApplicationCategory::IDE => "IDE".to_string(),
+                ApplicationCategory::AIAssistant => "AI Assistant".to_string(),
+                ApplicationCategory::DeveloperTool => "Developer Tool".to_string(),
+                ApplicationCategory::Terminal => "Terminal".to_string(),
                 ApplicationCategory::ChatClient => "Chat Client".to_string(),
                 ApplicationCategory::ProductivityTool => "Productivity Tool".to_string(),
                 ApplicationCategory::Other(name) => name.clone(),
@@ -681,6 +689,7 @@ mod tests {
             config_path: "~/test/config.json".to_string(),
             alt_config_paths: vec![],
             config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
             executable_paths: vec![],
             alt_executable_paths: vec![],
             detection_strategy: DetectionStrategy {
@@ -697,6 +706,7 @@ mod tests {
                 mcp_version: "1.0".to_string(),
                 notes: None,
                 requires_permissions: false,
+                ..Default::default()
             },
         };
         
@@ -732,6 +742,7 @@ mod tests {
                 config_path: "~/test/config.json".to_string(),
                 alt_config_paths: vec![],
                 config_format: ConfigFormat::Json,
+                config_structure: ConfigStructure::DirectMcpServers,
                 executable_paths: vec![],
                 alt_executable_paths: vec![],
                 detection_strategy: DetectionStrategy {
@@ -748,6 +759,7 @@ mod tests {
                     mcp_version: "1.0".to_string(),
                     notes: None,
                     requires_permissions: false,
+                    ..Default::default()
                 },
             };
             
@@ -1,10 +1,12 @@
+use crate::configuration::provenance::{ManagedStatus, ProvenanceSidecar};
 use crate::detection::profiles::ApplicationProfile;
 use crate::detection::detector::{ApplicationDetector, DetectionResult};
 use crate::detection::registry::ManualRegistryManager;
 use crate::detection::validator::{ConfigValidator, ConfigValidationResult};
+use crate::filesystem::{ManagedConfigDetector, ManagedConfigState};
 use anyhow::{Context, Result};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::path::PathBuf;
 
 /// Comprehensive application detection report generator
@@ -15,6 +17,8 @@ pub struct ReportGenerator {
     validator: ConfigValidator,
     /// Manual registry for custom applications
     registry: ManualRegistryManager,
+    /// Provenance of server entries MCP Control wrote to clients that reject extra keys
+    sidecar: ProvenanceSidecar,
 }
 
 /// Complete application detection report
@@ -107,6 +111,12 @@ pub struct ApplicationReport {
     pub status: ApplicationStatus,
     /// Recommendations for this application
     pub recommendations: Vec<String>,
+    /// Whether MDM or policy controls the application's config
+    #[serde(default)]
+    pub managed: ManagedConfigState,
+    /// Whether each configured server was written by MCP Control
+    #[serde(default)]
+    pub server_provenance: BTreeMap<String, ManagedStatus>,
 }
 
 /// Overall status of an application
@@ -146,10 +156,17 @@ impl ReportGenerator {
         let validator = ConfigValidator::new()?;
         let registry = ManualRegistryManager::new();
         
+        // A missing sidecar is empty; an unreadable one only costs the provenance column
+        let sidecar = ProvenanceSidecar::load_default().unwrap_or_else(|e| {
+            log::warn!("Could not load provenance sidecar: {}", e);
+            ProvenanceSidecar::default()
+        });
+        
         Ok(Self {
             detector,
             validator,
             registry,
+            sidecar,
         })
     }
 
@@ -204,7 +221,41 @@ impl ReportGenerator {
         let status = self.determine_application_status(&detection, &validation);
 
         // Generate recommendations
-        let recommendations = self.generate_application_recommendations(&detection, &validation, &status);
+        let mut recommendations = self.generate_application_recommendations(&detection, &validation, &status);
+
+        // Check whether syncing to this application would be overwritten by MDM or policy
+        let managed = match self.validator.resolve_active_config(app).await {
+            Ok(path) if path.exists() => ManagedConfigDetector::new().detect(&path, Some(&app.bundle_id)),
+            _ => ManagedConfigState::default(),
+        };
+        if managed.is_externally_managed() {
+            recommendations.push(format!(
+                "Configuration is externally managed ({}) - changes made here will be overwritten",
+                managed.reasons().join("; ")
+            ));
+        }
+
+        let server_provenance: BTreeMap<String, ManagedStatus> = if detection.detected {
+            match self.validator.read_managed_statuses(app, &self.sidecar).await {
+                Ok(statuses) => statuses.into_iter().collect(),
+                Err(e) => {
+                    log::warn!("Could not read server provenance for {}: {}", app.name, e);
+                    BTreeMap::new()
+                }
+            }
+        } else {
+            BTreeMap::new()
+        };
+        let edited: Vec<&str> = server_provenance.iter()
+            .filter(|(_, status)| **status == ManagedStatus::ModifiedExternally)
+            .map(|(name, _)| name.as_str())
+            .collect();
+        if !edited.is_empty() {
+            recommendations.push(format!(
+                "Servers written by MCP Control were edited by hand ({}) - the next sync will overwrite those edits",
+                edited.join(", ")
+            ));
+        }
 
         Ok(ApplicationReport {
             profile: app.clone(),
@@ -212,6 +263,8 @@ impl ReportGenerator {
             validation,
             status,
             recommendations,
+            managed,
+            server_provenance,
         })
     }
 
@@ -335,6 +388,9 @@ impl ReportGenerator {
                 crate::detection::profiles::ApplicationCategory::ChatClient => "ChatClient",
                 crate::detection::profiles::ApplicationCategory::CodeEditor => "CodeEditor",
                 crate::detection::profiles::ApplicationCategory::IDE => "IDE",
+                crate::detection::profiles::ApplicationCategory::AIAssistant => "AIAssistant",
+                crate::detection::profiles::ApplicationCategory::DeveloperTool => "DeveloperTool",
+                crate::detection::profiles::ApplicationCategory::Terminal => "Terminal",
                 crate::detection::profiles::ApplicationCategory::ProductivityTool => "ProductivityTool",
                 crate::detection::profiles::ApplicationCategory::Other(name) => name,
             };
@@ -462,11 +518,20 @@ impl ReportGenerator {
             output.push_str(&format!("\n{} ({})\n", app_report.profile.name, app_report.profile.id));
             output.push_str(&format!("  Status: {:?}\n", app_report.status));
             output.push_str(&format!("  Detected: {}\n", app_report.detection.detected));
+            if app_report.managed.is_externally_managed() {
+                output.push_str("  Externally Managed: yes\n");
+                for reason in app_report.managed.reasons() {
+                    output.push_str(&format!("    - {}\n", reason));
+                }
+            }
             
             if let Some(validation) = &app_report.validation {
                 output.push_str(&format!("  Config Valid: {}\n", validation.is_valid));
                 output.push_str(&format!("  MCP Servers: {}\n", validation.mcp_servers.len()));
             }
+            if !app_report.server_provenance.is_empty() {
+                output.push_str(&format!("  Provenance: {}\n", provenance_summary(&app_report.server_provenance)));
+            }
             
             if !app_report.recommendations.is_empty() {
                 output.push_str("  Recommendations:\n");
@@ -517,11 +582,17 @@ impl ReportGenerator {
             output.push_str(&format!("### {} ({})\n\n", app_report.profile.name, app_report.profile.id));
             output.push_str(&format!("- **Status:** {:?}\n", app_report.status));
             output.push_str(&format!("- **Detected:** {}\n", app_report.detection.detected));
+            if app_report.managed.is_externally_managed() {
+                output.push_str(&format!("- **Externally Managed:** {}\n", app_report.managed.reasons().join("; ")));
+            }
             
             if let Some(validation) = &app_report.validation {
                 output.push_str(&format!("- **Config Valid:** {}\n", validation.is_valid));
                 output.push_str(&format!("- **MCP Servers:** {}\n", validation.mcp_servers.len()));
             }
+            if !app_report.server_provenance.is_empty() {
+                output.push_str(&format!("- **Provenance:** {}\n", provenance_summary(&app_report.server_provenance)));
+            }
             
             if !app_report.recommendations.is_empty() {
                 output.push_str("\n**Recommendations:**\n");
@@ -544,6 +615,22 @@ impl ReportGenerator {
     }
 }
 
+/// Server counts by provenance, e.g. "2 managed, 1 modified externally"
+fn provenance_summary(statuses: &BTreeMap<String, ManagedStatus>) -> String {
+    [
+        (ManagedStatus::Managed, "managed"),
+        (ManagedStatus::ModifiedExternally, "modified externally"),
+        (ManagedStatus::Unmanaged, "unmanaged"),
+    ]
+    .iter()
+    .filter_map(|(status, label)| {
+        let count = statuses.values().filter(|s| *s == status).count();
+        (count > 0).then(|| format!("{} {}", count, label))
+    })
+    .collect::<Vec<_>>()
+    .join(", ")
+}
+
 impl Default for ReportGenerator {
     fn default() -> Self {
         Self::new().expect("Failed to create default ReportGenerator")
@@ -553,7 +640,7 @@ impl Default for ReportGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::detection::profiles::{ApplicationCategory, ApplicationMetadata, DetectionStrategy, DetectionMethod, ConfigFormat};
+    use crate::detection::profiles::{ApplicationCategory, ApplicationMetadata, DetectionStrategy, DetectionMethod, ConfigFormat, ConfigStructure};
     use crate::detection::detector::DetectionPaths;
     use crate::detection::validator::{McpServerConfig, ServerType, ServerMetadata, ConfigSource};
     use std::collections::HashMap;
@@ -566,6 +653,7 @@ mod tests {
             config_path: "~/test/config.json".to_string(),
             alt_config_paths: vec![],
             config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
             executable_paths: vec!["/Applications/Test.app".to_string()],
             alt_executable_paths: vec![],
             detection_strategy: DetectionStrategy {
@@ -582,6 +670,7 @@ mod tests {
                 mcp_version: "1.0".to_string(),
                 notes: None,
                 requires_permissions: false,
+                ..Default::default()
             },
         }
     }
@@ -599,6 +688,7 @@ mod tests {
             confidence: if is_detected { 0.95 } else { 0.0 },
             messages: vec![],
             detected_at: chrono::Utc::now(),
+            format_conversion: None,
         }
     }
 
@@ -611,6 +701,8 @@ mod tests {
                 args: vec![],
                 env: HashMap::new(),
                 cwd: None,
+                inherit_env: true,
+                extra_options: HashMap::new(),
                 server_type: ServerType::Stdio,
                 metadata: ServerMetadata {
                     description: None,
@@ -631,6 +723,7 @@ mod tests {
             mcp_servers,
             messages: vec![],
             raw_config: None,
+            env_file_vars: HashMap::new(),
             validated_at: chrono::Utc::now(),
         }
     }
@@ -713,6 +806,8 @@ mod tests {
                 validation: Some(create_test_validation_result(true, 2)),
                 status: ApplicationStatus::FullyFunctional,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
             ApplicationReport {
                 profile: create_test_application(),
@@ -720,6 +815,8 @@ mod tests {
                 validation: None,
                 status: ApplicationStatus::NotInstalled,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
         ];
 
@@ -745,6 +842,8 @@ mod tests {
                 validation: None,
                 status: ApplicationStatus::NotInstalled,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
             ApplicationReport {
                 profile: create_test_application(),
@@ -752,6 +851,8 @@ mod tests {
                 validation: None,
                 status: ApplicationStatus::NotInstalled,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
         ];
 
@@ -767,6 +868,8 @@ mod tests {
                 validation: Some(create_test_validation_result(true, 0)),
                 status: ApplicationStatus::InstalledNoConfig,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
         ];
 
@@ -895,6 +998,8 @@ mod tests {
                 validation: Some(create_test_validation_result(true, 1)),
                 status: ApplicationStatus::FullyFunctional,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
             ApplicationReport {
                 profile: create_test_application(),
@@ -902,6 +1007,8 @@ mod tests {
                 validation: Some(create_test_validation_result(false, 0)),
                 status: ApplicationStatus::InstalledInvalidConfig,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
             ApplicationReport {
                 profile: create_test_application(),
@@ -909,6 +1016,8 @@ mod tests {
                 validation: None,
                 status: ApplicationStatus::NotInstalled,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
         ];
 
@@ -938,6 +1047,8 @@ mod tests {
                 validation: Some(create_test_validation_result(true, 1)),
                 status: ApplicationStatus::FullyFunctional,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
             ApplicationReport {
                 profile: app2,
@@ -949,6 +1060,8 @@ mod tests {
                 }),
                 status: ApplicationStatus::FullyFunctional,
                 recommendations: vec![],
+                managed: ManagedConfigState::default(),
+                server_provenance: BTreeMap::new(),
             },
         ];
 
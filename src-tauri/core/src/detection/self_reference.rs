@@ -0,0 +1,282 @@
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+
+use super::validator::McpServerConfig;
+
+/// Executable names MCP Control ships under
+const SELF_NAMES: &[&str] = &["mcpctl", "mcp-control"];
+
+/// Interpreters whose first non-flag argument is a script we can read
+const INTERPRETERS: &[&str] = &["sh", "bash", "zsh", "dash", "fish", "cmd", "powershell", "pwsh", "python", "python3", "node"];
+
+/// Why a server entry would end up launching MCP Control or itself
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SelfReference {
+    #[error("Server '{server}' launches MCP Control itself ({command}), which would loop back into this configuration")]
+    OwnExecutable { server: String, command: String },
+    #[error("Server '{server}' runs wrapper {wrapper} which reads the managed config {config}")]
+    ReadsManagedConfig { server: String, wrapper: String, config: String },
+    #[error("Servers {} launch each other's wrapper scripts", servers.join(" -> "))]
+    Cycle { servers: Vec<String> },
+}
+
+/// A shell script or inline command sitting between the client and the real server
+#[derive(Debug, Clone, PartialEq)]
+pub struct WrapperScript {
+    /// File the script was read from; `None` for `sh -c` style inline commands
+    pub path: Option<PathBuf>,
+    pub content: String,
+}
+
+impl WrapperScript {
+    /// Words of the script that look like commands or paths, quotes stripped
+    pub fn tokens(&self) -> impl Iterator<Item = &str> {
+        self.content
+            .lines()
+            .map(|line| line.split('#').next().unwrap_or(""))
+            .flat_map(|line| line.split(|c: char| c.is_whitespace() || c == ';' || c == '|' || c == '&'))
+            .map(|token| token.trim_matches(|c| c == '"' || c == '\'' || c == '`' || c == '(' || c == ')'))
+            .filter(|token| !token.is_empty())
+    }
+}
+
+/// Read the wrapper script a server launches through, if any
+pub fn inspect_wrapper(server: &McpServerConfig) -> Option<WrapperScript> {
+    let command = server.command.as_deref()?;
+
+    if INTERPRETERS.contains(&executable_name(command).as_str()) {
+        let mut args = server.args.iter();
+        while let Some(arg) = args.next() {
+            if matches!(arg.as_str(), "-c" | "/c" | "/C" | "-Command" | "-e") {
+                return args.next().map(|inline| WrapperScript { path: None, content: inline.clone() });
+            }
+            if arg.starts_with('-') || arg.starts_with('/') && arg.len() == 2 {
+                continue;
+            }
+            return read_script(Path::new(arg));
+        }
+        return None;
+    }
+
+    read_script(Path::new(command)).filter(|script| script.content.starts_with("#!"))
+}
+
+fn read_script(path: &Path) -> Option<WrapperScript> {
+    if !path.is_file() {
+        return None;
+    }
+    let content = std::fs::read_to_string(path).ok()?;
+    Some(WrapperScript { path: Some(path.to_path_buf()), content })
+}
+
+/// Basename without a Windows `.exe` suffix, lowercased for comparison
+fn executable_name(command: &str) -> String {
+    let name = command.rsplit(['/', '\\']).next().unwrap_or(command).to_lowercase();
+    name.strip_suffix(".exe").map(str::to_string).unwrap_or(name)
+}
+
+fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+/// Rejects entries that would launch MCP Control or re-read the config it manages
+#[derive(Debug, Clone)]
+pub struct SelfReferenceGuard {
+    executables: Vec<PathBuf>,
+    managed_configs: Vec<PathBuf>,
+}
+
+impl Default for SelfReferenceGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SelfReferenceGuard {
+    /// Guard against the running executable and the `mcpctl` names
+    pub fn new() -> Self {
+        Self {
+            executables: std::env::current_exe().into_iter().collect(),
+            managed_configs: Vec::new(),
+        }
+    }
+
+    /// Treat another path as MCP Control's own executable
+    pub fn with_executable(mut self, path: impl Into<PathBuf>) -> Self {
+        self.executables.push(path.into());
+        self
+    }
+
+    /// Config file being validated or written; wrappers mentioning it are rejected
+    pub fn with_managed_config(mut self, path: impl Into<PathBuf>) -> Self {
+        self.managed_configs.push(path.into());
+        self
+    }
+
+    /// Check one entry on its own
+    pub fn check_server(&self, server: &McpServerConfig) -> Option<SelfReference> {
+        let command = server.command.as_deref()?;
+        if self.is_own_executable(command) {
+            return Some(SelfReference::OwnExecutable { server: server.name.clone(), command: command.to_string() });
+        }
+
+        let wrapper = inspect_wrapper(server)?;
+        let wrapper_name = wrapper.path.as_ref().map(|p| p.display().to_string()).unwrap_or_else(|| "inline command".to_string());
+        if let Some(token) = wrapper.tokens().find(|token| self.is_own_executable(token)) {
+            return Some(SelfReference::OwnExecutable {
+                server: server.name.clone(),
+                command: format!("{} via {}", token, wrapper_name),
+            });
+        }
+        self.managed_configs
+            .iter()
+            .find(|config| config_spellings(config).iter().any(|spelling| wrapper.content.contains(spelling.as_str())))
+            .map(|config| SelfReference::ReadsManagedConfig {
+                server: server.name.clone(),
+                wrapper: wrapper_name,
+                config: config.display().to_string(),
+            })
+    }
+
+    /// Check every entry, then look for wrapper scripts that launch each other
+    pub fn check_servers(&self, servers: &[McpServerConfig]) -> Vec<SelfReference> {
+        let mut findings: Vec<SelfReference> = servers.iter().filter_map(|server| self.check_server(server)).collect();
+
+        let wrappers: Vec<(&str, WrapperScript)> = servers
+            .iter()
+            .filter_map(|server| inspect_wrapper(server).map(|wrapper| (server.name.as_str(), wrapper)))
+            .collect();
+        let mut edges: HashMap<&str, Vec<&str>> = HashMap::new();
+        for (name, wrapper) in &wrappers {
+            let base = wrapper.path.as_deref().and_then(Path::parent).unwrap_or(Path::new("."));
+            for (other, target) in &wrappers {
+                let Some(target_path) = target.path.as_deref() else { continue };
+                if name == other {
+                    continue;
+                }
+                if wrapper.tokens().any(|token| same_file(&base.join(token), target_path)) {
+                    edges.entry(name).or_default().push(other);
+                }
+            }
+        }
+
+        let mut reported: HashSet<Vec<&str>> = HashSet::new();
+        for (start, _) in &wrappers {
+            if let Some(mut cycle) = find_cycle(start, &edges) {
+                // Rotate so the same loop found from another entry is reported once
+                let min = (0..cycle.len()).min_by_key(|&i| cycle[i]).unwrap_or(0);
+                cycle.rotate_left(min);
+                if reported.insert(cycle.clone()) {
+                    let mut servers: Vec<String> = cycle.iter().map(|s| s.to_string()).collect();
+                    servers.push(servers[0].clone());
+                    findings.push(SelfReference::Cycle { servers });
+                }
+            }
+        }
+        findings
+    }
+
+    fn is_own_executable(&self, command: &str) -> bool {
+        let name = executable_name(command);
+        SELF_NAMES.contains(&name.as_str())
+            || self.executables.iter().any(|exe| {
+                (command.contains(['/', '\\']) && same_file(Path::new(command), exe))
+                    || exe.file_name().map(|f| executable_name(&f.to_string_lossy())) == Some(name.clone())
+            })
+    }
+}
+
+/// Path spelled out, with `~` and `$HOME` forms a script might use instead
+fn config_spellings(path: &Path) -> Vec<String> {
+    let mut spellings = vec![path.display().to_string()];
+    if let Some(relative) = dirs::home_dir().and_then(|home| path.strip_prefix(home).ok().map(Path::to_path_buf)) {
+        spellings.push(format!("~/{}", relative.display()));
+        spellings.push(format!("$HOME/{}", relative.display()));
+    }
+    spellings
+}
+
+/// A loop through `start` in the wrapper graph, as the list of entries on it
+fn find_cycle<'a>(start: &'a str, edges: &HashMap<&'a str, Vec<&'a str>>) -> Option<Vec<&'a str>> {
+    fn walk<'a>(node: &'a str, start: &'a str, edges: &HashMap<&'a str, Vec<&'a str>>, path: &mut Vec<&'a str>) -> bool {
+        for &next in edges.get(node).into_iter().flatten() {
+            if next == start {
+                return true;
+            }
+            if !path.contains(&next) {
+                path.push(next);
+                if walk(next, start, edges, path) {
+                    return true;
+                }
+                path.pop();
+            }
+        }
+        false
+    }
+
+    let mut path = vec![start];
+    walk(start, start, edges, &mut path).then_some(path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{ConfigSource, ServerMetadata, ServerType};
+
+    fn server(name: &str, command: &str, args: &[&str]) -> McpServerConfig {
+        McpServerConfig {
+            name: name.to_string(),
+            command: Some(command.to_string()),
+            args: args.iter().map(|a| a.to_string()).collect(),
+            env: HashMap::new(),
+            cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
+            server_type: ServerType::Stdio,
+            metadata: ServerMetadata {
+                description: None,
+                version: None,
+                author: None,
+                capabilities: Vec::new(),
+                enabled: true,
+                source: ConfigSource::MainConfig,
+            },
+        }
+    }
+
+    #[test]
+    fn test_direct_self_reference_is_blocked() {
+        let guard = SelfReferenceGuard::new();
+        let finding = guard.check_server(&server("loop", "/usr/local/bin/mcpctl", &["serve"]));
+        assert!(matches!(finding, Some(SelfReference::OwnExecutable { .. })));
+
+        // Similar names are other tools, not us
+        assert_eq!(guard.check_server(&server("lint", "mcpctl-lint", &[])), None);
+    }
+
+    #[test]
+    fn test_wrapper_reading_managed_config_and_cycles() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("claude_desktop_config.json");
+        let reader = dir.path().join("reader.sh");
+        std::fs::write(&reader, format!("#!/bin/sh\njq . {}\n", config.display())).unwrap();
+        let a = dir.path().join("a.sh");
+        let b = dir.path().join("b.sh");
+        std::fs::write(&a, "#!/bin/sh\nexec ./b.sh\n").unwrap();
+        std::fs::write(&b, "#!/bin/sh\nexec ./a.sh \"$@\"\n").unwrap();
+
+        let guard = SelfReferenceGuard::new().with_managed_config(&config);
+        let findings = guard.check_servers(&[
+            server("reader", "bash", &[reader.to_str().unwrap()]),
+            server("a", a.to_str().unwrap(), &[]),
+            server("b", b.to_str().unwrap(), &[]),
+        ]);
+
+        assert!(matches!(&findings[0], SelfReference::ReadsManagedConfig { server, .. } if server == "reader"));
+        assert_eq!(findings.len(), 2);
+        assert_eq!(findings[1], SelfReference::Cycle { servers: vec!["a".into(), "b".into(), "a".into()] });
+    }
+}
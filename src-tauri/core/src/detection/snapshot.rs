@@ -0,0 +1,97 @@
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use super::profiles::ApplicationProfile;
+use crate::filesystem::ConfigFileService;
+
+/// The exact bytes of an application config at one moment, for a one-step undo.
+///
+/// Unlike timestamped backups, a snapshot lives only as long as the caller keeps it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigSnapshot {
+    pub app_id: String,
+    pub config_path: PathBuf,
+    /// File content, or None when the file did not exist; restoring then removes it
+    pub content: Option<Vec<u8>>,
+    pub taken_at: DateTime<Utc>,
+}
+
+impl ApplicationProfile {
+    /// Capture the current bytes of one of this application's config files
+    pub fn snapshot(&self, config_path: &Path) -> Result<ConfigSnapshot> {
+        let content = match fs::read(config_path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to snapshot {}", config_path.display()))
+            }
+        };
+
+        Ok(ConfigSnapshot {
+            app_id: self.id.clone(),
+            config_path: config_path.to_path_buf(),
+            content,
+            taken_at: Utc::now(),
+        })
+    }
+
+    /// Put a config file back exactly as it was when the snapshot was taken.
+    ///
+    /// The bytes are written atomically, so a failed restore leaves the current file intact.
+    pub fn restore(&self, snapshot: &ConfigSnapshot) -> Result<()> {
+        if snapshot.app_id != self.id {
+            bail!("Snapshot belongs to {}, not {}", snapshot.app_id, self.id);
+        }
+        let path = &snapshot.config_path;
+
+        let Some(content) = &snapshot.content else {
+            return match fs::remove_file(path) {
+                Ok(()) => Ok(()),
+                Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+                Err(e) => Err(e).with_context(|| format!("Failed to remove {}", path.display())),
+            };
+        };
+
+        ConfigFileService::write_atomic(path, content)
+            .with_context(|| format!("Failed to restore {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::ApplicationRegistry;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_restore_returns_original_bytes() {
+        let temp_dir = TempDir::new().unwrap();
+        let registry = ApplicationRegistry::new();
+        let claude = registry.get_application("claude-desktop").unwrap();
+
+        // Formatting and key order are part of what comes back
+        let path = temp_dir.path().join("claude_desktop_config.json");
+        let original = "{\n    \"mcpServers\": {\"github\": {\"command\": \"npx\"}},\n    \"theme\": \"dark\"\n}\n";
+        fs::write(&path, original).unwrap();
+
+        let snapshot = claude.snapshot(&path).unwrap();
+        fs::write(&path, r#"{"mcpServers": {}}"#).unwrap();
+        claude.restore(&snapshot).unwrap();
+        assert_eq!(fs::read(&path).unwrap(), original.as_bytes());
+        assert!(!ConfigFileService::temp_path(&path).exists());
+
+        // Restoring a snapshot of a missing file removes what was created since
+        let created = temp_dir.path().join("mcp.json");
+        let snapshot = claude.snapshot(&created).unwrap();
+        assert!(snapshot.content.is_none());
+        fs::write(&created, "{}").unwrap();
+        claude.restore(&snapshot).unwrap();
+        assert!(!created.exists());
+
+        let cursor = registry.get_application("cursor").unwrap();
+        assert!(cursor.restore(&claude.snapshot(&path).unwrap()).is_err());
+    }
+}
@@ -0,0 +1,363 @@
+use std::fs;
+use std::path::PathBuf;
+use anyhow::{anyhow, bail, Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+
+use super::profiles::ApplicationProfile;
+use super::validator::{ConfigValidator, ValidationMessage};
+use crate::configuration::create_backup_before_write;
+use crate::filesystem::ConfigFileService;
+
+/// Bundled issue-code to suggestion table
+const BUNDLED_RULES: &str = include_str!("../../resources/suggestions.json");
+
+/// Something the user can do about an issue
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum SuggestedAction {
+    /// Explanation or manual steps
+    Info { text: String },
+    /// Documentation or settings page to open
+    Link { label: String, url: String },
+    /// An automatic repair; run it through [`SuggestionEngine::plan_fix`] and confirm the plan
+    Fix {
+        title: String,
+        operation: FixOperation,
+        /// Server the fix applies to, filled in from the suggestion context
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        server: Option<String>,
+    },
+}
+
+/// Existing repairs a suggestion can run
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum FixOperation {
+    /// Apply the automatic repair for malformed server containers
+    RepairStructure,
+    /// Replace a server's bare command with the absolute path found on PATH
+    AbsoluteCommand,
+    /// Create the config file with an empty servers block
+    CreateSkeletonConfig,
+}
+
+/// One row of the suggestion table; rules scoped to a profile or platform only apply there
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestionRule {
+    pub code: String,
+    #[serde(default)]
+    pub profile: Option<String>,
+    /// Value of `std::env::consts::OS`, e.g. "macos"
+    #[serde(default)]
+    pub platform: Option<String>,
+    pub actions: Vec<SuggestedAction>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct SuggestionTable {
+    rules: Vec<SuggestionRule>,
+}
+
+/// Where an issue was raised
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SuggestionContext {
+    pub profile_id: String,
+    pub platform: String,
+    /// Server the issue is about, if any
+    pub server: Option<String>,
+}
+
+impl SuggestionContext {
+    /// Context for an application on the current platform
+    pub fn for_profile(profile: &ApplicationProfile) -> Self {
+        Self {
+            profile_id: profile.id.clone(),
+            platform: std::env::consts::OS.to_string(),
+            server: None,
+        }
+    }
+
+    /// Set the server the issue is about
+    pub fn with_server(mut self, server: &str) -> Self {
+        self.server = Some(server.to_string());
+        self
+    }
+}
+
+/// Pending write to one file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PlannedWrite {
+    pub path: PathBuf,
+    /// Content when the plan was made; None if the file did not exist
+    pub original: Option<String>,
+    pub updated: String,
+}
+
+/// Returned when a file changed between planning a fix and applying it
+#[derive(Debug, thiserror::Error)]
+#[error("{path} changed since the fix was planned; review the plan again")]
+pub struct StalePlan {
+    pub path: PathBuf,
+}
+
+/// File writes a fix would make, shown to the user before anything is touched
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ChangePlan {
+    pub description: String,
+    pub writes: Vec<PlannedWrite>,
+}
+
+impl ChangePlan {
+    /// Perform the writes after the user confirmed the plan.
+    ///
+    /// Fails with [`StalePlan`] without writing anything if any file no longer matches
+    /// what the plan was made from, and with `ExternallyManaged` if `file_service` refuses
+    /// one of the files. Existing files are backed up to the service's backup directory
+    /// first; already-written files are restored if a later write fails.
+    pub async fn apply(&self, file_service: &ConfigFileService) -> Result<()> {
+        for write in &self.writes {
+            let current = fs::read_to_string(&write.path).ok();
+            if current != write.original {
+                return Err(StalePlan { path: write.path.clone() }.into());
+            }
+            file_service.ensure_writable(&write.path)?;
+        }
+        for write in &self.writes {
+            create_backup_before_write(&write.path, file_service.backup_dir()).await?;
+        }
+
+        for (index, write) in self.writes.iter().enumerate() {
+            if let Err(e) = ConfigFileService::write_atomic(&write.path, &write.updated) {
+                for done in &self.writes[..index] {
+                    let restored = match &done.original {
+                        Some(original) => fs::write(&done.path, original),
+                        None => fs::remove_file(&done.path),
+                    };
+                    if let Err(restore) = restored {
+                        log::error!("Failed to roll back {}: {}", done.path.display(), restore);
+                    }
+                }
+                return Err(e);
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Maps issue codes to suggested actions using a resources-driven table
+#[derive(Debug, Clone)]
+pub struct SuggestionEngine {
+    rules: Vec<SuggestionRule>,
+}
+
+impl SuggestionEngine {
+    /// Create an engine using the bundled table
+    pub fn new() -> Self {
+        Self::from_json(BUNDLED_RULES).expect("Bundled suggestion table is invalid")
+    }
+
+    /// Create an engine from a table in JSON form
+    pub fn from_json(content: &str) -> Result<Self> {
+        let table: SuggestionTable = serde_json::from_str(content).context("Failed to parse suggestion table")?;
+        Ok(Self { rules: table.rules })
+    }
+
+    /// Actions for an issue code, most specific rules first
+    pub fn suggest(&self, code: &str, context: &SuggestionContext) -> Vec<SuggestedAction> {
+        let mut matching: Vec<&SuggestionRule> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.code == code)
+            .filter(|rule| rule.profile.as_ref().map_or(true, |p| *p == context.profile_id))
+            .filter(|rule| rule.platform.as_ref().map_or(true, |p| *p == context.platform))
+            .collect();
+        matching.sort_by_key(|rule| std::cmp::Reverse(rule.profile.is_some() as u8 + rule.platform.is_some() as u8));
+
+        let mut actions = Vec::new();
+        for action in matching.into_iter().flat_map(|rule| rule.actions.iter()) {
+            let action = match action.clone() {
+                SuggestedAction::Fix { title, operation, .. } => {
+                    SuggestedAction::Fix { title, operation, server: context.server.clone() }
+                }
+                other => other,
+            };
+            if !actions.contains(&action) {
+                actions.push(action);
+            }
+        }
+        actions
+    }
+
+    /// Actions for a validation message; messages without a code get none
+    pub fn for_message(&self, message: &ValidationMessage, context: &SuggestionContext) -> Vec<SuggestedAction> {
+        message.code.as_deref().map(|code| self.suggest(code, context)).unwrap_or_default()
+    }
+
+    /// Work out what a fix would write, without writing it
+    pub async fn plan_fix(&self, profile: &ApplicationProfile, action: &SuggestedAction) -> Result<ChangePlan> {
+        let SuggestedAction::Fix { title, operation, server } = action else {
+            bail!("Only fix suggestions can be applied");
+        };
+
+        let path = match operation {
+            FixOperation::CreateSkeletonConfig => profile.resolved_config_path(),
+            _ => ConfigValidator::new()?.resolve_active_config(profile).await?,
+        };
+        let original = fs::read_to_string(&path).ok();
+
+        let updated = match operation {
+            FixOperation::CreateSkeletonConfig => {
+                if original.is_some() {
+                    bail!("{} already exists", path.display());
+                }
+                let mut skeleton = serde_json::json!({});
+                profile.servers_path().get_or_insert(&mut skeleton);
+                serde_json::to_string_pretty(&skeleton)?
+            }
+            FixOperation::RepairStructure => {
+                let mut config = parse_config(&path, original.as_deref())?;
+                if profile.repair_config_structure(&mut config).is_empty() {
+                    bail!("No structure issues to repair in {}", path.display());
+                }
+                serde_json::to_string_pretty(&config)?
+            }
+            FixOperation::AbsoluteCommand => {
+                let server = server.as_deref().ok_or_else(|| anyhow!("No server given for '{}'", title))?;
+                let mut config = parse_config(&path, original.as_deref())?;
+                let entry = profile
+                    .servers_path()
+                    .get_mut(&mut config)
+                    .and_then(|servers| servers.get_mut(server))
+                    .ok_or_else(|| anyhow!("Server '{}' not found in {}", server, path.display()))?;
+                let command = entry.get("command").and_then(|c| c.as_str()).unwrap_or_default();
+                if command.is_empty() || PathBuf::from(command).is_absolute() {
+                    bail!("Server '{}' does not use a bare command", server);
+                }
+                let resolved = which::which(command)
+                    .with_context(|| format!("'{}' was not found on PATH", command))?;
+                entry["command"] = JsonValue::String(resolved.to_string_lossy().to_string());
+                serde_json::to_string_pretty(&config)?
+            }
+        };
+
+        Ok(ChangePlan {
+            description: format!("{}: {}", profile.name, title),
+            writes: vec![PlannedWrite { path, original, updated }],
+        })
+    }
+}
+
+impl Default for SuggestionEngine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn parse_config(path: &std::path::Path, content: Option<&str>) -> Result<JsonValue> {
+    let content = content.ok_or_else(|| anyhow!("Configuration file not found: {}", path.display()))?;
+    serde_json::from_str(content).with_context(|| format!("Failed to parse {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{ApplicationRegistry, ConfigValidator};
+    use crate::detection::profiles::current_platform;
+    use serde_json::json;
+    use tempfile::TempDir;
+
+    fn context(profile_id: &str, platform: &str) -> SuggestionContext {
+        SuggestionContext { profile_id: profile_id.to_string(), platform: platform.to_string(), server: None }
+    }
+
+    #[test]
+    fn test_codes_resolve_to_scoped_suggestions() {
+        let engine = SuggestionEngine::new();
+
+        let generic = engine.suggest("CONFIG_NOT_FOUND", &context("zed", "linux"));
+        assert_eq!(generic.len(), 1);
+        assert!(matches!(generic[0], SuggestedAction::Fix { operation: FixOperation::CreateSkeletonConfig, .. }));
+
+        // Profile-specific rules come first, then the generic ones
+        let claude = engine.suggest("CONFIG_NOT_FOUND", &context("claude-desktop", "linux"));
+        assert!(matches!(claude[0], SuggestedAction::Link { .. }));
+        assert_eq!(claude[1], generic[0]);
+
+        let mac = engine.suggest("COMMAND_NOT_ABSOLUTE", &context("zed", "macos").with_server("github"));
+        assert!(matches!(mac[0], SuggestedAction::Info { .. }));
+        assert_eq!(mac[1], SuggestedAction::Fix {
+            title: "Use the absolute path to the command".to_string(),
+            operation: FixOperation::AbsoluteCommand,
+            server: Some("github".to_string()),
+        });
+        assert_eq!(engine.suggest("COMMAND_NOT_ABSOLUTE", &context("zed", "linux")).len(), 1);
+
+        assert!(engine.suggest("NOT_A_CODE", &context("zed", "linux")).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_structure_fix_applies_through_plan() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("claude_desktop_config.json");
+        fs::write(&path, json!({"mcpServers": {"mcpServers": {"github": {"command": "npx"}}}}).to_string()).unwrap();
+
+        let mut profile = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        profile.set_config_path(path.to_string_lossy());
+        profile.alt_config_paths.clear();
+
+        let validator = ConfigValidator::new().unwrap();
+        let result = validator.validate_application_config(&profile).await.unwrap();
+        let message = result
+            .messages
+            .iter()
+            .find(|m| m.code.as_deref() == Some("MCP_DOUBLE_NESTED"))
+            .expect("structure issue reported");
+        let fix = message
+            .actions
+            .iter()
+            .find(|a| matches!(a, SuggestedAction::Fix { .. }))
+            .expect("fix suggested");
+
+        let engine = SuggestionEngine::new();
+        let plan = engine.plan_fix(&profile, fix).await.unwrap();
+        assert_eq!(plan.writes.len(), 1);
+        // Planning alone does not touch the file
+        assert!(fs::read_to_string(&path).unwrap().contains("\"mcpServers\":{\"mcpServers\""));
+
+        let file_service = ConfigFileService::new("test".to_string(), temp_dir.path().join("backups"));
+        plan.apply(&file_service).await.unwrap();
+        // The original was backed up before it was replaced
+        assert_eq!(fs::read_dir(temp_dir.path().join("backups")).unwrap().count(), 1);
+        let repaired: JsonValue = serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(repaired, json!({"mcpServers": {"github": {"command": "npx"}}}));
+
+        let result = validator.validate_application_config(&profile).await.unwrap();
+        assert!(result.messages.iter().all(|m| m.code.as_deref() != Some("MCP_DOUBLE_NESTED")));
+
+        // A plan made from old content is refused
+        assert!(plan.apply(&file_service).await.unwrap_err().downcast_ref::<StalePlan>().is_some());
+    }
+
+    #[tokio::test]
+    async fn test_skeleton_targets_this_platforms_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let platform_path = temp_dir.path().join("platform/mcp.json");
+
+        let mut profile = ApplicationRegistry::new().get_application("cursor").unwrap().clone();
+        profile.config_path = temp_dir.path().join("other-os/mcp.json").to_string_lossy().to_string();
+        profile
+            .metadata
+            .config_paths_by_platform
+            .insert(current_platform().to_string(), vec![platform_path.to_string_lossy().to_string()]);
+
+        let fix = SuggestedAction::Fix {
+            title: "Create config".to_string(),
+            operation: FixOperation::CreateSkeletonConfig,
+            server: None,
+        };
+        let plan = SuggestionEngine::new().plan_fix(&profile, &fix).await.unwrap();
+        assert_eq!(plan.writes[0].path, platform_path);
+    }
+}
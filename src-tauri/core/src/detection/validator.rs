@@ -0,0 +1,1766 @@
+use crate::adapters::server_env;
+use crate::adapters::declarative::declared_adapter;
+use crate::adapters::jetbrains_options::{is_options_xml_backed, read_options_xml, resolve_versioned_path};
+use crate::adapters::warp_store::{is_sqlite_backed, WarpStateStore};
+use crate::analysis::alternatives::runtime_for_command;
+use crate::analysis::dropped_path::is_executable;
+use crate::analysis::schema_detector::flatten_transports;
+use crate::analysis::server_analyzer::EnvVarConfig;
+use crate::configuration::annotations::AnnotationStore;
+use crate::configuration::provenance::{managed_statuses, ManagedStatus, ProvenanceSidecar};
+use crate::configuration::quarantine::QuarantinedServer;
+use crate::detection::profiles::{ApplicationProfile, ConfigFormat};
+use crate::detection::self_reference::SelfReferenceGuard;
+use crate::detection::suggestions::{SuggestedAction, SuggestionContext, SuggestionEngine};
+use crate::filesystem::{FsProvider, PathUtils, RealFs};
+use crate::filesystem::yaml_edit::parse_yaml_view;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Configuration validation and extraction service
+#[derive(Debug)]
+pub struct ConfigValidator {
+    /// Filesystem used to locate and read configuration files
+    fs: Arc<dyn FsProvider>,
+}
+
+/// Result of configuration validation and extraction
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ConfigValidationResult {
+    /// Application profile that was validated
+    pub application: ApplicationProfile,
+    /// Whether the configuration file exists and is valid
+    pub is_valid: bool,
+    /// Configuration file path that was found (if any)
+    pub config_path: Option<PathBuf>,
+    /// Configuration format detected
+    pub detected_format: Option<ConfigFormat>,
+    /// Extracted MCP server configurations
+    pub mcp_servers: Vec<McpServerConfig>,
+    /// Validation messages and errors
+    pub messages: Vec<ValidationMessage>,
+    /// Raw configuration data (for debugging)
+    pub raw_config: Option<JsonValue>,
+    /// Variables loaded from `envFile` references, keyed by server name
+    #[serde(default)]
+    pub env_file_vars: HashMap<String, Vec<EnvVarConfig>>,
+    /// Validation timestamp
+    pub validated_at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A server entry hidden by a same-named entry in a higher-priority config file
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ShadowedServer {
+    /// Server name shared by both entries
+    pub name: String,
+    /// File holding the hidden entry
+    pub path: PathBuf,
+    /// File whose entry is used instead
+    pub shadowed_by: PathBuf,
+}
+
+/// An application's servers merged across its primary and alternate config files.
+///
+/// Files are ranked in profile order: `config_path` first, then `alt_config_paths` as
+/// listed. A server name is taken from the highest-ranked file that defines it; lower-ranked
+/// files only contribute names absent from every file above them.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct EffectiveServers {
+    /// File that writes go to (see `ConfigValidator::resolve_active_config`)
+    pub active_config: Option<PathBuf>,
+    /// Merged servers, highest-ranked file first
+    pub servers: Vec<McpServerConfig>,
+    /// File each merged server was read from
+    pub sources: HashMap<String, PathBuf>,
+    /// Entries hidden by higher-ranked files
+    pub shadowed: Vec<ShadowedServer>,
+    /// Variables loaded from `envFile` references, keyed by server name
+    pub env_file_vars: HashMap<String, Vec<EnvVarConfig>>,
+    /// Read errors and shadowing diagnostics
+    pub messages: Vec<ValidationMessage>,
+}
+
+/// Servers read from one config file
+#[derive(Debug, Clone)]
+pub struct ConfigLayer {
+    pub path: PathBuf,
+    pub servers: Vec<McpServerConfig>,
+    pub env_file_vars: HashMap<String, Vec<EnvVarConfig>>,
+}
+
+impl ConfigLayer {
+    pub fn new(path: PathBuf, servers: Vec<McpServerConfig>) -> Self {
+        Self { path, servers, env_file_vars: HashMap::new() }
+    }
+}
+
+/// Merge config layers given in precedence order, recording every shadowed entry
+pub fn merge_config_layers(layers: Vec<ConfigLayer>) -> EffectiveServers {
+    let mut effective = EffectiveServers {
+        active_config: layers.first().map(|layer| layer.path.clone()),
+        ..Default::default()
+    };
+
+    for mut layer in layers {
+        for server in layer.servers {
+            if let Some(winner) = effective.sources.get(&server.name) {
+                effective.messages.push(ValidationMessage {
+                    level: MessageLevel::Warning,
+                    message: format!("Server '{}' is shadowed by the entry in {}", server.name, winner.display()),
+                    path: Some(layer.path.display().to_string()),
+                    suggestion: Some("Remove the duplicate entry or migrate it into the active config file".to_string()),
+                    code: Some("SERVER_SHADOWED".to_string()),
+                    actions: Vec::new(),
+                });
+                effective.shadowed.push(ShadowedServer {
+                    name: server.name,
+                    path: layer.path.clone(),
+                    shadowed_by: winner.clone(),
+                });
+                continue;
+            }
+
+            if let Some(vars) = layer.env_file_vars.remove(&server.name) {
+                effective.env_file_vars.insert(server.name.clone(), vars);
+            }
+            effective.sources.insert(server.name.clone(), layer.path.clone());
+            effective.servers.push(server);
+        }
+    }
+
+    effective
+}
+
+/// MCP server configuration extracted from application config
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct McpServerConfig {
+    /// Server identifier/name
+    pub name: String,
+    /// Server command or executable path
+    pub command: Option<String>,
+    /// Command arguments
+    pub args: Vec<String>,
+    /// Environment variables
+    pub env: HashMap<String, String>,
+    /// Working directory
+    pub cwd: Option<String>,
+    /// Whether the server inherits the parent environment when launched
+    #[serde(default = "default_inherit_env")]
+    pub inherit_env: bool,
+    /// Client-specific per-server options such as timeouts or auto-approval lists
+    #[serde(default)]
+    pub extra_options: HashMap<String, JsonValue>,
+    /// Server type (stdio, sse, websocket, etc.)
+    pub server_type: ServerType,
+    /// Additional metadata
+    pub metadata: ServerMetadata,
+}
+
+fn default_inherit_env() -> bool {
+    true
+}
+
+/// Whether a key is document metadata such as `$schema` rather than a server name
+pub fn is_metadata_key(key: &str) -> bool {
+    key.starts_with('$')
+}
+
+/// Server entry key that references a dotenv-style file
+const ENV_FILE_KEY: &str = "envFile";
+
+/// Keep an entry's `envFile` reference so it can be resolved against the config location
+fn env_file_option(server_obj: &serde_json::Map<String, JsonValue>) -> HashMap<String, JsonValue> {
+    server_obj
+        .get(ENV_FILE_KEY)
+        .filter(|v| v.is_string())
+        .map(|v| HashMap::from([(ENV_FILE_KEY.to_string(), v.clone())]))
+        .unwrap_or_default()
+}
+
+/// Parse `KEY=value` lines from a dotenv-style file
+fn parse_env_file(content: &str) -> Vec<(String, String)> {
+    content
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let value = value.trim();
+            let value = match value.chars().next() {
+                Some(quote @ ('"' | '\'')) => value[1..].split(quote).next().unwrap_or_default(),
+                _ => value.split(" #").next().unwrap_or_default().trim_end(),
+            };
+            Some((key.trim().to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+impl McpServerConfig {
+    /// Runtime implied by the stored command; url-only entries are remote
+    pub fn runtime(&self) -> ServerRuntime {
+        let Some(command) = self.command.as_deref().map(str::trim).filter(|c| !c.is_empty()) else {
+            return match self.server_type {
+                ServerType::Sse { .. } | ServerType::WebSocket { .. } | ServerType::Http { .. } => ServerRuntime::Remote,
+                _ => ServerRuntime::Unknown,
+            };
+        };
+
+        classify_runtime(command)
+    }
+
+    /// Problems that would prevent the server from launching as configured
+    pub fn readiness(&self) -> Vec<ReadinessIssue> {
+        let mut issues = Vec::new();
+
+        match self.command.as_deref().map(str::trim).filter(|c| !c.is_empty()) {
+            None if self.server_type == ServerType::Stdio => issues.push(ReadinessIssue::MissingCommand),
+            Some(command) if Path::new(command).is_file() && !is_executable(Path::new(command)) => {
+                issues.push(ReadinessIssue::CommandNotExecutable(command.to_string()));
+            }
+            _ => {}
+        }
+
+        if let Some(cwd) = &self.cwd {
+            if !std::path::Path::new(cwd).is_dir() {
+                issues.push(ReadinessIssue::MissingWorkingDirectory(cwd.clone()));
+            }
+        }
+
+        issues
+    }
+
+    /// `readiness` as messages
+    pub fn readiness_issues(&self) -> Vec<String> {
+        self.readiness().iter().map(ReadinessIssue::description).collect()
+    }
+
+    /// Whether the command is a bare name looked up on PATH, which GUI apps may not share with the shell
+    pub fn command_is_bare(&self) -> bool {
+        self.server_type == ServerType::Stdio
+            && self.command.as_deref().map(str::trim).is_some_and(|c| !c.is_empty() && !c.contains(['/', '\\']))
+    }
+}
+
+/// Something that would stop a configured server from launching
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReadinessIssue {
+    /// A stdio server without a command
+    MissingCommand,
+    /// The command is a file the user cannot execute
+    CommandNotExecutable(String),
+    /// The working directory does not exist
+    MissingWorkingDirectory(String),
+}
+
+impl ReadinessIssue {
+    /// Diagnostic code, matched against the suggestions table
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::MissingCommand => "COMMAND_MISSING",
+            Self::CommandNotExecutable(_) => "COMMAND_NOT_EXECUTABLE",
+            Self::MissingWorkingDirectory(_) => "CWD_MISSING",
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            Self::MissingCommand => "Server command not specified".to_string(),
+            Self::CommandNotExecutable(command) => format!("Command is not executable: {}", command),
+            Self::MissingWorkingDirectory(cwd) => format!("Working directory does not exist: {}", cwd),
+        }
+    }
+}
+
+/// Runtime a configured server runs on, for grouping servers in an overview
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ServerRuntime {
+    Node,
+    Bun,
+    Python,
+    Docker,
+    Deno,
+    /// Reached over the network rather than launched locally
+    Remote,
+    /// Launched with some other executable, named by its file stem
+    Other(String),
+    /// No command or url to classify
+    Unknown,
+}
+
+/// Runtime implied by a launch command, e.g. `npx` or `/usr/local/bin/node` is Node
+pub fn classify_runtime(command: &str) -> ServerRuntime {
+    // Windows shims such as npx.cmd classify like the bare command
+    let stem = Path::new(command.trim())
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    match runtime_for_command(&stem) {
+        Some("node") => ServerRuntime::Node,
+        Some("bun") => ServerRuntime::Bun,
+        Some("python") => ServerRuntime::Python,
+        _ => match stem.as_str() {
+            "uv" | "uvx" | "pipx" => ServerRuntime::Python,
+            "docker" | "podman" => ServerRuntime::Docker,
+            "deno" => ServerRuntime::Deno,
+            _ => ServerRuntime::Other(stem),
+        },
+    }
+}
+
+/// A server entry in one application's config
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ServerRef {
+    pub app_id: String,
+    pub server_name: String,
+    pub config_path: Option<PathBuf>,
+}
+
+/// Types of MCP server connections
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ServerType {
+    /// Standard input/output communication
+    Stdio,
+    /// Server-sent events
+    Sse { url: String },
+    /// WebSocket connection
+    WebSocket { url: String },
+    /// HTTP/REST API
+    Http { base_url: String },
+    /// Custom/unknown type
+    Custom(String),
+}
+
+/// Additional server metadata
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ServerMetadata {
+    /// Server description
+    pub description: Option<String>,
+    /// Server version
+    pub version: Option<String>,
+    /// Server author/maintainer
+    pub author: Option<String>,
+    /// Server capabilities
+    pub capabilities: Vec<String>,
+    /// Whether server is enabled
+    pub enabled: bool,
+    /// Server configuration source
+    pub source: ConfigSource,
+}
+
+/// Source of the server configuration
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ConfigSource {
+    /// From application's main config file
+    MainConfig,
+    /// From alternative config file
+    AlternativeConfig,
+    /// From environment variables
+    Environment,
+    /// From command line arguments
+    CommandLine,
+    /// Unknown source
+    Unknown,
+}
+
+/// Validation message for configuration issues
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValidationMessage {
+    /// Message severity level
+    pub level: MessageLevel,
+    /// Message content
+    pub message: String,
+    /// Configuration path or field that caused the message
+    pub path: Option<String>,
+    /// Suggested fix or action
+    pub suggestion: Option<String>,
+    /// Stable issue code, used to look up suggested actions
+    #[serde(default)]
+    pub code: Option<String>,
+    /// Suggested actions resolved from the issue code
+    #[serde(default)]
+    pub actions: Vec<SuggestedAction>,
+}
+
+/// Message severity levels
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum MessageLevel {
+    Info,
+    Warning,
+    Error,
+    Critical,
+}
+
+impl std::fmt::Display for MessageLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MessageLevel::Info => write!(f, "INFO"),
+            MessageLevel::Warning => write!(f, "WARN"),
+            MessageLevel::Error => write!(f, "ERROR"),
+            MessageLevel::Critical => write!(f, "CRITICAL"),
+        }
+    }
+}
+
+/// Summary of validation results
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ValidationSummary {
+    /// Total number of applications validated
+    pub total_applications: usize,
+    /// Number of applications with valid configurations
+    pub valid_configs: usize,
+    /// Number of applications with invalid configurations
+    pub invalid_configs: usize,
+    /// Total number of MCP servers found
+    pub total_servers: usize,
+    /// Number of applications with MCP servers
+    pub applications_with_servers: usize,
+    /// Number of applications without MCP servers
+    pub applications_without_servers: usize,
+    /// Breakdown of configuration formats found
+    pub format_breakdown: HashMap<String, usize>,
+    /// Servers switched off by quarantine, most recent first, awaiting acknowledgement or a fix
+    #[serde(default)]
+    pub quarantined: Vec<QuarantinedServer>,
+}
+
+impl ValidationSummary {
+    /// Include the quarantined servers recorded in the annotations, which may no longer
+    /// appear in any config
+    pub fn with_quarantined(mut self, annotations: &AnnotationStore) -> Self {
+        self.quarantined = annotations.quarantined_servers();
+        self
+    }
+}
+
+impl ConfigValidator {
+    /// Create a new configuration validator
+    pub fn new() -> Result<Self> {
+        Ok(Self { fs: Arc::new(RealFs) })
+    }
+
+    /// Create a validator that reads configuration through the given filesystem provider
+    pub fn with_fs(fs: Arc<dyn FsProvider>) -> Self {
+        Self { fs }
+    }
+
+    /// Validate and extract configuration for a single application
+    pub async fn validate_application_config(&self, application: &ApplicationProfile) -> Result<ConfigValidationResult> {
+        let engine = SuggestionEngine::new();
+        let context = SuggestionContext::for_profile(application);
+        let mut messages = Vec::new();
+        let mut mcp_servers = Vec::new();
+        let mut config_path = None;
+        let mut detected_format = None;
+        let mut raw_config = None;
+        let mut env_file_vars = HashMap::new();
+        let mut is_valid = false;
+
+        // Try to find and read the configuration file
+        if let Some((found_path, format, content)) = self.find_config_file(application).await? {
+            config_path = Some(found_path.clone());
+            detected_format = Some(format.clone());
+            
+            match self.parse_config_content(&content, &format) {
+                Ok(parsed_config) => {
+                    raw_config = Some(parsed_config.clone());
+
+                    if format == ConfigFormat::Json {
+                        for issue in application.detect_structure_issues(&parsed_config) {
+                            messages.push(ValidationMessage {
+                                level: MessageLevel::Warning,
+                                message: issue.description(),
+                                path: Some(found_path.display().to_string()),
+                                suggestion: Some("Repair the servers layout automatically".to_string()),
+                                code: Some(issue.code().to_string()),
+                                actions: Vec::new(),
+                            });
+                        }
+                    }
+                    
+                    // Extract MCP servers from the configuration
+                    match self.extract_mcp_servers(&parsed_config, application, &found_path) {
+                        Ok(servers) => {
+                            is_valid = true;
+                            let vars = self.apply_env_files(&servers, &found_path, &mut messages).await;
+
+                            // Alternate files contribute the servers the active file does not define
+                            let mut layers = vec![ConfigLayer { path: found_path.clone(), servers, env_file_vars: vars }];
+                            for path in self.existing_config_files(application).await? {
+                                if path != found_path {
+                                    layers.extend(self.read_layer(application, &path, &mut messages).await);
+                                }
+                            }
+                            let effective = merge_config_layers(layers);
+                            mcp_servers = effective.servers;
+                            env_file_vars = effective.env_file_vars;
+                            messages.extend(effective.messages);
+
+                            let guard = SelfReferenceGuard::new().with_managed_config(&found_path);
+                            for finding in guard.check_servers(&mcp_servers) {
+                                messages.push(ValidationMessage {
+                                    level: MessageLevel::Error,
+                                    message: finding.to_string(),
+                                    path: Some(found_path.display().to_string()),
+                                    suggestion: Some("Point the entry at the real server command instead of MCP Control or a wrapper that loops back".to_string()),
+                                    code: Some("SELF_REFERENTIAL_SERVER".to_string()),
+                                    actions: Vec::new(),
+                                });
+                            }
+
+                            // Per-server problems carry the server so fixes know which entry to change
+                            for server in &mcp_servers {
+                                let server_context = context.clone().with_server(&server.name);
+                                let mut server_messages: Vec<ValidationMessage> = server
+                                    .readiness()
+                                    .into_iter()
+                                    .map(|issue| ValidationMessage {
+                                        level: MessageLevel::Error,
+                                        message: format!("Server '{}': {}", server.name, issue.description()),
+                                        path: Some(found_path.display().to_string()),
+                                        suggestion: None,
+                                        code: Some(issue.code().to_string()),
+                                        actions: Vec::new(),
+                                    })
+                                    .collect();
+                                if server.command_is_bare() {
+                                    server_messages.push(ValidationMessage {
+                                        level: MessageLevel::Info,
+                                        message: format!("Server '{}' launches '{}' by name", server.name, server.command.as_deref().unwrap_or_default()),
+                                        path: Some(found_path.display().to_string()),
+                                        suggestion: Some("Use the absolute path if the application cannot find the command".to_string()),
+                                        code: Some("COMMAND_NOT_ABSOLUTE".to_string()),
+                                        actions: Vec::new(),
+                                    });
+                                }
+                                for mut message in server_messages {
+                                    message.actions = engine.for_message(&message, &server_context);
+                                    messages.push(message);
+                                }
+                            }
+                            
+                            if mcp_servers.is_empty() {
+                                messages.push(ValidationMessage {
+                                    level: MessageLevel::Warning,
+                                    message: "No MCP servers found in configuration".to_string(),
+                                    path: Some(found_path.display().to_string()),
+                                    suggestion: Some("Add MCP server configurations to enable MCP functionality".to_string()),
+                                    code: Some("NO_SERVERS".to_string()),
+                                    actions: Vec::new(),
+                                });
+                            } else {
+                                messages.push(ValidationMessage {
+                                    level: MessageLevel::Info,
+                                    message: format!("Found {} MCP server(s) in configuration", mcp_servers.len()),
+                                    path: Some(found_path.display().to_string()),
+                                    suggestion: None,
+                                    code: None,
+                                    actions: Vec::new(),
+                                });
+                            }
+                        }
+                        Err(e) => {
+                            messages.push(ValidationMessage {
+                                level: MessageLevel::Error,
+                                message: format!("Failed to extract MCP servers: {}", e),
+                                path: Some(found_path.display().to_string()),
+                                suggestion: Some("Check configuration format and MCP server definitions".to_string()),
+                                code: Some("SERVER_EXTRACT_FAILED".to_string()),
+                                actions: Vec::new(),
+                            });
+                        }
+                    }
+                }
+                Err(e) => {
+                    messages.push(ValidationMessage {
+                        level: MessageLevel::Error,
+                        message: format!("Failed to parse configuration file: {}", e),
+                        path: Some(found_path.display().to_string()),
+                        suggestion: Some("Check configuration file syntax and format".to_string()),
+                        code: Some("CONFIG_PARSE_FAILED".to_string()),
+                        actions: Vec::new(),
+                    });
+                }
+            }
+        } else {
+            messages.push(ValidationMessage {
+                level: MessageLevel::Warning,
+                message: "Configuration file not found".to_string(),
+                path: None,
+                suggestion: Some("Create a configuration file to enable MCP functionality".to_string()),
+                code: Some("CONFIG_NOT_FOUND".to_string()),
+                actions: Vec::new(),
+            });
+        }
+
+        for message in messages.iter_mut().filter(|message| message.actions.is_empty()) {
+            message.actions = engine.for_message(message, &context);
+        }
+
+        Ok(ConfigValidationResult {
+            application: application.clone(),
+            is_valid,
+            config_path,
+            detected_format,
+            mcp_servers,
+            messages,
+            raw_config,
+            env_file_vars,
+            validated_at: chrono::Utc::now(),
+        })
+    }
+
+    /// Validate configurations for multiple applications
+    pub async fn validate_multiple_configs(&self, applications: &[ApplicationProfile]) -> Result<Vec<ConfigValidationResult>> {
+        let mut results = Vec::new();
+        
+        for application in applications {
+            let result = self.validate_application_config(application).await?;
+            results.push(result);
+        }
+        
+        Ok(results)
+    }
+
+    /// Get summary statistics for validation results
+    pub fn get_validation_summary(&self, results: &[ConfigValidationResult]) -> ValidationSummary {
+        let total_applications = results.len();
+        let valid_configs = results.iter().filter(|r| r.is_valid).count();
+        let total_servers = results.iter().map(|r| r.mcp_servers.len()).sum();
+        let applications_with_servers = results.iter().filter(|r| !r.mcp_servers.is_empty()).count();
+        
+        let mut format_breakdown = HashMap::new();
+        for result in results {
+            if let Some(format) = &result.detected_format {
+                let format_name = match format {
+                    ConfigFormat::Json => "JSON",
+                    ConfigFormat::Yaml => "YAML", 
+                    ConfigFormat::Toml => "TOML",
+                    ConfigFormat::Plist => "Plist",
+                    ConfigFormat::Custom(name) => name,
+                };
+                *format_breakdown.entry(format_name.to_string()).or_insert(0) += 1;
+            }
+        }
+
+        ValidationSummary {
+            total_applications,
+            valid_configs,
+            invalid_configs: total_applications - valid_configs,
+            total_servers,
+            applications_with_servers,
+            applications_without_servers: total_applications - applications_with_servers,
+            format_breakdown,
+            quarantined: Vec::new(),
+        }
+    }
+
+    // Private helper methods
+
+    /// Existing config files in precedence order: the primary path, then alternates as listed
+    pub async fn existing_config_files(&self, application: &ApplicationProfile) -> Result<Vec<PathBuf>> {
+        let mut files = Vec::new();
+        for path in &application.get_config_paths_for_current_platform() {
+            let Some(expanded) = resolve_versioned_path(&*self.fs, &PathUtils::expand_tilde(path)?).await else {
+                continue;
+            };
+            if self.fs.exists(&expanded).await && !files.contains(&expanded) {
+                files.push(expanded);
+            }
+        }
+        Ok(files)
+    }
+
+    /// The file writes should target: the highest-ranked existing config, or the primary path
+    pub async fn resolve_active_config(&self, application: &ApplicationProfile) -> Result<PathBuf> {
+        if let Some(path) = self.existing_config_files(application).await?.into_iter().next() {
+            return Ok(path);
+        }
+        for path in &application.get_config_paths_for_current_platform() {
+            if let Some(resolved) = resolve_versioned_path(&*self.fs, &PathUtils::expand_tilde(path)?).await {
+                return Ok(resolved);
+            }
+        }
+        Err(anyhow::anyhow!("No installed version of {} found to write its configuration to", application.name))
+    }
+
+    /// Whether each server in the active config was written by MCP Control, sorted by name
+    pub async fn read_managed_statuses(
+        &self,
+        application: &ApplicationProfile,
+        sidecar: &ProvenanceSidecar,
+    ) -> Result<Vec<(String, ManagedStatus)>> {
+        let path = self.resolve_active_config(application).await?;
+        if !self.fs.exists(&path).await {
+            return Ok(Vec::new());
+        }
+        let content = self.read_config_content(application, &path).await?;
+        let config = self.parse_config_content(&content, &application.config_format)?;
+        Ok(managed_statuses(application, &config, sidecar))
+    }
+
+    /// Read an application's servers merged across all of its existing config files
+    pub async fn read_effective_servers(&self, application: &ApplicationProfile) -> Result<EffectiveServers> {
+        let mut messages = Vec::new();
+        let mut layers = Vec::new();
+        for path in self.existing_config_files(application).await? {
+            layers.extend(self.read_layer(application, &path, &mut messages).await);
+        }
+
+        let mut effective = merge_config_layers(layers);
+        effective.active_config = Some(self.resolve_active_config(application).await?);
+        messages.append(&mut effective.messages);
+        effective.messages = messages;
+        Ok(effective)
+    }
+
+    /// Read the servers from one config file, reporting failures as messages
+    async fn read_layer(
+        &self,
+        application: &ApplicationProfile,
+        path: &Path,
+        messages: &mut Vec<ValidationMessage>,
+    ) -> Option<ConfigLayer> {
+        let parsed = match self.read_config_content(application, path).await {
+            Ok(content) => self.parse_config_content(&content, &application.config_format),
+            Err(e) => Err(e),
+        };
+        let servers = parsed.and_then(|config| self.extract_mcp_servers(&config, application, path));
+
+        match servers {
+            Ok(servers) => {
+                let env_file_vars = self.apply_env_files(&servers, path, messages).await;
+                Some(ConfigLayer { path: path.to_path_buf(), servers, env_file_vars })
+            }
+            Err(e) => {
+                messages.push(ValidationMessage {
+                    level: MessageLevel::Error,
+                    message: format!("Failed to read servers from configuration file: {}", e),
+                    path: Some(path.display().to_string()),
+                    suggestion: Some("Check configuration file syntax and format".to_string()),
+                    code: Some("CONFIG_PARSE_FAILED".to_string()),
+                    actions: Vec::new(),
+                });
+                None
+            }
+        }
+    }
+
+    /// Find the configuration file for an application
+    async fn find_config_file(&self, application: &ApplicationProfile) -> Result<Option<(PathBuf, ConfigFormat, String)>> {
+        let Some(path) = self.existing_config_files(application).await?.into_iter().next() else {
+            return Ok(None);
+        };
+
+        let content = self.read_config_content(application, &path).await
+            .context("Failed to read config file")?;
+        Ok(Some((path, application.config_format.clone(), content)))
+    }
+
+    /// Read a config file, rendering database-backed stores as JSON
+    async fn read_config_content(&self, application: &ApplicationProfile, path: &Path) -> Result<String> {
+        if is_sqlite_backed(application) {
+            let config = WarpStateStore::open(path)?.read_config()?;
+            return Ok(config.to_string());
+        }
+        if is_options_xml_backed(application) {
+            let content = self.fs.read_to_string(path).await?;
+            return Ok(read_options_xml(&content)?.to_string());
+        }
+        self.fs.read_to_string(path).await
+    }
+
+    /// Parse configuration content based on format
+    fn parse_config_content(&self, content: &str, format: &ConfigFormat) -> Result<JsonValue> {
+        // A zero-byte or blank file is a config without servers, not a syntax error
+        if content.trim().is_empty() {
+            return Ok(serde_json::json!({}));
+        }
+
+        match format {
+            ConfigFormat::Json => {
+                serde_json::from_str(content)
+                    .context("Failed to parse JSON configuration")
+            }
+            ConfigFormat::Yaml => {
+                let yaml_value = parse_yaml_view(content)
+                    .context("Failed to parse YAML configuration")?;
+                serde_json::to_value(yaml_value)
+                    .context("Failed to convert YAML to JSON")
+            }
+            ConfigFormat::Toml => {
+                let toml_value: toml::Value = content.parse()
+                    .context("Failed to parse TOML configuration")?;
+                serde_json::to_value(toml_value)
+                    .context("Failed to convert TOML to JSON")
+            }
+            ConfigFormat::Plist => {
+                // For now, treat plist as JSON (could be enhanced later)
+                serde_json::from_str(content)
+                    .context("Failed to parse Plist configuration")
+            }
+            ConfigFormat::Custom(_) => {
+                // Try JSON first, then YAML as fallback
+                serde_json::from_str(content)
+                    .or_else(|_| {
+                        let yaml_value = parse_yaml_view(content)
+                            .context("Failed to parse as YAML")?;
+                        serde_json::to_value(yaml_value)
+                            .context("Failed to convert YAML to JSON")
+                    })
+                    .context("Failed to parse custom configuration format")
+            }
+        }
+    }
+
+    /// Extract MCP server configurations from parsed config
+    pub(crate) fn extract_mcp_servers(&self, config: &JsonValue, application: &ApplicationProfile, config_path: &Path) -> Result<Vec<McpServerConfig>> {
+        let mut servers = Vec::new();
+
+        // Newer configs may nest launch details under `transport`; read them in the flat shape
+        let mut config = config.clone();
+        flatten_transports(&mut config);
+        let config = &config;
+
+        // Different applications have different MCP server configuration structures; a profile
+        // that declares its own layout is read through that declaration
+        match application.id.as_str() {
+            _ if application.metadata.declarative_adapter.is_some() => {
+                if let Some(adapter) = declared_adapter(application)? {
+                    let extracted = adapter.extract(config);
+                    for message in &extracted.messages {
+                        log::warn!("{}: {}", application.id, message);
+                    }
+                    servers.extend(extracted.servers);
+                }
+            }
+            "claude-desktop" => {
+                servers.extend(self.extract_claude_desktop_servers(config)?);
+            }
+            "cursor" => {
+                servers.extend(self.extract_cursor_servers(config)?);
+            }
+            "zed" => {
+                servers.extend(self.extract_zed_servers(config, application)?);
+            }
+            "vscode" => {
+                servers.extend(self.extract_vscode_servers(config, application)?);
+            }
+            id if id.starts_with("jetbrains-") => {
+                // Options XML is rendered in this shape too, see `read_options_xml`
+                if let Some(section) = application.servers_path().lookup(config) {
+                    servers.extend(self.extract_claude_desktop_servers(&serde_json::json!({ "mcpServers": section }))?);
+                }
+            }
+            _ => {
+                // Generic extraction for custom applications
+                servers.extend(self.extract_generic_servers(config, application)?);
+            }
+        }
+
+        // Set source for all servers
+        for server in &mut servers {
+            server.metadata.source = if config_path == Path::new(&application.config_path) {
+                ConfigSource::MainConfig
+            } else {
+                ConfigSource::AlternativeConfig
+            };
+        }
+
+        Ok(servers)
+    }
+
+    /// Extract MCP servers from Claude Desktop configuration
+    fn extract_claude_desktop_servers(&self, config: &JsonValue) -> Result<Vec<McpServerConfig>> {
+        let mut servers = Vec::new();
+
+        if let Some(mcp_servers) = config.get("mcpServers").and_then(|v| v.as_object()) {
+            for (name, server_config) in mcp_servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
+                if let Some(server_obj) = server_config.as_object() {
+                    let command = server_obj.get("command").and_then(|v| v.as_str()).map(String::from);
+                    let args = server_obj.get("args")
+                        .and_then(|v| v.as_array())
+                        .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                        .unwrap_or_default();
+                    
+                    let env = server_env(server_config);
+
+                    servers.push(McpServerConfig {
+                        name: name.clone(),
+                        command,
+                        args,
+                        env,
+                        cwd: server_obj.get("cwd").and_then(|v| v.as_str()).map(String::from),
+                        inherit_env: server_obj.get("inheritEnv").and_then(|v| v.as_bool()).unwrap_or(true),
+                        extra_options: env_file_option(server_obj),
+                        server_type: ServerType::Stdio, // Claude Desktop uses stdio
+                        metadata: ServerMetadata {
+                            description: server_obj.get("description").and_then(|v| v.as_str()).map(String::from),
+                            version: None,
+                            author: None,
+                            capabilities: Vec::new(),
+                            enabled: !server_obj.get("disabled").and_then(|v| v.as_bool()).unwrap_or(false),
+                            source: ConfigSource::MainConfig,
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(servers)
+    }
+
+    /// Extract MCP servers from Cursor configuration
+    fn extract_cursor_servers(&self, config: &JsonValue) -> Result<Vec<McpServerConfig>> {
+        // ~/.cursor/mcp.json and legacy settings.json share the Claude Desktop entry shape
+        let mut servers = match crate::adapters::cursor::servers_section(config) {
+            Some(section) => self.extract_claude_desktop_servers(&serde_json::json!({ "mcpServers": section }))?,
+            None => Vec::new(),
+        };
+
+        // Cursor might have MCP servers in extensions or settings
+        if let Some(extensions) = config.get("extensions").and_then(|v| v.as_object()) {
+            for (name, ext_config) in extensions {
+                if name.contains("mcp") || name.contains("model-context-protocol") {
+                    if let Some(ext_obj) = ext_config.as_object() {
+                        servers.push(McpServerConfig {
+                            name: name.clone(),
+                            command: ext_obj.get("command").and_then(|v| v.as_str()).map(String::from),
+                            args: Vec::new(),
+                            env: HashMap::new(),
+                            cwd: None,
+                            inherit_env: true,
+                            extra_options: HashMap::new(),
+                            server_type: ServerType::Custom("extension".to_string()),
+                            metadata: ServerMetadata {
+                                description: Some(format!("Cursor extension: {}", name)),
+                                version: ext_obj.get("version").and_then(|v| v.as_str()).map(String::from),
+                                author: None,
+                                capabilities: Vec::new(),
+                                enabled: ext_obj.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true),
+                                source: ConfigSource::MainConfig,
+                            },
+                        });
+                    }
+                }
+            }
+        }
+
+        Ok(servers)
+    }
+
+    /// Extract MCP servers from Zed configuration
+    fn extract_zed_servers(&self, config: &JsonValue, application: &ApplicationProfile) -> Result<Vec<McpServerConfig>> {
+        let mut servers = Vec::new();
+
+        if let Some(servers_config) = application.servers_path().get(config) {
+            for (name, server_config) in servers_config.iter().filter(|(name, _)| !is_metadata_key(name)) {
+                if let Some(server_obj) = server_config.as_object() {
+                    // Older Zed releases nest the launch details as `"command": {"path", "args", "env"}`
+                    let launch = match server_obj.get("command") {
+                        Some(command) if command.is_object() => command,
+                        _ => server_config,
+                    };
+                    let command = launch
+                        .get("command")
+                        .or_else(|| launch.get("path"))
+                        .and_then(|v| v.as_str())
+                        .map(String::from);
+                    servers.push(McpServerConfig {
+                        name: name.clone(),
+                        command,
+                        args: launch.get("args")
+                            .and_then(|v| v.as_array())
+                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                            .unwrap_or_default(),
+                        env: server_env(launch),
+                        cwd: None,
+                        inherit_env: true,
+                        extra_options: env_file_option(server_obj),
+                        server_type: ServerType::Stdio,
+                        metadata: ServerMetadata {
+                            description: server_obj.get("description").and_then(|v| v.as_str()).map(String::from),
+                            version: None,
+                            author: None,
+                            capabilities: Vec::new(),
+                            enabled: server_obj.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true),
+                            source: ConfigSource::MainConfig,
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(servers)
+    }
+
+    /// Extract MCP servers from VS Code configuration
+    fn extract_vscode_servers(&self, config: &JsonValue, application: &ApplicationProfile) -> Result<Vec<McpServerConfig>> {
+        // Newer versions keep servers under chat.mcp.servers, written either as a single
+        // dotted settings key or as nested objects. Only the servers container holds
+        // servers; other settings keys, `$schema` included, never do
+        match application.servers_path().lookup(config) {
+            Some(section) => self.extract_claude_desktop_servers(&serde_json::json!({ "mcpServers": section })),
+            None => Ok(Vec::new()),
+        }
+    }
+
+    /// Generic MCP server extraction for unknown applications
+    ///
+    /// The profile's servers path is read first; common MCP configuration keys are the fallback.
+    fn extract_generic_servers(&self, config: &JsonValue, application: &ApplicationProfile) -> Result<Vec<McpServerConfig>> {
+        let mut servers = Vec::new();
+
+        // Look for common MCP server configuration patterns
+        let possible_keys = ["mcpServers", "mcp_servers", "mcp", "servers", "modelContextProtocol"];
+        let section = application
+            .servers_path()
+            .get(config)
+            .or_else(|| possible_keys.iter().find_map(|key| config.get(key)).and_then(|section| section.as_object()));
+
+        if let Some(servers_obj) = section {
+            for (name, server_config) in servers_obj.iter().filter(|(name, _)| !is_metadata_key(name)) {
+                if let Some(server_obj) = server_config.as_object() {
+                    servers.push(McpServerConfig {
+                        name: name.clone(),
+                        command: server_obj.get("command").and_then(|v| v.as_str()).map(String::from),
+                        args: server_obj.get("args")
+                            .and_then(|v| v.as_array())
+                            .map(|arr| arr.iter().filter_map(|v| v.as_str().map(String::from)).collect())
+                            .unwrap_or_default(),
+                        env: server_env(server_config),
+                        cwd: None,
+                        inherit_env: true,
+                        extra_options: env_file_option(server_obj),
+                        server_type: ServerType::Custom("generic".to_string()),
+                        metadata: ServerMetadata {
+                            description: server_obj.get("description").and_then(|v| v.as_str()).map(String::from),
+                            version: None,
+                            author: None,
+                            capabilities: Vec::new(),
+                            enabled: server_obj.get("enabled").and_then(|v| v.as_bool()).unwrap_or(true),
+                            source: ConfigSource::MainConfig,
+                        },
+                    });
+                }
+            }
+        }
+
+        Ok(servers)
+    }
+
+    /// Record the variables each server's `envFile` provides.
+    ///
+    /// Paths are resolved relative to the config file's directory. Only variable names and
+    /// the file they come from are kept; values stay in the env file and never reach
+    /// `server.env`, results or logs. A missing env file is reported as a warning.
+    async fn apply_env_files(
+        &self,
+        servers: &[McpServerConfig],
+        config_path: &Path,
+        messages: &mut Vec<ValidationMessage>,
+    ) -> HashMap<String, Vec<EnvVarConfig>> {
+        let mut loaded = HashMap::new();
+        let config_dir = config_path.parent().unwrap_or_else(|| Path::new(""));
+
+        for server in servers {
+            let Some(env_file) = server.extra_options.get(ENV_FILE_KEY).and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let env_path = config_dir.join(env_file);
+
+            let content = match self.fs.read_to_string(&env_path).await {
+                Ok(content) => content,
+                Err(_) => {
+                    messages.push(ValidationMessage {
+                        level: MessageLevel::Warning,
+                        message: format!("Env file for server '{}' not found", server.name),
+                        path: Some(env_path.display().to_string()),
+                        suggestion: Some("Create the env file or remove the envFile reference".to_string()),
+                        code: Some("ENV_FILE_MISSING".to_string()),
+                        actions: Vec::new(),
+                    });
+                    continue;
+                }
+            };
+
+            let source = env_path.display().to_string();
+            let configs = parse_env_file(&content)
+                .into_iter()
+                .map(|(name, _)| EnvVarConfig {
+                    name,
+                    description: None,
+                    required: false,
+                    default: None,
+                    example: None,
+                    source_location: Some(source.clone()),
+                })
+                .collect();
+            loaded.insert(server.name.clone(), configs);
+        }
+
+        loaded
+    }
+}
+
+impl Default for ConfigValidator {
+    fn default() -> Self {
+        Self::new().expect("Failed to create default ConfigValidator")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::profiles::{ApplicationCategory, ApplicationMetadata, ApplicationRegistry, ConfigStructure, DetectionStrategy, DetectionMethod};
+    use tempfile::tempdir;
+    use std::fs;
+
+    fn create_test_application() -> ApplicationProfile {
+        ApplicationProfile {
+            id: "test-app".to_string(),
+            name: "Test Application".to_string(),
+            bundle_id: "com.test.app".to_string(),
+            config_path: "~/test/config.json".to_string(),
+            alt_config_paths: vec!["~/.config/test/config.json".to_string()],
+            config_format: ConfigFormat::Json,
+            config_structure: ConfigStructure::DirectMcpServers,
+            executable_paths: vec!["/Applications/Test.app".to_string()],
+            alt_executable_paths: vec![],
+            detection_strategy: DetectionStrategy {
+                use_bundle_lookup: false,
+                use_executable_check: false,
+                use_config_check: true,
+                use_spotlight: false,
+                priority_order: vec![DetectionMethod::ConfigCheck],
+            },
+            metadata: ApplicationMetadata {
+                version: None,
+                developer: "Test Developer".to_string(),
+                category: ApplicationCategory::Other("Test".to_string()),
+                mcp_version: "1.0".to_string(),
+                notes: None,
+                requires_permissions: false,
+                ..Default::default()
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn test_config_validator_creation() {
+        let validator = ConfigValidator::new();
+        assert!(validator.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_validate_missing_config() {
+        let validator = ConfigValidator::new().unwrap();
+        let app = create_test_application();
+        
+        let result = validator.validate_application_config(&app).await;
+        assert!(result.is_ok());
+        
+        let validation_result = result.unwrap();
+        assert!(!validation_result.is_valid);
+        assert!(validation_result.config_path.is_none());
+        assert!(!validation_result.messages.is_empty());
+        assert_eq!(validation_result.messages[0].level, MessageLevel::Warning);
+        assert!(validation_result.messages[0].message.contains("Configuration file not found"));
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_server_diagnostics_carry_codes_and_fixes() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempdir().unwrap();
+        let script = temp_dir.path().join("server.sh");
+        fs::write(&script, "#!/bin/sh\n").unwrap();
+        fs::set_permissions(&script, fs::Permissions::from_mode(0o644)).unwrap();
+        let config_path = temp_dir.path().join("claude_desktop_config.json");
+        let config = serde_json::json!({"mcpServers": {
+            "github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]},
+            "local": {"command": script, "cwd": temp_dir.path().join("missing")}
+        }});
+        fs::write(&config_path, config.to_string()).unwrap();
+
+        let mut profile = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        profile.set_config_path(config_path.to_string_lossy());
+        profile.alt_config_paths.clear();
+
+        let result = ConfigValidator::new().unwrap().validate_application_config(&profile).await.unwrap();
+        let find = |code: &str| result.messages.iter().find(|m| m.code.as_deref() == Some(code)).unwrap_or_else(|| panic!("{} reported", code));
+
+        let bare = find("COMMAND_NOT_ABSOLUTE");
+        assert!(bare.message.contains("github"));
+        assert!(bare.actions.contains(&SuggestedAction::Fix {
+            title: "Use the absolute path to the command".to_string(),
+            operation: crate::detection::suggestions::FixOperation::AbsoluteCommand,
+            server: Some("github".to_string()),
+        }));
+        assert!(find("COMMAND_NOT_EXECUTABLE").message.contains("local"));
+        assert!(find("CWD_MISSING").message.contains("local"));
+        assert!(!find("CWD_MISSING").actions.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_validate_claude_desktop_config() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("claude_desktop_config.json");
+        
+        let config_content = r#"{
+            "mcpServers": {
+                "filesystem": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+                    "env": {
+                        "NODE_ENV": "production"
+                    }
+                },
+                "git": {
+                    "command": "python",
+                    "args": ["-m", "mcp_git"],
+                    "description": "Git repository server"
+                }
+            }
+        }"#;
+        
+        fs::write(&config_path, config_content).unwrap();
+        
+        let mut app = create_test_application();
+        app.id = "claude-desktop".to_string();
+        app.config_path = config_path.to_string_lossy().to_string();
+        
+        let validator = ConfigValidator::new().unwrap();
+        let result = validator.validate_application_config(&app).await.unwrap();
+        
+        assert!(result.is_valid);
+        assert_eq!(result.mcp_servers.len(), 2);
+        assert_eq!(result.mcp_servers[0].name, "filesystem");
+        assert_eq!(result.mcp_servers[0].command, Some("npx".to_string()));
+        assert_eq!(result.mcp_servers[0].args, vec!["-y", "@modelcontextprotocol/server-filesystem", "/tmp"]);
+        assert_eq!(result.mcp_servers[1].name, "git");
+        assert_eq!(result.mcp_servers[1].command, Some("python".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_nested_transport_is_read_flat() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("claude_desktop_config.json");
+        let config = serde_json::json!({
+            "mcpServers": {
+                "filesystem": {
+                    "transport": {"type": "stdio", "command": "npx", "args": ["-y", "@modelcontextprotocol/server-filesystem"]},
+                    "env": {"NODE_ENV": "production"}
+                }
+            }
+        });
+        fs::write(&config_path, config.to_string()).unwrap();
+
+        let mut app = create_test_application();
+        app.id = "claude-desktop".to_string();
+        app.config_path = config_path.to_string_lossy().to_string();
+
+        let result = ConfigValidator::new().unwrap().validate_application_config(&app).await.unwrap();
+        let server = &result.mcp_servers[0];
+        assert_eq!(server.command.as_deref(), Some("npx"));
+        assert_eq!(server.args, vec!["-y", "@modelcontextprotocol/server-filesystem"]);
+        assert_eq!(server.env["NODE_ENV"], "production");
+
+        // Written back in the target's flat shape
+        let adapter = crate::adapters::AdapterFactory::create_adapter(&app).unwrap();
+        let extracted = adapter.extract_server_configs(&config).await.unwrap();
+        let written = adapter.apply_server_configs(&config, &extracted.servers).await.unwrap().config;
+        assert_eq!(written["mcpServers"]["filesystem"]["command"], "npx");
+        assert!(written["mcpServers"]["filesystem"].get("transport").is_none());
+
+        let remote = serde_json::json!({"transport": {"type": "sse", "url": "https://example.com/sse"}});
+        assert_eq!(crate::analysis::SchemaDetector::new().detect_server_type(&remote), "sse");
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_with_env_file() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("claude_desktop_config.json");
+        fs::write(&config_path, r#"{
+            "mcpServers": {
+                "github": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-github"],
+                    "env": { "GITHUB_HOST": "inline.example.com" },
+                    "envFile": ".env"
+                },
+                "missing": { "command": "node", "envFile": "missing.env" }
+            }
+        }"#).unwrap();
+        fs::write(
+            temp_dir.path().join(".env"),
+            "# GitHub access\nexport GITHUB_TOKEN=\"ghp_123\"\nGITHUB_HOST=file.example.com\nLOG_LEVEL=debug # verbose\n",
+        ).unwrap();
+
+        let mut app = create_test_application();
+        app.id = "claude-desktop".to_string();
+        app.config_path = config_path.to_string_lossy().to_string();
+
+        let validator = ConfigValidator::new().unwrap();
+        let result = validator.validate_application_config(&app).await.unwrap();
+
+        assert!(result.is_valid);
+        // Env file values stay in the file; only inline entries are part of the server
+        let github = result.mcp_servers.iter().find(|s| s.name == "github").unwrap();
+        assert_eq!(github.env.len(), 1);
+        assert_eq!(github.env["GITHUB_HOST"], "inline.example.com");
+        assert!(!serde_json::to_string(&result).unwrap().contains("ghp_123"));
+
+        let vars = &result.env_file_vars["github"];
+        let names: Vec<&str> = vars.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, vec!["GITHUB_TOKEN", "GITHUB_HOST", "LOG_LEVEL"]);
+        assert!(vars[0].source_location.as_deref().unwrap().ends_with(".env"));
+        assert!(vars.iter().all(|v| v.default.is_none()));
+
+        // A missing env file is only a warning
+        assert!(result.messages.iter().any(|m| m.level == MessageLevel::Warning && m.message.contains("'missing'")));
+    }
+
+    #[tokio::test]
+    async fn test_validate_config_from_in_memory_fs() {
+        let fs = crate::filesystem::InMemoryFs::case_sensitive().with_file(
+            "/home/me/.config/Claude/claude_desktop_config.json",
+            r#"{"mcpServers": {"memory": {"command": "npx", "args": ["-y", "@mcp/memory"]}}}"#,
+        );
+
+        let mut app = create_test_application();
+        app.id = "claude-desktop".to_string();
+        app.config_path = "/home/me/.config/claude/claude_desktop_config.json".to_string();
+        app.alt_config_paths = vec!["/home/me/.config/Claude/claude_desktop_config.json".to_string()];
+
+        let validator = ConfigValidator::with_fs(Arc::new(fs));
+        let result = validator.validate_application_config(&app).await.unwrap();
+
+        // The primary path differs only by case, so only the alternative matches on Linux
+        assert!(result.is_valid);
+        assert_eq!(result.config_path, Some(PathBuf::from("/home/me/.config/Claude/claude_desktop_config.json")));
+        assert_eq!(result.mcp_servers.len(), 1);
+        assert_eq!(result.mcp_servers[0].name, "memory");
+    }
+
+    #[tokio::test]
+    async fn test_read_managed_statuses_from_active_config() {
+        use crate::configuration::provenance::entry_hash;
+
+        let fs = crate::filesystem::InMemoryFs::case_sensitive().with_file(
+            "/home/me/.config/test/config.json",
+            r#"{"mcpServers": {
+                "$schema": "https://example.com/schema.json",
+                "memory": {"command": "npx", "args": ["-y", "@mcp/memory"]},
+                "fetch": {"command": "uvx", "args": ["mcp-server-fetch", "--verbose"]},
+                "notes": {"command": "notes-mcp"}
+            }}"#,
+        );
+        let mut app = create_test_application();
+        app.set_config_path("/home/me/.config/test/config.json");
+        app.alt_config_paths.clear();
+
+        let mut sidecar = ProvenanceSidecar::default();
+        sidecar.record(&app.id, "memory", &entry_hash(&serde_json::json!({"command": "npx", "args": ["-y", "@mcp/memory"]})));
+        // Written as plain `mcp-server-fetch`, then edited by hand
+        sidecar.record(&app.id, "fetch", &entry_hash(&serde_json::json!({"command": "uvx", "args": ["mcp-server-fetch"]})));
+
+        let validator = ConfigValidator::with_fs(Arc::new(fs));
+        let statuses = validator.read_managed_statuses(&app, &sidecar).await.unwrap();
+        assert_eq!(statuses, vec![
+            ("fetch".to_string(), ManagedStatus::ModifiedExternally),
+            ("memory".to_string(), ManagedStatus::Managed),
+            ("notes".to_string(), ManagedStatus::Unmanaged),
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_parse_yaml_config() {
+        let validator = ConfigValidator::new().unwrap();
+        let yaml_content = r#"
+mcp:
+  servers:
+    test-server:
+      command: "python"
+      args: ["-m", "test_server"]
+      enabled: true
+"#;
+        
+        let parsed = validator.parse_config_content(yaml_content, &ConfigFormat::Yaml);
+        assert!(parsed.is_ok());
+        
+        let config = parsed.unwrap();
+        assert!(config.get("mcp").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_parse_toml_config() {
+        let validator = ConfigValidator::new().unwrap();
+        let toml_content = r#"
+[mcp.servers.test-server]
+command = "python"
+args = ["-m", "test_server"]
+enabled = true
+"#;
+        
+        let parsed = validator.parse_config_content(toml_content, &ConfigFormat::Toml);
+        assert!(parsed.is_ok());
+        
+        let config = parsed.unwrap();
+        assert!(config.get("mcp").is_some());
+    }
+
+    #[tokio::test]
+    async fn test_validation_summary() {
+        let validator = ConfigValidator::new().unwrap();
+        let app1 = create_test_application();
+        let mut app2 = create_test_application();
+        app2.id = "app2".to_string();
+        
+        let results = vec![
+            ConfigValidationResult {
+                application: app1,
+                is_valid: true,
+                config_path: Some(PathBuf::from("/test/config.json")),
+                detected_format: Some(ConfigFormat::Json),
+                mcp_servers: vec![McpServerConfig {
+                    name: "test".to_string(),
+                    command: Some("test".to_string()),
+                    args: vec![],
+                    env: HashMap::new(),
+                    cwd: None,
+                    inherit_env: true,
+                    extra_options: HashMap::new(),
+                    server_type: ServerType::Stdio,
+                    metadata: ServerMetadata {
+                        description: None,
+                        version: None,
+                        author: None,
+                        capabilities: vec![],
+                        enabled: true,
+                        source: ConfigSource::MainConfig,
+                    },
+                }],
+                messages: vec![],
+                raw_config: None,
+                env_file_vars: HashMap::new(),
+                validated_at: chrono::Utc::now(),
+            },
+            ConfigValidationResult {
+                application: app2,
+                is_valid: false,
+                config_path: None,
+                detected_format: None,
+                mcp_servers: vec![],
+                messages: vec![],
+                raw_config: None,
+                env_file_vars: HashMap::new(),
+                validated_at: chrono::Utc::now(),
+            },
+        ];
+        
+        let summary = validator.get_validation_summary(&results);
+        assert_eq!(summary.total_applications, 2);
+        assert_eq!(summary.valid_configs, 1);
+        assert_eq!(summary.invalid_configs, 1);
+        assert_eq!(summary.total_servers, 1);
+        assert_eq!(summary.applications_with_servers, 1);
+        assert_eq!(summary.applications_without_servers, 1);
+        assert_eq!(summary.format_breakdown.get("JSON"), Some(&1));
+    }
+
+    #[test]
+    fn test_server_type_serialization() {
+        let server_types = vec![
+            ServerType::Stdio,
+            ServerType::Sse { url: "http://example.com".to_string() },
+            ServerType::WebSocket { url: "ws://example.com".to_string() },
+            ServerType::Http { base_url: "https://api.example.com".to_string() },
+            ServerType::Custom("custom".to_string()),
+        ];
+        
+        for server_type in server_types {
+            let serialized = serde_json::to_string(&server_type).unwrap();
+            let deserialized: ServerType = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(server_type, deserialized);
+        }
+    }
+
+    #[test]
+    fn test_validation_message_levels() {
+        let levels = vec![
+            MessageLevel::Info,
+            MessageLevel::Warning,
+            MessageLevel::Error,
+            MessageLevel::Critical,
+        ];
+        
+        for level in levels {
+            let serialized = serde_json::to_string(&level).unwrap();
+            let deserialized: MessageLevel = serde_json::from_str(&serialized).unwrap();
+            assert_eq!(level, deserialized);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_multiple_config_validation() {
+        let validator = ConfigValidator::new().unwrap();
+        let apps = vec![create_test_application()];
+        
+        let results = validator.validate_multiple_configs(&apps).await;
+        assert!(results.is_ok());
+        assert_eq!(results.unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_disabled_flag_is_extracted() {
+        let validator = ConfigValidator::new().unwrap();
+        let config = serde_json::json!({"mcpServers": {
+            "github": {"command": "npx", "disabled": true},
+            "slack": {"command": "npx"}
+        }});
+
+        let mut servers = validator.extract_claude_desktop_servers(&config).unwrap();
+        servers.sort_by(|a, b| a.name.cmp(&b.name));
+        assert!(!servers[0].metadata.enabled);
+        assert!(servers[1].metadata.enabled);
+    }
+
+    #[tokio::test]
+    async fn test_zed_config_extraction() {
+        let validator = ConfigValidator::new().unwrap();
+        let zed = ApplicationRegistry::new().get_application("zed").unwrap().clone();
+        let zed_config = serde_json::json!({
+            "theme": "One Dark",
+            "context_servers": {
+                "filesystem": {
+                    "command": "npx",
+                    "args": ["-y", "@modelcontextprotocol/server-filesystem", "/tmp"],
+                    "enabled": true,
+                    "description": "File system access"
+                },
+                "github": {
+                    "command": {
+                        "path": "npx",
+                        "args": ["-y", "@modelcontextprotocol/server-github"],
+                        "env": {"GITHUB_TOKEN": "abc"}
+                    },
+                    "enabled": false
+                }
+            }
+        });
+        
+        let mut servers = validator.extract_zed_servers(&zed_config, &zed).unwrap();
+        servers.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(servers.len(), 2);
+        assert_eq!(servers[0].name, "filesystem");
+        assert_eq!(servers[0].command, Some("npx".to_string()));
+        assert!(servers[0].metadata.enabled);
+        assert_eq!(servers[1].command, Some("npx".to_string()));
+        assert_eq!(servers[1].args, vec!["-y", "@modelcontextprotocol/server-github"]);
+        assert_eq!(servers[1].env["GITHUB_TOKEN"], "abc");
+        assert!(!servers[1].metadata.enabled);
+    }
+
+    #[tokio::test]
+    async fn test_generic_config_extraction() {
+        let validator = ConfigValidator::new().unwrap();
+        let generic_config = serde_json::json!({
+            "mcpServers": {
+                "custom-server": {
+                    "command": "custom-mcp-server",
+                    "args": ["--port", "8080"],
+                    "description": "Custom MCP server"
+                }
+            }
+        });
+        
+        let mut profile = ApplicationRegistry::new().get_application("claude-desktop").unwrap().clone();
+        profile.id = "custom-app".to_string();
+        let servers = validator.extract_generic_servers(&generic_config, &profile).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "custom-server");
+        assert_eq!(servers[0].command, Some("custom-mcp-server".to_string()));
+        assert_eq!(servers[0].args, vec!["--port", "8080"]);
+    }
+
+    #[test]
+    fn test_declared_layout_is_extracted() {
+        let validator = ConfigValidator::new().unwrap();
+        let mut profile = ApplicationRegistry::new().get_application("cursor").unwrap().clone();
+        profile.id = "orbit".to_string();
+        profile.metadata.declarative_adapter = Some(serde_json::from_value(serde_json::json!({
+            "container": "/assistant/tools",
+            "layout": {"kind": "list", "name": "/id"},
+            "fields": {"command": "/launch/program", "args": "/launch/arguments"}
+        })).unwrap());
+        let config = serde_json::json!({
+            "mcpServers": {"ignored": {"command": "node"}},
+            "assistant": {"tools": [{"id": "github", "launch": {"program": "npx", "arguments": ["-y", "server-github"]}}]}
+        });
+
+        let servers = validator.extract_mcp_servers(&config, &profile, Path::new(&profile.config_path)).unwrap();
+        assert_eq!(servers.len(), 1);
+        assert_eq!(servers[0].name, "github");
+        assert_eq!(servers[0].command.as_deref(), Some("npx"));
+        assert_eq!(servers[0].args, vec!["-y", "server-github"]);
+    }
+
+    #[tokio::test]
+    async fn test_invalid_json_config() {
+        let validator = ConfigValidator::new().unwrap();
+        let invalid_json = "{ invalid json content }";
+        
+        let result = validator.parse_config_content(invalid_json, &ConfigFormat::Json);
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_config_source_detection() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        
+        let config_content = r#"{
+            "mcpServers": {
+                "test": {
+                    "command": "test-command"
+                }
+            }
+        }"#;
+        
+        fs::write(&config_path, config_content).unwrap();
+        
+        let mut app = create_test_application();
+        app.id = "claude-desktop".to_string();
+        app.config_path = config_path.to_string_lossy().to_string();
+        
+        let validator = ConfigValidator::new().unwrap();
+        let result = validator.validate_application_config(&app).await.unwrap();
+        
+        assert!(result.is_valid);
+        assert_eq!(result.mcp_servers[0].metadata.source, ConfigSource::MainConfig);
+    }
+
+    #[tokio::test]
+    async fn test_effective_servers_across_primary_and_alternates() {
+        let temp_dir = tempdir().unwrap();
+        let write = |name: &str, servers: &[&str]| {
+            let entries: serde_json::Map<String, JsonValue> = servers
+                .iter()
+                .map(|s| (s.to_string(), serde_json::json!({ "command": format!("{}-from-{}", s, name) })))
+                .collect();
+            let path = temp_dir.path().join(name);
+            fs::write(&path, serde_json::json!({ "mcpServers": entries }).to_string()).unwrap();
+            path
+        };
+        let primary = write("primary.json", &["github", "memory"]);
+        let legacy = write("legacy.json", &["memory", "search"]);
+        let oldest = write("oldest.json", &["search", "notes", "github"]);
+
+        let mut app = create_test_application();
+        app.id = "claude-desktop".to_string();
+        app.config_path = primary.to_string_lossy().to_string();
+        app.alt_config_paths = vec![legacy.to_string_lossy().to_string(), oldest.to_string_lossy().to_string()];
+
+        let validator = ConfigValidator::new().unwrap();
+        let effective = validator.read_effective_servers(&app).await.unwrap();
+
+        let command = |name: &str| {
+            effective.servers.iter().find(|s| s.name == name).and_then(|s| s.command.clone()).unwrap()
+        };
+        assert_eq!(effective.servers.len(), 4);
+        assert_eq!(command("github"), "github-from-primary.json");
+        assert_eq!(command("memory"), "memory-from-primary.json");
+        assert_eq!(command("search"), "search-from-legacy.json");
+        assert_eq!(command("notes"), "notes-from-oldest.json");
+        assert_eq!(effective.sources["search"], legacy);
+
+        assert_eq!(effective.shadowed, vec![
+            ShadowedServer { name: "memory".to_string(), path: legacy.clone(), shadowed_by: primary.clone() },
+            ShadowedServer { name: "github".to_string(), path: oldest.clone(), shadowed_by: primary.clone() },
+            ShadowedServer { name: "search".to_string(), path: oldest.clone(), shadowed_by: legacy.clone() },
+        ]);
+        assert_eq!(effective.messages.len(), 3);
+        assert!(effective.messages.iter().all(|m| m.level == MessageLevel::Warning));
+        assert_eq!(effective.messages[1].path, Some(oldest.display().to_string()));
+        assert_eq!(effective.active_config, Some(primary.clone()));
+
+        // Cards show the same merged view
+        let result = validator.validate_application_config(&app).await.unwrap();
+        assert_eq!(result.mcp_servers.len(), 4);
+        assert_eq!(result.config_path, Some(primary.clone()));
+        assert_eq!(result.messages.iter().filter(|m| m.message.contains("shadowed")).count(), 3);
+
+        // Once the primary is gone, the first alternate becomes the write target
+        fs::remove_file(&primary).unwrap();
+        assert_eq!(validator.resolve_active_config(&app).await.unwrap(), legacy);
+        let effective = validator.read_effective_servers(&app).await.unwrap();
+        assert_eq!(effective.servers.len(), 4);
+        assert_eq!(effective.sources["github"], oldest);
+        assert_eq!(effective.sources["memory"], legacy);
+    }
+
+    #[tokio::test]
+    async fn test_servers_grouped_by_runtime() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("config.json");
+        fs::write(&config_path, serde_json::json!({
+            "mcpServers": {
+                "github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]},
+                "memory": {"command": "/usr/local/bin/node", "args": ["memory/index.js"]},
+                "fetch": {"command": "uvx", "args": ["mcp-server-fetch"]},
+                "sqlite": {"command": "python3", "args": ["-m", "mcp_server_sqlite"]},
+                "postgres": {"command": "docker", "args": ["run", "-i", "mcp/postgres"]},
+                "notes": {"command": "notes-mcp"}
+            }
+        }).to_string()).unwrap();
+
+        let mut application = create_test_application();
+        application.config_path = config_path.to_string_lossy().to_string();
+        application.alt_config_paths.clear();
+        let scan = vec![ConfigValidator::new().unwrap().validate_application_config(&application).await.unwrap()];
+
+        let grouped = ApplicationRegistry::servers_by_runtime(&scan);
+        let names = |runtime: ServerRuntime| -> Vec<String> {
+            grouped.get(&runtime).map(|refs| refs.iter().map(|r| r.server_name.clone()).collect()).unwrap_or_default()
+        };
+        assert_eq!(names(ServerRuntime::Node), vec!["github", "memory"]);
+        assert_eq!(names(ServerRuntime::Python), vec!["fetch", "sqlite"]);
+        assert_eq!(names(ServerRuntime::Docker), vec!["postgres"]);
+        assert_eq!(names(ServerRuntime::Other("notes-mcp".to_string())), vec!["notes"]);
+        assert_eq!(grouped[&ServerRuntime::Node][0].app_id, "test-app");
+        assert_eq!(grouped[&ServerRuntime::Node][0].config_path.as_deref(), Some(config_path.as_path()));
+    }
+
+    #[tokio::test]
+    async fn test_vscode_reads_servers_from_chat_mcp_key() {
+        let temp_dir = tempdir().unwrap();
+        let config_path = temp_dir.path().join("settings.json");
+        fs::write(&config_path, serde_json::json!({
+            "editor.fontSize": 14,
+            "chat.mcp.servers": {
+                "github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}
+            }
+        }).to_string()).unwrap();
+
+        let registry = ApplicationRegistry::new();
+        let mut vscode = registry.get_application("vscode").unwrap().clone();
+        vscode.set_config_path(config_path.to_string_lossy());
+        vscode.alt_config_paths.clear();
+        vscode.metadata.version = Some("1.104.1".to_string());
+        assert_eq!(vscode.get_mcp_servers_path(), vec!["chat", "mcp", "servers"]);
+
+        let result = ConfigValidator::new().unwrap().validate_application_config(&vscode).await.unwrap();
+        assert_eq!(result.mcp_servers.len(), 1);
+        assert_eq!(result.mcp_servers[0].name, "github");
+        assert_eq!(result.mcp_servers[0].args, vec!["-y", "@modelcontextprotocol/server-github"]);
+
+        vscode.metadata.version = Some("1.90.0".to_string());
+        assert_eq!(vscode.active_config_structure(), &ConfigStructure::DirectMcpServers);
+    }
+}
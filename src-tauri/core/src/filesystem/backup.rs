@@ -189,6 +189,23 @@ impl BackupService {
     
     /// Restore a file from backup
     pub fn restore_backup(&self, backup_metadata: &BackupMetadata) -> Result<()> {
+        let restore_content = self.read_backup(backup_metadata)?;
+        
+        // Ensure target directory exists
+        if let Some(parent) = backup_metadata.original_path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+        
+        // Write restored content to original location
+        fs::write(&backup_metadata.original_path, &restore_content)
+            .with_context(|| format!("Failed to restore file: {}", backup_metadata.original_path.display()))?;
+        
+        Ok(())
+    }
+    
+    /// Read a backup's original content, verifying its integrity first
+    pub fn read_backup(&self, backup_metadata: &BackupMetadata) -> Result<Vec<u8>> {
         if !backup_metadata.backup_path.exists() {
             return Err(anyhow::anyhow!("Backup file does not exist: {}", backup_metadata.backup_path.display()));
         }
@@ -204,23 +221,11 @@ impl BackupService {
         }
         
         // Decompress if necessary
-        let restore_content = if backup_metadata.is_compressed {
-            self.decompress_data(&backup_content)?
+        if backup_metadata.is_compressed {
+            self.decompress_data(&backup_content)
         } else {
-            backup_content
-        };
-        
-        // Ensure target directory exists
-        if let Some(parent) = backup_metadata.original_path.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+            Ok(backup_content)
         }
-        
-        // Write restored content to original location
-        fs::write(&backup_metadata.original_path, &restore_content)
-            .with_context(|| format!("Failed to restore file: {}", backup_metadata.original_path.display()))?;
-        
-        Ok(())
     }
     
     /// List all backups for a specific file
@@ -246,7 +251,7 @@ impl BackupService {
         }
         
         // Sort by creation time (newest first)
-        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
         
         Ok(backups)
     }
@@ -270,7 +275,7 @@ impl BackupService {
         }
         
         // Sort by creation time (newest first)
-        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        backups.sort_by_key(|backup| std::cmp::Reverse(backup.created_at));
         
         Ok(backups)
     }
@@ -371,7 +376,7 @@ impl BackupService {
         
         if backups.len() > self.max_backups_per_file {
             // Sort by creation time (oldest first for deletion)
-            backups.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+            backups.sort_by_key(|backup| backup.created_at);
             
             // Delete oldest backups beyond the limit
             let to_delete = backups.len() - self.max_backups_per_file;
@@ -383,7 +388,7 @@ impl BackupService {
         Ok(())
     }
     
-    fn calculate_hash(&self, data: &[u8]) -> String {
+    pub(crate) fn calculate_hash(&self, data: &[u8]) -> String {
         use sha2::{Sha256, Digest};
         
         let mut hasher = Sha256::new();
@@ -0,0 +1,306 @@
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use uuid::Uuid;
+
+use super::backup::{BackupMetadata, BackupService};
+
+/// Where server entries live in the config formats we back up
+const SERVER_CONTAINERS: &[&[&str]] = &[&["mcpServers"], &["mcp", "servers"], &["context_servers"], &["servers"]];
+
+/// Server names that appeared or disappeared between two versions of a config
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServerDelta {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+}
+
+impl ServerDelta {
+    fn between(before: &BTreeMap<String, Value>, after: &BTreeMap<String, Value>) -> Self {
+        Self {
+            added: after.keys().filter(|name| !before.contains_key(*name)).cloned().collect(),
+            removed: before.keys().filter(|name| !after.contains_key(*name)).cloned().collect(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty()
+    }
+}
+
+impl fmt::Display for ServerDelta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_empty() {
+            return write!(f, "no server changes");
+        }
+        let changes: Vec<String> = self
+            .added
+            .iter()
+            .map(|name| format!("+{}", name))
+            .chain(self.removed.iter().map(|name| format!("-{}", name)))
+            .collect();
+        write!(f, "{}", changes.join(", "))
+    }
+}
+
+/// A backup of a file along with what changed since the backup before it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotSummary {
+    pub backup: BackupMetadata,
+    /// Servers added and removed relative to the previous snapshot; the oldest
+    /// snapshot lists all of its servers as added
+    pub delta: ServerDelta,
+}
+
+/// A backup whose content mentions the searched text
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SnapshotMatch {
+    pub backup: BackupMetadata,
+    /// Servers whose name or entry contains the text, such as a package in `args`
+    pub servers: Vec<String>,
+    /// Trimmed lines of the backup containing the text
+    pub lines: Vec<String>,
+}
+
+/// What restoring a backup would change in the current file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RestorePreview {
+    pub backup: BackupMetadata,
+    /// `added` are servers the restore brings back, `removed` are servers it drops
+    pub delta: ServerDelta,
+    /// Servers present in both whose entries differ
+    pub changed: Vec<String>,
+    /// The current file already matches the backup byte for byte
+    pub identical: bool,
+}
+
+/// Query view over the backup store for finding and previewing an older config.
+///
+/// Server entries are parsed from a backup the first time they are needed and cached
+/// for the browser's lifetime; backups are never modified after they are written.
+pub struct BackupBrowser<'a> {
+    service: &'a BackupService,
+    servers: RefCell<HashMap<Uuid, BTreeMap<String, Value>>>,
+}
+
+impl<'a> BackupBrowser<'a> {
+    pub fn new(service: &'a BackupService) -> Self {
+        Self {
+            service,
+            servers: RefCell::new(HashMap::new()),
+        }
+    }
+
+    /// List a file's backups, newest first, with the server changes each one recorded
+    pub fn snapshots<P: AsRef<Path>>(&self, file_path: P) -> Result<Vec<SnapshotSummary>> {
+        let backups = self.service.list_backups_for_file(file_path)?;
+
+        let mut summaries = Vec::with_capacity(backups.len());
+        for (index, backup) in backups.iter().enumerate() {
+            let servers = self.servers_in(backup)?;
+            let previous = match backups.get(index + 1) {
+                Some(older) => self.servers_in(older)?,
+                None => BTreeMap::new(),
+            };
+            summaries.push(SnapshotSummary {
+                backup: backup.clone(),
+                delta: ServerDelta::between(&previous, &servers),
+            });
+        }
+
+        Ok(summaries)
+    }
+
+    /// Find backups of any file mentioning `query`, newest first. Matching ignores case.
+    pub fn search(&self, query: &str) -> Result<Vec<SnapshotMatch>> {
+        let needle = query.to_lowercase();
+        if needle.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut matches = Vec::new();
+        for backup in self.service.list_all_backups()? {
+            let Ok(content) = self.service.read_backup(&backup) else {
+                log::warn!("Skipping unreadable backup {}", backup.backup_path.display());
+                continue;
+            };
+            let content = String::from_utf8_lossy(&content);
+            let lines: Vec<String> = content
+                .lines()
+                .filter(|line| line.to_lowercase().contains(&needle))
+                .map(|line| line.trim().to_string())
+                .collect();
+            if lines.is_empty() {
+                continue;
+            }
+
+            let servers = self
+                .servers_in(&backup)?
+                .iter()
+                .filter(|(name, entry)| {
+                    name.to_lowercase().contains(&needle) || entry.to_string().to_lowercase().contains(&needle)
+                })
+                .map(|(name, _)| name.clone())
+                .collect();
+            matches.push(SnapshotMatch { backup, servers, lines });
+        }
+
+        Ok(matches)
+    }
+
+    /// Compare a backup with the file it would overwrite, without touching either.
+    ///
+    /// A missing current file counts as having no servers.
+    pub fn preview_restore(&self, backup: &BackupMetadata) -> Result<RestorePreview> {
+        let snapshot = self.servers_in(backup)?;
+
+        let current_content = match fs::read(&backup.original_path) {
+            Ok(content) => Some(content),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => None,
+            Err(e) => {
+                return Err(e).with_context(|| format!("Failed to read current file: {}", backup.original_path.display()))
+            }
+        };
+        let current = current_content
+            .as_deref()
+            .map(|content| parse_servers(&backup.original_path, content))
+            .unwrap_or_default();
+        let identical = current_content.as_deref().map(|content| self.service.calculate_hash(content)) == Some(backup.original_hash.clone());
+
+        let changed = snapshot
+            .iter()
+            .filter(|(name, entry)| current.get(*name).is_some_and(|existing| existing != *entry))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        Ok(RestorePreview {
+            backup: backup.clone(),
+            delta: ServerDelta::between(&current, &snapshot),
+            changed,
+            identical,
+        })
+    }
+
+    fn servers_in(&self, backup: &BackupMetadata) -> Result<BTreeMap<String, Value>> {
+        if let Some(servers) = self.servers.borrow().get(&backup.id) {
+            return Ok(servers.clone());
+        }
+
+        let content = self.service.read_backup(backup)?;
+        let servers = parse_servers(&backup.original_path, &content);
+        self.servers.borrow_mut().insert(backup.id, servers.clone());
+        Ok(servers)
+    }
+}
+
+/// Server entries in a config, keyed by name. Unparseable content has no servers.
+fn parse_servers(path: &Path, content: &[u8]) -> BTreeMap<String, Value> {
+    let text = String::from_utf8_lossy(content);
+    let parsed: Option<Value> = match path.extension().and_then(|e| e.to_str()) {
+        Some("yaml") | Some("yml") => serde_yaml::from_str(&text).ok(),
+        Some("toml") => toml::from_str::<toml::Value>(&text).ok().and_then(|v| serde_json::to_value(v).ok()),
+        _ => serde_json::from_str(&text).ok(),
+    };
+    let Some(config) = parsed else {
+        return BTreeMap::new();
+    };
+
+    SERVER_CONTAINERS
+        .iter()
+        .find_map(|container| {
+            container
+                .iter()
+                .try_fold(&config, |node, key| node.get(*key))
+                .and_then(Value::as_object)
+        })
+        .map(|servers| servers.iter().map(|(name, entry)| (name.clone(), entry.clone())).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::filesystem::BackupType;
+    use std::time::Duration;
+    use tempfile::TempDir;
+
+    /// Five snapshots of a Claude config where `slack` is added in the third and removed in the fifth
+    fn seeded_history(temp_dir: &TempDir) -> (BackupService, std::path::PathBuf) {
+        let service = BackupService::new(temp_dir.path().join("backups"), "test_user".to_string()).unwrap();
+        let config = temp_dir.path().join("claude_desktop_config.json");
+
+        let versions = [
+            r#"{"mcpServers": {"github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}}}"#,
+            r#"{"mcpServers": {"github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}, "fetch": {"command": "uvx", "args": ["mcp-server-fetch"]}}}"#,
+            r#"{"mcpServers": {"github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}, "fetch": {"command": "uvx", "args": ["mcp-server-fetch"]}, "slack": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-slack"]}}}"#,
+            r#"{"mcpServers": {"github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}, "fetch": {"command": "uvx", "args": ["mcp-server-fetch", "--ignore-robots-txt"]}, "slack": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-slack"]}}}"#,
+            r#"{"mcpServers": {"github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}, "fetch": {"command": "uvx", "args": ["mcp-server-fetch", "--ignore-robots-txt"]}}}"#,
+        ];
+        for version in versions {
+            fs::write(&config, version).unwrap();
+            service.create_backup(&config, BackupType::Automatic, None).unwrap();
+            // Keep creation times distinct so the history has a stable order
+            std::thread::sleep(Duration::from_millis(5));
+        }
+
+        (service, config)
+    }
+
+    #[test]
+    fn test_snapshots_summarize_server_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let (service, config) = seeded_history(&temp_dir);
+        let browser = BackupBrowser::new(&service);
+
+        let snapshots = browser.snapshots(&config).unwrap();
+        let deltas: Vec<String> = snapshots.iter().map(|s| s.delta.to_string()).collect();
+        assert_eq!(deltas, vec!["-slack", "no server changes", "+slack", "+fetch", "+github"]);
+        assert!(snapshots.iter().all(|s| s.backup.original_size > 0));
+
+        // Every backup was parsed once and is served from the cache afterwards
+        assert_eq!(browser.servers.borrow().len(), 5);
+        assert_eq!(browser.snapshots(&config).unwrap().len(), 5);
+    }
+
+    #[test]
+    fn test_search_finds_server_across_snapshots() {
+        let temp_dir = TempDir::new().unwrap();
+        let (service, _) = seeded_history(&temp_dir);
+        let browser = BackupBrowser::new(&service);
+
+        let by_package = browser.search("server-SLACK").unwrap();
+        assert_eq!(by_package.len(), 2);
+        assert!(by_package.iter().all(|m| m.servers == vec!["slack"] && m.lines.len() == 1));
+        assert!(by_package[0].backup.created_at > by_package[1].backup.created_at);
+
+        assert_eq!(browser.search("github").unwrap().len(), 5);
+        assert!(browser.search("postgres").unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_preview_restore_diffs_against_current_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let (service, config) = seeded_history(&temp_dir);
+        let browser = BackupBrowser::new(&service);
+        let snapshots = browser.snapshots(&config).unwrap();
+
+        // The newest backup matches what is on disk
+        let latest = browser.preview_restore(&snapshots[0].backup).unwrap();
+        assert!(latest.identical && latest.delta.is_empty() && latest.changed.is_empty());
+
+        // Going back to before `fetch` gained a flag and `slack` was removed
+        let before = &snapshots[2].backup;
+        let preview = browser.preview_restore(before).unwrap();
+        assert!(!preview.identical);
+        assert_eq!(preview.delta, ServerDelta { added: vec!["slack".to_string()], removed: Vec::new() });
+        assert_eq!(preview.changed, vec!["fetch"]);
+
+        service.restore_backup(before).unwrap();
+        assert!(browser.preview_restore(before).unwrap().identical);
+    }
+}
@@ -8,6 +8,9 @@ use anyhow::{Result, Context, anyhow};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
 
+use super::jsonc::{has_jsonc_syntax, strip_jsonc, update_jsonc_preserving};
+use super::managed::{ExternallyManaged, ManagedConfigDetector};
+use super::yaml_edit::{parse_yaml_documents, parse_yaml_view, update_yaml_preserving};
 use crate::models::{ComplianceModel, ComplianceResult, DataClassification};
 use crate::models::audit::{AuditInfo, AuditEntry};
 use crate::models::security::AccessControl;
@@ -25,7 +28,7 @@ impl ConfigFormat {
     /// Detect format from file extension
     pub fn from_extension(path: &Path) -> Result<Self> {
         match path.extension().and_then(|ext| ext.to_str()) {
-            Some("json") => Ok(ConfigFormat::Json),
+            Some("json") | Some("jsonc") => Ok(ConfigFormat::Json),
             Some("yaml") | Some("yml") => Ok(ConfigFormat::Yaml),
             Some("toml") => Ok(ConfigFormat::Toml),
             Some(ext) => Err(anyhow!("Unsupported file extension: {}", ext)),
@@ -139,6 +142,12 @@ pub struct ConfigFileService {
     
     /// Backup directory
     backup_dir: PathBuf,
+    
+    /// Detects configs distributed by MDM or system policy
+    managed_detector: ManagedConfigDetector,
+    
+    /// Whether to write configs even when they are externally managed
+    force_managed_writes: bool,
 }
 
 impl ConfigFileService {
@@ -149,6 +158,8 @@ impl ConfigFileService {
             operations: Vec::new(),
             auto_backup: true,
             backup_dir,
+            managed_detector: ManagedConfigDetector::new(),
+            force_managed_writes: false,
         }
     }
     
@@ -192,9 +203,37 @@ impl ConfigFileService {
     where
         T: Serialize,
     {
+        let content = self.render_config(path, data);
+        self.write_recorded(path, content).await
+    }
+
+    /// Write text that is already in the file's format, such as a spliced IDE options file,
+    /// with the same managed-config guard, backup and operation record as `write_config`
+    pub async fn write_content(&mut self, path: &Path, content: impl Into<String>) -> Result<()> {
+        self.write_recorded(path, Ok(content.into())).await
+    }
+
+    async fn write_recorded(&mut self, path: &Path, content: Result<String>) -> Result<()> {
         let operation_id = Uuid::new_v4();
         let start_time = Utc::now();
         
+        // Writes to externally managed configs would just be reverted
+        if let Err(error) = self.ensure_writable(path) {
+            self.operations.push(ConfigOperation {
+                id: operation_id,
+                operation_type: ConfigOperationType::Write,
+                file_path: path.to_path_buf(),
+                timestamp: start_time,
+                user_id: self.user_id.clone(),
+                success: false,
+                error_message: Some(error.to_string()),
+                backup_path: None,
+                hash_before: None,
+                hash_after: None,
+            });
+            return Err(error);
+        }
+        
         // Create backup if file exists and auto_backup is enabled
         let backup_path = if self.auto_backup && path.exists() {
             Some(self.create_backup(path).await?)
@@ -209,7 +248,7 @@ impl ConfigFileService {
             None
         };
         
-        let result = self.write_config_internal(path, data).await;
+        let result = content.and_then(|content| Self::write_atomic(path, content));
         
         // Get hash after operation
         let hash_after = if result.is_ok() && path.exists() {
@@ -237,6 +276,19 @@ impl ConfigFileService {
         result
     }
     
+    /// Write configuration with object keys in stable alphabetical order
+    ///
+    /// Repeated writes of the same logical config produce identical bytes, which keeps
+    /// diffs minimal for users who version-control their configs.
+    pub async fn write_config_sorted<T>(&mut self, path: &Path, data: &T) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let value = serde_json::to_value(data)
+            .with_context(|| "Failed to serialize configuration")?;
+        self.write_config(path, &sort_json_keys(value)).await
+    }
+    
     /// Validate configuration file format and content
     pub async fn validate_config(&mut self, path: &Path) -> Result<ConfigFileMetadata> {
         let operation_id = Uuid::new_v4();
@@ -337,6 +389,71 @@ impl ConfigFileService {
         self.auto_backup = enabled;
     }
     
+    /// Allow writes to configs that MDM or policy manages
+    pub fn set_force_managed_writes(&mut self, enabled: bool) {
+        self.force_managed_writes = enabled;
+    }
+    
+    /// Fail with [`ExternallyManaged`] if `path` is externally managed and writes are not forced
+    pub fn ensure_writable(&self, path: &Path) -> Result<()> {
+        if !self.force_managed_writes && path.exists() {
+            let state = self.managed_detector.detect(path, None);
+            if state.is_externally_managed() {
+                return Err(ExternallyManaged {
+                    path: path.to_path_buf(),
+                    reasons: state.reasons(),
+                }
+                .into());
+            }
+        }
+        Ok(())
+    }
+
+    /// Directory backups are written to
+    pub fn backup_dir(&self) -> &Path {
+        &self.backup_dir
+    }
+
+    /// Replace the managed-config detector
+    pub fn set_managed_detector(&mut self, detector: ManagedConfigDetector) {
+        self.managed_detector = detector;
+    }
+
+    /// Write `content` to `path` through a temporary file that is renamed into place.
+    ///
+    /// A failed write leaves the original intact and removes the temporary file.
+    pub fn write_atomic(path: &Path, content: impl AsRef<[u8]>) -> Result<()> {
+        if let Some(parent) = path.parent().filter(|parent| !parent.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
+        }
+
+        let temp_path = Self::temp_path(path);
+        let written = (|| -> Result<()> {
+            let mut file = fs::File::create(&temp_path)
+                .with_context(|| format!("Failed to create temporary file: {}", temp_path.display()))?;
+            file.write_all(content.as_ref())
+                .with_context(|| format!("Failed to write to temporary file: {}", temp_path.display()))?;
+            file.sync_all()
+                .with_context(|| format!("Failed to sync temporary file: {}", temp_path.display()))?;
+            fs::rename(&temp_path, path)
+                .with_context(|| format!("Failed to move temporary file to target: {}", path.display()))
+        })();
+
+        if written.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+        written
+    }
+
+    /// Temporary file `write_atomic` writes next to `path`, e.g. `groups.json.tmp`
+    pub fn temp_path(path: &Path) -> PathBuf {
+        match path.extension() {
+            Some(ext) => path.with_extension(format!("{}.tmp", ext.to_string_lossy())),
+            None => path.with_extension("tmp"),
+        }
+    }
+    
     // Internal implementation methods
     
     async fn read_config_internal(&self, path: &Path) -> Result<String> {
@@ -360,39 +477,38 @@ impl ConfigFileService {
         Ok(content)
     }
     
-    async fn write_config_internal<T>(&self, path: &Path, data: &T) -> Result<()>
+    /// Serialize `data` for `path`, editing the existing file in place where its format allows
+    pub fn render_config<T>(&self, path: &Path, data: &T) -> Result<String>
     where
         T: Serialize,
     {
-        // Ensure parent directory exists
-        if let Some(parent) = path.parent() {
-            fs::create_dir_all(parent)
-                .with_context(|| format!("Failed to create directory: {}", parent.display()))?;
-        }
-        
-        // Determine format and serialize data
         let format = ConfigFormat::from_extension(path)?;
-        let content = self.serialize_config_content(data, &format)?;
-        
-        // Write to temporary file first
-        let temp_path = path.with_extension(format!("{}.tmp", path.extension().unwrap_or_default().to_string_lossy()));
-        
-        {
-            let mut file = fs::File::create(&temp_path)
-                .with_context(|| format!("Failed to create temporary file: {}", temp_path.display()))?;
-            
-            file.write_all(content.as_bytes())
-                .with_context(|| format!("Failed to write to temporary file: {}", temp_path.display()))?;
-            
-            file.sync_all()
-                .with_context(|| format!("Failed to sync temporary file: {}", temp_path.display()))?;
-        }
-        
-        // Atomically move temporary file to target
-        fs::rename(&temp_path, path)
-            .with_context(|| format!("Failed to move temporary file to target: {}", path.display()))?;
-        
-        Ok(())
+        let content = match format {
+            // Edit existing YAML in place so anchors and other documents survive
+            ConfigFormat::Yaml if path.exists() => {
+                let original = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read configuration file: {}", path.display()))?;
+                let updated = serde_yaml::to_value(data)
+                    .with_context(|| "Failed to serialize data to YAML")?;
+                update_yaml_preserving(&original, &updated)
+                    .with_context(|| format!("Failed to update YAML configuration: {}", path.display()))?
+            }
+            // Editor settings files carry comments a plain serializer would drop
+            ConfigFormat::Json if path.exists() => {
+                let original = fs::read_to_string(path)
+                    .with_context(|| format!("Failed to read configuration file: {}", path.display()))?;
+                if has_jsonc_syntax(&original) {
+                    let updated = serde_json::to_value(data)
+                        .with_context(|| "Failed to serialize data to JSON")?;
+                    update_jsonc_preserving(&original, &updated)
+                        .with_context(|| format!("Failed to update JSON configuration: {}", path.display()))?
+                } else {
+                    self.serialize_config_content(data, &format)?
+                }
+            }
+            _ => self.serialize_config_content(data, &format)?,
+        };
+        Ok(content)
     }
     
     async fn validate_config_internal(&self, path: &Path) -> Result<ConfigFileMetadata> {
@@ -480,14 +596,22 @@ impl ConfigFileService {
         T: for<'de> Deserialize<'de>,
     {
         let format = ConfigFormat::from_extension(path)?;
+
+        // A zero-byte or blank file is an empty config in every format
+        if content.trim().is_empty() {
+            return serde_json::from_value(serde_json::json!({}))
+                .with_context(|| format!("Failed to read empty configuration: {}", path.display()));
+        }
         
         match format {
             ConfigFormat::Json => {
-                serde_json::from_str(content)
+                serde_json::from_str(&strip_jsonc(content))
                     .with_context(|| format!("Failed to parse JSON configuration: {}", path.display()))
             }
             ConfigFormat::Yaml => {
-                serde_yaml::from_str(content)
+                let view = parse_yaml_view(content)
+                    .with_context(|| format!("Failed to parse YAML configuration: {}", path.display()))?;
+                serde_yaml::from_value(view)
                     .with_context(|| format!("Failed to parse YAML configuration: {}", path.display()))
             }
             ConfigFormat::Toml => {
@@ -520,11 +644,11 @@ impl ConfigFileService {
     fn validate_config_format(&self, content: &str, format: &ConfigFormat) -> Result<()> {
         match format {
             ConfigFormat::Json => {
-                serde_json::from_str::<serde_json::Value>(content)
+                serde_json::from_str::<serde_json::Value>(&strip_jsonc(content))
                     .with_context(|| "Invalid JSON format")?;
             }
             ConfigFormat::Yaml => {
-                serde_yaml::from_str::<serde_yaml::Value>(content)
+                parse_yaml_documents(content)
                     .with_context(|| "Invalid YAML format")?;
             }
             ConfigFormat::Toml => {
@@ -579,6 +703,26 @@ impl ConfigFileService {
     }
 }
 
+/// Recursively rebuild JSON objects with their keys in alphabetical order
+///
+/// serde_json's map only iterates in key order while no crate in the build enables its
+/// `preserve_order` feature, so sorted writes sort explicitly rather than rely on that.
+pub fn sort_json_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => {
+            let mut entries: Vec<(String, serde_json::Value)> = map.into_iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+            serde_json::Value::Object(
+                entries.into_iter().map(|(k, v)| (k, sort_json_keys(v))).collect(),
+            )
+        }
+        serde_json::Value::Array(items) => {
+            serde_json::Value::Array(items.into_iter().map(sort_json_keys).collect())
+        }
+        other => other,
+    }
+}
+
 impl Validatable for ConfigFileMetadata {
     fn validate_with_context(&self, ctx: &mut ValidationContext) {
         // Validate path
@@ -654,6 +798,22 @@ mod tests {
         assert_eq!(ConfigFormat::from_extension(Path::new("config.toml")).unwrap(), ConfigFormat::Toml);
     }
     
+    #[test]
+    fn test_failed_atomic_write_removes_temp_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("nested/groups.json");
+
+        ConfigFileService::write_atomic(&path, "{}").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "{}");
+        assert_eq!(ConfigFileService::temp_path(&path), temp_dir.path().join("nested/groups.json.tmp"));
+
+        // The rename fails when the target is a directory
+        let blocked = temp_dir.path().join("blocked.json");
+        fs::create_dir(&blocked).unwrap();
+        assert!(ConfigFileService::write_atomic(&blocked, "{}").is_err());
+        assert!(!ConfigFileService::temp_path(&blocked).exists());
+    }
+
     #[tokio::test]
     async fn test_read_write_json_config() {
         let temp_dir = TempDir::new().unwrap();
@@ -722,4 +882,95 @@ mod tests {
         assert!(metadata.writable);
         assert!(metadata.size > 0);
     }
+    
+    #[tokio::test]
+    async fn test_sorted_writes_are_byte_identical() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("sorted.json");
+        let mut service = ConfigFileService::new("test_user".to_string(), temp_dir.path().join("backups"));
+        service.set_auto_backup(false);
+
+        let names = ["zeta", "alpha", "memory", "github", "filesystem", "brave", "slack", "time"];
+        let mut forward = std::collections::HashMap::new();
+        for name in names {
+            forward.insert(name, json!({"command": "npx", "args": ["-y", name], "env": {"B": "2", "A": "1"}}));
+        }
+        let mut backward = std::collections::HashMap::new();
+        for name in names.iter().rev() {
+            backward.insert(*name, json!({"env": {"A": "1", "B": "2"}, "args": ["-y", name], "command": "npx"}));
+        }
+
+        service.write_config_sorted(&config_path, &json!({"mcpServers": forward})).await.unwrap();
+        let first = fs::read(&config_path).unwrap();
+        service.write_config_sorted(&config_path, &json!({"mcpServers": backward})).await.unwrap();
+        let second = fs::read(&config_path).unwrap();
+
+        assert_eq!(first, second);
+        let content = String::from_utf8(first).unwrap();
+        assert!(content.find("\"alpha\"").unwrap() < content.find("\"zeta\"").unwrap());
+    }
+    
+    #[tokio::test]
+    async fn test_sorted_jsonc_writes_add_members_in_key_order() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("settings.json");
+        fs::write(&config_path, "// user settings\n{\"editor.fontSize\": 14}\n").unwrap();
+        let mut service = ConfigFileService::new("test_user".to_string(), temp_dir.path().join("backups"));
+        service.set_auto_backup(false);
+
+        // Inserted out of order, so the result is sorted only if the writer sorts, even with preserve_order
+        let mut servers = serde_json::Map::new();
+        for name in ["zeta", "alpha"] {
+            servers.insert(name.to_string(), json!({"command": "npx", "args": ["-y", name]}));
+        }
+        service.write_config_sorted(&config_path, &json!({"mcpServers": servers, "editor.fontSize": 14})).await.unwrap();
+
+        let content = fs::read_to_string(&config_path).unwrap();
+        assert!(content.starts_with("// user settings\n{\"editor.fontSize\": 14,"));
+        assert!(content.find("\"alpha\"").unwrap() < content.find("\"zeta\"").unwrap());
+        assert!(content.find("\"args\"").unwrap() < content.find("\"command\"").unwrap());
+    }
+    
+    #[tokio::test]
+    async fn test_refuses_externally_managed_config() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("policy/settings.json");
+        fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        fs::write(&config_path, "{}").unwrap();
+        
+        let mut service = ConfigFileService::new("test_user".to_string(), temp_dir.path().join("backups"));
+        service.set_managed_detector(ManagedConfigDetector::with_locations(
+            vec![temp_dir.path().join("policy")],
+            vec![],
+            None,
+        ));
+        
+        let error = service.write_config(&config_path, &json!({"mcpServers": {}})).await.unwrap_err();
+        assert!(error.downcast_ref::<ExternallyManaged>().is_some());
+        assert_eq!(fs::read_to_string(&config_path).unwrap(), "{}");
+        assert!(!service.get_operations().last().unwrap().success);
+        
+        service.set_force_managed_writes(true);
+        service.write_config(&config_path, &json!({"mcpServers": {}})).await.unwrap();
+        assert!(fs::read_to_string(&config_path).unwrap().contains("mcpServers"));
+    }
+    
+    #[tokio::test]
+    async fn test_yaml_writes_preserve_anchors() {
+        let temp_dir = TempDir::new().unwrap();
+        let config_path = temp_dir.path().join("config.yaml");
+        fs::write(&config_path, "base: &base\n  command: npx\nmcpServers:\n  memory:\n    <<: *base\n    args: [\"-y\", \"memory\"]\n").unwrap();
+        
+        let mut service = ConfigFileService::new("test_user".to_string(), temp_dir.path().join("backups"));
+        service.set_auto_backup(false);
+        let mut config: serde_json::Value = service.read_config(&config_path).await.unwrap();
+        assert_eq!(config["mcpServers"]["memory"]["command"], "npx");
+        
+        config["mcpServers"]["time"] = json!({"command": "uvx", "args": ["mcp-server-time"]});
+        service.write_config(&config_path, &config).await.unwrap();
+        
+        let written = fs::read_to_string(&config_path).unwrap();
+        assert!(written.starts_with("base: &base\n  command: npx\nmcpServers:\n  memory:\n    <<: *base\n"));
+        assert!(written.contains("  time:\n"));
+    }
 }
@@ -0,0 +1,310 @@
+use std::ops::Range;
+use anyhow::{anyhow, Context, Result};
+use serde_json::{Map, Value};
+
+/// Blank out `//` and `/* */` comments and trailing commas so a JSONC document parses as JSON.
+///
+/// Removed text is replaced with spaces and newlines are kept, so offsets and line numbers
+/// in the result match the original.
+pub fn strip_jsonc(content: &str) -> String {
+    let bytes = content.as_bytes();
+    let mut out = bytes.to_vec();
+    let mut i = 0;
+    // Position of a comma that is trailing if only trivia follows before `}` or `]`
+    let mut pending_comma: Option<usize> = None;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            b'"' => {
+                pending_comma = None;
+                i = string_end(bytes, i);
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'/') => {
+                let end = bytes[i..].iter().position(|b| *b == b'\n').map_or(bytes.len(), |n| i + n);
+                blank(&mut out, i..end);
+                i = end;
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                let end = content[i + 2..].find("*/").map_or(bytes.len(), |n| i + 2 + n + 2);
+                blank(&mut out, i..end);
+                i = end;
+            }
+            b',' => {
+                pending_comma = Some(i);
+                i += 1;
+            }
+            b'}' | b']' => {
+                if let Some(comma) = pending_comma.take() {
+                    out[comma] = b' ';
+                }
+                i += 1;
+            }
+            b if b.is_ascii_whitespace() => i += 1,
+            _ => {
+                pending_comma = None;
+                i += 1;
+            }
+        }
+    }
+
+    // Only ASCII bytes outside strings were replaced, so the result is still UTF-8
+    String::from_utf8(out).expect("blanking ASCII keeps UTF-8 valid")
+}
+
+/// Parse a JSON document that may contain comments and trailing commas
+pub fn parse_jsonc(content: &str) -> Result<Value> {
+    serde_json::from_str(&strip_jsonc(content)).context("Failed to parse JSON")
+}
+
+/// Whether a document uses comments or trailing commas, which a strict JSON writer would drop
+pub fn has_jsonc_syntax(content: &str) -> bool {
+    strip_jsonc(content) != content
+}
+
+/// Rewrite only the object members whose value changed, keeping comments and layout.
+///
+/// `updated` is the new value of the whole document. Unchanged members keep their original
+/// text, along with the comments in front of them; changed values are re-serialized in
+/// place, removed members are dropped with their leading comments, and new members are
+/// appended at the end of their object.
+pub fn update_jsonc_preserving(content: &str, updated: &Value) -> Result<String> {
+    let stripped = strip_jsonc(content);
+    let old: Value = serde_json::from_str(&stripped).context("Failed to parse JSON")?;
+    let (Value::Object(old), Value::Object(new)) = (&old, updated) else {
+        return Err(anyhow!("Only JSON objects can be written back to a config"));
+    };
+
+    let start = skip_ws(stripped.as_bytes(), 0);
+    let end = value_end(stripped.as_bytes(), start)?;
+    let editor = Editor { content, stripped: stripped.as_bytes() };
+    let object = editor.edit_object(start..end, old, new)?;
+    Ok(format!("{}{}{}", &content[..start], object, &content[end..]))
+}
+
+/// One `"key": value` member of an object, as offsets into the document
+struct Member {
+    key: String,
+    /// From the end of the previous member's comma, or the opening brace, to the end of the value
+    chunk: Range<usize>,
+    value: Range<usize>,
+    /// Offset just past the comma that follows the value, if there is one
+    comma_end: Option<usize>,
+}
+
+struct Editor<'a> {
+    content: &'a str,
+    /// `content` with comments blanked, used for all scanning
+    stripped: &'a [u8],
+}
+
+impl Editor<'_> {
+    fn edit_object(&self, range: Range<usize>, old: &Map<String, Value>, new: &Map<String, Value>) -> Result<String> {
+        let members = self.members(range.clone())?;
+        let kept: Vec<&Member> = members.iter().filter(|m| new.contains_key(&m.key)).collect();
+        let indent = line_indent(self.content, range.start);
+        let child_indent = members
+            .first()
+            .map(|m| line_indent(self.content, m.value.start - 1))
+            .filter(|child| child.len() > indent.len())
+            .unwrap_or_else(|| format!("{}  ", indent));
+
+        if kept.is_empty() && !members.is_empty() {
+            return Ok(render(&Value::Object(new.clone()), &indent));
+        }
+
+        let mut parts = Vec::new();
+        for member in &kept {
+            let (old_value, new_value) = (&old[&member.key], &new[&member.key]);
+            let value = match (old_value, new_value) {
+                _ if old_value == new_value => self.content[member.value.clone()].to_string(),
+                (Value::Object(old_child), Value::Object(new_child)) => {
+                    self.edit_object(member.value.clone(), old_child, new_child)?
+                }
+                _ => render(new_value, &child_indent),
+            };
+            parts.push(format!("{}{}", &self.content[member.chunk.start..member.value.start], value));
+        }
+        for (key, value) in new.iter().filter(|(key, _)| !old.contains_key(*key)) {
+            parts.push(format!(
+                "\n{}{}: {}",
+                child_indent,
+                serde_json::to_string(key)?,
+                render(value, &child_indent)
+            ));
+        }
+
+        // Text between the last member and the closing brace, without its comma
+        let tail_start = members.last().map_or(range.start + 1, |m| m.comma_end.unwrap_or(m.value.end));
+        let mut tail = self.content[tail_start..range.end - 1].to_string();
+        if members.is_empty() && !parts.is_empty() && !tail.contains('\n') {
+            tail = format!("\n{}", indent);
+        }
+        let trailing_comma = members.last().is_some_and(|m| m.comma_end.is_some()) && !parts.is_empty();
+
+        Ok(format!("{{{}{}{}}}", parts.join(","), if trailing_comma { "," } else { "" }, tail))
+    }
+
+    /// Members of the object spanning `range`, which starts at `{` and ends after `}`
+    fn members(&self, range: Range<usize>) -> Result<Vec<Member>> {
+        let bytes = self.stripped;
+        let mut members = Vec::new();
+        let mut chunk_start = range.start + 1;
+        let mut i = skip_ws(bytes, chunk_start);
+
+        while i < range.end - 1 {
+            if bytes[i] != b'"' {
+                return Err(anyhow!("Expected an object key at offset {}", i));
+            }
+            let key_end = string_end(bytes, i);
+            let key: String = serde_json::from_slice(&bytes[i..key_end]).context("Invalid object key")?;
+            let colon = skip_ws(bytes, key_end);
+            if bytes.get(colon) != Some(&b':') {
+                return Err(anyhow!("Expected ':' after key \"{}\"", key));
+            }
+            let value_start = skip_ws(bytes, colon + 1);
+            let value_end = value_end(bytes, value_start)?;
+
+            let after = skip_ws(bytes, value_end);
+            let comma_end = (bytes.get(after) == Some(&b',')).then_some(after + 1);
+            members.push(Member {
+                key,
+                chunk: chunk_start..value_end,
+                value: value_start..value_end,
+                comma_end,
+            });
+
+            chunk_start = comma_end.unwrap_or(value_end);
+            i = skip_ws(bytes, chunk_start);
+        }
+        Ok(members)
+    }
+}
+
+/// Pretty-printed `value`, with continuation lines indented to sit under `indent`
+fn render(value: &Value, indent: &str) -> String {
+    let pretty = serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string());
+    pretty.replace('\n', &format!("\n{}", indent))
+}
+
+/// Leading whitespace of the line containing `offset`
+fn line_indent(content: &str, offset: usize) -> String {
+    let line_start = content[..offset].rfind('\n').map_or(0, |n| n + 1);
+    content[line_start..].chars().take_while(|c| *c == ' ' || *c == '\t').collect()
+}
+
+fn blank(out: &mut [u8], range: Range<usize>) {
+    for byte in &mut out[range] {
+        if *byte != b'\n' && *byte != b'\r' {
+            *byte = b' ';
+        }
+    }
+}
+
+fn skip_ws(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i].is_ascii_whitespace() {
+        i += 1;
+    }
+    i
+}
+
+/// Offset just past the string starting at the quote at `start`
+fn string_end(bytes: &[u8], start: usize) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'\\' => i += 2,
+            b'"' => return i + 1,
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Offset just past the value starting at `start`, in comment-free text
+fn value_end(bytes: &[u8], start: usize) -> Result<usize> {
+    match bytes.get(start) {
+        Some(b'"') => Ok(string_end(bytes, start)),
+        Some(b'{') | Some(b'[') => {
+            let mut depth = 0;
+            let mut i = start;
+            while i < bytes.len() {
+                match bytes[i] {
+                    b'"' => {
+                        i = string_end(bytes, i);
+                        continue;
+                    }
+                    b'{' | b'[' => depth += 1,
+                    b'}' | b']' => {
+                        depth -= 1;
+                        if depth == 0 {
+                            return Ok(i + 1);
+                        }
+                    }
+                    _ => {}
+                }
+                i += 1;
+            }
+            Err(anyhow!("Unterminated value at offset {}", start))
+        }
+        Some(_) => Ok(bytes[start..]
+            .iter()
+            .position(|b| matches!(b, b',' | b'}' | b']') || b.is_ascii_whitespace())
+            .map_or(bytes.len(), |n| start + n)),
+        None => Err(anyhow!("Expected a value at offset {}", start)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const SETTINGS: &str = r#"{
+  // Editor look
+  "theme": "dark",
+  /* Servers managed by hand */
+  "mcpServers": {
+    "github": {"command": "npx", "args": ["-y", "@modelcontextprotocol/server-github"]}, // keep
+    "slack": {
+      "command": "npx"
+    },
+  },
+}
+"#;
+
+    #[test]
+    fn test_parse_jsonc_ignores_comments_and_trailing_commas() {
+        let value = parse_jsonc(SETTINGS).unwrap();
+        assert_eq!(value["theme"], "dark");
+        assert_eq!(value["mcpServers"]["slack"]["command"], "npx");
+        assert!(has_jsonc_syntax(SETTINGS));
+        assert!(!has_jsonc_syntax(r#"{"url": "https://example.com/a//b"}"#));
+        assert_eq!(parse_jsonc(r#"{"url": "https://example.com/a//b"}"#).unwrap()["url"], "https://example.com/a//b");
+    }
+
+    #[test]
+    fn test_update_keeps_comments_around_unchanged_entries() {
+        let mut updated = parse_jsonc(SETTINGS).unwrap();
+        updated["mcpServers"]["slack"]["disabled"] = json!(true);
+        updated["mcpServers"]["notes"] = json!({"command": "uvx", "args": ["notes-mcp"]});
+        updated["mcpServers"].as_object_mut().unwrap().remove("github");
+
+        let written = update_jsonc_preserving(SETTINGS, &updated).unwrap();
+        assert!(written.contains("// Editor look"), "{}", written);
+        assert!(written.contains("/* Servers managed by hand */"), "{}", written);
+        assert!(!written.contains("@modelcontextprotocol/server-github"), "{}", written);
+        assert_eq!(parse_jsonc(&written).unwrap(), updated);
+    }
+
+    #[test]
+    fn test_update_into_empty_object_and_unchanged_round_trip() {
+        let content = "{\n  // nothing yet\n  \"mcpServers\": {}\n}\n";
+        let mut updated = parse_jsonc(content).unwrap();
+        assert_eq!(update_jsonc_preserving(content, &updated).unwrap(), content);
+
+        updated["mcpServers"]["fetch"] = json!({"command": "uvx"});
+        let written = update_jsonc_preserving(content, &updated).unwrap();
+        assert!(written.contains("// nothing yet"));
+        assert_eq!(parse_jsonc(&written).unwrap(), updated);
+    }
+}
@@ -0,0 +1,279 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use serde::{Deserialize, Serialize};
+
+/// Directories whose contents are provisioned by MDM or system policy
+///
+/// Only locations that exist to hold policy are listed; system-wide directories such as
+/// `/etc` also hold configs users edit themselves.
+const MANAGED_ROOTS: &[&str] = &[
+    "/Library/Managed Preferences",
+    "/Library/Application Support/ClaudeCode",
+    "/etc/claude-code",
+    "C:\\ProgramData\\ClaudeCode",
+    "C:/ProgramData/ClaudeCode",
+];
+
+/// Directories holding macOS managed preference payloads
+const MANAGED_PREFERENCES_DIRS: &[&str] = &["/Library/Managed Preferences"];
+
+/// Comment markers that configuration management tools leave in distributed files
+const MANAGED_MARKERS: &[&str] = &[
+    "managed by mdm",
+    "managed by policy",
+    "managed by your organization",
+    "@managed",
+];
+
+/// Only the top of a file is searched for a marker comment
+const MARKER_SCAN_LINES: usize = 10;
+
+/// Highest uid reserved for system accounts on macOS; Linux reserves fewer
+const SYSTEM_UID_MAX: u32 = 500;
+
+/// A single sign that a config file is distributed by an external manager
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum ManagedSign {
+    /// The file lives in a location provisioned by MDM or system policy
+    ManagedLocation { root: PathBuf },
+    /// A macOS managed preferences payload exists for the application
+    ManagedPreferences { payload: PathBuf },
+    /// The file carries a "managed by" comment near the top
+    MarkerComment { line: String },
+    /// The file is owned by a system account rather than the current user
+    SystemOwner { uid: u32 },
+}
+
+impl std::fmt::Display for ManagedSign {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ManagedSign::ManagedLocation { root } => {
+                write!(f, "config is inside the policy-managed directory {}", root.display())
+            }
+            ManagedSign::ManagedPreferences { payload } => {
+                write!(f, "managed preferences payload found at {}", payload.display())
+            }
+            ManagedSign::MarkerComment { line } => write!(f, "config contains the marker \"{}\"", line),
+            ManagedSign::SystemOwner { uid } => write!(f, "config is owned by system account uid {}", uid),
+        }
+    }
+}
+
+/// Whether an application's config is controlled by something other than the user
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ManagedConfigState {
+    /// Config path that was inspected
+    pub path: Option<PathBuf>,
+    /// Signs of external management that were found
+    pub signs: Vec<ManagedSign>,
+}
+
+impl ManagedConfigState {
+    /// Whether writes to this config are likely to be overwritten
+    pub fn is_externally_managed(&self) -> bool {
+        !self.signs.is_empty()
+    }
+
+    /// Human-readable reasons behind the detection
+    pub fn reasons(&self) -> Vec<String> {
+        self.signs.iter().map(|sign| sign.to_string()).collect()
+    }
+}
+
+/// Returned when a write targets a config distributed by MDM or policy
+#[derive(Debug, thiserror::Error)]
+#[error("{} is externally managed ({}); changes would be overwritten, force the write to override", path.display(), reasons.join("; "))]
+pub struct ExternallyManaged {
+    pub path: PathBuf,
+    pub reasons: Vec<String>,
+}
+
+/// Looks for signs that a config file is controlled by MDM or system policy
+#[derive(Debug, Clone)]
+pub struct ManagedConfigDetector {
+    managed_roots: Vec<PathBuf>,
+    preferences_dirs: Vec<PathBuf>,
+    /// Owner of the user's home directory; files owned by other system accounts are suspect
+    user_uid: Option<u32>,
+}
+
+impl ManagedConfigDetector {
+    /// Create a detector for the standard system locations
+    pub fn new() -> Self {
+        Self {
+            managed_roots: MANAGED_ROOTS.iter().map(PathBuf::from).collect(),
+            preferences_dirs: MANAGED_PREFERENCES_DIRS.iter().map(PathBuf::from).collect(),
+            user_uid: dirs::home_dir().and_then(|home| owner_uid(&home)),
+        }
+    }
+
+    /// Create a detector with custom locations, mainly for tests
+    pub fn with_locations(managed_roots: Vec<PathBuf>, preferences_dirs: Vec<PathBuf>, user_uid: Option<u32>) -> Self {
+        Self { managed_roots, preferences_dirs, user_uid }
+    }
+
+    /// Inspect a config file, optionally checking managed preferences for a bundle id
+    pub fn detect(&self, path: &Path, bundle_id: Option<&str>) -> ManagedConfigState {
+        let mut signs = Vec::new();
+
+        if let Some(root) = self.managed_roots.iter().find(|root| path.starts_with(root)) {
+            signs.push(ManagedSign::ManagedLocation { root: root.clone() });
+        }
+
+        if let Some(bundle_id) = bundle_id.filter(|id| !id.is_empty()) {
+            let payload = self.preferences_dirs.iter().find_map(|dir| self.find_payload(dir, bundle_id));
+            if let Some(payload) = payload {
+                signs.push(ManagedSign::ManagedPreferences { payload });
+            }
+        }
+
+        if let Some(line) = find_marker(path) {
+            signs.push(ManagedSign::MarkerComment { line });
+        }
+
+        if let Some(sign) = system_owner_sign(owner_uid(path), self.user_uid) {
+            signs.push(sign);
+        }
+
+        ManagedConfigState {
+            path: Some(path.to_path_buf()),
+            signs,
+        }
+    }
+
+    /// Payload for a bundle id, either machine-wide or under the current user's folder
+    fn find_payload(&self, dir: &Path, bundle_id: &str) -> Option<PathBuf> {
+        let file_name = format!("{}.plist", bundle_id);
+        let mut candidates = vec![dir.join(&file_name)];
+        if let Some(user) = std::env::var_os("USER") {
+            candidates.push(dir.join(user).join(&file_name));
+        }
+        candidates.into_iter().find(|candidate| candidate.is_file())
+    }
+}
+
+impl Default for ManagedConfigDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// A system account owning the file, unless that account is the user's own
+fn system_owner_sign(owner: Option<u32>, user_uid: Option<u32>) -> Option<ManagedSign> {
+    let uid = owner?;
+    (uid < SYSTEM_UID_MAX && Some(uid) != user_uid).then_some(ManagedSign::SystemOwner { uid })
+}
+
+/// First line near the top of the file that carries a management marker
+fn find_marker(path: &Path) -> Option<String> {
+    let content = fs::read_to_string(path).ok()?;
+    content
+        .lines()
+        .take(MARKER_SCAN_LINES)
+        .map(str::trim)
+        .find(|line| {
+            let lower = line.to_lowercase();
+            MANAGED_MARKERS.iter().any(|marker| lower.contains(marker))
+        })
+        .map(String::from)
+}
+
+#[cfg(unix)]
+fn owner_uid(path: &Path) -> Option<u32> {
+    use std::os::unix::fs::MetadataExt;
+
+    fs::metadata(path).ok().map(|m| m.uid())
+}
+
+#[cfg(not(unix))]
+fn owner_uid(_path: &Path) -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Detector rooted in a temp dir, with the current user owning every file it creates
+    fn detector(temp_dir: &TempDir) -> ManagedConfigDetector {
+        ManagedConfigDetector::with_locations(
+            vec![temp_dir.path().join("policy")],
+            vec![temp_dir.path().join("Managed Preferences")],
+            owner_uid(temp_dir.path()),
+        )
+    }
+
+    fn write(path: &Path, content: &str) {
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn test_user_config_is_not_managed() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("home/settings.json");
+        write(&path, "{\"mcpServers\": {}}");
+
+        let state = detector(&temp_dir).detect(&path, Some("com.example.editor"));
+        assert!(!state.is_externally_managed());
+    }
+
+    #[test]
+    fn test_managed_location() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("policy/editor/settings.json");
+        write(&path, "{}");
+
+        let state = detector(&temp_dir).detect(&path, None);
+        assert_eq!(state.signs, vec![ManagedSign::ManagedLocation { root: temp_dir.path().join("policy") }]);
+    }
+
+    #[test]
+    fn test_managed_preferences_payload() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("home/settings.json");
+        write(&path, "{}");
+        let payload = temp_dir.path().join("Managed Preferences/com.example.editor.plist");
+        write(&payload, "<plist/>");
+
+        let state = detector(&temp_dir).detect(&path, Some("com.example.editor"));
+        assert_eq!(state.signs, vec![ManagedSign::ManagedPreferences { payload }]);
+    }
+
+    #[test]
+    fn test_marker_comment() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("home/settings.jsonc");
+        write(&path, "// Managed by MDM - local edits are reverted\n{}");
+
+        let state = detector(&temp_dir).detect(&path, None);
+        assert!(state.is_externally_managed());
+        assert!(state.reasons()[0].contains("Managed by MDM"));
+    }
+
+    #[test]
+    fn test_system_owner() {
+        // Root-owned config while the user is a regular account
+        assert_eq!(system_owner_sign(Some(0), Some(501)), Some(ManagedSign::SystemOwner { uid: 0 }));
+        assert_eq!(system_owner_sign(Some(499), None), Some(ManagedSign::SystemOwner { uid: 499 }));
+
+        // The user's own files, other regular accounts and unknown owners are not signs
+        assert_eq!(system_owner_sign(Some(501), Some(501)), None);
+        assert_eq!(system_owner_sign(Some(0), Some(0)), None);
+        assert_eq!(system_owner_sign(Some(1000), Some(501)), None);
+        assert_eq!(system_owner_sign(None, Some(501)), None);
+    }
+
+    #[test]
+    fn test_system_directories_are_not_managed_locations() {
+        let detector = ManagedConfigDetector::new();
+        for path in ["/etc/xdg/zed/settings.json", "/Library/Application Support/Claude/claude_desktop_config.json"] {
+            let state = detector.detect(Path::new(path), None);
+            assert!(!state.signs.iter().any(|sign| matches!(sign, ManagedSign::ManagedLocation { .. })), "{}", path);
+        }
+
+        let state = detector.detect(Path::new("/etc/claude-code/managed-mcp.json"), None);
+        assert!(state.signs.contains(&ManagedSign::ManagedLocation { root: PathBuf::from("/etc/claude-code") }));
+    }
+}
@@ -1,9 +1,17 @@
 pub mod config;
 pub mod watcher;
 pub mod backup;
+pub mod backup_browser;
 pub mod paths;
+pub mod provider;
+pub mod managed;
+pub mod jsonc;
+pub mod yaml_edit;
 
 pub use config::{ConfigFileService, ConfigFileMetadata, ConfigOperation, ConfigOperationType};
 pub use watcher::{ConfigWatcher, WatchEvent, FileEvent};
 pub use backup::{BackupService, BackupMetadata, BackupType, BackupStats};
+pub use backup_browser::{BackupBrowser, RestorePreview, ServerDelta, SnapshotMatch, SnapshotSummary};
 pub use paths::{PathResolver, ApplicationPaths, McpApplication, PathUtils};
+pub use managed::{ExternallyManaged, ManagedConfigDetector, ManagedConfigState, ManagedSign};
+pub use provider::{FsProvider, FsMetadata, RealFs, InMemoryFs};
@@ -1,8 +1,11 @@
 use std::path::{Path, PathBuf};
 use std::collections::HashMap;
+use std::sync::Arc;
 use serde::{Deserialize, Serialize};
 use anyhow::{Result, Context};
 
+use super::provider::{FsProvider, RealFs};
+
 /// Known MCP application configurations
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum McpApplication {
@@ -82,16 +85,38 @@ pub struct PathResolver {
     
     /// Whether to use cache
     use_cache: bool,
+
+    /// Filesystem used to check install locations
+    fs: Arc<dyn FsProvider>,
+
+    /// Directories searched for application commands on Linux
+    search_path: Vec<PathBuf>,
 }
 
 impl PathResolver {
     /// Create a new path resolver
     pub fn new() -> Self {
+        Self::with_fs(Arc::new(RealFs))
+    }
+
+    /// Create a path resolver checking install locations through a filesystem provider
+    pub fn with_fs(fs: Arc<dyn FsProvider>) -> Self {
+        let search_path = std::env::var_os("PATH")
+            .map(|path| std::env::split_paths(&path).collect())
+            .unwrap_or_default();
         Self {
             cached_paths: HashMap::new(),
             use_cache: true,
+            fs,
+            search_path,
         }
     }
+
+    /// Search these directories for application commands instead of `PATH`
+    pub fn with_search_path(mut self, search_path: Vec<PathBuf>) -> Self {
+        self.search_path = search_path;
+        self
+    }
     
     /// Disable caching (useful for testing)
     pub fn disable_cache(&mut self) {
@@ -100,7 +125,7 @@ impl PathResolver {
     }
     
     /// Get configuration paths for all known applications
-    pub fn get_all_application_paths(&mut self) -> Result<Vec<ApplicationPaths>> {
+    pub async fn get_all_application_paths(&mut self) -> Result<Vec<ApplicationPaths>> {
         let applications = vec![
             McpApplication::ClaudeDesktop,
             McpApplication::Cursor,
@@ -110,7 +135,7 @@ impl PathResolver {
         
         let mut paths = Vec::new();
         for app in applications {
-            if let Ok(app_paths) = self.get_application_paths(&app) {
+            if let Ok(app_paths) = self.get_application_paths(&app).await {
                 paths.push(app_paths);
             }
         }
@@ -119,7 +144,7 @@ impl PathResolver {
     }
     
     /// Get configuration paths for a specific application
-    pub fn get_application_paths(&mut self, app: &McpApplication) -> Result<ApplicationPaths> {
+    pub async fn get_application_paths(&mut self, app: &McpApplication) -> Result<ApplicationPaths> {
         // Check cache first
         if self.use_cache {
             if let Some(cached) = self.cached_paths.get(app) {
@@ -128,10 +153,10 @@ impl PathResolver {
         }
         
         let paths = match app {
-            McpApplication::ClaudeDesktop => self.get_claude_desktop_paths()?,
-            McpApplication::Cursor => self.get_cursor_paths()?,
-            McpApplication::Zed => self.get_zed_paths()?,
-            McpApplication::VSCode => self.get_vscode_paths()?,
+            McpApplication::ClaudeDesktop => self.get_claude_desktop_paths().await?,
+            McpApplication::Cursor => self.get_cursor_paths().await?,
+            McpApplication::Zed => self.get_zed_paths().await?,
+            McpApplication::VSCode => self.get_vscode_paths().await?,
             McpApplication::Custom(name) => {
                 return Err(anyhow::anyhow!("Custom application paths not implemented: {}", name));
             }
@@ -146,24 +171,27 @@ impl PathResolver {
     }
     
     /// Find all existing configuration files
-    pub fn find_existing_configs(&mut self) -> Result<Vec<ApplicationPaths>> {
-        let all_paths = self.get_all_application_paths()?;
+    pub async fn find_existing_configs(&mut self) -> Result<Vec<ApplicationPaths>> {
+        let all_paths = self.get_all_application_paths().await?;
         
-        Ok(all_paths.into_iter()
-            .filter(|paths| paths.config_path.exists() || 
-                           paths.alt_config_paths.iter().any(|p| p.exists()))
-            .collect())
+        let mut existing = Vec::new();
+        for paths in all_paths {
+            if self.any_exists(std::iter::once(&paths.config_path).chain(&paths.alt_config_paths)).await {
+                existing.push(paths);
+            }
+        }
+        Ok(existing)
     }
     
     /// Get the primary configuration path for an application
-    pub fn get_primary_config_path(&mut self, app: &McpApplication) -> Result<PathBuf> {
-        let paths = self.get_application_paths(app)?;
+    pub async fn get_primary_config_path(&mut self, app: &McpApplication) -> Result<PathBuf> {
+        let paths = self.get_application_paths(app).await?;
         Ok(paths.config_path)
     }
     
     /// Check if an application is installed
-    pub fn is_application_installed(&mut self, app: &McpApplication) -> bool {
-        if let Ok(paths) = self.get_application_paths(app) {
+    pub async fn is_application_installed(&mut self, app: &McpApplication) -> bool {
+        if let Ok(paths) = self.get_application_paths(app).await {
             paths.is_installed
         } else {
             false
@@ -171,14 +199,14 @@ impl PathResolver {
     }
     
     /// Get the configuration format for an application
-    pub fn get_config_format(&mut self, app: &McpApplication) -> Result<ConfigFormat> {
-        let paths = self.get_application_paths(app)?;
+    pub async fn get_config_format(&mut self, app: &McpApplication) -> Result<ConfigFormat> {
+        let paths = self.get_application_paths(app).await?;
         Ok(paths.config_format)
     }
     
     /// Create configuration directory if it doesn't exist
-    pub fn ensure_config_directory(&mut self, app: &McpApplication) -> Result<PathBuf> {
-        let paths = self.get_application_paths(app)?;
+    pub async fn ensure_config_directory(&mut self, app: &McpApplication) -> Result<PathBuf> {
+        let paths = self.get_application_paths(app).await?;
         
         if let Some(parent) = paths.config_path.parent() {
             std::fs::create_dir_all(parent)
@@ -191,7 +219,7 @@ impl PathResolver {
     
     // Platform-specific path resolution methods
     
-    fn get_claude_desktop_paths(&self) -> Result<ApplicationPaths> {
+    async fn get_claude_desktop_paths(&self) -> Result<ApplicationPaths> {
         let config_path = if cfg!(target_os = "macos") {
             dirs::home_dir()
                 .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
@@ -215,7 +243,11 @@ impl PathResolver {
             dirs::data_dir().map(|d| d.join("claude"))
         };
         
-        let is_installed = self.detect_claude_desktop_installation();
+        let is_installed = self.is_installed(
+            "/Applications/Claude.app",
+            &["C:\\Program Files\\Claude\\Claude.exe", "C:\\Program Files (x86)\\Claude\\Claude.exe"],
+            "claude",
+        ).await;
         
         Ok(ApplicationPaths {
             application: McpApplication::ClaudeDesktop,
@@ -229,7 +261,7 @@ impl PathResolver {
         })
     }
     
-    fn get_cursor_paths(&self) -> Result<ApplicationPaths> {
+    async fn get_cursor_paths(&self) -> Result<ApplicationPaths> {
         let config_path = if cfg!(target_os = "macos") {
             dirs::home_dir()
                 .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
@@ -253,7 +285,11 @@ impl PathResolver {
             dirs::data_dir().map(|d| d.join("Cursor"))
         };
         
-        let is_installed = self.detect_cursor_installation();
+        let is_installed = self.is_installed(
+            "/Applications/Cursor.app",
+            &["C:\\Program Files\\Cursor\\Cursor.exe", "C:\\Program Files (x86)\\Cursor\\Cursor.exe"],
+            "cursor",
+        ).await;
         
         Ok(ApplicationPaths {
             application: McpApplication::Cursor,
@@ -267,7 +303,7 @@ impl PathResolver {
         })
     }
     
-    fn get_zed_paths(&self) -> Result<ApplicationPaths> {
+    async fn get_zed_paths(&self) -> Result<ApplicationPaths> {
         let config_path = if cfg!(target_os = "macos") {
             dirs::home_dir()
                 .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
@@ -291,7 +327,11 @@ impl PathResolver {
             dirs::data_dir().map(|d| d.join("zed"))
         };
         
-        let is_installed = self.detect_zed_installation();
+        let is_installed = self.is_installed(
+            "/Applications/Zed.app",
+            &["C:\\Program Files\\Zed\\zed.exe", "C:\\Program Files (x86)\\Zed\\zed.exe"],
+            "zed",
+        ).await;
         
         Ok(ApplicationPaths {
             application: McpApplication::Zed,
@@ -305,7 +345,7 @@ impl PathResolver {
         })
     }
     
-    fn get_vscode_paths(&self) -> Result<ApplicationPaths> {
+    async fn get_vscode_paths(&self) -> Result<ApplicationPaths> {
         let config_path = if cfg!(target_os = "macos") {
             dirs::home_dir()
                 .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
@@ -329,7 +369,11 @@ impl PathResolver {
             dirs::data_dir().map(|d| d.join("Code"))
         };
         
-        let is_installed = self.detect_vscode_installation();
+        let is_installed = self.is_installed(
+            "/Applications/Visual Studio Code.app",
+            &["C:\\Program Files\\Microsoft VS Code\\Code.exe", "C:\\Program Files (x86)\\Microsoft VS Code\\Code.exe"],
+            "code",
+        ).await;
         
         Ok(ApplicationPaths {
             application: McpApplication::VSCode,
@@ -343,68 +387,27 @@ impl PathResolver {
         })
     }
     
-    // Installation detection methods
-    
-    fn detect_claude_desktop_installation(&self) -> bool {
-        if cfg!(target_os = "macos") {
-            Path::new("/Applications/Claude.app").exists()
-        } else if cfg!(target_os = "windows") {
-            // Check common installation paths
-            Path::new("C:\\Program Files\\Claude\\Claude.exe").exists() ||
-            Path::new("C:\\Program Files (x86)\\Claude\\Claude.exe").exists()
-        } else {
-            // Linux - check if claude command is available
-            std::process::Command::new("which")
-                .arg("claude")
-                .output()
-                .map(|output| output.status.success())
-                .unwrap_or(false)
-        }
-    }
-    
-    fn detect_cursor_installation(&self) -> bool {
-        if cfg!(target_os = "macos") {
-            Path::new("/Applications/Cursor.app").exists()
-        } else if cfg!(target_os = "windows") {
-            Path::new("C:\\Program Files\\Cursor\\Cursor.exe").exists() ||
-            Path::new("C:\\Program Files (x86)\\Cursor\\Cursor.exe").exists()
-        } else {
-            std::process::Command::new("which")
-                .arg("cursor")
-                .output()
-                .map(|output| output.status.success())
-                .unwrap_or(false)
-        }
-    }
+    // Installation detection
     
-    fn detect_zed_installation(&self) -> bool {
-        if cfg!(target_os = "macos") {
-            Path::new("/Applications/Zed.app").exists()
+    /// Whether the macOS bundle or a Windows executable exists, or on Linux whether the command is on the search path
+    async fn is_installed(&self, macos_app: &str, windows_exes: &[&str], command: &str) -> bool {
+        let candidates: Vec<PathBuf> = if cfg!(target_os = "macos") {
+            vec![PathBuf::from(macos_app)]
         } else if cfg!(target_os = "windows") {
-            Path::new("C:\\Program Files\\Zed\\zed.exe").exists() ||
-            Path::new("C:\\Program Files (x86)\\Zed\\zed.exe").exists()
+            windows_exes.iter().map(PathBuf::from).collect()
         } else {
-            std::process::Command::new("which")
-                .arg("zed")
-                .output()
-                .map(|output| output.status.success())
-                .unwrap_or(false)
-        }
+            self.search_path.iter().map(|dir| dir.join(command)).collect()
+        };
+        self.any_exists(&candidates).await
     }
     
-    fn detect_vscode_installation(&self) -> bool {
-        if cfg!(target_os = "macos") {
-            Path::new("/Applications/Visual Studio Code.app").exists()
-        } else if cfg!(target_os = "windows") {
-            Path::new("C:\\Program Files\\Microsoft VS Code\\Code.exe").exists() ||
-            Path::new("C:\\Program Files (x86)\\Microsoft VS Code\\Code.exe").exists()
-        } else {
-            std::process::Command::new("which")
-                .arg("code")
-                .output()
-                .map(|output| output.status.success())
-                .unwrap_or(false)
+    async fn any_exists(&self, paths: impl IntoIterator<Item = &PathBuf>) -> bool {
+        for path in paths {
+            if self.fs.exists(path).await {
+                return true;
+            }
         }
+        false
     }
 }
 
@@ -418,19 +421,83 @@ impl Default for PathResolver {
 pub struct PathUtils;
 
 impl PathUtils {
-    /// Expand tilde (~) in path to home directory
+    /// Expand tilde (~) and variables in a path, see `expand_path`; errors when there is no home directory
     pub fn expand_tilde<P: AsRef<Path>>(path: P) -> Result<PathBuf> {
         let path = path.as_ref();
-        
-        if let Some(path_str) = path.to_str() {
-            if let Some(stripped) = path_str.strip_prefix("~/") {
-                if let Some(home) = dirs::home_dir() {
-                    return Ok(home.join(stripped));
+        match path.to_str() {
+            Some(path_str) => Self::expand_path(path_str).context("Could not find home directory"),
+            None => Ok(path.to_path_buf()),
+        }
+    }
+
+    /// Expand a configured path for this machine: Windows `%VAR%`, a leading `$XDG_CONFIG_HOME`,
+    /// a leading `~`, then `$VAR` and `${VAR}` references.
+    ///
+    /// Unknown variables are left as written, so the path reads as missing rather than wrong,
+    /// and repeated `/` separators are collapsed. None when the path is relative to a home
+    /// directory and there is none.
+    pub fn expand_path(path: &str) -> Option<PathBuf> {
+        Self::expand_path_with(path, |name| std::env::var(name).ok())
+    }
+
+    /// `expand_path` reading variables from `lookup` instead of the environment
+    ///
+    /// An unset, empty or relative `XDG_CONFIG_HOME` means `~/.config`, as the XDG spec requires.
+    pub fn expand_path_with(path: &str, lookup: impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+        let mut path = Self::expand_percent_vars_with(path, &lookup);
+        if let Some(rest) = Self::strip_xdg_config_home(&path) {
+            let config_home = lookup("XDG_CONFIG_HOME")
+                .filter(|value| Path::new(value).is_absolute())
+                .unwrap_or_else(|| "~/.config".to_string());
+            path = format!("{}{}", config_home, rest);
+        }
+        let path = match path.strip_prefix('~') {
+            Some(rest) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+                format!("{}{}", dirs::home_dir()?.display(), rest)
+            }
+            _ => path,
+        };
+        Some(PathBuf::from(collapse_separators(&expand_dollar_vars(&path, &lookup))))
+    }
+
+    /// The rest of a path starting with `$XDG_CONFIG_HOME` or `${XDG_CONFIG_HOME}`, separator included
+    pub fn strip_xdg_config_home(path: &str) -> Option<&str> {
+        path.strip_prefix("${XDG_CONFIG_HOME}")
+            .or_else(|| path.strip_prefix("$XDG_CONFIG_HOME"))
+            .filter(|rest| rest.is_empty() || rest.starts_with(['/', '\\']))
+    }
+
+    /// Replace Windows-style `%NAME%` references, e.g. `%APPDATA%`, with the variable's value
+    ///
+    /// Unset variables are left as written so the path reads as missing rather than wrong.
+    pub fn expand_percent_vars(path: &str) -> String {
+        Self::expand_percent_vars_with(path, |name| std::env::var(name).ok())
+    }
+
+    pub(crate) fn expand_percent_vars_with(path: &str, lookup: impl Fn(&str) -> Option<String>) -> String {
+        let mut expanded = String::with_capacity(path.len());
+        let mut rest = path;
+        while let Some(start) = rest.find('%') {
+            let after = &rest[start + 1..];
+            let Some(end) = after.find('%') else { break };
+            let name = &after[..end];
+            let value = (!name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+                .then(|| lookup(name))
+                .flatten();
+            match value {
+                Some(value) => {
+                    expanded.push_str(&rest[..start]);
+                    expanded.push_str(&value);
+                    rest = &after[end + 1..];
+                }
+                None => {
+                    expanded.push_str(&rest[..=start]);
+                    rest = after;
                 }
             }
         }
-        
-        Ok(path.to_path_buf())
+        expanded.push_str(rest);
+        expanded
     }
     
     /// Get relative path from base to target
@@ -453,11 +520,7 @@ impl PathUtils {
         for component in path.components() {
             match component {
                 std::path::Component::ParentDir => return false,
-                std::path::Component::Normal(name) => {
-                    if name.to_string_lossy().contains("..") {
-                        return false;
-                    }
-                }
+                std::path::Component::Normal(name) if name.to_string_lossy().contains("..") => return false,
                 _ => {}
             }
         }
@@ -489,9 +552,52 @@ impl PathUtils {
     }
 }
 
+/// Replace `$NAME` and `${NAME}` with the variable's value, keeping unknown ones as written
+fn expand_dollar_vars(path: &str, lookup: &impl Fn(&str) -> Option<String>) -> String {
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+        let (name, reference_len) = match after.strip_prefix('{') {
+            Some(braced) => match braced.find('}') {
+                Some(end) => (&braced[..end], end + 2),
+                None => ("", 0),
+            },
+            None => {
+                let end = after.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(after.len());
+                (&after[..end], end)
+            }
+        };
+        let valid = name.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')
+            && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+        match valid.then(|| lookup(name)).flatten() {
+            Some(value) => expanded.push_str(&value),
+            None => expanded.push_str(&rest[start..start + 1 + reference_len]),
+        }
+        rest = &after[reference_len..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Collapse runs of `/` into one, keeping a leading `//` network path prefix
+fn collapse_separators(path: &str) -> String {
+    let (prefix, rest) = if path.starts_with("//") && !path.starts_with("///") { path.split_at(2) } else { ("", path) };
+    let mut collapsed = String::with_capacity(path.len());
+    collapsed.push_str(prefix);
+    for c in rest.chars() {
+        if !(c == '/' && collapsed.ends_with('/') && collapsed.len() > prefix.len()) {
+            collapsed.push(c);
+        }
+    }
+    collapsed
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::filesystem::InMemoryFs;
     
     #[test]
     fn test_application_display_names() {
@@ -532,6 +638,64 @@ mod tests {
             assert_eq!(expanded, home.join("test/path"));
         }
     }
+
+    #[test]
+    fn test_path_utils_expand_percent_vars() {
+        std::env::set_var("MCPCTL_TEST_APPDATA", "C:/Users/me/AppData/Roaming");
+        assert_eq!(
+            PathUtils::expand_percent_vars("%MCPCTL_TEST_APPDATA%/Claude/claude_desktop_config.json"),
+            "C:/Users/me/AppData/Roaming/Claude/claude_desktop_config.json"
+        );
+        // Unset variables and stray percent signs stay as written
+        assert_eq!(PathUtils::expand_percent_vars("%MCPCTL_TEST_UNSET%/x"), "%MCPCTL_TEST_UNSET%/x");
+        assert_eq!(PathUtils::expand_percent_vars("100%/x"), "100%/x");
+    }
+
+    #[test]
+    fn test_path_utils_expand_path() {
+        let vars = |overrides: &'static [(&'static str, &'static str)]| {
+            move |name: &str| overrides.iter().find(|(k, _)| *k == name).map(|(_, v)| v.to_string())
+        };
+
+        let windows = vars(&[("APPDATA", r"C:\Users\me\AppData\Roaming")]);
+        assert_eq!(
+            PathUtils::expand_path_with("%APPDATA%/Claude/claude_desktop_config.json", windows),
+            Some(PathBuf::from(r"C:\Users\me\AppData\Roaming/Claude/claude_desktop_config.json"))
+        );
+
+        let xdg = vars(&[("XDG_CONFIG_HOME", "/srv/config")]);
+        assert_eq!(PathUtils::expand_path_with("$XDG_CONFIG_HOME/Code/User/settings.json", xdg), Some(PathBuf::from("/srv/config/Code/User/settings.json")));
+        assert_eq!(PathUtils::expand_path_with("${XDG_CONFIG_HOME}/zed/settings.json", xdg), Some(PathBuf::from("/srv/config/zed/settings.json")));
+        // Only the whole variable name is replaced
+        assert_eq!(PathUtils::expand_path_with("$XDG_CONFIG_HOMES/x", xdg), Some(PathBuf::from("$XDG_CONFIG_HOMES/x")));
+
+        let Some(home) = dirs::home_dir() else { return };
+        // Unset, empty and relative values all fall back to the spec default
+        for lookup in [vars(&[]), vars(&[("XDG_CONFIG_HOME", "")]), vars(&[("XDG_CONFIG_HOME", "relative/config")])] {
+            assert_eq!(PathUtils::expand_path_with("$XDG_CONFIG_HOME/zed/settings.json", lookup), Some(home.join(".config/zed/settings.json")));
+        }
+        assert!(PathUtils::expand_tilde("$XDG_CONFIG_HOME/zed/settings.json").unwrap().is_absolute());
+    }
+
+    #[test]
+    fn test_path_utils_expand_home_relative_paths() {
+        let Some(home) = dirs::home_dir() else { return };
+        assert_eq!(
+            PathUtils::expand_path("~/Library/Application Support/Claude/claude_desktop_config.json"),
+            Some(home.join("Library/Application Support/Claude/claude_desktop_config.json"))
+        );
+
+        let with_home = |name: &str| (name == "HOME").then(|| home.to_string_lossy().to_string());
+        assert_eq!(PathUtils::expand_path_with("${HOME}/.config/zed/settings.json", with_home), Some(home.join(".config/zed/settings.json")));
+        assert_eq!(PathUtils::expand_path_with("$HOME//.config///zed", with_home), Some(home.join(".config/zed")));
+    }
+
+    #[test]
+    fn test_path_utils_unknown_variables_are_kept() {
+        let lookup = |name: &str| (name == "APP_DIR").then(|| "/opt/app".to_string());
+        assert_eq!(PathUtils::expand_path_with("$APP_DIR/$UNSET/${ALSO_UNSET}/x", lookup), Some(PathBuf::from("/opt/app/$UNSET/${ALSO_UNSET}/x")));
+        assert_eq!(PathUtils::expand_path_with("/tmp/price$5/${open", lookup), Some(PathBuf::from("/tmp/price$5/${open")));
+    }
     
     #[test]
     fn test_path_utils_is_safe_path() {
@@ -553,9 +717,9 @@ mod tests {
         assert_eq!(normalized, PathBuf::from("/absolute/path"));
     }
     
-    #[test]
-    fn test_get_application_paths() {
-        let mut resolver = PathResolver::new();
+    #[tokio::test]
+    async fn test_get_application_paths() {
+        let mut resolver = PathResolver::with_fs(Arc::new(InMemoryFs::case_sensitive()));
         resolver.disable_cache(); // Disable cache for testing
         
         // Test getting paths for each application
@@ -567,13 +731,33 @@ mod tests {
         ];
         
         for app in apps {
-            let result = resolver.get_application_paths(&app);
+            let result = resolver.get_application_paths(&app).await;
             assert!(result.is_ok(), "Failed to get paths for {:?}", app);
             
             let paths = result.unwrap();
             assert_eq!(paths.application, app);
             assert!(!paths.config_path.as_os_str().is_empty());
             assert_eq!(paths.config_format, ConfigFormat::Json);
+            assert!(!paths.is_installed);
         }
     }
+
+    #[tokio::test]
+    async fn test_installs_and_configs_come_from_fs_provider() {
+        let fs = Arc::new(InMemoryFs::case_insensitive());
+        // Cursor installed in each platform's location, so the check passes wherever it runs
+        fs.add_dir("/Applications/Cursor.app");
+        fs.add_file("C:\\Program Files\\Cursor\\Cursor.exe", "");
+        fs.add_file("/usr/local/bin/cursor", "");
+
+        let mut resolver = PathResolver::with_fs(fs.clone()).with_search_path(vec![PathBuf::from("/usr/local/bin")]);
+        assert!(resolver.is_application_installed(&McpApplication::Cursor).await);
+        assert!(!resolver.is_application_installed(&McpApplication::Zed).await);
+        assert!(resolver.find_existing_configs().await.unwrap().is_empty());
+
+        let zed_config = resolver.get_primary_config_path(&McpApplication::Zed).await.unwrap();
+        fs.add_file(&zed_config, "{}");
+        let existing = resolver.find_existing_configs().await.unwrap();
+        assert_eq!(existing.iter().map(|p| p.application.clone()).collect::<Vec<_>>(), vec![McpApplication::Zed]);
+    }
 }
@@ -0,0 +1,299 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Component, Path, PathBuf};
+use std::sync::RwLock;
+use std::time::SystemTime;
+
+/// Minimal file metadata exposed by a filesystem provider
+#[derive(Debug, Clone, PartialEq)]
+pub struct FsMetadata {
+    pub is_file: bool,
+    pub is_dir: bool,
+    pub len: u64,
+    pub modified: Option<SystemTime>,
+}
+
+/// Filesystem operations used by detection and config reading.
+///
+/// Abstracted so detection logic can be tested against an in-memory tree
+/// instead of the real disk.
+#[async_trait]
+pub trait FsProvider: Send + Sync + std::fmt::Debug {
+    /// Check whether a file or directory exists
+    async fn exists(&self, path: &Path) -> bool;
+
+    /// Read the full contents of a file
+    async fn read(&self, path: &Path) -> Result<Vec<u8>>;
+
+    /// Read a file as UTF-8 text
+    async fn read_to_string(&self, path: &Path) -> Result<String> {
+        let bytes = self.read(path).await?;
+        String::from_utf8(bytes)
+            .with_context(|| format!("File is not valid UTF-8: {}", path.display()))
+    }
+
+    /// Get metadata for a file or directory
+    async fn metadata(&self, path: &Path) -> Result<FsMetadata>;
+
+    /// List the direct children of a directory
+    async fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>>;
+
+    /// Resolve a path to its canonical absolute form
+    async fn canonicalize(&self, path: &Path) -> Result<PathBuf>;
+}
+
+/// Filesystem provider backed by the real disk
+#[derive(Debug, Clone, Default)]
+pub struct RealFs;
+
+#[async_trait]
+impl FsProvider for RealFs {
+    async fn exists(&self, path: &Path) -> bool {
+        tokio::fs::try_exists(path).await.unwrap_or(false)
+    }
+
+    async fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        tokio::fs::read(path)
+            .await
+            .with_context(|| format!("Failed to read file: {}", path.display()))
+    }
+
+    async fn metadata(&self, path: &Path) -> Result<FsMetadata> {
+        let metadata = tokio::fs::metadata(path)
+            .await
+            .with_context(|| format!("Failed to read metadata: {}", path.display()))?;
+
+        Ok(FsMetadata {
+            is_file: metadata.is_file(),
+            is_dir: metadata.is_dir(),
+            len: metadata.len(),
+            modified: metadata.modified().ok(),
+        })
+    }
+
+    async fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let mut entries = tokio::fs::read_dir(path)
+            .await
+            .with_context(|| format!("Failed to read directory: {}", path.display()))?;
+
+        let mut paths = Vec::new();
+        while let Some(entry) = entries.next_entry().await? {
+            paths.push(entry.path());
+        }
+        paths.sort();
+        Ok(paths)
+    }
+
+    async fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        tokio::fs::canonicalize(path)
+            .await
+            .with_context(|| format!("Failed to canonicalize path: {}", path.display()))
+    }
+}
+
+/// In-memory filesystem for tests.
+///
+/// Supports both case-insensitive lookups (default macOS APFS) and
+/// case-sensitive lookups (typical Linux filesystems).
+#[derive(Debug)]
+pub struct InMemoryFs {
+    case_sensitive: bool,
+    /// Files keyed by lookup key, storing the path as written and its contents
+    files: RwLock<HashMap<String, (PathBuf, Vec<u8>)>>,
+    /// Directories keyed by lookup key, storing the path as written
+    dirs: RwLock<HashMap<String, PathBuf>>,
+}
+
+impl InMemoryFs {
+    /// Create an empty filesystem with case-sensitive lookups
+    pub fn case_sensitive() -> Self {
+        Self::new(true)
+    }
+
+    /// Create an empty filesystem with case-insensitive lookups
+    pub fn case_insensitive() -> Self {
+        Self::new(false)
+    }
+
+    fn new(case_sensitive: bool) -> Self {
+        let fs = Self {
+            case_sensitive,
+            files: RwLock::new(HashMap::new()),
+            dirs: RwLock::new(HashMap::new()),
+        };
+        fs.add_dir("/");
+        fs
+    }
+
+    /// Add a file (and its parent directories) with the given contents
+    pub fn add_file<P: AsRef<Path>, C: AsRef<[u8]>>(&self, path: P, contents: C) {
+        let path = normalize(path.as_ref());
+        if let Some(parent) = path.parent() {
+            self.add_dir(parent);
+        }
+        self.files
+            .write()
+            .unwrap()
+            .insert(self.key(&path), (path, contents.as_ref().to_vec()));
+    }
+
+    /// Add a directory and all of its ancestors
+    pub fn add_dir<P: AsRef<Path>>(&self, path: P) {
+        let path = normalize(path.as_ref());
+        let mut dirs = self.dirs.write().unwrap();
+        for ancestor in path.ancestors() {
+            dirs.entry(self.key(ancestor))
+                .or_insert_with(|| ancestor.to_path_buf());
+        }
+    }
+
+    /// Builder-style helper for adding a file
+    pub fn with_file<P: AsRef<Path>, C: AsRef<[u8]>>(self, path: P, contents: C) -> Self {
+        self.add_file(path, contents);
+        self
+    }
+
+    fn key(&self, path: &Path) -> String {
+        let key = normalize(path).to_string_lossy().to_string();
+        if self.case_sensitive {
+            key
+        } else {
+            key.to_lowercase()
+        }
+    }
+}
+
+/// Resolve `.` and `..` components without touching the disk
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+#[async_trait]
+impl FsProvider for InMemoryFs {
+    async fn exists(&self, path: &Path) -> bool {
+        let key = self.key(path);
+        self.files.read().unwrap().contains_key(&key) || self.dirs.read().unwrap().contains_key(&key)
+    }
+
+    async fn read(&self, path: &Path) -> Result<Vec<u8>> {
+        self.files
+            .read()
+            .unwrap()
+            .get(&self.key(path))
+            .map(|(_, contents)| contents.clone())
+            .ok_or_else(|| anyhow::anyhow!("No such file: {}", path.display()))
+    }
+
+    async fn metadata(&self, path: &Path) -> Result<FsMetadata> {
+        let key = self.key(path);
+        if let Some((_, contents)) = self.files.read().unwrap().get(&key) {
+            return Ok(FsMetadata {
+                is_file: true,
+                is_dir: false,
+                len: contents.len() as u64,
+                modified: None,
+            });
+        }
+        if self.dirs.read().unwrap().contains_key(&key) {
+            return Ok(FsMetadata {
+                is_file: false,
+                is_dir: true,
+                len: 0,
+                modified: None,
+            });
+        }
+        Err(anyhow::anyhow!("No such file or directory: {}", path.display()))
+    }
+
+    async fn read_dir(&self, path: &Path) -> Result<Vec<PathBuf>> {
+        let key = self.key(path);
+        let dirs = self.dirs.read().unwrap();
+        if !dirs.contains_key(&key) {
+            return Err(anyhow::anyhow!("No such directory: {}", path.display()));
+        }
+
+        let files = self.files.read().unwrap();
+        let children: BTreeSet<PathBuf> = files
+            .values()
+            .map(|(p, _)| p)
+            .chain(dirs.values())
+            .filter(|p| p.parent().map(|parent| self.key(parent) == key).unwrap_or(false))
+            .filter(|p| self.key(p) != key)
+            .cloned()
+            .collect();
+
+        Ok(children.into_iter().collect())
+    }
+
+    async fn canonicalize(&self, path: &Path) -> Result<PathBuf> {
+        let key = self.key(path);
+        if let Some((stored, _)) = self.files.read().unwrap().get(&key) {
+            return Ok(stored.clone());
+        }
+        self.dirs
+            .read()
+            .unwrap()
+            .get(&key)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("No such file or directory: {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_case_insensitive_lookup() {
+        let fs = InMemoryFs::case_insensitive()
+            .with_file("/Users/me/Library/Application Support/Claude/config.json", "{}");
+
+        let lower = Path::new("/users/me/library/application support/claude/CONFIG.json");
+        assert!(fs.exists(lower).await);
+        assert_eq!(fs.read_to_string(lower).await.unwrap(), "{}");
+        assert_eq!(
+            fs.canonicalize(lower).await.unwrap(),
+            PathBuf::from("/Users/me/Library/Application Support/Claude/config.json")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_case_sensitive_lookup() {
+        let fs = InMemoryFs::case_sensitive().with_file("/home/me/.config/Code/User/settings.json", "{}");
+
+        assert!(fs.exists(Path::new("/home/me/.config/Code/User/settings.json")).await);
+        assert!(!fs.exists(Path::new("/home/me/.config/code/User/settings.json")).await);
+    }
+
+    #[tokio::test]
+    async fn test_metadata_and_read_dir() {
+        let fs = InMemoryFs::case_sensitive()
+            .with_file("/home/me/.warp/mcp_config.json", "{\"a\":1}")
+            .with_file("/home/me/.warp/themes/dark.yaml", "");
+
+        let metadata = fs.metadata(Path::new("/home/me/.warp/mcp_config.json")).await.unwrap();
+        assert!(metadata.is_file);
+        assert_eq!(metadata.len, 7);
+        assert!(fs.metadata(Path::new("/home/me/.warp")).await.unwrap().is_dir);
+
+        let children = fs.read_dir(Path::new("/home/me/.warp")).await.unwrap();
+        assert_eq!(children, vec![
+            PathBuf::from("/home/me/.warp/mcp_config.json"),
+            PathBuf::from("/home/me/.warp/themes"),
+        ]);
+
+        let resolved = fs.canonicalize(Path::new("/home/me/.warp/themes/../mcp_config.json")).await.unwrap();
+        assert_eq!(resolved, PathBuf::from("/home/me/.warp/mcp_config.json"));
+    }
+}
@@ -0,0 +1,414 @@
+use std::ops::Range;
+use anyhow::{anyhow, Context, Result};
+use regex::Regex;
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+
+/// Reasons a YAML edit cannot be made without changing parts of the file we do not own
+#[derive(Debug, thiserror::Error)]
+pub enum YamlEditError {
+    #[error("cannot edit `{path}` (line {line}): it defines anchor `&{anchor}`, which is aliased elsewhere in the file; update the anchored value by hand")]
+    AliasedNode { path: String, anchor: String, line: usize },
+    #[error("cannot edit `{path}`: its value is inherited through the merge key at line {line}; change the merged mapping by hand")]
+    MergedValue { path: String, line: usize },
+    #[error("cannot edit `{path}` (line {line}): {reason}")]
+    Unsupported { path: String, line: usize, reason: String },
+}
+
+/// Parse every document in a YAML stream, resolving aliases and `<<` merge keys
+pub fn parse_yaml_documents(content: &str) -> Result<Vec<Value>> {
+    serde_yaml::Deserializer::from_str(content)
+        .map(|document| {
+            let mut value = Value::deserialize(document).context("Failed to parse YAML document")?;
+            value.apply_merge().context("Failed to apply YAML merge keys")?;
+            Ok(value)
+        })
+        .collect()
+}
+
+/// Resolved view of a YAML config: its first non-empty document
+pub fn parse_yaml_view(content: &str) -> Result<Value> {
+    Ok(parse_yaml_documents(content)?
+        .into_iter()
+        .find(|document| !document.is_null())
+        .unwrap_or(Value::Null))
+}
+
+/// Rewrite only the parts of a YAML config whose resolved value changed.
+///
+/// `updated` is the new value of the document returned by [`parse_yaml_view`]. Unchanged
+/// entries keep their original text, so anchors, aliases, merge keys, comments, and any
+/// other documents in the stream survive. Edits that would have to rewrite an anchored
+/// node that is aliased elsewhere are rejected with a [`YamlEditError`].
+pub fn update_yaml_preserving(content: &str, updated: &Value) -> Result<String> {
+    let Value::Mapping(new) = updated else {
+        return Err(anyhow!("Only YAML mappings can be written back to a config"));
+    };
+
+    let lines: Vec<&str> = content.lines().collect();
+    let documents = split_documents(&lines);
+
+    // Edit the same document the view was read from
+    let mut target = None;
+    for range in &documents {
+        let mut value: Value = serde_yaml::from_str(&lines[range.clone()].join("\n"))
+            .context("Failed to parse YAML document")?;
+        value.apply_merge().context("Failed to apply YAML merge keys")?;
+        if !value.is_null() {
+            target = Some((range.clone(), value));
+            break;
+        }
+    }
+    let (range, old) = match target {
+        Some(target) => target,
+        None => (documents.first().cloned().unwrap_or(0..0), Value::Null),
+    };
+    let old = match old {
+        Value::Mapping(old) => old,
+        Value::Null => Mapping::new(),
+        _ => return Err(anyhow!("Top level of the YAML config is not a mapping")),
+    };
+
+    let editor = Editor { lines: &lines, document: range.clone(), anchors: anchor_patterns() };
+    let mut out: Vec<String> = lines[..range.start].iter().map(|l| l.to_string()).collect();
+    editor.edit_mapping(range.clone(), 0, &old, new, "", &mut out)?;
+    out.extend(lines[range.end..].iter().map(|l| l.to_string()));
+
+    let mut result = out.join("\n");
+    if content.ends_with('\n') || content.is_empty() {
+        result.push('\n');
+    }
+    Ok(result)
+}
+
+/// Line ranges of each document, excluding `---` and `...` markers
+fn split_documents(lines: &[&str]) -> Vec<Range<usize>> {
+    let mut documents = Vec::new();
+    let mut start = 0;
+    for (i, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_end();
+        if trimmed == "---" || trimmed.starts_with("--- ") || trimmed == "..." {
+            if i > start {
+                documents.push(start..i);
+            }
+            start = i + 1;
+        }
+    }
+    if start < lines.len() || documents.is_empty() {
+        documents.push(start..lines.len());
+    }
+    documents
+}
+
+pub(crate) fn indent_of(line: &str) -> usize {
+    line.len() - line.trim_start_matches(' ').len()
+}
+
+pub(crate) fn is_content(line: &str) -> bool {
+    let trimmed = line.trim();
+    !trimmed.is_empty() && !trimmed.starts_with('#')
+}
+
+/// Split `key: rest` into the unquoted key and the inline value without its comment
+pub(crate) fn parse_key(text: &str) -> Option<(String, String)> {
+    let (key, rest) = if let Some(quote) = text.chars().next().filter(|c| *c == '"' || *c == '\'') {
+        let close = text[1..].find(quote)? + 1;
+        (text[1..close].to_string(), text[close + 1..].strip_prefix(':')?)
+    } else {
+        let colon = text
+            .char_indices()
+            .find(|(i, c)| *c == ':' && text[i + 1..].chars().next().map_or(true, |n| n == ' '))?
+            .0;
+        (text[..colon].trim_end().to_string(), &text[colon + 1..])
+    };
+
+    let inline = match rest.find(" #") {
+        Some(comment) => &rest[..comment],
+        None => rest,
+    };
+    Some((key, inline.trim().to_string()))
+}
+
+/// One `key: value` entry of a block mapping
+struct Entry {
+    key: String,
+    line: usize,
+    end: usize,
+    inline: String,
+}
+
+impl Entry {
+    /// Anchor set on the entry's value, e.g. `servers: &shared`
+    fn anchor(&self) -> Option<&str> {
+        self.inline.strip_prefix('&').map(|rest| rest.split_whitespace().next().unwrap_or(""))
+    }
+
+    /// Whether the value continues as an indented block on the following lines
+    fn is_block(&self) -> bool {
+        self.inline.is_empty() || (self.anchor().is_some() && self.inline.split_whitespace().count() == 1)
+    }
+}
+
+struct Editor<'a> {
+    lines: &'a [&'a str],
+    document: Range<usize>,
+    anchors: (Regex, Regex),
+}
+
+fn anchor_patterns() -> (Regex, Regex) {
+    (
+        Regex::new(r"(?:^|[\s\[{,:-])&([A-Za-z0-9_.-]+)").unwrap(),
+        Regex::new(r"(?:^|[\s\[{,:-])\*([A-Za-z0-9_.-]+)").unwrap(),
+    )
+}
+
+impl Editor<'_> {
+    /// Entries of the block mapping spanning `range`, with their shared indent
+    fn entries(&self, range: Range<usize>, path: &str) -> Result<(Option<usize>, Vec<Entry>)> {
+        let Some(indent) = self.lines[range.clone()].iter().find(|l| is_content(l)).map(|l| indent_of(l)) else {
+            return Ok((None, Vec::new()));
+        };
+
+        let mut entries: Vec<Entry> = Vec::new();
+        for i in range.clone() {
+            let line = self.lines[i];
+            if !is_content(line) || indent_of(line) != indent {
+                continue;
+            }
+            let text = &line[indent..];
+
+            // A sequence may sit at the same indent as its key
+            if text.starts_with('-') {
+                match entries.last() {
+                    Some(last) if last.inline.is_empty() => continue,
+                    _ => {
+                        return Err(YamlEditError::Unsupported {
+                            path: path.to_string(),
+                            line: i + 1,
+                            reason: "expected a mapping but found a sequence".to_string(),
+                        }
+                        .into())
+                    }
+                }
+            }
+
+            let (key, inline) = parse_key(text).ok_or_else(|| YamlEditError::Unsupported {
+                path: path.to_string(),
+                line: i + 1,
+                reason: "could not parse this mapping key".to_string(),
+            })?;
+            entries.push(Entry { key, line: i, end: range.end, inline });
+        }
+
+        // Each entry runs to the next one, minus trailing blank lines and outdented comments
+        for index in 0..entries.len() {
+            let mut end = entries.get(index + 1).map(|next| next.line).unwrap_or(range.end);
+            while end > entries[index].line + 1 {
+                let line = self.lines[end - 1];
+                if is_content(line) || (!line.trim().is_empty() && indent_of(line) > indent) {
+                    break;
+                }
+                end -= 1;
+            }
+            entries[index].end = end;
+        }
+
+        Ok((Some(indent), entries))
+    }
+
+    /// Fail if the entry defines an anchor that something outside it refers to
+    fn ensure_not_aliased(&self, entry: &Entry, path: &str) -> Result<()> {
+        let (anchor_pattern, alias_pattern) = &self.anchors;
+
+        for i in entry.line..entry.end {
+            for anchor in anchor_pattern.captures_iter(self.lines[i]) {
+                let anchor = &anchor[1];
+                let aliased = self.document.clone().filter(|j| *j < entry.line || *j >= entry.end).any(|j| {
+                    alias_pattern.captures_iter(self.lines[j]).any(|alias| &alias[1] == anchor)
+                });
+                if aliased {
+                    return Err(YamlEditError::AliasedNode {
+                        path: path.to_string(),
+                        anchor: anchor.to_string(),
+                        line: i + 1,
+                    }
+                    .into());
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Write the lines of a mapping, editing only entries whose value changed
+    fn edit_mapping(
+        &self,
+        range: Range<usize>,
+        default_indent: usize,
+        old: &Mapping,
+        new: &Mapping,
+        path: &str,
+        out: &mut Vec<String>,
+    ) -> Result<()> {
+        let (indent, entries) = self.entries(range.clone(), path)?;
+        let indent = indent.unwrap_or(default_indent);
+        let child_path = |key: &str| if path.is_empty() { key.to_string() } else { format!("{}.{}", path, key) };
+
+        // Keys that only exist through a merge key cannot be edited in place
+        if let Some(merge) = entries.iter().find(|e| e.key == "<<") {
+            for (key, value) in old {
+                let Some(key) = key.as_str() else { continue };
+                if entries.iter().any(|e| e.key == key) {
+                    continue;
+                }
+                if new.get(key) != Some(value) {
+                    return Err(YamlEditError::MergedValue { path: child_path(key), line: merge.line + 1 }.into());
+                }
+            }
+        }
+
+        let mut cursor = range.start;
+        let mut insert_at = out.len();
+        for entry in &entries {
+            out.extend(self.lines[cursor..entry.line].iter().map(|l| l.to_string()));
+            cursor = entry.end;
+            let entry_path = child_path(&entry.key);
+
+            if entry.key == "<<" {
+                out.extend(self.lines[entry.line..entry.end].iter().map(|l| l.to_string()));
+                insert_at = out.len();
+                continue;
+            }
+
+            let old_value = old.get(entry.key.as_str());
+            match new.get(entry.key.as_str()) {
+                Some(new_value) if old_value == Some(new_value) => {
+                    out.extend(self.lines[entry.line..entry.end].iter().map(|l| l.to_string()));
+                }
+                Some(new_value) => {
+                    self.ensure_not_aliased(entry, &entry_path)?;
+                    match (old_value, new_value) {
+                        (Some(Value::Mapping(old_child)), Value::Mapping(new_child)) if entry.is_block() => {
+                            out.push(self.lines[entry.line].to_string());
+                            self.edit_mapping(entry.line + 1..entry.end, indent + 2, old_child, new_child, &entry_path, out)?;
+                        }
+                        _ => out.extend(render_entry(&entry.key, new_value, indent)?),
+                    }
+                }
+                None => self.ensure_not_aliased(entry, &entry_path)?,
+            }
+            insert_at = out.len();
+        }
+        let trailing: Vec<String> = self.lines[cursor..range.end].iter().map(|l| l.to_string()).collect();
+
+        // New keys go after the last existing entry, in the order given
+        let mut added = Vec::new();
+        for (key, value) in new {
+            let name = key.as_str().ok_or_else(|| anyhow!("Only string keys can be written to YAML configs"))?;
+            if !old.contains_key(name) && !entries.iter().any(|e| e.key == name) {
+                added.extend(render_entry(name, value, indent)?);
+            }
+        }
+        out.splice(insert_at..insert_at, added);
+        out.extend(trailing);
+
+        Ok(())
+    }
+}
+
+/// Render `key: value` as block YAML at the given indent
+fn render_entry(key: &str, value: &Value, indent: usize) -> Result<Vec<String>> {
+    let mut mapping = Mapping::new();
+    mapping.insert(Value::String(key.to_string()), value.clone());
+    let rendered = serde_yaml::to_string(&mapping).context("Failed to serialize YAML entry")?;
+
+    let padding = " ".repeat(indent);
+    Ok(rendered.lines().map(|line| format!("{}{}", padding, line)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FIXTURE: &str = r#"# Shared settings
+defaults: &defaults
+  timeout: 30
+  retries: 2
+
+log_env: &log_env
+  LOG_LEVEL: info
+
+endpoints:
+  - name: primary
+    <<: *defaults
+
+mcpServers:
+  github:
+    command: npx
+    args: ["-y", "@modelcontextprotocol/server-github"]
+    env: *log_env
+  memory:
+    <<: *defaults
+    command: npx
+    args:
+      - -y
+      - "@modelcontextprotocol/server-memory"
+---
+second: document
+"#;
+
+    fn servers(content: &str) -> Value {
+        parse_yaml_view(content).unwrap()
+    }
+
+    #[test]
+    fn test_view_resolves_aliases_and_merge_keys() {
+        let view = servers(FIXTURE);
+
+        assert_eq!(view["mcpServers"]["memory"]["timeout"], Value::from(30));
+        assert_eq!(view["mcpServers"]["github"]["env"]["LOG_LEVEL"], Value::from("info"));
+        assert_eq!(view["endpoints"][0]["retries"], Value::from(2));
+        assert_eq!(parse_yaml_documents(FIXTURE).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_unchanged_round_trip_is_identical() {
+        let view = servers(FIXTURE);
+        assert_eq!(update_yaml_preserving(FIXTURE, &view).unwrap(), FIXTURE);
+    }
+
+    #[test]
+    fn test_edits_keep_anchors_outside_changed_entries() {
+        let mut view = servers(FIXTURE);
+        let mcp = view["mcpServers"].as_mapping_mut().unwrap();
+        mcp.insert("time".into(), serde_yaml::from_str("{command: uvx, args: [mcp-server-time]}").unwrap());
+        mcp.get_mut("github").unwrap()["command"] = Value::from("bunx");
+
+        let updated = update_yaml_preserving(FIXTURE, &view).unwrap();
+
+        assert!(updated.contains("defaults: &defaults\n"));
+        assert!(updated.contains("    <<: *defaults\n"), "merge key inside the MCP block survives");
+        assert!(updated.contains("    env: *log_env\n"), "alias in an edited server survives");
+        assert!(updated.contains("    command: bunx\n"));
+        assert!(updated.ends_with("---\nsecond: document\n"));
+
+        let reparsed = servers(&updated);
+        assert_eq!(reparsed, view);
+        assert_eq!(reparsed["mcpServers"]["time"]["command"], Value::from("uvx"));
+    }
+
+    #[test]
+    fn test_rejects_edits_to_aliased_nodes() {
+        let content = "mcpServers:\n  github: &gh\n    command: npx\n  backup: *gh\n";
+
+        let mut view = servers(content);
+        view["mcpServers"]["github"]["command"] = Value::from("bunx");
+        let error = update_yaml_preserving(content, &view).unwrap_err();
+        assert!(matches!(error.downcast_ref::<YamlEditError>(), Some(YamlEditError::AliasedNode { anchor, .. }) if anchor == "gh"));
+
+        // Values inherited through a merge key cannot be changed in place either
+        let mut view = servers(FIXTURE);
+        view["mcpServers"]["memory"]["timeout"] = Value::from(60);
+        let error = update_yaml_preserving(FIXTURE, &view).unwrap_err();
+        assert!(error.to_string().contains("mcpServers.memory.timeout"));
+    }
+}
@@ -0,0 +1,9 @@
+pub mod models;
+pub mod filesystem;
+pub mod detection;
+pub mod configuration;
+pub mod adapters;
+pub mod server;
+pub mod analysis;
+pub mod daemon;
+pub mod net;
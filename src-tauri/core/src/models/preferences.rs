@@ -38,6 +38,10 @@ pub struct UserPreferences {
     /// Favorite server IDs
     pub favorite_servers: Vec<Uuid>,
     
+    /// Write a generated provenance comment above managed servers in configs that allow comments
+    #[serde(default = "default_stamp_config_comments")]
+    pub stamp_config_comments: bool,
+    
     /// Notification preferences
     pub notifications: NotificationPreferences,
     
@@ -51,6 +55,10 @@ pub struct UserPreferences {
     pub audit_info: AuditInfo,
 }
 
+fn default_stamp_config_comments() -> bool {
+    true
+}
+
 /// Application theme options
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum Theme {
@@ -149,6 +157,7 @@ impl Default for UserPreferences {
             start_at_login: false,
             menu_bar_icon: true,
             favorite_servers: Vec::new(),
+            stamp_config_comments: true,
             notifications: NotificationPreferences::default(),
             security_preferences: SecurityPreferences::default(),
             accessibility_preferences: AccessibilityPreferences::default(),
@@ -0,0 +1,181 @@
+//! HTTP access for registry lookups, README fetches, and remote probes.
+//!
+//! Everything goes through here so the `online` feature can be turned off for
+//! embedders that must not touch the network; requests then fail with [`Offline`].
+
+use anyhow::Result;
+use serde_json::Value as JsonValue;
+
+/// User agent sent with every request
+pub const USER_AGENT: &str = "MCP-Control/1.0";
+
+/// Returned for any request when the crate is built without the `online` feature
+#[derive(Debug, thiserror::Error)]
+#[error("Network access is disabled in this build; cannot fetch {url}")]
+pub struct Offline {
+    pub url: String,
+}
+
+/// Status and body of a completed request
+#[derive(Debug, Clone)]
+pub struct Response {
+    pub status: u16,
+    /// Status line text, such as "404 Not Found"
+    pub status_text: String,
+    pub body: String,
+}
+
+impl Response {
+    /// Whether the status is in the 2xx range
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Parse the body as JSON
+    pub fn json(&self) -> Result<JsonValue> {
+        Ok(serde_json::from_str(&self.body)?)
+    }
+}
+
+/// One server-sent event
+#[derive(Debug, Clone, PartialEq)]
+pub struct Event {
+    /// Event name; `message` when the server sends none
+    pub event: String,
+    pub data: String,
+}
+
+/// Server-sent events read from an open GET request as they arrive
+pub struct EventStream {
+    body: imp::Body,
+    buffer: Vec<u8>,
+}
+
+impl EventStream {
+    /// Wait for the next event; None once the server closes the stream
+    pub async fn next_event(&mut self) -> Result<Option<Event>> {
+        loop {
+            if let Some(end) = self.buffer.windows(2).position(|pair| pair == b"\n\n") {
+                let block: Vec<u8> = self.buffer.drain(..end + 2).collect();
+                if let Some(event) = parse_event(&String::from_utf8_lossy(&block)) {
+                    return Ok(Some(event));
+                }
+                continue;
+            }
+            match self.body.chunk().await? {
+                // Line endings may be CRLF; events are split on blank lines either way
+                Some(chunk) => self.buffer.extend(chunk.into_iter().filter(|&byte| byte != b'\r')),
+                None => return Ok(None),
+            }
+        }
+    }
+}
+
+/// Parse one blank-line-terminated event block; comment-only blocks yield None
+fn parse_event(block: &str) -> Option<Event> {
+    let mut event = None;
+    let mut data: Option<String> = None;
+    for line in block.lines() {
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "event" => event = Some(value.to_string()),
+            "data" => match &mut data {
+                Some(data) => {
+                    data.push('\n');
+                    data.push_str(value);
+                }
+                None => data = Some(value.to_string()),
+            },
+            _ => {}
+        }
+    }
+    if event.is_none() && data.is_none() {
+        return None;
+    }
+    Some(Event { event: event.unwrap_or_else(|| "message".to_string()), data: data.unwrap_or_default() })
+}
+
+/// Open a server-sent event stream with a GET request
+pub async fn open_event_stream(url: &str) -> Result<EventStream> {
+    let body = imp::open_event_stream(url).await?;
+    Ok(EventStream { body, buffer: Vec::new() })
+}
+
+/// Send a GET request with extra headers
+pub async fn get(url: &str, headers: &[(&str, &str)]) -> Result<Response> {
+    imp::send(url, None, headers).await
+}
+
+/// Send a POST request with a JSON body and extra headers
+pub async fn post_json(url: &str, body: &JsonValue, headers: &[(&str, &str)]) -> Result<Response> {
+    imp::send(url, Some(body), headers).await
+}
+
+#[cfg(feature = "online")]
+mod imp {
+    use super::{Response, USER_AGENT};
+    use anyhow::Result;
+    use serde_json::Value as JsonValue;
+
+    pub async fn send(url: &str, body: Option<&JsonValue>, headers: &[(&str, &str)]) -> Result<Response> {
+        let client = reqwest::Client::builder().user_agent(USER_AGENT).build()?;
+        let mut request = match body {
+            Some(body) => client.post(url).json(body),
+            None => client.get(url),
+        };
+        for (name, value) in headers {
+            request = request.header(*name, *value);
+        }
+
+        let response = request.send().await?;
+        let status = response.status();
+        Ok(Response {
+            status: status.as_u16(),
+            status_text: status.to_string(),
+            body: response.text().await?,
+        })
+    }
+
+    /// Response body of an open event stream
+    pub struct Body(reqwest::Response);
+
+    impl Body {
+        pub async fn chunk(&mut self) -> Result<Option<Vec<u8>>> {
+            Ok(self.0.chunk().await?.map(|bytes| bytes.to_vec()))
+        }
+    }
+
+    pub async fn open_event_stream(url: &str) -> Result<Body> {
+        let client = reqwest::Client::builder().user_agent(USER_AGENT).build()?;
+        let response = client.get(url).header("Accept", "text/event-stream").send().await?;
+        if !response.status().is_success() {
+            anyhow::bail!("Event stream refused: {}", response.status());
+        }
+        Ok(Body(response))
+    }
+}
+
+#[cfg(not(feature = "online"))]
+mod imp {
+    use super::{Offline, Response};
+    use anyhow::Result;
+    use serde_json::Value as JsonValue;
+
+    pub async fn send(url: &str, _body: Option<&JsonValue>, _headers: &[(&str, &str)]) -> Result<Response> {
+        Err(Offline { url: url.to_string() }.into())
+    }
+
+    /// No stream can be opened offline
+    pub enum Body {}
+
+    impl Body {
+        pub async fn chunk(&mut self) -> Result<Option<Vec<u8>>> {
+            match *self {}
+        }
+    }
+
+    pub async fn open_event_stream(url: &str) -> Result<Body> {
+        Err(Offline { url: url.to_string() }.into())
+    }
+}
@@ -0,0 +1,985 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value as JsonValue;
+use std::collections::{HashMap, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::Semaphore;
+
+use crate::configuration::annotations::AnnotationStore;
+use crate::detection::{McpServerConfig, ServerType};
+use crate::net;
+
+/// Number of runs kept per server
+pub const HISTORY_LIMIT: usize = 30;
+
+/// Interval between scheduled nightly runs
+pub const NIGHTLY_INTERVAL: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Minimum number of runs before a server can be classified as flaky
+const FLAKY_MIN_RUNS: usize = 4;
+
+/// How a stdio server delimits JSON-RPC messages
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Framing {
+    /// `Content-Length` headers followed by a blank line, as in LSP
+    ContentLength,
+    /// One JSON message per line
+    Newline,
+}
+
+/// Outcome of a single health check
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct HealthRun {
+    pub timestamp: DateTime<Utc>,
+    pub healthy: bool,
+    pub latency_ms: u64,
+    pub error: Option<String>,
+    /// Framing the server answered with, when it was launched over stdio
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub framing: Option<Framing>,
+}
+
+impl HealthRun {
+    /// Create a successful run
+    pub fn healthy(latency_ms: u64) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            healthy: true,
+            latency_ms,
+            error: None,
+            framing: None,
+        }
+    }
+
+    /// Record the framing the server used
+    pub fn with_framing(mut self, framing: Framing) -> Self {
+        self.framing = Some(framing);
+        self
+    }
+
+    /// Create a failed run
+    pub fn failed(latency_ms: u64, error: String) -> Self {
+        Self {
+            timestamp: Utc::now(),
+            healthy: false,
+            latency_ms,
+            error: Some(error),
+            framing: None,
+        }
+    }
+}
+
+/// Performs a health check against a single server
+#[async_trait]
+pub trait HealthProbe: Send + Sync {
+    async fn health_check(&self, server: &McpServerConfig) -> HealthRun;
+}
+
+/// Health probe that launches a stdio server and completes the initialize handshake
+pub struct StdioHealthProbe {
+    pub timeout: Duration,
+    /// How long to wait for a reply to the `Content-Length` request before resending it newline-delimited
+    pub framing_fallback: Duration,
+}
+
+impl StdioHealthProbe {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout, framing_fallback: Duration::from_secs(2) }
+    }
+
+    /// Set how long a server gets to answer `Content-Length` framing before falling back to lines
+    pub fn with_framing_fallback(mut self, framing_fallback: Duration) -> Self {
+        self.framing_fallback = framing_fallback;
+        self
+    }
+
+    async fn handshake(&self, server: &McpServerConfig) -> Result<Framing> {
+        let command = server.command.as_deref()
+            .context("Server command not specified")?;
+
+        let mut cmd = Command::new(command);
+        cmd.args(&server.args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true);
+
+        if let Some(cwd) = &server.cwd {
+            cmd.current_dir(cwd);
+        }
+        if !server.inherit_env {
+            cmd.env_clear();
+        }
+        cmd.envs(&server.env);
+
+        let mut child = cmd.spawn()
+            .with_context(|| format!("Failed to spawn server: {}", command))?;
+        let mut stdin = child.stdin.take().context("Failed to open server stdin")?;
+        let stdout = child.stdout.take().context("Failed to open server stdout")?;
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "mcp-control", "version": env!("CARGO_PKG_VERSION") }
+            }
+        });
+        let body = request.to_string();
+
+        // Try Content-Length first; a line-framed server that stalls on it gets the request again as a line
+        stdin.write_all(format!("Content-Length: {}\r\n\r\n{}", body.len(), body).as_bytes()).await
+            .context("Failed to send initialize request")?;
+        stdin.flush().await?;
+
+        let mut reader = BufReader::new(stdout);
+        let response = read_initialize_response(&mut reader);
+        tokio::pin!(response);
+        let (framing, message) = tokio::select! {
+            result = &mut response => result?,
+            _ = tokio::time::sleep(self.framing_fallback) => {
+                stdin.write_all(format!("\n{}\n", body).as_bytes()).await
+                    .context("Failed to send initialize request")?;
+                stdin.flush().await?;
+                response.await?
+            }
+        };
+
+        if let Some(error) = message.get("error") {
+            anyhow::bail!("Initialize failed: {}", error);
+        }
+        let _ = child.kill().await;
+        Ok(framing)
+    }
+}
+
+/// Read messages in either framing until the reply to our initialize request arrives
+async fn read_initialize_response<R>(reader: &mut BufReader<R>) -> Result<(Framing, JsonValue)>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    let mut line = String::new();
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).await? == 0 {
+            anyhow::bail!("Server exited before responding to initialize");
+        }
+
+        let header = line.trim();
+        let (framing, parsed) = match header.split_once(':') {
+            Some((name, length)) if name.eq_ignore_ascii_case("content-length") => {
+                let length: usize = length.trim().parse().context("Invalid Content-Length header")?;
+                // Skip any further headers up to the blank separator line
+                loop {
+                    line.clear();
+                    if reader.read_line(&mut line).await? == 0 || line.trim().is_empty() {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).await.context("Server closed stdout mid-message")?;
+                (Framing::ContentLength, serde_json::from_slice::<JsonValue>(&body))
+            }
+            _ => (Framing::Newline, serde_json::from_str::<JsonValue>(header)),
+        };
+
+        // Servers may log to stdout before answering; skip anything that isn't our response
+        let Ok(message) = parsed else {
+            continue;
+        };
+        if message.get("id") == Some(&serde_json::json!(1)) {
+            return Ok((framing, message));
+        }
+    }
+}
+
+impl Default for StdioHealthProbe {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
+
+#[async_trait]
+impl HealthProbe for StdioHealthProbe {
+    async fn health_check(&self, server: &McpServerConfig) -> HealthRun {
+        let started = Instant::now();
+        let result = tokio::time::timeout(self.timeout, self.handshake(server)).await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(Ok(framing)) => HealthRun::healthy(latency_ms).with_framing(framing),
+            Ok(Err(e)) => HealthRun::failed(latency_ms, e.to_string()),
+            Err(_) => HealthRun::failed(
+                latency_ms,
+                format!("No response within {}ms", self.timeout.as_millis()),
+            ),
+        }
+    }
+}
+
+/// Health probe for servers reached over a url that a local command must serve first.
+///
+/// Starts the command, waits for the url to accept connections, then sends initialize.
+/// Streamable HTTP servers get it as a POST to the url and may answer with plain JSON or an
+/// event stream; SSE servers are opened with a GET, and initialize is posted to the
+/// endpoint they announce with the reply read back from the stream.
+pub struct HybridHealthProbe {
+    pub timeout: Duration,
+    /// Delay between reachability checks while the started process comes up
+    pub poll_interval: Duration,
+}
+
+impl HybridHealthProbe {
+    pub fn new(timeout: Duration) -> Self {
+        Self { timeout, poll_interval: Duration::from_millis(100) }
+    }
+
+    async fn handshake(&self, server: &McpServerConfig) -> Result<()> {
+        let url = match &server.server_type {
+            ServerType::Http { base_url } => base_url,
+            ServerType::Sse { url } => url,
+            _ => anyhow::bail!("Server has no url to connect to"),
+        };
+        let parsed = url::Url::parse(url).with_context(|| format!("Invalid server url: {}", url))?;
+        let address = format!(
+            "{}:{}",
+            parsed.host_str().context("Server url has no host")?,
+            parsed.port_or_known_default().context("Server url has no port")?
+        );
+
+        // Held until the handshake finishes; dropping it stops the process
+        let mut process = match &server.command {
+            Some(command) => {
+                let mut cmd = Command::new(command);
+                cmd.args(&server.args)
+                    .stdin(Stdio::null())
+                    .stdout(Stdio::null())
+                    .stderr(Stdio::null())
+                    .kill_on_drop(true);
+                if let Some(cwd) = &server.cwd {
+                    cmd.current_dir(cwd);
+                }
+                if !server.inherit_env {
+                    cmd.env_clear();
+                }
+                cmd.envs(&server.env);
+                Some(cmd.spawn().with_context(|| format!("Failed to spawn server: {}", command))?)
+            }
+            None => None,
+        };
+
+        while tokio::net::TcpStream::connect(&address).await.is_err() {
+            if let Some(status) = process.as_mut().map(|child| child.try_wait()).transpose()?.flatten() {
+                anyhow::bail!("Server exited before accepting connections ({})", status);
+            }
+            tokio::time::sleep(self.poll_interval).await;
+        }
+
+        let request = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": { "name": "mcp-control", "version": env!("CARGO_PKG_VERSION") }
+            }
+        });
+        let message = match &server.server_type {
+            ServerType::Sse { .. } => sse_initialize(&parsed, &request).await?,
+            _ => {
+                let response = net::post_json(parsed.as_str(), &request, &[("Accept", "application/json, text/event-stream")])
+                    .await
+                    .context("Failed to send initialize request")?;
+                if !response.is_success() {
+                    anyhow::bail!("Server rejected initialize: {}", response.status_text);
+                }
+
+                // Event streams carry the response on `data:` lines
+                response
+                    .body
+                    .lines()
+                    .map(|line| line.strip_prefix("data:").unwrap_or(line).trim())
+                    .filter_map(|line| serde_json::from_str::<JsonValue>(line).ok())
+                    .find(|message| message.get("id") == Some(&serde_json::json!(1)))
+                    .context("No initialize response from server")?
+            }
+        };
+        if let Some(error) = message.get("error") {
+            anyhow::bail!("Initialize failed: {}", error);
+        }
+
+        Ok(())
+    }
+}
+
+/// Run initialize over the SSE transport: the stream announces where to post requests
+/// in an `endpoint` event, and replies come back as events on the same stream
+async fn sse_initialize(url: &url::Url, request: &JsonValue) -> Result<JsonValue> {
+    let mut events = net::open_event_stream(url.as_str()).await.context("Failed to open event stream")?;
+    let endpoint = loop {
+        match events.next_event().await? {
+            Some(event) if event.event == "endpoint" => {
+                break url.join(event.data.trim()).context("Invalid endpoint announced by server")?;
+            }
+            Some(_) => continue,
+            None => anyhow::bail!("Event stream closed before announcing an endpoint"),
+        }
+    };
+
+    let response = net::post_json(endpoint.as_str(), request, &[])
+        .await
+        .context("Failed to send initialize request")?;
+    if !response.is_success() {
+        anyhow::bail!("Server rejected initialize: {}", response.status_text);
+    }
+
+    while let Some(event) = events.next_event().await? {
+        if let Ok(message) = serde_json::from_str::<JsonValue>(&event.data) {
+            if message.get("id") == Some(&serde_json::json!(1)) {
+                return Ok(message);
+            }
+        }
+    }
+    anyhow::bail!("Event stream closed before the initialize response")
+}
+
+impl Default for HybridHealthProbe {
+    fn default() -> Self {
+        Self::new(Duration::from_secs(10))
+    }
+}
+
+#[async_trait]
+impl HealthProbe for HybridHealthProbe {
+    async fn health_check(&self, server: &McpServerConfig) -> HealthRun {
+        let started = Instant::now();
+        let result = tokio::time::timeout(self.timeout, self.handshake(server)).await;
+        let latency_ms = started.elapsed().as_millis() as u64;
+
+        match result {
+            Ok(Ok(())) => HealthRun::healthy(latency_ms),
+            Ok(Err(e)) => HealthRun::failed(latency_ms, e.to_string()),
+            Err(_) => HealthRun::failed(
+                latency_ms,
+                format!("Server not ready within {}ms", self.timeout.as_millis()),
+            ),
+        }
+    }
+}
+
+/// Health probe that picks the handshake from each server's transport.
+///
+/// Url servers go through [`HybridHealthProbe`]; everything else uses the stdio handshake.
+#[derive(Default)]
+pub struct TransportHealthProbe {
+    pub stdio: StdioHealthProbe,
+    pub url: HybridHealthProbe,
+}
+
+#[async_trait]
+impl HealthProbe for TransportHealthProbe {
+    async fn health_check(&self, server: &McpServerConfig) -> HealthRun {
+        match server.server_type {
+            ServerType::Http { .. } | ServerType::Sse { .. } => self.url.health_check(server).await,
+            _ => self.stdio.health_check(server).await,
+        }
+    }
+}
+
+/// Latency regression detected across a server's recent runs
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct LatencyRegression {
+    pub server_name: String,
+    pub baseline_ms: f64,
+    pub recent_ms: f64,
+}
+
+/// Persisted health check history, keeping the last runs of every server
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HealthHistory {
+    #[serde(skip)]
+    path: PathBuf,
+    pub servers: HashMap<String, VecDeque<HealthRun>>,
+}
+
+impl HealthHistory {
+    /// Load history from disk, starting empty if it does not exist yet
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut history = if path.exists() {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read health history: {}", path.display()))?;
+            serde_json::from_str(&content)
+                .with_context(|| "Failed to parse health history")?
+        } else {
+            Self::default()
+        };
+
+        history.path = path.to_path_buf();
+        Ok(history)
+    }
+
+    /// Load history from its default location in the data directory
+    pub fn load_default() -> Result<Self> {
+        let path = dirs::home_dir()
+            .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
+            .join(".mcp-control")
+            .join("health_history.json");
+        Self::load(&path)
+    }
+
+    /// Save history to disk
+    pub fn save(&self) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create history directory: {}", parent.display()))?;
+        }
+
+        let content = serde_json::to_string_pretty(self)
+            .with_context(|| "Failed to serialize health history")?;
+        fs::write(&self.path, content)
+            .with_context(|| format!("Failed to write health history: {}", self.path.display()))?;
+
+        Ok(())
+    }
+
+    /// Record a run, dropping the oldest once the per-server limit is reached
+    pub fn record(&mut self, server_name: &str, run: HealthRun) {
+        let runs = self.servers.entry(server_name.to_string()).or_default();
+        runs.push_back(run);
+        while runs.len() > HISTORY_LIMIT {
+            runs.pop_front();
+        }
+    }
+
+    /// Get the recorded runs of a server, oldest first
+    pub fn runs(&self, server_name: &str) -> Vec<&HealthRun> {
+        self.servers
+            .get(server_name)
+            .map(|runs| runs.iter().collect())
+            .unwrap_or_default()
+    }
+
+    /// Servers that alternate between healthy and failing runs, sorted by name
+    pub fn flaky_servers(&self) -> Vec<String> {
+        let mut flaky: Vec<String> = self
+            .servers
+            .iter()
+            .filter(|(_, runs)| runs.len() >= FLAKY_MIN_RUNS)
+            .filter(|(_, runs)| {
+                let flips = runs
+                    .iter()
+                    .zip(runs.iter().skip(1))
+                    .filter(|(a, b)| a.healthy != b.healthy)
+                    .count();
+                flips >= 2
+            })
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        flaky.sort();
+        flaky
+    }
+
+    /// Servers whose recent healthy runs are slower than earlier ones by more than `factor`
+    pub fn latency_regressions(&self, factor: f64) -> Vec<LatencyRegression> {
+        let mut regressions: Vec<LatencyRegression> = self
+            .servers
+            .iter()
+            .filter_map(|(name, runs)| {
+                let latencies: Vec<f64> = runs
+                    .iter()
+                    .filter(|r| r.healthy)
+                    .map(|r| r.latency_ms as f64)
+                    .collect();
+                if latencies.len() < FLAKY_MIN_RUNS {
+                    return None;
+                }
+
+                let (older, recent) = latencies.split_at(latencies.len() / 2);
+                let baseline_ms = older.iter().sum::<f64>() / older.len() as f64;
+                let recent_ms = recent.iter().sum::<f64>() / recent.len() as f64;
+
+                (recent_ms > baseline_ms * factor).then(|| LatencyRegression {
+                    server_name: name.clone(),
+                    baseline_ms,
+                    recent_ms,
+                })
+            })
+            .collect();
+
+        regressions.sort_by(|a, b| a.server_name.cmp(&b.server_name));
+        regressions
+    }
+}
+
+/// Result of a batch health run
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchHealthReport {
+    /// Checked servers and their results, sorted by name
+    pub results: Vec<(String, HealthRun)>,
+    /// Servers that were not run, with the reason
+    pub skipped: Vec<(String, String)>,
+}
+
+/// Runs health checks across all configured servers with bounded concurrency
+pub struct HealthScheduler {
+    probe: Arc<dyn HealthProbe>,
+    max_concurrency: usize,
+    max_jitter: Duration,
+}
+
+impl HealthScheduler {
+    /// Create a scheduler that picks the probe from each server's transport
+    pub fn new() -> Self {
+        Self::with_probe(Arc::new(TransportHealthProbe::default()))
+    }
+
+    /// Create a scheduler using a custom probe
+    pub fn with_probe(probe: Arc<dyn HealthProbe>) -> Self {
+        Self {
+            probe,
+            max_concurrency: 4,
+            max_jitter: Duration::from_secs(2),
+        }
+    }
+
+    /// Set the maximum number of servers checked at once
+    pub fn with_concurrency(mut self, max_concurrency: usize) -> Self {
+        self.max_concurrency = max_concurrency.max(1);
+        self
+    }
+
+    /// Set the maximum random delay before each check
+    pub fn with_jitter(mut self, max_jitter: Duration) -> Self {
+        self.max_jitter = max_jitter;
+        self
+    }
+
+    /// Check every eligible server and record the results in the history.
+    ///
+    /// Servers marked manual-only are never run as part of a batch.
+    pub async fn run_all(
+        &self,
+        servers: &[McpServerConfig],
+        annotations: &AnnotationStore,
+        history: &mut HealthHistory,
+    ) -> BatchHealthReport {
+        let mut report = BatchHealthReport::default();
+        let semaphore = Arc::new(Semaphore::new(self.max_concurrency));
+        let mut tasks = tokio::task::JoinSet::new();
+
+        for server in servers {
+            if let Some(reason) = skip_reason(server, annotations) {
+                report.skipped.push((server.name.clone(), reason.to_string()));
+                continue;
+            }
+
+            let probe = Arc::clone(&self.probe);
+            let semaphore = Arc::clone(&semaphore);
+            let server = server.clone();
+            let delay = jitter(self.max_jitter);
+
+            tasks.spawn(async move {
+                let _permit = semaphore.acquire_owned().await;
+                tokio::time::sleep(delay).await;
+                let run = probe.health_check(&server).await;
+                (server.name, run)
+            });
+        }
+
+        while let Some(joined) = tasks.join_next().await {
+            match joined {
+                Ok(result) => report.results.push(result),
+                Err(e) => log::warn!("Health check task failed: {}", e),
+            }
+        }
+
+        report.results.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, run) in &report.results {
+            history.record(name, run.clone());
+        }
+
+        report
+    }
+
+    /// Run a batch at a fixed interval, saving the history after every run
+    pub fn spawn_periodic(
+        self: Arc<Self>,
+        interval: Duration,
+        servers: Vec<McpServerConfig>,
+        annotations: AnnotationStore,
+        mut history: HealthHistory,
+    ) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            // The first tick completes immediately; wait a full interval before the first run
+            ticker.tick().await;
+
+            loop {
+                ticker.tick().await;
+                self.run_all(&servers, &annotations, &mut history).await;
+                if let Err(e) = history.save() {
+                    log::warn!("Failed to save health history: {}", e);
+                }
+            }
+        })
+    }
+}
+
+impl Default for HealthScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Reason a server is excluded from batch runs, if any
+fn skip_reason(server: &McpServerConfig, annotations: &AnnotationStore) -> Option<&'static str> {
+    if annotations.is_manual_only(&server.name) {
+        Some("Marked manual-only")
+    } else if !server.metadata.enabled {
+        Some("Server is disabled")
+    } else if !is_probeable(server) {
+        Some("No health check for this transport")
+    } else {
+        None
+    }
+}
+
+/// Whether [`TransportHealthProbe`] has a handshake for the server's transport
+fn is_probeable(server: &McpServerConfig) -> bool {
+    match server.server_type {
+        ServerType::Stdio => server.command.is_some(),
+        ServerType::Http { .. } | ServerType::Sse { .. } => true,
+        _ => false,
+    }
+}
+
+/// Random delay up to `max`, spreading out server launches
+fn jitter(max: Duration) -> Duration {
+    let max_ms = max.as_millis();
+    if max_ms == 0 {
+        return Duration::ZERO;
+    }
+    Duration::from_millis((uuid::Uuid::new_v4().as_u128() % max_ms) as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detection::{ConfigSource, ServerMetadata};
+    use tempfile::TempDir;
+
+    /// Fake MCP server that answers initialize, failing every second launch in flaky mode
+    const FAKE_SERVER: &str = r#"
+count=$(cat "$STATE" 2>/dev/null || echo 0)
+count=$((count + 1))
+echo "$count" > "$STATE"
+if [ "$MODE" = flaky ] && [ $((count % 2)) -eq 0 ]; then
+    exit 1
+fi
+read line
+echo 'starting fake server'
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{},"serverInfo":{"name":"fake","version":"0.0.0"}}}'
+"#;
+
+    fn fake_server(dir: &Path, name: &str, mode: &str) -> McpServerConfig {
+        let mut env = HashMap::new();
+        env.insert("STATE".to_string(), dir.join(format!("{}.count", name)).display().to_string());
+        env.insert("MODE".to_string(), mode.to_string());
+
+        McpServerConfig {
+            name: name.to_string(),
+            command: Some("sh".to_string()),
+            args: vec!["-c".to_string(), FAKE_SERVER.to_string()],
+            env,
+            cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
+            server_type: ServerType::Stdio,
+            metadata: ServerMetadata {
+                description: None,
+                version: None,
+                author: None,
+                capabilities: vec![],
+                enabled: true,
+                source: ConfigSource::MainConfig,
+            },
+        }
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_batch_runs_detect_flaky_server() {
+        let temp_dir = TempDir::new().unwrap();
+        let history_path = temp_dir.path().join("health_history.json");
+        let servers = vec![
+            fake_server(temp_dir.path(), "steady", "ok"),
+            fake_server(temp_dir.path(), "flaky", "flaky"),
+            fake_server(temp_dir.path(), "browser", "ok"),
+        ];
+
+        let mut annotations = AnnotationStore::default();
+        annotations.entry("browser").manual_only = true;
+
+        let scheduler = HealthScheduler::new()
+            .with_concurrency(2)
+            .with_jitter(Duration::ZERO);
+        let mut history = HealthHistory::load(&history_path).unwrap();
+
+        for _ in 0..6 {
+            let report = scheduler.run_all(&servers, &annotations, &mut history).await;
+            assert_eq!(report.results.len(), 2);
+            assert_eq!(report.skipped, vec![("browser".to_string(), "Marked manual-only".to_string())]);
+        }
+        history.save().unwrap();
+
+        // The manual-only server was never launched
+        assert!(!temp_dir.path().join("browser.count").exists());
+
+        let history = HealthHistory::load(&history_path).unwrap();
+        assert_eq!(history.runs("steady").len(), 6);
+        assert!(history.runs("steady").iter().all(|r| r.healthy));
+        assert_eq!(history.runs("flaky").iter().filter(|r| r.healthy).count(), 3);
+        assert_eq!(history.flaky_servers(), vec!["flaky".to_string()]);
+    }
+
+    #[tokio::test]
+    async fn test_url_servers_are_probed_not_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+        let mut remote = fake_server(temp_dir.path(), "remote", "ok");
+        remote.server_type = ServerType::Http { base_url: format!("http://127.0.0.1:{}/mcp", port) };
+        remote.command = None;
+        let mut socket = fake_server(temp_dir.path(), "socket", "ok");
+        socket.server_type = ServerType::WebSocket { url: "ws://127.0.0.1:1".to_string() };
+
+        let probe = TransportHealthProbe {
+            stdio: StdioHealthProbe::default(),
+            url: HybridHealthProbe::new(Duration::from_millis(200)),
+        };
+        let scheduler = HealthScheduler::with_probe(Arc::new(probe)).with_jitter(Duration::ZERO);
+        let mut history = HealthHistory::load(&temp_dir.path().join("health_history.json")).unwrap();
+        let report = scheduler
+            .run_all(&[remote, socket], &AnnotationStore::default(), &mut history)
+            .await;
+
+        // Nothing listens on the port, so the url handshake runs and fails
+        assert_eq!(report.results.len(), 1);
+        assert_eq!(report.results[0].0, "remote");
+        assert!(!report.results[0].1.healthy);
+        assert_eq!(
+            report.skipped,
+            vec![("socket".to_string(), "No health check for this transport".to_string())]
+        );
+    }
+
+    /// Answers only once its request has been read with `Content-Length` framing
+    const CONTENT_LENGTH_SERVER: &str = r#"
+while IFS= read -r line; do
+    line=$(printf '%s' "$line" | tr -d '\r')
+    case "$line" in
+        Content-Length:*) len=${line#Content-Length: } ;;
+        '') break ;;
+    esac
+done
+dd bs=1 count="$len" 2>/dev/null >/dev/null
+body='{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{}}}'
+printf 'Content-Length: %d\r\n\r\n%s' "${#body}" "$body"
+"#;
+
+    /// Ignores anything until a complete JSON line arrives
+    const LINE_SERVER: &str = r#"
+while IFS= read -r line; do
+    case "$line" in
+        '{'*) echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{}}}'; exit 0 ;;
+    esac
+done
+"#;
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_probe_detects_framing() {
+        let temp_dir = TempDir::new().unwrap();
+        let probe = StdioHealthProbe::new(Duration::from_secs(10)).with_framing_fallback(Duration::from_millis(200));
+
+        for (script, expected) in [(CONTENT_LENGTH_SERVER, Framing::ContentLength), (LINE_SERVER, Framing::Newline)] {
+            let mut server = fake_server(temp_dir.path(), "framed", "ok");
+            server.args = vec!["-c".to_string(), script.to_string()];
+            let run = probe.health_check(&server).await;
+            assert!(run.healthy, "{:?}", run.error);
+            assert_eq!(run.framing, Some(expected));
+        }
+    }
+
+    #[test]
+    fn test_history_keeps_last_runs() {
+        let mut history = HealthHistory::default();
+        for latency in 0..(HISTORY_LIMIT as u64 + 5) {
+            history.record("memory", HealthRun::healthy(latency));
+        }
+
+        let runs = history.runs("memory");
+        assert_eq!(runs.len(), HISTORY_LIMIT);
+        assert_eq!(runs[0].latency_ms, 5);
+    }
+
+    #[test]
+    fn test_latency_regression() {
+        let mut history = HealthHistory::default();
+        for latency in [100, 110, 90, 400, 420, 380] {
+            history.record("search", HealthRun::healthy(latency));
+        }
+        for latency in [100, 105, 95, 100] {
+            history.record("memory", HealthRun::healthy(latency));
+        }
+
+        let regressions = history.latency_regressions(2.0);
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0].server_name, "search");
+        assert_eq!(regressions[0].baseline_ms, 100.0);
+        assert_eq!(regressions[0].recent_ms, 400.0);
+    }
+
+    /// Stub that only starts serving once `flag` exists, answering initialize as an event stream
+    #[cfg(feature = "online")]
+    async fn serve_after_flag(port: u16, flag: PathBuf) {
+        use tokio::io::AsyncReadExt;
+
+        while !flag.exists() {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let listener = tokio::net::TcpListener::bind(("127.0.0.1", port)).await.unwrap();
+
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            // Reachability checks connect and hang up without sending anything
+            while !String::from_utf8_lossy(&request).contains("initialize") {
+                match socket.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+            if request.is_empty() {
+                continue;
+            }
+
+            let body = "event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"protocolVersion\":\"2024-11-05\",\"capabilities\":{}}}\n\n";
+            let response = format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                body.len(),
+                body
+            );
+            let _ = socket.write_all(response.as_bytes()).await;
+        }
+    }
+
+    #[cfg(feature = "online")]
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hybrid_probe_starts_command_before_handshake() {
+        let temp_dir = TempDir::new().unwrap();
+        let flag = temp_dir.path().join("started");
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+        let stub = tokio::spawn(serve_after_flag(port, flag.clone()));
+
+        let mut server = fake_server(temp_dir.path(), "studio", "ok");
+        server.server_type = ServerType::Http { base_url: format!("http://127.0.0.1:{}/mcp", port) };
+        server.command = None;
+
+        // Nothing serves the url until the start command runs
+        let run = HybridHealthProbe::new(Duration::from_millis(500)).health_check(&server).await;
+        assert!(!run.healthy);
+        assert!(!flag.exists());
+
+        server.command = Some("sh".to_string());
+        server.args = vec!["-c".to_string(), format!("touch '{}'; sleep 30", flag.display())];
+        let run = HybridHealthProbe::default().health_check(&server).await;
+        assert!(run.healthy, "{:?}", run.error);
+
+        stub.abort();
+    }
+
+    /// Legacy SSE server: the GET stream announces `/messages`, and the reply to a post
+    /// there is sent back on the stream
+    #[cfg(feature = "online")]
+    async fn serve_sse(listener: tokio::net::TcpListener) {
+        use tokio::io::AsyncReadExt;
+
+        let mut stream = None;
+        loop {
+            let (mut socket, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            let mut buf = [0u8; 1024];
+            while !request.ends_with(b"\r\n\r\n") && !String::from_utf8_lossy(&request).contains("initialize") {
+                match socket.read(&mut buf).await {
+                    Ok(0) | Err(_) => break,
+                    Ok(n) => request.extend_from_slice(&buf[..n]),
+                }
+            }
+
+            if request.starts_with(b"GET") {
+                let _ = socket
+                    .write_all(b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\n\r\nevent: endpoint\r\ndata: /messages?session=1\r\n\r\n")
+                    .await;
+                stream = Some(socket);
+            } else if request.starts_with(b"POST /messages?session=1") {
+                let _ = socket.write_all(b"HTTP/1.1 202 Accepted\r\nContent-Length: 0\r\nConnection: close\r\n\r\n").await;
+                if let Some(stream) = stream.as_mut() {
+                    let _ = stream
+                        .write_all(b"event: message\ndata: {\"jsonrpc\":\"2.0\",\"id\":1,\"result\":{\"capabilities\":{}}}\n\n")
+                        .await;
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "online")]
+    #[tokio::test]
+    async fn test_sse_probe_posts_to_announced_endpoint() {
+        let temp_dir = TempDir::new().unwrap();
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let stub = tokio::spawn(serve_sse(listener));
+
+        let mut server = fake_server(temp_dir.path(), "legacy", "ok");
+        server.server_type = ServerType::Sse { url: format!("http://127.0.0.1:{}/sse", port) };
+        server.command = None;
+
+        let run = HybridHealthProbe::new(Duration::from_secs(5)).health_check(&server).await;
+        assert!(run.healthy, "{:?}", run.error);
+
+        stub.abort();
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_hybrid_probe_fails_fast_when_command_exits() {
+        let temp_dir = TempDir::new().unwrap();
+        let port = std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap().port();
+
+        let mut server = fake_server(temp_dir.path(), "crashy", "ok");
+        server.server_type = ServerType::Http { base_url: format!("http://127.0.0.1:{}/mcp", port) };
+        server.command = Some("sh".to_string());
+        server.args = vec!["-c".to_string(), "exit 3".to_string()];
+
+        let run = HybridHealthProbe::new(Duration::from_secs(30)).health_check(&server).await;
+        assert!(!run.healthy);
+        assert!(run.error.as_deref().unwrap().contains("exited"), "{:?}", run.error);
+        assert!(run.latency_ms < 5_000);
+    }
+}
@@ -40,12 +40,23 @@ impl ServerManager {
             }
         }
 
+        // Catch a missing command or working directory before spawning
+        let issues = server_config.readiness_issues();
+        if !issues.is_empty() {
+            return Ok(ServerOperationResult {
+                success: false,
+                server_id: server_id.clone(),
+                message: "Server is not ready to launch".to_string(),
+                errors: issues,
+            });
+        }
+
         // Get command and args
         let command = server_config.command.as_ref()
             .context("Server command not specified")?;
 
         // Start the process
-        match self.spawn_server_process(command, &server_config.args, &server_config.env).await {
+        match self.spawn_server_process(command, &server_config.args, &server_config.env, server_config.cwd.as_deref(), server_config.inherit_env).await {
             Ok(child) => {
                 let pid = child.id();
                 
@@ -202,6 +213,8 @@ impl ServerManager {
                 args: vec!["-y".to_string(), "@modelcontextprotocol/server-filesystem".to_string()],
                 env: HashMap::new(),
                 cwd: None,
+                inherit_env: true,
+                extra_options: HashMap::new(),
                 server_type: ServerType::Stdio,
                 metadata: ServerMetadata {
                     description: Some("File system operations server".to_string()),
@@ -219,6 +232,8 @@ impl ServerManager {
                 args: vec!["-y".to_string(), "@modelcontextprotocol/server-git".to_string()],
                 env: HashMap::new(),
                 cwd: None,
+                inherit_env: true,
+                extra_options: HashMap::new(),
                 server_type: ServerType::Stdio,
                 metadata: ServerMetadata {
                     description: Some("Git operations server".to_string()),
@@ -236,15 +251,12 @@ impl ServerManager {
     async fn is_server_available(&self, server_config: &McpServerConfig) -> bool {
         if let Some(ref command) = server_config.command {
             // Try to run the command with --help to see if it exists
-            match Command::new(command)
+            Command::new(command)
                 .arg("--help")
                 .stdout(Stdio::null())
                 .stderr(Stdio::null())
                 .status()
-            {
-                Ok(_) => true,
-                Err(_) => false,
-            }
+                .is_ok()
         } else {
             false
         }
@@ -360,6 +372,8 @@ impl ServerManager {
         command: &str,
         args: &[String],
         env: &std::collections::HashMap<String, String>,
+        cwd: Option<&str>,
+        inherit_env: bool,
     ) -> Result<std::process::Child> {
         let mut cmd = Command::new(command);
         cmd.args(args)
@@ -367,6 +381,14 @@ impl ServerManager {
             .stdout(Stdio::piped())
             .stderr(Stdio::piped());
 
+        if let Some(dir) = cwd {
+            cmd.current_dir(dir);
+        }
+
+        if !inherit_env {
+            cmd.env_clear();
+        }
+
         // Add environment variables
         for (key, value) in env {
             cmd.env(key, value);
@@ -395,6 +417,8 @@ mod tests {
             args: vec!["hello".to_string()],
             env: std::collections::HashMap::new(),
             cwd: None,
+            inherit_env: true,
+            extra_options: HashMap::new(),
             server_type: ServerType::Stdio,
             metadata: ServerMetadata {
                 description: None,
@@ -436,12 +460,8 @@ mod tests {
         // Initially no servers in registry
         assert_eq!(manager.get_registry().get_available_server_names().len(), 0);
         
-        // Run discovery
-        let discovered = manager.discover_servers().await.unwrap();
-        
-        // Should have discovered some servers (at least the common ones if commands exist)
-        // Note: This might be 0 in CI environments without npx
-        assert!(discovered >= 0);
+        // Run discovery; it may find nothing in CI environments without npx
+        manager.discover_servers().await.unwrap();
         
         // Registry should be updated with last scan
         assert!(manager.get_registry().last_scan.is_some());
@@ -584,4 +604,16 @@ mod tests {
         assert!(available.contains(&"available-server".to_string()));
         assert!(installed.contains(&"installed-server".to_string()));
     }
+
+    #[tokio::test]
+    async fn test_start_server_rejects_missing_cwd() {
+        let manager = ServerManager::new();
+        let mut config = create_test_server_config("missing-cwd");
+        config.cwd = Some("/nonexistent/mcp-server-dir".to_string());
+
+        let result = manager.start_server(&config).await.unwrap();
+        assert!(!result.success);
+        assert!(result.errors[0].contains("/nonexistent/mcp-server-dir"));
+        assert_eq!(manager.get_server_status("missing-cwd").await, ServerStatus::Stopped);
+    }
 }
@@ -3,10 +3,12 @@ use std::process::Child;
 
 use crate::detection::McpServerConfig;
 
+pub mod health;
 pub mod manager;
 pub mod registry;
 pub mod process;
 
+pub use health::{Framing, HealthScheduler, HealthHistory, HealthRun};
 pub use manager::ServerManager;
 pub use registry::ServerRegistry;
 
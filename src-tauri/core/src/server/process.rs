@@ -6,12 +6,15 @@ use std::collections::HashMap;
 pub struct ProcessManager;
 
 impl ProcessManager {
-    /// Spawn a new MCP server process
+    /// Spawn a new MCP server process.
+    ///
+    /// When `inherit_env` is false the child sees only the variables in `env`.
     pub fn spawn_server(
         command: &str,
         args: &[String],
         env: &HashMap<String, String>,
         working_dir: Option<&str>,
+        inherit_env: bool,
     ) -> Result<Child> {
         let mut cmd = Command::new(command);
         
@@ -25,6 +28,11 @@ impl ProcessManager {
             cmd.current_dir(dir);
         }
 
+        // Isolate the child from the parent environment if requested
+        if !inherit_env {
+            cmd.env_clear();
+        }
+
         // Add environment variables
         for (key, value) in env {
             cmd.env(key, value);
@@ -67,7 +75,7 @@ mod tests {
     #[test]
     fn test_spawn_simple_process() {
         let env = HashMap::new();
-        let result = ProcessManager::spawn_server("echo", &["hello".to_string()], &env, None);
+        let result = ProcessManager::spawn_server("echo", &["hello".to_string()], &env, None, true);
         assert!(result.is_ok());
         
         if let Ok(mut child) = result {
@@ -82,7 +90,21 @@ mod tests {
     #[test]
     fn test_spawn_nonexistent_command() {
         let env = HashMap::new();
-        let result = ProcessManager::spawn_server("nonexistent-command-12345", &[], &env, None);
+        let result = ProcessManager::spawn_server("nonexistent-command-12345", &[], &env, None, true);
         assert!(result.is_err());
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_spawn_without_inherited_env() {
+        let mut env = HashMap::new();
+        env.insert("MCP_ONLY_VAR".to_string(), "1".to_string());
+
+        let child = ProcessManager::spawn_server("/usr/bin/env", &[], &env, None, false).unwrap();
+        let output = child.wait_with_output().unwrap();
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        let vars: Vec<&str> = stdout.lines().collect();
+        assert_eq!(vars, vec!["MCP_ONLY_VAR=1"]);
+    }
 }
@@ -147,6 +147,8 @@ mod tests {
             args: vec![],
             env: std::collections::HashMap::new(),
             cwd: None,
+            inherit_env: true,
+            extra_options: std::collections::HashMap::new(),
             server_type: ServerType::Stdio,
             metadata: ServerMetadata {
                 description: None,
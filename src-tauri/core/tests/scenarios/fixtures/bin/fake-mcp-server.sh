@@ -0,0 +1,10 @@
+#!/bin/sh
+# Stand-in MCP server for scenario tests: answers initialize and exits.
+# A server whose env sets FAKE_MCP_FAIL crashes on launch instead.
+if [ -n "$FAKE_MCP_FAIL" ]; then
+    echo "fake server crashed" >&2
+    exit 1
+fi
+read -r request
+echo 'fake server starting'
+echo '{"jsonrpc":"2.0","id":1,"result":{"protocolVersion":"2024-11-05","capabilities":{"tools":{}},"serverInfo":{"name":"fake-mcp-server","version":"0.0.0"}}}'
@@ -0,0 +1,409 @@
+//! Reusable harness for end-to-end scenarios: detect → analyze → add → health-check.
+//!
+//! A scenario is a directory under `fixtures/` with:
+//! - `root/`: stands in for the filesystem root; `root/home` is `~`. Every application
+//!   profile is rewritten to look for its executables and configs under it.
+//! - `http.json` (optional): canned responses keyed by request path, served on a local
+//!   port and used as the npm registry. Without it the registry is unreachable.
+//!
+//! Steps record events as they go. `assert_golden` compares the event log and the listed
+//! files against `golden/<scenario>/`; run with `UPDATE_GOLDEN=1` to rewrite them.
+
+use std::collections::HashMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::net::TcpListener;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use mcp_control_core::analysis::{DetectedConfig, ServerAnalyzer};
+use mcp_control_core::configuration::annotations::AnnotationStore;
+use mcp_control_core::configuration::SyncManager;
+use mcp_control_core::detection::{
+    ApplicationDetector, ApplicationProfile, ApplicationRegistry, ConfigSource, ConfigValidationResult,
+    ConfigValidator, McpServerConfig, ServerMetadata, ServerType, SuggestedAction, SuggestionContext,
+    SuggestionEngine,
+};
+use mcp_control_core::filesystem::{ConfigFileService, InMemoryFs};
+use mcp_control_core::server::health::{HealthProbe, StdioHealthProbe};
+use mcp_control_core::server::{HealthHistory, HealthRun, HealthScheduler};
+use serde_json::Value as JsonValue;
+use tempfile::TempDir;
+
+/// Placeholder for the scenario root in events and golden files
+const ROOT_MARKER: &str = "$ROOT";
+
+fn scenarios_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests").join("scenarios")
+}
+
+/// A fixture machine plus the events recorded while driving it
+pub struct Scenario {
+    name: String,
+    root: PathBuf,
+    registry: ApplicationRegistry,
+    registry_url: String,
+    events: Vec<String>,
+    _http: Option<CannedHttp>,
+    temp: TempDir,
+}
+
+impl Scenario {
+    /// Copy a fixture machine into a temp dir and point every application profile at it
+    pub fn load(name: &str) -> Self {
+        let fixture = scenarios_dir().join("fixtures").join(name);
+        let temp = TempDir::new().unwrap();
+        let root = temp.path().join("root");
+        copy_dir(&fixture.join("root"), &root);
+
+        let mut registry = ApplicationRegistry::new();
+        let ids: Vec<String> = registry.get_all_applications().iter().map(|app| app.id.clone()).collect();
+        for id in ids {
+            let mut profile = registry.remove_application(&id).unwrap();
+            rehome_profile(&mut profile, &root);
+            registry.add_application(profile);
+        }
+
+        let http = fs::read_to_string(fixture.join("http.json"))
+            .ok()
+            .map(|content| CannedHttp::serve(serde_json::from_str(&content).unwrap()));
+        let registry_url = match &http {
+            Some(http) => http.base_url.clone(),
+            None => unreachable_url(),
+        };
+
+        Self {
+            name: name.to_string(),
+            root,
+            registry,
+            registry_url,
+            events: Vec::new(),
+            _http: http,
+            temp,
+        }
+    }
+
+    /// Absolute path of a file given relative to the scenario root
+    pub fn path(&self, relative: &str) -> PathBuf {
+        self.root.join(relative)
+    }
+
+    pub fn profile(&self, app_id: &str) -> ApplicationProfile {
+        self.registry.get_application(app_id).unwrap().clone()
+    }
+
+    /// Record an event; the scenario root is replaced so events are machine independent
+    pub fn event(&mut self, event: impl Into<String>) {
+        let event = event.into().replace(&*self.root.to_string_lossy(), ROOT_MARKER);
+        self.events.push(event);
+    }
+
+    /// Detect applications through an in-memory snapshot of the fixture tree
+    pub async fn detect(&mut self) -> Vec<String> {
+        let fs = InMemoryFs::case_sensitive();
+        snapshot_dir(&fs, &self.root);
+        let mut detector = ApplicationDetector::with_fs(self.registry.clone(), Arc::new(fs)).unwrap();
+
+        let mut detected: Vec<String> = detector
+            .detect_all_applications()
+            .await
+            .unwrap()
+            .into_iter()
+            .filter(|result| result.detected)
+            .map(|result| result.profile.id)
+            .collect();
+        detected.sort();
+        for id in &detected {
+            self.event(format!("detected {}", id));
+        }
+        detected
+    }
+
+    /// Read an application's config, recording every coded diagnostic
+    pub async fn scan(&mut self, app_id: &str) -> ConfigValidationResult {
+        let result = ConfigValidator::new()
+            .unwrap()
+            .validate_application_config(&self.profile(app_id))
+            .await
+            .unwrap();
+        for message in &result.messages {
+            if let Some(code) = &message.code {
+                self.event(format!("diagnostic {} {:?} {}", app_id, message.level, code));
+            }
+        }
+        let mut names: Vec<&str> = result.mcp_servers.iter().map(|server| server.name.as_str()).collect();
+        names.sort();
+        self.event(format!("scanned {}: [{}]", app_id, names.join(", ")));
+        result
+    }
+
+    /// Analyze a package through the scenario's registry
+    pub async fn analyze(&mut self, package: &str) -> Option<DetectedConfig> {
+        match ServerAnalyzer::with_registry(&self.registry_url).analyze_package(package).await {
+            Ok(result) => {
+                self.record_analysis(&result.config);
+                Some(result.config)
+            }
+            Err(_) => {
+                self.event(format!("analysis failed: {}", package));
+                None
+            }
+        }
+    }
+
+    /// Analyze a local checkout given relative to the scenario root
+    pub async fn analyze_path(&mut self, relative: &str) -> DetectedConfig {
+        let results = ServerAnalyzer::new().analyze_dropped_path(&self.path(relative)).await.unwrap();
+        let config = results.into_iter().next().unwrap().config;
+        self.record_analysis(&config);
+        config
+    }
+
+    fn record_analysis(&mut self, config: &DetectedConfig) {
+        self.event(format!("analyzed {}: {} {}", config.name, config.command, config.args.join(" ")));
+        if let Some(warning) = config.peer_dependency_warning() {
+            self.event(format!("warning {}", warning));
+        }
+    }
+
+    /// Add an analyzed server to an application, keeping the servers it already has
+    pub async fn add_server(&mut self, app_id: &str, name: &str, config: &DetectedConfig) {
+        let mut servers = self.scan(app_id).await.mcp_servers;
+        servers.retain(|server| server.name != name);
+        servers.push(server_from_analysis(name, config));
+        self.sync(app_id, &servers).await;
+        self.event(format!("added {} to {}", name, app_id));
+    }
+
+    /// Copy every server of one application into another, keeping the target's own servers
+    pub async fn sync_between(&mut self, from: &str, to: &str) {
+        let source = self.scan(from).await.mcp_servers;
+        let mut servers = self.scan(to).await.mcp_servers;
+        servers.retain(|server| !source.iter().any(|s| s.name == server.name));
+        servers.extend(source);
+        servers.sort_by(|a, b| a.name.cmp(&b.name));
+        self.sync(to, &servers).await;
+        self.event(format!("synced {} into {}", from, to));
+    }
+
+    async fn sync(&mut self, app_id: &str, servers: &[McpServerConfig]) {
+        let mut file_service = ConfigFileService::new("scenario".to_string(), self.temp.path().join("backups"));
+        let result = SyncManager::new()
+            .sync_to_application(&self.profile(app_id), servers, &mut file_service)
+            .await
+            .unwrap();
+        assert!(result.success, "sync to {} failed: {:?}", app_id, result.errors);
+    }
+
+    /// Apply every fix suggested for an application's diagnostics
+    pub async fn apply_suggested_fixes(&mut self, app_id: &str) {
+        let profile = self.profile(app_id);
+        let engine = SuggestionEngine::new();
+        let result = self.scan(app_id).await;
+        let context = SuggestionContext::for_profile(&profile);
+
+        for message in &result.messages {
+            for action in engine.for_message(message, &context) {
+                if let SuggestedAction::Fix { title, .. } = &action {
+                    let file_service = ConfigFileService::new("scenario".to_string(), self.temp.path().join("backups"));
+                    engine.plan_fix(&profile, &action).await.unwrap().apply(&file_service).await.unwrap();
+                    self.event(format!("fixed {}: {}", app_id, title));
+                }
+            }
+        }
+    }
+
+    /// Health-check an application's servers, launching the fake MCP server in their place
+    pub async fn health_check(&mut self, app_id: &str) -> Vec<(String, HealthRun)> {
+        let servers = self.scan(app_id).await.mcp_servers;
+        let mut history = HealthHistory::load(&self.temp.path().join("health_history.json")).unwrap();
+        let report = HealthScheduler::with_probe(Arc::new(FakeServerProbe::default()))
+            .with_jitter(Duration::ZERO)
+            .run_all(&servers, &AnnotationStore::default(), &mut history)
+            .await;
+
+        for (name, run) in &report.results {
+            let status = if run.healthy { "ok" } else { "failed" };
+            self.event(format!("health {}/{}: {}", app_id, name, status));
+        }
+        report.results
+    }
+
+    /// Compare the event log and the given files (relative to the root) with the golden copies
+    pub fn assert_golden(&self, files: &[&str]) {
+        let golden_dir = scenarios_dir().join("golden").join(&self.name);
+        let update = std::env::var_os("UPDATE_GOLDEN").is_some();
+
+        let mut actual = vec![("events.txt".to_string(), self.events.join("\n") + "\n")];
+        for file in files {
+            let content = fs::read_to_string(self.path(file))
+                .unwrap_or_else(|e| panic!("{} was not written: {}", file, e));
+            actual.push((file.to_string(), content.replace(&*self.root.to_string_lossy(), ROOT_MARKER)));
+        }
+
+        for (file, content) in actual {
+            let golden_path = golden_dir.join(&file);
+            if update {
+                fs::create_dir_all(golden_path.parent().unwrap()).unwrap();
+                fs::write(&golden_path, &content).unwrap();
+                continue;
+            }
+            let expected = fs::read_to_string(&golden_path)
+                .unwrap_or_else(|_| panic!("Missing golden file {}; run with UPDATE_GOLDEN=1", golden_path.display()));
+            assert_eq!(content, expected, "{} differs from {}", file, golden_path.display());
+        }
+    }
+}
+
+/// Runs the fake MCP server in place of a server's command, keeping its env
+#[derive(Default)]
+struct FakeServerProbe {
+    stdio: StdioHealthProbe,
+}
+
+#[async_trait]
+impl HealthProbe for FakeServerProbe {
+    async fn health_check(&self, server: &McpServerConfig) -> HealthRun {
+        let fake = scenarios_dir().join("fixtures").join("bin").join("fake-mcp-server.sh");
+        let server = McpServerConfig {
+            command: Some("sh".to_string()),
+            args: vec![fake.to_string_lossy().to_string()],
+            ..server.clone()
+        };
+        self.stdio.health_check(&server).await
+    }
+}
+
+/// Server entry for an analyzed config, with the env the config would be written with
+fn server_from_analysis(name: &str, config: &DetectedConfig) -> McpServerConfig {
+    let env = config
+        .to_mcp_entry()
+        .get("env")
+        .and_then(|env| env.as_object())
+        .map(|env| {
+            env.iter()
+                .map(|(key, value)| (key.clone(), value.as_str().unwrap_or_default().to_string()))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    McpServerConfig {
+        name: name.to_string(),
+        command: Some(config.command.clone()),
+        args: config.args.clone(),
+        env,
+        cwd: config.cwd.clone(),
+        inherit_env: config.inherit_env,
+        extra_options: HashMap::new(),
+        server_type: ServerType::Stdio,
+        metadata: ServerMetadata {
+            description: config.description.clone(),
+            version: config.version.clone(),
+            author: config.author.clone(),
+            capabilities: config.declared_capabilities.clone(),
+            enabled: true,
+            source: ConfigSource::MainConfig,
+        },
+    }
+}
+
+/// Point a profile's paths into the scenario root and turn off system-wide lookups
+fn rehome_profile(profile: &mut ApplicationProfile, root: &Path) {
+    let rehome = |path: &mut String| {
+        let rehomed = if let Some(rest) = path.strip_prefix("~/") {
+            root.join("home").join(rest)
+        } else if let Some(rest) = path.strip_prefix('/') {
+            root.join(rest)
+        } else {
+            return;
+        };
+        *path = rehomed.to_string_lossy().to_string();
+    };
+
+    // Fixtures lay out a macOS home, so every host reads the rehomed macOS paths
+    let mut config_path = profile.config_path.clone();
+    rehome(&mut config_path);
+    profile.set_config_path(config_path);
+    profile.alt_config_paths.iter_mut().for_each(rehome);
+    profile.executable_paths.iter_mut().for_each(rehome);
+    profile.alt_executable_paths.iter_mut().for_each(rehome);
+    profile.detection_strategy.use_bundle_lookup = false;
+    profile.detection_strategy.use_spotlight = false;
+}
+
+fn copy_dir(from: &Path, to: &Path) {
+    fs::create_dir_all(to).unwrap();
+    for entry in fs::read_dir(from).unwrap() {
+        let entry = entry.unwrap();
+        let target = to.join(entry.file_name());
+        if entry.file_type().unwrap().is_dir() {
+            copy_dir(&entry.path(), &target);
+        } else {
+            fs::copy(entry.path(), target).unwrap();
+        }
+    }
+}
+
+fn snapshot_dir(fs: &InMemoryFs, dir: &Path) {
+    fs.add_dir(dir);
+    for entry in std::fs::read_dir(dir).unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            snapshot_dir(fs, &path);
+        } else {
+            fs.add_file(&path, std::fs::read(&path).unwrap());
+        }
+    }
+}
+
+/// A local address nothing listens on, so every request fails like it would offline
+fn unreachable_url() -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    format!("http://{}", listener.local_addr().unwrap())
+}
+
+/// Serves canned JSON bodies by request path; unknown paths get a 404
+struct CannedHttp {
+    base_url: String,
+}
+
+impl CannedHttp {
+    fn serve(routes: HashMap<String, JsonValue>) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+
+        std::thread::spawn(move || {
+            for stream in listener.incoming() {
+                let Ok(mut stream) = stream else {
+                    continue;
+                };
+                let mut request = Vec::new();
+                let mut buf = [0u8; 4096];
+                while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                    match stream.read(&mut buf) {
+                        Ok(0) | Err(_) => break,
+                        Ok(n) => request.extend_from_slice(&buf[..n]),
+                    }
+                }
+
+                let request = String::from_utf8_lossy(&request);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+                let (status, body) = match routes.get(path) {
+                    Some(body) => ("200 OK", body.to_string()),
+                    None => ("404 Not Found", "{}".to_string()),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = stream.write_all(response.as_bytes());
+            }
+        });
+
+        Self { base_url }
+    }
+}
@@ -0,0 +1,95 @@
+//! Golden scenarios that drive the whole pipeline the way a user does.
+//!
+//! See `harness.rs` for the fixture layout; new scenarios only need a fixture directory,
+//! a test here, and `UPDATE_GOLDEN=1 cargo test --test scenarios` to record their golden files.
+//! Health checks launch a shell script as the fake server, so the suite is Unix-only.
+#![cfg(unix)]
+
+mod harness;
+
+use harness::Scenario;
+
+const CLAUDE_CONFIG: &str = "home/Library/Application Support/Claude/claude_desktop_config.json";
+const CURSOR_CONFIG: &str = "home/.cursor/mcp.json";
+
+// Analysis fetches the package from the harness's registry stub
+#[cfg(feature = "online")]
+#[tokio::test]
+async fn fresh_machine() {
+    let mut scenario = Scenario::load("fresh-machine");
+
+    assert_eq!(scenario.detect().await, vec!["claude-desktop"]);
+    scenario.apply_suggested_fixes("claude-desktop").await;
+
+    let weather = scenario.analyze("@acme/weather-mcp").await.expect("package analyzed");
+    scenario.add_server("claude-desktop", "weather", &weather).await;
+
+    let health = scenario.health_check("claude-desktop").await;
+    assert_eq!(health.len(), 1);
+    assert!(health[0].1.healthy);
+
+    scenario.assert_golden(&[CLAUDE_CONFIG]);
+}
+
+// Analysis fetches the package from the harness's registry stub
+#[cfg(feature = "online")]
+#[tokio::test]
+async fn machine_with_existing_hand_made_configs() {
+    let mut scenario = Scenario::load("existing-configs");
+
+    assert_eq!(scenario.detect().await, vec!["claude-desktop", "cursor"]);
+
+    let browser = scenario.analyze("@acme/browser-mcp").await.expect("package analyzed");
+    assert_eq!(browser.peer_dependencies, vec!["playwright@>=1.40"]);
+    scenario.add_server("claude-desktop", "browser", &browser).await;
+
+    let servers = scenario.scan("claude-desktop").await.mcp_servers;
+    assert_eq!(servers.len(), 3);
+
+    scenario.assert_golden(&[CLAUDE_CONFIG, CURSOR_CONFIG]);
+}
+
+#[tokio::test]
+async fn corrupted_config_recovery() {
+    let mut scenario = Scenario::load("corrupted-config-recovery");
+
+    assert_eq!(scenario.detect().await, vec!["claude-desktop", "cursor"]);
+    assert!(scenario.scan("claude-desktop").await.mcp_servers.is_empty());
+
+    scenario.apply_suggested_fixes("cursor").await;
+    let health = scenario.health_check("cursor").await;
+    let names: Vec<&str> = health.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, vec!["fetch", "github"]);
+    assert!(health.iter().all(|(_, run)| run.healthy));
+
+    // Unparseable files are reported, never rewritten
+    scenario.assert_golden(&[CURSOR_CONFIG, CLAUDE_CONFIG]);
+}
+
+#[tokio::test]
+async fn offline_mode() {
+    let mut scenario = Scenario::load("offline");
+
+    assert_eq!(scenario.detect().await, vec!["claude-desktop"]);
+    assert!(scenario.analyze("@acme/notes-mcp").await.is_none());
+
+    let notes = scenario.analyze_path("home/src/notes-mcp").await;
+    scenario.add_server("claude-desktop", "notes", &notes).await;
+    assert!(scenario.health_check("claude-desktop").await[0].1.healthy);
+
+    scenario.assert_golden(&[CLAUDE_CONFIG]);
+}
+
+#[tokio::test]
+async fn sync_between_two_apps() {
+    let mut scenario = Scenario::load("sync-two-apps");
+
+    assert_eq!(scenario.detect().await, vec!["claude-desktop", "cursor"]);
+    scenario.sync_between("claude-desktop", "cursor").await;
+
+    let health = scenario.health_check("cursor").await;
+    let failed: Vec<&str> = health.iter().filter(|(_, run)| !run.healthy).map(|(name, _)| name.as_str()).collect();
+    assert_eq!(failed, vec!["memory"]);
+
+    scenario.assert_golden(&[CLAUDE_CONFIG, CURSOR_CONFIG]);
+}
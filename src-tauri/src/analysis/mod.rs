@@ -1,9 +0,0 @@
-pub mod server_analyzer;
-pub mod package_parser;
-pub mod readme_parser;
-pub mod schema_detector;
-
-pub use server_analyzer::{ServerAnalyzer, AnalysisResult, DetectedConfig};
-pub use package_parser::PackageParser;
-pub use readme_parser::ReadmeParser;
-pub use schema_detector::SchemaDetector;
@@ -1,8 +1,19 @@
+use std::collections::HashMap;
 use anyhow::Result;
 use clap::{Parser, Subcommand};
 
-use crate::configuration::ConfigurationEngine;
-use crate::detection::{ApplicationDetector, ConfigValidator};
+use crate::adapters::ApplicationAdapter;
+use crate::adapters::generic::GenericAdapter;
+use crate::configuration::{default_backup_dir, ConfigurationEngine, ResolutionOutcome, SyncManager};
+use crate::configuration::annotations::AnnotationStore;
+use crate::configuration::cursor_migration::{classify_install, migrate_legacy_servers};
+use crate::configuration::manifest::{ManifestLocker, TeamManifest};
+use crate::configuration::provenance::{managed_status, ManagedStatus, ProvenanceSidecar};
+use crate::configuration::quarantine::{RestoreCondition, ServerAudit};
+use crate::configuration::resolution::{PendingResolutionStore, Resolution};
+use crate::configuration::home_paths::{HomePathAction, HomeRelocator};
+use crate::detection::{is_metadata_key, ApplicationDetector, ApplicationProfile, ConfigValidator, McpServerConfig};
+use crate::filesystem::ConfigFileService;
 use crate::server::ServerManager;
 
 /// MCP Control Lite - Basic CLI for testing backend functionality
@@ -45,6 +56,37 @@ pub enum Commands {
     ImportFrom { app_name: String },
     /// Export configs TO an application from central store (use 'list-apps' to see available apps)
     ExportTo { app_name: String },
+    /// Import a team manifest into an application, asking before replacing servers that differ
+    ImportManifest {
+        manifest: std::path::PathBuf,
+        app_name: String,
+        /// Lock to the registries' current releases and save the manifest before importing
+        #[arg(long)]
+        relock: bool,
+    },
+    /// Write the central store's servers to a team manifest
+    ExportManifest {
+        manifest: std::path::PathBuf,
+        /// Pin every server to its exact published release
+        #[arg(long)]
+        lock: bool,
+    },
+    /// Finish an export or import that stopped for conflicts, e.g. `claude-desktop:github=take-incoming`
+    Resolve {
+        app_name: String,
+        token: String,
+        /// `<conflict-id>=keep-local|take-incoming|merge-fields|rename-incoming:<name>`
+        choices: Vec<String>,
+    },
+    /// Audit an application's servers and switch off any that look compromised
+    Quarantine { app_name: String },
+    /// Put a quarantined server back once it is fixed, or regardless with `--acknowledge`
+    Unquarantine {
+        server_name: String,
+        app_name: String,
+        #[arg(long)]
+        acknowledge: bool,
+    },
     /// Show central store status
     StoreStatus,
     /// Browse available MCP servers
@@ -69,6 +111,19 @@ pub enum Commands {
     Version,
     /// List available applications for import/export
     ListApps,
+    /// Analyze a package, path, or URL and show the ways it can be run
+    Analyze { source: String, alternative: Option<String> },
+    /// Print which clients support which features, as JSON or a markdown table
+    CompatibilityMatrix {
+        #[arg(long)]
+        markdown: bool,
+    },
+    /// Validate every server config JSON file under a directory, failing if any is invalid
+    ValidateDir { dir: std::path::PathBuf },
+    /// Convert an application's legacy config to the format its installed version reads
+    ConvertConfig { app_name: String },
+    /// Move Cursor servers from legacy settings.json files into ~/.cursor/mcp.json
+    MigrateCursor,
 }
 
 pub async fn run_cli() -> Result<()> {
@@ -89,6 +144,13 @@ pub async fn run_cli() -> Result<()> {
         Commands::TestAmazonQ => test_amazon_q().await,
         Commands::ImportFrom { app_name } => import_from_app(&app_name).await,
         Commands::ExportTo { app_name } => export_to_app(&app_name).await,
+        Commands::ImportManifest { manifest, app_name, relock } => import_manifest(&manifest, &app_name, relock).await,
+        Commands::ExportManifest { manifest, lock } => export_manifest(&manifest, lock).await,
+        Commands::Resolve { app_name, token, choices } => resolve_conflicts(&app_name, &token, &choices).await,
+        Commands::Quarantine { app_name } => quarantine_servers(&app_name).await,
+        Commands::Unquarantine { server_name, app_name, acknowledge } => {
+            unquarantine_server(&server_name, &app_name, acknowledge).await
+        }
         Commands::StoreStatus => store_status().await,
         Commands::Browse { category } => browse_servers(category.as_deref()).await,
         Commands::Install { server_name, app_name } => install_server(&server_name, app_name.as_deref()).await,
@@ -101,9 +163,161 @@ pub async fn run_cli() -> Result<()> {
         Commands::Status => show_status().await,
         Commands::Version => show_version().await,
         Commands::ListApps => list_apps().await,
+        Commands::Analyze { source, alternative } => analyze_source(&source, alternative.as_deref()).await,
+        Commands::CompatibilityMatrix { markdown } => compatibility_matrix(markdown),
+        Commands::ValidateDir { dir } => validate_dir(&dir),
+        Commands::ConvertConfig { app_name } => convert_config(&app_name).await,
+        Commands::MigrateCursor => migrate_cursor().await,
     }
 }
 
+fn compatibility_matrix(markdown: bool) -> Result<()> {
+    use crate::detection::{generate_compatibility_matrix, ApplicationRegistry};
+
+    // The loaded registry, so profiles added in applications.json are listed too
+    let matrix = generate_compatibility_matrix(&ApplicationRegistry::with_auto_load());
+    if markdown {
+        print!("{}", matrix.to_markdown());
+    } else {
+        print!("{}", matrix.to_json()?);
+    }
+    Ok(())
+}
+
+fn validate_dir(dir: &std::path::Path) -> Result<()> {
+    use crate::analysis::SchemaDetector;
+
+    let reports = SchemaDetector::new().validate_dir(dir);
+    if reports.is_empty() {
+        println!("❌ No JSON files found under {}", dir.display());
+        return Ok(());
+    }
+
+    let mut invalid = 0;
+    for (path, report) in &reports {
+        if report.valid {
+            println!("✅ {} ({} server(s))", path.display(), report.servers);
+        } else {
+            invalid += 1;
+            println!("❌ {}", path.display());
+            for problem in &report.problems {
+                println!("     {}", problem);
+            }
+        }
+    }
+
+    if invalid > 0 {
+        anyhow::bail!("{} of {} config file(s) are invalid", invalid, reports.len());
+    }
+    Ok(())
+}
+
+async fn convert_config(app_name: &str) -> Result<()> {
+    use crate::configuration::format_migration::{convert_legacy_config, pending_conversion};
+    use crate::filesystem::{BackupService, RealFs};
+
+    let Some(profile) = find_detected_app(app_name).await? else {
+        return Ok(());
+    };
+    let Some(conversion) = pending_conversion(&profile, &RealFs).await? else {
+        println!("✅ {} config is already in the format its installed version reads", profile.name);
+        return Ok(());
+    };
+
+    println!(
+        "🔄 Converting {} from {:?} to {:?}...",
+        conversion.from_path.display(),
+        conversion.from_format,
+        conversion.to_format
+    );
+    let backups = BackupService::new(default_backup_dir()?, "mcpctl".to_string())?;
+    let report = convert_legacy_config(&conversion, &backups, &mut cli_file_service()?).await?;
+    println!("✅ Wrote {}", report.target.display());
+    if !report.not_carried.is_empty() {
+        println!("⚠️  Not carried over, kept only in the backup: {}", report.not_carried.join(", "));
+    }
+    println!("💾 Backup: {}", report.backup_path.display());
+    if let Some(tombstone) = report.tombstone {
+        println!("📝 Left a note at {}", tombstone.display());
+    }
+    Ok(())
+}
+
+async fn migrate_cursor() -> Result<()> {
+    use crate::filesystem::BackupService;
+
+    let Some(profile) = find_detected_app("cursor").await? else {
+        return Ok(());
+    };
+    if !classify_install(&profile)?.needs_migration() {
+        println!("✅ Cursor servers are already in {}", profile.resolved_config_path().display());
+        return Ok(());
+    }
+
+    let backups = BackupService::new(default_backup_dir()?, "mcpctl".to_string())?;
+    let report = migrate_legacy_servers(&profile, &backups)?;
+    println!("✅ Moved {} server(s) into {}", report.migrated.len(), report.target.display());
+    for name in &report.migrated {
+        println!("  - {}", name);
+    }
+    for name in &report.conflicts {
+        println!("  ⚠️  {} differs from the entry in {}; left in settings.json", name, report.target.display());
+    }
+    for backup in &report.backups {
+        println!("💾 Backup: {}", backup.display());
+    }
+    Ok(())
+}
+
+async fn analyze_source(source: &str, alternative: Option<&str>) -> Result<()> {
+    use crate::analysis::{recommend_detected_targets, select_primary, ServerAnalyzer, TargetAction};
+
+    println!("🔍 Analyzing {}...", source);
+
+    let mut result = ServerAnalyzer::new().analyze_package(source).await?;
+    // An explicit choice wins; otherwise a server added earlier stays on its alternative
+    let annotations = AnnotationStore::load_default()?;
+    let pinned = alternative.or_else(|| annotations.pinned_alternative(&result.config.name)).map(String::from);
+    select_primary(&mut result.config, pinned.as_deref());
+    let config = &result.config;
+
+    println!("📦 {} (confidence {:.0}%)", config.name, result.confidence * 100.0);
+    println!("   ▶ {} {}", config.command, config.args.join(" "));
+
+    if !config.alternatives.is_empty() {
+        println!("\n🔀 Alternatives:");
+        for alt in &config.alternatives {
+            let marker = if config.selected_alternative.as_deref() == Some(alt.id.as_str()) { "✓" } else { " " };
+            println!("  {} {} - {}", marker, alt.id, alt.label);
+            println!("      {} {}", alt.config.command, alt.config.args.join(" "));
+            if !alt.trade_offs.is_empty() {
+                let codes: Vec<String> = alt.trade_offs.iter()
+                    .map(|code| serde_json::to_value(code).ok().and_then(|v| v.as_str().map(String::from)).unwrap_or_default())
+                    .collect();
+                println!("      trade-offs: {}", codes.join(", "));
+            }
+        }
+    }
+
+    let targets = recommend_detected_targets(config).await?;
+    if !targets.is_empty() {
+        println!("\n🎯 Where to add it:");
+        for target in &targets {
+            let action = match &target.action {
+                TargetAction::Add => "add".to_string(),
+                TargetAction::Update { existing_name } => format!("update '{}'", existing_name),
+            };
+            let marker = if target.compatible { "✅" } else { "❌" };
+            println!("  {} {} ({})", marker, target.app_name, action);
+            for problem in target.problems() {
+                println!("      {}", problem);
+            }
+        }
+    }
+
+    Ok(())
+}
+
 async fn detect_apps() -> Result<()> {
     println!("🔍 Detecting MCP-enabled applications...");
     
@@ -128,6 +342,13 @@ async fn detect_apps() -> Result<()> {
         if let Some(exe_path) = &result.found_paths.executable {
             println!("     Found Executable: {}", exe_path.display());
         }
+        if result.detected && result.profile.id == "cursor" {
+            if let Ok(kind) = classify_install(&result.profile) {
+                if kind.needs_migration() {
+                    println!("     💡 Servers in legacy settings.json; run 'mcpctl migrate-cursor' to move them");
+                }
+            }
+        }
         
         if !result.messages.is_empty() {
             for msg in &result.messages {
@@ -145,6 +366,7 @@ async fn list_servers() -> Result<()> {
     
     let mut detector = ApplicationDetector::new()?;
     let results = detector.detect_all_applications().await?;
+    let sidecar = ProvenanceSidecar::load_default()?;
     
     for result in &results {
         if result.detected {
@@ -156,10 +378,10 @@ async fn list_servers() -> Result<()> {
                         match serde_json::from_str::<serde_json::Value>(&content) {
                             Ok(config) => {
                                 if let Some(servers) = config.get("mcpServers").and_then(|s| s.as_object()) {
-                                    if servers.is_empty() {
+                                    if servers.keys().all(|name| is_metadata_key(name)) {
                                         println!("   📭 No servers configured");
                                     } else {
-                                        for (name, server_config) in servers {
+                                        for (name, server_config) in servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
                                             let is_disabled = server_config.get("disabled")
                                                 .and_then(|d| d.as_bool())
                                                 .unwrap_or(false);
@@ -172,6 +394,12 @@ async fn list_servers() -> Result<()> {
                                                 println!("      💻 Command: {}", command);
                                             }
                                             
+                                            match managed_status(&result.profile, name, server_config, &sidecar) {
+                                                ManagedStatus::Managed => println!("      🏷️  Added by MCP Control"),
+                                                ManagedStatus::ModifiedExternally => println!("      ✏️  Added by MCP Control, edited by hand since"),
+                                                ManagedStatus::Unmanaged => {}
+                                            }
+                                            
                                             if is_disabled {
                                                 println!("      💡 Enable: mcpctl enable {}", name);
                                             } else {
@@ -224,6 +452,7 @@ async fn validate_config() -> Result<()> {
     let mut detector = ApplicationDetector::new()?;
     let results = detector.detect_all_applications().await?;
     let validator = ConfigValidator::new()?;
+    let mut validations = Vec::new();
     
     for result in results {
         println!("📱 Validating {}...", result.profile.name);
@@ -240,6 +469,19 @@ async fn validate_config() -> Result<()> {
                                 for server in &validation_result.mcp_servers {
                                     println!("    - {}", server.name);
                                 }
+
+                                // Flag paths that point into other users' home directories
+                                if let Some(relocator) = HomeRelocator::new() {
+                                    for server in &validation_result.mcp_servers {
+                                        for finding in relocator.scan_server(server) {
+                                            let icon = match finding.action {
+                                                HomePathAction::WorldReadable => "🚨",
+                                                _ => "⚠️ ",
+                                            };
+                                            println!("  {} {}", icon, finding);
+                                        }
+                                    }
+                                }
                             }
                         } else {
                             println!("  ❌ Configuration has issues:");
@@ -247,6 +489,7 @@ async fn validate_config() -> Result<()> {
                                 println!("    📝 {}: {}", msg.level, msg.message);
                             }
                         }
+                        validations.push(validation_result);
                     }
                     Err(e) => {
                         println!("  ❌ Validation failed: {}", e);
@@ -264,6 +507,16 @@ async fn validate_config() -> Result<()> {
         println!();
     }
     
+    let summary = validator.get_validation_summary(&validations).with_quarantined(&AnnotationStore::load_default()?);
+    if !summary.quarantined.is_empty() {
+        println!("🔒 Quarantined servers:");
+        for entry in &summary.quarantined {
+            let reasons: Vec<String> = entry.reasons.iter().map(ToString::to_string).collect();
+            println!("  - {} in {}: {}", entry.server, entry.app_id, reasons.join("; "));
+        }
+        println!("💡 Use 'mcpctl unquarantine <server> <app>' once fixed");
+    }
+    
     Ok(())
 }
 async fn discover_servers() -> Result<()> {
@@ -450,8 +703,9 @@ async fn test_amazon_q() -> Result<()> {
         let parsed: serde_json::Value = serde_json::from_str(&content)?;
         
         if let Some(servers) = parsed.get("mcpServers").and_then(|s| s.as_object()) {
-            println!("🔧 Found {} MCP servers:", servers.len());
-            for (name, _config) in servers {
+            let names: Vec<&String> = servers.keys().filter(|name| !is_metadata_key(name)).collect();
+            println!("🔧 Found {} MCP servers:", names.len());
+            for name in names {
                 println!("  - {}", name);
             }
         }
@@ -606,7 +860,11 @@ async fn import_from_app(app_name: &str) -> Result<()> {
                     
                     // Extract servers (basic extraction for Amazon Q)
                     if let Some(mcp_servers) = config.get("mcpServers").and_then(|v| v.as_object()) {
-                        println!("✅ Found {} MCP servers in {}", mcp_servers.len(), result.profile.name);
+                        println!(
+                            "✅ Found {} MCP servers in {}",
+                            mcp_servers.keys().filter(|name| !is_metadata_key(name)).count(),
+                            result.profile.name
+                        );
                         
                         // Create central store file
                         let store_path = dirs::home_dir()
@@ -622,7 +880,8 @@ async fn import_from_app(app_name: &str) -> Result<()> {
                         });
                         
                         // Convert each server to central store format - PRESERVE ALL DATA
-                        for (name, server_config) in mcp_servers {
+                        let mut imported = 0;
+                        for (name, server_config) in mcp_servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
                             println!("  📦 Importing: {} (preserving all config data)", name);
                             
                             // Store the COMPLETE server config without any filtering
@@ -634,13 +893,14 @@ async fn import_from_app(app_name: &str) -> Result<()> {
                                 "imported_at": chrono::Utc::now().to_rfc3339(),
                                 "enabled": true
                             });
+                            imported += 1;
                         }
                         
                         // Write central store
                         let store_content = serde_json::to_string_pretty(&central_store)?;
                         tokio::fs::write(&store_path, store_content).await?;
                         
-                        println!("✅ Successfully imported {} servers to central store", mcp_servers.len());
+                        println!("✅ Successfully imported {} servers to central store", imported);
                         println!("📁 Central store: {}", store_path.display());
                         return Ok(());
                     } else {
@@ -673,82 +933,231 @@ async fn import_from_app(app_name: &str) -> Result<()> {
 async fn export_to_app(app_name: &str) -> Result<()> {
     println!("📤 Exporting MCP servers TO {} from central store...", app_name);
     
-    // Check if central store exists
+    let Some(servers) = central_store_servers().await? else {
+        println!("❌ Central store not found. Run 'import-from' first.");
+        return Ok(());
+    };
+    println!("📊 Central store has {} servers", servers.len());
+    
+    let Some(profile) = find_detected_app(app_name).await? else {
+        return Ok(());
+    };
+    
+    let mut file_service = cli_file_service()?;
+    let mut pending = PendingResolutionStore::load_default()?;
+    let outcome = SyncManager::new()
+        .sync_with_resolution(&profile, &servers, &mut file_service, &mut pending)
+        .await?;
+    print_resolution_outcome(&profile, outcome);
+    
+    Ok(())
+}
+
+async fn import_manifest(manifest_path: &std::path::Path, app_name: &str, relock: bool) -> Result<()> {
+    println!("📥 Importing team manifest {} into {}...", manifest_path.display(), app_name);
+    
+    let mut manifest = TeamManifest::from_json(&tokio::fs::read_to_string(manifest_path).await?)?;
+    let Some(profile) = find_detected_app(app_name).await? else {
+        return Ok(());
+    };
+    
+    let locker = ManifestLocker::new();
+    if relock {
+        locker.relock(&mut manifest).await?;
+        ConfigFileService::write_atomic(manifest_path, manifest.to_json()?)?;
+        println!("🔒 Relocked {}", manifest_path.display());
+    }
+    
+    let mut file_service = cli_file_service()?;
+    let mut pending = PendingResolutionStore::load_default()?;
+    let import = SyncManager::new()
+        .import_manifest(&profile, &manifest, &locker, &mut file_service, &mut pending)
+        .await?;
+    if !import.lock_report.issues.is_empty() {
+        print!("{}", import.lock_report);
+    }
+    match import.outcome {
+        Some(outcome) => print_resolution_outcome(&profile, outcome),
+        None => {
+            println!("❌ The manifest's lock no longer matches the registries; nothing was written");
+            println!("💡 Accept the current releases with: mcpctl import-manifest {} {} --relock",
+                manifest_path.display(), app_name);
+        }
+    }
+    
+    Ok(())
+}
+
+async fn export_manifest(manifest_path: &std::path::Path, lock: bool) -> Result<()> {
+    let Some(servers) = central_store_servers().await? else {
+        println!("❌ Central store not found. Run 'import-from' first.");
+        return Ok(());
+    };
+    
+    let manifest = ManifestLocker::new().export(&servers, lock).await?;
+    ConfigFileService::write_atomic(manifest_path, manifest.to_json()?)?;
+    println!("✅ Exported {} servers to {}{}", servers.len(), manifest_path.display(),
+        if lock { " (locked)" } else { "" });
+    
+    Ok(())
+}
+
+async fn resolve_conflicts(app_name: &str, token: &str, choices: &[String]) -> Result<()> {
+    let choices = choices
+        .iter()
+        .map(|choice| parse_resolution_choice(choice))
+        .collect::<Result<HashMap<_, _>>>()?;
+    let Some(profile) = find_detected_app(app_name).await? else {
+        return Ok(());
+    };
+    
+    let mut file_service = cli_file_service()?;
+    let mut pending = PendingResolutionStore::load_default()?;
+    let result = SyncManager::new()
+        .resume_resolution(&profile, token, &choices, &mut file_service, &mut pending)
+        .await?;
+    print_resolution_outcome(&profile, ResolutionOutcome::Applied(result));
+    
+    Ok(())
+}
+
+async fn quarantine_servers(app_name: &str) -> Result<()> {
+    println!("🔒 Auditing servers in {}...", app_name);
+    let Some(profile) = find_detected_app(app_name).await? else {
+        return Ok(());
+    };
+
+    let mut file_service = cli_file_service()?;
+    let mut annotations = AnnotationStore::load_default()?;
+    let quarantined = SyncManager::new()
+        .quarantine_application_servers(&profile, &ServerAudit::new(), &mut annotations, &mut file_service)
+        .await?;
+    annotations.save()?;
+
+    if quarantined.is_empty() {
+        println!("✅ No risky servers found in {}", profile.name);
+    }
+    for (server, reasons) in &quarantined {
+        println!("🚨 Quarantined '{}':", server);
+        for reason in reasons {
+            println!("    - {}", reason);
+        }
+    }
+    Ok(())
+}
+
+async fn unquarantine_server(server_name: &str, app_name: &str, acknowledge: bool) -> Result<()> {
+    let Some(profile) = find_detected_app(app_name).await? else {
+        return Ok(());
+    };
+
+    let audit = ServerAudit::new();
+    let condition = if acknowledge { RestoreCondition::Acknowledged } else { RestoreCondition::Fixed(&audit) };
+    let mut file_service = cli_file_service()?;
+    let mut annotations = AnnotationStore::load_default()?;
+    SyncManager::new()
+        .restore_quarantined_server(&profile, server_name, condition, &mut annotations, &mut file_service)
+        .await?;
+    annotations.save()?;
+
+    println!("✅ Restored '{}' in {}", server_name, profile.name);
+    Ok(())
+}
+
+/// Servers in the central store written by `import-from`, or `None` if there is no store yet
+pub async fn central_store_servers() -> Result<Option<Vec<McpServerConfig>>> {
     let store_path = dirs::home_dir()
         .ok_or_else(|| anyhow::anyhow!("Could not find home directory"))?
         .join(".mcp-control-store.json");
-    
     if !store_path.exists() {
-        println!("❌ Central store not found. Run 'import-from' first.");
-        return Ok(());
+        return Ok(None);
     }
     
-    // Read central store
-    let store_content = tokio::fs::read_to_string(&store_path).await?;
-    let central_store: serde_json::Value = serde_json::from_str(&store_content)?;
-    
+    let central_store: serde_json::Value = serde_json::from_str(&tokio::fs::read_to_string(&store_path).await?)?;
     let servers = central_store.get("servers").and_then(|v| v.as_object())
         .ok_or_else(|| anyhow::anyhow!("Invalid central store format"))?;
     
-    println!("📊 Central store has {} servers", servers.len());
-    
-    // Find target application
+    // Entries keep the complete original config, in the mcpServers shape
+    let configs: serde_json::Map<String, serde_json::Value> = servers.iter()
+        .filter_map(|(name, data)| data.get("config").map(|config| (name.clone(), config.clone())))
+        .collect();
+    let extracted = GenericAdapter::new()
+        .extract_server_configs(&serde_json::json!({ "mcpServers": configs }))
+        .await?;
+    Ok(Some(extracted.servers))
+}
+
+/// Detected application whose name or id contains `app_name`, listing the detected ones otherwise
+async fn find_detected_app(app_name: &str) -> Result<Option<ApplicationProfile>> {
     let mut detector = ApplicationDetector::new()?;
     let results = detector.detect_all_applications().await?;
     
-    for result in &results {
-        if result.profile.name.to_lowercase().contains(&app_name.to_lowercase()) || 
-           result.profile.id.to_lowercase().contains(&app_name.to_lowercase()) {
-            
-            if result.detected {
-                if let Some(config_path) = &result.found_paths.config_file {
-                    println!("🔍 Found {} config at: {}", result.profile.name, config_path.display());
-                    
-                    // Read current config
-                    let current_content = tokio::fs::read_to_string(config_path).await?;
-                    let mut current_config: serde_json::Value = serde_json::from_str(&current_content)?;
-                    
-                    // Create/update mcpServers section
-                    let mut mcp_servers = serde_json::Map::new();
-                    
-                    // Add servers from central store
-                    for (name, server_data) in servers {
-                        if let Some(config) = server_data.get("config") {
-                            mcp_servers.insert(name.clone(), config.clone());
-                            println!("  📦 Adding: {}", name);
-                        }
-                    }
-                    
-                    current_config["mcpServers"] = serde_json::Value::Object(mcp_servers);
-                    
-                    // Write updated config
-                    let updated_content = serde_json::to_string_pretty(&current_config)?;
-                    tokio::fs::write(config_path, updated_content).await?;
-                    
-                    println!("✅ Successfully exported {} servers to {}", servers.len(), result.profile.name);
-                    return Ok(());
-                } else {
-                    println!("❌ Config file not found for {}", result.profile.name);
-                    return Ok(());
-                }
-            } else {
-                println!("❌ {} not detected on this system", result.profile.name);
-                return Ok(());
-            }
+    let found = results.iter().find(|result| {
+        result.profile.name.to_lowercase().contains(&app_name.to_lowercase()) ||
+            result.profile.id.to_lowercase().contains(&app_name.to_lowercase())
+    });
+    match found {
+        Some(result) if result.detected => Ok(Some(result.profile.clone())),
+        Some(result) => {
+            println!("❌ {} not detected on this system", result.profile.name);
+            Ok(None)
+        }
+        None => {
+            println!("❌ Application not found: {}", app_name);
+            println!("💡 Use 'mcpctl list-apps' to see all available applications");
+            Ok(None)
         }
     }
-    
-    println!("❌ Application not found: {}", app_name);
-    println!("💡 Available applications:");
-    for result in &results {
-        if result.detected {
-            println!("  - {} ({})", result.profile.name, result.profile.id);
+}
+
+/// File service for CLI writes; the sync manager takes the backup itself
+fn cli_file_service() -> Result<ConfigFileService> {
+    let mut file_service = ConfigFileService::new("mcpctl".to_string(), default_backup_dir()?);
+    file_service.set_auto_backup(false);
+    Ok(file_service)
+}
+
+/// Parse `<conflict-id>=<resolution>`; conflict ids contain a colon, so only the first `=` splits
+fn parse_resolution_choice(choice: &str) -> Result<(String, Resolution)> {
+    let (id, kind) = choice.split_once('=')
+        .ok_or_else(|| anyhow::anyhow!("Expected <conflict-id>=<resolution>, got '{}'", choice))?;
+    let resolution = match kind.split_once(':') {
+        Some(("rename-incoming", name)) if !name.is_empty() => Resolution::RenameIncoming { name: name.to_string() },
+        None if kind == "keep-local" => Resolution::KeepLocal,
+        None if kind == "take-incoming" => Resolution::TakeIncoming,
+        None if kind == "merge-fields" => Resolution::MergeFields,
+        _ => anyhow::bail!(
+            "Unknown resolution '{}'; use keep-local, take-incoming, merge-fields or rename-incoming:<name>",
+            kind
+        ),
+    };
+    Ok((id.to_string(), resolution))
+}
+
+fn print_resolution_outcome(profile: &ApplicationProfile, outcome: ResolutionOutcome) {
+    match outcome {
+        ResolutionOutcome::Applied(result) if result.success => {
+            println!("✅ Successfully synced {} servers to {}", result.servers_synced, profile.name);
+        }
+        ResolutionOutcome::Applied(result) => {
+            println!("❌ Failed to sync {}: {}", profile.name, result.errors.join("; "));
+        }
+        ResolutionOutcome::NeedsResolution(pending) => {
+            println!("⚠️  {} server(s) differ from the ones in {}; nothing was written:", pending.conflicts.len(), profile.name);
+            for conflict in &pending.conflicts {
+                println!("  - {}: {} has '{}', {} has '{}'",
+                    conflict.id,
+                    conflict.local_origin,
+                    conflict.local.command.as_deref().unwrap_or("-"),
+                    conflict.incoming_origin,
+                    conflict.incoming.command.as_deref().unwrap_or("-"));
+            }
+            println!("💡 Decide with: mcpctl resolve {} {} <conflict-id>=keep-local|take-incoming|merge-fields|rename-incoming:<name>...",
+                profile.id, pending.token);
         }
     }
-    println!("💡 Use 'mcpctl list-apps' to see all available applications");
-    
-    Ok(())
 }
+
 async fn store_status() -> Result<()> {
     println!("📊 Central Store Status");
     
@@ -1132,9 +1541,9 @@ async fn show_status() -> Result<()> {
                     Ok(content) => {
                         if let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) {
                             if let Some(servers) = config.get("mcpServers").and_then(|s| s.as_object()) {
-                                let app_total = servers.len();
-                                let app_enabled = servers.values()
-                                    .filter(|s| !s.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false))
+                                let app_total = servers.keys().filter(|name| !is_metadata_key(name)).count();
+                                let app_enabled = servers.iter()
+                                    .filter(|(name, s)| !is_metadata_key(name) && !s.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false))
                                     .count();
                                 let app_disabled = app_total - app_enabled;
                                 
@@ -1238,7 +1647,7 @@ async fn list_apps() -> Result<()> {
                     Ok(content) => {
                         if let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) {
                             if let Some(servers) = config.get("mcpServers").and_then(|s| s.as_object()) {
-                                println!("      📦 {} MCP servers configured", servers.len());
+                                println!("      📦 {} MCP servers configured", servers.keys().filter(|name| !is_metadata_key(name)).count());
                             } else {
                                 println!("      📦 No MCP servers configured");
                             }
@@ -1,7 +0,0 @@
-pub mod engine;
-pub mod store;
-pub mod sync;
-
-pub use engine::*;
-pub use store::*;
-pub use sync::*;
@@ -1,8 +1,4 @@
-pub mod models;
-pub mod filesystem;
-pub mod detection;
-pub mod configuration;
-pub mod adapters;
-pub mod server;
+// Core logic lives in mcp-control-core; re-exported so existing `mcpctl_lib::` paths keep working
+pub use mcp_control_core::*;
+
 pub mod cli;
-pub mod analysis;
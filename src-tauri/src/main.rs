@@ -5,41 +5,117 @@ use std::env;
 use tauri::{Manager, menu::{Menu, MenuItem}, tray::TrayIconBuilder, Emitter};
 
 // Import our CLI module for backend functionality
-use mcpctl_lib::detection::ApplicationDetector;
+use mcpctl_lib::detection::{is_metadata_key, ApplicationDetector, ApplicationProfile, ApplicationRegistry, ChangePlan, ConfigValidator, SuggestedAction, SuggestionEngine};
+use mcpctl_lib::daemon::{AppState, DaemonConfig};
+use mcpctl_lib::configuration::integrity::{check_default_data_dir, IntegrityReport};
+use mcpctl_lib::configuration::{default_backup_dir, ResolutionOutcome, SyncManager};
+use mcpctl_lib::configuration::annotations::AnnotationStore;
+use mcpctl_lib::configuration::comment_headers::CommentStamper;
+use mcpctl_lib::configuration::cursor_migration::{classify_install, migrate_legacy_servers};
+use mcpctl_lib::configuration::groups::GroupStore;
+use mcpctl_lib::configuration::manifest::{ManifestLocker, TeamManifest};
+use mcpctl_lib::configuration::provenance::{entry_hash, ManagedStatus, ProvenanceSidecar};
+use mcpctl_lib::configuration::quarantine::{QuarantinedServer, RestoreCondition, ServerAudit};
+use mcpctl_lib::configuration::resolution::{PendingResolutionStore, Resolution};
+use mcpctl_lib::configuration::undo::{ConfigMutation, UndoManager};
+use mcpctl_lib::filesystem::ConfigFileService;
+use mcpctl_lib::models::UserPreferences;
+
+/// What the startup self-check of our data directory repaired
+static SELF_CHECK: std::sync::OnceLock<IntegrityReport> = std::sync::OnceLock::new();
+
+/// Whether the user chose to write configs even when MDM or policy manages them
+static FORCE_MANAGED_WRITES: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+
+/// Undo history for config changes made during this session
+fn undo_manager() -> &'static tokio::sync::Mutex<UndoManager> {
+    static UNDO: std::sync::OnceLock<tokio::sync::Mutex<UndoManager>> = std::sync::OnceLock::new();
+    UNDO.get_or_init(|| tokio::sync::Mutex::new(UndoManager::new()))
+}
+
+/// Remember a config change so it can be undone; failing to record never fails the change itself
+async fn record_mutation(profile: &ApplicationProfile, config_path: &std::path::Path, mutation: ConfigMutation) {
+    // The keys as written, so a flat settings key such as `"chat.mcp.servers"` stays one key
+    let servers_path = std::fs::read_to_string(config_path)
+        .ok()
+        .and_then(|content| mcpctl_lib::filesystem::jsonc::parse_jsonc(&content).ok())
+        .map(|config| profile.servers_path().keys_in(&config))
+        .unwrap_or_else(|| profile.get_mcp_servers_path());
+    let mut manager = undo_manager().lock().await;
+    if let Err(e) = manager.record(&profile.id, config_path, &servers_path, mutation) {
+        log::warn!("Could not record change for undo: {}", e);
+    }
+}
+
+/// File service for config writes made by commands; callers take the backup themselves
+fn config_file_service() -> Result<ConfigFileService, String> {
+    let backup_dir = default_backup_dir().map_err(|e| e.to_string())?;
+    let mut service = ConfigFileService::new("mcp-control".to_string(), backup_dir);
+    service.set_auto_backup(false);
+    service.set_force_managed_writes(FORCE_MANAGED_WRITES.load(std::sync::atomic::Ordering::Relaxed));
+    Ok(service)
+}
+
+/// Sync manager for writes from the app, stamping comments as the settings ask
+async fn sync_manager() -> SyncManager {
+    let mut preferences = UserPreferences::default();
+    let settings = get_settings().await.unwrap_or_default();
+    if let Some(enabled) = settings.get("stampConfigComments").and_then(|v| v.as_bool()) {
+        preferences.stamp_config_comments = enabled;
+    }
+    SyncManager::new().with_comment_stamper(CommentStamper::from_preferences(&preferences))
+}
+
+/// Write an application's config with a backup, refusing externally managed configs unless forced
+async fn write_app_config(profile: &ApplicationProfile, config_path: &std::path::Path, config: &serde_json::Value) -> Result<(), String> {
+    let mut file_service = config_file_service()?;
+    sync_manager().await
+        .write_config_at(profile, config_path, config, &mut file_service)
+        .await
+        .map_err(|e| format!("Failed to write config: {}", e))
+}
+
+#[tauri::command]
+fn set_force_managed_writes(enabled: bool) {
+    FORCE_MANAGED_WRITES.store(enabled, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[tauri::command]
+fn get_self_check_report() -> IntegrityReport {
+    SELF_CHECK.get().cloned().unwrap_or_default()
+}
 
 #[tauri::command]
 async fn get_servers() -> Result<Vec<serde_json::Value>, String> {
     let mut detector = ApplicationDetector::new().map_err(|e| e.to_string())?;
     let results = detector.detect_all_applications().await.map_err(|e| e.to_string())?;
+    let validator = ConfigValidator::new().map_err(|e| e.to_string())?;
+    let sidecar = ProvenanceSidecar::load_default().map_err(|e| e.to_string())?;
     
     let mut servers = Vec::new();
     
-    for result in &results {
-        if result.detected {
-            if let Some(config_path) = &result.found_paths.config_file {
-                match tokio::fs::read_to_string(config_path).await {
-                    Ok(content) => {
-                        if let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) {
-                            if let Some(mcp_servers) = config.get("mcpServers").and_then(|s| s.as_object()) {
-                                for (name, server_config) in mcp_servers {
-                                    let is_disabled = server_config.get("disabled")
-                                        .and_then(|d| d.as_bool())
-                                        .unwrap_or(false);
-                                    
-                                    servers.push(serde_json::json!({
-                                        "name": name,
-                                        "enabled": !is_disabled,
-                                        "application": result.profile.name,
-                                        "command": server_config.get("command"),
-                                        "args": server_config.get("args")
-                                    }));
-                                }
-                            }
-                        }
-                    }
-                    Err(_) => continue,
-                }
-            }
+    for result in results.iter().filter(|result| result.detected) {
+        // Servers merged across the primary and alternate config files
+        let effective = match validator.read_effective_servers(&result.profile).await {
+            Ok(effective) => effective,
+            Err(_) => continue,
+        };
+        // Provenance is tracked for the active config; servers from other files have none
+        let provenance: std::collections::HashMap<String, ManagedStatus> = validator
+            .read_managed_statuses(&result.profile, &sidecar)
+            .await
+            .map(|statuses| statuses.into_iter().collect())
+            .unwrap_or_default();
+        for server in &effective.servers {
+            servers.push(serde_json::json!({
+                "name": server.name,
+                "enabled": server.metadata.enabled,
+                "application": result.profile.name,
+                "command": server.command,
+                "args": server.args,
+                "source": effective.sources.get(&server.name),
+                "managed": provenance.get(&server.name),
+            }));
         }
     }
     
@@ -74,7 +150,7 @@ async fn get_applications() -> Result<Vec<serde_json::Value>, String> {
                 if let Ok(content) = tokio::fs::read_to_string(config_path).await {
                     if let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) {
                         if let Some(servers) = config.get("mcpServers").and_then(|s| s.as_object()) {
-                            server_count = servers.len();
+                            server_count = servers.keys().filter(|name| !is_metadata_key(name)).count();
                         }
                     }
                 }
@@ -94,7 +170,10 @@ async fn get_applications() -> Result<Vec<serde_json::Value>, String> {
             "configPath": result.found_paths.config_file.as_ref().map(|p| p.to_string_lossy()),
             "serverCount": server_count,
             "lastSync": last_sync,
-            "syncStatus": sync_status
+            "syncStatus": sync_status,
+            "formatConversion": result.format_conversion,
+            "needsMigration": result.detected && result.profile.id == "cursor"
+                && classify_install(&result.profile).is_ok_and(|kind| kind.needs_migration())
         }));
     }
     
@@ -103,34 +182,23 @@ async fn get_applications() -> Result<Vec<serde_json::Value>, String> {
 
 #[tauri::command]
 async fn toggle_server(server_name: String, application: String, enabled: bool) -> Result<(), String> {
-    let mut detector = ApplicationDetector::new().map_err(|e| e.to_string())?;
-    let results = detector.detect_all_applications().await.map_err(|e| e.to_string())?;
-    
-    for result in &results {
-        if result.profile.name == application && result.detected {
-            if let Some(config_path) = &result.found_paths.config_file {
-                let config_content = tokio::fs::read_to_string(config_path).await.map_err(|e| e.to_string())?;
-                let mut config: serde_json::Value = serde_json::from_str(&config_content).map_err(|e| e.to_string())?;
-                
-                if let Some(servers) = config.get_mut("mcpServers").and_then(|s| s.as_object_mut()) {
-                    if let Some(server_config) = servers.get_mut(&server_name).and_then(|s| s.as_object_mut()) {
-                        if enabled {
-                            server_config.remove("disabled");
-                        } else {
-                            server_config.insert("disabled".to_string(), serde_json::Value::Bool(true));
-                        }
-                        
-                        let updated_content = serde_json::to_string_pretty(&config).map_err(|e| e.to_string())?;
-                        tokio::fs::write(config_path, updated_content).await.map_err(|e| e.to_string())?;
-                        
-                        return Ok(());
-                    }
-                }
-            }
-        }
+    let profile = detected_profile(&application).await?;
+    let (config_path, mut config) = read_active_config(&profile).await?;
+    
+    let server_config = servers_mut(&profile, &mut config)
+        .and_then(|servers| servers.get_mut(&server_name))
+        .and_then(|s| s.as_object_mut())
+        .ok_or_else(|| format!("Server '{}' not found in {}", server_name, config_path.display()))?;
+    if enabled {
+        server_config.remove("disabled");
+    } else {
+        server_config.insert("disabled".to_string(), serde_json::Value::Bool(true));
     }
     
-    Err("Server or application not found".to_string())
+    write_app_config(&profile, &config_path, &config).await?;
+    record_mutation(&profile, &config_path, ConfigMutation::SetEnabled { name: server_name, enabled }).await;
+    
+    Ok(())
 }
 
 #[tauri::command]
@@ -150,9 +218,9 @@ async fn get_system_status() -> Result<serde_json::Value, String> {
                 if let Ok(content) = tokio::fs::read_to_string(config_path).await {
                     if let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) {
                         if let Some(servers) = config.get("mcpServers").and_then(|s| s.as_object()) {
-                            total_servers += servers.len();
-                            enabled_servers += servers.values()
-                                .filter(|s| !s.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false))
+                            total_servers += servers.keys().filter(|name| !is_metadata_key(name)).count();
+                            enabled_servers += servers.iter()
+                                .filter(|(name, s)| !is_metadata_key(name) && !s.get("disabled").and_then(|d| d.as_bool()).unwrap_or(false))
                                 .count();
                         }
                     }
@@ -195,6 +263,7 @@ async fn get_settings() -> Result<serde_json::Value, String> {
             "developerMode": false,
             "sourceOfTruth": "none",
             "autoSync": false,
+            "stampConfigComments": true,
             "enabledApps": {
                 "Claude Desktop": true,
                 "Cursor": true,
@@ -740,11 +809,7 @@ async fn add_server_to_config(package_name: &str) -> Result<(), String> {
                 }));
                 
                 // Write back to config
-                let updated_content = serde_json::to_string_pretty(&config)
-                    .map_err(|e| format!("Failed to serialize config: {}", e))?;
-                
-                tokio::fs::write(config_path, updated_content).await
-                    .map_err(|e| format!("Failed to write config: {}", e))?;
+                write_app_config(&result.profile, config_path, &config).await?;
                 
                 return Ok(());
             }
@@ -767,7 +832,7 @@ async fn get_installed_package_names() -> Result<std::collections::HashSet<Strin
                 if let Ok(content) = tokio::fs::read_to_string(config_path).await {
                     if let Ok(config) = serde_json::from_str::<serde_json::Value>(&content) {
                         if let Some(mcp_servers) = config.get("mcpServers").and_then(|s| s.as_object()) {
-                            for (_, server_config) in mcp_servers {
+                            for (_, server_config) in mcp_servers.iter().filter(|(name, _)| !is_metadata_key(name)) {
                                 // Extract package name from args if using npx
                                 if let Some(command) = server_config.get("command").and_then(|c| c.as_str()) {
                                     if command == "npx" {
@@ -799,55 +864,42 @@ async fn delete_server(server_name: String) -> Result<(), String> {
     
     let mut deleted_from_apps = Vec::new();
     
-    for result in &results {
-        if result.detected {
-            if let Some(config_path) = &result.found_paths.config_file {
-                
-                // Read existing config
-                if let Ok(content) = tokio::fs::read_to_string(config_path).await {
-                    if let Ok(mut config) = serde_json::from_str::<serde_json::Value>(&content) {
-                        if let Some(mcp_servers) = config.get_mut("mcpServers").and_then(|s| s.as_object_mut()) {
-                            
-                            // Remove servers with matching name (handle variations)
-                            let keys_to_remove: Vec<String> = mcp_servers.keys()
-                                .filter(|key| {
-                                    let matches = key.as_str() == &server_name || 
-                                        key.replace('_', "-") == server_name ||
-                                        key.replace('-', "_") == server_name ||
-                                        key.to_lowercase() == server_name.to_lowercase();
-                                    
-                                    if matches {
-                                        // Found matching key
-                                    }
-                                    matches
-                                })
-                                .cloned()
-                                .collect();
-                            
-                            for key in &keys_to_remove {
-                                mcp_servers.remove(key);
-                            }
-                            
-                            if !keys_to_remove.is_empty() {
-                                // Write back to config
-                                let updated_content = serde_json::to_string_pretty(&config)
-                                    .map_err(|e| format!("Failed to serialize config: {}", e))?;
-                                
-                                tokio::fs::write(config_path, updated_content).await
-                                    .map_err(|e| format!("Failed to write config: {}", e))?;
-                                
-                                deleted_from_apps.push(result.profile.name.clone());
-                            }
-                        } else {
-                            // No mcpServers found in config
-                        }
-                    } else {
-                        // Failed to parse config as JSON
-                    }
-                } else {
-                    // Failed to read config file
-                }
-            }
+    for result in results.iter().filter(|result| result.detected) {
+        let Some(config_path) = &result.found_paths.config_file else {
+            continue;
+        };
+        let profile = &result.profile;
+        let Ok(content) = tokio::fs::read_to_string(config_path).await else {
+            continue;
+        };
+        let Ok(mut config) = mcpctl_lib::filesystem::jsonc::parse_jsonc(&content) else {
+            continue;
+        };
+
+        // Remove servers with matching name (handle variations)
+        let matching: Vec<(String, serde_json::Value)> = profile.servers_path().get(&config)
+            .map(|servers| servers.iter()
+                .filter(|(key, _)| !is_metadata_key(key))
+                .filter(|(key, _)| {
+                    key.as_str() == server_name ||
+                        key.replace('_', "-") == server_name ||
+                        key.replace('-', "_") == server_name ||
+                        key.to_lowercase() == server_name.to_lowercase()
+                })
+                .map(|(key, entry)| (key.clone(), entry.clone()))
+                .collect())
+            .unwrap_or_default();
+
+        let removed: Vec<ConfigMutation> = matching.into_iter()
+            .filter(|(key, _)| profile.remove_server(&mut config, key))
+            .map(|(name, entry)| ConfigMutation::RemoveServer { name, entry })
+            .collect();
+
+        if !removed.is_empty() {
+            write_app_config(profile, config_path, &config).await?;
+            // One operation per config so a single undo restores every variation key
+            record_mutation(profile, config_path, ConfigMutation::Batch { mutations: removed }).await;
+            deleted_from_apps.push(profile.name.clone());
         }
     }
     
@@ -898,15 +950,44 @@ async fn create_server(application: String, config: serde_json::Value) -> Result
                     "env": config.get("env").unwrap_or(&serde_json::Value::Object(serde_json::Map::new()))
                 });
                 
+                // Refuse servers the application cannot run, with the reasons the ranking found
+                let scan = ConfigValidator::new()
+                    .map_err(|e| e.to_string())?
+                    .validate_application_config(&result.profile)
+                    .await
+                    .map_err(|e| e.to_string())?;
+                let detected = detected_config(server_name, &config);
+                if let Some(target) = mcpctl_lib::analysis::recommend_targets(&detected, &[scan]).pop() {
+                    if let Some(message) = target.incompatibility() {
+                        return Err(message);
+                    }
+                    if let mcpctl_lib::analysis::TargetAction::Update { existing_name } = &target.action {
+                        log::info!("{} already has this server as '{}'", application, existing_name);
+                    }
+                }
+                
                 // Add the server
-                mcp_servers.insert(server_name.to_string(), server_config);
+                mcp_servers.insert(server_name.to_string(), server_config.clone());
+                
+                // Record the entry as ours so the write stamps its provenance comment
+                let mut sidecar = ProvenanceSidecar::load_default().map_err(|e| e.to_string())?;
+                sidecar.record(&result.profile.id, server_name, &entry_hash(&server_config));
+                sidecar.save().map_err(|e| e.to_string())?;
                 
                 // Write back to config
-                let updated_content = serde_json::to_string_pretty(&app_config)
-                    .map_err(|e| format!("Failed to serialize config: {}", e))?;
+                write_app_config(&result.profile, config_path, &app_config).await?;
+                record_mutation(&result.profile, config_path, ConfigMutation::AddServer {
+                    name: server_name.to_string(),
+                    entry: server_config,
+                }).await;
                 
-                tokio::fs::write(config_path, updated_content).await
-                    .map_err(|e| format!("Failed to write config: {}", e))?;
+                // Remember which analysis alternative was chosen so update checks stay on it;
+                // only once the server is actually in the config
+                if let Some(alternative) = config.get("selected_alternative").and_then(|a| a.as_str()) {
+                    let mut annotations = AnnotationStore::load_default().map_err(|e| e.to_string())?;
+                    annotations.entry(server_name).alternative = Some(alternative.to_string());
+                    annotations.save().map_err(|e| e.to_string())?;
+                }
                 
                 return Ok(());
             }
@@ -916,6 +997,257 @@ async fn create_server(application: String, config: serde_json::Value) -> Result
     Err(format!("Application '{}' not found or not configured", application))
 }
 
+/// The server being created as the analyzer describes it; hand-written entries get a minimal one
+fn detected_config(name: &str, config: &serde_json::Value) -> mcpctl_lib::analysis::DetectedConfig {
+    serde_json::from_value(config.clone()).unwrap_or_else(|_| {
+        let command = config.get("command").and_then(|c| c.as_str()).unwrap_or("npx");
+        let mut detected = mcpctl_lib::analysis::ServerAnalyzer::basic_config(name, command);
+        detected.args = config.get("args")
+            .and_then(|a| serde_json::from_value(a.clone()).ok())
+            .unwrap_or_default();
+        detected.url = config.get("url").and_then(|u| u.as_str()).map(String::from);
+        detected
+    })
+}
+
+/// The config file writes to an application go to, and its current contents
+async fn read_active_config(profile: &ApplicationProfile) -> Result<(std::path::PathBuf, serde_json::Value), String> {
+    let config_path = ConfigValidator::new()
+        .map_err(|e| e.to_string())?
+        .resolve_active_config(profile)
+        .await
+        .map_err(|e| e.to_string())?;
+    let mut file_service = config_file_service()?;
+    let config = SyncManager::new()
+        .read_config_at(profile, &config_path, &mut file_service)
+        .await
+        .map_err(|e| format!("Failed to read config: {}", e))?;
+    Ok((config_path, config))
+}
+
+/// The servers object of an application's config, if it has one
+fn servers_mut<'a>(profile: &ApplicationProfile, config: &'a mut serde_json::Value) -> Option<&'a mut serde_json::Map<String, serde_json::Value>> {
+    profile.servers_path().get_mut(config)
+}
+
+/// Detected application with the given display name
+async fn detected_profile(application: &str) -> Result<ApplicationProfile, String> {
+    let mut detector = ApplicationDetector::new().map_err(|e| e.to_string())?;
+    let results = detector.detect_all_applications().await.map_err(|e| e.to_string())?;
+    results.into_iter()
+        .find(|result| result.profile.name == application && result.detected)
+        .map(|result| result.profile)
+        .ok_or_else(|| format!("Application '{}' not found or not configured", application))
+}
+
+fn resolution_outcome_json(outcome: ResolutionOutcome) -> serde_json::Value {
+    match outcome {
+        ResolutionOutcome::Applied(result) => serde_json::json!({
+            "status": "applied",
+            "success": result.success,
+            "serversSynced": result.servers_synced,
+            "errors": result.errors
+        }),
+        ResolutionOutcome::NeedsResolution(pending) => serde_json::json!({
+            "status": "needs_resolution",
+            "resolution": pending
+        }),
+    }
+}
+
+/// Sync the central store into an application; servers that differ wait for a decision
+#[tauri::command]
+async fn sync_with_resolution(application: String) -> Result<serde_json::Value, String> {
+    let servers = mcpctl_lib::cli::central_store_servers().await.map_err(|e| e.to_string())?
+        .ok_or("Central store not found; import from an application first")?;
+    let profile = detected_profile(&application).await?;
+    let mut file_service = config_file_service()?;
+    let mut pending = PendingResolutionStore::load_default().map_err(|e| e.to_string())?;
+    let outcome = sync_manager().await
+        .sync_with_resolution(&profile, &servers, &mut file_service, &mut pending)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(resolution_outcome_json(outcome))
+}
+
+/// Import a team manifest into an application; servers that differ wait for a decision.
+/// With `relock`, the manifest is first locked to the registries' current releases and saved.
+#[tauri::command]
+async fn import_manifest(application: String, manifest_path: String, relock: bool) -> Result<serde_json::Value, String> {
+    let content = tokio::fs::read_to_string(&manifest_path).await
+        .map_err(|e| format!("Failed to read manifest: {}", e))?;
+    let mut manifest = TeamManifest::from_json(&content).map_err(|e| e.to_string())?;
+    let profile = detected_profile(&application).await?;
+    let locker = ManifestLocker::new();
+    if relock {
+        locker.relock(&mut manifest).await.map_err(|e| e.to_string())?;
+        let json = manifest.to_json().map_err(|e| e.to_string())?;
+        ConfigFileService::write_atomic(std::path::Path::new(&manifest_path), json).map_err(|e| e.to_string())?;
+    }
+    let mut file_service = config_file_service()?;
+    let mut pending = PendingResolutionStore::load_default().map_err(|e| e.to_string())?;
+    let import = sync_manager().await
+        .import_manifest(&profile, &manifest, &locker, &mut file_service, &mut pending)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(match import.outcome {
+        Some(outcome) => {
+            let mut json = resolution_outcome_json(outcome);
+            json["lockReport"] = serde_json::json!(import.lock_report);
+            json
+        }
+        None => serde_json::json!({
+            "status": "lock_mismatch",
+            "lockReport": import.lock_report
+        }),
+    })
+}
+
+/// Write the central store's servers to a team manifest, pinned to exact releases when `lock` is set
+#[tauri::command]
+async fn export_manifest(manifest_path: String, lock: bool) -> Result<usize, String> {
+    let servers = mcpctl_lib::cli::central_store_servers().await.map_err(|e| e.to_string())?
+        .ok_or("Central store not found; import from an application first")?;
+    let manifest = ManifestLocker::new().export(&servers, lock).await.map_err(|e| e.to_string())?;
+    let json = manifest.to_json().map_err(|e| e.to_string())?;
+    ConfigFileService::write_atomic(std::path::Path::new(&manifest_path), json).map_err(|e| e.to_string())?;
+    Ok(servers.len())
+}
+
+/// Finish a sync or import once every conflict has a choice, keyed by conflict id
+#[tauri::command]
+async fn resume_resolution(
+    application: String,
+    token: String,
+    choices: std::collections::HashMap<String, Resolution>,
+) -> Result<serde_json::Value, String> {
+    let profile = detected_profile(&application).await?;
+    let mut file_service = config_file_service()?;
+    let mut pending = PendingResolutionStore::load_default().map_err(|e| e.to_string())?;
+    let result = sync_manager().await
+        .resume_resolution(&profile, &token, &choices, &mut file_service, &mut pending)
+        .await
+        .map_err(|e| e.to_string())?;
+    Ok(resolution_outcome_json(ResolutionOutcome::Applied(result)))
+}
+
+#[tauri::command]
+async fn undo_last(app_id: String) -> Result<Option<String>, String> {
+    let mut file_service = config_file_service()?;
+    let mut manager = undo_manager().lock().await;
+    let undone = manager.undo_last(&app_id, &mut file_service).await.map_err(|e| e.to_string())?;
+    Ok(undone.map(|mutation| format!("Undid {}", mutation.describe())))
+}
+
+#[tauri::command]
+async fn redo_last(app_id: String) -> Result<Option<String>, String> {
+    let mut file_service = config_file_service()?;
+    let mut manager = undo_manager().lock().await;
+    let redone = manager.redo_last(&app_id, &mut file_service).await.map_err(|e| e.to_string())?;
+    Ok(redone.map(|mutation| format!("Redid {}", mutation.describe())))
+}
+
+/// Work out the writes for a suggested fix; nothing changes until the plan is confirmed
+#[tauri::command]
+async fn plan_suggested_fix(app_id: String, action: SuggestedAction) -> Result<ChangePlan, String> {
+    let registry = ApplicationRegistry::new();
+    let profile = registry
+        .get_application(&app_id)
+        .ok_or_else(|| format!("Unknown application: {}", app_id))?;
+    SuggestionEngine::new().plan_fix(profile, &action).await.map_err(|e| e.to_string())
+}
+
+/// Apply a fix plan the user confirmed
+///
+/// The plan is worked out again from the application's profile, so only the files that
+/// profile resolves to are written; if it no longer matches what was confirmed, nothing is.
+#[tauri::command]
+async fn apply_change_plan(app_id: String, action: SuggestedAction, plan: ChangePlan) -> Result<(), String> {
+    let registry = ApplicationRegistry::new();
+    let profile = registry
+        .get_application(&app_id)
+        .ok_or_else(|| format!("Unknown application: {}", app_id))?;
+    let current = SuggestionEngine::new().plan_fix(profile, &action).await.map_err(|e| e.to_string())?;
+    if current != plan {
+        return Err("The fix no longer matches the reviewed plan; review it again".to_string());
+    }
+
+    current.apply(&config_file_service()?).await.map_err(|e| e.to_string())?;
+    log::info!("Applied fix: {}", current.description);
+    Ok(())
+}
+
+/// Audit an application's servers and switch off the ones that look compromised
+#[tauri::command]
+async fn quarantine_risky_servers(application: String) -> Result<Vec<serde_json::Value>, String> {
+    let profile = detected_profile(&application).await?;
+    let mut file_service = config_file_service()?;
+    let mut annotations = AnnotationStore::load_default().map_err(|e| e.to_string())?;
+    let quarantined = sync_manager().await
+        .quarantine_application_servers(&profile, &ServerAudit::new(), &mut annotations, &mut file_service)
+        .await
+        .map_err(|e| e.to_string())?;
+    annotations.save().map_err(|e| e.to_string())?;
+    
+    Ok(quarantined.into_iter().map(|(server, reasons)| serde_json::json!({
+        "server": server,
+        "reasons": reasons.iter().map(ToString::to_string).collect::<Vec<_>>(),
+    })).collect())
+}
+
+/// Servers switched off by quarantine, most recent first
+#[tauri::command]
+async fn get_quarantined_servers() -> Result<Vec<QuarantinedServer>, String> {
+    let annotations = AnnotationStore::load_default().map_err(|e| e.to_string())?;
+    Ok(annotations.quarantined_servers())
+}
+
+/// Put a quarantined server back once the audit passes, or regardless when acknowledged
+#[tauri::command]
+async fn restore_quarantined_server(application: String, server_name: String, acknowledged: bool) -> Result<(), String> {
+    let profile = detected_profile(&application).await?;
+    let audit = ServerAudit::new();
+    let condition = if acknowledged { RestoreCondition::Acknowledged } else { RestoreCondition::Fixed(&audit) };
+    let mut file_service = config_file_service()?;
+    let mut annotations = AnnotationStore::load_default().map_err(|e| e.to_string())?;
+    sync_manager().await
+        .restore_quarantined_server(&profile, &server_name, condition, &mut annotations, &mut file_service)
+        .await
+        .map_err(|e| e.to_string())?;
+    annotations.save().map_err(|e| e.to_string())
+}
+
+/// Convert an application's legacy config to the format its installed version reads
+#[tauri::command]
+async fn convert_legacy_config(application: String) -> Result<serde_json::Value, String> {
+    use mcpctl_lib::configuration::format_migration;
+
+    let profile = detected_profile(&application).await?;
+    let conversion = format_migration::pending_conversion(&profile, &mcpctl_lib::filesystem::RealFs)
+        .await
+        .map_err(|e| e.to_string())?
+        .ok_or_else(|| format!("{} config does not need converting", application))?;
+    let backup_dir = default_backup_dir().map_err(|e| e.to_string())?;
+    let backups = mcpctl_lib::filesystem::BackupService::new(backup_dir, "mcp-control".to_string())
+        .map_err(|e| e.to_string())?;
+    let mut file_service = config_file_service()?;
+    let report = format_migration::convert_legacy_config(&conversion, &backups, &mut file_service)
+        .await
+        .map_err(|e| e.to_string())?;
+    serde_json::to_value(report).map_err(|e| e.to_string())
+}
+
+/// Move Cursor servers from legacy settings.json files into ~/.cursor/mcp.json
+#[tauri::command]
+async fn migrate_cursor_servers() -> Result<serde_json::Value, String> {
+    let profile = detected_profile("Cursor").await?;
+    let backup_dir = default_backup_dir().map_err(|e| e.to_string())?;
+    let backups = mcpctl_lib::filesystem::BackupService::new(backup_dir, "mcp-control".to_string())
+        .map_err(|e| e.to_string())?;
+    let report = migrate_legacy_servers(&profile, &backups).map_err(|e| e.to_string())?;
+    serde_json::to_value(report).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn show_notification(title: String, body: String) -> Result<(), String> {
     // For now, just log the notification - can be enhanced with actual system notifications
@@ -934,47 +1266,53 @@ async fn sync_application(app_name: String) -> Result<(), String> {
 
 #[tauri::command]
 async fn save_server_config(server_id: String, application: String, config: serde_json::Value) -> Result<(), String> {
-    let mut detector = ApplicationDetector::new().map_err(|e| e.to_string())?;
-    let results = detector.detect_all_applications().await.map_err(|e| e.to_string())?;
-
-    for result in &results {
-        if result.profile.name == application && result.detected {
-            if let Some(config_path) = &result.found_paths.config_file {
-                let config_content = tokio::fs::read_to_string(config_path).await.map_err(|e| e.to_string())?;
-                let mut app_config: serde_json::Value = serde_json::from_str(&config_content).map_err(|e| e.to_string())?;
-
-                if let Some(servers) = app_config.get_mut("mcpServers").and_then(|s| s.as_object_mut()) {
-                    // If the server name has changed, we need to remove the old entry
-                    let new_name = config.get("name").and_then(|n| n.as_str()).unwrap_or(&server_id);
-                    if new_name != server_id {
-                        servers.remove(&server_id);
-                    }
-
-                    // Create a new server config from the provided data
-                    let mut new_server_config = serde_json::Map::new();
-                    if let Some(c) = config.get("command").and_then(|v| v.as_str()) { new_server_config.insert("command".to_string(), c.into()); }
-                    if let Some(a) = config.get("args").and_then(|v| v.as_array()) { new_server_config.insert("args".to_string(), a.clone().into()); }
-                    if let Some(e) = config.get("env").and_then(|v| v.as_object()) { new_server_config.insert("env".to_string(), e.clone().into()); }
-                    if let Some(d) = config.get("description").and_then(|v| v.as_str()) { new_server_config.insert("description".to_string(), d.into()); }
-                    if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
-                        if !enabled {
-                            new_server_config.insert("disabled".to_string(), true.into());
-                        }
-                    }
+    let profile = detected_profile(&application).await?;
+    let (config_path, mut app_config) = read_active_config(&profile).await?;
+
+    let servers = servers_mut(&profile, &mut app_config)
+        .ok_or_else(|| format!("{} has no servers in {}", application, config_path.display()))?;
+
+    // If the server name has changed, we need to remove the old entry
+    let new_name = config.get("name").and_then(|n| n.as_str()).unwrap_or(&server_id);
+    let previous = servers.remove(&server_id);
+
+    // Create a new server config from the provided data
+    let mut new_server_config = serde_json::Map::new();
+    if let Some(c) = config.get("command").and_then(|v| v.as_str()) { new_server_config.insert("command".to_string(), c.into()); }
+    if let Some(a) = config.get("args").and_then(|v| v.as_array()) { new_server_config.insert("args".to_string(), a.clone().into()); }
+    if let Some(e) = config.get("env").and_then(|v| v.as_object()) { new_server_config.insert("env".to_string(), e.clone().into()); }
+    if let Some(d) = config.get("description").and_then(|v| v.as_str()) { new_server_config.insert("description".to_string(), d.into()); }
+    if let Some(enabled) = config.get("enabled").and_then(|v| v.as_bool()) {
+        if !enabled {
+            new_server_config.insert("disabled".to_string(), true.into());
+        }
+    }
 
-                    // Update the server entry
-                    servers.insert(new_name.to_string(), new_server_config.into());
+    // Update the server entry
+    let new_entry = serde_json::Value::Object(new_server_config);
+    let replaced = servers.insert(new_name.to_string(), new_entry.clone());
 
-                    let updated_content = serde_json::to_string_pretty(&app_config).map_err(|e| e.to_string())?;
-                    tokio::fs::write(config_path, updated_content).await.map_err(|e| e.to_string())?;
+    write_app_config(&profile, &config_path, &app_config).await?;
 
-                    return Ok(());
-                }
-            }
+    // Keep group membership pointing at the renamed server
+    if new_name != server_id {
+        let mut groups = GroupStore::load_default().map_err(|e| e.to_string())?;
+        if groups.rename_member(&server_id, new_name) > 0 {
+            groups.save().map_err(|e| format!("Failed to update server groups: {}", e))?;
         }
     }
 
-    Err(format!("Application '{}' not found or not configured", application))
+    // An edit or rename replays as removing the old entries and adding the new one
+    let mut mutations: Vec<ConfigMutation> = previous.into_iter()
+        .map(|entry| ConfigMutation::RemoveServer { name: server_id.clone(), entry })
+        .collect();
+    if let Some(entry) = replaced {
+        mutations.push(ConfigMutation::RemoveServer { name: new_name.to_string(), entry });
+    }
+    mutations.push(ConfigMutation::AddServer { name: new_name.to_string(), entry: new_entry });
+    record_mutation(&profile, &config_path, ConfigMutation::Batch { mutations }).await;
+
+    Ok(())
 }
 
 /// Constants for special application names
@@ -992,7 +1330,7 @@ async fn read_and_validate_config(
     let content = tokio::fs::read_to_string(config_path).await
         .map_err(|e| format!("Failed to read config: {}", e))?;
 
-    let config: serde_json::Value = serde_json::from_str(&content)
+    let config = mcpctl_lib::filesystem::jsonc::parse_jsonc(&content)
         .map_err(|e| format!("Failed to parse config: {}", e))?;
 
     // Validate config structure matches profile declaration
@@ -1109,11 +1447,8 @@ async fn sync_from_source(source_app: String) -> Result<String, String> {
             }
 
             // Write back to target
-            let updated_content = serde_json::to_string_pretty(&target_config)
-                .map_err(|e| format!("Failed to serialize {}: {}", result.profile.name, e))?;
-
-            tokio::fs::write(config_path, updated_content).await
-                .map_err(|e| format!("Failed to write {}: {}", result.profile.name, e))?;
+            write_app_config(&result.profile, config_path, &target_config).await
+                .map_err(|e| format!("{}: {}", result.profile.name, e))?;
 
             synced_apps.push(result.profile.name.clone());
         }
@@ -1184,13 +1519,22 @@ async fn analyze_server(package_identifier: String) -> Result<serde_json::Value,
     let analyzer = ServerAnalyzer::new();
 
     match analyzer.analyze_package(&package_identifier).await {
-        Ok(result) => {
+        Ok(mut result) => {
+            // A server added earlier stays on the alternative it was added with
+            let annotations = AnnotationStore::load_default().map_err(|e| e.to_string())?;
+            let pinned = annotations.pinned_alternative(&result.config.name).map(String::from);
+            mcpctl_lib::analysis::select_primary(&mut result.config, pinned.as_deref());
             log::info!("Analysis completed with confidence: {:.2}", result.confidence);
+            let targets = mcpctl_lib::analysis::recommend_detected_targets(&result.config).await.unwrap_or_else(|e| {
+                log::warn!("Could not rank applications for {}: {}", result.config.name, e);
+                Vec::new()
+            });
             Ok(serde_json::json!({
                 "success": result.success,
                 "confidence": result.confidence,
                 "config": result.config,
-                "messages": result.messages
+                "messages": result.messages,
+                "targets": targets
             }))
         }
         Err(e) => {
@@ -1200,6 +1544,14 @@ async fn analyze_server(package_identifier: String) -> Result<serde_json::Value,
     }
 }
 
+#[tauri::command]
+async fn parse_install_link(link: String) -> Result<serde_json::Value, String> {
+    use mcpctl_lib::analysis::ServerAnalyzer;
+
+    let config = ServerAnalyzer::parse_install_link(&link).map_err(|e| e.to_string())?;
+    serde_json::to_value(config).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn export_logs() -> Result<(), String> {
     Ok(())
@@ -1220,6 +1572,14 @@ async fn main() {
             .plugin(tauri_plugin_fs::init())
             .plugin(tauri_plugin_shell::init())
             .setup(|app| {
+                // Repair our own data directory before anything loads from it
+                match check_default_data_dir() {
+                    Ok(report) => {
+                        let _ = SELF_CHECK.set(report);
+                    }
+                    Err(e) => log::warn!("Data directory self-check failed: {:#}", e),
+                }
+
                 // Create enhanced system tray menu
                 let show = MenuItem::with_id(app, "show", "Show MCP Control", true, None::<&str>)?;
                 let separator1 = MenuItem::with_id(app, "sep1", "", false, None::<&str>)?;
@@ -1304,6 +1664,25 @@ Right-click for options")
                     })
                     .build(app)?;
                 
+                // Push detection, config, and health changes to the UI instead of having it poll
+                app.manage(AppState::default());
+                let handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let state = handle.state::<AppState>();
+                    match state.start_daemon(DaemonConfig::default()).await {
+                        Ok(mut deltas) => loop {
+                            match deltas.recv().await {
+                                Ok(delta) => {
+                                    let _ = handle.emit("state-delta", &delta);
+                                }
+                                Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                                Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                            }
+                        },
+                        Err(e) => log::warn!("Failed to start detection daemon: {}", e),
+                    }
+                });
+                
                 if let Some(window) = app.get_webview_window("main") {
                     let _ = window.show();
                     let _ = window.set_focus();
@@ -1344,9 +1723,37 @@ Right-click for options")
                 sync_from_source,
                 save_mcp_control_config,
                 get_mcp_control_config,
-                analyze_server
+                analyze_server,
+                parse_install_link,
+                undo_last,
+                redo_last,
+                set_force_managed_writes,
+                sync_with_resolution,
+                import_manifest,
+                export_manifest,
+                resume_resolution,
+                plan_suggested_fix,
+                apply_change_plan,
+                get_self_check_report,
+                quarantine_risky_servers,
+                get_quarantined_servers,
+                restore_quarantined_server,
+                convert_legacy_config,
+                migrate_cursor_servers
             ])
-            .run(tauri::generate_context!())
-            .expect("error while running tauri application");
+            .build(tauri::generate_context!())
+            .expect("error while running tauri application")
+            .run(|app, event| {
+                // Let the daemon's watchers and in-flight health checks finish before the process goes
+                if let tauri::RunEvent::Exit = event {
+                    let state = app.state::<AppState>();
+                    let stopped = tokio::task::block_in_place(|| {
+                        tokio::runtime::Handle::current().block_on(state.stop_daemon(std::time::Duration::from_secs(2)))
+                    });
+                    if let Some(report) = stopped.filter(|report| report.aborted > 0) {
+                        log::warn!("Aborted {} detection daemon task(s) on exit", report.aborted);
+                    }
+                }
+            });
     }
 }
\ No newline at end of file